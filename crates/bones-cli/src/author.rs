@@ -0,0 +1,31 @@
+//! Serialization for events a command authors.
+//!
+//! [`writer::write_event`] only serializes. Events a command creates on
+//! behalf of its agent are also signed with that agent's secret key, if the
+//! key directory holds one (see [`bones_core::signing`]). Events that come
+//! from elsewhere — imports, pulls, bundles, migrations — go through the
+//! plain writer and keep whatever signature they arrived with.
+
+use bones_core::event::Event;
+use bones_core::event::writer::{self, WriteError};
+use bones_core::signing;
+
+/// Compute `event`'s hash, sign it as its agent when a secret key exists,
+/// and serialize it to a TSJSON line.
+///
+/// An unreadable key file leaves the event unsigned; the signature policy
+/// and `bn verify --signatures` report it.
+///
+/// # Errors
+///
+/// Same as [`writer::write_event`].
+pub fn write_authored_event(event: &mut Event) -> Result<String, WriteError> {
+    event.event_hash = writer::compute_event_hash(event)?;
+    event.signature = signing::default_key_dir().and_then(|key_dir| {
+        signing::sign_as_author(&key_dir, event).unwrap_or_else(|e| {
+            tracing::warn!(agent = %event.agent, "event left unsigned: {e}");
+            None
+        })
+    });
+    writer::write_line(event)
+}
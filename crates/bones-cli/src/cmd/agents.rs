@@ -215,6 +215,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         });
 
         let ts2 = shard_mgr.next_timestamp().expect("ts2");
@@ -237,6 +238,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         });
 
         let ts3 = shard_mgr.next_timestamp().expect("ts3");
@@ -253,6 +255,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         });
 
         let ts4 = shard_mgr.next_timestamp().expect("ts4");
@@ -269,6 +272,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         });
 
         let ts5 = shard_mgr.next_timestamp().expect("ts5");
@@ -282,9 +286,11 @@ mod tests {
             data: EventData::Move(MoveData {
                 state: State::Doing,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         });
 
         conn.execute(
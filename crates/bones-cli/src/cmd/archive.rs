@@ -5,6 +5,7 @@
 //! - `bn archive --auto [--days N]`: archive done bones older than N days
 
 use crate::agent;
+use crate::author::write_authored_event;
 use crate::cmd::open_projection_for_mutation;
 use crate::cmd::show::resolve_item_id;
 use crate::itc_state::assign_next_itc;
@@ -24,7 +25,6 @@ use bones_core::db::query;
 use bones_core::event::Event;
 use bones_core::event::data::{EventData, MoveData};
 use bones_core::event::types::EventType;
-use bones_core::event::writer;
 use bones_core::model::item::State;
 use bones_core::model::item_id::ItemId;
 use bones_core::shard::ShardManager;
@@ -130,6 +130,7 @@ fn append_archive_event(
     let move_data = MoveData {
         state: State::Archived,
         reason: None,
+        substate: None,
         extra: BTreeMap::new(),
     };
 
@@ -142,6 +143,7 @@ fn append_archive_event(
         item_id: ItemId::new_unchecked(item_id),
         data: EventData::Move(move_data),
         event_hash: String::new(),
+        signature: None,
     };
 
     {
@@ -160,7 +162,7 @@ fn append_archive_event(
 
         assign_next_itc(project_root, &mut event)?;
 
-        let line = writer::write_event(&mut event)
+        let line = write_authored_event(&mut event)
             .map_err(|e| anyhow::anyhow!("failed to serialize event: {e}"))?;
 
         shard_mgr
@@ -456,6 +458,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         let line1 = writer::write_event(&mut create_event).unwrap();
         shard_mgr
@@ -484,9 +487,11 @@ mod tests {
                     data: EventData::Move(MoveData {
                         state: step_state,
                         reason: None,
+                        substate: None,
                         extra: BTreeMap::new(),
                     }),
                     event_hash: String::new(),
+                    signature: None,
                 };
                 let line2 = writer::write_event(&mut move_event).unwrap();
                 shard_mgr
@@ -0,0 +1,127 @@
+//! `--at` support for read commands.
+//!
+//! `bn show`, `bn list`, `bn progress` and `bn triage` normally query the
+//! live projection. With `--at <when>` they query an in-memory replay of the
+//! event log up to that point instead (see [`bones_core::db::as_of`]), so the
+//! same queries answer "what did the backlog look like last Friday?".
+
+use std::io::{self, Write};
+use std::path::Path;
+
+use bones_core::db::as_of::{AsOf, project_as_of};
+use bones_core::db::query;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::Connection;
+
+use crate::output::{CliError, OutputMode, render_error};
+
+/// Projection a read command runs its queries against.
+pub struct ReadProjection {
+    pub conn: Connection,
+    /// Set when the projection was replayed with `--at`.
+    pub replay: Option<Replay>,
+}
+
+/// What an `--at` replay covered.
+#[derive(Debug, Clone, Copy)]
+pub struct Replay {
+    /// The instant the projection represents.
+    pub at_us: i64,
+    /// Events replayed to build it.
+    pub events: usize,
+}
+
+impl ReadProjection {
+    /// The instant time-relative logic (staleness, due dates) should treat
+    /// as "now": the `--at` instant, or the current time.
+    pub fn now_us(&self) -> i64 {
+        self.replay
+            .map_or_else(|| Utc::now().timestamp_micros(), |replay| replay.at_us)
+    }
+}
+
+/// Pretty-mode note that the output shows a replayed backlog. Writes nothing
+/// for the live projection.
+pub fn pretty_replay_note(replay: Option<Replay>, w: &mut dyn Write) -> io::Result<()> {
+    let Some(replay) = replay else {
+        return Ok(());
+    };
+    writeln!(
+        w,
+        "note: showing the backlog as of {} ({} event(s) replayed)",
+        format_instant(replay.at_us),
+        replay.events
+    )?;
+    writeln!(w)
+}
+
+/// Open the live projection, or replay the log up to `at` when given.
+///
+/// Returns `None` when there is no projection to read (no bones project, or
+/// a missing live database), leaving the "not found" handling to the caller.
+///
+/// # Errors
+///
+/// Returns an error (after rendering it) if `at` cannot be parsed or names
+/// an unknown event, or if opening or replaying fails.
+pub fn open_read_projection(
+    project_root: &Path,
+    at: Option<&str>,
+    output: OutputMode,
+) -> anyhow::Result<Option<ReadProjection>> {
+    let bones_dir = project_root.join(".bones");
+    let Some(raw) = at else {
+        return Ok(query::try_open_projection(&bones_dir.join("bones.db"))?
+            .map(|conn| ReadProjection { conn, replay: None }));
+    };
+
+    let as_of = match AsOf::parse(raw, Utc::now()) {
+        Ok(as_of) => as_of,
+        Err(e) => {
+            render_error(
+                output,
+                &CliError::with_details(
+                    e.to_string(),
+                    "use YYYY-MM-DD, RFC3339, an offset like 3d, or an event hash from `bn log`",
+                    "invalid_at",
+                ),
+            )?;
+            anyhow::bail!("invalid --at value");
+        }
+    };
+
+    if !bones_dir.join("events").is_dir() {
+        return Ok(None);
+    }
+
+    let projection = match project_as_of(&bones_dir, &as_of) {
+        Ok(projection) => projection,
+        Err(e) if matches!(as_of, AsOf::Event(_)) => {
+            render_error(
+                output,
+                &CliError::with_details(
+                    e.to_string(),
+                    "use a longer hash prefix from `bn log --format json`",
+                    "unknown_event",
+                ),
+            )?;
+            return Err(e);
+        }
+        Err(e) => return Err(e),
+    };
+
+    Ok(Some(ReadProjection {
+        conn: projection.conn,
+        replay: Some(Replay {
+            at_us: projection.at_us,
+            events: projection.events,
+        }),
+    }))
+}
+
+fn format_instant(us: i64) -> String {
+    DateTime::<Utc>::from_timestamp_micros(us).map_or_else(
+        || us.to_string(),
+        |dt| dt.to_rfc3339_opts(SecondsFormat::Secs, true),
+    )
+}
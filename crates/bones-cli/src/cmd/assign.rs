@@ -5,6 +5,7 @@
 //!   currently resolved command agent.
 
 use crate::agent;
+use crate::author::write_authored_event;
 use crate::cmd::show::resolve_item_id;
 use crate::itc_state::assign_next_itc;
 use crate::output::{CliError, OutputMode, render_error, render_mode};
@@ -14,7 +15,6 @@ use bones_core::db::project;
 use bones_core::event::Event;
 use bones_core::event::data::{AssignAction, AssignData, EventData};
 use bones_core::event::types::EventType;
-use bones_core::event::writer;
 use bones_core::model::item_id::ItemId;
 use bones_core::shard::ShardManager;
 use clap::Args;
@@ -113,6 +113,7 @@ pub fn emit_assign_event(
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
 
     {
@@ -131,7 +132,7 @@ pub fn emit_assign_event(
 
         assign_next_itc(project_root, &mut event)?;
 
-        let line = writer::write_event(&mut event)
+        let line = write_authored_event(&mut event)
             .map_err(|e| anyhow::anyhow!("failed to serialize event: {e}"))?;
 
         shard_mgr
@@ -358,6 +359,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
 
         let line = write_event(&mut event).expect("serialize create");
@@ -4,6 +4,7 @@ use std::path::Path;
 
 const EVENTS_ENTRY: &str = "events/*.events merge=union";
 const MANIFEST_ENTRY: &str = "events/*.manifest -merge";
+const BLOBS_ENTRY: &str = "blobs/** binary";
 const LEGACY_ROOT_ENTRY: &str = ".bones/events merge=union";
 /// Old pattern that matched only a file literally named `events`, not files
 /// inside the `events/` directory.
@@ -15,7 +16,7 @@ const LEGACY_GLOB_ENTRY: &str = "events/** merge=union";
 
 /// The canonical bones-managed block. Event logs are append-only and replay
 /// order-independent, so `union` is safe; manifests are single coherent
-/// snapshots that must never be union-merged.
+/// snapshots that must never be union-merged; blobs are opaque bytes.
 const MANAGED_BLOCK: &str = "\
 # bones: merge policy for event logs
 # Event logs are append-only and replay order-independent: union concatenates
@@ -26,6 +27,10 @@ events/*.events merge=union
 # Never union them — that corrupts the integrity record. Let merges surface a
 # conflict; regenerate with `bn verify` / rebuild after merging a sealed shard.
 events/*.manifest -merge
+
+# Attachment blobs are content-addressed: a path never changes contents, so
+# they never need a merge or a text diff.
+blobs/** binary
 ";
 
 pub fn ensure_bones_gitattributes(bones_dir: &Path) -> Result<()> {
@@ -39,13 +44,14 @@ pub fn ensure_bones_gitattributes(bones_dir: &Path) -> Result<()> {
 
     let has_events = existing.lines().any(|line| line.trim() == EVENTS_ENTRY);
     let has_manifest = existing.lines().any(|line| line.trim() == MANIFEST_ENTRY);
+    let has_blobs = existing.lines().any(|line| line.trim() == BLOBS_ENTRY);
     let has_legacy = existing.lines().any(|line| {
         let t = line.trim();
         t == LEGACY_BARE_ENTRY || t == LEGACY_GLOB_ENTRY
     });
 
     // Already in the desired end state with nothing to migrate.
-    if has_events && has_manifest && !has_legacy {
+    if has_events && has_manifest && has_blobs && !has_legacy {
         return Ok(());
     }
 
@@ -143,12 +149,34 @@ mod tests {
         ensure_bones_gitattributes(dir.path()).expect("third");
 
         let content = std::fs::read_to_string(dir.path().join(".gitattributes")).expect("read");
-        for entry in [EVENTS_ENTRY, MANIFEST_ENTRY] {
+        for entry in [EVENTS_ENTRY, MANIFEST_ENTRY, BLOBS_ENTRY] {
             let count = content.lines().filter(|line| line.trim() == entry).count();
             assert_eq!(count, 1, "duplicate `{entry}`:\n{content}");
         }
     }
 
+    #[test]
+    fn adds_blobs_entry_to_existing_block() {
+        let dir = TempDir::new().expect("tmp");
+        std::fs::write(
+            dir.path().join(".gitattributes"),
+            format!("*.png binary\n\n{EVENTS_ENTRY}\n{MANIFEST_ENTRY}\n"),
+        )
+        .expect("seed");
+
+        ensure_bones_gitattributes(dir.path()).expect("upgrade");
+
+        let content = std::fs::read_to_string(dir.path().join(".gitattributes")).expect("read");
+        assert!(
+            content.contains("*.png binary"),
+            "user line lost:\n{content}"
+        );
+        for entry in [EVENTS_ENTRY, MANIFEST_ENTRY, BLOBS_ENTRY] {
+            let count = content.lines().filter(|line| line.trim() == entry).count();
+            assert_eq!(count, 1, "expected one `{entry}`:\n{content}");
+        }
+    }
+
     #[test]
     fn migrates_legacy_glob_entry_to_scoped() {
         let dir = TempDir::new().expect("tmp");
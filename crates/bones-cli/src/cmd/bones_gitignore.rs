@@ -12,7 +12,10 @@ const MANAGED_ENTRIES: &[&str] = &[
     "cache/",
     "itc/",
     "lock",
+    "merge-reports/",
+    "replication.toml",
     "events/current.events",
+    "events/*.rejected",
 ];
 
 pub fn ensure_bones_gitignore(bones_dir: &Path) -> Result<()> {
@@ -57,6 +60,34 @@ pub fn ensure_bones_gitignore(bones_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Ignore `.bones/events/` for projects that keep their event log on
+/// `refs/bones/events` rather than in tracked shards.
+pub fn ensure_events_ignored(bones_dir: &Path) -> Result<()> {
+    const ENTRY: &str = "events/";
+
+    let path = bones_dir.join(".gitignore");
+    let existing = if path.exists() {
+        std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?
+    } else {
+        String::new()
+    };
+    if existing.lines().any(|line| line.trim() == ENTRY) {
+        return Ok(());
+    }
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("failed to open {} for append", path.display()))?;
+    if !existing.is_empty() && !existing.ends_with('\n') {
+        writeln!(file)?;
+    }
+    writeln!(file, "{ENTRY}")?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,4 +118,19 @@ mod tests {
             assert_eq!(count, 1, "duplicate entry {entry}:\n{content}");
         }
     }
+
+    #[test]
+    fn events_ignore_is_added_once() {
+        let dir = TempDir::new().expect("tmp");
+        ensure_bones_gitignore(dir.path()).expect("managed");
+        ensure_events_ignored(dir.path()).expect("first");
+        ensure_events_ignored(dir.path()).expect("second");
+
+        let content = std::fs::read_to_string(dir.path().join(".gitignore")).expect("read");
+        let count = content
+            .lines()
+            .filter(|line| line.trim() == "events/")
+            .count();
+        assert_eq!(count, 1, "{content}");
+    }
 }
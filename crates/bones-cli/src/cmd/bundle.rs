@@ -0,0 +1,367 @@
+//! `bn bundle` — offline sync through self-contained bundle files.
+//!
+//! Bundles carry events between replicas that never share a git remote (USB
+//! sticks, email). A bundle holds the events missing from a Prolly root plus
+//! the roots it was built from; applying one verifies every event hash,
+//! union-merges through `sync::merge`, appends the new events to the active
+//! shard, and updates the projection incrementally. Events refused by
+//! `.bones/policy.toml` are quarantined instead of appended.
+//!
+//! # Usage
+//!
+//! ```text
+//! bn bundle create handoff.bundle                   # every event
+//! bn bundle create handoff.bundle --since 3fa9c1d2  # events since a known root
+//! bn bundle apply handoff.bundle
+//! bn bundle root                                    # this replica's root
+//! ```
+
+use crate::cmd::do_cmd::find_bones_dir;
+use crate::output::{CliError, OutputMode, pretty_kv, pretty_section, render, render_error};
+use anyhow::Context as _;
+use bones_core::event::Event;
+use bones_core::event::parse_lines;
+use bones_core::event::writer::write_line;
+use bones_core::policy::{self, Policy};
+use bones_core::shard::ShardManager;
+use bones_core::sync::bundle::{Bundle, BundleError, RootStore, merge_bundle};
+use bones_core::sync::prolly::ProllyTree;
+use clap::{Args, Subcommand};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Args, Debug)]
+pub struct BundleArgs {
+    #[command(subcommand)]
+    pub command: BundleCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BundleCommand {
+    /// Write events another replica is missing to a bundle file
+    Create {
+        /// Bundle file to write
+        file: PathBuf,
+        /// Only include events not under this Prolly root (a unique prefix
+        /// works). The root must be known to this replica, e.g. the head of
+        /// a bundle it created or applied earlier.
+        #[arg(long, value_name = "ROOT")]
+        since: Option<String>,
+    },
+    /// Verify a bundle and merge its events into this replica
+    Apply {
+        /// Bundle file to read
+        file: PathBuf,
+    },
+    /// Print this replica's current Prolly root
+    Root,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateOutput {
+    ok: bool,
+    file: String,
+    base: Option<String>,
+    head: String,
+    events: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct ApplyOutput {
+    ok: bool,
+    file: String,
+    base: Option<String>,
+    /// Whether this replica has recorded the bundle's base root. When it has
+    /// not, events between that root and ours may still be missing.
+    base_known: bool,
+    head: String,
+    received: usize,
+    already_present: usize,
+    root: String,
+    /// Whether this replica now matches the bundle's head.
+    in_sync: bool,
+    events_applied: usize,
+    /// Events refused by `.bones/policy.toml` and quarantined.
+    quarantined: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct RootOutput {
+    root: String,
+    events: usize,
+}
+
+fn fail(output: OutputMode, msg: &str, suggestion: &str, code: &str) -> anyhow::Error {
+    let _ = render_error(output, &CliError::with_details(msg, suggestion, code));
+    anyhow::anyhow!("{msg}")
+}
+
+fn bundle_failure(output: OutputMode, err: &BundleError) -> anyhow::Error {
+    let (suggestion, code) = match err {
+        BundleError::UnknownRoot(_) => (
+            "pass the head of a bundle this replica created or applied, or omit --since",
+            "unknown_root",
+        ),
+        BundleError::AmbiguousRoot(_) => ("use a longer root prefix", "ambiguous_root"),
+        BundleError::MissingHeader
+        | BundleError::UnsupportedVersion(_)
+        | BundleError::InvalidHeader { .. }
+        | BundleError::Parse { .. }
+        | BundleError::CountMismatch { .. } => (
+            "the bundle is corrupt, truncated, or from a newer bones; recreate it",
+            "invalid_bundle",
+        ),
+        _ => ("check file permissions and free space", "bundle_failed"),
+    };
+    fail(output, &err.to_string(), suggestion, code)
+}
+
+/// Read every event in the project's shards.
+pub fn load_events(bones_dir: &Path) -> anyhow::Result<Vec<Event>> {
+    let content = ShardManager::new(bones_dir)
+        .replay()
+        .context("failed to read event shards")?;
+    parse_lines(&content).map_err(|(line, err)| {
+        anyhow::anyhow!("event shards are corrupt at line {line}: {err}; run `bn admin verify`")
+    })
+}
+
+/// What became of events received from another replica.
+#[derive(Debug)]
+pub struct Received {
+    /// Events that passed the policy and were appended, in order.
+    pub accepted: Vec<Event>,
+    /// Projection update for the appended events.
+    pub apply: bones_core::db::incremental::ApplyReport,
+    /// Events `.bones/policy.toml` refused and quarantined.
+    pub quarantined: usize,
+}
+
+/// Quarantine received events that `.bones/policy.toml` refuses, append the
+/// rest to the active shard, then bring the projection up to date
+/// incrementally.
+pub fn append_events(bones_dir: &Path, events: &[Event]) -> anyhow::Result<Received> {
+    let policy = Policy::load(bones_dir).context("failed to load .bones/policy.toml")?;
+    let (accepted, rejected) = policy.partition(events.to_vec());
+    policy::quarantine(bones_dir, &rejected).context("failed to quarantine rejected events")?;
+
+    let shards = ShardManager::new(bones_dir);
+    for event in &accepted {
+        let line = write_line(event).context("failed to serialize received event")?;
+        shards
+            .append(&line, false, Duration::from_secs(5))
+            .context("failed to append received event")?;
+    }
+    let apply = bones_core::db::incremental::incremental_apply(
+        &shards.events_dir(),
+        &bones_dir.join("bones.db"),
+        false,
+    )
+    .context("events were appended but the projection update failed; run `bn admin rebuild`")?;
+    Ok(Received {
+        accepted,
+        apply,
+        quarantined: rejected.len(),
+    })
+}
+
+/// Execute `bn bundle`.
+///
+/// # Errors
+///
+/// Returns an error if the project cannot be read, the bundle is invalid, or
+/// writing events fails.
+pub fn run_bundle(
+    args: &BundleArgs,
+    output: OutputMode,
+    project_root: &Path,
+) -> anyhow::Result<()> {
+    let bones_dir = find_bones_dir(project_root).ok_or_else(|| {
+        fail(
+            output,
+            "Not a bones project: .bones directory not found",
+            "Run 'bn init' to create a new project",
+            "not_a_project",
+        )
+    })?;
+    match &args.command {
+        BundleCommand::Create { file, since } => {
+            run_create(&bones_dir, file, since.as_deref(), output)
+        }
+        BundleCommand::Apply { file } => run_apply(&bones_dir, file, output),
+        BundleCommand::Root => run_root(&bones_dir, output),
+    }
+}
+
+fn run_create(
+    bones_dir: &Path,
+    file: &Path,
+    since: Option<&str>,
+    output: OutputMode,
+) -> anyhow::Result<()> {
+    let store = RootStore::new(bones_dir);
+    let base = since
+        .map(|root| store.resolve(root))
+        .transpose()
+        .map_err(|e| bundle_failure(output, &e))?;
+
+    let events = load_events(bones_dir)?;
+    let bundle = Bundle::create(&events, base.as_ref());
+    bundle.write(file).map_err(|e| bundle_failure(output, &e))?;
+    store
+        .record(&ProllyTree::build(&events))
+        .map_err(|e| bundle_failure(output, &e))?;
+
+    let out = CreateOutput {
+        ok: true,
+        file: file.display().to_string(),
+        base: bundle.base.map(|h| h.to_string()),
+        head: bundle.head.to_string(),
+        events: bundle.events.len(),
+    };
+    render(output, &out, |out, w| {
+        writeln!(w, "Wrote {} event(s) to {}", out.events, out.file)?;
+        if let Some(base) = &out.base {
+            writeln!(w, "  base: {base}")?;
+        }
+        writeln!(w, "  head: {}", out.head)?;
+        writeln!(
+            w,
+            "Next time, `bn bundle create <file> --since {}` sends only newer events.",
+            &out.head[..12]
+        )
+    })
+}
+
+fn run_apply(bones_dir: &Path, file: &Path, output: OutputMode) -> anyhow::Result<()> {
+    let bundle = Bundle::read(file).map_err(|e| bundle_failure(output, &e))?;
+    let store = RootStore::new(bones_dir);
+    let base_tree = match bundle.base {
+        Some(base) if store.contains(&base) => Some(
+            store
+                .resolve(&base.to_string())
+                .map_err(|e| bundle_failure(output, &e))?,
+        ),
+        _ => None,
+    };
+    let base_known = bundle.base.is_none() || base_tree.is_some();
+
+    let local = load_events(bones_dir)?;
+    let merge = merge_bundle(&local, &bundle, base_tree.as_ref());
+
+    let received = append_events(bones_dir, &merge.new_events)?;
+    // Quarantined events never reach the shards, so the root and whether
+    // this replica matches the bundle come from what was appended.
+    let (root_tree, in_sync) = if received.quarantined == 0 {
+        (merge.root, merge.in_sync)
+    } else {
+        let mut events = local;
+        events.extend(received.accepted.iter().cloned());
+        let tree = ProllyTree::build(&events);
+        let in_sync = tree.root.hash() == bundle.head;
+        (tree, in_sync)
+    };
+    let root = store
+        .record(&root_tree)
+        .map_err(|e| bundle_failure(output, &e))?;
+    if let Some(head_tree) = &merge.head_tree {
+        store
+            .record(head_tree)
+            .map_err(|e| bundle_failure(output, &e))?;
+    }
+
+    let out = ApplyOutput {
+        ok: true,
+        file: file.display().to_string(),
+        base: bundle.base.map(|h| h.to_string()),
+        base_known,
+        head: bundle.head.to_string(),
+        received: received.accepted.len(),
+        already_present: merge.already_present,
+        root: root.to_string(),
+        in_sync,
+        events_applied: received.apply.events_applied,
+        quarantined: received.quarantined,
+    };
+    render(output, &out, |out, w| {
+        pretty_section(w, "Bundle Applied")?;
+        pretty_kv(w, "New events", out.received.to_string())?;
+        pretty_kv(w, "Already present", out.already_present.to_string())?;
+        if out.quarantined > 0 {
+            pretty_kv(
+                w,
+                "Quarantined",
+                format!("{} (refused by .bones/policy.toml)", out.quarantined),
+            )?;
+        }
+        pretty_kv(w, "Root", &out.root)?;
+        if out.in_sync {
+            writeln!(w, "This replica now matches the bundle's head.")?;
+        } else if out.quarantined > 0 {
+            writeln!(
+                w,
+                "This replica does not match the bundle's head: it refused the quarantined \
+                 event(s)."
+            )?;
+        } else {
+            writeln!(
+                w,
+                "This replica has events the bundle's creator lacks; send a bundle back with \
+                 `bn bundle create <file> --since {}`.",
+                &out.head[..12]
+            )?;
+        }
+        if !out.base_known {
+            writeln!(
+                w,
+                "warning: the bundle was built against a root this replica has not seen; \
+                 some earlier events may still be missing."
+            )?;
+        }
+        Ok(())
+    })
+}
+
+fn run_root(bones_dir: &Path, output: OutputMode) -> anyhow::Result<()> {
+    let events = load_events(bones_dir)?;
+    let tree = ProllyTree::build(&events);
+    let root = RootStore::new(bones_dir)
+        .record(&tree)
+        .map_err(|e| bundle_failure(output, &e))?;
+
+    let out = RootOutput {
+        root: root.to_string(),
+        events: tree.event_count,
+    };
+    render(output, &out, |out, w| {
+        writeln!(w, "{} ({} events)", out.root, out.events)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Wrapper {
+        #[command(flatten)]
+        args: BundleArgs,
+    }
+
+    #[test]
+    fn parses_subcommands() {
+        let w = Wrapper::parse_from(["test", "create", "out.bundle", "--since", "abcd1234"]);
+        assert!(matches!(
+            w.args.command,
+            BundleCommand::Create { ref since, .. } if since.as_deref() == Some("abcd1234")
+        ));
+
+        let w = Wrapper::parse_from(["test", "apply", "in.bundle"]);
+        assert!(matches!(w.args.command, BundleCommand::Apply { .. }));
+
+        let w = Wrapper::parse_from(["test", "root"]);
+        assert!(matches!(w.args.command, BundleCommand::Root));
+    }
+}
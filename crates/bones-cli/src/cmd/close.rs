@@ -105,6 +105,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         let line = writer::write_event(&mut create_event).unwrap();
         shard_mgr
@@ -130,9 +131,11 @@ mod tests {
                     data: EventData::Move(MoveData {
                         state: step_state,
                         reason: None,
+                        substate: None,
                         extra: BTreeMap::new(),
                     }),
                     event_hash: String::new(),
+                    signature: None,
                 };
                 let line = writer::write_event(&mut move_event).unwrap();
                 shard_mgr
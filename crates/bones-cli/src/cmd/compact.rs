@@ -3,11 +3,15 @@
 //! Replaces event sequences for old done/archived items with a single
 //! `item.snapshot` event. Compaction is coordination-free: each replica
 //! can compact independently and converge.
+//!
+//! Also prunes `.bones/blobs/` entries that no live attachment references
+//! (for example, blobs left behind after their attachment was redacted).
 
 use std::collections::{BTreeMap, HashSet};
 use std::path::Path;
 
 use anyhow::{Context, Result};
+use bones_core::blob::{self, BlobStore};
 use bones_core::compact;
 use bones_core::db::query;
 use bones_core::event::parser;
@@ -45,6 +49,8 @@ pub struct CompactOutput {
     pub events_replaced: usize,
     pub snapshots_created: usize,
     pub items_skipped: usize,
+    /// Unreferenced blobs removed (or that would be removed in a dry run).
+    pub blobs_pruned: usize,
     pub dry_run: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub verification: Option<String>,
@@ -156,11 +162,14 @@ pub fn run_compact(args: &CompactArgs, output: OutputMode, project_root: &Path)
         }
     }
 
+    let blobs_pruned = prune_blobs(&bones_dir, &events, args.dry_run)?;
+
     let out = CompactOutput {
         items_compacted: report.items_compacted,
         events_replaced: report.events_replaced,
         snapshots_created: report.snapshots_created,
         items_skipped: report.items_skipped,
+        blobs_pruned,
         dry_run: args.dry_run,
         verification,
     };
@@ -173,6 +182,7 @@ pub fn run_compact(args: &CompactArgs, output: OutputMode, project_root: &Path)
         writeln!(w, "Events replaced:  {}", out.events_replaced)?;
         writeln!(w, "Snapshots created: {}", out.snapshots_created)?;
         writeln!(w, "Items skipped:    {}", out.items_skipped)?;
+        writeln!(w, "Blobs pruned:     {}", out.blobs_pruned)?;
         if let Some(ref verify) = out.verification {
             writeln!(w, "Verification:     {verify}")?;
         }
@@ -182,6 +192,24 @@ pub fn run_compact(args: &CompactArgs, output: OutputMode, project_root: &Path)
     Ok(())
 }
 
+/// Remove blobs that no non-redacted attachment references.
+///
+/// In a dry run, only counts the blobs that would be removed.
+fn prune_blobs(
+    bones_dir: &Path,
+    events: &[bones_core::event::Event],
+    dry_run: bool,
+) -> Result<usize> {
+    let store = BlobStore::new(bones_dir);
+    let keep = blob::referenced_blobs(events);
+    if dry_run {
+        let stored = store.list().context("list blob store")?;
+        return Ok(stored.iter().filter(|hash| !keep.contains(*hash)).count());
+    }
+    let removed = store.prune(&keep).context("prune blob store")?;
+    Ok(removed.len())
+}
+
 /// Collect redacted event hashes from the projection database.
 ///
 /// If the DB doesn't exist or can't be read, returns an empty set
@@ -185,6 +185,7 @@ fn split_known_key(scope: ConfigScope, key: &str) -> Result<(&str, &str)> {
                 )
                 | ("triage", "feedback_learning")
                 | ("done", "require_reason")
+                | ("sync", "events" | "remote")
         ),
         ConfigScope::User => matches!((section, leaf), ("user", "output")),
     };
@@ -200,7 +201,9 @@ fn parse_value(scope: ConfigScope, key: &str, raw: &str) -> Result<Value> {
     let (section, leaf) = split_known_key(scope, key)?;
 
     match (section, leaf) {
-        ("search", "model") | ("user", "output") => Ok(Value::String(raw.to_string())),
+        ("search", "model") | ("sync", "events" | "remote") | ("user", "output") => {
+            Ok(Value::String(raw.to_string()))
+        }
         ("search", "duplicate_threshold" | "related_threshold") => {
             let number: f64 = raw
                 .parse()
@@ -284,7 +287,30 @@ fn print_effective(value: &EffectiveConfig, output: OutputMode) -> Result<()> {
                 "triage.feedback_learning={}",
                 value.project.triage.feedback_learning
             );
+            let durations = &value.project.triage.size_durations;
+            println!(
+                "triage.size_durations=xs:{} s:{} m:{} l:{} xl:{} unestimated:{}",
+                durations.xs,
+                durations.s,
+                durations.m,
+                durations.l,
+                durations.xl,
+                durations.unestimated
+            );
             println!("done.require_reason={}", value.project.done.require_reason);
+            println!("sync.events={}", value.project.sync.events);
+            println!("sync.remote={}", value.project.sync.remote);
+            for (name, def) in &value.project.custom_fields {
+                if def.values.is_empty() {
+                    println!("custom_fields.{name}={}", def.field_type);
+                } else {
+                    println!(
+                        "custom_fields.{name}={}:{}",
+                        def.field_type,
+                        def.values.join("|")
+                    );
+                }
+            }
             if let Some(out) = &value.user.output {
                 println!("user.output={out}");
             }
@@ -313,10 +339,33 @@ fn print_effective(value: &EffectiveConfig, output: OutputMode) -> Result<()> {
                 "feedback_learning = {}",
                 value.project.triage.feedback_learning
             );
+            let durations = &value.project.triage.size_durations;
+            println!();
+            println!("[triage.size_durations]");
+            println!("xs = {}", durations.xs);
+            println!("s = {}", durations.s);
+            println!("m = {}", durations.m);
+            println!("l = {}", durations.l);
+            println!("xl = {}", durations.xl);
+            println!("unestimated = {}", durations.unestimated);
             println!();
             println!("[done]");
             println!("require_reason = {}", value.project.done.require_reason);
             println!();
+            println!("[sync]");
+            println!("events = \"{}\"", value.project.sync.events);
+            println!("remote = \"{}\"", value.project.sync.remote);
+            println!();
+            for (name, def) in &value.project.custom_fields {
+                println!("[custom_fields.{name}]");
+                println!("type = \"{}\"", def.field_type);
+                if !def.values.is_empty() {
+                    let values: Vec<String> =
+                        def.values.iter().map(|v| format!("\"{v}\"")).collect();
+                    println!("values = [{}]", values.join(", "));
+                }
+                println!();
+            }
             println!("[user]");
             if let Some(out) = &value.user.output {
                 println!("output = \"{out}\"");
@@ -0,0 +1,521 @@
+//! `bn conflicts` — list LWW writes that lost to a concurrent write.
+//!
+//! When two replicas write the same field (title, urgency, parent, ...)
+//! without seeing each other's write, the LWW register keeps one value and
+//! the other disappears. This command lists those losing writes with their
+//! agents, and `--reapply` writes a chosen value again so it supersedes
+//! every concurrent write to the field.
+//!
+//! # Usage
+//!
+//! ```text
+//! # List conflicts across the project, or for one bone
+//! bn conflicts
+//! bn conflicts bn-abc
+//!
+//! # Restore the value written by a losing event (a unique prefix works)
+//! bn conflicts --reapply blake3:abcdef...
+//! ```
+
+use crate::agent;
+use crate::author::write_authored_event;
+use crate::cmd::show::{micros_to_local_datetime, resolve_item_id};
+use crate::itc_state::assign_next_itc;
+use crate::output::{CliError, OutputMode, render, render_error};
+use crate::validate;
+use bones_core::conflicts::{FieldConflict, FieldWrite, list_conflicts, observed_parents};
+use bones_core::db::{project, query};
+use bones_core::event::data::{EventData, UpdateData};
+use bones_core::event::{Event, EventType};
+use bones_core::model::item_id::ItemId;
+use bones_core::shard::ShardManager;
+use clap::Args;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+#[derive(Args, Debug)]
+pub struct ConflictsArgs {
+    /// Bone ID to inspect (default: every bone). Supports partial IDs.
+    pub id: Option<String>,
+
+    /// Write the value of this event again so it supersedes every
+    /// concurrent write to the same field. Pass the winner's hash to keep
+    /// the current value and clear the conflict.
+    #[arg(long, value_name = "EVENT_HASH")]
+    pub reapply: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ConflictsOutput {
+    conflicts: Vec<FieldConflict>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReappliedField {
+    item_id: String,
+    field: String,
+    value: serde_json::Value,
+    event_hash: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ReapplyOutput {
+    ok: bool,
+    source_hash: String,
+    reapplied: Vec<ReappliedField>,
+}
+
+fn fail(output: OutputMode, msg: &str, suggestion: &str, code: &str) -> anyhow::Error {
+    let _ = render_error(output, &CliError::with_details(msg, suggestion, code));
+    anyhow::anyhow!("{msg}")
+}
+
+/// Short display form of a JSON field value.
+pub fn value_display(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => "(unset)".to_string(),
+        serde_json::Value::String(s) => format!("{s:?}"),
+        other => other.to_string(),
+    }
+}
+
+fn render_write(w: &mut dyn Write, marker: &str, write: &FieldWrite) -> std::io::Result<()> {
+    writeln!(
+        w,
+        "  {marker} {}  by {} at {}  ({})",
+        value_display(&write.value),
+        write.agent,
+        micros_to_local_datetime(write.wall_ts_us),
+        write.event_hash
+    )
+}
+
+/// Render conflicts as "kept"/"lost" lines, one block per field.
+pub fn render_conflict_lines(
+    w: &mut dyn Write,
+    conflicts: &[FieldConflict],
+    with_item: bool,
+) -> std::io::Result<()> {
+    for (idx, conflict) in conflicts.iter().enumerate() {
+        if idx > 0 {
+            writeln!(w)?;
+        }
+        if with_item {
+            writeln!(w, "{} {}", conflict.item_id, conflict.field)?;
+        } else {
+            writeln!(w, "{}", conflict.field)?;
+        }
+        render_write(w, "kept", &conflict.winner)?;
+        for loser in &conflict.losers {
+            render_write(w, "lost", loser)?;
+        }
+    }
+    Ok(())
+}
+
+fn render_conflicts_human(payload: &ConflictsOutput, w: &mut dyn Write) -> std::io::Result<()> {
+    if payload.conflicts.is_empty() {
+        writeln!(w, "No conflicting writes found.")?;
+        return Ok(());
+    }
+    writeln!(w, "Conflicts ({})", payload.conflicts.len())?;
+    writeln!(w)?;
+    render_conflict_lines(w, &payload.conflicts, true)?;
+    writeln!(w)?;
+    writeln!(
+        w,
+        "Restore a lost value with `bn conflicts --reapply <event-hash>`."
+    )
+}
+
+/// Find the single write whose hash starts with `prefix` among `conflicts`.
+fn find_write<'a>(
+    conflicts: &'a [FieldConflict],
+    prefix: &str,
+) -> Result<Vec<(&'a FieldConflict, &'a FieldWrite)>, BTreeSet<&'a str>> {
+    let matches: Vec<(&FieldConflict, &FieldWrite)> = conflicts
+        .iter()
+        .flat_map(|c| {
+            std::iter::once(&c.winner)
+                .chain(&c.losers)
+                .filter(|write| write.event_hash.starts_with(prefix))
+                .map(move |write| (c, write))
+        })
+        .collect();
+    let hashes: BTreeSet<&str> = matches
+        .iter()
+        .map(|(_, write)| write.event_hash.as_str())
+        .collect();
+    if hashes.len() > 1 {
+        return Err(hashes);
+    }
+    Ok(matches)
+}
+
+fn reapply(
+    project_root: &Path,
+    conn: &rusqlite::Connection,
+    shard_mgr: &ShardManager,
+    agent: &str,
+    conflict: &FieldConflict,
+    value: &serde_json::Value,
+) -> anyhow::Result<ReappliedField> {
+    let mut event = Event {
+        wall_ts_us: 0,
+        agent: agent.to_string(),
+        itc: String::new(),
+        parents: observed_parents(conn, &conflict.item_id, &conflict.field)?,
+        event_type: EventType::Update,
+        item_id: ItemId::new_unchecked(&conflict.item_id),
+        data: EventData::Update(UpdateData {
+            field: conflict.field.clone(),
+            value: value.clone(),
+            extra: BTreeMap::new(),
+        }),
+        event_hash: String::new(),
+        signature: None,
+    };
+
+    {
+        use bones_core::lock::ShardLock;
+        let lock_path = shard_mgr.lock_path();
+        let _lock = ShardLock::acquire(&lock_path, Duration::from_secs(5))
+            .map_err(|e| anyhow::anyhow!("failed to acquire lock: {e}"))?;
+
+        let (year, month) = shard_mgr
+            .rotate_if_needed()
+            .map_err(|e| anyhow::anyhow!("failed to rotate shards: {e}"))?;
+
+        event.wall_ts_us = shard_mgr
+            .next_timestamp()
+            .map_err(|e| anyhow::anyhow!("failed to get timestamp: {e}"))?;
+
+        assign_next_itc(project_root, &mut event)?;
+
+        let line = write_authored_event(&mut event)
+            .map_err(|e| anyhow::anyhow!("failed to serialize event: {e}"))?;
+
+        shard_mgr
+            .append_raw(year, month, &line)
+            .map_err(|e| anyhow::anyhow!("failed to write event: {e}"))?;
+    }
+
+    let projector = project::Projector::new(conn);
+    if let Err(e) = projector.project_event(&event) {
+        tracing::warn!(
+            "projection failed for field '{}' (will be fixed on next rebuild): {e}",
+            conflict.field
+        );
+    }
+
+    Ok(ReappliedField {
+        item_id: conflict.item_id.clone(),
+        field: conflict.field.clone(),
+        value: value.clone(),
+        event_hash: event.event_hash,
+    })
+}
+
+#[tracing::instrument(skip_all, name = "cmd.conflicts")]
+pub fn run_conflicts(
+    args: &ConflictsArgs,
+    agent_flag: Option<&str>,
+    output: OutputMode,
+    project_root: &Path,
+) -> anyhow::Result<()> {
+    let bones_dir = project_root.join(".bones");
+    let db_path = bones_dir.join("bones.db");
+    let conn = query::try_open_projection(&db_path)?.ok_or_else(|| {
+        fail(
+            output,
+            "projection database not found",
+            "run `bn admin rebuild` to initialize the projection",
+            "projection_missing",
+        )
+    })?;
+
+    let item_id = match args.id.as_deref() {
+        Some(raw) => Some(resolve_item_id(&conn, raw)?.ok_or_else(|| {
+            fail(
+                output,
+                &format!("item '{raw}' not found"),
+                "use `bn list` to see available items",
+                "item_not_found",
+            )
+        })?),
+        None => None,
+    };
+
+    let conflicts = list_conflicts(&conn, item_id.as_deref())?;
+
+    let Some(prefix) = args.reapply.as_deref().map(str::trim) else {
+        let payload = ConflictsOutput { conflicts };
+        return render(output, &payload, render_conflicts_human);
+    };
+
+    let agent = match agent::require_agent(agent_flag) {
+        Ok(a) => a,
+        Err(e) => {
+            render_error(
+                output,
+                &CliError::with_details(&e.message, "Set --agent, BONES_AGENT, or AGENT", e.code),
+            )?;
+            anyhow::bail!("{}", e.message);
+        }
+    };
+    if let Err(e) = validate::validate_agent(&agent) {
+        render_error(output, &e.to_cli_error())?;
+        anyhow::bail!("{}", e.reason);
+    }
+
+    let targets = match find_write(&conflicts, prefix) {
+        Ok(targets) if !targets.is_empty() && !prefix.is_empty() => targets,
+        Ok(_) => {
+            return Err(fail(
+                output,
+                &format!("no conflicting write matches '{prefix}'"),
+                "List conflicting writes with `bn conflicts`",
+                "conflict_not_found",
+            ));
+        }
+        Err(hashes) => {
+            return Err(fail(
+                output,
+                &format!(
+                    "ambiguous event hash prefix '{prefix}'; matches: {}",
+                    hashes.into_iter().collect::<Vec<_>>().join(", ")
+                ),
+                "Pass more of the event hash",
+                "ambiguous_hash",
+            ));
+        }
+    };
+
+    let _ = project::ensure_tracking_table(&conn);
+    let shard_mgr = ShardManager::new(&bones_dir);
+    let source_hash = targets[0].1.event_hash.clone();
+    let mut reapplied = Vec::new();
+    for (conflict, write) in targets {
+        reapplied.push(reapply(
+            project_root,
+            &conn,
+            &shard_mgr,
+            &agent,
+            conflict,
+            &write.value,
+        )?);
+    }
+
+    let payload = ReapplyOutput {
+        ok: true,
+        source_hash,
+        reapplied,
+    };
+    render(output, &payload, |r, w| {
+        for field in &r.reapplied {
+            writeln!(
+                w,
+                "✓ {}: {} = {} (re-applied from {})",
+                field.item_id,
+                field.field,
+                value_display(&field.value),
+                r.source_hash
+            )?;
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bones_core::db;
+    use bones_core::event::data::CreateData;
+    use bones_core::event::writer::write_event;
+    use bones_core::model::item::{Kind, Urgency};
+    use clap::Parser;
+    use tempfile::TempDir;
+
+    #[derive(Parser)]
+    struct Wrapper {
+        #[command(flatten)]
+        args: ConflictsArgs,
+    }
+
+    fn write(hash: &str, agent: &str, value: &str) -> FieldWrite {
+        FieldWrite {
+            event_hash: hash.to_string(),
+            agent: agent.to_string(),
+            wall_ts_us: 1_000,
+            itc: String::new(),
+            value: serde_json::Value::from(value),
+        }
+    }
+
+    fn sample_conflict() -> FieldConflict {
+        FieldConflict {
+            item_id: "bn-abc".into(),
+            field: "title".into(),
+            winner: write("blake3:bbb", "bob", "B"),
+            losers: vec![write("blake3:aaa", "alice", "A")],
+        }
+    }
+
+    /// Append `event` to the shard and project it, like a replica would.
+    fn append(root: &Path, conn: &rusqlite::Connection, mut event: Event) -> String {
+        let shard_mgr = ShardManager::new(root.join(".bones"));
+        event.wall_ts_us = shard_mgr.next_timestamp().unwrap();
+        let line = write_event(&mut event).unwrap();
+        shard_mgr
+            .append(&line, false, Duration::from_secs(5))
+            .unwrap();
+        project::Projector::new(conn).project_event(&event).unwrap();
+        event.event_hash
+    }
+
+    fn event(agent: &str, itc: String, parents: Vec<String>, data: EventData) -> Event {
+        Event {
+            wall_ts_us: 0,
+            agent: agent.into(),
+            itc,
+            parents,
+            event_type: match data {
+                EventData::Create(_) => EventType::Create,
+                _ => EventType::Update,
+            },
+            item_id: ItemId::new_unchecked("bn-abc"),
+            data,
+            event_hash: String::new(),
+            signature: None,
+        }
+    }
+
+    fn title(value: &str) -> EventData {
+        EventData::Update(UpdateData {
+            field: "title".into(),
+            value: serde_json::Value::from(value),
+            extra: BTreeMap::new(),
+        })
+    }
+
+    /// A project where alice and bob renamed `bn-abc` concurrently.
+    fn setup_conflict() -> (TempDir, String, String) {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let bones_dir = root.join(".bones");
+        std::fs::create_dir_all(&bones_dir).unwrap();
+        ShardManager::new(&bones_dir).init().unwrap();
+        let conn = db::open_projection(&bones_dir.join("bones.db")).unwrap();
+        let _ = project::ensure_tracking_table(&conn);
+
+        let itc = |agent: &str| crate::itc_state::next_itc(root, agent).unwrap();
+        let create = append(
+            root,
+            &conn,
+            event(
+                "alice",
+                itc("alice"),
+                vec![],
+                EventData::Create(CreateData {
+                    title: "Original".into(),
+                    kind: Kind::Task,
+                    size: None,
+                    urgency: Urgency::Default,
+                    labels: vec![],
+                    parent: None,
+                    causation: None,
+                    description: None,
+                    extra: BTreeMap::new(),
+                }),
+            ),
+        );
+        let alice = append(
+            root,
+            &conn,
+            event(
+                "alice",
+                itc("alice"),
+                vec![create.clone()],
+                title("Alice's"),
+            ),
+        );
+        let bob = append(
+            root,
+            &conn,
+            event("bob", itc("bob"), vec![create], title("Bob's")),
+        );
+        (dir, alice, bob)
+    }
+
+    #[test]
+    fn conflicts_args_parse() {
+        let w = Wrapper::parse_from(["test"]);
+        assert!(w.args.id.is_none());
+        assert!(w.args.reapply.is_none());
+
+        let w = Wrapper::parse_from(["test", "bn-abc", "--reapply", "blake3:aaa"]);
+        assert_eq!(w.args.id.as_deref(), Some("bn-abc"));
+        assert_eq!(w.args.reapply.as_deref(), Some("blake3:aaa"));
+    }
+
+    #[test]
+    fn render_human_lists_kept_and_lost() {
+        let payload = ConflictsOutput {
+            conflicts: vec![sample_conflict()],
+        };
+        let mut out = Vec::new();
+        render_conflicts_human(&payload, &mut out).unwrap();
+        let rendered = String::from_utf8(out).unwrap();
+        assert!(rendered.contains("bn-abc title"));
+        assert!(rendered.contains("kept \"B\"  by bob"));
+        assert!(rendered.contains("lost \"A\"  by alice"));
+        assert!(rendered.contains("blake3:aaa"));
+    }
+
+    #[test]
+    fn find_write_by_prefix() {
+        let conflicts = vec![sample_conflict()];
+        let found = find_write(&conflicts, "blake3:a").unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].1.agent, "alice");
+        assert!(find_write(&conflicts, "blake3:c").unwrap().is_empty());
+        assert!(find_write(&conflicts, "blake3:").is_err());
+    }
+
+    #[test]
+    fn reapply_restores_losing_value_and_clears_conflict() {
+        let (dir, alice, bob) = setup_conflict();
+        let db_path = dir.path().join(".bones/bones.db");
+        {
+            let conn = db::open_projection(&db_path).unwrap();
+            let conflicts = list_conflicts(&conn, Some("bn-abc")).unwrap();
+            assert_eq!(conflicts.len(), 1);
+            assert_eq!(conflicts[0].winner.event_hash, bob);
+            assert_eq!(conflicts[0].losers[0].event_hash, alice);
+        }
+
+        let args = ConflictsArgs {
+            id: None,
+            reapply: Some(alice),
+        };
+        run_conflicts(&args, Some("carol"), OutputMode::Json, dir.path()).unwrap();
+
+        let conn = db::open_projection(&db_path).unwrap();
+        let item = query::get_item(&conn, "bn-abc", false).unwrap().unwrap();
+        assert_eq!(item.title, "Alice's");
+        assert!(list_conflicts(&conn, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn reapply_unknown_hash_fails() {
+        let (dir, _, _) = setup_conflict();
+        let args = ConflictsArgs {
+            id: Some("bn-abc".into()),
+            reapply: Some("blake3:nope".into()),
+        };
+        assert!(run_conflicts(&args, Some("carol"), OutputMode::Json, dir.path()).is_err());
+    }
+}
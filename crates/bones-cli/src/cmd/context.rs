@@ -4,6 +4,8 @@ use std::collections::HashSet;
 use std::io::Write;
 use std::path::Path;
 
+use bones_core::config::load_project_config;
+use bones_core::db::filter_query::FilterQuery;
 use bones_core::db::query::{self, ItemFilter, QueryItem, SortOrder};
 use bones_core::shard::ShardManager;
 use clap::Args;
@@ -11,6 +13,7 @@ use rusqlite::{OptionalExtension, params};
 use serde::Serialize;
 
 use crate::cmd::triage_support::{RankedItem, build_triage_snapshot};
+use crate::cmd::view::view_query;
 use crate::output::{CliError, OutputMode, render_error, render_mode};
 
 const CHIEF_CONTEXT_SCHEMA_VERSION: u32 = 1;
@@ -18,7 +21,17 @@ const CONTEXT_COMMAND: &str = "bn context --format json";
 
 /// Arguments for `bn context`.
 #[derive(Args, Debug, Default)]
-pub struct ContextArgs {}
+pub struct ContextArgs {
+    /// Scope the snapshot to bones matching a query (same syntax as
+    /// `bn list --where`).
+    #[arg(long = "where", value_name = "query")]
+    pub query: Option<String>,
+
+    /// Scope the snapshot to a saved view (see `bn view`); combined with
+    /// `--where` when both are given.
+    #[arg(long, value_name = "name")]
+    pub view: Option<String>,
+}
 
 #[derive(Debug, Serialize)]
 struct ContextPayload {
@@ -26,6 +39,12 @@ struct ContextPayload {
     generated_at: String,
     provider: &'static str,
     command: &'static str,
+    /// Saved view the snapshot is scoped to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    view: Option<String>,
+    /// The query the snapshot is scoped to (`--view` and `--where`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filter: Option<String>,
     summary: ContextSummary,
     #[serde(skip_serializing_if = "Option::is_none")]
     recommended_next: Option<RecommendedNext>,
@@ -94,7 +113,7 @@ struct ProjectionFreshness {
 
 /// Execute `bn context`.
 pub fn run_context(
-    _args: &ContextArgs,
+    args: &ContextArgs,
     output: OutputMode,
     project_root: &Path,
 ) -> anyhow::Result<()> {
@@ -113,14 +132,61 @@ pub fn run_context(
         anyhow::bail!("projection not found");
     };
 
+    let scope = view_query(
+        args.view.as_deref(),
+        args.query.as_deref(),
+        project_root,
+        output,
+    )?;
+    let where_query = match scope.as_deref() {
+        Some(raw) => match FilterQuery::parse(raw) {
+            Ok(query) => Some(query),
+            Err(e) => {
+                render_error(
+                    output,
+                    &CliError::with_details(
+                        format!("invalid --where query: {e}"),
+                        "e.g. --where 'label:api AND NOT assignee:*'",
+                        "invalid_where",
+                    ),
+                )?;
+                anyhow::bail!("invalid --where query");
+            }
+        },
+        None => None,
+    };
+
     let generated_at = chrono::Utc::now().to_rfc3339();
     let now_us = chrono::Utc::now().timestamp_micros();
-    let snapshot = build_triage_snapshot(&conn, now_us)?;
+    let triage_config = load_project_config(project_root).unwrap_or_default().triage;
+    let mut snapshot = build_triage_snapshot(&conn, now_us, &triage_config)?;
+
+    // Triage ranks the whole graph so blockers outside the scope still count;
+    // only the reported items are narrowed to the query.
+    if let Some(ref query) = where_query {
+        let scope: HashSet<String> = query::list_items(
+            &conn,
+            &ItemFilter {
+                query: Some(query.clone()),
+                ..Default::default()
+            },
+        )?
+        .into_iter()
+        .map(|item| item.item_id)
+        .collect();
+        for items in [
+            &mut snapshot.ranked,
+            &mut snapshot.unblocked_ranked,
+            &mut snapshot.stale_in_progress,
+        ] {
+            items.retain(|item| scope.contains(&item.id));
+        }
+    }
 
-    let open_count = count_state(&conn, "open")?;
-    let doing_count = count_state(&conn, "doing")?;
+    let open_count = count_state(&conn, "open", where_query.as_ref())?;
+    let doing_count = count_state(&conn, "doing", where_query.as_ref())?;
     let blocked_items = blocked_items(&conn, &snapshot.ranked)?;
-    let active_goals = active_goals(&conn)?;
+    let active_goals = active_goals(&conn, where_query.as_ref())?;
     let freshness = projection_freshness(&conn, project_root)?;
 
     let payload = ContextPayload {
@@ -128,6 +194,8 @@ pub fn run_context(
         generated_at: generated_at.clone(),
         provider: "bones",
         command: CONTEXT_COMMAND,
+        view: args.view.clone(),
+        filter: where_query.map(|query| query.to_string()),
         summary: ContextSummary {
             open_count,
             doing_count,
@@ -160,9 +228,14 @@ pub fn run_context(
     )
 }
 
-fn count_state(conn: &rusqlite::Connection, state: &str) -> anyhow::Result<u64> {
+fn count_state(
+    conn: &rusqlite::Connection,
+    state: &str,
+    where_query: Option<&FilterQuery>,
+) -> anyhow::Result<u64> {
     let filter = ItemFilter {
         state: Some(state.to_string()),
+        query: where_query.cloned(),
         include_deleted: false,
         ..Default::default()
     };
@@ -234,11 +307,15 @@ fn active_blockers(conn: &rusqlite::Connection, item_id: &str) -> anyhow::Result
     Ok(blockers)
 }
 
-fn active_goals(conn: &rusqlite::Connection) -> anyhow::Result<Vec<GoalItem>> {
+fn active_goals(
+    conn: &rusqlite::Connection,
+    where_query: Option<&FilterQuery>,
+) -> anyhow::Result<Vec<GoalItem>> {
     let goals = query::list_items(
         conn,
         &ItemFilter {
             kind: Some("goal".to_string()),
+            query: where_query.cloned(),
             include_deleted: false,
             sort: SortOrder::UpdatedDesc,
             limit: Some(25),
@@ -308,7 +385,10 @@ fn projection_freshness(
 }
 
 fn render_context_human(payload: &ContextPayload, w: &mut dyn Write) -> std::io::Result<()> {
-    writeln!(w, "Context")?;
+    match &payload.filter {
+        Some(filter) => writeln!(w, "Context (where {filter})")?,
+        None => writeln!(w, "Context")?,
+    }
     writeln!(w, "{:-<72}", "")?;
     writeln!(
         w,
@@ -329,6 +409,9 @@ fn render_context_human(payload: &ContextPayload, w: &mut dyn Write) -> std::io:
 }
 
 fn render_context_text(payload: &ContextPayload, w: &mut dyn Write) -> std::io::Result<()> {
+    if let Some(filter) = &payload.filter {
+        writeln!(w, "where\t{filter}")?;
+    }
     writeln!(
         w,
         "summary\topen={}\tdoing={}\tblocked={}\tstale={}",
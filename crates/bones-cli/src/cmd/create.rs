@@ -4,11 +4,13 @@
 //! shard, projects it into the `SQLite` database, and outputs the result.
 
 use crate::agent;
+use crate::author::write_authored_event;
 use crate::cmd::dup::build_fts_query;
 use crate::itc_state::assign_next_itc;
 use crate::output::{CliError, OutputMode, render, render_error};
 use crate::validate;
 use anyhow::Context;
+use chrono::Utc;
 use clap::Args;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -20,9 +22,9 @@ use bones_core::config::load_project_config;
 use bones_core::db;
 use bones_core::db::project;
 use bones_core::event::Event;
-use bones_core::event::data::{CreateData, EventData, LinkData};
+use bones_core::event::data::{CreateData, EventData, LinkData, UpdateData};
 use bones_core::event::types::EventType;
-use bones_core::event::writer;
+use bones_core::model::due::{DUE_FIELD, due_to_value, format_due, parse_due};
 use bones_core::model::item::Kind;
 use bones_core::model::item::Size;
 use bones_core::model::item::Urgency;
@@ -53,7 +55,8 @@ pub struct CreateArgs {
             "labels",
             "tags",
             "description",
-            "blocks"
+            "blocks",
+            "due"
         ]
     )]
     pub from_file: Option<PathBuf>,
@@ -98,6 +101,10 @@ pub struct CreateArgs {
     #[arg(long)]
     pub blocks: Vec<String>,
 
+    /// Due date: YYYY-MM-DD, RFC 3339, today, tomorrow, or an offset (3d, 2w).
+    #[arg(long, value_name = "when")]
+    pub due: Option<String>,
+
     /// Skip duplicate check entirely.
     #[arg(long)]
     pub force: bool,
@@ -132,6 +139,7 @@ impl CreateArgs {
             labels: self.all_labels(),
             description: self.description.clone(),
             blocks: self.blocks.clone(),
+            due: self.due.clone(),
         })
     }
 }
@@ -146,6 +154,7 @@ struct CreateRequest {
     labels: Vec<String>,
     description: Option<String>,
     blocks: Vec<String>,
+    due: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -167,6 +176,7 @@ struct CreateFileEntry {
     description: Option<String>,
     #[serde(default)]
     blocks: Vec<String>,
+    due: Option<String>,
 }
 
 impl CreateFileEntry {
@@ -189,6 +199,7 @@ impl CreateFileEntry {
             labels,
             description: self.description,
             blocks: self.blocks,
+            due: self.due,
         }
     }
 }
@@ -252,6 +263,8 @@ struct CreateOutput {
     labels: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    due: Option<String>,
     agent: String,
     event_hash: String,
     #[serde(skip_serializing_if = "Vec::is_empty")]
@@ -387,6 +400,9 @@ fn render_create_result(output: OutputMode, result: &CreateOutput) -> anyhow::Re
         if let Some(ref size) = r.size {
             writeln!(w, "Size:    {size}")?;
         }
+        if let Some(ref due) = r.due {
+            writeln!(w, "Due:     {due}")?;
+        }
         Ok(())
     })
 }
@@ -416,6 +432,9 @@ fn render_create_batch(output: OutputMode, results: &[CreateOutput]) -> anyhow::
             if let Some(ref size) = item.size {
                 writeln!(w, "Size:    {size}")?;
             }
+            if let Some(ref due) = item.due {
+                writeln!(w, "Due:     {due}")?;
+            }
         }
         Ok(())
     })
@@ -532,6 +551,24 @@ fn run_create_single(
         None => Urgency::Default,
     };
 
+    // 4c. Parse due date (optional)
+    let due_us: Option<u64> = match &request.due {
+        Some(expr) => match parse_due(expr, Utc::now()) {
+            Ok(us) => Some(us),
+            Err(e) => {
+                return render_and_bail(
+                    output,
+                    CliError::with_details(
+                        e.to_string(),
+                        "Use --due 2026-03-01, --due tomorrow, or --due 3d",
+                        "invalid_due",
+                    ),
+                );
+            }
+        },
+        None => None,
+    };
+
     // 5. Read description
     let description = read_description(&request.description)?;
 
@@ -752,8 +789,9 @@ fn run_create_single(
         item_id: item_id.clone(),
         data: EventData::Create(create_data),
         event_hash: String::new(),
+        signature: None,
     };
-    let mut emitted_events = Vec::with_capacity(1 + block_targets.len());
+    let mut emitted_events = Vec::with_capacity(2 + block_targets.len());
 
     {
         use bones_core::lock::ShardLock;
@@ -775,7 +813,7 @@ fn run_create_single(
         assign_next_itc(project_root, &mut event)?;
 
         // Compute hash and serialize
-        let line = writer::write_event(&mut event)
+        let line = write_authored_event(&mut event)
             .map_err(|e| anyhow::anyhow!("failed to serialize event: {e}"))?;
 
         // Append raw (we hold the lock)
@@ -785,6 +823,37 @@ fn run_create_single(
 
         emitted_events.push(event.clone());
 
+        if let Some(due) = due_us {
+            let mut due_event = Event {
+                wall_ts_us: shard_mgr
+                    .next_timestamp()
+                    .map_err(|e| anyhow::anyhow!("failed to get timestamp: {e}"))?,
+                agent: agent.clone(),
+                itc: String::new(),
+                parents: vec![],
+                event_type: EventType::Update,
+                item_id: item_id.clone(),
+                data: EventData::Update(UpdateData {
+                    field: DUE_FIELD.to_string(),
+                    value: due_to_value(Some(due)),
+                    extra: BTreeMap::new(),
+                }),
+                event_hash: String::new(),
+                signature: None,
+            };
+
+            assign_next_itc(project_root, &mut due_event)?;
+
+            let line = write_authored_event(&mut due_event)
+                .map_err(|e| anyhow::anyhow!("failed to serialize due event: {e}"))?;
+
+            shard_mgr
+                .append_raw(year, month, &line)
+                .map_err(|e| anyhow::anyhow!("failed to write due event: {e}"))?;
+
+            emitted_events.push(due_event);
+        }
+
         for block_target in &block_targets {
             let mut link_event = Event {
                 wall_ts_us: shard_mgr
@@ -802,11 +871,12 @@ fn run_create_single(
                     extra: BTreeMap::new(),
                 }),
                 event_hash: String::new(),
+                signature: None,
             };
 
             assign_next_itc(project_root, &mut link_event)?;
 
-            let line = writer::write_event(&mut link_event)
+            let line = write_authored_event(&mut link_event)
                 .map_err(|e| anyhow::anyhow!("failed to serialize link event: {e}"))?;
 
             shard_mgr
@@ -841,6 +911,7 @@ fn run_create_single(
         parent: request.parent.clone(),
         labels: all_labels,
         description,
+        due: due_us.map(format_due),
         agent,
         event_hash: event.event_hash.clone(),
         duplicates: duplicate_matches,
@@ -1036,6 +1107,7 @@ labels:
             tags: vec![],
             description: None,
             blocks: vec![],
+            due: None,
             force: false,
             allow_secret: false,
         };
@@ -1085,6 +1157,7 @@ labels:
             tags: vec![],
             description: Some("A test description".to_string()),
             blocks: vec![],
+            due: None,
             force: false,
             allow_secret: false,
         };
@@ -1131,6 +1204,7 @@ labels:
             tags: vec![],
             description: None,
             blocks: vec![],
+            due: None,
             force: false,
             allow_secret: false,
         };
@@ -1169,6 +1243,7 @@ labels:
             tags: vec![],
             description: None,
             blocks: vec![],
+            due: None,
             force: false,
             allow_secret: false,
         };
@@ -1200,6 +1275,7 @@ labels:
             tags: vec![],
             description: None,
             blocks: vec![],
+            due: None,
             force: false,
             allow_secret: false,
         };
@@ -1231,6 +1307,7 @@ labels:
             tags: vec![],
             description: None,
             blocks: vec![],
+            due: None,
             force: false,
             allow_secret: false,
         };
@@ -1262,6 +1339,7 @@ labels:
             tags: vec![],
             description: None,
             blocks: vec![],
+            due: None,
             force: false,
             allow_secret: false,
         };
@@ -1295,6 +1373,7 @@ labels:
                 tags: vec![],
                 description: None,
                 blocks: vec![],
+                due: None,
                 force: false,
                 allow_secret: false,
             };
@@ -1343,6 +1422,7 @@ labels:
             tags: vec![],
             description: None,
             blocks: vec![],
+            due: None,
             force: true,
             allow_secret: false,
         };
@@ -1377,6 +1457,7 @@ labels:
             tags: vec![],
             description: None,
             blocks: vec![target_id.clone()],
+            due: None,
             force: true,
             allow_secret: false,
         };
@@ -1437,6 +1518,7 @@ labels:
             tags: vec![],
             description: Some("Detailed description here".to_string()),
             blocks: vec![],
+            due: None,
             force: false,
             allow_secret: false,
         };
@@ -1481,6 +1563,7 @@ labels:
             tags: vec![],
             description: None,
             blocks: vec![],
+            due: None,
             force: false,
             allow_secret: false,
         };
@@ -1535,6 +1618,7 @@ labels:
             tags: vec![],
             description: None,
             blocks: vec![],
+            due: None,
             force: false,
             allow_secret: false,
         };
@@ -1567,6 +1651,7 @@ labels:
             tags: vec![],
             description: None,
             blocks: vec![],
+            due: None,
             force: false,
             allow_secret: true,
         };
@@ -1599,6 +1684,7 @@ labels:
             tags: vec![],
             description: None,
             blocks: vec![],
+            due: None,
             force: false,
             allow_secret: false,
         };
@@ -1620,6 +1706,7 @@ labels:
             tags: vec![],
             description: None,
             blocks: vec![],
+            due: None,
             force: false,
             allow_secret: false,
         };
@@ -1660,6 +1747,7 @@ labels:
             tags: vec![],
             description: None,
             blocks: vec![],
+            due: None,
             force: false,
             allow_secret: false,
         };
@@ -1681,6 +1769,7 @@ labels:
             tags: vec![],
             description: None,
             blocks: vec![],
+            due: None,
             force: true, // Force skip duplicate check
             allow_secret: false,
         };
@@ -4,6 +4,7 @@
 //! append-only event log but are excluded from active views.
 
 use crate::agent;
+use crate::author::write_authored_event;
 use crate::cmd::show::resolve_item_id;
 use crate::itc_state::assign_next_itc;
 use crate::output::{CliError, OutputMode, render, render_error};
@@ -14,7 +15,6 @@ use bones_core::db::query;
 use bones_core::event::Event;
 use bones_core::event::data::{DeleteData, EventData};
 use bones_core::event::types::EventType;
-use bones_core::event::writer;
 use bones_core::model::item_id::ItemId;
 use bones_core::shard::ShardManager;
 use clap::Args;
@@ -181,6 +181,7 @@ fn run_delete_single(
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
 
     {
@@ -199,7 +200,7 @@ fn run_delete_single(
 
         assign_next_itc(project_root, &mut event)?;
 
-        let line = writer::write_event(&mut event)
+        let line = write_authored_event(&mut event)
             .map_err(|e| anyhow::anyhow!("failed to serialize event: {e}"))?;
 
         shard_mgr
@@ -379,6 +380,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
 
         let line = writer::write_event(&mut create_event).unwrap();
@@ -16,12 +16,12 @@ use serde_json::json;
 
 use bones_core::db::query::{item_exists, try_open_projection};
 use bones_core::event::data::{EventData, LinkData, UnlinkData};
-use bones_core::event::writer::write_event;
 use bones_core::event::{Event, EventType};
 use bones_core::model::item_id::ItemId;
 use bones_core::shard::ShardManager;
 
 use crate::agent;
+use crate::author::write_authored_event;
 use crate::itc_state::assign_next_itc;
 use crate::output::{CliError, OutputMode, render_error, render_mode};
 use crate::validate;
@@ -133,6 +133,7 @@ fn emit_event(
         item_id: item_id.clone(),
         data,
         event_hash: String::new(),
+        signature: None,
     };
 
     let project_root = bones_dir.parent().unwrap_or(bones_dir);
@@ -153,7 +154,8 @@ fn emit_event(
 
         assign_next_itc(project_root, &mut event)?;
 
-        let line = write_event(&mut event).map_err(|e| anyhow::anyhow!("serialize event: {e}"))?;
+        let line = write_authored_event(&mut event)
+            .map_err(|e| anyhow::anyhow!("serialize event: {e}"))?;
 
         shard_mgr
             .append_raw(year, month, &line)
@@ -573,6 +575,7 @@ mod tests {
                     extra: BTreeMap::new(),
                 }),
                 event_hash: String::new(),
+                signature: None,
             };
             let line = write_event(&mut evt).expect("write");
             shard_mgr
@@ -662,6 +665,7 @@ mod tests {
                     extra: BTreeMap::new(),
                 }),
                 event_hash: String::new(),
+                signature: None,
             };
             let line = write_event(&mut evt).expect("write");
             shard_mgr
@@ -686,6 +686,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         writer::write_event(&mut event).expect("write create event");
         event
@@ -702,9 +703,11 @@ mod tests {
             data: EventData::Move(MoveData {
                 state: State::Doing,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         writer::write_event(&mut event).expect("write move event");
         event
@@ -0,0 +1,300 @@
+//! `bn diff` — how the backlog changed between two git revisions.
+//!
+//! Reads `.bones/events` at both revisions, takes the events only the newer
+//! side has, and reports per-bone field changes (created, moved, relinked,
+//! relabelled, ...) instead of raw event-line diffs. See
+//! [`bones_core::diff`] for how the changes are derived.
+//!
+//! # Usage
+//!
+//! ```text
+//! # What a feature branch did to the backlog
+//! bn diff main..HEAD
+//!
+//! # Since the branch forked from main, ignoring later work on main
+//! bn diff main...feature
+//!
+//! # Uncommitted and unpushed changes relative to main
+//! bn diff main
+//! ```
+
+use crate::cmd::bundle::load_events;
+use crate::cmd::conflicts::value_display;
+use crate::output::{CliError, OutputMode, pretty_rule, render_error, render_mode};
+use anyhow::Context as _;
+use bones_core::config::{EventStorage, load_project_config};
+use bones_core::diff::{BacklogDiff, Change, diff_event_sets};
+use bones_core::event::Event;
+use bones_core::sync::git_ref::GitRefStore;
+use clap::Args;
+use serde::Serialize;
+use std::io::Write;
+use std::path::Path;
+
+/// Directory holding event shards, relative to the project root.
+const EVENTS_DIR: &str = ".bones/events";
+
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    /// Revisions to compare: `A..B`, `A...B` (from their merge base), or a
+    /// single revision to compare with the working tree.
+    pub range: String,
+}
+
+#[derive(Debug, Serialize)]
+struct DiffOutput {
+    /// Description of the older side.
+    from: String,
+    /// Description of the newer side.
+    to: String,
+    #[serde(flatten)]
+    diff: BacklogDiff,
+}
+
+/// The two sides of a revision range.
+#[derive(Debug, PartialEq, Eq)]
+enum Range<'a> {
+    /// `A..B`: compare the two revisions directly.
+    Direct(&'a str, &'a str),
+    /// `A...B`: compare `B` with the merge base of `A` and `B`.
+    Forked(&'a str, &'a str),
+    /// `A`: compare `A` with the shards in the working tree.
+    WorkingTree(&'a str),
+}
+
+/// Parse a git-style revision range; an empty side means `HEAD`.
+fn parse_range(range: &str) -> Range<'_> {
+    const fn side(rev: &str) -> &str {
+        if rev.is_empty() { "HEAD" } else { rev }
+    }
+    if let Some((old, new)) = range.split_once("...") {
+        Range::Forked(side(old), side(new))
+    } else if let Some((old, new)) = range.split_once("..") {
+        Range::Direct(side(old), side(new))
+    } else {
+        Range::WorkingTree(range)
+    }
+}
+
+fn fail(output: OutputMode, msg: &str, suggestion: &str, code: &str) -> anyhow::Error {
+    let _ = render_error(output, &CliError::with_details(msg, suggestion, code));
+    anyhow::anyhow!("{msg}")
+}
+
+/// Execute `bn diff`.
+pub fn run_diff(args: &DiffArgs, output: OutputMode, project_root: &Path) -> anyhow::Result<()> {
+    let config = load_project_config(project_root)?;
+    if config.sync.events == EventStorage::Ref {
+        return Err(fail(
+            output,
+            "event shards are not committed on branches in this project",
+            "events live on refs/bones/events (`[sync] events = \"ref\"`), so branches carry no backlog changes",
+            "events_not_on_branches",
+        ));
+    }
+
+    let store = GitRefStore::new(project_root);
+    let read = |rev: &str| -> anyhow::Result<Vec<Event>> {
+        store.read_shards_at(rev, EVENTS_DIR).map_err(|e| {
+            fail(
+                output,
+                &e.to_string(),
+                "check the revision names",
+                "bad_revision",
+            )
+        })
+    };
+
+    let (from, to, old, new) = match parse_range(&args.range) {
+        Range::Direct(old_rev, new_rev) => (
+            old_rev.to_string(),
+            new_rev.to_string(),
+            read(old_rev)?,
+            read(new_rev)?,
+        ),
+        Range::Forked(old_rev, new_rev) => {
+            let base = store
+                .merge_base(old_rev, new_rev)
+                .context("failed to run git merge-base")?
+                .ok_or_else(|| {
+                    fail(
+                        output,
+                        &format!("'{old_rev}' and '{new_rev}' have no common ancestor"),
+                        "compare them directly with `A..B`",
+                        "no_merge_base",
+                    )
+                })?;
+            (
+                format!("merge-base({old_rev}, {new_rev})"),
+                new_rev.to_string(),
+                read(&base)?,
+                read(new_rev)?,
+            )
+        }
+        Range::WorkingTree(old_rev) => (
+            old_rev.to_string(),
+            "working tree".to_string(),
+            read(old_rev)?,
+            load_events(&project_root.join(".bones"))?,
+        ),
+    };
+
+    let payload = DiffOutput {
+        from,
+        to,
+        diff: diff_event_sets(&old, &new),
+    };
+    render_mode(output, &payload, render_diff_text, render_diff_human)
+}
+
+/// Space-separated `+added -removed` members.
+fn members(added: &[String], removed: &[String]) -> String {
+    added
+        .iter()
+        .map(|m| format!("+{m}"))
+        .chain(removed.iter().map(|m| format!("-{m}")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Change name and details, shared by the text and pretty renderers.
+fn describe(change: &Change) -> (&'static str, String) {
+    match change {
+        Change::Created { kind, state } => ("created", format!("{kind} ({state})")),
+        Change::Moved { from, to } => ("moved", format!("{from} -> {to}")),
+        Change::Relinked {
+            link,
+            added,
+            removed,
+        } => ("relinked", format!("{link} {}", members(added, removed))),
+        Change::Relabelled { added, removed } => ("relabelled", members(added, removed)),
+        Change::Reassigned { added, removed } => ("reassigned", members(added, removed)),
+        Change::Updated { field, from, to } => (
+            "updated",
+            format!("{field} {} -> {}", value_display(from), value_display(to)),
+        ),
+        Change::Commented { count } => ("commented", format!("{count} new")),
+        Change::Attached { count } => ("attached", format!("{count} new")),
+        Change::Deleted => ("deleted", String::new()),
+    }
+}
+
+fn render_diff_text(payload: &DiffOutput, w: &mut dyn Write) -> std::io::Result<()> {
+    for item in &payload.diff.items {
+        for change in &item.changes {
+            let (name, details) = describe(change);
+            writeln!(w, "{}  {name}  {details}", item.item_id)?;
+        }
+    }
+    Ok(())
+}
+
+fn render_diff_human(payload: &DiffOutput, w: &mut dyn Write) -> std::io::Result<()> {
+    let diff = &payload.diff;
+    writeln!(
+        w,
+        "Backlog changes {} -> {}: {} bone(s), {} new event(s)",
+        payload.from,
+        payload.to,
+        diff.items.len(),
+        diff.added_events
+    )?;
+    pretty_rule(w)?;
+    if diff.items.is_empty() {
+        writeln!(w, "No backlog changes.")?;
+    }
+    for item in &diff.items {
+        writeln!(w, "{}  {}", item.item_id, item.title)?;
+        for change in &item.changes {
+            let (name, details) = describe(change);
+            writeln!(w, "  {name:<11} {details}")?;
+        }
+    }
+    if diff.missing_events > 0 {
+        writeln!(w)?;
+        writeln!(
+            w,
+            "{} event(s) on {} are not on {} and are not shown.",
+            diff.missing_events, payload.from, payload.to
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bones_core::diff::ItemDiff;
+
+    #[test]
+    fn ranges_parse_like_git() {
+        assert_eq!(parse_range("main..HEAD"), Range::Direct("main", "HEAD"));
+        assert_eq!(parse_range("main.."), Range::Direct("main", "HEAD"));
+        assert_eq!(parse_range("main...topic"), Range::Forked("main", "topic"));
+        assert_eq!(parse_range("main"), Range::WorkingTree("main"));
+    }
+
+    fn sample() -> DiffOutput {
+        DiffOutput {
+            from: "main".to_string(),
+            to: "HEAD".to_string(),
+            diff: BacklogDiff {
+                added_events: 3,
+                missing_events: 1,
+                items: vec![ItemDiff {
+                    item_id: "bn-a1".to_string(),
+                    title: "Fix login".to_string(),
+                    events: 3,
+                    changes: vec![
+                        Change::Moved {
+                            from: "open".to_string(),
+                            to: "doing".to_string(),
+                        },
+                        Change::Relabelled {
+                            added: vec!["backend".to_string()],
+                            removed: vec!["frontend".to_string()],
+                        },
+                        Change::Updated {
+                            field: "urgency".to_string(),
+                            from: serde_json::Value::from("default"),
+                            to: serde_json::Value::from("urgent"),
+                        },
+                    ],
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn text_output_is_one_line_per_change() {
+        let mut out = Vec::new();
+        render_diff_text(&sample(), &mut out).expect("render");
+        let rendered = String::from_utf8(out).expect("utf8");
+        assert_eq!(
+            rendered,
+            "bn-a1  moved  open -> doing\n\
+             bn-a1  relabelled  +backend -frontend\n\
+             bn-a1  updated  urgency \"default\" -> \"urgent\"\n"
+        );
+    }
+
+    #[test]
+    fn pretty_output_groups_changes_by_bone() {
+        let mut out = Vec::new();
+        render_diff_human(&sample(), &mut out).expect("render");
+        let rendered = String::from_utf8(out).expect("utf8");
+        assert!(rendered.contains("Backlog changes main -> HEAD: 1 bone(s), 3 new event(s)"));
+        assert!(rendered.contains("bn-a1  Fix login"));
+        assert!(rendered.contains("  moved       open -> doing"));
+        assert!(rendered.contains("1 event(s) on main are not on HEAD"));
+    }
+
+    #[test]
+    fn json_output_tags_changes() {
+        let json = serde_json::to_value(sample()).expect("json");
+        assert_eq!(json["from"], "main");
+        assert_eq!(json["added_events"], 3);
+        assert_eq!(json["items"][0]["changes"][0]["change"], "moved");
+        assert_eq!(json["items"][0]["changes"][1]["added"][0], "backend");
+    }
+}
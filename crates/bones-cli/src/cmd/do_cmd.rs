@@ -5,6 +5,7 @@
 //! projects the state change into `SQLite`, and outputs the result.
 
 use crate::agent;
+use crate::author::write_authored_event;
 use crate::cmd::assign::emit_assign_event;
 use crate::cmd::open_projection_for_mutation;
 use crate::cmd::show::resolve_item_id;
@@ -24,7 +25,6 @@ use bones_core::db::query;
 use bones_core::event::Event;
 use bones_core::event::data::{AssignAction, EventData, MoveData};
 use bones_core::event::types::EventType;
-use bones_core::event::writer;
 use bones_core::model::item::State;
 use bones_core::model::item_id::ItemId;
 use bones_core::shard::ShardManager;
@@ -124,6 +124,7 @@ pub fn run_do_single(
     let move_data = MoveData {
         state: target_state,
         reason: None,
+        substate: None,
         extra: BTreeMap::new(),
     };
 
@@ -136,6 +137,7 @@ pub fn run_do_single(
         item_id: ItemId::new_unchecked(&resolved_id),
         data: EventData::Move(move_data),
         event_hash: String::new(),
+        signature: None,
     };
 
     {
@@ -155,7 +157,7 @@ pub fn run_do_single(
         assign_next_itc(project_root, &mut event)?;
 
         // Serialize and write
-        let line = writer::write_event(&mut event)
+        let line = write_authored_event(&mut event)
             .map_err(|e| anyhow::anyhow!("failed to serialize event: {e}"))?;
 
         shard_mgr
@@ -364,6 +366,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
 
         let line = writer::write_event(&mut create_event).unwrap();
@@ -395,9 +398,11 @@ mod tests {
                     data: EventData::Move(MoveData {
                         state: step_state,
                         reason: None,
+                        substate: None,
                         extra: BTreeMap::new(),
                     }),
                     event_hash: String::new(),
+                    signature: None,
                 };
                 let line = writer::write_event(&mut move_event).unwrap();
                 shard_mgr
@@ -9,6 +9,7 @@
 //! move event for the parent goal.
 
 use crate::agent;
+use crate::author::write_authored_event;
 use crate::cmd::open_projection_for_mutation;
 use crate::cmd::show::resolve_item_id;
 use crate::itc_state::assign_next_itc;
@@ -27,7 +28,6 @@ use bones_core::db::query;
 use bones_core::event::Event;
 use bones_core::event::data::{EventData, MoveData};
 use bones_core::event::types::EventType;
-use bones_core::event::writer;
 use bones_core::model::goal::{GoalPolicy, goal_policy_override_from_labels};
 use bones_core::model::item::State;
 use bones_core::model::item_id::ItemId;
@@ -211,6 +211,7 @@ fn run_done_single(
     let move_data = MoveData {
         state: target_state,
         reason: reason.map(String::from),
+        substate: None,
         extra: BTreeMap::new(),
     };
 
@@ -223,6 +224,7 @@ fn run_done_single(
         item_id: ItemId::new_unchecked(&resolved_id),
         data: EventData::Move(move_data),
         event_hash: String::new(),
+        signature: None,
     };
 
     {
@@ -241,7 +243,7 @@ fn run_done_single(
 
         assign_next_itc(project_root, &mut event)?;
 
-        let line = writer::write_event(&mut event)
+        let line = write_authored_event(&mut event)
             .map_err(|e| anyhow::anyhow!("failed to serialize event: {e}"))?;
 
         shard_mgr
@@ -262,6 +264,7 @@ fn run_done_single(
                 reason: Some(format!(
                     "auto-completed: all children of {parent_id} are done"
                 )),
+                substate: None,
                 extra: BTreeMap::new(),
             };
 
@@ -274,6 +277,7 @@ fn run_done_single(
                 item_id: ItemId::new_unchecked(&parent_id),
                 data: EventData::Move(parent_move_data),
                 event_hash: String::new(),
+                signature: None,
             };
 
             {
@@ -292,7 +296,7 @@ fn run_done_single(
 
                 assign_next_itc(project_root, &mut parent_event)?;
 
-                let parent_line = writer::write_event(&mut parent_event)
+                let parent_line = write_authored_event(&mut parent_event)
                     .map_err(|e| anyhow::anyhow!("failed to serialize parent event: {e}"))?;
 
                 shard_mgr
@@ -497,6 +501,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
 
         let line = writer::write_event(&mut create_event).unwrap();
@@ -525,9 +530,11 @@ mod tests {
                     data: EventData::Move(MoveData {
                         state: step_state,
                         reason: None,
+                        substate: None,
                         extra: BTreeMap::new(),
                     }),
                     event_hash: String::new(),
+                    signature: None,
                 };
                 let line = writer::write_event(&mut move_event).unwrap();
                 shard_mgr
@@ -581,6 +588,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         let line = writer::write_event(&mut goal_event).unwrap();
         shard_mgr
@@ -612,6 +620,7 @@ mod tests {
                     extra: BTreeMap::new(),
                 }),
                 event_hash: String::new(),
+                signature: None,
             };
             let line = writer::write_event(&mut child_event).unwrap();
             shard_mgr
@@ -633,9 +642,11 @@ mod tests {
                         data: EventData::Move(MoveData {
                             state: step,
                             reason: None,
+                            substate: None,
                             extra: BTreeMap::new(),
                         }),
                         event_hash: String::new(),
+                        signature: None,
                     };
                     let line = writer::write_event(&mut move_event).unwrap();
                     shard_mgr
@@ -901,6 +912,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         let line = writer::write_event(&mut goal_event).unwrap();
         shard_mgr
@@ -929,6 +941,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         let line = writer::write_event(&mut child_event).unwrap();
         shard_mgr
@@ -1034,6 +1047,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         let line = writer::write_event(&mut parent_event).unwrap();
         shard_mgr
@@ -1062,6 +1076,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         let line = writer::write_event(&mut child_event).unwrap();
         shard_mgr
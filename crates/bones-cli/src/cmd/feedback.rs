@@ -276,6 +276,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
 
         let line = writer::write_event(&mut create_event).unwrap();
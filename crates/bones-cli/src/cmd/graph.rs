@@ -1539,6 +1539,7 @@ mod tests {
                     extra: BTreeMap::new(),
                 }),
                 event_hash: String::new(),
+                signature: None,
             };
             let line = write_event(&mut evt).expect("write");
             shard_mgr
@@ -1562,6 +1563,7 @@ mod tests {
                     extra: BTreeMap::new(),
                 }),
                 event_hash: String::new(),
+                signature: None,
             };
             let line = write_event(&mut evt).expect("write");
             shard_mgr
@@ -1,10 +1,12 @@
 use anyhow::{Context as _, Result};
 use bones_core::event::{
     AssignAction, AssignData, CommentData, CreateData, Event, EventData, EventType, MoveData,
-    PartialParsedLine, parse_line_partial, writer::write_event,
+    PartialParsedLine, parse_line_partial,
+    writer::{compute_event_hash, write_event, write_line},
 };
 use bones_core::model::item::{Kind, State, Urgency};
 use bones_core::model::item_id::ItemId;
+use bones_core::policy::{self, Policy};
 use bones_core::shard::ShardManager;
 use chrono::Datelike;
 use clap::Args;
@@ -18,7 +20,7 @@ use std::io::{self, BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
 use crate::itc_state::assign_next_itc;
-use crate::output::{OutputMode, pretty_kv, pretty_section};
+use crate::output::{OutputMode, pretty_kv, pretty_section, render_mode};
 
 #[derive(Args, Debug)]
 pub struct ImportArgs {
@@ -320,6 +322,7 @@ pub fn run_import(args: &ImportArgs, output: OutputMode, project_root: &Path) ->
                 extra,
             }),
             event_hash: String::new(),
+            signature: None,
         };
 
         append_event(project_root, &shard_manager, &mut event)?;
@@ -363,6 +366,7 @@ pub fn run_import(args: &ImportArgs, output: OutputMode, project_root: &Path) ->
                 item_id: issue_id.clone(),
                 data,
                 event_hash: String::new(),
+                signature: None,
             };
 
             append_event(project_root, &shard_manager, &mut event)?;
@@ -410,9 +414,20 @@ struct ImportSummary {
     total_lines: usize,
     imported: usize,
     skipped_invalid: usize,
+    /// Events refused by `.bones/policy.toml` and quarantined.
+    quarantined: usize,
+    /// Which input lines were quarantined, and why.
+    quarantined_lines: Vec<QuarantinedLine>,
     imported_per_type: HashMap<String, usize>,
 }
 
+#[derive(Debug, Serialize)]
+struct QuarantinedLine {
+    line: usize,
+    event_hash: String,
+    reason: String,
+}
+
 fn skip_invalid_jsonl_record(
     report: &mut ImportSummary,
     line_no: usize,
@@ -436,6 +451,9 @@ fn run_jsonl_import(args: &ImportArgs, output: OutputMode, project_root: &Path)
     shard_manager
         .init()
         .context("failed to initialize .bones shard state")?;
+    let bones_dir = project_root.join(".bones");
+    let policy = Policy::load(&bones_dir).context("failed to load .bones/policy.toml")?;
+    let mut rejected = Vec::new();
 
     let mut parent_index: HashMap<String, String> = HashMap::new();
     let mut report = ImportSummary {
@@ -444,6 +462,8 @@ fn run_jsonl_import(args: &ImportArgs, output: OutputMode, project_root: &Path)
         total_lines: 0,
         imported: 0,
         skipped_invalid: 0,
+        quarantined: 0,
+        quarantined_lines: Vec::new(),
         imported_per_type: HashMap::new(),
     };
 
@@ -526,10 +546,23 @@ fn run_jsonl_import(args: &ImportArgs, output: OutputMode, project_root: &Path)
             item_id: item_id.clone(),
             data,
             event_hash: String::new(),
+            signature: None,
         };
 
+        // The agent name comes from the file, so the event is never signed
+        // with a local key: the policy sees it unsigned.
         assign_next_itc(project_root, &mut event)?;
-        let line = write_event(&mut event).context("failed to serialize imported event")?;
+        event.event_hash = compute_event_hash(&event).context("failed to hash imported event")?;
+        let line = write_line(&event).context("failed to serialize imported event")?;
+        if let Some(rejection) = policy.check(&event) {
+            report.quarantined_lines.push(QuarantinedLine {
+                line: line_no,
+                event_hash: event.event_hash.clone(),
+                reason: rejection.reason.clone(),
+            });
+            rejected.push(rejection);
+            continue;
+        }
 
         {
             use bones_core::lock::ShardLock;
@@ -554,40 +587,56 @@ fn run_jsonl_import(args: &ImportArgs, output: OutputMode, project_root: &Path)
         report.imported += 1;
     }
 
-    match output {
-        OutputMode::Json => {
-            println!("{}", serde_json::to_string_pretty(&report)?);
-        }
-        OutputMode::Text => {
-            println!(
-                "import_jsonl input={} total_lines={} imported={} skipped_invalid={}",
+    policy::quarantine(&bones_dir, &rejected).context("failed to quarantine rejected events")?;
+    report.quarantined = rejected.len();
+
+    render_mode(
+        output,
+        &report,
+        |report, w| {
+            writeln!(
+                w,
+                "import_jsonl input={} total_lines={} imported={} skipped_invalid={} quarantined={}",
                 report.input_path.as_deref().unwrap_or("<stdin>"),
                 report.total_lines,
                 report.imported,
-                report.skipped_invalid
-            );
+                report.skipped_invalid,
+                report.quarantined
+            )?;
             for (event_type, count) in &report.imported_per_type {
-                println!("type={event_type} count={count}");
+                writeln!(w, "type={event_type} count={count}")?;
             }
-        }
-        OutputMode::Pretty => {
-            println!(
+            for entry in &report.quarantined_lines {
+                writeln!(
+                    w,
+                    "quarantined line={} event={} reason={}",
+                    entry.line, entry.event_hash, entry.reason
+                )?;
+            }
+            Ok(())
+        },
+        |report, w| {
+            writeln!(
+                w,
                 "bn data import --jsonl {}",
                 report.input_path.as_deref().unwrap_or("<stdin>")
-            );
-            println!("  total lines:     {}", report.total_lines);
-            println!("  imported:       {}", report.imported);
-            println!("  skipped:        {}", report.skipped_invalid);
+            )?;
+            writeln!(w, "  total lines:     {}", report.total_lines)?;
+            writeln!(w, "  imported:       {}", report.imported)?;
+            writeln!(w, "  skipped:        {}", report.skipped_invalid)?;
+            writeln!(w, "  quarantined:    {}", report.quarantined)?;
+            for entry in &report.quarantined_lines {
+                writeln!(w, "    line {}: {}", entry.line, entry.reason)?;
+            }
             if !report.imported_per_type.is_empty() {
-                println!("  types:");
+                writeln!(w, "  types:")?;
                 for (event_type, count) in &report.imported_per_type {
-                    println!("    {event_type}: {count}");
+                    writeln!(w, "    {event_type}: {count}")?;
                 }
             }
-        }
-    }
-
-    Ok(())
+            Ok(())
+        },
+    )
 }
 
 fn event_to_shard_timestamp(timestamp_us: i64) -> Result<(i32, u32)> {
@@ -708,6 +757,7 @@ fn plan_issue_events(
             agent: github_agent(&comment.user.login),
             payload: PlannedPayload::Comment(CommentData {
                 body: comment.body.clone(),
+                reply_to: None,
                 extra: comment_extra,
             }),
         });
@@ -732,6 +782,7 @@ fn plan_issue_events(
             payload: PlannedPayload::Move(MoveData {
                 state: State::Done,
                 reason: Some("Imported closed issue from GitHub".to_string()),
+                substate: None,
                 extra: move_extra,
             }),
         });
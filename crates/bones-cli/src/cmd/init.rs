@@ -33,6 +33,14 @@ const CONFIG_TOML: &str = "[goals]\n\
     [triage]\n\
     feedback_learning = true\n\
     \n\
+    [triage.size_durations]\n\
+    xs = 1\n\
+    s = 2\n\
+    m = 3\n\
+    l = 5\n\
+    xl = 8\n\
+    unestimated = 3\n\
+    \n\
     [archive]\n\
     auto_days = 30\n";
 
@@ -312,6 +320,10 @@ mod tests {
             content.contains("feedback_learning = true"),
             "missing feedback_learning"
         );
+        assert!(
+            content.contains("[triage.size_durations]"),
+            "missing [triage.size_durations]"
+        );
         assert!(content.contains("[archive]"), "missing [archive]");
         assert!(
             content.contains("auto_days = 30"),
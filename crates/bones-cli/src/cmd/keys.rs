@@ -0,0 +1,397 @@
+//! `bn keys` — manage Ed25519 keys used to sign events.
+//!
+//! Public keys live in `.bones/keys/<agent>.pub` and are committed with the
+//! project so every replica can check signatures offline. Secret keys stay in
+//! the user's config directory (`$BONES_KEY_DIR` overrides it). Once an agent
+//! has a secret key, every event it writes is signed automatically.
+//!
+//! # Usage
+//!
+//! ```text
+//! bn keys generate                   # key for the current agent
+//! bn keys list
+//! bn keys add alice ed25519:...      # register a teammate's key
+//! bn keys remove alice [ed25519:...]
+//! ```
+
+use crate::agent;
+use crate::cmd::do_cmd::find_bones_dir;
+use crate::output::{CliError, OutputMode, pretty_table, render, render_error};
+use crate::validate;
+use bones_core::signing::{
+    Keyring, SigningError, decode_public_key, default_key_dir, encode_public_key,
+    generate_signing_key, load_signing_key, public_key_path, register_public_key,
+    remove_public_key,
+};
+use clap::{Args, Subcommand};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Args, Debug)]
+pub struct KeysArgs {
+    #[command(subcommand)]
+    pub command: KeysCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum KeysCommand {
+    /// Generate a signing key for the current agent and register its public key
+    Generate {
+        /// Replace an existing secret key (the old public key stays
+        /// registered so earlier signatures still verify)
+        #[arg(long)]
+        force: bool,
+    },
+    /// List registered public keys
+    List,
+    /// Register another agent's public key
+    Add {
+        /// Agent the key belongs to
+        agent: String,
+        /// Public key (`ed25519:<base64url>`)
+        public_key: String,
+    },
+    /// Remove an agent's public key, or all of its keys
+    Remove {
+        /// Agent whose key to remove
+        agent: String,
+        /// Key to remove (default: every key for the agent)
+        public_key: Option<String>,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct GenerateOutput {
+    ok: bool,
+    agent: String,
+    public_key: String,
+    secret_key_path: String,
+    registry_path: String,
+}
+
+#[derive(Debug, Serialize)]
+struct KeyRow {
+    agent: String,
+    public_key: String,
+    /// Whether the matching secret key is available on this machine.
+    local: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ListOutput {
+    keys: Vec<KeyRow>,
+}
+
+#[derive(Debug, Serialize)]
+struct AddOutput {
+    ok: bool,
+    agent: String,
+    public_key: String,
+    added: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct RemoveOutput {
+    ok: bool,
+    agent: String,
+    removed: usize,
+}
+
+fn fail(output: OutputMode, msg: &str, suggestion: &str, code: &str) -> anyhow::Error {
+    let _ = render_error(output, &CliError::with_details(msg, suggestion, code));
+    anyhow::anyhow!("{msg}")
+}
+
+fn signing_failure(output: OutputMode, err: &SigningError) -> anyhow::Error {
+    let (suggestion, code) = match err {
+        SigningError::KeyExists(_) => (
+            "pass --force to replace it (the old public key stays registered)",
+            "key_exists",
+        ),
+        SigningError::InvalidAgent(_) => (
+            "use an agent name without path separators or leading dots",
+            "invalid_agent",
+        ),
+        SigningError::InvalidKey { .. } => (
+            "keys look like ed25519:<base64url>; fix or remove the offending line",
+            "invalid_key",
+        ),
+        SigningError::NoKeyDir => ("set BONES_KEY_DIR", "no_key_dir"),
+        _ => (
+            "check permissions on .bones/keys and the key directory",
+            "keys_failed",
+        ),
+    };
+    fail(output, &err.to_string(), suggestion, code)
+}
+
+fn bones_dir(project_root: &Path, output: OutputMode) -> anyhow::Result<PathBuf> {
+    find_bones_dir(project_root).ok_or_else(|| {
+        fail(
+            output,
+            "Not a bones project: .bones directory not found",
+            "Run 'bn init' to create a new project",
+            "not_a_project",
+        )
+    })
+}
+
+fn relative_display(project_root: &Path, path: &Path) -> String {
+    path.strip_prefix(project_root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// Execute `bn keys`.
+///
+/// # Errors
+///
+/// Returns an error if the project or key directory cannot be read or
+/// written, or an argument is invalid.
+pub fn run_keys(
+    args: &KeysArgs,
+    agent_flag: Option<&str>,
+    output: OutputMode,
+    project_root: &Path,
+) -> anyhow::Result<()> {
+    let bones_dir = bones_dir(project_root, output)?;
+    match &args.command {
+        KeysCommand::Generate { force } => {
+            run_generate(&bones_dir, *force, agent_flag, output, project_root)
+        }
+        KeysCommand::List => run_list(&bones_dir, output),
+        KeysCommand::Add { agent, public_key } => run_add(&bones_dir, agent, public_key, output),
+        KeysCommand::Remove { agent, public_key } => {
+            run_remove(&bones_dir, agent, public_key.as_deref(), output)
+        }
+    }
+}
+
+fn run_generate(
+    bones_dir: &Path,
+    force: bool,
+    agent_flag: Option<&str>,
+    output: OutputMode,
+    project_root: &Path,
+) -> anyhow::Result<()> {
+    let agent = match agent::require_agent(agent_flag) {
+        Ok(a) => a,
+        Err(e) => {
+            return Err(fail(
+                output,
+                &e.message,
+                "Set --agent, BONES_AGENT, or AGENT",
+                e.code,
+            ));
+        }
+    };
+    if let Err(e) = validate::validate_agent(&agent) {
+        render_error(output, &e.to_cli_error())?;
+        anyhow::bail!("{}", e.reason);
+    }
+    let key_dir =
+        default_key_dir().ok_or_else(|| signing_failure(output, &SigningError::NoKeyDir))?;
+
+    let key =
+        generate_signing_key(&key_dir, &agent, force).map_err(|e| signing_failure(output, &e))?;
+    let public = key.verifying_key();
+    register_public_key(bones_dir, &agent, &public).map_err(|e| signing_failure(output, &e))?;
+
+    let secret_path = bones_core::signing::secret_key_path(&key_dir, &agent)
+        .map_err(|e| signing_failure(output, &e))?;
+    let registry_path =
+        public_key_path(bones_dir, &agent).map_err(|e| signing_failure(output, &e))?;
+    let out = GenerateOutput {
+        ok: true,
+        agent,
+        public_key: encode_public_key(&public),
+        secret_key_path: secret_path.display().to_string(),
+        registry_path: relative_display(project_root, &registry_path),
+    };
+
+    render(output, &out, |out, w| {
+        writeln!(w, "Generated signing key for {}", out.agent)?;
+        writeln!(w, "  public key: {}", out.public_key)?;
+        writeln!(w, "  secret key: {}", out.secret_key_path)?;
+        writeln!(
+            w,
+            "Registered in {}; commit it so other replicas can verify your events.",
+            out.registry_path
+        )
+    })
+}
+
+fn run_list(bones_dir: &Path, output: OutputMode) -> anyhow::Result<()> {
+    let keyring = Keyring::load(bones_dir).map_err(|e| signing_failure(output, &e))?;
+    let key_dir = default_key_dir();
+
+    let keys = keyring
+        .agents()
+        .flat_map(|(agent, keys)| {
+            let local = key_dir
+                .as_deref()
+                .and_then(|dir| load_signing_key(dir, agent).ok().flatten())
+                .map(|secret| secret.verifying_key());
+            keys.iter().map(move |key| KeyRow {
+                agent: agent.to_string(),
+                public_key: encode_public_key(key),
+                local: local.as_ref() == Some(key),
+            })
+        })
+        .collect();
+
+    render(output, &ListOutput { keys }, |out, w| {
+        if out.keys.is_empty() {
+            return writeln!(
+                w,
+                "No keys registered. Run `bn keys generate` to sign your events."
+            );
+        }
+        let rows: Vec<Vec<String>> = out
+            .keys
+            .iter()
+            .map(|row| {
+                vec![
+                    row.agent.clone(),
+                    row.public_key.clone(),
+                    if row.local { "yes" } else { "" }.to_string(),
+                ]
+            })
+            .collect();
+        pretty_table(w, &["AGENT", "PUBLIC KEY", "LOCAL"], &rows)
+    })
+}
+
+fn parse_public_key(
+    raw: &str,
+    output: OutputMode,
+) -> anyhow::Result<bones_core::signing::VerifyingKey> {
+    decode_public_key(raw).ok_or_else(|| {
+        fail(
+            output,
+            &format!("'{raw}' is not an ed25519 public key"),
+            "copy the key from `bn keys list` on the other replica",
+            "invalid_key",
+        )
+    })
+}
+
+fn run_add(bones_dir: &Path, agent: &str, raw_key: &str, output: OutputMode) -> anyhow::Result<()> {
+    if let Err(e) = validate::validate_agent(agent) {
+        render_error(output, &e.to_cli_error())?;
+        anyhow::bail!("{}", e.reason);
+    }
+    let key = parse_public_key(raw_key, output)?;
+    let added =
+        register_public_key(bones_dir, agent, &key).map_err(|e| signing_failure(output, &e))?;
+
+    let out = AddOutput {
+        ok: true,
+        agent: agent.to_string(),
+        public_key: encode_public_key(&key),
+        added,
+    };
+    render(output, &out, |out, w| {
+        if out.added {
+            writeln!(w, "Registered {} for {}", out.public_key, out.agent)
+        } else {
+            writeln!(
+                w,
+                "{} is already registered for {}",
+                out.public_key, out.agent
+            )
+        }
+    })
+}
+
+fn run_remove(
+    bones_dir: &Path,
+    agent: &str,
+    raw_key: Option<&str>,
+    output: OutputMode,
+) -> anyhow::Result<()> {
+    let key = raw_key
+        .map(|raw| parse_public_key(raw, output))
+        .transpose()?;
+    let removed = remove_public_key(bones_dir, agent, key.as_ref())
+        .map_err(|e| signing_failure(output, &e))?;
+    if removed == 0 {
+        return Err(fail(
+            output,
+            &format!("no matching key registered for {agent}"),
+            "run `bn keys list` to see registered keys",
+            "key_not_found",
+        ));
+    }
+
+    let out = RemoveOutput {
+        ok: true,
+        agent: agent.to_string(),
+        removed,
+    };
+    render(output, &out, |out, w| {
+        writeln!(w, "Removed {} key(s) for {}", out.removed, out.agent)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Wrapper {
+        #[command(flatten)]
+        args: KeysArgs,
+    }
+
+    #[test]
+    fn parses_subcommands() {
+        let w = Wrapper::parse_from(["test", "generate", "--force"]);
+        assert!(matches!(
+            w.args.command,
+            KeysCommand::Generate { force: true }
+        ));
+
+        let w = Wrapper::parse_from(["test", "add", "alice", "ed25519:abc"]);
+        assert!(matches!(
+            w.args.command,
+            KeysCommand::Add { ref agent, .. } if agent == "alice"
+        ));
+
+        let w = Wrapper::parse_from(["test", "remove", "alice"]);
+        assert!(matches!(
+            w.args.command,
+            KeysCommand::Remove {
+                public_key: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn add_and_remove_update_the_registry() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let bones_dir = dir.path().join(".bones");
+        std::fs::create_dir_all(&bones_dir).expect("mkdir");
+        let key = bones_core::signing::SigningKey::from_bytes(&[1; 32]).verifying_key();
+        let encoded = encode_public_key(&key);
+
+        run_add(&bones_dir, "alice", &encoded, OutputMode::Json).expect("add");
+        assert_eq!(
+            Keyring::load(&bones_dir).expect("load").keys_for("alice"),
+            [key]
+        );
+        assert!(run_add(&bones_dir, "alice", "ed25519:nope", OutputMode::Json).is_err());
+
+        run_remove(&bones_dir, "alice", None, OutputMode::Json).expect("remove");
+        assert!(
+            Keyring::load(&bones_dir)
+                .expect("load")
+                .keys_for("alice")
+                .is_empty()
+        );
+        assert!(run_remove(&bones_dir, "alice", None, OutputMode::Json).is_err());
+    }
+}
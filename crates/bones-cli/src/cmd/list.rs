@@ -3,26 +3,44 @@
 //! By default, shows all open items (state = "open"). Filters can be
 //! combined. Supports both human-readable table and JSON output.
 
+use crate::cmd::as_of::{open_read_projection, pretty_replay_note};
 use crate::output::{
     CliError, OutputMode, pretty_kv, pretty_table, render, render_error, render_mode,
 };
 use crate::validate;
-use bones_core::db::query::{self, ItemFilter, QueryItem, SortOrder};
+use bones_core::config::load_project_config;
+use bones_core::db::filter_query::FilterQuery;
+use bones_core::db::query::{self, CustomFieldSort, ItemFilter, QueryItem, SortOrder};
+use bones_core::model::custom::{
+    CUSTOM_FIELD_PREFIX, CustomFieldError, is_valid_field_name, parse_assignment, value_to_text,
+};
+use bones_core::model::due::{format_due, parse_due};
 use bones_core::model::item::Urgency;
+use bones_core::model::workflow::WorkflowStates;
+use bones_core::views::VIEW_COLUMNS;
+use chrono::{DateTime, Utc};
 use clap::Args;
 use serde::Serialize;
 use std::cmp::Ordering;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::io::Write;
 use std::str::FromStr;
 
+/// Default for `--limit`.
+pub const DEFAULT_LIST_LIMIT: usize = 50;
+
+/// Default for `--sort`.
+pub const DEFAULT_LIST_SORT: &str = "updated";
+
 #[derive(Args, Debug, Clone, Default)]
 pub struct ListArgs {
     /// Filter by state/status: open, doing, done, archived, blocked.
     /// Default: open (when no other filters are set).
     ///
-    /// May be repeated or comma-separated. `blocked` is a virtual status for
-    /// items with unresolved dependencies; item state remains open/doing/etc.
+    /// May be repeated or comma-separated. Sub-states declared under
+    /// `[states]` in `.bones/config.toml` (e.g. `review`) are accepted too.
+    /// `blocked` is a virtual status for items with unresolved dependencies
+    /// unless the project declares a `blocked` sub-state.
     #[arg(long, alias = "status", value_delimiter = ',')]
     pub state: Vec<String>,
 
@@ -78,20 +96,57 @@ pub struct ListArgs {
     #[arg(long)]
     pub until: Option<String>,
 
+    /// Filter to bones due at or before this date.
+    ///
+    /// Accepts YYYY-MM-DD, RFC3339, today, tomorrow, or an offset (3d, 2w).
+    #[arg(long, value_name = "when")]
+    pub due: Option<String>,
+
+    /// Filter by custom field value (may be repeated for AND semantics).
+    #[arg(long = "field", value_name = "name=value")]
+    pub field: Vec<String>,
+
+    /// Filter with a query, e.g. `label:api AND NOT assignee:* AND size>=M`.
+    ///
+    /// Supports AND/OR/NOT and parentheses, comparisons on size, urgency,
+    /// created, updated and due, `has:blocker`, `under:<goal>`, and free text.
+    #[arg(long = "where", value_name = "query")]
+    pub query: Option<String>,
+
     /// Maximum number of bones to show (0 = all).
-    #[arg(short = 'n', long, default_value = "50")]
+    #[arg(short = 'n', long, default_value_t = DEFAULT_LIST_LIMIT)]
     pub limit: usize,
 
     /// Pagination offset.
     #[arg(long, default_value = "0")]
     pub offset: usize,
 
-    /// Sort order: priority, created, updated, state.
+    /// Sort order: priority, created, updated, state, due, or
+    /// `custom.<name>` (prefix with `-` for descending).
     ///
     /// Legacy values are also accepted: `created_desc`, `created_asc`,
     /// `updated_desc`, `updated_asc`.
-    #[arg(long, default_value = "updated")]
+    #[arg(long, default_value = DEFAULT_LIST_SORT)]
     pub sort: String,
+
+    /// Table columns to show, comma-separated: id, kind, state, urgency,
+    /// size, assignees, labels, due, parent, updated, title.
+    #[arg(long, value_delimiter = ',', value_name = "columns")]
+    pub columns: Vec<String>,
+
+    /// Start from a saved view in `.bones/views.toml` (see `bn view`).
+    ///
+    /// Flags given on the command line override the view's; `--where` is
+    /// combined with the view's query.
+    #[arg(long, value_name = "name")]
+    pub view: Option<String>,
+
+    /// List bones as it was at a point in the past.
+    ///
+    /// Accepts YYYY-MM-DD (end of that UTC day), RFC3339, an offset into
+    /// the past (3d, 2w), or an event hash prefix (blake3:...).
+    #[arg(long, value_name = "when")]
+    pub at: Option<String>,
 }
 
 impl ListArgs {
@@ -112,6 +167,9 @@ pub struct ListItem {
     pub title: String,
     pub kind: String,
     pub state: String,
+    /// Configured sub-state within `state`, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub substate: Option<String>,
     pub urgency: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub size: Option<String>,
@@ -122,6 +180,12 @@ pub struct ListItem {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub assignees: Vec<String>,
     pub updated_at_us: i64,
+    /// Due date as an RFC3339 UTC timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due: Option<String>,
+    /// Custom field values, keyed by field name.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom: BTreeMap<String, serde_json::Value>,
 }
 
 /// Structured advice entry following the JSON envelope convention.
@@ -144,7 +208,7 @@ struct ListResponse {
     advice: Vec<Advice>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 enum ListSort {
     Priority,
     CreatedAsc,
@@ -153,21 +217,39 @@ enum ListSort {
     #[default]
     UpdatedDesc,
     State,
+    Due,
+    /// Custom field order, applied in SQL.
+    Field(CustomFieldSort),
 }
 
 impl FromStr for ListSort {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> anyhow::Result<Self> {
-        match s.trim().to_ascii_lowercase().as_str() {
+        let trimmed = s.trim();
+        let (descending, key) = trimmed
+            .strip_prefix('-')
+            .map_or((false, trimmed), |rest| (true, rest));
+        if let Some(name) = key.strip_prefix(CUSTOM_FIELD_PREFIX) {
+            if !is_valid_field_name(name) {
+                anyhow::bail!("{}", CustomFieldError::InvalidName { name: name.into() });
+            }
+            return Ok(Self::Field(CustomFieldSort {
+                name: name.to_string(),
+                descending,
+            }));
+        }
+
+        match trimmed.to_ascii_lowercase().as_str() {
             "priority" | "triage" => Ok(Self::Priority),
             "created" | "created_asc" | "created-asc" | "oldest" => Ok(Self::CreatedAsc),
             "created_desc" | "created-desc" | "newest" => Ok(Self::CreatedDesc),
             "updated" | "updated_desc" | "updated-desc" | "recent" => Ok(Self::UpdatedDesc),
             "updated_asc" | "updated-asc" | "stale" => Ok(Self::UpdatedAsc),
             "state" => Ok(Self::State),
+            "due" | "deadline" => Ok(Self::Due),
             other => anyhow::bail!(
-                "unknown sort order '{other}': expected one of priority, created, updated, state"
+                "unknown sort order '{other}': expected one of priority, created, updated, state, due, custom.<name>"
             ),
         }
     }
@@ -190,11 +272,9 @@ pub fn run_list(
     output: OutputMode,
     project_root: &std::path::Path,
 ) -> anyhow::Result<()> {
-    let db_path = project_root.join(".bones/bones.db");
-
     // Gracefully handle missing / corrupt projection
-    let conn = if let Some(c) = query::try_open_projection(&db_path)? {
-        c
+    let read = if let Some(read) = open_read_projection(project_root, args.at.as_deref(), output)? {
+        read
     } else {
         if output.is_json() {
             let response = ListResponse {
@@ -227,6 +307,25 @@ pub fn run_list(
         );
     };
 
+    let now = DateTime::<Utc>::from_timestamp_micros(read.now_us()).unwrap_or_else(Utc::now);
+    let conn = read.conn;
+
+    if let Some(column) = args
+        .columns
+        .iter()
+        .find(|c| !VIEW_COLUMNS.contains(&c.as_str()))
+    {
+        render_error(
+            output,
+            &CliError::with_details(
+                format!("unknown column '{column}'"),
+                format!("valid columns: {}", VIEW_COLUMNS.join(", ")),
+                "invalid_columns",
+            ),
+        )?;
+        anyhow::bail!("unknown column '{column}'");
+    }
+
     // Validate sort order
     let sort = match args.sort.parse::<ListSort>() {
         Ok(s) => s,
@@ -235,7 +334,7 @@ pub fn run_list(
                 output,
                 &CliError::with_details(
                     format!("invalid --sort value: {e}"),
-                    "valid values: priority, created, updated, state",
+                    "valid values: priority, created, updated, state, due, custom.<name>",
                     "invalid_sort_order",
                 ),
             )?;
@@ -243,9 +342,10 @@ pub fn run_list(
         }
     };
 
+    let config = load_project_config(project_root).unwrap_or_default();
     let statuses = normalized_statuses(args);
     for state in &statuses {
-        if state == "blocked" {
+        if state == "blocked" || config.states.contains_key(state) {
             continue;
         }
         if let Err(e) = validate::validate_state(state) {
@@ -347,27 +447,136 @@ pub fn run_list(
         anyhow::bail!("invalid date range");
     }
 
-    let response = build_list_response(&conn, args, &normalized_labels, sort, since_us, until_us)?;
+    let due_before_us = match args.due.as_deref() {
+        Some(raw) => match parse_due(raw, now) {
+            Ok(us) => Some(i64::try_from(us).unwrap_or(i64::MAX)),
+            Err(e) => {
+                render_error(
+                    output,
+                    &CliError::with_details(
+                        e.to_string(),
+                        "use YYYY-MM-DD, RFC3339, today, tomorrow, or an offset like 3d",
+                        "invalid_due",
+                    ),
+                )?;
+                anyhow::bail!("invalid --due value");
+            }
+        },
+        None => None,
+    };
+
+    let where_query = match args.query.as_deref() {
+        Some(raw) => match FilterQuery::parse_at(raw, now) {
+            Ok(query) => Some(query),
+            Err(e) => {
+                render_error(
+                    output,
+                    &CliError::with_details(
+                        format!("invalid --where query: {e}"),
+                        "e.g. --where 'label:api AND NOT assignee:* AND size>=M'",
+                        "invalid_where",
+                    ),
+                )?;
+                anyhow::bail!("invalid --where query");
+            }
+        },
+        None => None,
+    };
+
+    // Custom field filters compare against the canonical text stored in the
+    // projection, so `--field severity=03` still matches `3`.
+    let schema = &config.custom_fields;
+    let mut custom_filters = Vec::with_capacity(args.field.len());
+    for raw in &args.field {
+        let parsed = parse_assignment(schema, raw).and_then(|(name, value)| {
+            value_to_text(&value)
+                .map(|text| (name, text))
+                .ok_or_else(|| CustomFieldError::Malformed { input: raw.clone() })
+        });
+        match parsed {
+            Ok(filter) => custom_filters.push(filter),
+            Err(e) => {
+                render_error(
+                    output,
+                    &CliError::with_details(
+                        e.to_string(),
+                        "use --field name=value with a field declared under [custom_fields]",
+                        "invalid_custom_field",
+                    ),
+                )?;
+                anyhow::bail!("invalid --field value");
+            }
+        }
+    }
+    if let ListSort::Field(ref custom) = sort
+        && !schema.contains_key(&custom.name)
+    {
+        let e = CustomFieldError::UnknownField {
+            name: custom.name.clone(),
+        };
+        render_error(
+            output,
+            &CliError::with_details(
+                e.to_string(),
+                "sort by a field declared under [custom_fields]",
+                "invalid_sort_order",
+            ),
+        )?;
+        anyhow::bail!("invalid sort order: {e}");
+    }
+
+    let response = build_list_response(
+        &conn,
+        args,
+        &normalized_labels,
+        &custom_filters,
+        &config.states,
+        &sort,
+        since_us,
+        until_us,
+        due_before_us,
+        where_query.as_ref(),
+    )?;
 
     if output.is_json() {
         return render(output, &response, |_, _| Ok(()));
     }
 
+    if !args.columns.is_empty() {
+        return render_mode(
+            output,
+            &response,
+            |resp, w| render_list_columns(resp, &args.columns, false, w),
+            |resp, w| {
+                pretty_replay_note(read.replay, w)?;
+                render_list_columns(resp, &args.columns, true, w)
+            },
+        );
+    }
+
     render_mode(
         output,
         &response,
         |resp, w| render_list_text(resp, w),
-        |resp, w| render_list_human(resp, w),
+        |resp, w| {
+            pretty_replay_note(read.replay, w)?;
+            render_list_human(resp, w)
+        },
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 fn build_list_response(
     conn: &rusqlite::Connection,
     args: &ListArgs,
     all_labels: &[String],
-    sort: ListSort,
+    custom_filters: &[(String, String)],
+    workflow: &WorkflowStates,
+    sort: &ListSort,
     since_us: Option<i64>,
     until_us: Option<i64>,
+    due_before_us: Option<i64>,
+    where_query: Option<&FilterQuery>,
 ) -> anyhow::Result<ListResponse> {
     // Default to showing open items unless any filter is explicitly set.
     // Pagination/sort alone should not disable this default behavior.
@@ -381,28 +590,48 @@ fn build_list_response(
         || args.parent.is_some()
         || args.assignee.is_some()
         || since_us.is_some()
-        || until_us.is_some();
-
-    let state_filter = if !has_any_filter {
-        Some("open".to_string())
-    } else if statuses.len() == 1 && statuses[0] != "blocked" {
-        Some(statuses[0].clone())
+        || until_us.is_some()
+        || due_before_us.is_some()
+        || !custom_filters.is_empty()
+        || where_query.is_some();
+
+    // A declared `blocked` sub-state takes precedence over the virtual one.
+    let virtual_blocked = |status: &str| status == "blocked" && !workflow.contains_key(status);
+
+    let (state_filter, substate_filter) = if !has_any_filter {
+        (Some("open".to_string()), None)
+    } else if statuses.len() == 1 && !virtual_blocked(&statuses[0]) {
+        match workflow.get(&statuses[0]) {
+            Some(def) => (
+                Some(def.phase.as_str().to_string()),
+                Some(statuses[0].clone()),
+            ),
+            None => (Some(statuses[0].clone()), None),
+        }
     } else {
-        None
+        (None, None)
     };
 
     // Fetch an unpaginated set first, then apply deterministic sort + pagination
     // in Rust so metadata remains consistent even with composite label filters.
     let filter = ItemFilter {
         state: state_filter.clone(),
+        substate: substate_filter,
         kind: args.kind.clone(),
         urgency: args.urgency.clone(),
         label: all_labels.first().cloned(),
         parent_id: args.parent.clone(),
         assignee: args.assignee.clone(),
+        due_before: due_before_us,
+        custom_fields: custom_filters.to_vec(),
+        query: where_query.cloned(),
         limit: None,
         offset: None,
         sort: SortOrder::UpdatedDesc,
+        sort_custom: match sort {
+            ListSort::Field(custom) => Some(custom.clone()),
+            _ => None,
+        },
         ..Default::default()
     };
 
@@ -411,16 +640,21 @@ fn build_list_response(
     if !statuses.is_empty() && state_filter.is_none() {
         let state_statuses: HashSet<&str> = statuses
             .iter()
-            .filter_map(|status| (status != "blocked").then_some(status.as_str()))
+            .filter_map(|status| (!virtual_blocked(status)).then_some(status.as_str()))
             .collect();
-        let blocked_ids = if statuses.iter().any(|status| status == "blocked") {
+        let blocked_ids = if statuses.iter().any(|status| virtual_blocked(status)) {
             blocked_item_ids(conn)?
         } else {
             HashSet::new()
         };
 
         raw.retain(|item| {
-            state_statuses.contains(item.state.as_str()) || blocked_ids.contains(&item.item_id)
+            state_statuses.contains(item.state.as_str())
+                || item
+                    .substate
+                    .as_deref()
+                    .is_some_and(|sub| state_statuses.contains(sub))
+                || blocked_ids.contains(&item.item_id)
         });
     }
 
@@ -471,12 +705,18 @@ fn build_list_response(
                 title: qi.title.clone(),
                 kind: qi.kind.clone(),
                 state: qi.state.clone(),
+                substate: qi.substate.clone(),
                 urgency: qi.urgency.clone(),
                 size: qi.size.clone(),
                 parent_id: qi.parent_id.clone(),
                 labels,
                 assignees,
                 updated_at_us: qi.updated_at_us,
+                due: qi
+                    .due_at_us
+                    .and_then(|us| u64::try_from(us).ok())
+                    .map(format_due),
+                custom: query::get_custom_fields(conn, &qi.item_id).unwrap_or_default(),
             })
         })
         .collect();
@@ -504,11 +744,15 @@ fn build_list_response(
     })
 }
 
-fn sort_query_items(items: &mut [QueryItem], sort: ListSort) {
+fn sort_query_items(items: &mut [QueryItem], sort: &ListSort) {
+    // Custom field order comes from SQL; the filters above keep it stable.
+    if matches!(sort, ListSort::Field(_)) {
+        return;
+    }
     items.sort_by(|a, b| compare_query_items(a, b, sort));
 }
 
-fn compare_query_items(a: &QueryItem, b: &QueryItem, sort: ListSort) -> Ordering {
+fn compare_query_items(a: &QueryItem, b: &QueryItem, sort: &ListSort) -> Ordering {
     match sort {
         ListSort::Priority => urgency_rank(&a.urgency)
             .cmp(&urgency_rank(&b.urgency))
@@ -534,6 +778,13 @@ fn compare_query_items(a: &QueryItem, b: &QueryItem, sort: ListSort) -> Ordering
             .updated_at_us
             .cmp(&a.updated_at_us)
             .then_with(|| a.item_id.cmp(&b.item_id)),
+        ListSort::Due => a
+            .due_at_us
+            .is_none()
+            .cmp(&b.due_at_us.is_none())
+            .then_with(|| a.due_at_us.cmp(&b.due_at_us))
+            .then_with(|| a.item_id.cmp(&b.item_id)),
+        ListSort::Field(_) => Ordering::Equal,
     }
 }
 
@@ -640,14 +891,19 @@ fn render_list_human(resp: &ListResponse, w: &mut dyn Write) -> std::io::Result<
         };
 
         let title = truncate_title(&item.title, 40);
+        let due_suffix = item
+            .due
+            .as_deref()
+            .map(|due| format!("  (due {})", due.get(..10).unwrap_or(due)))
+            .unwrap_or_default();
 
         rows.push(vec![
             item.id.clone(),
             item.kind.clone(),
-            item.state.clone(),
+            display_state(item).to_string(),
             item.urgency.clone(),
             item.assignees.join(", "),
-            format!("{title}{labels_suffix}"),
+            format!("{title}{labels_suffix}{due_suffix}"),
         ]);
     }
 
@@ -664,6 +920,11 @@ fn render_list_human(resp: &ListResponse, w: &mut dyn Write) -> std::io::Result<
     Ok(())
 }
 
+/// State shown in table output: the sub-state when set, else the phase.
+fn display_state(item: &ListItem) -> &str {
+    item.substate.as_deref().unwrap_or(&item.state)
+}
+
 fn truncate_title(title: &str, max_chars: usize) -> String {
     if title.chars().count() <= max_chars {
         return title.to_string();
@@ -677,6 +938,82 @@ fn truncate_title(title: &str, max_chars: usize) -> String {
     out
 }
 
+/// Render the table with caller-chosen columns (`--columns` or a view).
+fn render_list_columns(
+    resp: &ListResponse,
+    columns: &[String],
+    pretty: bool,
+    w: &mut dyn Write,
+) -> std::io::Result<()> {
+    if resp.items.is_empty() {
+        return if pretty {
+            render_list_human(resp, w)
+        } else {
+            render_list_text(resp, w)
+        };
+    }
+
+    let headers: Vec<String> = columns.iter().map(|c| c.to_ascii_uppercase()).collect();
+    let rows: Vec<Vec<String>> = resp
+        .items
+        .iter()
+        .map(|item| {
+            columns
+                .iter()
+                .map(|column| column_value(item, column, pretty))
+                .collect()
+        })
+        .collect();
+
+    if pretty {
+        pretty_kv(
+            w,
+            "Showing",
+            format!("{} of {} bones", resp.showing, resp.total),
+        )?;
+        let header_refs: Vec<&str> = headers.iter().map(String::as_str).collect();
+        pretty_table(w, &header_refs, &rows)?;
+        for a in &resp.advice {
+            writeln!(w, "{}", a.message)?;
+        }
+    } else {
+        writeln!(w, "Showing {} of {} bones.", resp.showing, resp.total)?;
+        writeln!(w, "{}", headers.join("\t"))?;
+        for row in rows {
+            writeln!(w, "{}", row.join("\t"))?;
+        }
+        for a in &resp.advice {
+            writeln!(w, "advice  {}  {}", a.r#type, a.message)?;
+        }
+    }
+    Ok(())
+}
+
+fn column_value(item: &ListItem, column: &str, pretty: bool) -> String {
+    let list_sep = if pretty { ", " } else { "," };
+    match column {
+        "id" => item.id.clone(),
+        "kind" => item.kind.clone(),
+        "state" => display_state(item).to_string(),
+        "urgency" => item.urgency.clone(),
+        "size" => item.size.clone().unwrap_or_default(),
+        "assignees" => item.assignees.join(list_sep),
+        "labels" => item.labels.join(list_sep),
+        "due" => item
+            .due
+            .as_deref()
+            .map(|due| due.get(..10).unwrap_or(due).to_string())
+            .unwrap_or_default(),
+        "parent" => item.parent_id.clone().unwrap_or_default(),
+        "updated" => DateTime::<Utc>::from_timestamp_micros(item.updated_at_us)
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_default(),
+        "title" if pretty => truncate_title(&item.title, 60),
+        "title" => item.title.replace('\t', " "),
+        _ => String::new(),
+    }
+}
+
 fn render_list_text(resp: &ListResponse, w: &mut dyn Write) -> std::io::Result<()> {
     if resp.items.is_empty() {
         writeln!(w, "advice  no-items  bn create --title \"...\"")?;
@@ -699,7 +1036,7 @@ fn render_list_text(resp: &ListResponse, w: &mut dyn Write) -> std::io::Result<(
             "{}\t{}\t{}\t{}\t{}\t{}\t{}",
             item.id,
             item.kind,
-            item.state,
+            display_state(item),
             item.urgency,
             assignees,
             item.title.replace('\t', " "),
@@ -742,9 +1079,15 @@ mod tests {
             assignee: None,
             since: None,
             until: None,
+            due: None,
+            field: vec![],
             limit: 50,
             offset: 0,
             sort: "updated".into(),
+            query: None,
+            columns: vec![],
+            view: None,
+            at: None,
         }
     }
 
@@ -910,6 +1253,8 @@ mod tests {
                 search_labels: "".into(),
                 created_at_us: 100,
                 updated_at_us: 200,
+                due_at_us: None,
+                substate: None,
             },
             QueryItem {
                 item_id: "bn-aaa".into(),
@@ -926,10 +1271,12 @@ mod tests {
                 search_labels: "".into(),
                 created_at_us: 100,
                 updated_at_us: 200,
+                due_at_us: None,
+                substate: None,
             },
         ];
 
-        sort_query_items(&mut items, ListSort::UpdatedDesc);
+        sort_query_items(&mut items, &ListSort::UpdatedDesc);
         assert_eq!(items[0].item_id, "bn-aaa");
         assert_eq!(items[1].item_id, "bn-zzz");
     }
@@ -968,12 +1315,15 @@ mod tests {
             title: "Fix auth".into(),
             kind: "task".into(),
             state: "open".into(),
+            substate: None,
             urgency: "urgent".into(),
             size: None,
             parent_id: None,
             labels: vec!["backend".into()],
             assignees: vec!["alice".into()],
             updated_at_us: 1000,
+            due: None,
+            custom: BTreeMap::new(),
         }]);
         let mut buf = Vec::new();
         render_list_human(&resp, &mut buf).unwrap();
@@ -995,12 +1345,15 @@ mod tests {
             title: long_title.clone(),
             kind: "task".into(),
             state: "open".into(),
+            substate: None,
             urgency: "default".into(),
             size: None,
             parent_id: None,
             labels: vec![],
             assignees: vec![],
             updated_at_us: 1000,
+            due: None,
+            custom: BTreeMap::new(),
         }]);
         let mut buf = Vec::new();
         render_list_human(&resp, &mut buf).unwrap();
@@ -1018,12 +1371,15 @@ mod tests {
             title: long_title,
             kind: "task".into(),
             state: "open".into(),
+            substate: None,
             urgency: "default".into(),
             size: None,
             parent_id: None,
             labels: vec![],
             assignees: vec![],
             updated_at_us: 1000,
+            due: None,
+            custom: BTreeMap::new(),
         }]);
         let mut buf = Vec::new();
         render_list_human(&resp, &mut buf).unwrap();
@@ -1038,12 +1394,15 @@ mod tests {
             title: "Fix auth".into(),
             kind: "task".into(),
             state: "open".into(),
+            substate: None,
             urgency: "urgent".into(),
             size: None,
             parent_id: None,
             labels: vec!["backend".into()],
             assignees: vec![],
             updated_at_us: 1000,
+            due: None,
+            custom: BTreeMap::new(),
         }]);
         let mut buf = Vec::new();
         render_list_text(&resp, &mut buf).expect("render text");
@@ -1061,12 +1420,15 @@ mod tests {
                 title: "Fix auth".into(),
                 kind: "task".into(),
                 state: "open".into(),
+                substate: None,
                 urgency: "urgent".into(),
                 size: None,
                 parent_id: None,
                 labels: vec![],
                 assignees: vec![],
                 updated_at_us: 1000,
+                due: None,
+                custom: BTreeMap::new(),
             }],
             total: 75,
             showing: 1,
@@ -1161,7 +1523,11 @@ mod tests {
             &conn,
             &args,
             &args.all_labels(),
-            ListSort::CreatedAsc,
+            &[],
+            &WorkflowStates::new(),
+            &ListSort::CreatedAsc,
+            None,
+            None,
             None,
             None,
         )
@@ -1188,9 +1554,13 @@ mod tests {
             &conn,
             &args,
             &args.all_labels(),
-            ListSort::UpdatedDesc,
+            &[],
+            &WorkflowStates::new(),
+            &ListSort::UpdatedDesc,
             Some(2000),
             Some(2001),
+            None,
+            None,
         )
         .unwrap();
         assert_eq!(response.total, 1);
@@ -1200,14 +1570,221 @@ mod tests {
             &conn,
             &args,
             &args.all_labels(),
-            ListSort::UpdatedDesc,
+            &[],
+            &WorkflowStates::new(),
+            &ListSort::UpdatedDesc,
             Some(2002),
             None,
+            None,
+            None,
         )
         .unwrap();
         assert_eq!(response_none.total, 0);
     }
 
+    #[test]
+    fn build_list_response_filters_and_sorts_by_due() {
+        let (_dir, root) = setup_test_db();
+        let db_path = root.join(".bones/bones.db");
+        let conn = Connection::open(db_path).unwrap();
+        conn.execute_batch(
+            "UPDATE items SET due_at_us = 1773619199000000 WHERE item_id = 'bn-001';
+             UPDATE items SET due_at_us = 1773000000000000 WHERE item_id = 'bn-002';",
+        )
+        .unwrap();
+
+        let args = default_args();
+        let response = build_list_response(
+            &conn,
+            &args,
+            &args.all_labels(),
+            &[],
+            &WorkflowStates::new(),
+            &ListSort::Due,
+            None,
+            None,
+            Some(1_773_619_199_000_000),
+            None,
+        )
+        .unwrap();
+        let ids: Vec<&str> = response.items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, ["bn-002", "bn-001"]);
+        assert_eq!(
+            response.items[1].due.as_deref(),
+            Some("2026-03-15T23:59:59Z")
+        );
+
+        let response = build_list_response(
+            &conn,
+            &args,
+            &args.all_labels(),
+            &[],
+            &WorkflowStates::new(),
+            &ListSort::Due,
+            None,
+            None,
+            Some(1_773_000_000_000_000),
+            None,
+        )
+        .unwrap();
+        assert_eq!(response.total, 1);
+        assert_eq!(response.items[0].id, "bn-002");
+    }
+
+    #[test]
+    fn build_list_response_filters_and_sorts_by_custom_field() {
+        let (_dir, root) = setup_test_db();
+        let db_path = root.join(".bones/bones.db");
+        let conn = Connection::open(db_path).unwrap();
+        conn.execute_batch(
+            "INSERT INTO item_custom_fields VALUES ('bn-001', 'severity', '2', 2, 1);
+             INSERT INTO item_custom_fields VALUES ('bn-002', 'severity', '10', 10, 1);",
+        )
+        .unwrap();
+
+        let mut args = default_args();
+        args.all_states = true;
+        let sort = ListSort::Field(CustomFieldSort {
+            name: "severity".into(),
+            descending: true,
+        });
+        let response = build_list_response(
+            &conn,
+            &args,
+            &[],
+            &[],
+            &WorkflowStates::new(),
+            &sort,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let ids: Vec<&str> = response.items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids[..2], ["bn-002", "bn-001"]);
+        assert_eq!(response.items[0].custom["severity"], serde_json::json!(10));
+
+        let filters = vec![("severity".to_string(), "2".to_string())];
+        let response = build_list_response(
+            &conn,
+            &default_args(),
+            &[],
+            &filters,
+            &WorkflowStates::new(),
+            &ListSort::UpdatedDesc,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(response.total, 1);
+        assert_eq!(response.items[0].id, "bn-001");
+    }
+
+    #[test]
+    fn build_list_response_applies_where_query() {
+        let (_dir, root) = setup_test_db();
+        let conn = Connection::open(root.join(".bones/bones.db")).unwrap();
+        let ids = |raw: &str| {
+            let query = FilterQuery::parse(raw).unwrap();
+            let args = default_args();
+            let response = build_list_response(
+                &conn,
+                &args,
+                &[],
+                &[],
+                &WorkflowStates::new(),
+                &ListSort::UpdatedAsc,
+                None,
+                None,
+                None,
+                Some(&query),
+            )
+            .unwrap();
+            response
+                .items
+                .into_iter()
+                .map(|item| item.id)
+                .collect::<Vec<_>>()
+        };
+
+        // A query replaces the implicit open-only default.
+        assert_eq!(ids("urgency>=default"), ["bn-001", "bn-002"]);
+        assert_eq!(ids("kind:goal OR label:auth"), ["bn-001", "bn-003"]);
+        assert_eq!(ids("NOT label:* AND -state:done"), ["bn-002"]);
+    }
+
+    #[test]
+    fn build_list_response_filters_by_substate() {
+        use bones_core::model::item::State;
+        use bones_core::model::workflow::SubStateDef;
+
+        let (_dir, root) = setup_test_db();
+        let conn = Connection::open(root.join(".bones/bones.db")).unwrap();
+        conn.execute_batch(
+            "INSERT INTO items (item_id, title, kind, state, substate, urgency, is_deleted, \
+             search_labels, created_at_us, updated_at_us) \
+             VALUES ('bn-004', 'In review', 'task', 'doing', 'review', 'default', 0, '', 1003, 2003)",
+        )
+        .unwrap();
+        let mut workflow = WorkflowStates::new();
+        workflow.insert(
+            "review".into(),
+            SubStateDef {
+                phase: State::Doing,
+                from: vec![],
+                to: vec![],
+            },
+        );
+        let ids = |states: &[&str]| {
+            let mut args = default_args();
+            args.state = states.iter().map(ToString::to_string).collect();
+            let response = build_list_response(
+                &conn,
+                &args,
+                &[],
+                &[],
+                &workflow,
+                &ListSort::UpdatedAsc,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+            response
+                .items
+                .into_iter()
+                .map(|item| (item.id, item.substate))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids(&["review"]), [("bn-004".into(), Some("review".into()))]);
+        // The phase still matches items in its sub-states.
+        assert_eq!(ids(&["doing"]).len(), 2);
+        assert_eq!(
+            ids(&["open", "review"]),
+            [
+                ("bn-001".into(), None),
+                ("bn-004".into(), Some("review".into()))
+            ]
+        );
+    }
+
+    #[test]
+    fn list_sort_parses_custom_fields() {
+        assert_eq!(
+            "-custom.severity".parse::<ListSort>().unwrap(),
+            ListSort::Field(CustomFieldSort {
+                name: "severity".into(),
+                descending: true,
+            })
+        );
+        assert!("custom.Bad Name".parse::<ListSort>().is_err());
+    }
+
     #[test]
     fn build_list_response_all_includes_non_open_states() {
         let (_dir, root) = setup_test_db();
@@ -1221,7 +1798,11 @@ mod tests {
             &conn,
             &args,
             &args.all_labels(),
-            ListSort::UpdatedDesc,
+            &[],
+            &WorkflowStates::new(),
+            &ListSort::UpdatedDesc,
+            None,
+            None,
             None,
             None,
         )
@@ -1303,12 +1884,15 @@ mod tests {
                 title: "Test item".into(),
                 kind: "task".into(),
                 state: "open".into(),
+                substate: None,
                 urgency: "default".into(),
                 size: None,
                 parent_id: None,
                 labels: vec!["auth".into()],
                 assignees: vec![],
                 updated_at_us: 1000,
+                due: None,
+                custom: BTreeMap::new(),
             }],
             total: 1,
             showing: 1,
@@ -1368,12 +1952,15 @@ mod tests {
             title: "Test item".into(),
             kind: "task".into(),
             state: "open".into(),
+            substate: None,
             urgency: "default".into(),
             size: None,
             parent_id: None,
             labels: vec!["auth".into()],
             assignees: vec!["bob".into()],
             updated_at_us: 1000,
+            due: None,
+            custom: BTreeMap::new(),
         };
         let json = serde_json::to_string(&item).unwrap();
         assert!(json.contains("bn-001"));
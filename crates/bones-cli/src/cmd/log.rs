@@ -2,6 +2,7 @@
 
 use crate::output::{CliError, OutputMode, render, render_error};
 use anyhow::Context;
+use bones_core::crdt::text::TextOp;
 use bones_core::event::Event;
 use bones_core::event::data::EventData;
 use bones_core::event::parser::{ParsedLine, PartialParsedLine, parse_line, parse_line_partial};
@@ -59,8 +60,7 @@ struct EventRow {
     pub agent: String,
     pub event_type: String,
     pub summary: String,
-    #[serde(skip_serializing)]
-    sort_event_hash: String,
+    pub event_hash: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -165,13 +165,42 @@ fn event_summary(event: &Event) -> String {
             None => format!("state={}", data.state),
         },
         EventData::Assign(data) => format!("{} {}", data.action, data.agent),
-        EventData::Comment(data) => format!("comment {}", truncate(&data.body, 64)),
+        EventData::Comment(data) => match &data.reply_to {
+            Some(parent) => format!(
+                "reply to {} {}",
+                truncate(parent, 15),
+                truncate(&data.body, 48)
+            ),
+            None => format!("comment {}", truncate(&data.body, 64)),
+        },
+        EventData::CommentEdit(data) => format!(
+            "edit comment {} {}",
+            truncate(&data.comment, 15),
+            truncate(&data.body, 48)
+        ),
+        EventData::CommentRetract(data) => match &data.reason {
+            Some(reason) => format!("retract comment {} ({reason})", truncate(&data.comment, 15)),
+            None => format!("retract comment {}", truncate(&data.comment, 15)),
+        },
+        EventData::Attach(data) => format!(
+            "attach {} ({}, {} bytes)",
+            truncate(&data.name, 48),
+            data.mime,
+            data.size
+        ),
         EventData::Link(data) => format!("link {} -> {}", data.link_type, data.target),
         EventData::Unlink(data) => format!("unlink {}", data.target),
         EventData::Delete(_) => "delete".to_string(),
         EventData::Compact(data) => format!("compact {}", truncate(&data.summary, 64)),
         EventData::Snapshot(_) => "snapshot".to_string(),
         EventData::Redact(data) => format!("redact {} ({})", data.target_hash, data.reason),
+        EventData::Patch(data) => {
+            let (inserted, deleted) = data.ops.iter().fold((0, 0), |(ins, del), op| match op {
+                TextOp::Insert { text, .. } => (ins + text.chars().count() as u64, del),
+                TextOp::Delete { len, .. } => (ins, del + len),
+            });
+            format!("edit description +{inserted} -{deleted} chars")
+        }
     }
 }
 
@@ -202,7 +231,7 @@ fn to_event_row(event: &Event) -> EventRow {
         agent: event.agent.clone(),
         event_type: event.event_type.as_str().to_string(),
         summary: event_summary(event),
-        sort_event_hash: event.event_hash.clone(),
+        event_hash: event.event_hash.clone(),
     }
 }
 
@@ -211,7 +240,7 @@ fn sort_rows_asc(rows: &mut [EventRow]) {
         a.timestamp_us
             .cmp(&b.timestamp_us)
             .then_with(|| a.agent.cmp(&b.agent))
-            .then_with(|| a.sort_event_hash.cmp(&b.sort_event_hash))
+            .then_with(|| a.event_hash.cmp(&b.event_hash))
     });
 }
 
@@ -451,6 +480,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         });
 
         let ts2 = shard_mgr.next_timestamp().expect("ts2");
@@ -464,9 +494,11 @@ mod tests {
             data: EventData::Move(MoveData {
                 state: State::Doing,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         });
 
         let ts3 = shard_mgr.next_timestamp().expect("ts3");
@@ -489,6 +521,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         });
 
         let ts4 = shard_mgr.next_timestamp().expect("ts4");
@@ -505,6 +538,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         });
 
         dir
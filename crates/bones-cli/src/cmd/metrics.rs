@@ -0,0 +1,239 @@
+//! `bn metrics` — flow metrics from the state-transition history.
+//!
+//! # Usage
+//!
+//! ```text
+//! bn metrics flow
+//! bn metrics flow --by kind --weeks 4
+//! bn metrics flow --by label --where 'kind:bug'
+//! ```
+
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+
+use bones_core::db::filter_query::FilterQuery;
+use bones_core::db::query::{self, ItemFilter};
+use bones_triage::flow::{self, DurationStats, FlowGroup, FlowSlice};
+use chrono::{DateTime, Utc};
+use clap::{Args, Subcommand, ValueEnum};
+use serde::Serialize;
+
+use crate::cmd::view::view_query;
+use crate::output::{CliError, OutputMode, render, render_error};
+
+#[derive(Args, Debug)]
+pub struct MetricsArgs {
+    #[command(subcommand)]
+    pub command: MetricsCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum MetricsCommand {
+    /// Cycle time, lead time, throughput and WIP over recent weeks
+    Flow(FlowArgs),
+}
+
+/// Dimension for `bn metrics flow --by`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SliceArg {
+    Kind,
+    Label,
+    Assignee,
+    Goal,
+}
+
+impl From<SliceArg> for FlowSlice {
+    fn from(value: SliceArg) -> Self {
+        match value {
+            SliceArg::Kind => Self::Kind,
+            SliceArg::Label => Self::Label,
+            SliceArg::Assignee => Self::Assignee,
+            SliceArg::Goal => Self::Goal,
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct FlowArgs {
+    /// Number of whole weeks, ending now, to report on
+    #[arg(long, default_value_t = 12, value_parser = clap::value_parser!(u32).range(1..=520))]
+    pub weeks: u32,
+
+    /// Report one group per kind, label, assignee or goal
+    #[arg(long, value_enum)]
+    pub by: Option<SliceArg>,
+
+    /// Only count bones matching a query (same syntax as `bn list --where`)
+    #[arg(long = "where", value_name = "query")]
+    pub query: Option<String>,
+
+    /// Only count bones in a saved view (see `bn view`)
+    #[arg(long, value_name = "name")]
+    pub view: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct FlowOutput {
+    weeks: u32,
+    by: Option<SliceArg>,
+    filter: Option<String>,
+    window_start_us: i64,
+    now_us: i64,
+    groups: Vec<FlowGroup>,
+}
+
+/// Execute `bn metrics`.
+///
+/// # Errors
+///
+/// Returns an error if the projection cannot be read or the filter is
+/// invalid.
+pub fn run_metrics(
+    args: &MetricsArgs,
+    output: OutputMode,
+    project_root: &Path,
+) -> anyhow::Result<()> {
+    match &args.command {
+        MetricsCommand::Flow(flow_args) => run_flow(flow_args, output, project_root),
+    }
+}
+
+fn run_flow(args: &FlowArgs, output: OutputMode, project_root: &Path) -> anyhow::Result<()> {
+    let db_path = project_root.join(".bones/bones.db");
+    let conn = if let Some(conn) = query::try_open_projection(&db_path)? {
+        conn
+    } else {
+        render_error(
+            output,
+            &CliError::with_details(
+                "projection database not found",
+                "run `bn admin rebuild` to initialize the projection",
+                "projection_missing",
+            ),
+        )?;
+        anyhow::bail!("projection not found");
+    };
+
+    let scope = view_query(
+        args.view.as_deref(),
+        args.query.as_deref(),
+        project_root,
+        output,
+    )?;
+    let mut items = flow::load_flow_items(&conn)?;
+    if let Some(raw) = scope.as_deref() {
+        let where_query = match FilterQuery::parse(raw) {
+            Ok(query) => query,
+            Err(e) => {
+                render_error(
+                    output,
+                    &CliError::with_details(
+                        format!("invalid --where query: {e}"),
+                        "e.g. --where 'kind:bug AND label:api'",
+                        "invalid_where",
+                    ),
+                )?;
+                anyhow::bail!("invalid --where query");
+            }
+        };
+        let ids: HashSet<String> = query::list_items(
+            &conn,
+            &ItemFilter {
+                query: Some(where_query),
+                ..Default::default()
+            },
+        )?
+        .into_iter()
+        .map(|item| item.item_id)
+        .collect();
+        items.retain(|item| ids.contains(&item.id));
+    }
+
+    let now_us = Utc::now().timestamp_micros();
+    let groups = flow::flow_metrics(&items, now_us, args.weeks, args.by.map(Into::into));
+    let out = FlowOutput {
+        weeks: args.weeks,
+        by: args.by,
+        filter: scope,
+        window_start_us: flow::window_start_us(now_us, args.weeks),
+        now_us,
+        groups,
+    };
+    render(output, &out, render_flow_human)
+}
+
+fn render_flow_human(out: &FlowOutput, w: &mut dyn Write) -> std::io::Result<()> {
+    if out.groups.is_empty() {
+        return writeln!(w, "No bones match.");
+    }
+    for (idx, group) in out.groups.iter().enumerate() {
+        if idx > 0 {
+            writeln!(w)?;
+        }
+        writeln!(
+            w,
+            "{}: {} completed in the last {} week(s)",
+            group.group, group.completed, out.weeks
+        )?;
+        write_durations(w, "lead time", group.lead_time.as_ref())?;
+        write_durations(w, "cycle time", group.cycle_time.as_ref())?;
+        writeln!(w, "  {:<12} {:>5} {:>5}", "week of", "done", "wip")?;
+        for week in &group.weeks {
+            writeln!(
+                w,
+                "  {:<12} {:>5} {:>5}",
+                format_date(week.start_us),
+                week.throughput,
+                week.wip
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn write_durations(
+    w: &mut dyn Write,
+    label: &str,
+    stats: Option<&DurationStats>,
+) -> std::io::Result<()> {
+    let Some(stats) = stats else {
+        return writeln!(w, "  {label:<11} -");
+    };
+    writeln!(
+        w,
+        "  {label:<11} p50 {}  p85 {}  p95 {}  mean {}  max {}  (n={})",
+        format_days(stats.p50_days),
+        format_days(stats.p85_days),
+        format_days(stats.p95_days),
+        format_days(stats.mean_days),
+        format_days(stats.max_days),
+        stats.count
+    )
+}
+
+/// Days, or hours when under a day.
+pub fn format_days(days: f64) -> String {
+    if days < 1.0 {
+        format!("{:.1}h", days * 24.0)
+    } else {
+        format!("{days:.1}d")
+    }
+}
+
+fn format_date(us: i64) -> String {
+    DateTime::<Utc>::from_timestamp_micros(us)
+        .map_or_else(String::new, |dt| dt.format("%Y-%m-%d").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_days_switches_to_hours_under_a_day() {
+        assert_eq!(format_days(0.5), "12.0h");
+        assert_eq!(format_days(2.5), "2.5d");
+    }
+}
@@ -295,6 +295,7 @@ pub fn run_migrate(args: &MigrateArgs, output: OutputMode, project_root: &Path)
             item_id: item_id.clone(),
             data: EventData::Create(create),
             event_hash: String::new(),
+            signature: None,
         };
         append_event(project_root, &shard_manager, &mut create_event)?;
         previous_hash.insert(item_id.to_string(), create_event.event_hash.clone());
@@ -321,6 +322,7 @@ pub fn run_migrate(args: &MigrateArgs, output: OutputMode, project_root: &Path)
                     extra: BTreeMap::new(),
                 }),
                 event_hash: String::new(),
+                signature: None,
             };
             append_event(project_root, &shard_manager, &mut assign_event)?;
             previous_hash.insert(item_id.to_string(), assign_event.event_hash.clone());
@@ -348,9 +350,11 @@ pub fn run_migrate(args: &MigrateArgs, output: OutputMode, project_root: &Path)
                 data: EventData::Move(MoveData {
                     state,
                     reason: Some("Imported from beads".to_string()),
+                    substate: None,
                     extra: BTreeMap::new(),
                 }),
                 event_hash: String::new(),
+                signature: None,
             };
             append_event(project_root, &shard_manager, &mut move_event)?;
             previous_hash.insert(item_id.to_string(), move_event.event_hash.clone());
@@ -372,9 +376,11 @@ pub fn run_migrate(args: &MigrateArgs, output: OutputMode, project_root: &Path)
                     item_id: item_id.clone(),
                     data: EventData::Comment(CommentData {
                         body: comment.body.clone(),
+                        reply_to: None,
                         extra: BTreeMap::new(),
                     }),
                     event_hash: String::new(),
+                    signature: None,
                 };
                 append_event(project_root, &shard_manager, &mut comment_event)?;
                 previous_hash.insert(item_id.to_string(), comment_event.event_hash.clone());
@@ -415,6 +421,7 @@ pub fn run_migrate(args: &MigrateArgs, output: OutputMode, project_root: &Path)
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
 
         let source_ts = source
@@ -95,9 +95,15 @@ mod tests {
                 parent: None,
                 since: None,
                 until: None,
+                due: None,
+                field: vec![],
                 limit: 50,
                 offset: 0,
                 sort: "updated".to_string(),
+                query: None,
+                columns: vec![],
+                view: None,
+                at: None,
             },
         };
 
@@ -143,9 +149,15 @@ mod tests {
                 parent: None,
                 since: None,
                 until: None,
+                due: None,
+                field: vec![],
                 limit: 50,
                 offset: 0,
                 sort: "updated".to_string(),
+                query: None,
+                columns: vec![],
+                view: None,
+                at: None,
             },
         };
 
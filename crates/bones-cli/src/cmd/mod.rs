@@ -1,13 +1,17 @@
 pub mod agents;
 pub mod archive;
+pub mod as_of;
 pub mod assign;
+pub mod attach;
 pub mod bones_gitattributes;
 pub mod bones_gitignore;
+pub mod bundle;
 pub mod close;
 pub mod comment;
 pub mod compact;
 pub mod completions;
 pub mod config;
+pub mod conflicts;
 pub mod context;
 pub mod create;
 pub mod cycles;
@@ -15,6 +19,7 @@ pub mod dedup;
 pub mod delete;
 pub mod dep;
 pub mod diagnose;
+pub mod diff;
 pub mod do_cmd;
 pub mod doctor;
 pub mod done;
@@ -25,9 +30,11 @@ pub mod graph;
 pub mod health;
 pub mod import;
 pub mod init;
+pub mod keys;
 pub mod labels;
 pub mod list;
 pub mod log;
+pub mod metrics;
 pub mod migrate;
 pub mod migrate_format;
 pub mod mine;
@@ -35,7 +42,9 @@ pub mod move_cmd;
 pub mod next;
 pub mod plan;
 pub mod progress;
+pub mod query;
 pub mod rebuild;
+pub mod redact;
 pub mod redact_verify;
 pub mod reopen;
 pub mod search;
@@ -44,6 +53,7 @@ pub mod sim;
 pub mod similar;
 pub mod stats;
 pub mod status;
+pub mod sync;
 pub mod tag;
 pub mod triage;
 pub mod triage_support;
@@ -52,6 +62,7 @@ pub mod unstart;
 pub mod update;
 pub mod urgency;
 pub mod verify;
+pub mod view;
 pub mod warm_search;
 
 fn open_projection_for_mutation(
@@ -1,14 +1,22 @@
-//! `bn move` — reparent a bone under a different goal.
+//! `bn move` — reparent a bone under a different goal, or move it to a
+//! workflow state.
+//!
+//! State moves accept the built-in states and any sub-state declared under
+//! `[states]` in `.bones/config.toml` (see [`bones_core::model::workflow`]).
 
 use crate::agent;
+use crate::author::write_authored_event;
 use crate::itc_state::assign_next_itc;
 use crate::output::{CliError, OutputMode, render, render_error};
 use crate::validate;
+use bones_core::config::load_project_config;
+use bones_core::conflicts::observed_parents;
 use bones_core::db::query::{get_item, try_open_projection};
-use bones_core::event::data::UpdateData;
-use bones_core::event::writer::write_event;
+use bones_core::event::data::{MoveData, UpdateData};
 use bones_core::event::{Event, EventData, EventType};
+use bones_core::model::item::State;
 use bones_core::model::item_id::ItemId;
+use bones_core::model::workflow::{self, WorkflowState};
 use bones_core::shard::ShardManager;
 use clap::Args;
 use rusqlite::Connection;
@@ -22,9 +30,18 @@ pub struct MoveArgs {
     /// Bone ID to move.
     pub id: String,
 
+    /// Target state: open, doing, done, archived, or a sub-state declared
+    /// under [states] in .bones/config.toml.
+    #[arg(required_unless_present = "parent", conflicts_with = "parent")]
+    pub state: Option<String>,
+
     /// New parent bone ID. Use "--parent none" to make top-level.
     #[arg(long)]
-    pub parent: String,
+    pub parent: Option<String>,
+
+    /// Reason recorded with a state move.
+    #[arg(long, requires = "state")]
+    pub reason: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -37,6 +54,19 @@ struct MoveOutput {
     parent_id: Option<String>,
 }
 
+#[derive(Debug, Serialize)]
+struct StateMoveOutput {
+    schema_version: u32,
+    ok: bool,
+    id: String,
+    item_id: String,
+    previous_state: String,
+    state: String,
+    /// Lifecycle phase the new state maps onto.
+    phase: String,
+    event_hash: String,
+}
+
 /// Open the projection DB, returning a helpful error if it doesn't exist.
 fn open_db(project_root: &std::path::Path) -> anyhow::Result<Connection> {
     let db_path = project_root.join(".bones").join("bones.db");
@@ -57,19 +87,24 @@ fn emit_parent_event(
     item_id: &ItemId,
     new_parent: Option<&str>,
 ) -> anyhow::Result<()> {
-    let bones_dir = project_root.join(".bones");
-    let shard_mgr = ShardManager::new(&bones_dir);
+    let db_path = project_root.join(".bones").join("bones.db");
+
+    // Supersede every parent value this replica has seen.
+    let parents = match try_open_projection(&db_path)? {
+        Some(conn) => observed_parents(&conn, item_id.as_str(), "parent")?,
+        None => vec![],
+    };
 
     let parent_value = match new_parent {
         Some(p) => json!(p),
         None => json!(null),
     };
 
-    let mut event = Event {
+    let event = Event {
         wall_ts_us: 0,
         agent: agent.to_string(),
         itc: String::new(),
-        parents: vec![],
+        parents,
         event_type: EventType::Update,
         item_id: item_id.clone(),
         data: EventData::Update(UpdateData {
@@ -78,8 +113,46 @@ fn emit_parent_event(
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
 
+    append_and_project(project_root, event).map(|_| ())
+}
+
+/// Emit an `item.move` event for a workflow state and project it inline.
+fn emit_state_event(
+    project_root: &std::path::Path,
+    agent: &str,
+    item_id: &ItemId,
+    target: &WorkflowState,
+    reason: Option<&str>,
+) -> anyhow::Result<Event> {
+    let event = Event {
+        wall_ts_us: 0,
+        agent: agent.to_string(),
+        itc: String::new(),
+        parents: vec![],
+        event_type: EventType::Move,
+        item_id: item_id.clone(),
+        data: EventData::Move(MoveData {
+            state: target.phase,
+            substate: target.substate.clone(),
+            reason: reason.map(str::to_string),
+            extra: BTreeMap::new(),
+        }),
+        event_hash: String::new(),
+        signature: None,
+    };
+
+    append_and_project(project_root, event)
+}
+
+/// Append `event` to the active shard under the shard lock, then project it.
+fn append_and_project(project_root: &std::path::Path, mut event: Event) -> anyhow::Result<Event> {
+    let bones_dir = project_root.join(".bones");
+    let shard_mgr = ShardManager::new(&bones_dir);
+    let db_path = bones_dir.join("bones.db");
+
     {
         use bones_core::lock::ShardLock;
         let lock_path = shard_mgr.lock_path();
@@ -96,7 +169,7 @@ fn emit_parent_event(
 
         assign_next_itc(project_root, &mut event)?;
 
-        let line = write_event(&mut event)
+        let line = write_authored_event(&mut event)
             .map_err(|e| anyhow::anyhow!("failed to serialize event: {e}"))?;
 
         shard_mgr
@@ -105,7 +178,6 @@ fn emit_parent_event(
     }
 
     // Apply inline so the projection reflects the move immediately.
-    let db_path = bones_dir.join("bones.db");
     if let Some(conn) = try_open_projection(&db_path)? {
         let projector = bones_core::db::project::Projector::new(&conn);
         if let Err(e) = projector.project_event(&event) {
@@ -113,7 +185,7 @@ fn emit_parent_event(
         }
     }
 
-    Ok(())
+    Ok(event)
 }
 
 pub fn run_move(
@@ -146,16 +218,28 @@ pub fn run_move(
     let item_id = ItemId::parse(&args.id)
         .map_err(|e| anyhow::anyhow!("invalid item ID '{}': {}", args.id, e))?;
 
+    if let Some(ref state) = args.state {
+        return run_state_move(
+            &item_id,
+            state,
+            args.reason.as_deref(),
+            &agent,
+            output,
+            project_root,
+        );
+    }
+    let parent = args.parent.as_deref().unwrap_or("none");
+
     // Determine new parent: "none" means top-level (null parent)
-    let new_parent: Option<ItemId> = if args.parent.to_lowercase() == "none" {
+    let new_parent: Option<ItemId> = if parent.to_lowercase() == "none" {
         None
     } else {
-        if let Err(e) = validate::validate_item_id(&args.parent) {
+        if let Err(e) = validate::validate_item_id(parent) {
             render_error(output, &e.to_cli_error())?;
             anyhow::bail!("{}", e.reason);
         }
-        let parent_id = ItemId::parse(&args.parent)
-            .map_err(|e| anyhow::anyhow!("invalid parent ID '{}': {}", args.parent, e))?;
+        let parent_id = ItemId::parse(parent)
+            .map_err(|e| anyhow::anyhow!("invalid parent ID '{parent}': {e}"))?;
         Some(parent_id)
     };
 
@@ -232,6 +316,108 @@ pub fn run_move(
     Ok(())
 }
 
+fn run_state_move(
+    item_id: &ItemId,
+    state: &str,
+    reason: Option<&str>,
+    agent: &str,
+    output: OutputMode,
+    project_root: &std::path::Path,
+) -> anyhow::Result<()> {
+    let config = match load_project_config(project_root) {
+        Ok(config) => config,
+        Err(e) => {
+            render_error(output, &CliError::new(format!("{e:#}")))?;
+            return Err(e);
+        }
+    };
+
+    let target = match workflow::resolve_state(&config.states, state) {
+        Ok(target) => target,
+        Err(e) => {
+            render_error(
+                output,
+                &CliError::with_details(
+                    e.to_string(),
+                    "Declare custom states under [states] in .bones/config.toml",
+                    "unknown_state",
+                ),
+            )?;
+            anyhow::bail!("{e}");
+        }
+    };
+
+    let conn = match open_db(project_root) {
+        Ok(c) => c,
+        Err(e) => {
+            render_error(output, &CliError::new(e.to_string()))?;
+            return Err(e);
+        }
+    };
+
+    let Some(item) = get_item(&conn, item_id.as_str(), false)? else {
+        let err = anyhow::anyhow!("item not found: {}", item_id.as_str());
+        render_error(output, &CliError::new(err.to_string()))?;
+        return Err(err);
+    };
+    let phase: State = item.state.parse().map_err(|_| {
+        anyhow::anyhow!("item '{}' has invalid state '{}'", item.item_id, item.state)
+    })?;
+    let current = WorkflowState {
+        phase,
+        substate: item.substate,
+    };
+
+    if let Err(e) = workflow::check_transition(&config.states, &current, &target) {
+        render_error(
+            output,
+            &CliError::with_details(
+                e.to_string(),
+                "Check allowed transitions under [states] in .bones/config.toml",
+                "invalid_transition",
+            ),
+        )?;
+        anyhow::bail!("{e}");
+    }
+
+    let event = match emit_state_event(project_root, agent, item_id, &target, reason) {
+        Ok(event) => event,
+        Err(e) => {
+            render_error(output, &CliError::new(e.to_string()))?;
+            return Err(e);
+        }
+    };
+
+    let val = StateMoveOutput {
+        schema_version: 1,
+        ok: true,
+        id: item_id.as_str().to_string(),
+        item_id: item_id.as_str().to_string(),
+        previous_state: current.name().to_string(),
+        state: target.name().to_string(),
+        phase: target.phase.to_string(),
+        event_hash: event.event_hash,
+    };
+
+    render(output, &val, |v, w| {
+        if v.state == v.phase {
+            writeln!(
+                w,
+                "Moved {} from {} to {}",
+                v.item_id, v.previous_state, v.state
+            )
+        } else {
+            writeln!(
+                w,
+                "Moved {} from {} to {} ({})",
+                v.item_id, v.previous_state, v.state, v.phase
+            )
+        }
+    })?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -247,7 +433,15 @@ mod tests {
         }
         let w = Wrapper::parse_from(["test", "item-1", "--parent", "goal-1"]);
         assert_eq!(w.args.id, "item-1");
-        assert_eq!(w.args.parent, "goal-1");
+        assert_eq!(w.args.parent.as_deref(), Some("goal-1"));
+        assert!(w.args.state.is_none());
+
+        let w = Wrapper::parse_from(["test", "item-1", "review", "--reason", "ready"]);
+        assert_eq!(w.args.state.as_deref(), Some("review"));
+        assert_eq!(w.args.reason.as_deref(), Some("ready"));
+
+        assert!(Wrapper::try_parse_from(["test", "item-1"]).is_err());
+        assert!(Wrapper::try_parse_from(["test", "item-1", "review", "--parent", "g"]).is_err());
     }
 
     #[test]
@@ -260,7 +454,7 @@ mod tests {
             args: MoveArgs,
         }
         let w = Wrapper::parse_from(["test", "item-1", "--parent", "none"]);
-        assert_eq!(w.args.parent, "none");
+        assert_eq!(w.args.parent.as_deref(), Some("none"));
     }
 
     #[test]
@@ -370,6 +564,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         let line = write_event(&mut task_event).expect("write task event");
         shard_mgr
@@ -397,6 +592,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         let line = write_event(&mut goal_event).expect("write goal event");
         shard_mgr
@@ -421,7 +617,9 @@ mod tests {
         // Move task under goal
         let args = MoveArgs {
             id: task_id.clone(),
-            parent: goal_id.clone(),
+            state: None,
+            parent: Some(goal_id.clone()),
+            reason: None,
         };
         run_move(&args, Some("test-agent"), OutputMode::Pretty, &root)
             .expect("run_move should succeed");
@@ -450,7 +648,9 @@ mod tests {
         // Try to move task under another task (not a goal) - use task_id as parent
         let args = MoveArgs {
             id: "bn-tsk1".to_string(),
-            parent: task_id.clone(), // task is not a goal
+            state: None,
+            parent: Some(task_id.clone()), // task is not a goal
+            reason: None,
         };
         // This will try to use the same item as both child and parent,
         // but the important thing is that the parent kind validation happens
@@ -474,7 +674,9 @@ mod tests {
         // First move task under the goal
         let args = MoveArgs {
             id: task_id.clone(),
-            parent: goal_id.clone(),
+            state: None,
+            parent: Some(goal_id.clone()),
+            reason: None,
         };
         run_move(&args, Some("test-agent"), OutputMode::Pretty, &root).expect("first move");
 
@@ -487,7 +689,9 @@ mod tests {
         // Now move to top-level
         let args2 = MoveArgs {
             id: task_id.clone(),
-            parent: "none".to_string(),
+            state: None,
+            parent: Some("none".to_string()),
+            reason: None,
         };
         run_move(&args2, Some("test-agent"), OutputMode::Pretty, &root)
             .expect("move to top-level should succeed");
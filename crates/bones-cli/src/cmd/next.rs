@@ -4,6 +4,7 @@ use std::path::Path;
 use clap::{Args, ValueEnum};
 use serde::Serialize;
 
+use bones_core::config::load_project_config;
 use bones_core::db;
 use bones_core::db::project;
 use bones_core::db::query::{self, ItemFilter, SortOrder};
@@ -21,7 +22,7 @@ use crate::agent;
 use crate::cmd::do_cmd;
 use crate::cmd::triage_support::{
     BlockingAncestor, RankedItem, blocking_ancestor_reason, build_triage_snapshot,
-    compute_blocking_ancestors,
+    compute_blocking_ancestors, warn_if_partial_replica,
 };
 use crate::output::{CliError, OutputMode, render, render_error, render_mode};
 
@@ -152,7 +153,10 @@ pub fn run_next(
         anyhow::bail!("projection not found");
     };
 
-    let snapshot = build_triage_snapshot(&conn, chrono::Utc::now().timestamp_micros())?;
+    warn_if_partial_replica(project_root);
+    let triage_config = load_project_config(project_root).unwrap_or_default().triage;
+    let snapshot =
+        build_triage_snapshot(&conn, chrono::Utc::now().timestamp_micros(), &triage_config)?;
     if snapshot.unblocked_ranked.is_empty() {
         let total_suppressed = snapshot.parent_block_origin.len();
         let bottlenecks = compute_blocking_ancestors(&snapshot);
@@ -2,17 +2,30 @@
 //!
 //! - `bn plan` shows layers across all open items.
 //! - `bn plan <goal-id>` restricts to open children of a goal.
+//!
+//! Alongside the layers, the plan reports the size-weighted critical path
+//! through the scoped items and each item's slack, both in the units of the
+//! project's `triage.size_durations` table.
+//!
+//! It also forecasts P50/P85/P95 completion dates by Monte-Carlo simulation
+//! (see [`bones_triage::forecast`]): for a goal, over all of its unfinished
+//! descendants; otherwise over the planned open items.
 
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::io::Write;
 use std::path::Path;
 
+use bones_core::config::{SizeDurations, TriageConfig, load_project_config};
 use bones_core::db::query::{self, ItemFilter, SortOrder, item_exists};
-use bones_triage::graph::{self, RawGraph};
+use bones_core::model::item::Size;
+use bones_triage::flow;
+use bones_triage::forecast::{self, Forecast, ForecastOptions};
+use bones_triage::graph::{self, NormalizedGraph, RawGraph, compute_weighted_critical_path};
 use bones_triage::schedule::{ScheduleRegime, check_indexability};
 use clap::Args;
 use serde::Serialize;
 
+use crate::cmd::progress::{goal_forecast, render_forecast_human};
 use crate::cmd::triage_support::build_triage_snapshot;
 use crate::output::{CliError, OutputMode, render, render_error};
 use crate::validate;
@@ -33,6 +46,25 @@ struct PlanOutput {
     layers: Vec<Vec<String>>,
     explanations: HashMap<String, Vec<String>>,
     schedule_regime: Option<PlanScheduleRegime>,
+    critical_path: Option<PlanCriticalPath>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    forecast: Option<Forecast>,
+}
+
+#[derive(Debug, Serialize)]
+struct PlanCriticalPath {
+    /// Item IDs on the weighted critical path, in dependency order.
+    items: Vec<String>,
+    /// Earliest completion of the scoped plan, in size-duration units.
+    total_duration: usize,
+    /// Per-item duration and slack, in size-duration units.
+    timings: BTreeMap<String, PlanItemTiming>,
+}
+
+#[derive(Debug, Serialize)]
+struct PlanItemTiming {
+    duration: usize,
+    slack: usize,
 }
 
 #[derive(Debug, Serialize)]
@@ -108,24 +140,49 @@ pub fn run_plan(args: &PlanArgs, output: OutputMode, project_root: &Path) -> any
 
     let scoped_ids: BTreeSet<String> = open_items.iter().map(|item| item.item_id.clone()).collect();
 
-    let (layers, explanations, schedule_regime) = if scoped_ids.is_empty() {
-        (Vec::new(), HashMap::new(), None)
+    let triage_config = load_project_config(project_root).unwrap_or_default().triage;
+
+    let (layers, explanations, schedule_regime, critical_path, forecast) = if scoped_ids.is_empty()
+    {
+        (Vec::new(), HashMap::new(), None, None, None)
     } else {
         let raw = RawGraph::from_sqlite(&conn)
             .map_err(|e| anyhow::anyhow!("failed to load dependency graph: {e}"))?;
         let scoped_graph = build_scoped_graph(&raw, &scoped_ids);
         let mut layers = graph::topological_layers(&scoped_graph, None);
-        let score_map = build_score_map(&conn);
+        let score_map = build_score_map(&conn, &triage_config);
         sort_layers_by_score(&mut layers, &score_map);
         let explanations = build_layer_explanations(&scoped_graph, &layers);
         let schedule_regime = derive_schedule_regime(&scoped_graph);
-        (layers, explanations, Some(schedule_regime))
+        let durations = item_durations(&open_items, &triage_config.size_durations);
+        let critical_path = build_plan_critical_path(&scoped_graph, &durations);
+        let forecast = if let Some(goal_id) = &args.goal_id {
+            goal_forecast(&conn, goal_id, now_us())?
+        } else {
+            let items = flow::load_flow_items(&conn)?;
+            forecast::forecast_completion(
+                &items,
+                &scoped_ids,
+                &raw,
+                now_us(),
+                ForecastOptions::default(),
+            )
+        };
+        (
+            layers,
+            explanations,
+            Some(schedule_regime),
+            Some(critical_path),
+            Some(forecast),
+        )
     };
 
     let output_payload = PlanOutput {
         layers,
         explanations,
         schedule_regime,
+        critical_path,
+        forecast,
     };
 
     render(output, &output_payload, |payload, w| {
@@ -168,6 +225,55 @@ fn derive_schedule_regime(scoped_graph: &graph::DiGraph) -> PlanScheduleRegime {
     }
 }
 
+/// Map each scoped item to the duration of its size.
+fn item_durations(
+    items: &[query::QueryItem],
+    size_durations: &SizeDurations,
+) -> HashMap<String, usize> {
+    items
+        .iter()
+        .map(|item| {
+            let size = item.size.as_deref().and_then(|s| s.parse::<Size>().ok());
+            (item.item_id.clone(), size_durations.duration(size) as usize)
+        })
+        .collect()
+}
+
+fn build_plan_critical_path(
+    scoped_graph: &graph::DiGraph,
+    durations: &HashMap<String, usize>,
+) -> PlanCriticalPath {
+    let node_map = scoped_graph
+        .node_indices()
+        .filter_map(|idx| scoped_graph.node_weight(idx).map(|id| (id.clone(), idx)))
+        .collect();
+    let normalized = NormalizedGraph::from_raw(RawGraph {
+        graph: scoped_graph.clone(),
+        node_map,
+        content_hash: String::new(),
+    });
+    let result =
+        compute_weighted_critical_path(&normalized, |id| durations.get(id).copied().unwrap_or(0));
+
+    PlanCriticalPath {
+        items: result.critical_path,
+        total_duration: result.total_duration,
+        timings: result
+            .item_timings
+            .into_iter()
+            .map(|(id, timing)| {
+                (
+                    id,
+                    PlanItemTiming {
+                        duration: timing.duration,
+                        slack: timing.slack,
+                    },
+                )
+            })
+            .collect(),
+    }
+}
+
 fn build_layer_explanations(
     scoped_graph: &graph::DiGraph,
     layers: &[Vec<String>],
@@ -237,13 +343,8 @@ fn build_scoped_graph(raw: &RawGraph, scoped_ids: &BTreeSet<String>) -> graph::D
 
 /// Build a map of `item_id` -> composite triage score.
 /// Falls back to an empty map if triage snapshot computation fails.
-fn build_score_map(conn: &rusqlite::Connection) -> HashMap<String, f64> {
-    let now_us = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map(|d| d.as_micros() as i64)
-        .unwrap_or(0);
-
-    match build_triage_snapshot(conn, now_us) {
+fn build_score_map(conn: &rusqlite::Connection, config: &TriageConfig) -> HashMap<String, f64> {
+    match build_triage_snapshot(conn, now_us(), config) {
         Ok(snapshot) => snapshot
             .ranked
             .into_iter()
@@ -253,6 +354,13 @@ fn build_score_map(conn: &rusqlite::Connection) -> HashMap<String, f64> {
     }
 }
 
+fn now_us() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_micros() as i64)
+        .unwrap_or(0)
+}
+
 /// Re-sort each layer by triage score descending, with ID ascending as tie-break.
 /// Items missing from the score map sort to the end.
 fn sort_layers_by_score(layers: &mut [Vec<String>], score_map: &HashMap<String, f64>) {
@@ -299,15 +407,38 @@ fn render_plan_human(
         }
     }
 
+    if let Some(cp) = &payload.critical_path
+        && !cp.items.is_empty()
+    {
+        writeln!(
+            w,
+            "\nCritical path ({} units): {}",
+            cp.total_duration,
+            cp.items.join(" → ")
+        )?;
+    }
+
     for (idx, layer) in payload.layers.iter().enumerate() {
         let noun = if layer.len() == 1 { "item" } else { "items" };
         writeln!(w, "\nLayer {} ({} {noun}):", idx + 1, layer.len())?;
 
         for item_id in layer {
+            let timing = payload
+                .critical_path
+                .as_ref()
+                .and_then(|cp| cp.timings.get(item_id))
+                .map(|t| {
+                    if t.slack == 0 {
+                        "  [critical]".to_string()
+                    } else {
+                        format!("  [slack {}]", t.slack)
+                    }
+                })
+                .unwrap_or_default();
             if let Some(title) = titles.get(item_id.as_str()) {
-                writeln!(w, "  - {item_id} — {title}")?;
+                writeln!(w, "  - {item_id} — {title}{timing}")?;
             } else {
-                writeln!(w, "  - {item_id}")?;
+                writeln!(w, "  - {item_id}{timing}")?;
             }
 
             if explain {
@@ -325,7 +456,10 @@ fn render_plan_human(
         }
     }
 
-    Ok(())
+    match &payload.forecast {
+        Some(forecast) => render_forecast_human(forecast, w),
+        None => Ok(()),
+    }
 }
 
 #[cfg(test)]
@@ -395,6 +529,8 @@ mod tests {
             layers: Vec::new(),
             explanations: HashMap::new(),
             schedule_regime: None,
+            forecast: None,
+            critical_path: None,
         };
         let mut out = Vec::new();
 
@@ -405,6 +541,81 @@ mod tests {
         assert!(rendered.contains("(no open items)"));
     }
 
+    #[test]
+    fn plan_critical_path_weights_by_duration() {
+        // a → b → d (1 + 1 + 1) vs a → c → d with c = 5
+        let mut graph = graph::DiGraph::new();
+        let a = graph.add_node("bn-a".to_string());
+        let b = graph.add_node("bn-b".to_string());
+        let c = graph.add_node("bn-c".to_string());
+        let d = graph.add_node("bn-d".to_string());
+        graph.add_edge(a, b, ());
+        graph.add_edge(b, d, ());
+        graph.add_edge(a, c, ());
+        graph.add_edge(c, d, ());
+
+        let durations = HashMap::from([
+            ("bn-a".to_string(), 1),
+            ("bn-b".to_string(), 1),
+            ("bn-c".to_string(), 5),
+            ("bn-d".to_string(), 1),
+        ]);
+        let cp = build_plan_critical_path(&graph, &durations);
+
+        assert_eq!(cp.items, vec!["bn-a", "bn-c", "bn-d"]);
+        assert_eq!(cp.total_duration, 7);
+        assert_eq!(cp.timings["bn-b"].slack, 4);
+        assert_eq!(cp.timings["bn-c"].slack, 0);
+    }
+
+    #[test]
+    fn render_plan_human_marks_critical_and_slack() {
+        let payload = PlanOutput {
+            layers: vec![
+                vec!["bn-a".to_string()],
+                vec!["bn-c".to_string(), "bn-b".to_string()],
+            ],
+            explanations: HashMap::new(),
+            schedule_regime: None,
+            forecast: None,
+            critical_path: Some(PlanCriticalPath {
+                items: vec!["bn-a".to_string(), "bn-c".to_string()],
+                total_duration: 6,
+                timings: BTreeMap::from([
+                    (
+                        "bn-a".to_string(),
+                        PlanItemTiming {
+                            duration: 1,
+                            slack: 0,
+                        },
+                    ),
+                    (
+                        "bn-b".to_string(),
+                        PlanItemTiming {
+                            duration: 2,
+                            slack: 3,
+                        },
+                    ),
+                    (
+                        "bn-c".to_string(),
+                        PlanItemTiming {
+                            duration: 5,
+                            slack: 0,
+                        },
+                    ),
+                ]),
+            }),
+        };
+        let mut out = Vec::new();
+
+        render_plan_human(&payload, &[], None, false, &mut out).expect("render");
+
+        let rendered = String::from_utf8(out).expect("utf8");
+        assert!(rendered.contains("Critical path (6 units): bn-a → bn-c"));
+        assert!(rendered.contains("- bn-c  [critical]"));
+        assert!(rendered.contains("- bn-b  [slack 3]"));
+    }
+
     #[test]
     fn intra_layer_score_ordering() {
         // Items within the same layer should be sorted by score descending.
@@ -2,22 +2,41 @@
 //!
 //! Shows a focused goal-progress view with child tree and progress bars.
 //! Distinct from `bn show` (full bone detail) — this is a focused view
-//! of how far a goal is from completion.
+//! of how far a goal is from completion. With `--forecast` it also
+//! estimates when the goal will finish (see [`bones_triage::forecast`]).
 
 use std::io::Write;
 use std::path::Path;
 
 use bones_core::db::query::{self, QueryItem};
+use bones_triage::flow;
+use bones_triage::forecast::{self, Forecast, ForecastOptions, ForecastPoint};
+use bones_triage::graph::RawGraph;
+use chrono::{DateTime, Utc};
 use clap::Args;
 use serde::Serialize;
 
-use crate::output::{CliError, OutputMode, render, render_error};
+use crate::cmd::as_of::{open_read_projection, pretty_replay_note};
+use crate::cmd::metrics::format_days;
+use crate::output::{CliError, OutputMode, render_error, render_mode};
 
 /// Arguments for `bn progress`.
 #[derive(Args, Debug)]
 pub struct ProgressArgs {
     /// Goal bone ID to show progress for.
     pub id: String,
+
+    /// Show progress as it was at a point in the past.
+    ///
+    /// Accepts YYYY-MM-DD (end of that UTC day), RFC3339, an offset into
+    /// the past (3d, 2w), or an event hash prefix (blake3:...).
+    #[arg(long, value_name = "when")]
+    pub at: Option<String>,
+
+    /// Forecast P50/P85/P95 completion dates by Monte-Carlo simulation
+    /// over the goal's remaining dependency graph.
+    #[arg(long)]
+    pub forecast: bool,
 }
 
 /// Per-child summary for progress display.
@@ -51,6 +70,8 @@ struct GoalProgressOutput {
     kind: String,
     progress: ProgressCounts,
     children: Vec<ChildProgress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    forecast: Option<Forecast>,
 }
 
 /// Execute `bn progress`.
@@ -59,10 +80,7 @@ pub fn run_progress(
     output: OutputMode,
     project_root: &Path,
 ) -> anyhow::Result<()> {
-    let db_path = project_root.join(".bones/bones.db");
-    let conn = if let Some(conn) = query::try_open_projection(&db_path)? {
-        conn
-    } else {
+    let Some(read) = open_read_projection(project_root, args.at.as_deref(), output)? else {
         render_error(
             output,
             &CliError::with_details(
@@ -73,6 +91,9 @@ pub fn run_progress(
         )?;
         anyhow::bail!("projection not found");
     };
+    let now_us = read.now_us();
+    let replay = read.replay;
+    let conn = read.conn;
 
     // Resolve the item ID (partial ID support).
     let item_id = resolve_item_id(&conn, &args.id)?;
@@ -82,11 +103,79 @@ pub fn run_progress(
         .ok_or_else(|| anyhow::anyhow!("Item not found: {}", args.id))?;
 
     // Build progress tree recursively.
-    let progress = build_progress(&conn, &parent)?;
+    let mut progress = build_progress(&conn, &parent)?;
+    if args.forecast {
+        progress.forecast = Some(goal_forecast(&conn, &item_id, now_us)?);
+    }
 
-    render(output, &progress, |report, w| {
-        render_progress_human(report, 0, w)
-    })
+    render_mode(
+        output,
+        &progress,
+        |report, w| render_progress_report(report, w),
+        |report, w| {
+            pretty_replay_note(replay, w)?;
+            render_progress_report(report, w)
+        },
+    )
+}
+
+fn render_progress_report(report: &GoalProgressOutput, w: &mut dyn Write) -> std::io::Result<()> {
+    render_progress_human(report, 0, w)?;
+    match &report.forecast {
+        Some(forecast) => render_forecast_human(forecast, w),
+        None => Ok(()),
+    }
+}
+
+/// Forecast completion of `goal_id`'s remaining descendants.
+pub fn goal_forecast(
+    conn: &rusqlite::Connection,
+    goal_id: &str,
+    now_us: i64,
+) -> anyhow::Result<Forecast> {
+    let items = flow::load_flow_items(conn)?;
+    let remaining = forecast::goal_remaining(&items, goal_id);
+    let raw = RawGraph::from_sqlite(conn)
+        .map_err(|e| anyhow::anyhow!("failed to load dependency graph: {e}"))?;
+    Ok(forecast::forecast_completion(
+        &items,
+        &remaining,
+        &raw,
+        now_us,
+        ForecastOptions::default(),
+    ))
+}
+
+/// Render a forecast as a short block of text.
+pub fn render_forecast_human(forecast: &Forecast, w: &mut dyn Write) -> std::io::Result<()> {
+    let (Some(p50), Some(p85), Some(p95)) = (&forecast.p50, &forecast.p85, &forecast.p95) else {
+        return writeln!(
+            w,
+            "\nForecast: no completed bones to learn from yet ({} remaining)",
+            forecast.remaining
+        );
+    };
+    if forecast.remaining == 0 {
+        return writeln!(w, "\nForecast: nothing left to do");
+    }
+    writeln!(
+        w,
+        "\nForecast ({} remaining, {} runs, {} past completions, {:.1}/week):",
+        forecast.remaining,
+        forecast.simulations,
+        forecast.history_samples,
+        forecast.weekly_throughput
+    )?;
+    for (label, point) in [("P50", p50), ("P85", p85), ("P95", p95)] {
+        writeln!(w, "  {label}  {}", format_point(point))?;
+    }
+    Ok(())
+}
+
+fn format_point(point: &ForecastPoint) -> String {
+    let date = DateTime::<Utc>::from_timestamp_micros(point.date_us)
+        .map_or_else(String::new, |dt| dt.format("%Y-%m-%d").to_string());
+    format!("{date}  (in {})", format_days(point.days))
 }
 
 /// Resolve a possibly-partial item ID to a full item ID.
@@ -192,6 +281,7 @@ fn build_progress(
         kind: item.kind.clone(),
         progress: counts,
         children: child_entries,
+        forecast: None,
     })
 }
 
@@ -261,6 +351,7 @@ mod tests {
     #[test]
     fn render_progress_basic() {
         let report = GoalProgressOutput {
+            forecast: None,
             id: "bn-p1".to_string(),
             title: "Phase 1: Auth Migration".to_string(),
             state: "open".to_string(),
@@ -313,6 +404,7 @@ mod tests {
     #[test]
     fn render_progress_empty_children() {
         let report = GoalProgressOutput {
+            forecast: None,
             id: "bn-g1".to_string(),
             title: "Empty goal".to_string(),
             state: "open".to_string(),
@@ -338,6 +430,7 @@ mod tests {
     #[test]
     fn render_progress_all_done() {
         let report = GoalProgressOutput {
+            forecast: None,
             id: "bn-g2".to_string(),
             title: "Complete goal".to_string(),
             state: "done".to_string(),
@@ -379,6 +472,7 @@ mod tests {
     #[test]
     fn render_progress_nested_goals() {
         let sub_goal = GoalProgressOutput {
+            forecast: None,
             id: "bn-sub".to_string(),
             title: "Sub-goal".to_string(),
             state: "doing".to_string(),
@@ -401,6 +495,7 @@ mod tests {
         };
 
         let report = GoalProgressOutput {
+            forecast: None,
             id: "bn-top".to_string(),
             title: "Top goal".to_string(),
             state: "open".to_string(),
@@ -431,6 +526,42 @@ mod tests {
         assert!(rendered.contains("Sub task"));
     }
 
+    #[test]
+    fn render_forecast_percentiles() {
+        let point = |days: f64| ForecastPoint {
+            days,
+            date_us: 1_767_225_600_000_000 + (days * 86_400_000_000.0) as i64,
+        };
+        let forecast = Forecast {
+            remaining: 4,
+            simulations: 2000,
+            history_samples: 30,
+            weekly_throughput: 2.5,
+            p50: Some(point(3.0)),
+            p85: Some(point(5.5)),
+            p95: Some(point(8.0)),
+        };
+
+        let mut out = Vec::new();
+        render_forecast_human(&forecast, &mut out).expect("render");
+        let rendered = String::from_utf8(out).expect("utf8");
+
+        assert!(rendered.contains("4 remaining, 2000 runs, 30 past completions, 2.5/week"));
+        assert!(rendered.contains("P50  2026-01-04  (in 3.0d)"));
+        assert!(rendered.contains("P95  2026-01-09  (in 8.0d)"));
+
+        let unknown = Forecast {
+            p50: None,
+            p85: None,
+            p95: None,
+            ..forecast
+        };
+        let mut out = Vec::new();
+        render_forecast_human(&unknown, &mut out).expect("render");
+        let rendered = String::from_utf8(out).expect("utf8");
+        assert!(rendered.contains("no completed bones to learn from yet (4 remaining)"));
+    }
+
     #[test]
     fn progress_counts_serialize() {
         let counts = ProgressCounts {
@@ -477,6 +608,8 @@ mod tests {
 
         let args = ProgressArgs {
             id: "bn-goal".to_string(),
+            at: None,
+            forecast: true,
         };
         let result = run_progress(&args, OutputMode::Json, dir.path());
         assert!(result.is_ok());
@@ -0,0 +1,226 @@
+//! `bn query` — read-only SQL over the projection.
+//!
+//! Queries run against the documented `bones_*` views (see
+//! [`bones_core::db::sql_console`]), which keep their shape across
+//! projection migrations.
+//!
+//! # Usage
+//!
+//! ```text
+//! bn query sql "SELECT state, count(*) FROM bones_items GROUP BY state"
+//! bn query schema
+//! ```
+
+use std::io::Write;
+use std::path::Path;
+
+use bones_core::db::sql_console::{self, SQL_API_VERSION, SQL_VIEWS, SqlError, SqlResult};
+use clap::{Args, Subcommand};
+use serde::Serialize;
+
+use crate::cmd::do_cmd::find_bones_dir;
+use crate::output::{CliError, OutputMode, pretty_table, render, render_error, render_mode};
+
+/// Default cap on returned rows.
+const DEFAULT_MAX_ROWS: usize = 1000;
+
+#[derive(Args, Debug)]
+pub struct QueryArgs {
+    #[command(subcommand)]
+    pub command: QueryCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum QueryCommand {
+    /// Run one read-only SQL statement against the `bones_*` views
+    Sql {
+        /// A single SELECT, WITH, VALUES or EXPLAIN statement
+        sql: String,
+        /// Maximum rows to return
+        #[arg(long, default_value_t = DEFAULT_MAX_ROWS)]
+        max_rows: usize,
+    },
+    /// Describe the `bones_*` views and their columns
+    Schema,
+}
+
+#[derive(Debug, Serialize)]
+struct SqlOutput {
+    api_version: u32,
+    #[serde(flatten)]
+    result: SqlResult,
+    row_count: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct SchemaColumn {
+    name: &'static str,
+    description: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct SchemaView {
+    name: &'static str,
+    description: &'static str,
+    columns: Vec<SchemaColumn>,
+}
+
+#[derive(Debug, Serialize)]
+struct SchemaOutput {
+    api_version: u32,
+    views: Vec<SchemaView>,
+}
+
+fn fail(output: OutputMode, msg: &str, suggestion: &str, code: &str) -> anyhow::Error {
+    let _ = render_error(output, &CliError::with_details(msg, suggestion, code));
+    anyhow::anyhow!("{msg}")
+}
+
+/// Execute `bn query`.
+///
+/// # Errors
+///
+/// Returns an error if the projection cannot be opened or the statement is
+/// rejected or fails.
+pub fn run_query(args: &QueryArgs, output: OutputMode, project_root: &Path) -> anyhow::Result<()> {
+    match &args.command {
+        QueryCommand::Sql { sql, max_rows } => run_sql(sql, *max_rows, output, project_root),
+        QueryCommand::Schema => run_schema(output),
+    }
+}
+
+fn run_sql(
+    sql: &str,
+    max_rows: usize,
+    output: OutputMode,
+    project_root: &Path,
+) -> anyhow::Result<()> {
+    let Some(bones_dir) = find_bones_dir(project_root) else {
+        return Err(fail(
+            output,
+            "Not a bones project: .bones directory not found",
+            "Run 'bn init' to create a new project",
+            "not_a_project",
+        ));
+    };
+    // Bring the projection up to date, then reopen it for the console.
+    let fresh = bones_core::db::ensure_projection(&bones_dir)?;
+    drop(fresh);
+    let Some(conn) = sql_console::open_sql_console(&bones_dir.join("bones.db"))? else {
+        return Err(fail(
+            output,
+            "projection database not found",
+            "run `bn admin rebuild` to initialize the projection",
+            "projection_missing",
+        ));
+    };
+
+    let result = match sql_console::run_sql(&conn, sql, max_rows) {
+        Ok(result) => result,
+        Err(err) => {
+            let code = match err {
+                SqlError::Sqlite(_) => "sql_error",
+                SqlError::Empty | SqlError::MultipleStatements | SqlError::NotReadOnly => {
+                    "sql_rejected"
+                }
+            };
+            return Err(fail(
+                output,
+                &err.to_string(),
+                "Query the bones_* views with one SELECT; see `bn query schema`",
+                code,
+            ));
+        }
+    };
+
+    let out = SqlOutput {
+        api_version: SQL_API_VERSION,
+        row_count: result.rows.len(),
+        result,
+    };
+    render_mode(
+        output,
+        &out,
+        |out, w| {
+            writeln!(w, "{}", out.result.columns.join("\t"))?;
+            for row in &out.result.rows {
+                let cells: Vec<String> = row.iter().map(cell_text).collect();
+                writeln!(w, "{}", cells.join("\t"))?;
+            }
+            render_truncation(out, w)
+        },
+        |out, w| {
+            let headers: Vec<&str> = out.result.columns.iter().map(String::as_str).collect();
+            let rows: Vec<Vec<String>> = out
+                .result
+                .rows
+                .iter()
+                .map(|row| row.iter().map(cell_text).collect())
+                .collect();
+            pretty_table(w, &headers, &rows)?;
+            writeln!(w, "{} row(s)", out.row_count)?;
+            render_truncation(out, w)
+        },
+    )
+}
+
+fn render_truncation(out: &SqlOutput, w: &mut dyn Write) -> std::io::Result<()> {
+    if out.result.truncated {
+        writeln!(
+            w,
+            "Stopped after {} rows; raise --max-rows or add a LIMIT.",
+            out.row_count
+        )?;
+    }
+    Ok(())
+}
+
+fn cell_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => s.replace(['\t', '\n'], " "),
+        other => other.to_string(),
+    }
+}
+
+fn run_schema(output: OutputMode) -> anyhow::Result<()> {
+    let out = SchemaOutput {
+        api_version: SQL_API_VERSION,
+        views: SQL_VIEWS
+            .iter()
+            .map(|view| SchemaView {
+                name: view.name,
+                description: view.description,
+                columns: view
+                    .columns
+                    .iter()
+                    .map(|&(name, description)| SchemaColumn { name, description })
+                    .collect(),
+            })
+            .collect(),
+    };
+    render(output, &out, |out, w| {
+        writeln!(w, "bones SQL views, version {}", out.api_version)?;
+        for view in &out.views {
+            writeln!(w)?;
+            writeln!(w, "{} — {}", view.name, view.description)?;
+            for column in &view.columns {
+                writeln!(w, "  {:<14} {}", column.name, column.description)?;
+            }
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn cell_text_flattens_values_for_tabular_output() {
+        assert_eq!(cell_text(&json!(null)), "");
+        assert_eq!(cell_text(&json!(3)), "3");
+        assert_eq!(cell_text(&json!("a\tb\nc")), "a b c");
+    }
+}
@@ -1,6 +1,8 @@
 use std::path::Path;
 
 use anyhow::{Context, Result};
+use bones_core::shard::ShardManager;
+use bones_core::sync::index::ProllyIndex;
 
 use crate::output::{OutputMode, pretty_kv, pretty_section};
 
@@ -36,7 +38,11 @@ fn ensure_semantic_index_state(conn: &rusqlite::Connection) -> Result<SemanticIn
     })
 }
 
-/// Run `bn admin rebuild` and refresh both projection DB and binary cache.
+/// Run `bn admin rebuild` and refresh both projection DB and binary cache,
+/// plus the prolly index when one has been written.
+///
+/// Projects that keep events on `refs/bones/events` first copy any events on
+/// the ref that the local shards lack, so a fresh clone rebuilds from the ref.
 ///
 /// # Errors
 ///
@@ -46,6 +52,8 @@ pub fn run_rebuild(project_root: &Path, _incremental: bool, output: OutputMode)
     let events_dir = bones_dir.join("events");
     let db_path = bones_dir.join("bones.db");
     let cache_path = bones_dir.join("cache/events.bin");
+    let ref_restored = crate::cmd::sync::restore_from_ref(project_root)
+        .context("failed to read events from refs/bones/events")?;
 
     let (db_report, conn) = if _incremental {
         let apply = bones_core::db::incremental::incremental_apply(&events_dir, &db_path, false)?;
@@ -70,6 +78,14 @@ pub fn run_rebuild(project_root: &Path, _incremental: bool, output: OutputMode)
     };
     let cache_stats = bones_core::cache::rebuild_cache(&events_dir, &cache_path)?;
     let semantic_state = ensure_semantic_index_state(&conn)?;
+    let prolly_events = if bones_core::sync::index::index_path(&bones_dir).exists() {
+        let index =
+            ProllyIndex::build(&ShardManager::new(&bones_dir)).context("prolly index rebuild")?;
+        index.save(&bones_dir).context("prolly index rebuild")?;
+        Some(index.event_count())
+    } else {
+        None
+    };
 
     match output {
         OutputMode::Json => {
@@ -79,6 +95,8 @@ pub fn run_rebuild(project_root: &Path, _incremental: bool, output: OutputMode)
                 "shards": db_report.shard_count,
                 "cache_events": cache_stats.total_events,
                 "cache_bytes": cache_stats.file_size_bytes,
+                "prolly_index_events": prolly_events,
+                "ref_events_restored": ref_restored,
                 "semantic_tables_ready": semantic_state.tables_ready,
                 "semantic_embeddings": semantic_state.embeddings,
                 "semantic_deferred": semantic_state.deferred,
@@ -115,6 +133,12 @@ pub fn run_rebuild(project_root: &Path, _incremental: bool, output: OutputMode)
                 "Cache bytes",
                 cache_stats.file_size_bytes.to_string(),
             )?;
+            if let Some(events) = prolly_events {
+                pretty_kv(&mut w, "Prolly index events", events.to_string())?;
+            }
+            if let Some(restored) = ref_restored {
+                pretty_kv(&mut w, "Restored from ref", format!("{restored} event(s)"))?;
+            }
             pretty_kv(
                 &mut w,
                 "Semantic tables",
@@ -182,6 +206,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         writer::write_event(&mut create).expect("compute event hash");
         let line = writer::write_line(&create).expect("serialize event line");
@@ -0,0 +1,386 @@
+//! `bn redact` — permanently hide the content of a single event.
+//!
+//! Emits an `item.redact` event targeting either an explicit event hash or
+//! the Nth comment on a bone, scrubs every derived store (projection rows,
+//! FTS5 index, semantic embeddings, binary cache, attachment blobs), and then
+//! runs the `redact-verify` checks for the affected bone so a leaked secret
+//! can be removed in one step.
+//!
+//! # Usage
+//!
+//! ```text
+//! # Redact the second comment on a bone
+//! bn redact bn-abc --comment 2 --reason "leaked API token"
+//!
+//! # Redact a specific event by hash
+//! bn redact blake3:abcdef... --reason "PII in title"
+//! ```
+
+use crate::agent;
+use crate::author::write_authored_event;
+use crate::cmd::do_cmd::find_bones_dir;
+use crate::cmd::show::resolve_item_id;
+use crate::itc_state::assign_next_itc;
+use crate::output::{CliError, OutputMode, render_error, render_mode};
+use crate::validate;
+use bones_core::blob::BlobStore;
+use bones_core::db::{project, query};
+use bones_core::event::data::{EventData, RedactData};
+use bones_core::event::parser::{ParsedLine, PartialParsedLine, parse_line, parse_line_partial};
+use bones_core::event::{Event, EventType};
+use bones_core::redact::{ProjectionScrub, scrub_projection};
+use bones_core::shard::ShardManager;
+use bones_core::verify::redact::verify_item_redaction;
+use clap::Args;
+use rusqlite::OptionalExtension;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+#[derive(Args, Debug)]
+pub struct RedactArgs {
+    /// Event hash (`blake3:...`) to redact, or a bone ID when used with
+    /// `--comment`.
+    pub target: String,
+
+    /// Redact the Nth comment on the bone (1-based, oldest first, as listed
+    /// by `bn bone comments`).
+    #[arg(long, value_name = "N")]
+    pub comment: Option<usize>,
+
+    /// Why the content is being redacted (recorded in the event log).
+    #[arg(long)]
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+struct RedactOutput {
+    ok: bool,
+    item_id: String,
+    target_hash: String,
+    target_type: String,
+    event_hash: String,
+    reason: String,
+    scrubbed: ProjectionScrub,
+    blob_removed: bool,
+    cache_rebuilt: bool,
+    verified: bool,
+}
+
+/// Find an event across all shards by its hash.
+fn find_event_by_hash(shard_mgr: &ShardManager, hash: &str) -> anyhow::Result<Option<Event>> {
+    for (year, month) in shard_mgr
+        .list_shards()
+        .map_err(|e| anyhow::anyhow!("list shards: {e}"))?
+    {
+        let content = shard_mgr
+            .read_shard(year, month)
+            .map_err(|e| anyhow::anyhow!("read shard: {e}"))?;
+
+        for line in content.lines() {
+            let Ok(PartialParsedLine::Event(partial)) = parse_line_partial(line) else {
+                continue;
+            };
+            if partial.event_hash_raw != hash {
+                continue;
+            }
+            if let Ok(ParsedLine::Event(event)) = parse_line(line) {
+                return Ok(Some(*event));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Map `--comment N` to the hash of the Nth comment (oldest first).
+fn nth_comment_hash(
+    conn: &rusqlite::Connection,
+    item_id: &str,
+    n: usize,
+) -> anyhow::Result<Option<String>> {
+    let mut comments = query::get_comments(conn, item_id, None, None)?;
+    // Query returns newest-first; comment numbering follows the timeline.
+    comments.sort_by(|a, b| {
+        a.created_at_us
+            .cmp(&b.created_at_us)
+            .then_with(|| a.comment_id.cmp(&b.comment_id))
+    });
+    Ok(n.checked_sub(1)
+        .and_then(|idx| comments.into_iter().nth(idx))
+        .map(|c| c.event_hash))
+}
+
+fn is_already_redacted(conn: &rusqlite::Connection, hash: &str) -> anyhow::Result<bool> {
+    let found: Option<i64> = conn
+        .query_row(
+            "SELECT 1 FROM event_redactions WHERE target_event_hash = ?1",
+            [hash],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(found.is_some())
+}
+
+fn fail(output: OutputMode, msg: &str, suggestion: &str, code: &str) -> anyhow::Error {
+    let _ = render_error(output, &CliError::with_details(msg, suggestion, code));
+    anyhow::anyhow!("{msg}")
+}
+
+#[tracing::instrument(skip_all, name = "cmd.redact")]
+pub fn run_redact(
+    args: &RedactArgs,
+    agent_flag: Option<&str>,
+    output: OutputMode,
+    project_root: &Path,
+) -> anyhow::Result<()> {
+    let agent = match agent::require_agent(agent_flag) {
+        Ok(a) => a,
+        Err(e) => {
+            render_error(
+                output,
+                &CliError::with_details(&e.message, "Set --agent, BONES_AGENT, or AGENT", e.code),
+            )?;
+            anyhow::bail!("{}", e.message);
+        }
+    };
+
+    if let Err(e) = validate::validate_agent(&agent) {
+        render_error(output, &e.to_cli_error())?;
+        anyhow::bail!("{}", e.reason);
+    }
+
+    let reason = args.reason.trim();
+    if reason.is_empty() {
+        return Err(fail(
+            output,
+            "redaction reason must not be empty",
+            "Pass --reason describing why the content is removed",
+            "invalid_reason",
+        ));
+    }
+
+    let bones_dir = find_bones_dir(project_root).ok_or_else(|| {
+        fail(
+            output,
+            "Not a bones project: .bones directory not found",
+            "Run 'bn init' to create a new project",
+            "not_a_project",
+        )
+    })?;
+
+    let db_path = bones_dir.join("bones.db");
+    let conn = query::try_open_projection(&db_path)?.ok_or_else(|| {
+        fail(
+            output,
+            &format!(
+                "projection database not found or corrupt at {}",
+                db_path.display()
+            ),
+            "Run `bn admin rebuild` to initialize the projection",
+            "projection_missing",
+        )
+    })?;
+
+    let target_hash = if let Some(n) = args.comment {
+        let resolved = resolve_item_id(&conn, &args.target)?.ok_or_else(|| {
+            fail(
+                output,
+                &format!("item '{}' not found", args.target),
+                "Check the item ID with `bn list`",
+                "item_not_found",
+            )
+        })?;
+        nth_comment_hash(&conn, &resolved, n)?.ok_or_else(|| {
+            fail(
+                output,
+                &format!("{resolved} has no comment #{n}"),
+                "List comments with `bn bone comments <id>`",
+                "comment_not_found",
+            )
+        })?
+    } else if args.target.starts_with("blake3:") {
+        args.target.clone()
+    } else {
+        return Err(fail(
+            output,
+            "target must be an event hash or a bone ID with --comment N",
+            "Use `bn redact <blake3:hash>` or `bn redact <id> --comment N`",
+            "invalid_target",
+        ));
+    };
+
+    let shard_mgr = ShardManager::new(&bones_dir);
+    let target = find_event_by_hash(&shard_mgr, &target_hash)?.ok_or_else(|| {
+        fail(
+            output,
+            &format!("event {target_hash} not found in the event log"),
+            "Check the hash with `bn log <id>`",
+            "event_not_found",
+        )
+    })?;
+
+    if target.event_type == EventType::Redact {
+        return Err(fail(
+            output,
+            "cannot redact a redaction event",
+            "Target the original event instead",
+            "invalid_target",
+        ));
+    }
+    if is_already_redacted(&conn, &target_hash)? {
+        return Err(fail(
+            output,
+            &format!("event {target_hash} is already redacted"),
+            "Run `bn admin redact-verify` to check it",
+            "already_redacted",
+        ));
+    }
+
+    let mut event = Event {
+        wall_ts_us: 0,
+        agent,
+        itc: String::new(),
+        parents: vec![],
+        event_type: EventType::Redact,
+        item_id: target.item_id.clone(),
+        data: EventData::Redact(RedactData {
+            target_hash: target_hash.clone(),
+            reason: reason.to_string(),
+            extra: BTreeMap::new(),
+        }),
+        event_hash: String::new(),
+        signature: None,
+    };
+
+    {
+        use bones_core::lock::ShardLock;
+        let lock_path = shard_mgr.lock_path();
+        let _lock = ShardLock::acquire(&lock_path, Duration::from_secs(5))
+            .map_err(|e| anyhow::anyhow!("failed to acquire lock: {e}"))?;
+
+        let (year, month) = shard_mgr
+            .rotate_if_needed()
+            .map_err(|e| anyhow::anyhow!("failed to rotate shards: {e}"))?;
+
+        event.wall_ts_us = shard_mgr
+            .next_timestamp()
+            .map_err(|e| anyhow::anyhow!("failed to get timestamp: {e}"))?;
+
+        assign_next_itc(project_root, &mut event)?;
+
+        let line = write_authored_event(&mut event)
+            .map_err(|e| anyhow::anyhow!("failed to serialize event: {e}"))?;
+
+        shard_mgr
+            .append_raw(year, month, &line)
+            .map_err(|e| anyhow::anyhow!("failed to write event: {e}"))?;
+    }
+
+    let _ = project::ensure_tracking_table(&conn);
+    let projector = project::Projector::new(&conn);
+    projector
+        .project_event(&event)
+        .map_err(|e| anyhow::anyhow!("failed to project redaction: {e}"))?;
+
+    let scrubbed = scrub_projection(&conn, &target)?;
+
+    // Attachment bytes live outside the log; drop the blob once no other
+    // live attachment points at the same content.
+    let blob_removed = if let EventData::Attach(attach) = &target.data
+        && query::count_live_blob_references(&conn, &attach.blob)? == 0
+    {
+        BlobStore::new(&bones_dir)
+            .remove(&attach.blob)
+            .map_err(|e| anyhow::anyhow!("failed to remove blob {}: {e}", attach.blob))?
+    } else {
+        false
+    };
+
+    // The binary cache is only ever rebuilt wholesale; rebuilding now drops
+    // the redacted payload instead of waiting for the next stale check.
+    let events_dir = bones_dir.join("events");
+    let cache_path = bones_dir.join("cache/events.bin");
+    let cache_rebuilt = cache_path.exists();
+    if cache_rebuilt {
+        bones_core::cache::rebuild_cache(&events_dir, &cache_path)?;
+    }
+
+    let item_id = target.item_id.as_str().to_string();
+    let failures = verify_item_redaction(&item_id, &events_dir, &conn)?;
+    if !failures.is_empty() {
+        return Err(fail(
+            output,
+            &format!(
+                "redaction written but verification found {} residual(s) for {item_id}",
+                failures.len()
+            ),
+            "Run `bn admin redact-verify` for details, then `bn admin rebuild`",
+            "redaction_incomplete",
+        ));
+    }
+
+    let result = RedactOutput {
+        ok: true,
+        item_id,
+        target_hash,
+        target_type: target.event_type.as_str().to_string(),
+        event_hash: event.event_hash,
+        reason: reason.to_string(),
+        scrubbed,
+        blob_removed,
+        cache_rebuilt,
+        verified: true,
+    };
+
+    render_mode(
+        output,
+        &result,
+        |r, w| {
+            writeln!(
+                w,
+                "ok=true  item={}  action=redact  target={}  verified=true",
+                r.item_id, r.target_hash
+            )
+        },
+        |r, w| {
+            writeln!(
+                w,
+                "✓ {}: redacted {} ({}) — verified",
+                r.item_id, r.target_type, r.target_hash
+            )
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Wrapper {
+        #[command(flatten)]
+        args: RedactArgs,
+    }
+
+    #[test]
+    fn redact_args_comment_target() {
+        let w = Wrapper::parse_from(["test", "bn-abc", "--comment", "2", "--reason", "leak"]);
+        assert_eq!(w.args.target, "bn-abc");
+        assert_eq!(w.args.comment, Some(2));
+        assert_eq!(w.args.reason, "leak");
+    }
+
+    #[test]
+    fn redact_args_hash_target() {
+        let w = Wrapper::parse_from(["test", "blake3:abc", "--reason", "pii"]);
+        assert_eq!(w.args.target, "blake3:abc");
+        assert!(w.args.comment.is_none());
+    }
+
+    #[test]
+    fn redact_args_require_reason() {
+        assert!(Wrapper::try_parse_from(["test", "blake3:abc"]).is_err());
+    }
+}
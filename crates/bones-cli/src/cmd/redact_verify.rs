@@ -39,6 +39,21 @@ impl Renderable for FailureRow<'_> {
                         "     ↳ comment #{comment_id} body not replaced with [redacted]"
                     )?;
                 }
+                ResidualLocation::CommentEditNotRedacted { event_hash } => {
+                    writeln!(
+                        w,
+                        "     ↳ comment edit {event_hash} body not replaced with [redacted]"
+                    )?;
+                }
+                ResidualLocation::AttachmentNotRedacted { event_hash } => {
+                    writeln!(
+                        w,
+                        "     ↳ attachment {event_hash} name not replaced with [redacted]"
+                    )?;
+                }
+                ResidualLocation::AttachmentBlobPresent { blob_hash } => {
+                    writeln!(w, "     ↳ blob {blob_hash} still present in .bones/blobs")?;
+                }
                 ResidualLocation::Fts5Index { matched_term } => {
                     writeln!(
                         w,
@@ -65,6 +80,15 @@ impl Renderable for FailureRow<'_> {
                 ResidualLocation::CommentNotRedacted { comment_id } => {
                     format!("comment:{comment_id}")
                 }
+                ResidualLocation::CommentEditNotRedacted { event_hash } => {
+                    format!("comment_edit:{event_hash}")
+                }
+                ResidualLocation::AttachmentNotRedacted { event_hash } => {
+                    format!("attachment:{event_hash}")
+                }
+                ResidualLocation::AttachmentBlobPresent { blob_hash } => {
+                    format!("blob:{blob_hash}")
+                }
                 ResidualLocation::Fts5Index { matched_term } => {
                     format!("fts5:{matched_term}")
                 }
@@ -9,6 +9,7 @@
 //! epoch-increment intent to CRDT-aware projectors.
 
 use crate::agent;
+use crate::author::write_authored_event;
 use crate::cmd::open_projection_for_mutation;
 use crate::cmd::show::resolve_item_id;
 use crate::itc_state::assign_next_itc;
@@ -27,7 +28,6 @@ use bones_core::db::query;
 use bones_core::event::Event;
 use bones_core::event::data::{EventData, MoveData};
 use bones_core::event::types::EventType;
-use bones_core::event::writer;
 use bones_core::model::item::State;
 use bones_core::model::item_id::ItemId;
 use bones_core::shard::ShardManager;
@@ -118,6 +118,7 @@ fn run_reopen_single(
     let move_data = MoveData {
         state: State::Open,
         reason: None,
+        substate: None,
         extra,
     };
 
@@ -130,6 +131,7 @@ fn run_reopen_single(
         item_id: ItemId::new_unchecked(&resolved_id),
         data: EventData::Move(move_data),
         event_hash: String::new(),
+        signature: None,
     };
 
     {
@@ -148,7 +150,7 @@ fn run_reopen_single(
 
         assign_next_itc(project_root, &mut event)?;
 
-        let line = writer::write_event(&mut event)
+        let line = write_authored_event(&mut event)
             .map_err(|e| anyhow::anyhow!("failed to serialize event: {e}"))?;
 
         shard_mgr
@@ -335,6 +337,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         let line = writer::write_event(&mut create_event).unwrap();
         shard_mgr
@@ -361,9 +364,11 @@ mod tests {
                     data: EventData::Move(MoveData {
                         state: step_state,
                         reason: None,
+                        substate: None,
                         extra: BTreeMap::new(),
                     }),
                     event_hash: String::new(),
+                    signature: None,
                 };
                 let line = writer::write_event(&mut move_event).unwrap();
                 shard_mgr
@@ -459,6 +459,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: format!("blake3:{hash}"),
+            signature: None,
         }
     }
 
@@ -3,22 +3,34 @@
 //! Supports partial ID resolution: "a7x" → "bn-a7x", and prefix matching
 //! when an exact match is not found.
 
+use crate::cmd::as_of::{open_read_projection, pretty_replay_note};
+use crate::cmd::conflicts::render_conflict_lines;
 use crate::output::{
     CliError, OutputMode, pretty_kv, pretty_markdown, pretty_rule, pretty_section, render_error,
     render_mode,
 };
 use crate::validate;
+use bones_core::conflicts::{FieldConflict, list_conflicts};
 use bones_core::db::query;
+use bones_core::model::due::format_due;
 use chrono::{DateTime, Local, Utc};
 use clap::Args;
 use rusqlite::params;
 use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::io::Write;
 
 #[derive(Args, Debug)]
 pub struct ShowArgs {
     /// Bone ID to display. Supports partial IDs: "a7x" → "bn-a7x".
     pub id: String,
+
+    /// Show the bone as it was at a point in the past.
+    ///
+    /// Accepts YYYY-MM-DD (end of that UTC day), RFC3339, an offset into
+    /// the past (3d, 2w), or an event hash prefix (blake3:...).
+    #[arg(long, value_name = "when")]
+    pub at: Option<String>,
 }
 
 /// Full item detail as returned in JSON output.
@@ -30,11 +42,20 @@ pub struct ShowItem {
     pub description: Option<String>,
     pub kind: String,
     pub state: String,
+    /// Configured sub-state within `state`, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub substate: Option<String>,
     pub urgency: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub size: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parent_id: Option<String>,
+    /// Due date as an RFC3339 UTC timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due: Option<String>,
+    /// Custom field values, keyed by field name.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom: BTreeMap<String, serde_json::Value>,
     pub labels: Vec<String>,
     pub assignees: Vec<String>,
     /// Items this item depends on (blockers).
@@ -42,19 +63,49 @@ pub struct ShowItem {
     /// Items that depend on this item.
     pub dependents: Vec<String>,
     pub comments: Vec<ShowComment>,
+    /// Concurrent LWW writes that lost (see `bn conflicts`).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<FieldConflict>,
     pub created_at_us: i64,
     pub updated_at_us: i64,
 }
 
-/// A single comment in the `show` output.
+/// A single comment in the `show` output, in thread order.
 #[derive(Debug, Serialize)]
 pub struct ShowComment {
+    pub hash: String,
     pub author: String,
     pub body: String,
     pub created_at_us: i64,
+    /// Hash of the comment this one replies to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<String>,
+    /// Nesting level within its thread (0 = top-level comment).
+    pub depth: usize,
+    /// When the edit that produced `body` was made.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edited_at_us: Option<i64>,
+    /// Agent that retracted the comment; `body` is then a placeholder.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retracted_by: Option<String>,
+    /// Every version of an edited comment, oldest first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<ShowCommentVersion>,
 }
 
-fn micros_to_local_datetime(us: i64) -> String {
+/// One version of an edited comment.
+#[derive(Debug, Serialize)]
+pub struct ShowCommentVersion {
+    pub hash: String,
+    pub author: String,
+    pub body: String,
+    pub created_at_us: i64,
+}
+
+/// Body shown in place of a retracted comment that still has replies.
+pub const RETRACTED_PLACEHOLDER: &str = "[retracted]";
+
+pub fn micros_to_local_datetime(us: i64) -> String {
     DateTime::<Utc>::from_timestamp_micros(us)
         .map(|ts| {
             ts.with_timezone(&Local)
@@ -64,23 +115,87 @@ fn micros_to_local_datetime(us: i64) -> String {
         .unwrap_or_else(|| us.to_string())
 }
 
-fn timeline_comments(mut comments: Vec<query::QueryComment>) -> Vec<ShowComment> {
-    comments.sort_by(|a, b| {
-        a.created_at_us
-            .cmp(&b.created_at_us)
-            .then_with(|| a.comment_id.cmp(&b.comment_id))
-    });
+/// Arrange comments into threads and attach the edit history of each
+/// edited comment.
+pub fn threaded_comments(
+    comments: Vec<query::QueryComment>,
+    edits: Vec<query::QueryCommentEdit>,
+) -> Vec<ShowComment> {
+    let mut edits_by_comment: HashMap<String, Vec<query::QueryCommentEdit>> = HashMap::new();
+    for edit in edits {
+        edits_by_comment
+            .entry(edit.comment_hash.clone())
+            .or_default()
+            .push(edit);
+    }
 
-    comments
+    query::thread_comments(comments)
         .into_iter()
-        .map(|c| ShowComment {
-            author: c.author,
-            body: c.body,
-            created_at_us: c.created_at_us,
+        .map(|(depth, c)| {
+            let edits = edits_by_comment.remove(&c.event_hash).unwrap_or_default();
+            let retracted = c.retracted_by.is_some();
+            let history = if retracted || c.edited_at_us.is_none() {
+                Vec::new()
+            } else {
+                std::iter::once(ShowCommentVersion {
+                    hash: c.event_hash.clone(),
+                    author: c.author.clone(),
+                    body: c.original_body,
+                    created_at_us: c.created_at_us,
+                })
+                .chain(edits.into_iter().map(|e| ShowCommentVersion {
+                    hash: e.event_hash,
+                    author: e.author,
+                    body: e.body,
+                    created_at_us: e.created_at_us,
+                }))
+                .collect()
+            };
+            ShowComment {
+                hash: c.event_hash,
+                author: c.author,
+                body: if retracted {
+                    RETRACTED_PLACEHOLDER.to_string()
+                } else {
+                    c.body
+                },
+                created_at_us: c.created_at_us,
+                reply_to: c.reply_to,
+                depth,
+                edited_at_us: c.edited_at_us.filter(|_| !retracted),
+                retracted_by: c.retracted_by,
+                history,
+            }
         })
         .collect()
 }
 
+/// Load an item's comments as threads, with edit history.
+///
+/// # Errors
+///
+/// Returns an error if the projection query fails.
+pub fn load_comment_threads(
+    conn: &rusqlite::Connection,
+    item_id: &str,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> anyhow::Result<Vec<ShowComment>> {
+    Ok(threaded_comments(
+        query::get_comments(conn, item_id, limit, offset)?,
+        query::get_comment_edits(conn, item_id)?,
+    ))
+}
+
+/// Header suffix noting an edit or retraction.
+pub fn comment_marker(comment: &ShowComment) -> String {
+    match (&comment.retracted_by, comment.edited_at_us) {
+        (Some(agent), _) => format!(" (retracted by {agent})"),
+        (None, Some(_)) => " (edited)".to_string(),
+        (None, None) => String::new(),
+    }
+}
+
 /// Execute `bn show <id>`.
 ///
 /// Resolves partial IDs before querying. Opens the projection database
@@ -99,22 +214,21 @@ pub fn run_show(
         anyhow::bail!("{}", e.reason);
     }
 
-    let db_path = project_root.join(".bones/bones.db");
-
     // Gracefully handle missing / corrupt projection
-    let conn = if let Some(c) = query::try_open_projection(&db_path)? {
-        c
-    } else {
-        render_error(
-            output,
-            &CliError::with_details(
-                "projection database not found",
-                "run `bn admin rebuild` to initialize the projection",
-                "projection_missing",
-            ),
-        )?;
-        anyhow::bail!("projection not found");
-    };
+    let (conn, replay) =
+        if let Some(read) = open_read_projection(project_root, args.at.as_deref(), output)? {
+            (read.conn, read.replay)
+        } else {
+            render_error(
+                output,
+                &CliError::with_details(
+                    "projection database not found",
+                    "run `bn admin rebuild` to initialize the projection",
+                    "projection_missing",
+                ),
+            )?;
+            anyhow::bail!("projection not found");
+        };
 
     // Resolve the ID (possibly partial)
     let resolved_id = if let Some(id) = resolve_item_id(&conn, &args.id)? {
@@ -167,7 +281,7 @@ pub fn run_show(
         .map(|d| d.item_id)
         .collect();
 
-    let comments = timeline_comments(query::get_comments(&conn, &resolved_id, None, None)?);
+    let comments = load_comment_threads(&conn, &resolved_id, None, None)?;
 
     let show_item = ShowItem {
         id: item.item_id.clone(),
@@ -175,14 +289,21 @@ pub fn run_show(
         description: item.description.clone(),
         kind: item.kind.clone(),
         state: item.state.clone(),
+        substate: item.substate.clone(),
         urgency: item.urgency.clone(),
         size: item.size.clone(),
         parent_id: item.parent_id.clone(),
+        due: item
+            .due_at_us
+            .and_then(|us| u64::try_from(us).ok())
+            .map(format_due),
+        custom: query::get_custom_fields(&conn, &resolved_id)?,
         labels,
         assignees,
         depends_on,
         dependents,
         comments,
+        conflicts: list_conflicts(&conn, Some(&resolved_id))?,
         created_at_us: item.created_at_us,
         updated_at_us: item.updated_at_us,
     };
@@ -191,7 +312,10 @@ pub fn run_show(
         output,
         &show_item,
         |item, w| render_show_text(item, w),
-        |item, w| render_show_human(item, w),
+        |item, w| {
+            pretty_replay_note(replay, w)?;
+            render_show_human(item, w)
+        },
     )
 }
 
@@ -200,7 +324,10 @@ fn render_show_human(item: &ShowItem, w: &mut dyn Write) -> std::io::Result<()>
     writeln!(w, "{}: {}", item.id, item.title)?;
     pretty_rule(w)?;
     pretty_kv(w, "kind", &item.kind)?;
-    pretty_kv(w, "state", &item.state)?;
+    match item.substate {
+        Some(ref sub) => pretty_kv(w, "state", format!("{sub} ({})", item.state))?,
+        None => pretty_kv(w, "state", &item.state)?,
+    }
     pretty_kv(w, "urgency", &item.urgency)?;
     if let Some(ref size) = item.size {
         pretty_kv(w, "size", size)?;
@@ -208,6 +335,12 @@ fn render_show_human(item: &ShowItem, w: &mut dyn Write) -> std::io::Result<()>
     if let Some(ref parent) = item.parent_id {
         pretty_kv(w, "parent", parent)?;
     }
+    if let Some(ref due) = item.due {
+        pretty_kv(w, "due", due)?;
+    }
+    for (name, value) in &item.custom {
+        pretty_kv(w, name, custom_value_display(value))?;
+    }
     if !item.labels.is_empty() {
         pretty_kv(w, "labels", item.labels.join(", "))?;
     }
@@ -234,22 +367,50 @@ fn render_show_human(item: &ShowItem, w: &mut dyn Write) -> std::io::Result<()>
             if i > 0 {
                 writeln!(w)?;
             }
+            let indent = "  ".repeat(comment.depth);
             writeln!(
                 w,
-                "[{}] {}:",
+                "{indent}[{}] {}:{}",
                 micros_to_local_datetime(comment.created_at_us),
                 comment.author,
+                comment_marker(comment),
             )?;
-            pretty_markdown(w, &comment.body)?;
+            if comment.retracted_by.is_some() {
+                continue;
+            }
+            if indent.is_empty() {
+                pretty_markdown(w, &comment.body)?;
+            } else {
+                let mut body = Vec::new();
+                pretty_markdown(&mut body, &comment.body)?;
+                for line in String::from_utf8_lossy(&body).lines() {
+                    writeln!(w, "{indent}{line}")?;
+                }
+            }
         }
     }
+
+    if !item.conflicts.is_empty() {
+        writeln!(w)?;
+        pretty_section(w, &format!("Conflicts ({})", item.conflicts.len()))?;
+        render_conflict_lines(w, &item.conflicts, false)?;
+    }
     Ok(())
 }
 
+fn custom_value_display(value: &serde_json::Value) -> String {
+    value
+        .as_str()
+        .map_or_else(|| value.to_string(), ToString::to_string)
+}
+
 fn render_show_text(item: &ShowItem, w: &mut dyn Write) -> std::io::Result<()> {
     writeln!(w, "{}: {}", item.id, item.title)?;
     writeln!(w, "kind:        {}", item.kind)?;
-    writeln!(w, "state:       {}", item.state)?;
+    match item.substate {
+        Some(ref sub) => writeln!(w, "state:       {sub} ({})", item.state)?,
+        None => writeln!(w, "state:       {}", item.state)?,
+    }
     writeln!(w, "urgency:     {}", item.urgency)?;
     if let Some(ref size) = item.size {
         writeln!(w, "size:        {size}")?;
@@ -257,6 +418,17 @@ fn render_show_text(item: &ShowItem, w: &mut dyn Write) -> std::io::Result<()> {
     if let Some(ref parent) = item.parent_id {
         writeln!(w, "parent:      {parent}")?;
     }
+    if let Some(ref due) = item.due {
+        writeln!(w, "due:         {due}")?;
+    }
+    for (name, value) in &item.custom {
+        writeln!(
+            w,
+            "{:<13}{}",
+            format!("{name}:"),
+            custom_value_display(value)
+        )?;
+    }
     if !item.labels.is_empty() {
         writeln!(w, "labels:      {}", item.labels.join(", "))?;
     }
@@ -288,13 +460,22 @@ fn render_show_text(item: &ShowItem, w: &mut dyn Write) -> std::io::Result<()> {
             }
             writeln!(
                 w,
-                "[{}] {}: {}",
+                "{}[{}] {}: {}{}",
+                "  ".repeat(comment.depth),
                 micros_to_local_datetime(comment.created_at_us),
                 comment.author,
-                comment.body
+                comment.body,
+                comment_marker(comment),
             )?;
         }
     }
+
+    if !item.conflicts.is_empty() {
+        writeln!(w)?;
+        writeln!(w, "Conflicts ({})", item.conflicts.len())?;
+        writeln!(w, "{:-<72}", "")?;
+        render_conflict_lines(w, &item.conflicts, false)?;
+    }
     Ok(())
 }
 
@@ -419,6 +600,20 @@ mod tests {
     // render_show_human
     // -----------------------------------------------------------------------
 
+    fn show_comment(author: &str, body: &str, created_at_us: i64) -> ShowComment {
+        ShowComment {
+            hash: format!("blake3:{author}{created_at_us}"),
+            author: author.into(),
+            body: body.into(),
+            created_at_us,
+            reply_to: None,
+            depth: 0,
+            edited_at_us: None,
+            retracted_by: None,
+            history: vec![],
+        }
+    }
+
     fn make_show_item() -> ShowItem {
         ShowItem {
             id: "bn-abc".into(),
@@ -426,18 +621,18 @@ mod tests {
             description: Some("The auth service times out after 30s.".into()),
             kind: "bug".into(),
             state: "doing".into(),
+            substate: None,
             urgency: "urgent".into(),
             size: Some("m".into()),
             parent_id: Some("bn-parent".into()),
+            due: None,
+            custom: BTreeMap::new(),
             labels: vec!["backend".into(), "auth".into()],
             assignees: vec!["alice".into()],
             depends_on: vec!["bn-001".into()],
             dependents: vec!["bn-002".into()],
-            comments: vec![ShowComment {
-                author: "alice".into(),
-                body: "Looking into it.".into(),
-                created_at_us: 1000,
-            }],
+            comments: vec![show_comment("alice", "Looking into it.", 1000)],
+            conflicts: vec![],
             created_at_us: 500,
             updated_at_us: 2000,
         }
@@ -473,14 +668,18 @@ mod tests {
             description: None,
             kind: "task".into(),
             state: "open".into(),
+            substate: None,
             urgency: "default".into(),
             size: None,
             parent_id: None,
+            due: None,
+            custom: BTreeMap::new(),
             labels: vec![],
             assignees: vec![],
             depends_on: vec![],
             dependents: vec![],
             comments: vec![],
+            conflicts: vec![],
             created_at_us: 100,
             updated_at_us: 200,
         };
@@ -494,31 +693,73 @@ mod tests {
         assert!(!out.contains("labels"));
     }
 
+    fn query_comment(
+        id: i64,
+        author: &str,
+        ts: i64,
+        reply_to: Option<&str>,
+    ) -> query::QueryComment {
+        query::QueryComment {
+            comment_id: id,
+            item_id: "bn-1".into(),
+            event_hash: format!("blake3:{id}"),
+            author: author.into(),
+            body: format!("comment {id}"),
+            created_at_us: ts,
+            original_body: format!("comment {id}"),
+            reply_to: reply_to.map(str::to_string),
+            edited_at_us: None,
+            retracted_by: None,
+        }
+    }
+
     #[test]
-    fn timeline_comments_sorted_oldest_first() {
+    fn threaded_comments_nest_replies_under_parent() {
         let input = vec![
-            query::QueryComment {
-                comment_id: 2,
-                item_id: "bn-1".into(),
-                event_hash: "blake3:b".into(),
-                author: "bob".into(),
-                body: "second".into(),
-                created_at_us: 2_000,
-            },
-            query::QueryComment {
-                comment_id: 1,
-                item_id: "bn-1".into(),
-                event_hash: "blake3:a".into(),
-                author: "alice".into(),
-                body: "first".into(),
-                created_at_us: 1_000,
-            },
+            query_comment(3, "carol", 3_000, Some("blake3:1")),
+            query_comment(2, "bob", 2_000, None),
+            query_comment(1, "alice", 1_000, None),
         ];
 
-        let out = timeline_comments(input);
-        assert_eq!(out.len(), 2);
-        assert_eq!(out[0].author, "alice");
-        assert_eq!(out[1].author, "bob");
+        let out = threaded_comments(input, vec![]);
+        let order: Vec<(&str, usize)> = out.iter().map(|c| (c.author.as_str(), c.depth)).collect();
+        assert_eq!(order, vec![("alice", 0), ("carol", 1), ("bob", 0)]);
+        assert_eq!(out[1].reply_to.as_deref(), Some("blake3:1"));
+    }
+
+    #[test]
+    fn threaded_comments_attach_history_and_hide_retracted() {
+        let mut edited = query_comment(1, "alice", 1_000, None);
+        edited.body = "fixed typo".into();
+        edited.edited_at_us = Some(5_000);
+        let mut retracted = query_comment(2, "bob", 2_000, None);
+        retracted.retracted_by = Some("bob".into());
+        let mut parent = query_comment(3, "bob", 3_000, None);
+        parent.retracted_by = Some("bob".into());
+        let reply = query_comment(4, "alice", 4_000, Some("blake3:3"));
+
+        let edit = query::QueryCommentEdit {
+            event_hash: "blake3:e1".into(),
+            comment_hash: "blake3:1".into(),
+            author: "alice".into(),
+            body: "fixed typo".into(),
+            itc: String::new(),
+            created_at_us: 5_000,
+            is_current: true,
+        };
+
+        let out = threaded_comments(vec![edited, retracted, parent, reply], vec![edit]);
+        let hashes: Vec<&str> = out.iter().map(|c| c.hash.as_str()).collect();
+        assert_eq!(hashes, vec!["blake3:1", "blake3:3", "blake3:4"]);
+
+        assert_eq!(out[0].body, "fixed typo");
+        let versions: Vec<&str> = out[0].history.iter().map(|v| v.body.as_str()).collect();
+        assert_eq!(versions, vec!["comment 1", "fixed typo"]);
+        assert_eq!(comment_marker(&out[0]), " (edited)");
+
+        assert_eq!(out[1].body, RETRACTED_PLACEHOLDER);
+        assert_eq!(comment_marker(&out[1]), " (retracted by bob)");
+        assert_eq!(out[2].depth, 1);
     }
 
     #[test]
@@ -531,6 +772,31 @@ mod tests {
         assert!(out.contains("] alice: Looking into it."));
     }
 
+    #[test]
+    fn render_show_text_lists_conflicts() {
+        let write = |hash: &str, agent: &str, value: &str| bones_core::conflicts::FieldWrite {
+            event_hash: hash.into(),
+            agent: agent.into(),
+            wall_ts_us: 1_000,
+            itc: String::new(),
+            value: serde_json::Value::from(value),
+        };
+        let mut item = make_show_item();
+        item.conflicts = vec![FieldConflict {
+            item_id: "bn-abc".into(),
+            field: "urgency".into(),
+            winner: write("blake3:b", "bob", "punt"),
+            losers: vec![write("blake3:a", "alice", "urgent")],
+        }];
+
+        let mut buf = Vec::new();
+        render_show_text(&item, &mut buf).expect("render text");
+        let out = String::from_utf8(buf).expect("utf8");
+        assert!(out.contains("Conflicts (1)"));
+        assert!(out.contains("kept \"punt\"  by bob"));
+        assert!(out.contains("lost \"urgent\"  by alice"));
+    }
+
     // -----------------------------------------------------------------------
     // resolve_item_id
     // -----------------------------------------------------------------------
@@ -669,6 +935,7 @@ mod tests {
         let (_dir, root) = setup_test_db();
         let args = ShowArgs {
             id: "bn-xyz789".into(),
+            at: None,
         };
         run_show(&args, OutputMode::Pretty, &root).unwrap();
     }
@@ -679,6 +946,7 @@ mod tests {
         // "xyz789" → "bn-xyz789"
         let args = ShowArgs {
             id: "xyz789".into(),
+            at: None,
         };
         run_show(&args, OutputMode::Pretty, &root).unwrap();
     }
@@ -687,7 +955,10 @@ mod tests {
     fn run_show_prefix_partial_id() {
         let (_dir, root) = setup_test_db();
         // "xyz" → prefix match → "bn-xyz789"
-        let args = ShowArgs { id: "xyz".into() };
+        let args = ShowArgs {
+            id: "xyz".into(),
+            at: None,
+        };
         run_show(&args, OutputMode::Pretty, &root).unwrap();
     }
 
@@ -696,6 +967,7 @@ mod tests {
         let (_dir, root) = setup_test_db();
         let args = ShowArgs {
             id: "bn-xyz789".into(),
+            at: None,
         };
         run_show(&args, OutputMode::Json, &root).unwrap();
     }
@@ -705,6 +977,7 @@ mod tests {
         let (_dir, root) = setup_test_db();
         let args = ShowArgs {
             id: "nonexistent".into(),
+            at: None,
         };
         assert!(run_show(&args, OutputMode::Pretty, &root).is_err());
     }
@@ -714,6 +987,7 @@ mod tests {
         let dir = tempfile::tempdir().expect("tempdir");
         let args = ShowArgs {
             id: "bn-001".into(),
+            at: None,
         };
         assert!(run_show(&args, OutputMode::Pretty, dir.path()).is_err());
     }
@@ -726,18 +1000,18 @@ mod tests {
             description: Some("Desc".into()),
             kind: "task".into(),
             state: "open".into(),
+            substate: None,
             urgency: "default".into(),
             size: None,
             parent_id: None,
+            due: None,
+            custom: BTreeMap::new(),
             labels: vec!["auth".into()],
             assignees: vec!["alice".into()],
             depends_on: vec!["bn-001".into()],
             dependents: vec![],
-            comments: vec![ShowComment {
-                author: "alice".into(),
-                body: "LGTM".into(),
-                created_at_us: 1000,
-            }],
+            comments: vec![show_comment("alice", "LGTM", 1000)],
+            conflicts: vec![],
             created_at_us: 500,
             updated_at_us: 1000,
         };
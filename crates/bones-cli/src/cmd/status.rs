@@ -3,11 +3,17 @@
 //! Like `git status` for work management: shows what you're working on,
 //! what's assigned to you, and project-level counts. Designed to be the
 //! first command after crash/restart.
+//!
+//! It also lists merge reports the git merge driver wrote for fields both
+//! sides of a merge changed differently, until they are acknowledged with
+//! `--ack <ID>` or `--ack-all`.
 
 use std::io::Write;
 use std::path::Path;
 
+use anyhow::Context as _;
 use bones_core::db::query::{self, ItemFilter};
+use bones_core::sync::merge_report::{self, MergeReport, MergeReportError};
 use clap::Args;
 use serde::Serialize;
 
@@ -16,7 +22,15 @@ use crate::output::{CliError, OutputMode, render_error, render_mode};
 
 /// Arguments for `bn status`.
 #[derive(Args, Debug, Default)]
-pub struct StatusArgs {}
+pub struct StatusArgs {
+    /// Acknowledge a merge report by ID so it is no longer shown (repeatable).
+    #[arg(long, value_name = "ID")]
+    pub ack: Vec<String>,
+
+    /// Acknowledge every pending merge report.
+    #[arg(long, conflicts_with = "ack")]
+    pub ack_all: bool,
+}
 
 /// Project-level status counts.
 #[derive(Debug, Serialize)]
@@ -44,11 +58,13 @@ struct StatusOutput {
     agent: Option<String>,
     assigned: Vec<AssignedItem>,
     project: ProjectCounts,
+    /// Unacknowledged reports of overlapping writes from git merges.
+    merge_reports: Vec<MergeReport>,
 }
 
 /// Execute `bn status`.
 pub fn run_status(
-    _args: &StatusArgs,
+    args: &StatusArgs,
     agent_flag: Option<&str>,
     output: OutputMode,
     project_root: &Path,
@@ -68,6 +84,36 @@ pub fn run_status(
         anyhow::bail!("projection not found");
     };
 
+    let bones_dir = project_root.join(".bones");
+    let ack: Vec<String> = if args.ack_all {
+        merge_report::pending_reports(&bones_dir)
+            .context("failed to read merge reports")?
+            .into_iter()
+            .map(|report| report.id)
+            .collect()
+    } else {
+        args.ack.clone()
+    };
+    for id in &ack {
+        match merge_report::acknowledge(&bones_dir, id) {
+            Ok(()) => {}
+            Err(err @ MergeReportError::NotFound(_)) => {
+                render_error(
+                    output,
+                    &CliError::with_details(
+                        err.to_string(),
+                        "run `bn status` to list pending merge reports",
+                        "merge_report_not_found",
+                    ),
+                )?;
+                anyhow::bail!(err);
+            }
+            Err(err) => return Err(err).context("failed to acknowledge merge report"),
+        }
+    }
+    let merge_reports =
+        merge_report::pending_reports(&bones_dir).context("failed to read merge reports")?;
+
     // Try to resolve agent identity (optional — status works without it).
     let resolved_agent = agent::resolve_agent(agent_flag);
 
@@ -75,15 +121,20 @@ pub fn run_status(
     let assigned = if let Some(ref agent_id) = resolved_agent {
         let filter = ItemFilter {
             state: None,
+            substate: None,
             kind: None,
             label: None,
             urgency: None,
             parent_id: None,
+            due_before: None,
+            custom_fields: Vec::new(),
+            query: None,
             assignee: Some(agent_id.clone()),
             include_deleted: false,
             limit: None,
             offset: None,
             sort: Default::default(),
+            sort_custom: None,
         };
         let items = query::list_items(&conn, &filter)?;
         items
@@ -104,15 +155,20 @@ pub fn run_status(
     let count_state = |state: &str| -> u64 {
         let filter = ItemFilter {
             state: Some(state.to_string()),
+            substate: None,
             kind: None,
             label: None,
             urgency: None,
             parent_id: None,
+            due_before: None,
+            custom_fields: Vec::new(),
+            query: None,
             assignee: None,
             include_deleted: false,
             limit: None,
             offset: None,
             sort: Default::default(),
+            sort_custom: None,
         };
         query::count_items(&conn, &filter).unwrap_or(0)
     };
@@ -135,6 +191,7 @@ pub fn run_status(
             archived: archived_count,
             blocked: blocked_count,
         },
+        merge_reports,
     };
 
     render_mode(
@@ -210,6 +267,33 @@ fn render_status_human(report: &StatusOutput, w: &mut dyn Write) -> std::io::Res
         report.project.blocked
     )?;
 
+    // Merge reports awaiting acknowledgement.
+    if !report.merge_reports.is_empty() {
+        writeln!(w)?;
+        writeln!(
+            w,
+            "Merge reports: {} awaiting acknowledgement",
+            report.merge_reports.len()
+        )?;
+        writeln!(w, "{:-<72}", "")?;
+        for merge in &report.merge_reports {
+            writeln!(w, "Report {}", merge.id)?;
+            for conflict in &merge.conflicts {
+                writeln!(
+                    w,
+                    "  {:<16}  {:<10}  ours={} ({})  theirs={} ({})",
+                    conflict.item_id,
+                    conflict.field,
+                    conflict.ours.value,
+                    conflict.ours.agent,
+                    conflict.theirs.value,
+                    conflict.theirs.agent,
+                )?;
+            }
+        }
+        writeln!(w, "Acknowledge with `bn status --ack <ID>` or `--ack-all`.")?;
+    }
+
     Ok(())
 }
 
@@ -236,7 +320,22 @@ fn render_status_text(report: &StatusOutput, w: &mut dyn Write) -> std::io::Resu
         report.project.done,
         report.project.archived,
         report.project.blocked
-    )
+    )?;
+
+    for merge in &report.merge_reports {
+        for conflict in &merge.conflicts {
+            writeln!(
+                w,
+                "{}  merge-report  {}  {}  ours={}  theirs={}",
+                merge.id,
+                conflict.item_id,
+                conflict.field,
+                conflict.ours.value,
+                conflict.theirs.value
+            )?;
+        }
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -258,7 +357,7 @@ mod tests {
     #[test]
     fn status_empty_project() {
         let (dir, _conn) = setup_db();
-        let args = StatusArgs {};
+        let args = StatusArgs::default();
         let result = run_status(&args, Some("test-agent"), OutputMode::Json, dir.path());
         assert!(result.is_ok());
     }
@@ -292,7 +391,7 @@ mod tests {
 
         drop(conn);
 
-        let args = StatusArgs {};
+        let args = StatusArgs::default();
         let result = run_status(&args, Some("test-agent"), OutputMode::Json, dir.path());
         assert!(result.is_ok());
     }
@@ -322,6 +421,7 @@ mod tests {
                 archived: 15,
                 blocked: 3,
             },
+            merge_reports: vec![],
         };
 
         let mut out = Vec::new();
@@ -351,6 +451,7 @@ mod tests {
                 archived: 0,
                 blocked: 0,
             },
+            merge_reports: vec![],
         };
 
         let mut out = Vec::new();
@@ -359,6 +460,69 @@ mod tests {
         assert!(rendered.contains("Agent: (none"));
     }
 
+    fn sample_report() -> MergeReport {
+        let write = |agent: &str, value: &str| bones_core::conflicts::FieldWrite {
+            event_hash: format!("blake3:{agent}"),
+            agent: agent.to_string(),
+            wall_ts_us: 1000,
+            itc: String::new(),
+            value: value.into(),
+        };
+        MergeReport::new(
+            vec![merge_report::OverlappingWrite {
+                item_id: "bn-a1".to_string(),
+                field: "state".to_string(),
+                ours: write("alice", "doing"),
+                theirs: write("bob", "done"),
+            }],
+            2000,
+        )
+    }
+
+    #[test]
+    fn status_shows_merge_reports_until_acknowledged() {
+        let (dir, _conn) = setup_db();
+        let bones_dir = dir.path().join(".bones");
+        let report = sample_report();
+        merge_report::write_report(&bones_dir, &report).expect("write report");
+
+        let mut out = Vec::new();
+        let payload = StatusOutput {
+            agent: None,
+            assigned: vec![],
+            project: ProjectCounts {
+                open: 1,
+                doing: 0,
+                done: 0,
+                archived: 0,
+                blocked: 0,
+            },
+            merge_reports: merge_report::pending_reports(&bones_dir).expect("pending"),
+        };
+        render_status_human(&payload, &mut out).expect("render");
+        let rendered = String::from_utf8(out).expect("utf8");
+        assert!(rendered.contains("Merge reports: 1 awaiting acknowledgement"));
+        assert!(rendered.contains(&format!("Report {}", report.id)));
+        assert!(rendered.contains(r#"ours="doing" (alice)  theirs="done" (bob)"#));
+
+        let args = StatusArgs {
+            ack: vec![report.id.clone()],
+            ack_all: false,
+        };
+        run_status(&args, None, OutputMode::Json, dir.path()).expect("ack");
+        assert!(
+            merge_report::pending_reports(&bones_dir)
+                .expect("pending")
+                .is_empty()
+        );
+
+        let args = StatusArgs {
+            ack: vec![report.id],
+            ack_all: false,
+        };
+        assert!(run_status(&args, None, OutputMode::Json, dir.path()).is_err());
+    }
+
     #[test]
     fn count_blocked_items_empty_db() {
         let (_dir, conn) = setup_db();
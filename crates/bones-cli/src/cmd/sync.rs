@@ -1,12 +1,50 @@
 //! `bn sync` — pull/rebuild/push workflow with git configuration management.
+//!
+//! `bn sync --remote <COMMAND>` skips git entirely: it spawns `COMMAND`
+//! (typically `ssh host 'cd repo && bn sync-serve --stdio'`), runs the
+//! Prolly Tree sync protocol over the child's stdin/stdout, and appends the
+//! events it receives. `bn sync-serve --stdio` is the serving half, in the
+//! way `git-upload-pack` serves `git fetch`.
+//!
+//! `--goal`, `--label`, and `--item` pull only part of the project: the
+//! server resolves the filter (plus the blockers and parents those items
+//! reference) and both sides sync just those items' events. The filter is
+//! recorded in `.bones/replication.toml`, reused by later syncs, and widened
+//! by further filtered ones; `--full` pulls everything and clears it.
+//!
+//! A `git pull` that fast-forwards never invokes the merge driver, so after
+//! pulling `bn sync` screens every event the pull added against
+//! `.bones/policy.toml` itself: refused events are quarantined and removed
+//! from the shards, leaving the rewrite to be committed. A bare `git pull`
+//! gets no such screening.
+//!
+//! With `[sync] events = "ref"` in `.bones/config.toml`, shards stay out of
+//! branches altogether: `bn sync` fetches `refs/bones/events` from the
+//! configured remote, union-merges it with the local shards, and pushes the
+//! ref back, without pulling or pushing any branch.
 
 use anyhow::{Context as _, Result};
+use bones_core::config::{EventStorage, load_project_config};
+use bones_core::event::Event;
+use bones_core::event::writer::write_line;
+use bones_core::policy::{self, Policy};
+use bones_core::shard::ShardManager;
+use bones_core::sync::filter::ReplicationFilter;
+use bones_core::sync::framed::FramedTransport;
+use bones_core::sync::git_ref::{EVENTS_REF, GitRefStore, merge_into_ref};
+use bones_core::sync::index::{self, ProllyIndex, ShardEventStore};
+use bones_core::sync::protocol::{self, serve_sync_with_store, sync_with_store};
 use clap::Args;
 use serde::Serialize;
-use std::io::Write as _;
+use std::collections::{BTreeSet, HashSet};
+use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::Path;
 use std::process::{Command, Stdio};
+use std::time::Duration;
 
+use crate::cmd::bones_gitignore::ensure_events_ignored;
+use crate::cmd::bundle::{append_events, load_events};
+use crate::cmd::do_cmd::find_bones_dir;
 use crate::output::{OutputMode, pretty_kv, pretty_section};
 
 /// Result of a `bn sync` run.
@@ -16,6 +54,9 @@ pub struct SyncReport {
     pub pulled: bool,
     /// Number of event lines merged (from git pull output; heuristic).
     pub events_merged: usize,
+    /// Pulled events refused by `.bones/policy.toml`, quarantined and
+    /// removed from the shards.
+    pub events_quarantined: usize,
     /// Whether `bn admin rebuild --incremental` succeeded.
     pub rebuilt: bool,
     /// Whether `git push` succeeded.
@@ -35,6 +76,97 @@ pub struct SyncArgs {
     /// Skip `git push` after rebuilding.
     #[arg(long)]
     pub no_push: bool,
+
+    /// Sync directly with the replica served by this shell command instead
+    /// of going through git, e.g. "ssh host 'cd repo && bn sync-serve --stdio'".
+    #[arg(long, value_name = "COMMAND", conflicts_with_all = ["config_only", "no_push"])]
+    pub remote: Option<String>,
+
+    /// With --remote, only pull the subtree of this goal (repeatable).
+    #[arg(long = "goal", value_name = "ID", requires = "remote")]
+    pub goals: Vec<String>,
+
+    /// With --remote, only pull items carrying this label (repeatable).
+    #[arg(long = "label", value_name = "LABEL", requires = "remote")]
+    pub labels: Vec<String>,
+
+    /// With --remote, only pull this item (repeatable).
+    #[arg(long = "item", value_name = "ID", requires = "remote")]
+    pub items: Vec<String>,
+
+    /// With --remote, pull every item and stop being a partial replica.
+    #[arg(long, requires = "remote", conflicts_with_all = ["goals", "labels", "items"])]
+    pub full: bool,
+}
+
+impl SyncArgs {
+    /// The replication filter given on the command line.
+    fn filter(&self) -> ReplicationFilter {
+        ReplicationFilter {
+            goals: self.goals.clone(),
+            labels: self.labels.clone(),
+            items: self.items.clone(),
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct SyncServeArgs {
+    /// Serve the sync protocol on stdin/stdout.
+    #[arg(long, required = true)]
+    pub stdio: bool,
+}
+
+/// Result of a `bn sync --remote` run.
+#[derive(Debug, Default, Serialize)]
+pub struct RemoteSyncReport {
+    /// Command used to reach the remote replica.
+    pub remote: String,
+    /// Events sent to the remote.
+    pub events_sent: usize,
+    /// New events received from the remote.
+    pub events_received: usize,
+    /// Protocol rounds used.
+    pub rounds: usize,
+    /// Frame bytes written to the remote.
+    pub bytes_sent: u64,
+    /// Frame bytes read from the remote.
+    pub bytes_received: u64,
+    /// Events applied to the local projection.
+    pub events_applied: usize,
+    /// Received events refused by `.bones/policy.toml` and quarantined.
+    pub events_quarantined: usize,
+    /// Filter this replica syncs through, if it is partial.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<ReplicationFilter>,
+    /// Items the filter resolved to on the remote.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items_in_scope: Option<usize>,
+}
+
+/// Result of a `bn sync` run when events are kept on `refs/bones/events`.
+#[derive(Debug, Default, Serialize)]
+pub struct RefSyncReport {
+    /// Git remote the event ref was synced with.
+    pub remote: String,
+    /// Whether the remote's event ref was fetched.
+    pub fetched: bool,
+    /// Events from the remote ref that were new to this replica.
+    pub events_received: usize,
+    /// Events on the local ref that the remote ref lacked.
+    pub events_published: usize,
+    /// Events applied to the local projection.
+    pub events_applied: usize,
+    /// Remote events refused by `.bones/policy.toml` and quarantined.
+    pub events_quarantined: usize,
+    /// Commit the local event ref points at.
+    pub head: Option<String>,
+    /// Whether the event ref was pushed.
+    pub pushed: bool,
+    /// Hard errors collected during the run.
+    pub errors: Vec<String>,
+    /// Non-fatal warnings collected during the run.
+    pub warnings: Vec<String>,
 }
 
 // ─── public API ─────────────────────────────────────────────────────────────
@@ -42,16 +174,38 @@ pub struct SyncArgs {
 /// Orchestrate `git pull` → `bn admin rebuild --incremental` → `git push`.
 ///
 /// Each step is attempted in order. If `git pull` fails the workflow still
-/// continues so callers can see the full picture.
+/// continues so callers can see the full picture. Events the pull added are
+/// screened against `.bones/policy.toml` before the rebuild.
+///
+/// # Errors
+///
+/// Returns an error if the policy or the shards cannot be read, or refused
+/// events cannot be quarantined.
 pub fn sync_workflow(repo_dir: &Path, no_push: bool) -> Result<SyncReport> {
     let mut report = SyncReport::default();
+    let bones_dir = repo_dir.join(".bones");
 
     // Step 1: git pull
     if has_tracking_upstream(repo_dir)? {
+        let policy = Policy::load(&bones_dir).context("failed to load .bones/policy.toml")?;
+        let before = if policy.is_empty() {
+            HashSet::new()
+        } else {
+            event_hashes(&bones_dir)?
+        };
         match run_git_pull(repo_dir) {
             Ok(events_merged) => {
                 report.pulled = true;
                 report.events_merged = events_merged;
+                if !policy.is_empty() {
+                    report.events_quarantined = screen_pulled_events(&bones_dir, &policy, &before)?;
+                }
+                if report.events_quarantined > 0 {
+                    report.warnings.push(format!(
+                        "{} pulled event(s) refused by .bones/policy.toml were quarantined and removed from the shards; commit the shard changes",
+                        report.events_quarantined
+                    ));
+                }
             }
             Err(e) => {
                 report.errors.push(format!("git pull: {e}"));
@@ -94,6 +248,118 @@ pub fn sync_workflow(repo_dir: &Path, no_push: bool) -> Result<SyncReport> {
     Ok(report)
 }
 
+/// Fetch `refs/bones/events` from `remote`, union it with the local shards
+/// and ref, then push the ref back (skipped with `no_push`).
+///
+/// Branches are never pulled or pushed. Unlike [`sync_workflow`], the fetch
+/// and merge are prerequisites for the push, so a failed fetch stops the run.
+///
+/// # Errors
+///
+/// Returns an error if the local shards or event ref cannot be read or
+/// written; remote failures are collected in the report.
+pub fn ref_sync_workflow(
+    project_root: &Path,
+    remote: &str,
+    no_push: bool,
+) -> Result<RefSyncReport> {
+    let bones_dir = project_root.join(".bones");
+    let store = GitRefStore::new(project_root);
+    let mut report = RefSyncReport {
+        remote: remote.to_string(),
+        ..RefSyncReport::default()
+    };
+
+    if tracks_event_shards(project_root) {
+        report.warnings.push(
+            "event shards are still tracked on branches; run `git rm -r --cached .bones/events` to stop committing them"
+                .to_string(),
+        );
+    }
+
+    let has_remote = store.has_remote(remote)?;
+    let fetched = if has_remote {
+        match store.fetch(remote) {
+            Ok(commit) => {
+                report.fetched = true;
+                commit
+            }
+            Err(e) => {
+                report.errors.push(format!("git fetch {EVENTS_REF}: {e}"));
+                return Ok(report);
+            }
+        }
+    } else {
+        report.warnings.push(format!(
+            "fetch and push skipped: no git remote named `{remote}` (set sync.remote)"
+        ));
+        None
+    };
+
+    let local = load_events(&bones_dir)?;
+    let policy = Policy::load(&bones_dir).context("failed to load .bones/policy.toml")?;
+    let merged = merge_into_ref(&store, &local, fetched.as_deref(), &policy)
+        .with_context(|| format!("failed to update {EVENTS_REF}"))?;
+    policy::quarantine(&bones_dir, &merged.rejected)
+        .context("failed to quarantine rejected events")?;
+    let received = append_events(&bones_dir, &merged.missing_locally)?;
+    report.events_received = merged.missing_locally.len();
+    report.events_applied = received.apply.events_applied;
+    report.events_quarantined = merged.rejected.len() + received.quarantined;
+    report.events_published = merged.unpublished;
+    report.head = merged.head;
+
+    if has_remote && !no_push && report.head.is_some() && report.head != fetched {
+        match store.push(remote) {
+            Ok(()) => report.pushed = true,
+            Err(e) => report
+                .errors
+                .push(format!("git push {EVENTS_REF}: {e}; run `bn sync` again")),
+        }
+    }
+
+    Ok(report)
+}
+
+/// Copy events on `refs/bones/events` that the local shards lack into the
+/// active shard, so a rebuild sees the whole log.
+///
+/// Returns `None` when the project keeps its events in tracked files.
+///
+/// # Errors
+///
+/// Returns an error if the config, shards, or event ref cannot be read, or
+/// appending fails.
+pub fn restore_from_ref(project_root: &Path) -> Result<Option<usize>> {
+    let config = load_project_config(project_root)?;
+    if config.sync.events != EventStorage::Ref {
+        return Ok(None);
+    }
+    let store = GitRefStore::new(project_root);
+    let Some(head) = store.resolve(EVENTS_REF)? else {
+        return Ok(Some(0));
+    };
+
+    let bones_dir = project_root.join(".bones");
+    let shards = ShardManager::new(&bones_dir);
+    let local: BTreeSet<String> = load_events(&bones_dir)?
+        .into_iter()
+        .map(|e| e.event_hash)
+        .collect();
+    let mut restored = 0;
+    for event in store.read_events(&head)? {
+        if local.contains(&event.event_hash) {
+            continue;
+        }
+        let line = write_line(&event).context("failed to serialize event from the ref")?;
+        shards
+            .append(&line, false, Duration::from_secs(5))
+            .context("failed to append event from the ref")?;
+        restored += 1;
+    }
+    Ok(Some(restored))
+}
+
 /// Ensure `.gitattributes` contains the bones-events merge driver entry.
 ///
 /// If the file exists the function appends the line only when it is not already
@@ -174,11 +440,182 @@ pub fn ensure_gitignore(repo_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Sync with the replica served by `remote` over the framed stdio protocol.
+///
+/// A non-empty `requested` filter is merged into the replica's recorded one;
+/// `full` drops the recorded filter.
+///
+/// # Errors
+///
+/// Returns an error if the command cannot be spawned, the protocol fails,
+/// or received events cannot be written.
+pub fn remote_sync(
+    project_root: &Path,
+    remote: &str,
+    requested: &ReplicationFilter,
+    full: bool,
+) -> Result<RemoteSyncReport> {
+    let bones_dir = project_root.join(".bones");
+    let recorded = ReplicationFilter::load_marker(&bones_dir)?;
+    let filter = match recorded {
+        _ if full => ReplicationFilter::default(),
+        Some(recorded) => recorded.union(requested),
+        None => requested.clone(),
+    };
+    let local = load_index(&bones_dir)?;
+
+    let mut child = shell_command(remote)
+        .current_dir(project_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .with_context(|| format!("Failed to spawn `{remote}`"))?;
+    let stdin = child.stdin.take().context("remote stdin unavailable")?;
+    let stdout = child.stdout.take().context("remote stdout unavailable")?;
+
+    let mut transport = FramedTransport::new(BufReader::new(stdout), BufWriter::new(stdin));
+    let result = client_session(&mut transport, &bones_dir, &local, &filter);
+    let (bytes_sent, bytes_received) = (transport.bytes_sent(), transport.bytes_received());
+    // Closing our end of the pipes lets the server exit.
+    drop(transport);
+    let status = child
+        .wait()
+        .with_context(|| format!("Failed to wait for `{remote}`"))?;
+
+    let (received, report, items_in_scope) = match result {
+        Ok(done) => done,
+        Err(e) if status.success() => anyhow::bail!("sync with `{remote}` failed: {e:#}"),
+        Err(e) => anyhow::bail!("sync with `{remote}` failed ({status}): {e:#}"),
+    };
+    let appended = append_events(&bones_dir, &received)?;
+    if filter.is_empty() {
+        ReplicationFilter::clear_marker(&bones_dir)?;
+    } else {
+        filter.save_marker(&bones_dir)?;
+    }
+
+    Ok(RemoteSyncReport {
+        remote: remote.to_string(),
+        events_sent: report.events_sent,
+        events_received: report.events_received,
+        rounds: report.rounds,
+        bytes_sent,
+        bytes_received,
+        events_applied: appended.apply.events_applied,
+        events_quarantined: appended.quarantined,
+        filter: (!filter.is_empty()).then_some(filter),
+        items_in_scope,
+    })
+}
+
+/// The client half of a session: handshake, filter exchange, then the sync
+/// protocol over the items in scope.
+fn client_session<R: BufRead, W: Write>(
+    transport: &mut FramedTransport<R, W>,
+    bones_dir: &Path,
+    local: &ProllyIndex,
+    filter: &ReplicationFilter,
+) -> Result<(Vec<Event>, protocol::SyncReport, Option<usize>)> {
+    transport.handshake_client()?;
+    transport.send_filter(filter)?;
+    let scoped;
+    let (index, items_in_scope) = if filter.is_empty() {
+        (local, None)
+    } else {
+        let items = transport.recv_item_ids()?;
+        scoped = local.restricted(&items);
+        (&scoped, Some(items.len()))
+    };
+    let mut store = ShardEventStore::new(bones_dir, index);
+    let (received, report) = sync_with_store(&index.tree(), &mut store, transport)?;
+    Ok((received, report, items_in_scope))
+}
+
+/// Bring the persisted prolly index up to date with the shards, so a sync
+/// only reads events this replica has not indexed yet.
+fn load_index(bones_dir: &Path) -> Result<ProllyIndex> {
+    let (index, report) = index::refresh(bones_dir).context("failed to update the prolly index")?;
+    if let Some(reason) = report.rebuilt {
+        tracing::debug!("rebuilt prolly index: {reason}");
+    }
+    Ok(index)
+}
+
+/// Resolve a client's filter against this replica's projection.
+fn resolve_filter(bones_dir: &Path, filter: &ReplicationFilter) -> Result<BTreeSet<String>> {
+    let conn = bones_core::db::ensure_projection(bones_dir)?
+        .context("the serving replica has no projection database")?;
+    Ok(filter.resolve(&conn, &ShardManager::new(bones_dir))?)
+}
+
+/// Entry point for `bn sync-serve --stdio`: answer one sync session on
+/// stdin/stdout. Diagnostics go to stderr so they never corrupt the stream.
+///
+/// # Errors
+///
+/// Returns an error if the handshake, protocol, or event writes fail. Errors
+/// after the handshake are also reported to the client.
+pub fn run_sync_serve(_args: &SyncServeArgs, current_dir: &Path) -> Result<()> {
+    let mut transport = FramedTransport::new(
+        std::io::stdin().lock(),
+        BufWriter::new(std::io::stdout().lock()),
+    );
+    transport
+        .handshake_server()
+        .context("sync handshake failed")?;
+    let filter = transport.recv_filter().context("sync handshake failed")?;
+
+    let loaded = find_bones_dir(current_dir)
+        .context("not a bones project (`.bones` directory not found)")
+        .and_then(|bones_dir| {
+            let local = load_index(&bones_dir)?;
+            if filter.is_empty() {
+                return Ok((bones_dir, local, None));
+            }
+            let items = resolve_filter(&bones_dir, &filter)?;
+            let scoped = local.restricted(&items);
+            Ok((bones_dir, scoped, Some(items)))
+        });
+    let (bones_dir, local, items) = match loaded {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            let _ = transport.send_error(&format!("{e:#}"));
+            return Err(e);
+        }
+    };
+    if let Some(items) = &items {
+        transport.send_item_ids(items)?;
+    }
+
+    let mut store = ShardEventStore::new(&bones_dir, &local);
+    let (received, _) = serve_sync_with_store(&local.tree(), &mut store, &mut transport)
+        .context("sync session failed")?;
+    drop(transport);
+    append_events(&bones_dir, &received)?;
+    Ok(())
+}
+
 /// Entry point wired from `main.rs`.
 pub fn run_sync(args: &SyncArgs, output: OutputMode, project_root: &Path) -> Result<()> {
+    if let Some(remote) = &args.remote {
+        let report = remote_sync(project_root, remote, &args.filter(), args.full)?;
+        if output.is_json() {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            print_remote_report(&report, output);
+        }
+        return Ok(());
+    }
+
     // Always ensure git configuration is up-to-date
+    let config = load_project_config(project_root)?;
     ensure_gitattributes(project_root).context("Failed to update .gitattributes")?;
     ensure_gitignore(project_root).context("Failed to update .gitignore")?;
+    if config.sync.events == EventStorage::Ref {
+        ensure_events_ignored(&project_root.join(".bones"))
+            .context("Failed to update .bones/.gitignore")?;
+    }
 
     let json = output.is_json();
 
@@ -215,6 +652,22 @@ pub fn run_sync(args: &SyncArgs, output: OutputMode, project_root: &Path) -> Res
         );
     }
 
+    if config.sync.events == EventStorage::Ref {
+        let report = ref_sync_workflow(project_root, &config.sync.remote, args.no_push)?;
+        if json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            print_ref_report(&report, output);
+        }
+        if !report.errors.is_empty() {
+            anyhow::bail!(
+                "Sync completed with errors:\n{}",
+                report.errors.join("\n  ")
+            );
+        }
+        return Ok(());
+    }
+
     let report = sync_workflow(project_root, args.no_push)?;
 
     if json {
@@ -236,6 +689,30 @@ pub fn run_sync(args: &SyncArgs, output: OutputMode, project_root: &Path) -> Res
 
 // ─── private helpers ─────────────────────────────────────────────────────────
 
+fn event_hashes(bones_dir: &Path) -> Result<HashSet<String>> {
+    Ok(load_events(bones_dir)?
+        .into_iter()
+        .map(|event| event.event_hash)
+        .collect())
+}
+
+/// Quarantine and remove the events a pull added that `policy` refuses,
+/// returning how many there were.
+fn screen_pulled_events(
+    bones_dir: &Path,
+    policy: &Policy,
+    before: &HashSet<String>,
+) -> Result<usize> {
+    let pulled: Vec<Event> = load_events(bones_dir)?
+        .into_iter()
+        .filter(|event| !before.contains(&event.event_hash))
+        .collect();
+    let (_, rejected) = policy.partition(pulled);
+    policy::quarantine(bones_dir, &rejected).context("failed to quarantine rejected events")?;
+    policy::expel(bones_dir, &rejected).context("failed to remove rejected events")?;
+    Ok(rejected.len())
+}
+
 fn run_git_pull(repo_dir: &Path) -> Result<usize> {
     let output = Command::new("git")
         .args(["pull", "--rebase"])
@@ -265,6 +742,7 @@ fn run_rebuild(repo_dir: &Path) -> Result<()> {
         .args(["admin", "rebuild", "--incremental"])
         .current_dir(repo_dir)
         .env("BONES_LOG", "error")
+        .stdout(Stdio::null())
         .status();
 
     match status {
@@ -314,6 +792,17 @@ fn has_push_target(repo_dir: &Path) -> Result<bool> {
     Ok(output.status.success())
 }
 
+/// Whether any event shard is still tracked in the index.
+fn tracks_event_shards(repo_dir: &Path) -> bool {
+    Command::new("git")
+        .args(["ls-files", "--", ".bones/events"])
+        .current_dir(repo_dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .is_ok_and(|output| output.status.success() && !output.stdout.is_empty())
+}
+
 fn is_git_work_tree(repo_dir: &Path) -> bool {
     let output = Command::new("git")
         .args(["rev-parse", "--is-inside-work-tree"])
@@ -329,6 +818,20 @@ fn is_git_work_tree(repo_dir: &Path) -> bool {
     output.status.success() && String::from_utf8_lossy(&output.stdout).trim() == "true"
 }
 
+#[cfg(unix)]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(not(unix))]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
 fn run_git_push(repo_dir: &Path) -> Result<()> {
     let output = Command::new("git")
         .args(["push"])
@@ -346,13 +849,142 @@ fn run_git_push(repo_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+fn print_remote_report(report: &RemoteSyncReport, output: OutputMode) {
+    match output {
+        OutputMode::Text => {
+            println!(
+                "sync remote={:?} sent={} received={} rounds={} bytes_sent={} bytes_received={}",
+                report.remote,
+                report.events_sent,
+                report.events_received,
+                report.rounds,
+                report.bytes_sent,
+                report.bytes_received
+            );
+            if report.events_quarantined > 0 {
+                println!("quarantined={}", report.events_quarantined);
+            }
+            if let Some(filter) = &report.filter {
+                println!(
+                    "partial filter={:?} items={}",
+                    filter.to_string(),
+                    report.items_in_scope.unwrap_or(0)
+                );
+            }
+        }
+        OutputMode::Pretty => {
+            let stdout = std::io::stdout();
+            let mut w = stdout.lock();
+            let _ = pretty_section(&mut w, "Sync Report");
+            let _ = pretty_kv(&mut w, "Remote", &report.remote);
+            let _ = pretty_kv(&mut w, "Sent", format!("{} event(s)", report.events_sent));
+            let _ = pretty_kv(
+                &mut w,
+                "Received",
+                format!("{} event(s)", report.events_received),
+            );
+            let _ = pretty_kv(
+                &mut w,
+                "Transfer",
+                format!(
+                    "{} B out, {} B in, {} round(s)",
+                    report.bytes_sent, report.bytes_received, report.rounds
+                ),
+            );
+            if report.events_quarantined > 0 {
+                let _ = pretty_kv(
+                    &mut w,
+                    "Quarantined",
+                    format!(
+                        "{} event(s) refused by .bones/policy.toml",
+                        report.events_quarantined
+                    ),
+                );
+            }
+            if let Some(filter) = &report.filter {
+                let _ = pretty_kv(
+                    &mut w,
+                    "Partial",
+                    format!("{filter} ({} item(s))", report.items_in_scope.unwrap_or(0)),
+                );
+            }
+        }
+        OutputMode::Json => {}
+    }
+}
+
+fn print_ref_report(report: &RefSyncReport, output: OutputMode) {
+    match output {
+        OutputMode::Text => {
+            println!(
+                "sync events_ref={} remote={} fetched={} received={} published={} quarantined={} pushed={} errors={}",
+                report.head.as_deref().unwrap_or("none"),
+                report.remote,
+                report.fetched,
+                report.events_received,
+                report.events_published,
+                report.events_quarantined,
+                report.pushed,
+                report.errors.len()
+            );
+            for err in &report.errors {
+                println!("error={err}");
+            }
+            for warning in &report.warnings {
+                println!("warning={warning}");
+            }
+        }
+        OutputMode::Pretty => {
+            let stdout = std::io::stdout();
+            let mut w = stdout.lock();
+            let _ = pretty_section(&mut w, "Sync Report");
+            let _ = pretty_kv(&mut w, "Events ref", EVENTS_REF);
+            let _ = pretty_kv(&mut w, "Remote", &report.remote);
+            let _ = pretty_kv(
+                &mut w,
+                "Fetch",
+                format!(
+                    "{} ({} new event(s), {} quarantined)",
+                    report.fetched, report.events_received, report.events_quarantined
+                ),
+            );
+            let _ = pretty_kv(
+                &mut w,
+                "Push",
+                format!(
+                    "{} ({} event(s) published)",
+                    report.pushed, report.events_published
+                ),
+            );
+
+            if !report.errors.is_empty() {
+                println!();
+                let _ = pretty_section(&mut w, "Errors");
+                for e in &report.errors {
+                    println!("- {e}");
+                }
+            }
+
+            if !report.warnings.is_empty() {
+                println!();
+                let _ = pretty_section(&mut w, "Warnings");
+                for wmsg in &report.warnings {
+                    println!("- {wmsg}");
+                }
+            }
+        }
+        OutputMode::Json => {}
+    }
+}
+
 fn print_report(report: &SyncReport, output: OutputMode) {
     match output {
         OutputMode::Text => {
             println!(
-                "sync pulled={} events_merged={} rebuilt={} pushed={} errors={}",
+                "sync pulled={} events_merged={} quarantined={} rebuilt={} pushed={} errors={}",
                 report.pulled,
                 report.events_merged,
+                report.events_quarantined,
                 report.rebuilt,
                 report.pushed,
                 report.errors.len()
@@ -372,8 +1004,8 @@ fn print_report(report: &SyncReport, output: OutputMode) {
                 &mut w,
                 "Pull",
                 format!(
-                    "{} ({} event file(s) merged)",
-                    report.pulled, report.events_merged
+                    "{} ({} event file(s) merged, {} event(s) quarantined)",
+                    report.pulled, report.events_merged, report.events_quarantined
                 ),
             );
             let _ = pretty_kv(&mut w, "Rebuild", report.rebuilt.to_string());
@@ -561,6 +1193,7 @@ mod tests {
         let report = SyncReport {
             pulled: true,
             events_merged: 3,
+            events_quarantined: 0,
             rebuilt: true,
             pushed: false,
             errors: vec!["git push: no remote".to_string()],
@@ -1,12 +1,12 @@
 //! `bn bone tag` and `bn bone untag` — add/remove labels from bones.
 
 use crate::agent;
+use crate::author::write_authored_event;
 use crate::itc_state::assign_next_itc;
 use crate::output::{CliError, OutputMode, render_error, render_mode};
 use crate::validate;
 use bones_core::db::query::{get_labels, try_open_projection};
 use bones_core::event::data::UpdateData;
-use bones_core::event::writer::write_event;
 use bones_core::event::{Event, EventData, EventType};
 use bones_core::model::item_id::ItemId;
 use bones_core::shard::ShardManager;
@@ -98,6 +98,7 @@ fn emit_label_event(
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
 
     {
@@ -116,7 +117,7 @@ fn emit_label_event(
 
         assign_next_itc(project_root, &mut event)?;
 
-        let line = write_event(&mut event)
+        let line = write_authored_event(&mut event)
             .map_err(|e| anyhow::anyhow!("failed to serialize event: {e}"))?;
 
         shard_mgr
@@ -657,6 +658,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         let line = write_event(&mut create_event).expect("write event");
         shard_mgr
@@ -5,11 +5,12 @@ use std::path::Path;
 use clap::Args;
 use serde::Serialize;
 
-use bones_core::db::query;
+use bones_core::config::load_project_config;
 
+use crate::cmd::as_of::{open_read_projection, pretty_replay_note};
 use crate::cmd::triage_support::{
     BlockingAncestor, RankedItem, blocking_ancestor_reason, build_triage_snapshot,
-    compute_blocking_ancestors,
+    compute_blocking_ancestors, warn_if_partial_replica,
 };
 use crate::output::{
     CliError, OutputMode, pretty_color_enabled, pretty_section, render_error, render_mode,
@@ -17,7 +18,14 @@ use crate::output::{
 
 /// Arguments for `bn triage`.
 #[derive(Args, Debug, Default)]
-pub struct TriageArgs {}
+pub struct TriageArgs {
+    /// Rank the backlog as it was at a point in the past.
+    ///
+    /// Accepts YYYY-MM-DD (end of that UTC day), RFC3339, an offset into
+    /// the past (3d, 2w), or an event hash prefix (blake3:...).
+    #[arg(long, value_name = "when")]
+    pub at: Option<String>,
+}
 
 #[derive(Debug, Clone, Serialize)]
 struct TriageRow {
@@ -38,13 +46,12 @@ struct TriageRow {
 /// - Cycles
 #[tracing::instrument(skip_all, name = "cmd.triage")]
 pub fn run_triage(
-    _args: &TriageArgs,
+    args: &TriageArgs,
     output: OutputMode,
     project_root: &Path,
 ) -> anyhow::Result<()> {
-    let db_path = project_root.join(".bones/bones.db");
-    let conn = if let Some(conn) = query::try_open_projection(&db_path)? {
-        conn
+    let read = if let Some(read) = open_read_projection(project_root, args.at.as_deref(), output)? {
+        read
     } else {
         render_error(
             output,
@@ -57,7 +64,10 @@ pub fn run_triage(
         anyhow::bail!("projection not found");
     };
 
-    let snapshot = build_triage_snapshot(&conn, chrono::Utc::now().timestamp_micros())?;
+    warn_if_partial_replica(project_root);
+    let triage_config = load_project_config(project_root).unwrap_or_default().triage;
+    let now_us = read.now_us();
+    let snapshot = build_triage_snapshot(&read.conn, now_us, &triage_config)?;
 
     let top_picks: Vec<&RankedItem> = snapshot.unblocked_ranked.iter().take(5).collect();
 
@@ -130,7 +140,6 @@ pub fn run_triage(
         .map(|item| (item.id.clone(), item.score))
         .collect();
 
-    let now_us = chrono::Utc::now().timestamp_micros();
     let rows = build_rows(
         &top_picks,
         &actionable_blockers,
@@ -163,6 +172,7 @@ pub fn run_triage(
             )
         },
         |_, w| {
+            pretty_replay_note(read.replay, w)?;
             render_triage_human(
                 w,
                 &top_picks,
@@ -1,8 +1,12 @@
 use anyhow::{Context, Result};
+use bones_core::config::TriageConfig;
 use bones_core::db::query::{self, ItemFilter, SortOrder};
-use bones_core::model::item::Urgency;
+use bones_core::model::item::{Size, Urgency};
+use bones_core::sync::filter::ReplicationFilter;
 use bones_triage::feedback::{load_agent_profile, sample_weights};
-use bones_triage::graph::{NormalizedGraph, RawGraph, compute_critical_path, find_all_cycles};
+use bones_triage::graph::{
+    NormalizedGraph, RawGraph, compute_weighted_critical_path, find_all_cycles,
+};
 use bones_triage::metrics::betweenness::betweenness_centrality;
 use bones_triage::metrics::eigenvector::eigenvector_centrality;
 use bones_triage::metrics::hits::hits;
@@ -10,7 +14,10 @@ use bones_triage::metrics::pagerank::{
     EdgeChange, EdgeChangeKind, PageRankConfig, PageRankMethod, PageRankResult, pagerank,
     pagerank_incremental,
 };
-use bones_triage::score::{CompositeWeights, MetricInputs, composite_score, normalize_metric};
+use bones_triage::score::{
+    CompositeWeights, DEADLINE_WEIGHT, MetricInputs, composite_score, deadline_component,
+    normalize_metric,
+};
 use petgraph::{Direction, visit::EdgeRef};
 use rand::SeedableRng;
 use rand::rngs::StdRng;
@@ -19,7 +26,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::hash::{Hash, Hasher};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::{debug, warn};
 
 const MICROS_PER_DAY: f64 = 86_400_000_000.0;
@@ -158,7 +165,22 @@ struct PageRankDiskCache {
     edges: Vec<(String, String)>,
 }
 
-pub fn build_triage_snapshot(conn: &Connection, now_us: i64) -> Result<TriageSnapshot> {
+/// Warn on stderr when triage runs on a partial replica, whose graph lacks
+/// every item outside its replication filter.
+pub fn warn_if_partial_replica(project_root: &Path) {
+    if let Ok(Some(filter)) = ReplicationFilter::load_marker(&project_root.join(".bones")) {
+        eprintln!(
+            "warning: partial replica ({filter}); triage only sees items pulled through \
+             this filter, so rankings elsewhere in the project are missing"
+        );
+    }
+}
+
+pub fn build_triage_snapshot(
+    conn: &Connection,
+    now_us: i64,
+    config: &TriageConfig,
+) -> Result<TriageSnapshot> {
     let all_items = query::list_items(
         conn,
         &ItemFilter {
@@ -215,9 +237,24 @@ pub fn build_triage_snapshot(conn: &Connection, now_us: i64) -> Result<TriageSna
     let direct_urgent_unblock_counts =
         compute_direct_urgent_unblocks(&raw_graph, &urgency_by_id, &unresolved_blockers);
 
+    // Critical path measures remaining work, so only active items carry a
+    // size-derived duration; finished items still in the graph count as zero.
+    let duration_by_id: HashMap<&str, usize> = active_items
+        .iter()
+        .map(|item| {
+            let size = item.size.as_deref().and_then(|s| s.parse::<Size>().ok());
+            (
+                item.item_id.as_str(),
+                config.size_durations.duration(size) as usize,
+            )
+        })
+        .collect();
+
     let mut normalized = NormalizedGraph::from_raw(raw_graph);
     normalized.condensed = normalized.reduced.clone();
-    let critical_path = compute_critical_path(&normalized);
+    let critical_path = compute_weighted_critical_path(&normalized, |id| {
+        duration_by_id.get(id).copied().unwrap_or(0)
+    });
     let pagerank_result = compute_pagerank(conn, &normalized);
     let betweenness = betweenness_centrality(&normalized);
     let hits_result = hits(&normalized, 100, 1e-6);
@@ -228,13 +265,16 @@ pub fn build_triage_snapshot(conn: &Connection, now_us: i64) -> Result<TriageSna
         .iter()
         .map(|item| item.item_id.clone())
         .collect();
+    // Weighted earliest start: the sized work that must finish before this
+    // item can begin. The item's own duration is left out so a large item is
+    // not promoted simply for being large.
     let cp_raw: Vec<f64> = ids
         .iter()
         .map(|id| {
             critical_path
                 .item_timings
                 .get(id)
-                .map_or(0.0, |timing| timing.earliest_finish as f64)
+                .map_or(0.0, |timing| timing.earliest_start as f64)
         })
         .collect();
     let pr_raw: Vec<f64> = ids
@@ -287,6 +327,9 @@ pub fn build_triage_snapshot(conn: &Connection, now_us: i64) -> Result<TriageSna
             } else {
                 0.0
             };
+            let due_in_days = item
+                .due_at_us
+                .map(|due_us| ((due_us - now_us) as f64) / MICROS_PER_DAY);
 
             let score = composite_score(
                 &MetricInputs {
@@ -295,6 +338,7 @@ pub fn build_triage_snapshot(conn: &Connection, now_us: i64) -> Result<TriageSna
                     betweenness: bc_norm[idx],
                     urgency,
                     decay_days,
+                    due_in_days,
                 },
                 &weights,
             );
@@ -322,7 +366,8 @@ pub fn build_triage_snapshot(conn: &Connection, now_us: i64) -> Result<TriageSna
                     URGENT_CHAIN_BLEND_WEIGHT * urgent_chain_norm[idx],
                 ),
                 ("urgency", weights.delta * urgency_component(urgency)),
-                ("decay", weights.epsilon * decay_component(decay_days))];
+                ("decay", weights.epsilon * decay_component(decay_days)),
+                ("deadline", DEADLINE_WEIGHT * deadline_component(due_in_days))];
             drivers.sort_by(|a, b| b.1.total_cmp(&a.1));
 
             let driver_a = drivers.first().map_or("priority", |(name, _)| *name);
@@ -1070,7 +1115,8 @@ mod tests {
         );
         insert_blocks_edge(&conn, "bn-ready", "bn-blocked");
 
-        let snapshot = build_triage_snapshot(&conn, 100).expect("snapshot");
+        let snapshot =
+            build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snapshot");
         let unblocked_ids: Vec<&str> = snapshot
             .unblocked_ranked
             .iter()
@@ -1081,13 +1127,73 @@ mod tests {
         assert!(!unblocked_ids.contains(&"bn-blocked"));
     }
 
+    #[test]
+    fn critical_path_input_is_weighted_by_blocker_size() {
+        let conn = test_db();
+        insert_item(
+            &conn,
+            "bn-big",
+            "Big blocker",
+            "open",
+            "default",
+            Some("xl"),
+            10,
+        );
+        insert_item(
+            &conn,
+            "bn-tiny",
+            "Tiny blocker",
+            "open",
+            "default",
+            Some("xs"),
+            10,
+        );
+        insert_item(
+            &conn,
+            "bn-after-big",
+            "After big",
+            "open",
+            "default",
+            None,
+            10,
+        );
+        insert_item(
+            &conn,
+            "bn-after-tiny",
+            "After tiny",
+            "open",
+            "default",
+            None,
+            10,
+        );
+        insert_blocks_edge(&conn, "bn-big", "bn-after-big");
+        insert_blocks_edge(&conn, "bn-tiny", "bn-after-tiny");
+
+        let snapshot =
+            build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snapshot");
+        let score = |id: &str| {
+            snapshot
+                .ranked
+                .iter()
+                .find(|item| item.id == id)
+                .map(|item| item.score)
+                .expect("ranked")
+        };
+
+        assert!(
+            score("bn-after-big") > score("bn-after-tiny"),
+            "item behind an xl blocker sits later on the weighted critical path"
+        );
+    }
+
     #[test]
     fn urgent_items_rank_above_default() {
         let conn = test_db();
         insert_item(&conn, "bn-default", "Default", "open", "default", None, 10);
         insert_item(&conn, "bn-urgent", "Urgent", "open", "urgent", None, 11);
 
-        let snapshot = build_triage_snapshot(&conn, 100).expect("snapshot");
+        let snapshot =
+            build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snapshot");
 
         assert_eq!(
             snapshot.ranked.first().map(|item| item.id.as_str()),
@@ -1180,7 +1286,8 @@ mod tests {
             "blocker should receive chain pressure from urgent descendant"
         );
 
-        let snapshot = build_triage_snapshot(&conn, 100).expect("snapshot");
+        let snapshot =
+            build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snapshot");
         let blocker = snapshot
             .ranked
             .iter()
@@ -1219,7 +1326,8 @@ mod tests {
             30,
         );
 
-        let snapshot = build_triage_snapshot(&conn, 100).expect("snapshot");
+        let snapshot =
+            build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snapshot");
         let ids: Vec<&str> = snapshot
             .ranked
             .iter()
@@ -1294,7 +1402,8 @@ mod tests {
         );
         insert_blocks_edge(&conn, "bn-blocker", "bn-done-urgent");
 
-        let snapshot = build_triage_snapshot(&conn, 100).expect("snapshot");
+        let snapshot =
+            build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snapshot");
         // done items are not active, so they don't appear in ranked at all
         let ids: Vec<&str> = snapshot.ranked.iter().map(|r| r.id.as_str()).collect();
         assert!(!ids.contains(&"bn-done-urgent"));
@@ -1307,8 +1416,8 @@ mod tests {
         insert_item(&conn, "bn-aaa", "Alpha", "open", "default", None, 10);
         insert_item(&conn, "bn-bbb", "Beta", "open", "default", None, 10);
 
-        let snap1 = build_triage_snapshot(&conn, 100).expect("snap1");
-        let snap2 = build_triage_snapshot(&conn, 100).expect("snap2");
+        let snap1 = build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snap1");
+        let snap2 = build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snap2");
 
         let ids1: Vec<&str> = snap1.ranked.iter().map(|r| r.id.as_str()).collect();
         let ids2: Vec<&str> = snap2.ranked.iter().map(|r| r.id.as_str()).collect();
@@ -1371,7 +1480,8 @@ mod tests {
         );
         insert_blocks_edge(&conn, "bn-prereq", "bn-urgent-target");
 
-        let snapshot = build_triage_snapshot(&conn, 100).expect("snapshot");
+        let snapshot =
+            build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snapshot");
         let prereq = snapshot
             .ranked
             .iter()
@@ -1434,7 +1544,8 @@ mod tests {
         );
         insert_blocks_edge(&conn, "bn-phase1", "bn-phase2");
 
-        let snapshot = build_triage_snapshot(&conn, 100).expect("snapshot");
+        let snapshot =
+            build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snapshot");
         let unblocked_ids: Vec<&str> = snapshot
             .unblocked_ranked
             .iter()
@@ -1488,7 +1599,8 @@ mod tests {
         insert_blocks_edge(&conn, "bn-phase1", "bn-phase2");
         insert_blocks_edge(&conn, "bn-phase2", "bn-phase3");
 
-        let snapshot = build_triage_snapshot(&conn, 100).expect("snapshot");
+        let snapshot =
+            build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snapshot");
 
         // Each suppressed leaf is recorded with its nearest blocked ancestor.
         assert_eq!(
@@ -1545,7 +1657,8 @@ mod tests {
         insert_blocks_edge(&conn, "bn-blocker", "bn-blocked");
         insert_blocks_edge(&conn, "bn-blocker", "bn-other");
 
-        let snapshot = build_triage_snapshot(&conn, 100).expect("snapshot");
+        let snapshot =
+            build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snapshot");
         let ancestors = compute_blocking_ancestors(&snapshot);
 
         assert_eq!(ancestors.len(), 2, "both blocked goals should appear");
@@ -1564,7 +1677,8 @@ mod tests {
         // A standalone unblocked goal with one task — no propagation occurs.
         insert_goal_with_children(&conn, "bn-g", "Goal", &[("bn-t", "Task")]);
 
-        let snapshot = build_triage_snapshot(&conn, 100).expect("snapshot");
+        let snapshot =
+            build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snapshot");
         let ancestors = compute_blocking_ancestors(&snapshot);
 
         assert!(ancestors.is_empty());
@@ -1601,7 +1715,8 @@ mod tests {
         )
         .expect("insert leaf");
 
-        let snapshot = build_triage_snapshot(&conn, 100).expect("snapshot");
+        let snapshot =
+            build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snapshot");
         let unblocked_ids: Vec<&str> = snapshot
             .unblocked_ranked
             .iter()
@@ -1658,7 +1773,8 @@ mod tests {
             &[("bn-normal-task", "Normal task")],
         );
 
-        let snapshot = build_triage_snapshot(&conn, 100).expect("snapshot");
+        let snapshot =
+            build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snapshot");
         let unblocked_ids: Vec<&str> = snapshot
             .unblocked_ranked
             .iter()
@@ -1751,7 +1867,8 @@ mod tests {
         )
         .expect("insert child");
 
-        let snapshot = build_triage_snapshot(&conn, 100).expect("snapshot");
+        let snapshot =
+            build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snapshot");
         let decomp_ids: Vec<&str> = snapshot
             .needs_decomposition
             .iter()
@@ -1845,7 +1962,8 @@ mod tests {
             10,
         );
 
-        let snapshot = build_triage_snapshot(&conn, 100).expect("snapshot");
+        let snapshot =
+            build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snapshot");
 
         let small = snapshot
             .ranked
@@ -1921,7 +2039,8 @@ mod tests {
             10,
         );
 
-        let snapshot = build_triage_snapshot(&conn, 100).expect("snapshot");
+        let snapshot =
+            build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snapshot");
         let parent = snapshot
             .ranked
             .iter()
@@ -1971,7 +2090,8 @@ mod tests {
         );
         insert_blocks_edge(&conn, "bn-punted", "bn-downstream");
 
-        let snapshot = build_triage_snapshot(&conn, 100).expect("snapshot");
+        let snapshot =
+            build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snapshot");
 
         // Punted goal and its child should be in punt_suppressed.
         assert!(
@@ -2021,7 +2141,8 @@ mod tests {
             10,
         );
 
-        let snapshot = build_triage_snapshot(&conn, 100).expect("snapshot");
+        let snapshot =
+            build_triage_snapshot(&conn, 100, &TriageConfig::default()).expect("snapshot");
         let goal = snapshot
             .ranked
             .iter()
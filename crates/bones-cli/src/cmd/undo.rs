@@ -21,6 +21,7 @@
 //! ```
 
 use crate::agent;
+use crate::author::write_authored_event;
 use crate::itc_state::assign_next_itc;
 use crate::output::{CliError, OutputMode, render, render_error};
 use crate::validate;
@@ -284,7 +285,7 @@ fn emit_compensating_event(
                 }
 
                 // Write the event to the shard
-                let line = writer::write_event(&mut comp_event)
+                let line = write_authored_event(&mut comp_event)
                     .map_err(|e| anyhow::anyhow!("failed to serialize event: {e}"))?;
 
                 shard_mgr
@@ -581,6 +582,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         let line = writer::write_event(&mut create_event).unwrap();
         shard_mgr
@@ -600,9 +602,11 @@ mod tests {
             data: EventData::Move(MoveData {
                 state: State::Doing,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         let line2 = writer::write_event(&mut move_event).unwrap();
         shard_mgr
@@ -12,6 +12,7 @@
 //! from a fresh `bn do` after a manual edit.
 
 use crate::agent;
+use crate::author::write_authored_event;
 use crate::cmd::open_projection_for_mutation;
 use crate::cmd::show::resolve_item_id;
 use crate::itc_state::assign_next_itc;
@@ -28,7 +29,6 @@ use bones_core::db::query;
 use bones_core::event::Event;
 use bones_core::event::data::{EventData, MoveData};
 use bones_core::event::types::EventType;
-use bones_core::event::writer;
 use bones_core::model::item::State;
 use bones_core::model::item_id::ItemId;
 use bones_core::shard::ShardManager;
@@ -124,6 +124,7 @@ fn run_unstart_single(
     let move_data = MoveData {
         state: State::Open,
         reason: None,
+        substate: None,
         extra,
     };
 
@@ -136,6 +137,7 @@ fn run_unstart_single(
         item_id: ItemId::new_unchecked(&resolved_id),
         data: EventData::Move(move_data),
         event_hash: String::new(),
+        signature: None,
     };
 
     {
@@ -154,7 +156,7 @@ fn run_unstart_single(
 
         assign_next_itc(project_root, &mut event)?;
 
-        let line = writer::write_event(&mut event)
+        let line = write_authored_event(&mut event)
             .map_err(|e| anyhow::anyhow!("failed to serialize event: {e}"))?;
 
         shard_mgr
@@ -339,6 +341,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         let line = writer::write_event(&mut create_event).unwrap();
         shard_mgr
@@ -365,9 +368,11 @@ mod tests {
                     data: EventData::Move(MoveData {
                         state: step_state,
                         reason: None,
+                        substate: None,
                         extra: BTreeMap::new(),
                     }),
                     event_hash: String::new(),
+                    signature: None,
                 };
                 let line = writer::write_event(&mut move_event).unwrap();
                 shard_mgr
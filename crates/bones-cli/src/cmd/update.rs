@@ -1,34 +1,47 @@
 //! `bn update` — patch one or more fields on a bone.
 //!
 //! Each field change emits a separate `item.update` event for CRDT
-//! correctness (one LWW write per field). Supports partial ID resolution.
+//! correctness (one LWW write per field). Description edits are sent as an
+//! `item.patch` against the current description so concurrent edits merge
+//! character by character. Each LWW write lists the field's current heads
+//! as `parents`, so `bn conflicts` only reports writes made concurrently.
+//! Supports partial ID resolution.
 //!
 //! # Supported fields
 //! - `--title`       — bone title (LWW string)
-//! - `--description` — bone description (LWW string)
+//! - `--description` — bone description (sequence CRDT patch; LWW when unset)
 //! - `--size`        — t-shirt size estimate (xs|s|m|l|xl)
 //! - `--urgency`     — urgency level (punt|low|default|high|urgent)
 //! - `--kind`        — bone kind (task|bug|goal)
+//! - `--due`         — due date (YYYY-MM-DD, RFC 3339, relative; `none` clears)
+//! - `--field`       — custom field declared in `[custom_fields]` (`name=value`;
+//!   an empty value clears)
 
 use crate::agent;
+use crate::author::write_authored_event;
 use crate::cmd::open_projection_for_mutation;
 use crate::cmd::show::resolve_item_id;
 use crate::itc_state::assign_next_itc;
 use crate::output::{CliError, OutputMode, render, render_error};
 use crate::validate;
+use chrono::Utc;
 use clap::Args;
 use serde::Serialize;
 use std::collections::BTreeMap;
 use std::path::Path;
 use std::time::Duration;
 
+use bones_core::config::load_project_config;
+use bones_core::conflicts::observed_parents;
 #[cfg(test)]
 use bones_core::db;
-use bones_core::db::project;
+use bones_core::db::{project, query};
 use bones_core::event::Event;
-use bones_core::event::data::{EventData, UpdateData};
+use bones_core::event::data::{EventData, PatchData, UpdateData};
 use bones_core::event::types::EventType;
-use bones_core::event::writer;
+use bones_core::event::validate::validate_custom_field;
+use bones_core::model::custom::{CustomFieldSchema, custom_field_key, parse_assignment};
+use bones_core::model::due::{DUE_FIELD, due_to_value, parse_due};
 use bones_core::model::item::{Kind, Size, Urgency};
 use bones_core::model::item_id::ItemId;
 use bones_core::shard::ShardManager;
@@ -63,6 +76,16 @@ pub struct UpdateArgs {
     #[arg(long)]
     pub kind: Option<String>,
 
+    /// New due date: YYYY-MM-DD, RFC 3339, today, tomorrow, or an offset
+    /// (3d, 2w). Pass `none` to clear.
+    #[arg(long, value_name = "when")]
+    pub due: Option<String>,
+
+    /// Set a custom field declared in `[custom_fields]` (repeatable). An
+    /// empty value clears the field.
+    #[arg(long = "field", value_name = "name=value")]
+    pub field: Vec<String>,
+
     /// Allow writing high-confidence secret-like text.
     #[arg(long)]
     pub allow_secret: bool,
@@ -121,6 +144,7 @@ fn run_update_single(
     agent: &str,
     raw_id: &str,
     pending: &[(String, serde_json::Value)],
+    schema: &CustomFieldSchema,
 ) -> anyhow::Result<UpdateOutput> {
     validate::validate_item_id(raw_id)
         .map_err(|e| anyhow::anyhow!("invalid item_id '{}': {}", e.value, e.reason))?;
@@ -132,23 +156,36 @@ fn run_update_single(
     let mut applied: Vec<FieldUpdate> = Vec::new();
 
     for (field, value) in pending {
-        let update_data = UpdateData {
-            field: field.clone(),
-            value: value.clone(),
-            extra: BTreeMap::new(),
-        };
+        let (event_type, data, parents) =
+            match description_patch(conn, &resolved_id, field, value, agent)? {
+                // Text is already identical: nothing to write.
+                Some(patch) if patch.ops.is_empty() => continue,
+                Some(patch) => (EventType::Patch, EventData::Patch(patch), vec![]),
+                None => (
+                    EventType::Update,
+                    EventData::Update(UpdateData {
+                        field: field.clone(),
+                        value: value.clone(),
+                        extra: BTreeMap::new(),
+                    }),
+                    observed_parents(conn, &resolved_id, field)?,
+                ),
+            };
 
         let mut event = Event {
             wall_ts_us: 0,
             agent: agent.to_string(),
             itc: String::new(),
-            parents: vec![],
-            event_type: EventType::Update,
+            parents,
+            event_type,
             item_id: ItemId::new_unchecked(&resolved_id),
-            data: EventData::Update(update_data),
+            data,
             event_hash: String::new(),
+            signature: None,
         };
 
+        validate_custom_field(&event, schema)?;
+
         {
             use bones_core::lock::ShardLock;
             let lock_path = shard_mgr.lock_path();
@@ -165,7 +202,7 @@ fn run_update_single(
 
             assign_next_itc(project_root, &mut event)?;
 
-            let line = writer::write_event(&mut event)
+            let line = write_authored_event(&mut event)
                 .map_err(|e| anyhow::anyhow!("failed to serialize event: {e}"))?;
 
             shard_mgr
@@ -193,6 +230,34 @@ fn run_update_single(
     })
 }
 
+/// Express a description change as character edits against the current base.
+///
+/// Returns `None` (whole-value update) when the field is not the
+/// description, the description is being cleared, or the bone has no
+/// description to patch yet.
+fn description_patch(
+    conn: &rusqlite::Connection,
+    item_id: &str,
+    field: &str,
+    value: &serde_json::Value,
+    agent: &str,
+) -> anyhow::Result<Option<PatchData>> {
+    if field != "description" {
+        return Ok(None);
+    }
+    let Some(text) = value.as_str().filter(|t| !t.is_empty()) else {
+        return Ok(None);
+    };
+    let Some(edits) = query::get_description_edits(conn, item_id)? else {
+        return Ok(None);
+    };
+    Ok(Some(PatchData {
+        ops: edits.seq.diff(&edits.base_text, text, agent),
+        base: edits.base_hash,
+        extra: BTreeMap::new(),
+    }))
+}
+
 fn item_ids(args: &UpdateArgs) -> impl Iterator<Item = &str> {
     std::iter::once(args.id.as_str()).chain(args.ids.iter().map(String::as_str))
 }
@@ -226,8 +291,10 @@ pub fn run_update(
         && args.size.is_none()
         && args.urgency.is_none()
         && args.kind.is_none()
+        && args.due.is_none()
+        && args.field.is_empty()
     {
-        let msg = "no fields specified: use --title, --description, --size, --urgency, or --kind";
+        let msg = "no fields specified: use --title, --description, --size, --urgency, --kind, --due, or --field";
         render_error(
             output,
             &CliError::with_details(msg, "Specify at least one field to update", "no_fields"),
@@ -285,6 +352,49 @@ pub fn run_update(
         None
     };
 
+    // `Some(None)` clears the due date.
+    let validated_due: Option<Option<u64>> = match args.due.as_deref().map(str::trim) {
+        None => None,
+        Some("" | "none") => Some(None),
+        Some(expr) => match parse_due(expr, Utc::now()) {
+            Ok(us) => Some(Some(us)),
+            Err(e) => {
+                let msg = e.to_string();
+                render_error(
+                    output,
+                    &CliError::with_details(
+                        &msg,
+                        "Use --due 2026-03-01, --due tomorrow, --due 3d, or --due none",
+                        "invalid_due",
+                    ),
+                )?;
+                anyhow::bail!("{msg}");
+            }
+        },
+    };
+
+    let schema = load_project_config(project_root)
+        .unwrap_or_default()
+        .custom_fields;
+    let mut validated_custom: Vec<(String, serde_json::Value)> = Vec::new();
+    for raw in &args.field {
+        match parse_assignment(&schema, raw) {
+            Ok(field) => validated_custom.push(field),
+            Err(e) => {
+                let msg = e.to_string();
+                render_error(
+                    output,
+                    &CliError::with_details(
+                        &msg,
+                        "Declare fields under [custom_fields] in .bones/config.toml and pass --field name=value",
+                        "invalid_custom_field",
+                    ),
+                )?;
+                anyhow::bail!("{msg}");
+            }
+        }
+    }
+
     // 4. Find .bones directory
     let bones_dir = find_bones_dir(project_root).ok_or_else(|| {
         let msg = "Not a bones project: .bones directory not found";
@@ -372,12 +482,28 @@ pub fn run_update(
         ));
     }
 
+    if let Some(due) = validated_due {
+        pending.push((DUE_FIELD.to_string(), due_to_value(due)));
+    }
+
+    for (name, value) in validated_custom {
+        pending.push((custom_field_key(&name), value));
+    }
+
     // 7. Process each item independently
     let mut results = Vec::new();
     let mut failures = Vec::new();
 
     for raw_id in item_ids(args) {
-        match run_update_single(project_root, &conn, &shard_mgr, &agent, raw_id, &pending) {
+        match run_update_single(
+            project_root,
+            &conn,
+            &shard_mgr,
+            &agent,
+            raw_id,
+            &pending,
+            &schema,
+        ) {
             Ok(ok) => results.push(UpdateResult {
                 id: ok.id,
                 ok: true,
@@ -490,6 +616,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
 
         let line = writer::write_event(&mut create_event).unwrap();
@@ -537,6 +664,8 @@ mod tests {
             size: None,
             urgency: None,
             kind: None,
+            due: None,
+            field: vec![],
             allow_secret: false,
         };
         let result = run_update(&args, Some("test-agent"), OutputMode::Json, dir.path());
@@ -548,6 +677,93 @@ mod tests {
         assert_eq!(item.title, "Updated title");
     }
 
+    #[test]
+    fn update_due_sets_and_clears() {
+        let (dir, item_id) = setup_project();
+        let mut args = UpdateArgs {
+            id: item_id.clone(),
+            ids: vec![],
+            title: None,
+            description: None,
+            size: None,
+            urgency: None,
+            kind: None,
+            due: Some("2026-03-15".to_string()),
+            field: vec![],
+            allow_secret: false,
+        };
+        run_update(&args, Some("test-agent"), OutputMode::Json, dir.path()).unwrap();
+
+        let db_path = dir.path().join(".bones/bones.db");
+        let conn = db::open_projection(&db_path).unwrap();
+        let item = query::get_item(&conn, &item_id, false).unwrap().unwrap();
+        assert_eq!(item.due_at_us, Some(1_773_619_199_000_000));
+
+        args.due = Some("none".to_string());
+        run_update(&args, Some("test-agent"), OutputMode::Json, dir.path()).unwrap();
+        let item = query::get_item(&conn, &item_id, false).unwrap().unwrap();
+        assert_eq!(item.due_at_us, None);
+    }
+
+    #[test]
+    fn update_rejects_invalid_due() {
+        let (dir, item_id) = setup_project();
+        let args = UpdateArgs {
+            id: item_id,
+            ids: vec![],
+            title: None,
+            description: None,
+            size: None,
+            urgency: None,
+            kind: None,
+            due: Some("someday".to_string()),
+            field: vec![],
+            allow_secret: false,
+        };
+        assert!(run_update(&args, Some("test-agent"), OutputMode::Json, dir.path()).is_err());
+    }
+
+    #[test]
+    fn update_custom_field_validated_against_schema() {
+        let (dir, item_id) = setup_project();
+        std::fs::write(
+            dir.path().join(".bones/config.toml"),
+            "[custom_fields.severity]\ntype = \"int\"\n",
+        )
+        .unwrap();
+        let mut args = UpdateArgs {
+            id: item_id.clone(),
+            ids: vec![],
+            title: None,
+            description: None,
+            size: None,
+            urgency: None,
+            kind: None,
+            due: None,
+            field: vec!["severity=2".to_string()],
+            allow_secret: false,
+        };
+        run_update(&args, Some("test-agent"), OutputMode::Json, dir.path()).unwrap();
+
+        let db_path = dir.path().join(".bones/bones.db");
+        let conn = db::open_projection(&db_path).unwrap();
+        let fields = query::get_custom_fields(&conn, &item_id).unwrap();
+        assert_eq!(fields.get("severity"), Some(&serde_json::json!(2)));
+
+        args.field = vec!["severity=high".to_string()];
+        assert!(run_update(&args, Some("test-agent"), OutputMode::Json, dir.path()).is_err());
+        args.field = vec!["owner=bob".to_string()];
+        assert!(run_update(&args, Some("test-agent"), OutputMode::Json, dir.path()).is_err());
+
+        args.field = vec!["severity=".to_string()];
+        run_update(&args, Some("test-agent"), OutputMode::Json, dir.path()).unwrap();
+        assert!(
+            query::get_custom_fields(&conn, &item_id)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
     #[test]
     fn update_urgency() {
         let (dir, item_id) = setup_project();
@@ -559,6 +775,8 @@ mod tests {
             size: None,
             urgency: Some("urgent".to_string()),
             kind: None,
+            due: None,
+            field: vec![],
             allow_secret: false,
         };
         let result = run_update(&args, Some("test-agent"), OutputMode::Json, dir.path());
@@ -581,6 +799,8 @@ mod tests {
             size: None,
             urgency: None,
             kind: Some("bug".to_string()),
+            due: None,
+            field: vec![],
             allow_secret: false,
         };
         let result = run_update(&args, Some("test-agent"), OutputMode::Json, dir.path());
@@ -603,6 +823,8 @@ mod tests {
             size: Some("l".to_string()),
             urgency: None,
             kind: None,
+            due: None,
+            field: vec![],
             allow_secret: false,
         };
         run_update(&args, Some("test-agent"), OutputMode::Json, dir.path()).unwrap();
@@ -625,6 +847,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn update_description_emits_patch_once_set() {
+        let (dir, item_id) = setup_project();
+        let mut args = UpdateArgs {
+            id: item_id.clone(),
+            ids: vec![],
+            title: None,
+            description: Some("Fix the parser".to_string()),
+            size: None,
+            urgency: None,
+            kind: None,
+            due: None,
+            field: vec![],
+            allow_secret: false,
+        };
+        run_update(&args, Some("test-agent"), OutputMode::Json, dir.path()).unwrap();
+
+        args.description = Some("Fix the config parser".to_string());
+        run_update(&args, Some("test-agent"), OutputMode::Json, dir.path()).unwrap();
+
+        // Unchanged text writes nothing.
+        run_update(&args, Some("test-agent"), OutputMode::Json, dir.path()).unwrap();
+
+        let shard_mgr = ShardManager::new(dir.path().join(".bones"));
+        let replay = shard_mgr.replay().unwrap();
+        let types: Vec<&str> = replay
+            .lines()
+            .filter(|l| !l.starts_with('#') && !l.is_empty())
+            .map(|l| l.split('\t').nth(4).unwrap())
+            .collect();
+        assert_eq!(types, ["item.create", "item.update", "item.patch"]);
+
+        let conn = db::open_projection(&dir.path().join(".bones/bones.db")).unwrap();
+        let item = query::get_item(&conn, &item_id, false).unwrap().unwrap();
+        assert_eq!(item.description.as_deref(), Some("Fix the config parser"));
+    }
+
     #[test]
     fn update_rejects_no_fields() {
         let (dir, item_id) = setup_project();
@@ -636,6 +895,8 @@ mod tests {
             size: None,
             urgency: None,
             kind: None,
+            due: None,
+            field: vec![],
             allow_secret: false,
         };
         let result = run_update(&args, Some("test-agent"), OutputMode::Json, dir.path());
@@ -659,6 +920,8 @@ mod tests {
             size: Some("huge".to_string()),
             urgency: None,
             kind: None,
+            due: None,
+            field: vec![],
             allow_secret: false,
         };
         let result = run_update(&args, Some("test-agent"), OutputMode::Json, dir.path());
@@ -677,6 +940,8 @@ mod tests {
             size: None,
             urgency: Some("super-urgent".to_string()),
             kind: None,
+            due: None,
+            field: vec![],
             allow_secret: false,
         };
         let result = run_update(&args, Some("test-agent"), OutputMode::Json, dir.path());
@@ -695,6 +960,8 @@ mod tests {
             size: None,
             urgency: None,
             kind: Some("chore".to_string()),
+            due: None,
+            field: vec![],
             allow_secret: false,
         };
         let result = run_update(&args, Some("test-agent"), OutputMode::Json, dir.path());
@@ -713,6 +980,8 @@ mod tests {
             size: None,
             urgency: None,
             kind: None,
+            due: None,
+            field: vec![],
             allow_secret: false,
         };
         let result = run_update(&args, Some("test-agent"), OutputMode::Json, dir.path());
@@ -745,6 +1014,8 @@ mod tests {
             size: None,
             urgency: None,
             kind: None,
+            due: None,
+            field: vec![],
             allow_secret: false,
         };
         let result = run_update(&args, Some("test-agent"), OutputMode::Json, root);
@@ -762,6 +1033,8 @@ mod tests {
             size: None,
             urgency: None,
             kind: None,
+            due: None,
+            field: vec![],
             allow_secret: false,
         };
         let result = run_update(&args, Some("test-agent"), OutputMode::Json, dir.path());
@@ -790,6 +1063,8 @@ mod tests {
             size: None,
             urgency: None,
             kind: None,
+            due: None,
+            field: vec![],
             allow_secret: false,
         };
         run_update(&args, Some("test-agent"), OutputMode::Json, dir.path()).unwrap();
@@ -824,6 +1099,8 @@ mod tests {
             size: None,
             urgency: None,
             kind: None,
+            due: None,
+            field: vec![],
             allow_secret: false,
         };
         let result = run_update(&args, Some("test-agent"), OutputMode::Json, dir.path());
@@ -842,6 +1119,8 @@ mod tests {
             size: None,
             urgency: None,
             kind: None,
+            due: None,
+            field: vec![],
             allow_secret: true,
         };
         let result = run_update(&args, Some("test-agent"), OutputMode::Json, dir.path());
@@ -864,6 +1143,8 @@ mod tests {
             size: None,
             urgency: None,
             kind: None,
+            due: None,
+            field: vec![],
             allow_secret: false,
         };
         let result = run_update(&args, Some("test-agent"), OutputMode::Json, dir.path());
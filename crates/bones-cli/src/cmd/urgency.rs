@@ -25,6 +25,8 @@ fn to_update_args(args: &UrgencyQuickArgs, urgency: &str) -> UpdateArgs {
         size: None,
         urgency: Some(urgency.to_string()),
         kind: None,
+        due: None,
+        field: vec![],
         allow_secret: false,
     }
 }
@@ -1,6 +1,9 @@
 use std::path::Path;
 
 use anyhow::Result;
+use bones_core::signing::{AgentSignatures, SignatureIssue, verify_signatures};
+use bones_core::sync::filter::ReplicationFilter;
+use bones_core::sync::index::{self, IndexCheck};
 use bones_core::verify::{ShardCheckStatus, verify_repository};
 use serde::Serialize;
 
@@ -14,21 +17,158 @@ struct VerifyShardRow {
     reason: Option<String>,
 }
 
+#[derive(Debug, Serialize)]
+struct SignatureAgentRow {
+    agent: String,
+    keys: usize,
+    valid: usize,
+    unsigned: usize,
+    unknown_key: usize,
+    invalid: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct SignatureIssueRow {
+    event_hash: String,
+    agent: String,
+    item_id: String,
+    status: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct SignatureSection {
+    ok: bool,
+    require_signed: bool,
+    agents: Vec<SignatureAgentRow>,
+    issues: Vec<SignatureIssueRow>,
+}
+
+#[derive(Debug, Serialize)]
+struct ProllyIndexRow {
+    /// `ok`, `absent`, or `mismatch`.
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    events: Option<usize>,
+    /// Shard events the index has not caught up with yet.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pending: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+}
+
+impl From<IndexCheck> for ProllyIndexRow {
+    fn from(check: IndexCheck) -> Self {
+        match check {
+            IndexCheck::Absent => Self {
+                status: "absent",
+                events: None,
+                pending: None,
+                reason: None,
+            },
+            IndexCheck::Consistent { events, pending } => Self {
+                status: "ok",
+                events: Some(events),
+                pending: Some(pending),
+                reason: None,
+            },
+            IndexCheck::Mismatch(reason) => Self {
+                status: "mismatch",
+                events: None,
+                pending: None,
+                reason: Some(reason),
+            },
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 struct VerifyOutput {
     ok: bool,
     active_shard_parse_ok: bool,
     shards: Vec<VerifyShardRow>,
+    prolly_index: ProllyIndexRow,
+    /// Filter this replica was pulled through; absent for complete replicas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    partial_replica: Option<ReplicationFilter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signatures: Option<SignatureSection>,
+}
+
+/// Which optional checks `bn verify` runs in addition to the shard checks.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VerifyOptions {
+    /// Regenerate missing manifests for sealed shards.
+    pub regenerate_missing: bool,
+    /// Check event signatures against `.bones/keys/`.
+    pub signatures: bool,
+    /// With `signatures`, also fail on unsigned or unverifiable events.
+    pub require_signed: bool,
+}
+
+impl From<&AgentSignatures> for SignatureAgentRow {
+    fn from(a: &AgentSignatures) -> Self {
+        Self {
+            agent: a.agent.clone(),
+            keys: a.keys,
+            valid: a.valid,
+            unsigned: a.unsigned,
+            unknown_key: a.unknown_key,
+            invalid: a.invalid,
+        }
+    }
+}
+
+impl From<&SignatureIssue> for SignatureIssueRow {
+    fn from(issue: &SignatureIssue) -> Self {
+        Self {
+            event_hash: issue.event_hash.clone(),
+            agent: issue.agent.clone(),
+            item_id: issue.item_id.clone(),
+            status: issue.status.as_str(),
+        }
+    }
+}
+
+fn render_signatures(
+    section: &SignatureSection,
+    w: &mut dyn std::io::Write,
+) -> std::io::Result<()> {
+    for agent in &section.agents {
+        let status = if agent.invalid > 0
+            || (section.require_signed && (agent.unsigned > 0 || agent.unknown_key > 0))
+        {
+            "FAIL"
+        } else {
+            "OK  "
+        };
+        writeln!(
+            w,
+            "{status} signatures {}: {} valid, {} unsigned, {} unknown key, {} invalid ({} key(s))",
+            agent.agent, agent.valid, agent.unsigned, agent.unknown_key, agent.invalid, agent.keys
+        )?;
+    }
+    for issue in &section.issues {
+        writeln!(
+            w,
+            "     {} {} {} ({})",
+            issue.status, issue.agent, issue.item_id, issue.event_hash
+        )?;
+    }
+    Ok(())
 }
 
 /// Run repository verification against `.bones/events` shards.
 ///
+/// Also checks the persisted prolly index, if any, and notes when this is a
+/// partial replica (see `bn sync --remote --goal`). With [`VerifyOptions::signatures`], also checks every event signature
+/// against the public keys in `.bones/keys/`, reporting counts per agent.
+///
 /// # Errors
 ///
 /// Returns an error when verification checks fail.
-pub fn run_verify(project_root: &Path, regenerate_missing: bool, output: OutputMode) -> Result<()> {
+pub fn run_verify(project_root: &Path, options: VerifyOptions, output: OutputMode) -> Result<()> {
     let bones_dir = project_root.join(".bones");
-    let report = match verify_repository(&bones_dir, regenerate_missing) {
+    let report = match verify_repository(&bones_dir, options.regenerate_missing) {
         Ok(report) => report,
         Err(e) => {
             render_error(
@@ -60,10 +200,51 @@ pub fn run_verify(project_root: &Path, regenerate_missing: bool, output: OutputM
         })
         .collect();
 
+    let signatures = if options.signatures {
+        let sig_report = match verify_signatures(&bones_dir) {
+            Ok(report) => report,
+            Err(e) => {
+                render_error(
+                    output,
+                    &CliError::with_details(
+                        format!("signature check failed: {e}"),
+                        "check .bones/keys/*.pub for malformed keys",
+                        "verify_failed",
+                    ),
+                )?;
+                return Err(e.into());
+            }
+        };
+        let ok = if options.require_signed {
+            sig_report.all_signed()
+        } else {
+            sig_report.is_ok()
+        };
+        Some(SignatureSection {
+            ok,
+            require_signed: options.require_signed,
+            agents: sig_report.agents.iter().map(Into::into).collect(),
+            issues: sig_report.issues.iter().map(Into::into).collect(),
+        })
+    } else {
+        None
+    };
+
+    let prolly_index = ProllyIndexRow::from(
+        index::check(&bones_dir).unwrap_or_else(|e| IndexCheck::Mismatch(e.to_string())),
+    );
+
+    let partial_replica = ReplicationFilter::load_marker(&bones_dir)?;
+
     let out = VerifyOutput {
-        ok: report.is_ok(),
+        ok: report.is_ok()
+            && prolly_index.status != "mismatch"
+            && signatures.as_ref().is_none_or(|s| s.ok),
         active_shard_parse_ok: report.active_shard_parse_ok,
         shards,
+        prolly_index,
+        partial_replica,
+        signatures,
     };
 
     render(output, &out, |out, w| {
@@ -91,6 +272,26 @@ pub fn run_verify(project_root: &Path, regenerate_missing: bool, output: OutputM
             writeln!(w, "FAIL active shard parse sanity")?;
         }
 
+        match (out.prolly_index.status, &out.prolly_index.reason) {
+            ("ok", _) => writeln!(w, "OK   prolly index")?,
+            ("mismatch", Some(reason)) => writeln!(
+                w,
+                "FAIL prolly index ({reason}; run `bn admin rebuild` to recreate it)"
+            )?,
+            _ => {}
+        }
+
+        if let Some(filter) = &out.partial_replica {
+            writeln!(
+                w,
+                "NOTE partial replica ({filter}); items outside the filter are not present"
+            )?;
+        }
+
+        if let Some(section) = &out.signatures {
+            render_signatures(section, w)?;
+        }
+
         if out.ok {
             writeln!(w, "verify: success")?;
         } else {
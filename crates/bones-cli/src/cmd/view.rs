@@ -0,0 +1,441 @@
+//! `bn view` — saved list views.
+//!
+//! Views live in `.bones/views.toml` (see [`bones_core::views`]) and are
+//! committed with the project, so a team shares them through git.
+//!
+//! # Usage
+//!
+//! ```text
+//! bn view save triage-api --label api --where 'NOT assignee:*' --sort priority
+//! bn view list
+//! bn list --view triage-api
+//! bn context --view triage-api
+//! bn view remove triage-api
+//! ```
+
+use crate::cmd::do_cmd::find_bones_dir;
+use crate::cmd::list::{DEFAULT_LIST_LIMIT, DEFAULT_LIST_SORT, ListArgs};
+use crate::output::{CliError, OutputMode, pretty_table, render, render_error};
+use bones_core::views::{SavedView, Views, ViewsError};
+use clap::{Args, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Args, Debug)]
+pub struct ViewArgs {
+    #[command(subcommand)]
+    pub command: ViewCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ViewCommand {
+    /// List saved views
+    List,
+    /// Save `bn list` flags as a named view, replacing any view of that name
+    Save {
+        /// View name (letters, digits, '-' and '_')
+        name: String,
+        /// What the view is for
+        #[arg(long)]
+        description: Option<String>,
+        /// Output mode `bn list --view` uses unless --format is given
+        #[arg(long, value_enum, value_name = "format")]
+        view_format: Option<OutputMode>,
+        #[command(flatten)]
+        list: ListArgs,
+    },
+    /// Remove a saved view
+    Remove {
+        /// View name
+        name: String,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct ViewRow {
+    name: String,
+    #[serde(flatten)]
+    view: SavedView,
+}
+
+#[derive(Debug, Serialize)]
+struct ListOutput {
+    views: Vec<ViewRow>,
+}
+
+#[derive(Debug, Serialize)]
+struct SaveOutput {
+    ok: bool,
+    name: String,
+    path: String,
+    replaced: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct RemoveOutput {
+    ok: bool,
+    name: String,
+}
+
+fn fail(output: OutputMode, msg: &str, suggestion: &str, code: &str) -> anyhow::Error {
+    let _ = render_error(output, &CliError::with_details(msg, suggestion, code));
+    anyhow::anyhow!("{msg}")
+}
+
+fn views_failure(output: OutputMode, err: &ViewsError) -> anyhow::Error {
+    let (suggestion, code) = match err {
+        ViewsError::InvalidName { .. } => ("use letters, digits, '-' and '_'", "invalid_view_name"),
+        ViewsError::Io(_) => ("check permissions on .bones/views.toml", "views_failed"),
+        _ => (
+            "fix .bones/views.toml or re-save the view with `bn view save`",
+            "invalid_views",
+        ),
+    };
+    fail(output, &err.to_string(), suggestion, code)
+}
+
+fn bones_dir(project_root: &Path, output: OutputMode) -> anyhow::Result<PathBuf> {
+    find_bones_dir(project_root).ok_or_else(|| {
+        fail(
+            output,
+            "Not a bones project: .bones directory not found",
+            "Run 'bn init' to create a new project",
+            "not_a_project",
+        )
+    })
+}
+
+/// Load the named view, rendering an error if it is missing or the views
+/// file is invalid.
+fn load_view(project_root: &Path, name: &str, output: OutputMode) -> anyhow::Result<SavedView> {
+    let bones_dir = bones_dir(project_root, output)?;
+    let views = Views::load(&bones_dir).map_err(|e| views_failure(output, &e))?;
+    views.get(name).cloned().ok_or_else(|| {
+        fail(
+            output,
+            &format!("no saved view named '{name}'"),
+            "run `bn view list` to see saved views",
+            "unknown_view",
+        )
+    })
+}
+
+/// Join two optional queries with AND.
+fn and_queries(first: Option<String>, second: Option<&str>) -> Option<String> {
+    match (first, second) {
+        (Some(a), Some(b)) => Some(format!("({a}) AND ({b})")),
+        (Some(a), None) => Some(a),
+        (None, b) => b.map(str::to_string),
+    }
+}
+
+/// Apply `args.view` to list flags.
+///
+/// Flags set on the command line win over the view's; `--sort` and
+/// `--limit` count as unset while they hold their defaults. `--where` is
+/// combined with the view's query. Also returns the view's output mode.
+///
+/// # Errors
+///
+/// Returns an error (after rendering it) if the view does not exist or the
+/// views file is invalid.
+pub fn apply_list_view(
+    args: &ListArgs,
+    project_root: &Path,
+    output: OutputMode,
+) -> anyhow::Result<(ListArgs, Option<OutputMode>)> {
+    let mut merged = args.clone();
+    let Some(name) = args.view.as_deref() else {
+        return Ok((merged, None));
+    };
+    let view = load_view(project_root, name, output)?;
+
+    if merged.state.is_empty() {
+        merged.state = view.state;
+    }
+    for (flag, value) in [
+        (&mut merged.kind, view.kind),
+        (&mut merged.urgency, view.urgency),
+        (&mut merged.parent, view.parent),
+        (&mut merged.assignee, view.assignee),
+        (&mut merged.due, view.due),
+    ] {
+        if flag.is_none() {
+            *flag = value;
+        }
+    }
+    if merged.all_labels().is_empty() {
+        merged.label = view.label;
+    }
+    if merged.field.is_empty() {
+        merged.field = view.field;
+    }
+    merged.query = and_queries(view.query, args.query.as_deref());
+    if merged.sort == DEFAULT_LIST_SORT
+        && let Some(sort) = view.sort
+    {
+        merged.sort = sort;
+    }
+    if merged.limit == DEFAULT_LIST_LIMIT
+        && let Some(limit) = view.limit
+    {
+        merged.limit = limit;
+    }
+    if merged.columns.is_empty() {
+        merged.columns = view.columns;
+    }
+
+    let format = view
+        .format
+        .as_deref()
+        .and_then(|f| OutputMode::from_str(f, true).ok());
+    Ok((merged, format))
+}
+
+/// The query for `--view` and `--where` combined, for commands that take a
+/// filter query rather than list flags.
+///
+/// # Errors
+///
+/// Returns an error (after rendering it) if the view does not exist or the
+/// views file is invalid.
+pub fn view_query(
+    view: Option<&str>,
+    query: Option<&str>,
+    project_root: &Path,
+    output: OutputMode,
+) -> anyhow::Result<Option<String>> {
+    let Some(name) = view else {
+        return Ok(query.map(str::to_string));
+    };
+    let view = load_view(project_root, name, output)?;
+    Ok(and_queries(view.to_query(), query))
+}
+
+/// Execute `bn view`.
+///
+/// # Errors
+///
+/// Returns an error if the views file cannot be read or written, or an
+/// argument is invalid.
+pub fn run_view(args: &ViewArgs, output: OutputMode, project_root: &Path) -> anyhow::Result<()> {
+    let bones_dir = bones_dir(project_root, output)?;
+    let mut views = Views::load(&bones_dir).map_err(|e| views_failure(output, &e))?;
+
+    match &args.command {
+        ViewCommand::List => run_list(views, output),
+        ViewCommand::Save {
+            name,
+            description,
+            view_format,
+            list,
+        } => {
+            let view = saved_view_from_args(list, description.clone(), *view_format, output)?;
+            let replaced = views.get(name).is_some();
+            views
+                .insert(name, view)
+                .map_err(|e| views_failure(output, &e))?;
+            let path = views
+                .save(&bones_dir)
+                .map_err(|e| views_failure(output, &e))?;
+            let out = SaveOutput {
+                ok: true,
+                name: name.clone(),
+                path: path
+                    .strip_prefix(project_root)
+                    .unwrap_or(&path)
+                    .display()
+                    .to_string(),
+                replaced,
+            };
+            render(output, &out, |out, w| {
+                writeln!(
+                    w,
+                    "{} view '{}' in {}; use it with `bn list --view {}`",
+                    if out.replaced { "Updated" } else { "Saved" },
+                    out.name,
+                    out.path,
+                    out.name
+                )
+            })
+        }
+        ViewCommand::Remove { name } => {
+            if views.views.remove(name).is_none() {
+                return Err(fail(
+                    output,
+                    &format!("no saved view named '{name}'"),
+                    "run `bn view list` to see saved views",
+                    "unknown_view",
+                ));
+            }
+            views
+                .save(&bones_dir)
+                .map_err(|e| views_failure(output, &e))?;
+            let out = RemoveOutput {
+                ok: true,
+                name: name.clone(),
+            };
+            render(output, &out, |out, w| {
+                writeln!(w, "Removed view '{}'", out.name)
+            })
+        }
+    }
+}
+
+/// Build a view from `bn view save` flags, refusing flags a view cannot
+/// hold rather than dropping them.
+fn saved_view_from_args(
+    list: &ListArgs,
+    description: Option<String>,
+    view_format: Option<OutputMode>,
+    output: OutputMode,
+) -> anyhow::Result<SavedView> {
+    let unsupported = [
+        ("--at", list.at.is_some()),
+        ("--since", list.since.is_some()),
+        ("--until", list.until.is_some()),
+        ("--all", list.all || list.all_states),
+        ("--offset", list.offset != 0),
+        ("--view", list.view.is_some()),
+    ];
+    if let Some((flag, _)) = unsupported.iter().find(|(_, set)| *set) {
+        return Err(fail(
+            output,
+            &format!("{flag} cannot be saved in a view"),
+            "save the remaining flags and pass this one on the command line",
+            "invalid_view",
+        ));
+    }
+
+    Ok(SavedView {
+        description,
+        state: list.state.clone(),
+        kind: list.kind.clone(),
+        label: list.all_labels(),
+        urgency: list.urgency.clone(),
+        parent: list.parent.clone(),
+        assignee: list.assignee.clone(),
+        due: list.due.clone(),
+        field: list.field.clone(),
+        query: list.query.clone(),
+        sort: (list.sort != DEFAULT_LIST_SORT).then(|| list.sort.clone()),
+        columns: list.columns.clone(),
+        format: view_format.map(|f| {
+            f.to_possible_value()
+                .map_or_else(String::new, |v| v.get_name().to_string())
+        }),
+        limit: (list.limit != DEFAULT_LIST_LIMIT).then_some(list.limit),
+    })
+}
+
+fn run_list(views: Views, output: OutputMode) -> anyhow::Result<()> {
+    let out = ListOutput {
+        views: views
+            .views
+            .into_iter()
+            .map(|(name, view)| ViewRow { name, view })
+            .collect(),
+    };
+    render(output, &out, |out, w| {
+        if out.views.is_empty() {
+            return writeln!(
+                w,
+                "No saved views. Save one with `bn view save <name> [list flags]`."
+            );
+        }
+        let rows: Vec<Vec<String>> = out
+            .views
+            .iter()
+            .map(|row| {
+                vec![
+                    row.name.clone(),
+                    row.view.to_query().unwrap_or_else(|| "(all)".to_string()),
+                    row.view.description.clone().unwrap_or_default(),
+                ]
+            })
+            .collect();
+        pretty_table(w, &["VIEW", "FILTER", "DESCRIPTION"], &rows)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Wrapper {
+        #[command(flatten)]
+        list: ListArgs,
+    }
+
+    fn list_args(flags: &[&str]) -> ListArgs {
+        let mut argv = vec!["bn"];
+        argv.extend_from_slice(flags);
+        Wrapper::parse_from(argv).list
+    }
+
+    fn project_with_views(text: &str) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir_all(dir.path().join(".bones")).expect("bones dir");
+        std::fs::write(dir.path().join(".bones/views.toml"), text).expect("write views");
+        dir
+    }
+
+    const VIEWS: &str = r#"
+[view.api]
+state = ["open"]
+label = ["api"]
+where = "NOT assignee:*"
+sort = "priority"
+limit = 10
+columns = ["id", "title"]
+format = "json"
+"#;
+
+    #[test]
+    fn view_fills_unset_flags_and_command_line_wins() {
+        let dir = project_with_views(VIEWS);
+        let args = list_args(&["--view", "api", "--where", "size>=m", "-n", "5"]);
+        let (merged, format) =
+            apply_list_view(&args, dir.path(), OutputMode::Text).expect("apply view");
+
+        assert_eq!(merged.state, ["open"]);
+        assert_eq!(merged.label, ["api"]);
+        assert_eq!(
+            merged.query.as_deref(),
+            Some("(NOT assignee:*) AND (size>=m)")
+        );
+        assert_eq!(merged.sort, "priority");
+        assert_eq!(merged.limit, 5);
+        assert_eq!(merged.columns, ["id", "title"]);
+        assert_eq!(format, Some(OutputMode::Json));
+
+        let args = list_args(&["--view", "api", "--label", "docs", "--sort", "due"]);
+        let (merged, _) = apply_list_view(&args, dir.path(), OutputMode::Text).expect("apply");
+        assert_eq!(merged.label, ["docs"]);
+        assert_eq!(merged.sort, "due");
+    }
+
+    #[test]
+    fn unknown_view_is_an_error() {
+        let dir = project_with_views(VIEWS);
+        let args = list_args(&["--view", "nope"]);
+        assert!(apply_list_view(&args, dir.path(), OutputMode::Json).is_err());
+    }
+
+    #[test]
+    fn save_keeps_only_non_default_flags() {
+        let args = list_args(&["--kind", "bug", "--label", "api", "--sort", "priority"]);
+        let view = saved_view_from_args(&args, None, Some(OutputMode::Text), OutputMode::Json)
+            .expect("view");
+        assert_eq!(view.kind.as_deref(), Some("bug"));
+        assert_eq!(view.label, ["api"]);
+        assert_eq!(view.sort.as_deref(), Some("priority"));
+        assert_eq!(view.limit, None);
+        assert_eq!(view.format.as_deref(), Some("text"));
+
+        let args = list_args(&["--at", "1w"]);
+        assert!(saved_view_from_args(&args, None, None, OutputMode::Json).is_err());
+    }
+}
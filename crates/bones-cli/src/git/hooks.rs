@@ -247,6 +247,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: "placeholder".to_string(),
+            signature: None,
         };
         write_event(&mut event).expect("write_event")
     }
@@ -31,12 +31,22 @@
 //! # Merge Algorithm
 //!
 //! 1. Read and parse TSJSON events from base, ours, and theirs.
-//! 2. Call [`bones_core::sync::merge::merge_event_sets`] to union ours +
-//!    theirs, deduplicating by event hash.
+//! 2. Call [`bones_core::sync::merge::merge_event_sets_with_policy`] to union
+//!    ours + theirs, deduplicating by event hash. Events only theirs has are
+//!    checked against `.bones/policy.toml`; refused ones are quarantined to
+//!    `.bones/events/*.rejected` instead of merged.
 //! 3. Sort merged events by `(wall_ts_us, agent, event_hash)` for
 //!    deterministic output.
 //! 4. Write the shard header followed by all merged events to the ours path.
-//! 5. Return `Ok(())` — the caller exits with code 0.
+//! 5. Replay both sides from their common ancestor and, if they wrote the
+//!    same field of an item with different values, write a report to
+//!    `.bones/merge-reports/` (see [`bones_core::sync::merge_report`]).
+//!    `bn status` shows the report until it is acknowledged.
+//! 6. Return `Ok(())` — the caller exits with code 0.
+//!
+//! Git never calls the driver when a pull fast-forwards, so the policy
+//! check in step 2 only covers true merges. `bn sync` screens the events a
+//! pull added afterwards; see [`crate::cmd::sync`].
 
 use std::fs;
 use std::io::Write as _;
@@ -45,7 +55,11 @@ use std::path::Path;
 use anyhow::{Context as _, Result};
 use bones_core::event::parser::parse_lines;
 use bones_core::event::writer::{shard_header, write_line};
-use bones_core::sync::merge::merge_event_sets;
+use bones_core::policy::{self, Policy};
+use bones_core::sync::merge::merge_event_sets_with_policy;
+use bones_core::sync::merge_report::{self, MergeReport, detect_overlapping_writes};
+
+use crate::cmd::do_cmd::find_bones_dir;
 use tracing::{info, warn};
 
 // ---------------------------------------------------------------------------
@@ -54,20 +68,45 @@ use tracing::{info, warn};
 
 /// Run the git merge driver for a TSJSON `.events` shard file.
 ///
+/// Git runs the driver from the work tree root, so the project's policy and
+/// quarantine are found from the current directory.
+///
+/// # Errors
+///
+/// Returns an error if the merge fails; see [`merge_shard_files`].
+pub fn merge_driver_main(base: &Path, ours: &Path, theirs: &Path) -> Result<()> {
+    let bones_dir = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| find_bones_dir(&cwd));
+    merge_shard_files(base, ours, theirs, bones_dir.as_deref())
+}
+
+/// Merge three versions of a shard file.
+///
 /// Reads events from `base`, `ours`, and `theirs`, merges ours and theirs
 /// using union semantics (dedup by hash, sort by timestamp/agent/hash), and
 /// writes the merged result to `ours` (overwriting it).
 ///
-/// The `base` file is read and parsed but not included in the merge output —
-/// all base events that were not superseded will already appear on at least
-/// one of the two sides. Reading base enables future optimisations (e.g.
-/// detecting genuine conflicts) without changing the current behaviour.
+/// The `base` file is not included in the merge output — all base events
+/// that were not superseded will already appear on at least one of the two
+/// sides. It is the common ancestor both sides are replayed from to find
+/// overlapping field writes.
+///
+/// With a `bones_dir`, events only `theirs` has are screened through its
+/// `policy.toml` and refused ones are quarantined there, and overlapping
+/// writes are reported under its `merge-reports/`. Failing to write a report
+/// is logged but does not fail the merge.
 ///
 /// # Errors
 ///
-/// Returns an error if any file cannot be read, cannot be parsed, or if
-/// writing the merged output fails.
-pub fn merge_driver_main(base: &Path, ours: &Path, theirs: &Path) -> Result<()> {
+/// Returns an error if any file cannot be read, cannot be parsed, the policy
+/// is invalid, or if writing the merged output or quarantine fails.
+pub fn merge_shard_files(
+    base: &Path,
+    ours: &Path,
+    theirs: &Path,
+    bones_dir: Option<&Path>,
+) -> Result<()> {
     info!(
         base = %base.display(),
         ours = %ours.display(),
@@ -75,7 +114,7 @@ pub fn merge_driver_main(base: &Path, ours: &Path, theirs: &Path) -> Result<()>
         "bones git merge driver invoked"
     );
 
-    // --- Parse base (for logging / future use) ---
+    // --- Parse base (the common ancestor) ---
     let base_content = fs::read_to_string(base)
         .with_context(|| format!("failed to read base file: {}", base.display()))?;
     let base_events = parse_lines(&base_content)
@@ -96,8 +135,24 @@ pub fn merge_driver_main(base: &Path, ours: &Path, theirs: &Path) -> Result<()>
         .map_err(|(line, err)| anyhow::anyhow!("parse error in theirs at line {line}: {err}"))?;
     info!(count = theirs_events.len(), "parsed theirs events");
 
-    // --- Merge ours + theirs ---
-    let merge_result = merge_event_sets(&ours_events, &theirs_events);
+    // --- Merge ours + theirs, screening theirs through the policy ---
+    let policy = bones_dir
+        .map(Policy::load)
+        .transpose()
+        .context("failed to load .bones/policy.toml")?
+        .unwrap_or_default();
+    let screened = merge_event_sets_with_policy(&ours_events, &theirs_events, &policy);
+    if let Some(bones_dir) = bones_dir
+        && !screened.rejected.is_empty()
+    {
+        policy::quarantine(bones_dir, &screened.rejected)
+            .context("failed to quarantine rejected events")?;
+        warn!(
+            count = screened.rejected.len(),
+            "events refused by policy were quarantined instead of merged"
+        );
+    }
+    let merge_result = screened.merge;
     let merged_count = merge_result.events.len();
     let dedup_count = merge_result.duplicates_skipped;
 
@@ -145,6 +200,32 @@ pub fn merge_driver_main(base: &Path, ours: &Path, theirs: &Path) -> Result<()>
         "merge driver wrote output successfully"
     );
 
+    // --- Report fields both sides wrote differently ---
+    if let Some(bones_dir) = bones_dir {
+        let accepted_theirs: Vec<_> = theirs_events
+            .into_iter()
+            .filter(|event| {
+                !screened
+                    .rejected
+                    .iter()
+                    .any(|r| r.event.event_hash == event.event_hash)
+            })
+            .collect();
+        let overlaps = detect_overlapping_writes(&base_events, &ours_events, &accepted_theirs);
+        if !overlaps.is_empty() {
+            let report = MergeReport::new(overlaps, chrono::Utc::now().timestamp_micros());
+            match merge_report::write_report(bones_dir, &report) {
+                Ok(Some(path)) => warn!(
+                    report = %path.display(),
+                    conflicts = report.conflicts.len(),
+                    "both sides wrote the same fields differently; see `bn status`"
+                ),
+                Ok(None) => {}
+                Err(err) => warn!(error = %err, "failed to write merge report"),
+            }
+        }
+    }
+
     Ok(())
 }
 
@@ -156,7 +237,7 @@ pub fn merge_driver_main(base: &Path, ours: &Path, theirs: &Path) -> Result<()>
 mod tests {
     use super::*;
     use bones_core::event::parser::parse_lines;
-    use bones_core::event::writer::{shard_header, write_event};
+    use bones_core::event::writer::{shard_header, write_event, write_line};
     use bones_core::event::{Event, EventData, EventType, data::*};
     use bones_core::model::item::*;
     use bones_core::model::item_id::ItemId;
@@ -200,6 +281,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: "placeholder".to_string(),
+            signature: None,
         };
         // Compute and set the real hash
         let line = write_event(&mut event).expect("write_event");
@@ -218,20 +300,44 @@ mod tests {
             item_id: ItemId::new_unchecked("bn-a7x"),
             data: EventData::Comment(CommentData {
                 body: body.to_string(),
+                reply_to: None,
                 extra: BTreeMap::new(),
             }),
             event_hash: "placeholder".to_string(),
+            signature: None,
         };
         let line = write_event(&mut event).expect("write_event");
         let _ = line;
         event
     }
 
+    /// Build a move Event descending from `parent`, with its hash computed.
+    fn make_move_event(ts: i64, agent: &str, parent: &Event, state: State) -> Event {
+        let mut event = Event {
+            wall_ts_us: ts,
+            agent: agent.to_string(),
+            itc: "itc:AQ".to_string(),
+            parents: vec![parent.event_hash.clone()],
+            event_type: EventType::Move,
+            item_id: ItemId::new_unchecked("bn-a7x"),
+            data: EventData::Move(MoveData {
+                state,
+                substate: None,
+                reason: None,
+                extra: BTreeMap::new(),
+            }),
+            event_hash: "placeholder".to_string(),
+            signature: None,
+        };
+        write_event(&mut event).expect("write_event");
+        event
+    }
+
     /// Write a shard file with the given events.
     fn write_shard(path: &Path, events: &[Event]) {
         let mut content = shard_header();
         for event in events {
-            let line = write_event(&mut event.clone()).expect("write_event");
+            let line = write_line(event).expect("write_line");
             content.push_str(&line);
         }
         fs::write(path, content).expect("write shard");
@@ -293,6 +399,71 @@ mod tests {
         assert_eq!(merged_events.len(), 2, "duplicate events should be deduped");
     }
 
+    #[test]
+    fn merge_quarantines_incoming_events_the_policy_refuses() {
+        let dir = temp_dir("policy");
+        let bones_dir = dir.join(".bones");
+        fs::create_dir_all(&bones_dir).expect("create .bones");
+        fs::write(
+            bones_dir.join("policy.toml"),
+            "[[rule]]\nevents = [\"item.comment\"]\nagents = [\"alice\"]\n",
+        )
+        .expect("write policy");
+        let base = dir.join("base.events");
+        let ours = dir.join("ours.events");
+        let theirs = dir.join("theirs.events");
+
+        let key = bones_core::signing::SigningKey::from_bytes(&[7; 32]);
+        bones_core::signing::register_public_key(&bones_dir, "alice", &key.verifying_key())
+            .expect("register key");
+
+        let create = make_create_event(1000, "alice");
+        let mut allowed = make_comment_event(2000, "alice", "Allowed");
+        allowed.signature =
+            Some(bones_core::signing::sign_event_hash(&key, &allowed.event_hash).expect("sign"));
+        let refused = make_comment_event(3000, "mallory", "Refused");
+        write_shard(&base, &[create.clone()]);
+        write_shard(&ours, &[create.clone()]);
+        write_shard(&theirs, &[create, allowed, refused.clone()]);
+
+        merge_shard_files(&base, &ours, &theirs, Some(&bones_dir)).expect("merge");
+
+        let merged = parse_lines(&fs::read_to_string(&ours).expect("read")).expect("parse");
+        assert_eq!(merged.len(), 2);
+        assert!(merged.iter().all(|e| e.event_hash != refused.event_hash));
+        let quarantine = policy::quarantine_path(&bones_dir, refused.wall_ts_us);
+        let quarantined = fs::read_to_string(quarantine).expect("quarantine file");
+        assert!(quarantined.contains(&refused.event_hash));
+    }
+
+    #[test]
+    fn merge_reports_contradictory_moves() {
+        let dir = temp_dir("report");
+        let bones_dir = dir.join(".bones");
+        fs::create_dir_all(&bones_dir).expect("create .bones");
+        let base = dir.join("base.events");
+        let ours = dir.join("ours.events");
+        let theirs = dir.join("theirs.events");
+
+        let create = make_create_event(1000, "alice");
+        let our_move = make_move_event(2000, "alice", &create, State::Doing);
+        let their_move = make_move_event(3000, "bob", &create, State::Done);
+        write_shard(&base, &[create.clone()]);
+        write_shard(&ours, &[create.clone(), our_move.clone()]);
+        write_shard(&theirs, &[create, their_move.clone()]);
+
+        merge_shard_files(&base, &ours, &theirs, Some(&bones_dir)).expect("merge");
+
+        let merged = parse_lines(&fs::read_to_string(&ours).expect("read")).expect("parse");
+        assert_eq!(merged.len(), 3, "the union still succeeds");
+        let reports = merge_report::pending_reports(&bones_dir).expect("reports");
+        assert_eq!(reports.len(), 1);
+        let conflict = &reports[0].conflicts[0];
+        assert_eq!(conflict.field, "state");
+        assert_eq!(conflict.ours.event_hash, our_move.event_hash);
+        assert_eq!(conflict.theirs.event_hash, their_move.event_hash);
+    }
+
     #[test]
     fn merge_preserves_sort_order_by_timestamp() {
         let dir = temp_dir("sort-order");
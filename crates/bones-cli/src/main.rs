@@ -53,6 +53,7 @@ static ALLOC: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
 static ALLOC: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
 mod agent;
+mod author;
 mod cmd;
 mod git;
 mod itc_state;
@@ -154,7 +155,7 @@ enum Commands {
         next_help_heading = "Read",
         about = "List bones in the pile",
         long_about = "List bones with optional filters and sort order.",
-        after_help = "EXAMPLES:\n    # List open bones (default)\n    bn list\n\n    # List all states\n    bn list --all\n\n    # Filter by state and label\n    bn list --state doing --label backend\n\n    # Machine-readable output\n    bn list --format json"
+        after_help = "EXAMPLES:\n    # List open bones (default)\n    bn list\n\n    # List all states\n    bn list --all\n\n    # Filter by state and label\n    bn list --state doing --label backend\n\n    # Structured query\n    bn list --where 'label:api AND NOT assignee:* AND size>=M'\n\n    # A saved view (see `bn view`)\n    bn list --view triage-api\n\n    # The backlog as it was at the end of a past day\n    bn list --at 2026-03-06\n\n    # Machine-readable output\n    bn list --format json"
     )]
     List(cmd::list::ListArgs),
 
@@ -162,18 +163,72 @@ enum Commands {
         next_help_heading = "Read",
         about = "Emit a bundled task context snapshot",
         long_about = "Emit a chief-facing JSON context snapshot with project counts, recommended next work, blocked work, active goals, and projection freshness metadata.",
-        after_help = "EXAMPLES:\n    # Machine-readable task context\n    bn context --format json"
+        after_help = "EXAMPLES:\n    # Machine-readable task context\n    bn context --format json\n\n    # Context for one area of the backlog\n    bn context --where 'label:api OR under:bn-goal'\n\n    # Context for a saved view\n    bn context --view triage-api"
     )]
     Context(cmd::context::ContextArgs),
 
+    #[command(
+        next_help_heading = "Read",
+        about = "Manage saved list views",
+        long_about = "Save, list, and remove named `bn list` filter combinations.\n\n\
+                      Views live in .bones/views.toml and are committed with the project,\n\
+                      so the whole team shares them. Use a view with `bn list --view <name>`,\n\
+                      `bn context --view <name>`, or the TUI view switcher (V).",
+        after_help = "EXAMPLES:\n    # Save a view\n    bn view save triage-api --label api --where 'NOT assignee:*' --sort priority\n\n    # List saved views\n    bn view list\n\n    # Use it\n    bn list --view triage-api\n\n    # Remove it\n    bn view remove triage-api"
+    )]
+    View(cmd::view::ViewArgs),
+
+    #[command(
+        next_help_heading = "Read",
+        about = "Run read-only SQL over the projection",
+        long_about = "Run one read-only SQL statement against documented views of the projection.\n\n\
+                      The bones_items, bones_labels, bones_assignees, bones_dependencies,\n\
+                      bones_comments, bones_events and bones_transitions views keep their\n\
+                      columns across schema migrations; `bn query schema` describes them.\n\
+                      Writes are rejected.",
+        after_help = "EXAMPLES:\n    # Open bones per label\n    bn query sql \"SELECT label, count(*) FROM bones_labels l JOIN bones_items i ON i.id = l.item_id WHERE i.state = 'open' GROUP BY label\"\n\n    # Who wrote the most events\n    bn query sql \"SELECT agent, count(*) AS n FROM bones_events GROUP BY agent ORDER BY n DESC\"\n\n    # Describe the views\n    bn query schema\n\n    # Machine-readable output\n    bn query sql \"SELECT id, title FROM bones_items\" --format json"
+    )]
+    Query(cmd::query::QueryArgs),
+
+    #[command(
+        next_help_heading = "Reporting",
+        about = "Report flow metrics from state-transition history",
+        long_about = "Report cycle time (first start to done), lead time (creation to done),\n\
+                      weekly throughput, and WIP at the end of each week, from the history of\n\
+                      state moves. Durations are reported as p50/p85/p95 percentiles in days.\n\
+                      Slice with --by kind, label, assignee, or goal.",
+        after_help = "EXAMPLES:\n    # Flow over the last 12 weeks\n    bn metrics flow\n\n    # How long bugs take, per label, over 4 weeks\n    bn metrics flow --where 'kind:bug' --by label --weeks 4\n\n    # Per goal\n    bn metrics flow --by goal\n\n    # Machine-readable output with percentiles\n    bn metrics flow --format json"
+    )]
+    Metrics(cmd::metrics::MetricsArgs),
+
     #[command(
         next_help_heading = "Read",
         about = "Show one bone",
         long_about = "Show full details for a single bone by ID.",
-        after_help = "EXAMPLES:\n    # Show a bone\n    bn show bn-abc\n\n    # Use a short prefix when unique\n    bn show abc\n\n    # Machine-readable output\n    bn show bn-abc --format json"
+        after_help = "EXAMPLES:\n    # Show a bone\n    bn show bn-abc\n\n    # Use a short prefix when unique\n    bn show abc\n\n    # The bone as it was a week ago\n    bn show bn-abc --at 1w\n\n    # Machine-readable output\n    bn show bn-abc --format json"
     )]
     Show(cmd::show::ShowArgs),
 
+    #[command(
+        next_help_heading = "Read",
+        about = "List concurrent writes that lost",
+        long_about = "List LWW field writes (title, urgency, parent, ...) that lost to a concurrent\n\
+                      write from another replica, with the agents involved. --reapply writes\n\
+                      the chosen value again so it supersedes every concurrent write.",
+        after_help = "EXAMPLES:\n    # List conflicts across the project\n    bn conflicts\n\n    # Conflicts for one bone\n    bn conflicts bn-abc\n\n    # Restore the value written by a losing event\n    bn conflicts --reapply blake3:abcd...\n\n    # Machine-readable output\n    bn conflicts --format json"
+    )]
+    Conflicts(cmd::conflicts::ConflictsArgs),
+
+    #[command(
+        next_help_heading = "Read",
+        about = "Show how the backlog changed between git revisions",
+        long_about = "Read .bones/events at two git revisions and show per-bone changes (created,\n\
+                      moved, relinked, relabelled, updated) made by the events only the newer\n\
+                      side has. A single revision is compared with the working tree.",
+        after_help = "EXAMPLES:\n    # What a feature branch did to the backlog\n    bn diff main..HEAD\n\n    # Only changes since the branch forked from main\n    bn diff main...HEAD\n\n    # Working tree compared with main\n    bn diff main\n\n    # Machine-readable output\n    bn diff main..HEAD --format json"
+    )]
+    Diff(cmd::diff::DiffArgs),
+
     #[command(hide = true)]
     #[command(
         next_help_heading = "Read",
@@ -359,7 +414,8 @@ enum Commands {
                       Does NOT delete or modify existing events — the append-only event log\n\
                       and Merkle-DAG integrity are preserved.\n\n\
                       Events that CANNOT be undone (grow-only):\n\
-                      - item.comment (G-Set: comments are permanent)\n\
+                      - item.comment (G-Set: use `bn bone comment retract` to hide one)\n\
+                      - item.comment_retract (retractions are permanent)\n\
                       - item.compact, item.snapshot (compaction)\n\
                       - item.redact (intentionally permanent)",
         after_help = "EXAMPLES:\n    # Undo the last event on a bone\n    bn bone undo bn-abc\n\n    # Undo the last 3 events\n    bn bone undo bn-abc --last 3\n\n    # Undo a specific event by hash\n    bn bone undo --event blake3:abcdef...\n\n    # Preview without emitting\n    bn bone undo bn-abc --dry-run\n\n    # Machine-readable output\n    bn bone undo bn-abc --format json"
@@ -388,8 +444,10 @@ enum Commands {
     #[command(
         next_help_heading = "Metadata",
         about = "Add a comment to a bone",
-        long_about = "Append an immutable comment event to a bone.",
-        after_help = "EXAMPLES:\n    # Add a comment\n    bn bone comment add bn-abc \"Investigating timeout path\"\n\n    # Emit machine-readable output\n    bn bone comment add bn-abc \"Investigating timeout path\" --format json"
+        long_about = "Append, edit, or retract comments on a bone.\n\n\
+                      Edits supersede the body but keep earlier versions in the history;\n\
+                      retraction hides a comment without redacting its events.",
+        after_help = "EXAMPLES:\n    # Add a comment\n    bn bone comment add bn-abc \"Investigating timeout path\"\n\n    # Reply to a comment\n    bn bone comment add bn-abc \"Same here\" --reply-to blake3:1f2e\n\n    # Edit or retract a comment\n    bn bone comment edit bn-abc blake3:1f2e \"Fixed typo\"\n    bn bone comment retract bn-abc blake3:1f2e --reason \"wrong bone\"\n\n    # Emit machine-readable output\n    bn bone comment add bn-abc \"Investigating timeout path\" --format json"
     )]
     Comment(cmd::comment::CommentArgs),
 
@@ -397,11 +455,31 @@ enum Commands {
     #[command(
         next_help_heading = "Read",
         about = "Show comment timeline for a bone",
-        long_about = "List comments for a bone in chronological order.",
+        long_about = "List comments for a bone as reply threads in chronological order.",
         after_help = "EXAMPLES:\n    # Show comments\n    bn bone comments bn-abc\n\n    # Emit machine-readable output\n    bn bone comments bn-abc --format json"
     )]
     Comments(cmd::comment::CommentsArgs),
 
+    #[command(hide = true)]
+    #[command(
+        next_help_heading = "Metadata",
+        about = "Attach a file to a bone",
+        long_about = "Store a file in the content-addressed blob store (.bones/blobs/) and record\n\
+                      an attachment event referencing its hash, name, and MIME type.\n\n\
+                      Identical files are stored once. Text files are checked for secrets.",
+        after_help = "EXAMPLES:\n    # Attach a log file\n    bn bone attach bn-abc ./ci-failure.log\n\n    # Override name and MIME type\n    bn bone attach bn-abc ./out.bin --name trace.json --mime application/json\n\n    # Emit machine-readable output\n    bn bone attach bn-abc ./shot.png --format json"
+    )]
+    Attach(cmd::attach::AttachArgs),
+
+    #[command(hide = true)]
+    #[command(
+        next_help_heading = "Read",
+        about = "List or extract a bone's attachments",
+        long_about = "List attachments on a bone, or copy one out of the blob store with --extract.",
+        after_help = "EXAMPLES:\n    # List attachments\n    bn bone attachments bn-abc\n\n    # Extract by name\n    bn bone attachments bn-abc --extract ci-failure.log --out /tmp/ci.log\n\n    # Write to stdout\n    bn bone attachments bn-abc --extract ci-failure.log --out -"
+    )]
+    Attachments(cmd::attach::AttachmentsArgs),
+
     #[command(hide = true)]
     #[command(
         next_help_heading = "Metadata",
@@ -441,9 +519,12 @@ enum Commands {
     #[command(hide = true)]
     #[command(
         next_help_heading = "Lifecycle",
-        about = "Move a bone under a parent",
-        long_about = "Change a bone's parent to reorganize hierarchy.",
-        after_help = "EXAMPLES:\n    # Move under a goal\n    bn bone move bn-task --parent bn-goal\n\n    # Emit machine-readable output\n    bn bone move bn-task --parent bn-goal --format json"
+        about = "Move a bone under a parent or to a workflow state",
+        long_about = "Change a bone's parent to reorganize hierarchy, or move it to a workflow state.\n\n\
+                      States are open, doing, done, archived, plus any sub-states declared under\n\
+                      [states] in .bones/config.toml (e.g. review, blocked). Each sub-state maps\n\
+                      onto a built-in phase, which triage and scheduling use.",
+        after_help = "EXAMPLES:\n    # Move under a goal\n    bn bone move bn-task --parent bn-goal\n\n    # Move to a configured sub-state\n    bn bone move bn-task review\n\n    # Emit machine-readable output\n    bn bone move bn-task --parent bn-goal --format json"
     )]
     Move(cmd::move_cmd::MoveArgs),
 
@@ -492,8 +573,8 @@ enum Commands {
     #[command(
         next_help_heading = "Read",
         about = "Show goal completion progress",
-        long_about = "Show a focused goal-progress view with child tree and progress bars.\n\nDistinct from `bn show` — this is focused on completion status of a goal and its children.",
-        after_help = "EXAMPLES:\n    # Show progress for a goal\n    bn triage progress bn-p1\n\n    # Machine-readable output\n    bn triage progress bn-p1 --format json"
+        long_about = "Show a focused goal-progress view with child tree and progress bars.\n\nDistinct from `bn show` — this is focused on completion status of a goal and its children.\n\nWith --forecast, simulates the goal's remaining dependency graph using past cycle times per size and weekly throughput, and reports P50/P85/P95 completion dates.",
+        after_help = "EXAMPLES:\n    # Show progress for a goal\n    bn triage progress bn-p1\n\n    # Forecast when the goal will be done\n    bn triage progress bn-p1 --forecast\n\n    # Machine-readable output\n    bn triage progress bn-p1 --format json"
     )]
     Progress(cmd::progress::ProgressArgs),
 
@@ -501,7 +582,7 @@ enum Commands {
     #[command(
         next_help_heading = "Triage",
         about = "Compute parallel execution layers",
-        long_about = "Compute topological dependency layers where each layer can be worked in parallel.",
+        long_about = "Compute topological dependency layers where each layer can be worked in parallel.\n\nAlso reports the size-weighted critical path and a Monte-Carlo forecast of P50/P85/P95 completion dates.",
         after_help = "EXAMPLES:\n    # Project-wide plan\n    bn triage plan\n\n    # Scope to one goal's children\n    bn triage plan bn-goal\n\n    # Emit machine-readable output\n    bn triage plan --format json"
     )]
     Plan(cmd::plan::PlanArgs),
@@ -547,8 +628,10 @@ enum Commands {
     #[command(
         next_help_heading = "Project Maintenance",
         about = "Verify event and manifest integrity",
-        long_about = "Verify shard manifests and event integrity checks for this project.",
-        after_help = "EXAMPLES:\n    # Verify all shard files\n    bn admin verify\n\n    # Verify only staged files\n    bn admin verify --staged\n\n    # Emit machine-readable output\n    bn admin verify --format json"
+        long_about = "Verify shard manifests and event integrity checks for this project.\n\n\
+                      --signatures also checks every event signature against the public keys\n\
+                      in .bones/keys/ and reports valid, unsigned, and bad signatures per agent.",
+        after_help = "EXAMPLES:\n    # Verify all shard files\n    bn admin verify\n\n    # Verify only staged files\n    bn admin verify --staged\n\n    # Check event signatures\n    bn verify --signatures\n\n    # Emit machine-readable output\n    bn admin verify --format json"
     )]
     Verify {
         /// Validate only staged files.
@@ -558,8 +641,28 @@ enum Commands {
         /// Regenerate missing manifests for sealed shards.
         #[arg(long)]
         regenerate_missing: bool,
+
+        /// Also check event signatures against `.bones/keys/`.
+        #[arg(long)]
+        signatures: bool,
+
+        /// With --signatures, also fail on unsigned or unverifiable events.
+        #[arg(long, requires = "signatures")]
+        require_signed: bool,
     },
 
+    #[command(hide = true)]
+    #[command(
+        next_help_heading = "Security",
+        about = "Redact an event's content",
+        long_about = "Emit an item.redact event for one event and scrub its content from the\n\
+                      projection, FTS5 index, semantic embeddings, and binary cache.\n\n\
+                      Target an event by hash, or a bone's Nth comment (oldest first) with\n\
+                      --comment. The redact-verify checks run automatically afterwards.",
+        after_help = "EXAMPLES:\n    # Redact the second comment on a bone\n    bn admin redact bn-abc --comment 2 --reason \"leaked token\"\n\n    # Redact an event by hash\n    bn admin redact blake3:abcd... --reason \"PII\"\n\n    # Machine-readable output\n    bn admin redact bn-abc --comment 1 --reason leak --format json"
+    )]
+    Redact(cmd::redact::RedactArgs),
+
     #[command(hide = true)]
     #[command(
         name = "redact-verify",
@@ -572,6 +675,17 @@ enum Commands {
     )]
     RedactVerify(cmd::redact_verify::RedactVerifyArgs),
 
+    #[command(
+        next_help_heading = "Security",
+        about = "Manage event signing keys",
+        long_about = "Generate, list, and register Ed25519 keys used to sign events.\n\n\
+                      Public keys live in .bones/keys/<agent>.pub and are committed with the\n\
+                      project; secret keys stay in the user config directory (or $BONES_KEY_DIR).\n\
+                      Once an agent has a secret key, every event it writes is signed.",
+        after_help = "EXAMPLES:\n    # Create a key for the current agent\n    bn keys generate\n\n    # List registered keys\n    bn keys list\n\n    # Register a teammate's public key\n    bn keys add alice ed25519:...\n\n    # Check signatures\n    bn verify --signatures"
+    )]
+    Keys(cmd::keys::KeysArgs),
+
     #[command(hide = true)]
     #[command(
         next_help_heading = "Read",
@@ -582,6 +696,7 @@ enum Commands {
                       - /: search (filter by text)\n\
                       - f: open filter popup (state, kind, urgency, label)\n\
                       - s: cycle sort order (execution → priority → created → updated)\n\
+                      - V: cycle saved views from .bones/views.toml\n\
                       - r: refresh from database\n\
                       - ESC: clear all filters\n\
                       - q or Ctrl+C: quit",
@@ -656,7 +771,7 @@ enum Commands {
         next_help_heading = "Lifecycle",
         about = "Bone-scoped operations",
         long_about = "Grouped bone operations including history, metadata, assignment, comments, and lifecycle detail.",
-        after_help = "QUICK REFERENCE:\n    bn bone log <id>                 # bone event timeline\n    bn bone assign <id> <agent>      # assign\n    bn bone comment add <id> <text>  # add comment\n    bn bone tag <id> <label...>      # add labels\n    bn bone punt <id>                # set urgency=punt\n    bn bone escalate <id>            # set urgency=urgent\n    bn bone normalize <id>           # set urgency=default\n    bn bone close <id>               # close bone\n    bn bone reopen <id>              # reopen bone\n\nEXAMPLES:\n    # Show bone event timeline\n    bn bone log bn-abc\n\n    # Assign a bone\n    bn bone assign bn-abc alice\n\n    # Add a comment\n    bn bone comment add bn-abc \"Investigating\""
+        after_help = "QUICK REFERENCE:\n    bn bone log <id>                 # bone event timeline\n    bn bone assign <id> <agent>      # assign\n    bn bone comment add <id> <text>  # add comment\n    bn bone attach <id> <file>       # attach a file\n    bn bone tag <id> <label...>      # add labels\n    bn bone punt <id>                # set urgency=punt\n    bn bone escalate <id>            # set urgency=urgent\n    bn bone normalize <id>           # set urgency=default\n    bn bone close <id>               # close bone\n    bn bone reopen <id>              # reopen bone\n\nEXAMPLES:\n    # Show bone event timeline\n    bn bone log bn-abc\n\n    # Assign a bone\n    bn bone assign bn-abc alice\n\n    # Add a comment\n    bn bone comment add bn-abc \"Investigating\""
     )]
     Bone {
         #[command(subcommand)]
@@ -729,6 +844,44 @@ enum Commands {
     )]
     Export(cmd::export::ExportArgs),
 
+    #[command(
+        next_help_heading = "Sync",
+        about = "Sync events with git or another replica",
+        long_about = "Pull, rebuild the projection incrementally, and push through git.\n\n\
+                      With --remote, skip git and run the Prolly Tree sync protocol against the\n\
+                      replica served by a shell command, usually `bn sync-serve --stdio` under SSH.\n\
+                      Both sides end up with the union of their events.\n\n\
+                      Received events, including those a git pull fast-forwards in, are checked\n\
+                      against .bones/policy.toml. Refused ones move to events/*.rejected; events a\n\
+                      rule covers must be signed by their author (see `bn keys`).",
+        after_help = "EXAMPLES:\n    # Pull, rebuild, and push through git\n    bn sync\n\n    # Only refresh .gitattributes / .gitignore\n    bn sync --config-only\n\n    # Sync with a replica over SSH\n    bn sync --remote \"ssh host 'cd repo && bn sync-serve --stdio'\"\n\n    # Only pull one goal's subtree (plus its blockers)\n    bn sync --remote \"ssh host 'cd repo && bn sync-serve --stdio'\" --goal bn-abc"
+    )]
+    Sync(cmd::sync::SyncArgs),
+
+    #[command(hide = true)]
+    #[command(
+        name = "sync-serve",
+        next_help_heading = "Sync",
+        about = "Serve one sync session on stdin/stdout",
+        long_about = "Answer a `bn sync --remote` session over stdin/stdout. This is plumbing:\n\
+                      the client spawns it, usually through SSH.",
+        after_help = "EXAMPLES:\n    # What `bn sync --remote` runs on the other host\n    bn sync-serve --stdio"
+    )]
+    SyncServe(cmd::sync::SyncServeArgs),
+
+    #[command(
+        next_help_heading = "Sync",
+        about = "Sync with air-gapped replicas through bundle files",
+        long_about = "Write the events another replica is missing to a self-contained bundle file,\n\
+                      or verify and merge a bundle received from one. Bundles travel by USB\n\
+                      stick or email between machines that never share a git remote.\n\n\
+                      A bundle records the Prolly root it was built against and the creator's\n\
+                      root at creation time; pass a previous head to --since to send only\n\
+                      newer events.",
+        after_help = "EXAMPLES:\n    # Bundle every event\n    bn bundle create handoff.bundle\n\n    # Bundle events since the previous handoff\n    bn bundle create handoff.bundle --since 3fa9c1d2e4b5\n\n    # Merge a bundle from another machine\n    bn bundle apply handoff.bundle\n\n    # Show this replica's root\n    bn bundle root"
+    )]
+    Bundle(cmd::bundle::BundleArgs),
+
     #[command(hide = true)]
     #[command(
         next_help_heading = "Sync",
@@ -847,6 +1000,10 @@ enum BoneCommand {
     Comment(cmd::comment::CommentArgs),
     #[command(about = "Show comment timeline for a bone")]
     Comments(cmd::comment::CommentsArgs),
+    #[command(about = "Attach a file to a bone")]
+    Attach(cmd::attach::AttachArgs),
+    #[command(about = "List or extract a bone's attachments")]
+    Attachments(cmd::attach::AttachmentsArgs),
     #[command(about = "List all labels with usage counts")]
     Labels(cmd::labels::LabelsArgs),
     #[command(about = "Single-label operations")]
@@ -861,14 +1018,18 @@ enum BoneCommand {
     Escalate(cmd::urgency::UrgencyQuickArgs),
     #[command(about = "Reset bone urgency to default")]
     Normalize(cmd::urgency::UrgencyQuickArgs),
-    #[command(about = "Move a bone under a parent")]
+    #[command(about = "Move a bone under a parent or to a workflow state")]
     Move(cmd::move_cmd::MoveArgs),
 }
 
 #[derive(Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
 struct TriageGroupArgs {
     #[command(subcommand)]
     command: Option<TriageCommand>,
+
+    #[command(flatten)]
+    report: cmd::triage::TriageArgs,
 }
 
 #[derive(Subcommand, Debug)]
@@ -912,7 +1073,13 @@ enum AdminCommand {
         staged: bool,
         #[arg(long)]
         regenerate_missing: bool,
+        #[arg(long)]
+        signatures: bool,
+        #[arg(long, requires = "signatures")]
+        require_signed: bool,
     },
+    #[command(about = "Redact an event's content")]
+    Redact(cmd::redact::RedactArgs),
     #[command(name = "redact-verify", about = "Verify redaction completeness")]
     RedactVerify(cmd::redact_verify::RedactVerifyArgs),
     #[command(about = "Compact event log for completed bones")]
@@ -1130,6 +1297,7 @@ QUICK REFERENCE
       bn list                              # open bones (default)
       bn list --all                        # all states
       bn list --state doing                # filter by state
+      bn list --where 'has:blocker'        # structured query
       bn list --sort newest                # sort by creation
 
   Show bone details
@@ -1140,7 +1308,7 @@ QUICK REFERENCE
 
       bn search \"query\"
 
-  Move a bone under a parent (reparent)
+  Move a bone under a parent or to a workflow state
 
       bn bone move <id> --parent <goal-id>
       bn bone move <id> --parent none        # make top-level
@@ -1203,6 +1371,11 @@ fn main() -> anyhow::Result<()> {
 
     let current_dir = std::env::current_dir()?;
     let output = cli.output_mode();
+    // A saved view's output mode applies only when --format/--json is absent.
+    let format_explicit = cli.format.is_some() || cli.json;
+    let view_output = |output: OutputMode, view_format: Option<OutputMode>| {
+        view_format.filter(|_| !format_explicit).unwrap_or(output)
+    };
 
     // Check that we're inside a bones project for commands that need one.
     let needs_project = !matches!(
@@ -1212,6 +1385,7 @@ fn main() -> anyhow::Result<()> {
             | Commands::Completions(_)
             | Commands::MergeTool { .. }
             | Commands::MergeDriver { .. }
+            | Commands::SyncServe(_)
     );
     let project_root = if needs_project {
         find_project_root(&current_dir).ok_or_else(|| {
@@ -1243,20 +1417,42 @@ fn main() -> anyhow::Result<()> {
             cmd::create::run_create(args, cli.agent_flag(), output, &project_root)
         }),
         Commands::List(ref args) => timing::timed("cmd.list", || {
-            cmd::list::run_list(args, output, &project_root)
+            let (args, view_format) = cmd::view::apply_list_view(args, &project_root, output)?;
+            let output = view_output(output, view_format);
+            cmd::list::run_list(&args, output, &project_root)
+        }),
+        Commands::View(ref args) => timing::timed("cmd.view", || {
+            cmd::view::run_view(args, output, &project_root)
         }),
         Commands::Context(ref args) => timing::timed("cmd.context", || {
             cmd::context::run_context(args, output, &project_root)
         }),
+        Commands::Query(ref args) => timing::timed("cmd.query", || {
+            cmd::query::run_query(args, output, &project_root)
+        }),
+        Commands::Metrics(ref args) => timing::timed("cmd.metrics", || {
+            cmd::metrics::run_metrics(args, output, &project_root)
+        }),
         Commands::Agents(ref args) => timing::timed("cmd.agents", || {
             cmd::agents::run_agents(args, output, &project_root)
         }),
         Commands::Mine(ref args) => timing::timed("cmd.mine", || {
-            cmd::mine::run_mine(args, cli.agent_flag(), output, &project_root)
+            let mut args = args.clone();
+            let (list, view_format) =
+                cmd::view::apply_list_view(&args.list, &project_root, output)?;
+            args.list = list;
+            let output = view_output(output, view_format);
+            cmd::mine::run_mine(&args, cli.agent_flag(), output, &project_root)
         }),
         Commands::Show(ref args) => timing::timed("cmd.show", || {
             cmd::show::run_show(args, output, &project_root)
         }),
+        Commands::Conflicts(ref args) => timing::timed("cmd.conflicts", || {
+            cmd::conflicts::run_conflicts(args, cli.agent_flag(), output, &project_root)
+        }),
+        Commands::Diff(ref args) => timing::timed("cmd.diff", || {
+            cmd::diff::run_diff(args, output, &project_root)
+        }),
         Commands::Log(ref args) => {
             timing::timed("cmd.log", || cmd::log::run_log(args, output, &project_root))
         }
@@ -1323,6 +1519,12 @@ fn main() -> anyhow::Result<()> {
         Commands::Comments(ref args) => timing::timed("cmd.comments", || {
             cmd::comment::run_comments(args, output, &project_root)
         }),
+        Commands::Attach(ref args) => timing::timed("cmd.attach", || {
+            cmd::attach::run_attach(args, cli.agent_flag(), output, &project_root)
+        }),
+        Commands::Attachments(ref args) => timing::timed("cmd.attachments", || {
+            cmd::attach::run_attachments(args, output, &project_root)
+        }),
         Commands::Labels(ref args) => timing::timed("cmd.labels", || {
             cmd::labels::run_labels(args, output, &project_root)
         }),
@@ -1348,10 +1550,7 @@ fn main() -> anyhow::Result<()> {
             cmd::next::run_next(args, cli.agent_flag(), output, &project_root)
         }),
         Commands::Triage(ref args) => timing::timed("cmd.triage", || match &args.command {
-            None => {
-                let defaults = cmd::triage::TriageArgs::default();
-                cmd::triage::run_triage(&defaults, output, &project_root)
-            }
+            None => cmd::triage::run_triage(&args.report, output, &project_root),
             Some(TriageCommand::Report(report_args)) => {
                 cmd::triage::run_triage(report_args, output, &project_root)
             }
@@ -1444,6 +1643,12 @@ fn main() -> anyhow::Result<()> {
                 cmd::comment::run_comment(args, cli.agent_flag(), output, &project_root)
             }
             BoneCommand::Comments(args) => cmd::comment::run_comments(args, output, &project_root),
+            BoneCommand::Attach(args) => {
+                cmd::attach::run_attach(args, cli.agent_flag(), output, &project_root)
+            }
+            BoneCommand::Attachments(args) => {
+                cmd::attach::run_attachments(args, output, &project_root)
+            }
             BoneCommand::Labels(args) => cmd::labels::run_labels(args, output, &project_root),
             BoneCommand::Label(args) => {
                 cmd::labels::run_label(args, cli.agent_flag(), output, &project_root)
@@ -1479,13 +1684,23 @@ fn main() -> anyhow::Result<()> {
             AdminCommand::Verify {
                 staged,
                 regenerate_missing,
+                signatures,
+                require_signed,
             } => {
                 if *staged {
                     git::hooks::verify_staged_events()
                 } else {
-                    cmd::verify::run_verify(&project_root, *regenerate_missing, output)
+                    let options = cmd::verify::VerifyOptions {
+                        regenerate_missing: *regenerate_missing,
+                        signatures: *signatures,
+                        require_signed: *require_signed,
+                    };
+                    cmd::verify::run_verify(&project_root, options, output)
                 }
             }
+            AdminCommand::Redact(args) => {
+                cmd::redact::run_redact(args, cli.agent_flag(), output, &project_root)
+            }
             AdminCommand::RedactVerify(args) => {
                 cmd::redact_verify::run_redact_verify(args, output, &project_root)
             }
@@ -1562,13 +1777,35 @@ fn main() -> anyhow::Result<()> {
         Commands::Verify {
             staged,
             regenerate_missing,
+            signatures,
+            require_signed,
         } => timing::timed("cmd.verify", || {
             if staged {
                 git::hooks::verify_staged_events()
             } else {
-                cmd::verify::run_verify(&project_root, regenerate_missing, output)
+                let options = cmd::verify::VerifyOptions {
+                    regenerate_missing,
+                    signatures,
+                    require_signed,
+                };
+                cmd::verify::run_verify(&project_root, options, output)
             }
         }),
+        Commands::Sync(ref args) => timing::timed("cmd.sync", || {
+            cmd::sync::run_sync(args, output, &project_root)
+        }),
+        Commands::SyncServe(ref args) => timing::timed("cmd.sync_serve", || {
+            cmd::sync::run_sync_serve(args, &project_root)
+        }),
+        Commands::Bundle(ref args) => timing::timed("cmd.bundle", || {
+            cmd::bundle::run_bundle(args, output, &project_root)
+        }),
+        Commands::Keys(ref args) => timing::timed("cmd.keys", || {
+            cmd::keys::run_keys(args, cli.agent_flag(), output, &project_root)
+        }),
+        Commands::Redact(ref args) => timing::timed("cmd.redact", || {
+            cmd::redact::run_redact(args, cli.agent_flag(), output, &project_root)
+        }),
         Commands::RedactVerify(ref args) => timing::timed("cmd.redact_verify", || {
             cmd::redact_verify::run_redact_verify(args, output, &project_root)
         }),
@@ -1869,6 +2106,19 @@ mod tests {
         assert!(matches!(cli.command, Commands::Comments(_)));
     }
 
+    #[test]
+    fn attach_subcommands_parse() {
+        let cli = Cli::parse_from(["bn", "attach", "item-123", "ci.log"]);
+        assert!(matches!(cli.command, Commands::Attach(_)));
+        let cli = Cli::parse_from(["bn", "bone", "attachments", "item-123"]);
+        assert!(matches!(
+            cli.command,
+            Commands::Bone {
+                command: BoneCommand::Attachments(_)
+            }
+        ));
+    }
+
     #[test]
     fn labels_subcommand_parses() {
         let cli = Cli::parse_from(["bn", "labels"]);
@@ -1906,6 +2156,8 @@ mod tests {
             vec!["bn", "create", "--title", "x"],
             vec!["bn", "list"],
             vec!["bn", "show", "x"],
+            vec!["bn", "conflicts"],
+            vec!["bn", "diff", "main..HEAD"],
             vec!["bn", "log", "x"],
             vec!["bn", "history"],
             vec!["bn", "blame", "x", "title"],
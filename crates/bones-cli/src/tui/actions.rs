@@ -4,16 +4,17 @@
 //! item creation directly using the bones-core API, bypassing the CLI
 //! rendering layer that would corrupt the terminal screen.
 
+use crate::author::write_authored_event;
 use crate::itc_state::assign_next_itc;
 use crate::validate;
 use anyhow::{Context, Result};
+use bones_core::conflicts::observed_parents;
 use bones_core::db::{project, query};
 use bones_core::event::Event;
 use bones_core::event::data::{
     CommentData, CreateData, EventData, LinkData, MoveData, UnlinkData, UpdateData,
 };
 use bones_core::event::types::EventType;
-use bones_core::event::writer;
 use bones_core::model::item::{Kind, Size, State, Urgency};
 use bones_core::model::item_id::{ItemId, generate_item_id};
 use bones_core::shard::ShardManager;
@@ -47,7 +48,7 @@ fn append_event_locked(
 
     assign_next_itc(project_root, event)?;
 
-    let line = writer::write_event(event).context("serialize event")?;
+    let line = write_authored_event(event).context("serialize event")?;
     shard_mgr
         .append_raw(year, month, &line)
         .context("append to shard")?;
@@ -92,9 +93,11 @@ pub fn do_item(project_root: &Path, db_path: &Path, agent: &str, item_id: &str)
         data: EventData::Move(MoveData {
             state: target_state,
             reason: None,
+            substate: None,
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
 
     append_event_locked(project_root, &shard_mgr, &mut event)?;
@@ -144,9 +147,11 @@ pub fn done_item(project_root: &Path, db_path: &Path, agent: &str, item_id: &str
         data: EventData::Move(MoveData {
             state: target_state,
             reason: None,
+            substate: None,
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
 
     append_event_locked(project_root, &shard_mgr, &mut event)?;
@@ -212,6 +217,7 @@ pub fn create_item(
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
 
     append_event_locked(project_root, &shard_mgr, &mut event)?;
@@ -267,7 +273,7 @@ pub fn update_item_fields(
             wall_ts_us: 0,
             agent: agent.to_string(),
             itc: String::new(),
-            parents: vec![],
+            parents: observed_parents(&conn, item_id, field)?,
             event_type: EventType::Update,
             item_id: ItemId::new_unchecked(item_id),
             data: EventData::Update(UpdateData {
@@ -276,6 +282,7 @@ pub fn update_item_fields(
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
 
         append_event_locked(project_root, &shard_mgr, &mut event)?;
@@ -311,9 +318,11 @@ pub fn add_comment(
         item_id: ItemId::new_unchecked(item_id),
         data: EventData::Comment(CommentData {
             body: body.to_string(),
+            reply_to: None,
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
 
     append_event_locked(project_root, &shard_mgr, &mut event)?;
@@ -355,6 +364,7 @@ pub fn add_link(
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
 
     append_event_locked(project_root, &shard_mgr, &mut event)?;
@@ -396,6 +406,7 @@ pub fn remove_link(
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
 
     append_event_locked(project_root, &shard_mgr, &mut event)?;
@@ -483,9 +494,11 @@ pub fn move_item_state(
         data: EventData::Move(MoveData {
             state,
             reason,
+            substate: None,
             extra,
         }),
         event_hash: String::new(),
+        signature: None,
     };
 
     append_event_locked(project_root, &shard_mgr, &mut event)?;
@@ -505,6 +518,7 @@ mod tests {
     use bones_core::db::project::{Projector, ensure_tracking_table};
     use bones_core::event::data::EventData;
     use bones_core::event::types::EventType;
+    use bones_core::event::writer;
     use tempfile::tempdir;
 
     fn setup_project() -> (tempfile::TempDir, std::path::PathBuf) {
@@ -550,6 +564,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         let line = writer::write_event(&mut event).unwrap();
         shard_mgr
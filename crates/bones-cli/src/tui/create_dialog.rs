@@ -464,6 +464,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: format!("hash:{id}"),
+            signature: None,
         })
         .unwrap();
     }
@@ -42,6 +42,8 @@ impl ListView {
             semantic_model,
             semantic_search_ids: Vec::new(),
             semantic_search_active: false,
+            structured_query_ids: None,
+            active_view: None,
             semantic_refinement_rx: None,
             semantic_search_gen: 0,
             last_searched_query: String::new(),
@@ -135,8 +137,9 @@ impl ListView {
         // Only re-run search if the query changed since the last search.
         // Auto-refresh reloads the item list but shouldn't re-trigger search
         // (it causes a visible flash as results clear and re-populate).
+        // Structured filters are cheap and must track edits, so always rerun them.
         let query_changed = self.filter.search_query.trim() != self.last_searched_query;
-        if query_changed {
+        if query_changed || self.structured_query_ids.is_some() {
             let _ = self.refresh_semantic_search_ids();
         }
         self.apply_filter_and_sort();
@@ -152,7 +155,9 @@ impl ListView {
         let query_active = !self.filter.search_query.trim().is_empty();
         if query_active {
             let q = self.filter.search_query.trim().to_ascii_lowercase();
-            if self.semantic_search_active {
+            if let Some(ids) = &self.structured_query_ids {
+                filtered.retain(|bone| ids.contains(&bone.item_id));
+            } else if self.semantic_search_active {
                 let rank_index: HashMap<&str, usize> = self
                     .semantic_search_ids
                     .iter()
@@ -173,6 +178,9 @@ impl ListView {
             }
         }
 
+        // Structured queries only narrow the list; the usual ordering applies.
+        let ranked_search = query_active && self.structured_query_ids.is_none();
+
         let mut active_items = Vec::new();
         let mut done_items = Vec::new();
         for item in filtered {
@@ -183,7 +191,7 @@ impl ListView {
             }
         }
 
-        if !query_active {
+        if !ranked_search {
             match self.sort {
                 SortField::Execution => {
                     sort_items(&mut active_items, SortField::Priority);
@@ -193,11 +201,11 @@ impl ListView {
             }
         }
 
-        let (mut ordered, mut depths) = if query_active && self.semantic_search_active {
+        let (mut ordered, mut depths) = if ranked_search && self.semantic_search_active {
             // Search results are already ranked; preserve flat order.
             let len = active_items.len();
             (active_items, vec![0; len])
-        } else if !query_active && self.sort == SortField::Execution {
+        } else if !ranked_search && self.sort == SortField::Execution {
             build_dependency_order(active_items, &self.blocker_map, &self.parent_map)
         } else {
             build_hierarchy_order(active_items, &self.parent_map)
@@ -193,6 +193,9 @@ impl ListView {
                 self.input_mode = InputMode::FilterPopup;
             }
 
+            // Cycle saved views from .bones/views.toml
+            KeyCode::Char('V') => self.cycle_saved_view(),
+
             // Cycle sort order
             KeyCode::Char('s') if !self.show_detail => {
                 self.sort = self.sort.next();
@@ -548,10 +551,9 @@ impl ListView {
                 self.edit_link_modal = None;
                 self.input_mode = InputMode::Normal;
             }
-            KeyCode::Char('j') | KeyCode::Down
-                if !modal.links.is_empty() => {
-                    modal.list_idx = (modal.list_idx + 1).min(modal.links.len() - 1);
-                }
+            KeyCode::Char('j') | KeyCode::Down if !modal.links.is_empty() => {
+                modal.list_idx = (modal.list_idx + 1).min(modal.links.len() - 1);
+            }
             KeyCode::Char('k') | KeyCode::Up => {
                 modal.list_idx = modal.list_idx.saturating_sub(1);
             }
@@ -1240,16 +1242,17 @@ impl ListView {
         let relationships = load_detail_refs(&conn, relationship_ids)?;
 
         // Cap at 200 most recent comments to bound memory in the detail pane.
-        let mut comments: Vec<DetailComment> =
-            query::get_comments(&conn, item_id, Some(200), None)?
+        let comments: Vec<DetailComment> =
+            crate::cmd::show::load_comment_threads(&conn, item_id, Some(200), None)?
                 .into_iter()
                 .map(|comment| DetailComment {
+                    marker: crate::cmd::show::comment_marker(&comment),
                     author: comment.author,
                     body: comment.body,
                     created_at_us: comment.created_at_us,
+                    depth: comment.depth,
                 })
                 .collect();
-        comments.sort_by_key(|a| a.created_at_us);
 
         Ok(DetailItem {
             id: item.item_id,
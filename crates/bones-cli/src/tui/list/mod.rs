@@ -25,8 +25,10 @@
 use crate::{agent, validate};
 use anyhow::{Context, Result};
 use bones_core::config::load_project_config;
+use bones_core::db::filter_query::FilterQuery;
 use bones_core::db::query::{self, ItemFilter, QueryItem, SortOrder};
 use bones_core::model::item::{Kind, Size, State, Urgency};
+use bones_core::views::Views;
 use bones_search::fusion::{hybrid_search, hybrid_search_fast};
 use bones_search::semantic::SemanticModel;
 use chrono::{DateTime, Local, Utc};
@@ -41,6 +43,7 @@ use ratatui::{
 use std::{
     collections::{HashMap, HashSet},
     io::Write as _,
+    ops::Bound,
     path::Path,
     path::PathBuf,
     time::{Duration, Instant},
@@ -2,7 +2,6 @@ impl ListView {
     pub fn render(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect) {
         render_into(frame, self, area);
     }
-
 }
 
 fn state_color(state: &str) -> Color {
@@ -293,16 +292,24 @@ fn detail_lines(detail: &DetailItem) -> Vec<Line<'static>> {
                 .add_modifier(Modifier::BOLD),
         )]));
         for comment in &detail.comments {
+            let indent = "  ".repeat(comment.depth);
             lines.push(Line::from(""));
             lines.push(Line::from(vec![
+                Span::raw(indent.clone()),
                 Span::styled(comment.author.clone(), Style::default().fg(Color::Cyan)),
                 Span::raw("  "),
                 Span::styled(
                     micros_to_local_datetime(comment.created_at_us),
                     Style::default().fg(Color::DarkGray),
                 ),
+                Span::styled(comment.marker.clone(), Style::default().fg(Color::DarkGray)),
             ]));
-            lines.extend(super::markdown::markdown_to_lines(&comment.body));
+            for mut line in super::markdown::markdown_to_lines(&comment.body) {
+                if comment.depth > 0 {
+                    line.spans.insert(0, Span::raw(indent.clone()));
+                }
+                lines.push(line);
+            }
         }
     }
 
@@ -957,8 +964,9 @@ fn help_hotkeys() -> Vec<(&'static str, &'static str, &'static str)> {
         ("s", "list", "cycle list sort"),
         ("a", "list", "add bone"),
         ("F", "list", "open filter popup"),
+        ("V", "list", "cycle saved views"),
         ("D", "list", "toggle done visibility"),
-        ("/", "global", "search bones"),
+        ("/", "global", "search, or filter (label:api size>=m)"),
         ("?", "global", "open help overlay"),
         ("q", "global", "quit tui"),
         ("j/k", "detail", "scroll detail pane"),
@@ -971,7 +979,11 @@ fn help_hotkeys() -> Vec<(&'static str, &'static str, &'static str)> {
         ("E", "detail", "edit/remove links"),
         ("x", "detail", "done/reopen with note"),
         ("y", "global", "copy bone ID to clipboard"),
-        ("drag", "detail", "select text; copies to clipboard on release"),
+        (
+            "drag",
+            "detail",
+            "select text; copies to clipboard on release",
+        ),
         ("Tab", "create", "next field"),
         ("Shift+Tab", "create", "previous field"),
         ("Ctrl+S", "create", "save/create bone"),
@@ -1129,6 +1141,11 @@ fn render_into(frame: &mut ratatui::Frame<'_>, app: &mut ListView, area: Rect) {
             " bones — search: {}{refining_indicator} ",
             with_cursor_marker(&app.search_buf, app.search_cursor)
         ),
+        _ if let Some(name) = app.active_view_name() => format!(
+            " bones — view {name}: {} of {} bones{refining_indicator} ",
+            app.visible_items.len(),
+            app.all_items.len(),
+        ),
         _ if !app.filter.search_query.is_empty() => format!(
             " bones — {} results for \"{}\"{refining_indicator} ",
             app.visible_items.len(),
@@ -1538,4 +1555,3 @@ fn render_filter_popup(frame: &mut ratatui::Frame<'_>, app: &ListView, area: Rec
         );
     }
 }
-
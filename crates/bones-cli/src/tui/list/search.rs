@@ -3,6 +3,7 @@ impl ListView {
         // Bump generation to invalidate any in-flight background search.
         self.semantic_search_gen = self.semantic_search_gen.wrapping_add(1);
         self.semantic_refinement_rx = None;
+        self.structured_query_ids = None;
 
         let query = self.filter.search_query.trim();
         self.last_searched_query = query.to_string();
@@ -20,6 +21,29 @@ impl ListView {
             return Ok(());
         };
 
+        // Field predicates and boolean operators filter exactly; anything that
+        // does not parse as a structured query falls back to ranked search.
+        if let Ok(filter_query) = FilterQuery::parse(query)
+            && filter_query.is_structured()
+        {
+            let filter = ItemFilter {
+                query: Some(filter_query),
+                ..Default::default()
+            };
+            let ids = match query::list_items(&conn, &filter) {
+                Ok(items) => items.into_iter().map(|item| item.item_id).collect(),
+                Err(err) => {
+                    tracing::warn!("bones structured slash filter failed: {err:#}");
+                    HashSet::new()
+                }
+            };
+            self.structured_query_ids = Some(ids);
+            self.semantic_search_ids.clear();
+            self.semantic_search_active = false;
+            self.search_refining = false;
+            return Ok(());
+        }
+
         let effective_query =
             if !query.contains(' ') && !query.contains('*') && !query.contains(':') {
                 format!("{query}*")
@@ -114,4 +138,58 @@ impl ListView {
         }
     }
 
+
+    /// Name of the saved view in effect, if the search query still matches it.
+    pub(super) fn active_view_name(&self) -> Option<&str> {
+        self.active_view
+            .as_ref()
+            .filter(|(_, query)| *query == self.filter.search_query)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Apply the next saved view from `.bones/views.toml`, clearing the
+    /// filter after the last one.
+    fn cycle_saved_view(&mut self) {
+        let views = match Views::load(&self.project_root.join(".bones")) {
+            Ok(views) => views,
+            Err(err) => {
+                self.set_status(format!("Views: {err}"));
+                return;
+            }
+        };
+        if views.views.is_empty() {
+            self.set_status("No saved views (bn view save <name> ...)".to_string());
+            return;
+        }
+
+        let next = match self.active_view.as_ref() {
+            Some((current, _)) => views
+                .views
+                .range::<str, _>((Bound::Excluded(current.as_str()), Bound::Unbounded))
+                .next(),
+            None => views.views.iter().next(),
+        };
+        let Some((name, view)) = next else {
+            self.active_view = None;
+            self.set_search_query(String::new());
+            self.set_status("View cleared".to_string());
+            return;
+        };
+
+        let query = view.to_query().unwrap_or_default();
+        self.active_view = Some((name.clone(), query.clone()));
+        self.set_search_query(query);
+        self.set_status(format!(
+            "View: {name} ({} bones)",
+            self.visible_items.len()
+        ));
+    }
+
+    fn set_search_query(&mut self, query: String) {
+        self.search_cursor = char_len(&query);
+        self.search_buf.clone_from(&query);
+        self.filter.search_query = query;
+        let _ = self.refresh_semantic_search_ids();
+        self.apply_filter_and_sort();
+    }
 }
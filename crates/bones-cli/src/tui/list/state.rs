@@ -221,6 +221,10 @@ struct DetailComment {
     author: String,
     body: String,
     created_at_us: i64,
+    /// Reply nesting depth (0 for top-level comments).
+    depth: usize,
+    /// `" (edited)"`, `" (retracted by …)"`, or empty.
+    marker: String,
 }
 
 #[derive(Debug, Clone)]
@@ -1494,16 +1498,14 @@ fn edit_multiline(lines: &mut Vec<String>, row: &mut usize, col: &mut usize, key
                 *col = 0;
             }
         }
-        KeyCode::Up
-            if *row > 0 => {
-                *row -= 1;
-                *col = (*col).min(char_len(&lines[*row]));
-            }
-        KeyCode::Down
-            if *row + 1 < lines.len() => {
-                *row += 1;
-                *col = (*col).min(char_len(&lines[*row]));
-            }
+        KeyCode::Up if *row > 0 => {
+            *row -= 1;
+            *col = (*col).min(char_len(&lines[*row]));
+        }
+        KeyCode::Down if *row + 1 < lines.len() => {
+            *row += 1;
+            *col = (*col).min(char_len(&lines[*row]));
+        }
         KeyCode::Home => *col = 0,
         KeyCode::End => *col = char_len(&lines[*row]),
         KeyCode::Enter => insert_newline(lines, row, col),
@@ -1512,11 +1514,10 @@ fn edit_multiline(lines: &mut Vec<String>, row: &mut usize, col: &mut usize, key
         }
         KeyCode::Delete => delete_multiline(lines, row, col),
         KeyCode::Char('\n' | '\r') => insert_newline(lines, row, col),
-        KeyCode::Char(c)
-            if !ctrl && !alt => {
-                insert_char_at(&mut lines[*row], *col, c);
-                *col += 1;
-            }
+        KeyCode::Char(c) if !ctrl && !alt => {
+            insert_char_at(&mut lines[*row], *col, c);
+            *col += 1;
+        }
         _ => {}
     }
 }
@@ -1698,24 +1699,22 @@ fn edit_single_line_readline(text: &mut String, cursor: &mut usize, key: KeyEven
         KeyCode::Right => *cursor = (*cursor + 1).min(char_len(text)),
         KeyCode::Home => *cursor = 0,
         KeyCode::End => *cursor = char_len(text),
-        KeyCode::Backspace
-            if *cursor > 0 => {
-                let remove_idx = *cursor - 1;
-                remove_char_at(text, remove_idx);
-                *cursor = remove_idx;
-                return true;
-            }
+        KeyCode::Backspace if *cursor > 0 => {
+            let remove_idx = *cursor - 1;
+            remove_char_at(text, remove_idx);
+            *cursor = remove_idx;
+            return true;
+        }
         KeyCode::Delete => {
             let before = text.len();
             remove_char_at(text, *cursor);
             return text.len() != before;
         }
-        KeyCode::Char(c)
-            if !ctrl && !alt && !matches!(c, '\n' | '\r') => {
-                insert_char_at(text, *cursor, c);
-                *cursor += 1;
-                return true;
-            }
+        KeyCode::Char(c) if !ctrl && !alt && !matches!(c, '\n' | '\r') => {
+            insert_char_at(text, *cursor, c);
+            *cursor += 1;
+            return true;
+        }
         _ => {}
     }
     false
@@ -1815,6 +1814,12 @@ pub struct ListView {
     semantic_search_ids: Vec<String>,
     /// Whether semantic search executed successfully for the active query.
     semantic_search_active: bool,
+    /// Matching IDs when the slash query parses as a structured filter
+    /// (`label:api -size:*`); such queries filter without ranking.
+    structured_query_ids: Option<HashSet<String>>,
+    /// Saved view applied with `V`, with the query it set. The view stays
+    /// active only while the search query still matches it.
+    active_view: Option<(String, String)>,
     /// Receiver for background semantic refinement results.
     semantic_refinement_rx: Option<std::sync::mpsc::Receiver<Vec<String>>>,
     /// Generation counter to discard stale background results.
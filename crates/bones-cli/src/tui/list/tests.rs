@@ -841,6 +841,8 @@ mod tests {
             semantic_model: None,
             semantic_search_ids: Vec::new(),
             semantic_search_active: false,
+            structured_query_ids: None,
+            active_view: None,
             semantic_refinement_rx: None,
             semantic_search_gen: 0,
             last_searched_query: String::new(),
@@ -986,6 +988,97 @@ mod tests {
         assert!(view.semantic_refinement_rx.is_none());
     }
 
+    #[test]
+    fn slash_structured_query_filters_without_ranking() {
+        let (_dir, project_root, db_path) = setup_project();
+        let create = |title: &str, labels: Vec<String>| {
+            actions::create_item(
+                &project_root,
+                &db_path,
+                "test-agent",
+                title,
+                None,
+                Kind::Task,
+                None,
+                Urgency::Default,
+                labels,
+            )
+            .expect("create item")
+        };
+        let api = create("Api work", vec!["api".to_string()]);
+        create("Docs work", Vec::new());
+
+        let mut view = make_list_view();
+        view.project_root = project_root;
+        view.db_path = db_path;
+        view.semantic_model = None;
+        view.filter.search_query = "label:api -state:done".to_string();
+        view.reload().expect("reload");
+
+        assert!(!view.semantic_search_active);
+        let visible: Vec<&str> = view
+            .visible_items
+            .iter()
+            .map(|item| item.item_id.as_str())
+            .collect();
+        assert_eq!(visible, vec![api.as_str()]);
+
+        // Free text still goes through ranked search.
+        view.filter.search_query = "docs".to_string();
+        view.refresh_semantic_search_ids().expect("refresh search");
+        assert!(view.structured_query_ids.is_none());
+        assert!(view.semantic_search_active);
+    }
+
+    #[test]
+    fn cycle_saved_view_applies_views_in_order_then_clears() {
+        let (_dir, project_root, db_path) = setup_project();
+        let api = actions::create_item(
+            &project_root,
+            &db_path,
+            "test-agent",
+            "Api work",
+            None,
+            Kind::Task,
+            None,
+            Urgency::Default,
+            vec!["api".to_string()],
+        )
+        .expect("create item");
+        std::fs::write(
+            project_root.join(".bones/views.toml"),
+            "[view.api]\nlabel = [\"api\"]\n\n[view.bugs]\nkind = \"bug\"\n",
+        )
+        .expect("write views");
+
+        let mut view = make_list_view();
+        view.project_root = project_root;
+        view.db_path = db_path;
+        view.semantic_model = None;
+        view.reload().expect("reload");
+
+        view.handle_key(KeyEvent::new(KeyCode::Char('V'), KeyModifiers::NONE))
+            .expect("cycle view");
+        assert_eq!(view.active_view_name(), Some("api"));
+        let visible: Vec<&str> = view
+            .visible_items
+            .iter()
+            .map(|item| item.item_id.as_str())
+            .collect();
+        assert_eq!(visible, vec![api.as_str()]);
+
+        view.handle_key(KeyEvent::new(KeyCode::Char('V'), KeyModifiers::NONE))
+            .expect("cycle view");
+        assert_eq!(view.active_view_name(), Some("bugs"));
+        assert!(view.visible_items.is_empty());
+
+        view.handle_key(KeyEvent::new(KeyCode::Char('V'), KeyModifiers::NONE))
+            .expect("cycle view");
+        assert_eq!(view.active_view_name(), None);
+        assert!(view.filter.search_query.is_empty());
+        assert_eq!(view.visible_items.len(), 1);
+    }
+
     #[test]
     fn list_view_filter_clamp_selection_after_filter() {
         let mut view = make_list_view();
@@ -1092,6 +1185,8 @@ mod tests {
             semantic_model: None,
             semantic_search_ids: Vec::new(),
             semantic_search_active: false,
+            structured_query_ids: None,
+            active_view: None,
             semantic_refinement_rx: None,
             semantic_search_gen: 0,
             last_searched_query: String::new(),
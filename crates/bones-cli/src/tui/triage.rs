@@ -5,6 +5,7 @@
 
 use crate::cmd::triage_support::{self, RankedItem};
 use anyhow::{Context, Result};
+use bones_core::config::load_project_config;
 use bones_core::db::query;
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
@@ -50,7 +51,14 @@ impl TriageView {
 
         // Use current time for decay calculation
         let now_us = chrono::Utc::now().timestamp_micros();
-        let snapshot = triage_support::build_triage_snapshot(&conn, now_us)
+        let triage_config = self
+            .db_path
+            .parent()
+            .and_then(std::path::Path::parent)
+            .and_then(|root| load_project_config(root).ok())
+            .unwrap_or_default()
+            .triage;
+        let snapshot = triage_support::build_triage_snapshot(&conn, now_us, &triage_config)
             .context("build triage snapshot")?;
 
         // Filter to unblocked items (ready for action)
@@ -318,6 +326,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: format!("hash:{id}"),
+            signature: None,
         })
         .unwrap();
     }
@@ -142,6 +142,149 @@ fn list_status_filter_accepts_repeated_and_virtual_blocked() {
     assert_eq!(blocked_ids, vec![blocked.as_str()]);
 }
 
+#[test]
+fn where_query_scopes_list_and_context() {
+    let dir = TempDir::new().unwrap();
+    init_project(dir.path());
+
+    let goal = create_item(dir.path(), "Public API", Some("goal"));
+    let json = json_command(
+        dir.path(),
+        &[
+            "create",
+            "--title",
+            "Paginate endpoints",
+            "--parent",
+            &goal,
+            "--label",
+            "api",
+            "--size",
+            "m",
+            "--json",
+        ],
+    );
+    let big = json["id"].as_str().expect("id").to_string();
+    let json = json_command(
+        dir.path(),
+        &[
+            "create", "--title", "Fix typo", "--label", "api", "--size", "xs", "--json",
+        ],
+    );
+    let small = json["id"].as_str().expect("id").to_string();
+    create_item(dir.path(), "Unrelated chore", None);
+    bn_cmd(dir.path())
+        .args(["admin", "rebuild"])
+        .assert()
+        .success();
+
+    let ids = |args: &[&str]| -> Vec<String> {
+        let json = json_command(dir.path(), args);
+        json["items"]
+            .as_array()
+            .expect("items")
+            .iter()
+            .filter_map(|item| item["id"].as_str().map(str::to_string))
+            .collect()
+    };
+    let query = "label:api AND NOT assignee:* AND size>=M";
+    assert_eq!(
+        ids(&["list", "--json", "--where", query]),
+        vec![big.clone()]
+    );
+    assert_eq!(
+        ids(&["list", "--json", "--where", &format!("under:{goal}")]),
+        vec![big.clone()]
+    );
+    let mut either = ids(&["list", "--json", "--where", "size:xs OR size:m"]);
+    either.sort();
+    let mut expected = vec![big.clone(), small];
+    expected.sort();
+    assert_eq!(either, expected);
+
+    let context = json_command(dir.path(), &["context", "--json", "--where", query]);
+    assert_eq!(context["filter"], query);
+    assert_eq!(context["summary"]["open_count"], 1);
+    assert_eq!(context["recommended_next"]["id"], big.as_str());
+
+    let output = bn_cmd(dir.path())
+        .args(["list", "--json", "--where", "colour:red"])
+        .output()
+        .expect("list runs");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid_where"), "stderr: {stderr}");
+}
+
+#[test]
+fn saved_views_scope_list_and_context() {
+    let dir = TempDir::new().unwrap();
+    init_project(dir.path());
+
+    let json = json_command(
+        dir.path(),
+        &[
+            "create",
+            "--title",
+            "Paginate endpoints",
+            "--label",
+            "api",
+            "--size",
+            "m",
+            "--json",
+        ],
+    );
+    let api = json["id"].as_str().expect("id").to_string();
+    create_item(dir.path(), "Unrelated chore", None);
+    bn_cmd(dir.path())
+        .args(["admin", "rebuild"])
+        .assert()
+        .success();
+
+    let saved = json_command(
+        dir.path(),
+        &[
+            "view",
+            "save",
+            "api",
+            "--label",
+            "api",
+            "--columns",
+            "id,title",
+            "--description",
+            "API backlog",
+            "--json",
+        ],
+    );
+    assert_eq!(saved["name"], "api");
+    assert_eq!(saved["replaced"], false);
+    assert!(dir.path().join(".bones/views.toml").exists());
+
+    let views = json_command(dir.path(), &["view", "list", "--json"]);
+    assert_eq!(views["views"][0]["name"], "api");
+    assert_eq!(views["views"][0]["description"], "API backlog");
+
+    let list = json_command(dir.path(), &["list", "--view", "api", "--json"]);
+    let items = list["items"].as_array().expect("items");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["id"], api.as_str());
+
+    let context = json_command(dir.path(), &["context", "--view", "api", "--json"]);
+    assert_eq!(context["view"], "api");
+    assert_eq!(context["summary"]["open_count"], 1);
+
+    let output = bn_cmd(dir.path())
+        .args(["list", "--view", "missing", "--json"])
+        .output()
+        .expect("list runs");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unknown_view"), "stderr: {stderr}");
+
+    json_command(dir.path(), &["view", "remove", "api", "--json"]);
+    let views = json_command(dir.path(), &["view", "list", "--json"]);
+    assert!(views["views"].as_array().expect("views").is_empty());
+}
+
 #[test]
 fn mutation_json_includes_schema_and_current_state() {
     let dir = TempDir::new().unwrap();
@@ -207,6 +207,7 @@ fn verify_json_schema_is_stable() {
     assert!(json["ok"].is_boolean());
     assert!(json["active_shard_parse_ok"].is_boolean());
     assert!(json["shards"].is_array());
+    assert_eq!(json["prolly_index"]["status"], "absent");
 }
 
 #[test]
@@ -226,6 +227,636 @@ fn verify_succeeds_with_missing_projection_db() {
         .success();
 }
 
+#[test]
+fn verify_signatures_reports_signed_and_tampered_events() {
+    let dir = TempDir::new().unwrap();
+    let keys = TempDir::new().unwrap();
+    init_project(dir.path());
+
+    bn_cmd(dir.path())
+        .env("BONES_KEY_DIR", keys.path())
+        .args(["keys", "generate"])
+        .assert()
+        .success();
+    assert!(dir.path().join(".bones/keys/test-agent.pub").exists());
+
+    let output = bn_cmd(dir.path())
+        .env("BONES_KEY_DIR", keys.path())
+        .args(["create", "--title", "Signed", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let output = bn_cmd(dir.path())
+        .args(["verify", "--signatures", "--require-signed", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: Value = serde_json::from_slice(&output.stdout).expect("verify --json must parse");
+    assert_eq!(json["signatures"]["ok"], true);
+    assert_eq!(json["signatures"]["agents"][0]["valid"], 1);
+
+    let shard = first_event_shard(dir.path());
+    let content = fs::read_to_string(&shard).unwrap();
+    let tampered = content.replace("\"Signed\"", "\"Forged\"");
+    assert_ne!(content, tampered);
+    fs::write(&shard, tampered).unwrap();
+
+    let output = bn_cmd(dir.path())
+        .args(["verify", "--signatures", "--json"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let json: Value = serde_json::from_slice(&output.stdout).expect("verify --json must parse");
+    assert_eq!(json["signatures"]["ok"], false);
+    assert_eq!(json["signatures"]["issues"][0]["status"], "invalid");
+}
+
+fn bn_json(dir: &Path, agent: &str, args: &[&str]) -> Value {
+    let output = bn_cmd(dir)
+        .env("AGENT", agent)
+        .args(args)
+        .arg("--json")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("valid JSON")
+}
+
+#[test]
+fn bundles_round_trip_between_replicas() {
+    let a = TempDir::new().unwrap();
+    let b = TempDir::new().unwrap();
+    let handoff = TempDir::new().unwrap();
+    let first = handoff.path().join("first.bundle");
+    let reply = handoff.path().join("reply.bundle");
+    let first_str = first.to_str().unwrap();
+    let reply_str = reply.to_str().unwrap();
+
+    init_project(a.path());
+    init_project(b.path());
+    create_item(a.path(), "From A");
+    create_item(b.path(), "From B");
+
+    let created = bn_json(a.path(), "alice", &["bundle", "create", first_str]);
+    assert_eq!(created["events"], 1);
+    let head = created["head"].as_str().unwrap().to_string();
+
+    let applied = bn_json(b.path(), "bob", &["bundle", "apply", first_str]);
+    assert_eq!(applied["received"], 1);
+    assert_eq!(applied["in_sync"], false);
+
+    let back = bn_json(
+        b.path(),
+        "bob",
+        &["bundle", "create", reply_str, "--since", &head[..12]],
+    );
+    assert_eq!(back["events"], 1);
+
+    let applied = bn_json(a.path(), "alice", &["bundle", "apply", reply_str]);
+    assert_eq!(applied["received"], 1);
+    assert_eq!(applied["base_known"], true);
+    assert_eq!(applied["in_sync"], true);
+
+    let root_a = bn_json(a.path(), "alice", &["bundle", "root"]);
+    let root_b = bn_json(b.path(), "bob", &["bundle", "root"]);
+    assert_eq!(root_a["root"], root_b["root"]);
+    assert_eq!(root_a["events"], 2);
+
+    let list = bn_json(a.path(), "alice", &["list"]);
+    assert_eq!(list["items"].as_array().unwrap().len(), 2);
+
+    // Re-applying is a no-op; a tampered bundle is rejected.
+    let again = bn_json(a.path(), "alice", &["bundle", "apply", reply_str]);
+    assert_eq!(again["received"], 0);
+    let content = fs::read_to_string(&reply).unwrap();
+    fs::write(&reply, content.replace("From B", "From Z")).unwrap();
+    bn_cmd(a.path())
+        .args(["bundle", "apply", reply_str])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("bundle line"));
+}
+
+#[test]
+fn bundle_apply_roots_exclude_quarantined_events() {
+    let a = TempDir::new().unwrap();
+    let b = TempDir::new().unwrap();
+    let handoff = TempDir::new().unwrap();
+    let file = handoff.path().join("a.bundle");
+    let file_str = file.to_str().unwrap();
+
+    init_project(a.path());
+    init_project(b.path());
+    fs::write(
+        b.path().join(".bones/policy.toml"),
+        "[[rule]]\nevents = [\"item.delete\"]\nagents = [\"lead\"]\n",
+    )
+    .unwrap();
+    create_item(a.path(), "Kept");
+    let doomed = create_item(a.path(), "Doomed");
+    bn_cmd(a.path())
+        .args(["delete", &doomed, "--force"])
+        .assert()
+        .success();
+
+    bn_json(a.path(), "alice", &["bundle", "create", file_str]);
+    let applied = bn_json(b.path(), "bob", &["bundle", "apply", file_str]);
+    assert_eq!(applied["received"], 2);
+    assert_eq!(applied["quarantined"], 1);
+    assert_eq!(applied["in_sync"], false);
+
+    // The recorded root is the one this replica actually has.
+    let root_b = bn_json(b.path(), "bob", &["bundle", "root"]);
+    assert_eq!(applied["root"], root_b["root"]);
+    assert_eq!(root_b["events"], 2);
+}
+
+#[test]
+fn sync_remote_exchanges_events_with_sync_serve() {
+    let a = TempDir::new().unwrap();
+    let b = TempDir::new().unwrap();
+    init_project(a.path());
+    init_project(b.path());
+    create_item(a.path(), "From A");
+    create_item(b.path(), "From B");
+
+    let bn = assert_cmd::cargo::cargo_bin!("bn");
+    let remote = format!(
+        "cd '{}' && '{}' sync-serve --stdio",
+        b.path().display(),
+        bn.display()
+    );
+    let report = bn_json(a.path(), "alice", &["sync", "--remote", &remote]);
+    assert_eq!(report["events_sent"], 1);
+    assert_eq!(report["events_received"], 1);
+    assert_eq!(report["rounds"], 4);
+
+    for dir in [a.path(), b.path()] {
+        let list = bn_json(dir, "alice", &["list"]);
+        assert_eq!(list["items"].as_array().unwrap().len(), 2);
+    }
+
+    let again = bn_json(a.path(), "alice", &["sync", "--remote", &remote]);
+    assert_eq!(again["rounds"], 1);
+    assert_eq!(again["events_received"], 0);
+
+    bn_cmd(a.path())
+        .args(["sync", "--remote", "echo not-bones"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "did not speak the bones sync protocol",
+        ));
+}
+
+#[test]
+fn sync_keeps_the_prolly_index_current_and_verify_checks_it() {
+    let a = TempDir::new().unwrap();
+    let b = TempDir::new().unwrap();
+    init_project(a.path());
+    init_project(b.path());
+    create_item(b.path(), "From B");
+
+    let bn = assert_cmd::cargo::cargo_bin!("bn");
+    let remote = format!(
+        "cd '{}' && '{}' sync-serve --stdio",
+        b.path().display(),
+        bn.display()
+    );
+    bn_json(a.path(), "alice", &["sync", "--remote", &remote]);
+    let index = a.path().join(".bones/cache/prolly.idx");
+    assert!(index.exists());
+
+    // New events are journaled rather than re-indexed on the next sync.
+    create_item(a.path(), "After sync");
+    assert!(a.path().join(".bones/cache/prolly.journal").exists());
+    let verify = bn_json(a.path(), "alice", &["verify"]);
+    assert_eq!(verify["prolly_index"]["status"], "ok");
+    assert_eq!(verify["prolly_index"]["events"], 2);
+    assert_eq!(verify["prolly_index"]["pending"], 0);
+
+    let report = bn_json(a.path(), "alice", &["sync", "--remote", &remote]);
+    assert_eq!(report["events_sent"], 1);
+
+    // A damaged index fails verify until it is rebuilt.
+    let text = fs::read_to_string(&index).unwrap();
+    let damaged: Vec<&str> = text.lines().filter(|l| !l.starts_with('@')).collect();
+    fs::write(&index, damaged.join("\n") + "\n").unwrap();
+    bn_cmd(a.path())
+        .args(["verify"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("FAIL prolly index"));
+    bn_cmd(a.path())
+        .args(["admin", "rebuild"])
+        .assert()
+        .success();
+    bn_cmd(a.path()).args(["verify"]).assert().success();
+}
+
+#[test]
+fn filtered_sync_pulls_a_goal_subtree_and_its_blockers() {
+    let a = TempDir::new().unwrap();
+    let b = TempDir::new().unwrap();
+    init_project(a.path());
+    init_project(b.path());
+    let goal = bn_json(
+        b.path(),
+        "alice",
+        &["create", "--title", "Goal", "--kind", "goal"],
+    )["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let task = bn_json(
+        b.path(),
+        "alice",
+        &["create", "--title", "Task", "--parent", &goal],
+    )["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let blocker = create_item(b.path(), "Blocker");
+    create_item(b.path(), "Unrelated");
+    bn_cmd(b.path())
+        .args(["dep", "add", &blocker, "--blocks", &task])
+        .assert()
+        .success();
+
+    let bn = assert_cmd::cargo::cargo_bin!("bn");
+    let remote = format!(
+        "cd '{}' && '{}' sync-serve --stdio",
+        b.path().display(),
+        bn.display()
+    );
+    let report = bn_json(
+        a.path(),
+        "alice",
+        &["sync", "--remote", &remote, "--goal", &goal],
+    );
+    assert_eq!(report["items_in_scope"], 3);
+    assert_eq!(report["filter"]["goals"][0], goal.as_str());
+    let list = bn_json(a.path(), "alice", &["list"]);
+    assert_eq!(list["items"].as_array().unwrap().len(), 3);
+
+    let verify = bn_json(a.path(), "alice", &["verify"]);
+    assert_eq!(verify["ok"], true);
+    assert_eq!(verify["partial_replica"]["goals"][0], goal.as_str());
+    bn_cmd(a.path())
+        .args(["triage"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("partial replica"));
+
+    // Later syncs keep the recorded filter until --full.
+    let again = bn_json(a.path(), "alice", &["sync", "--remote", &remote]);
+    assert_eq!(again["items_in_scope"], 3);
+    assert_eq!(again["events_received"], 0);
+    let full = bn_json(a.path(), "alice", &["sync", "--remote", &remote, "--full"]);
+    assert!(full.get("filter").is_none());
+    let list = bn_json(a.path(), "alice", &["list"]);
+    assert_eq!(list["items"].as_array().unwrap().len(), 4);
+    assert!(!a.path().join(".bones/replication.toml").exists());
+
+    bn_cmd(a.path())
+        .args(["sync", "--remote", &remote, "--item", "bn-nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "unknown item in replication filter",
+        ));
+}
+
+#[test]
+fn remote_sync_quarantines_events_the_policy_refuses() {
+    let a = TempDir::new().unwrap();
+    let b = TempDir::new().unwrap();
+    init_project(a.path());
+    init_project(b.path());
+    fs::write(
+        a.path().join(".bones/policy.toml"),
+        "[[rule]]\nevents = [\"item.create\"]\nagents = [\"lead\"]\n",
+    )
+    .unwrap();
+    // Gated events must be signed by their author, and A must know the key.
+    let keys = TempDir::new().unwrap();
+    for args in [
+        &["keys", "generate"][..],
+        &["create", "--title", "Planned"][..],
+    ] {
+        bn_cmd(b.path())
+            .env("AGENT", "lead")
+            .env("BONES_KEY_DIR", keys.path())
+            .args(args)
+            .assert()
+            .success();
+    }
+    fs::create_dir_all(a.path().join(".bones/keys")).unwrap();
+    fs::copy(
+        b.path().join(".bones/keys/lead.pub"),
+        a.path().join(".bones/keys/lead.pub"),
+    )
+    .unwrap();
+    bn_json(b.path(), "bot", &["create", "--title", "Unplanned"]);
+    // Claiming to be lead without lead's key is not enough.
+    bn_cmd(b.path())
+        .env("AGENT", "lead")
+        .args(["create", "--title", "Forged"])
+        .assert()
+        .success();
+
+    let bn = assert_cmd::cargo::cargo_bin!("bn");
+    let remote = format!(
+        "cd '{}' && '{}' sync-serve --stdio",
+        b.path().display(),
+        bn.display()
+    );
+    let report = bn_json(a.path(), "alice", &["sync", "--remote", &remote]);
+    assert_eq!(report["events_received"], 3);
+    assert_eq!(report["events_quarantined"], 2);
+    let list = bn_json(a.path(), "alice", &["list"]);
+    let items = list["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["title"], "Planned");
+
+    let rejected: Vec<_> = fs::read_dir(a.path().join(".bones/events"))
+        .unwrap()
+        .filter_map(Result::ok)
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "rejected"))
+        .collect();
+    assert_eq!(rejected.len(), 1);
+    let content = fs::read_to_string(rejected[0].path()).unwrap();
+    assert!(
+        content.contains("agent 'bot' may not write item.create"),
+        "{content}"
+    );
+    assert!(
+        content.contains("'lead' lacks a valid signature"),
+        "{content}"
+    );
+}
+
+fn git(dir: &Path, args: &[&str]) {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_AUTHOR_NAME", "test")
+        .env("GIT_AUTHOR_EMAIL", "test@example.com")
+        .env("GIT_COMMITTER_NAME", "test")
+        .env("GIT_COMMITTER_EMAIL", "test@example.com")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "git {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn git_sync_screens_events_a_fast_forward_pull_brings_in() {
+    let root = TempDir::new().unwrap();
+    git(root.path(), &["init", "--bare", "--quiet", "remote.git"]);
+    git(root.path(), &["clone", "--quiet", "remote.git", "a"]);
+    let a = root.path().join("a");
+    init_project(&a);
+    bn_cmd(&a)
+        .args(["sync", "--config-only"])
+        .assert()
+        .success();
+    fs::write(
+        a.join(".bones/policy.toml"),
+        "[[rule]]\nevents = [\"item.create\"]\nagents = [\"lead\"]\n",
+    )
+    .unwrap();
+    git(&a, &["add", "-A"]);
+    git(&a, &["commit", "--quiet", "-m", "add bones"]);
+    git(&a, &["push", "--quiet", "origin", "HEAD"]);
+
+    git(root.path(), &["clone", "--quiet", "remote.git", "b"]);
+    let b = root.path().join("b");
+    create_item(&b, "Unplanned");
+    git(&b, &["add", "-A"]);
+    git(&b, &["commit", "--quiet", "-m", "unplanned work"]);
+    git(&b, &["push", "--quiet", "origin", "HEAD"]);
+
+    // The pull fast-forwards, so the merge driver never runs.
+    let bn = assert_cmd::cargo::cargo_bin!("bn");
+    let path = std::env::join_paths(
+        std::iter::once(bn.parent().unwrap().to_path_buf())
+            .chain(std::env::split_paths(&std::env::var_os("PATH").unwrap())),
+    )
+    .unwrap();
+    let output = bn_cmd(&a)
+        .env("PATH", path)
+        .args(["sync", "--no-push", "--json"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["pulled"], true);
+    assert_eq!(report["events_quarantined"], 1);
+
+    let list = bn_json(&a, "alice", &["list"]);
+    assert!(list["items"].as_array().unwrap().is_empty());
+    let shard = fs::read_to_string(first_event_shard(&a)).unwrap();
+    assert!(!shard.contains("Unplanned"), "{shard}");
+    let rejected = fs::read_dir(a.join(".bones/events"))
+        .unwrap()
+        .filter_map(Result::ok)
+        .find(|e| e.path().extension().is_some_and(|ext| ext == "rejected"))
+        .expect("quarantine file");
+    let content = fs::read_to_string(rejected.path()).unwrap();
+    assert!(content.contains("Unplanned"), "{content}");
+}
+
+#[test]
+fn ref_storage_syncs_events_outside_branches() {
+    let root = TempDir::new().unwrap();
+    git(root.path(), &["init", "--bare", "--quiet", "remote.git"]);
+    git(root.path(), &["clone", "--quiet", "remote.git", "a"]);
+    let a = root.path().join("a");
+    init_project(&a);
+    let config = a.join(".bones/config.toml");
+    let mut toml = fs::read_to_string(&config).unwrap();
+    toml.push_str("\n[sync]\nevents = \"ref\"\n");
+    fs::write(&config, toml).unwrap();
+    let first = create_item(&a, "Written on a");
+
+    let report = bn_json(&a, "alice", &["sync"]);
+    assert_eq!(report["events_published"], 1);
+    assert_eq!(report["pushed"], true);
+    git(&a, &["add", "-A"]);
+    git(&a, &["commit", "--quiet", "-m", "add bones"]);
+    git(&a, &["push", "--quiet", "origin", "HEAD"]);
+
+    // The branch carries the config but no event shards.
+    let tracked = std::process::Command::new("git")
+        .args(["ls-tree", "-r", "--name-only", "HEAD"])
+        .current_dir(&a)
+        .output()
+        .unwrap();
+    let tracked = String::from_utf8_lossy(&tracked.stdout);
+    assert!(tracked.contains(".bones/config.toml"), "{tracked}");
+    assert!(!tracked.contains(".events"), "{tracked}");
+
+    git(root.path(), &["clone", "--quiet", "remote.git", "b"]);
+    let b = root.path().join("b");
+    let report = bn_json(&b, "bob", &["sync"]);
+    assert_eq!(report["events_received"], 1);
+    let second = create_item(&b, "Written on b");
+    let report = bn_json(&b, "bob", &["sync"]);
+    assert_eq!(report["events_published"], 1);
+
+    let report = bn_json(&a, "alice", &["sync"]);
+    assert_eq!(report["events_received"], 1);
+    assert_eq!(report["events_published"], 0);
+    let list = bn_json(&a, "alice", &["list"]);
+    let ids: Vec<&str> = list["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|item| item["id"].as_str())
+        .collect();
+    assert!(ids.contains(&first.as_str()) && ids.contains(&second.as_str()));
+
+    // A fresh clone that only fetched the ref rebuilds from it.
+    git(root.path(), &["clone", "--quiet", "remote.git", "c"]);
+    let c = root.path().join("c");
+    git(
+        &c,
+        &[
+            "fetch",
+            "--quiet",
+            "origin",
+            "refs/bones/events:refs/bones/events",
+        ],
+    );
+    let rebuilt = bn_json(&c, "carol", &["admin", "rebuild"]);
+    assert_eq!(rebuilt["ref_events_restored"], 2);
+    let list = bn_json(&c, "carol", &["list"]);
+    assert_eq!(list["items"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn diff_reports_backlog_changes_between_revisions() {
+    let root = TempDir::new().unwrap();
+    let dir = root.path();
+    git(dir, &["init", "--quiet"]);
+    init_project(dir);
+    let existing = create_item(dir, "Existing bone");
+    git(dir, &["add", "-A"]);
+    git(dir, &["commit", "--quiet", "-m", "base"]);
+    git(dir, &["tag", "base"]);
+
+    bn_cmd(dir).args(["do", &existing]).assert().success();
+    bn_cmd(dir)
+        .args(["tag", &existing, "backend"])
+        .assert()
+        .success();
+    let added = create_item(dir, "Branch bone");
+    git(dir, &["add", "-A"]);
+    git(dir, &["commit", "--quiet", "-m", "branch work"]);
+
+    let diff = bn_json(dir, "alice", &["diff", "base..HEAD"]);
+    assert_eq!(
+        diff["added_events"], 4,
+        "move, assign, label, create: {diff}"
+    );
+    let items = diff["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    let changes_of = |id: &str| {
+        items
+            .iter()
+            .find(|item| item["item_id"] == id)
+            .map(|item| item["changes"].clone())
+            .unwrap_or_else(|| panic!("{id} missing from {diff}"))
+    };
+    let existing_changes = changes_of(&existing);
+    assert_eq!(existing_changes[0]["change"], "moved");
+    assert_eq!(existing_changes[0]["to"], "doing");
+    assert_eq!(existing_changes[1]["change"], "relabelled");
+    assert_eq!(existing_changes[1]["added"][0], "backend");
+    assert_eq!(changes_of(&added)[0]["change"], "created");
+
+    // A single revision is compared with the working tree.
+    bn_cmd(dir).args(["done", &added]).assert().success();
+    let diff = bn_json(dir, "alice", &["diff", "HEAD"]);
+    assert_eq!(diff["to"], "working tree");
+    assert_eq!(diff["items"][0]["item_id"], added.as_str());
+    assert_eq!(diff["items"][0]["changes"][0]["to"], "done");
+
+    bn_cmd(dir)
+        .args(["diff", "no-such-rev..HEAD"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn at_flag_reads_the_backlog_as_it_was() {
+    let dir = TempDir::new().unwrap();
+    init_project(dir.path());
+    let first = create_item(dir.path(), "First bone");
+
+    let log = bn_json(dir.path(), "alice", &["log", &first]);
+    let create_hash = log
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|row| row["event_type"] == "item.create")
+        .and_then(|row| row["event_hash"].as_str())
+        .unwrap_or_else(|| panic!("create event missing from {log}"))
+        .to_string();
+
+    bn_cmd(dir.path()).args(["do", &first]).assert().success();
+    let second = create_item(dir.path(), "Second bone");
+
+    let then = bn_json(dir.path(), "alice", &["show", &first, "--at", &create_hash]);
+    assert_eq!(then["state"], "open");
+    let now = bn_json(dir.path(), "alice", &["show", &first]);
+    assert_eq!(now["state"], "doing");
+
+    let listed = bn_json(
+        dir.path(),
+        "alice",
+        &["list", "--all", "--at", &create_hash],
+    );
+    let ids: Vec<&str> = listed["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|item| item["id"].as_str())
+        .collect();
+    assert_eq!(ids, vec![first.as_str()]);
+
+    // Before the project existed nothing is visible, and the bone is unknown.
+    bn_cmd(dir.path())
+        .args(["show", &second, "--at", "2000-01-01"])
+        .assert()
+        .failure();
+    bn_json(dir.path(), "alice", &["triage", "--at", &create_hash]);
+    bn_cmd(dir.path())
+        .args(["show", &first, "--at", &create_hash, "--format", "pretty"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("note: showing the backlog as of"));
+
+    bn_cmd(dir.path())
+        .args(["list", "--at", "last friday"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid --at value"));
+}
+
 #[test]
 fn history_fails_on_corrupted_shard_with_actionable_error() {
     let dir = TempDir::new().unwrap();
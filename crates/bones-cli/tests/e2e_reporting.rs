@@ -1,5 +1,6 @@
 //! E2E tests for reporting and interoperability commands:
-//! `bn stats`, `bn export`, `bn import`.
+//! `bn stats`, `bn query sql`, `bn metrics flow`, `bn progress --forecast`,
+//! `bn export`, `bn import`.
 //!
 //! Covers: stats JSON schema, export JSONL format, import round-trip,
 //! and graceful handling of malformed import input.
@@ -611,6 +612,99 @@ fn import_jsonl_reports_skipped_count_in_summary() {
     );
 }
 
+#[test]
+fn import_jsonl_quarantines_events_the_policy_refuses() {
+    let dir = TempDir::new().unwrap();
+    init_project(dir.path());
+    std::fs::write(
+        dir.path().join(".bones/policy.toml"),
+        "[groups]\nhumans = [\"alice\"]\n\n[[rule]]\nevents = [\"item.delete\"]\nagents = [\"@humans\"]\n",
+    )
+    .unwrap();
+
+    let lines = [
+        r#"{"timestamp":1771563284510882,"agent":"alice","type":"item.create","item_id":"bn-abc","data":{"kind":"task","title":"Keep me"}}"#,
+        r#"{"timestamp":1771563284510883,"agent":"bot","type":"item.delete","item_id":"bn-abc","data":{}}"#,
+    ];
+    let file = dir.path().join("events.jsonl");
+    std::fs::write(&file, lines.join("\n")).unwrap();
+
+    let result = bn_cmd(dir.path())
+        .args([
+            "import",
+            "--jsonl",
+            "--input",
+            file.to_str().unwrap(),
+            "--json",
+        ])
+        .output()
+        .unwrap();
+    assert!(result.status.success());
+    let report: Value = serde_json::from_slice(&result.stdout).expect("must produce valid JSON");
+    assert_eq!(report["imported"], 1);
+    assert_eq!(report["quarantined"], 1);
+    assert_eq!(report["quarantined_lines"][0]["line"], 2);
+    assert!(
+        report["quarantined_lines"][0]["reason"]
+            .as_str()
+            .unwrap()
+            .contains("agent 'bot' may not write item.delete")
+    );
+    assert!(!String::from_utf8_lossy(&result.stderr).contains("quarantine line"));
+
+    let quarantine = dir.path().join(".bones/events/2026-02.rejected");
+    let content = std::fs::read_to_string(quarantine).expect("quarantine file");
+    assert!(content.contains("# rejected: agent 'bot' may not write item.delete"));
+    assert!(content.contains("item.delete"));
+}
+
+#[test]
+fn import_jsonl_does_not_sign_records_with_local_keys() {
+    let dir = TempDir::new().unwrap();
+    let keys = TempDir::new().unwrap();
+    init_project(dir.path());
+    bn_cmd(dir.path())
+        .env("BONES_KEY_DIR", keys.path())
+        .args(["--agent", "alice", "keys", "generate"])
+        .assert()
+        .success();
+    std::fs::write(
+        dir.path().join(".bones/policy.toml"),
+        "[groups]\nhumans = [\"alice\"]\n\n[[rule]]\nevents = [\"item.create\"]\nagents = [\"@humans\"]\n",
+    )
+    .unwrap();
+
+    // The file claims to be alice, whose secret key is on this machine.
+    let file = dir.path().join("events.jsonl");
+    std::fs::write(
+        &file,
+        r#"{"timestamp":1771563284510882,"agent":"alice","type":"item.create","item_id":"bn-abc","data":{"kind":"task","title":"Claims to be alice"}}"#,
+    )
+    .unwrap();
+
+    let result = bn_cmd(dir.path())
+        .env("BONES_KEY_DIR", keys.path())
+        .args([
+            "import",
+            "--jsonl",
+            "--input",
+            file.to_str().unwrap(),
+            "--json",
+        ])
+        .output()
+        .unwrap();
+    assert!(result.status.success());
+    let report: Value = serde_json::from_slice(&result.stdout).expect("must produce valid JSON");
+    assert_eq!(report["imported"], 0);
+    assert_eq!(report["quarantined"], 1);
+    assert!(
+        report["quarantined_lines"][0]["reason"]
+            .as_str()
+            .unwrap()
+            .contains("lacks a valid signature")
+    );
+}
+
 #[test]
 fn import_jsonl_skips_semantically_invalid_records_and_continues() {
     let dir = TempDir::new().unwrap();
@@ -724,3 +818,201 @@ fn import_nonexistent_file_fails_gracefully() {
         "error should produce actionable stderr message"
     );
 }
+
+// ---------------------------------------------------------------------------
+// bn query sql tests
+// ---------------------------------------------------------------------------
+
+fn query_sql(dir: &Path, sql: &str) -> Value {
+    let output = bn_cmd(dir)
+        .args(["query", "sql", sql, "--json"])
+        .output()
+        .expect("query should not crash");
+    assert!(
+        output.status.success(),
+        "bn query sql failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("query --json must produce valid JSON")
+}
+
+#[test]
+fn query_sql_reads_stable_views() {
+    let dir = TempDir::new().unwrap();
+    init_project(dir.path());
+
+    let open = create_item(dir.path(), "Open item alpha");
+    let done = create_item(dir.path(), "Done item beta");
+    done_item(dir.path(), &done);
+
+    let result = query_sql(
+        dir.path(),
+        "SELECT id, state FROM bones_items ORDER BY title",
+    );
+    assert_eq!(result["api_version"], 1);
+    assert_eq!(result["columns"], serde_json::json!(["id", "state"]));
+    assert_eq!(
+        result["rows"],
+        serde_json::json!([[done, "done"], [open, "open"]])
+    );
+    assert_eq!(result["row_count"], 2);
+
+    let events = query_sql(
+        dir.path(),
+        "SELECT count(*) FROM bones_events WHERE event_type = 'item.create'",
+    );
+    assert_eq!(events["rows"], serde_json::json!([[2]]));
+}
+
+#[test]
+fn query_sql_rejects_writes() {
+    let dir = TempDir::new().unwrap();
+    init_project(dir.path());
+    create_item(dir.path(), "Keep me");
+
+    for sql in ["DELETE FROM items", "SELECT 1; DELETE FROM items"] {
+        let output = bn_cmd(dir.path())
+            .args(["query", "sql", sql, "--json"])
+            .output()
+            .expect("query should not crash");
+        assert!(!output.status.success(), "{sql} must be rejected");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("sql_rejected"), "stderr: {stderr}");
+    }
+
+    let result = query_sql(dir.path(), "SELECT count(*) FROM bones_items");
+    assert_eq!(result["rows"], serde_json::json!([[1]]));
+}
+
+// ---------------------------------------------------------------------------
+// bn metrics flow tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn metrics_flow_reports_completions_from_transitions() {
+    let dir = TempDir::new().unwrap();
+    init_project(dir.path());
+
+    let started = create_item(dir.path(), "Started then done");
+    bn_cmd(dir.path()).args(["do", &started]).assert().success();
+    done_item(dir.path(), &started);
+    let skipped = create_item(dir.path(), "Done without starting");
+    done_item(dir.path(), &skipped);
+    let doing = create_item(dir.path(), "Still in progress");
+    bn_cmd(dir.path()).args(["do", &doing]).assert().success();
+
+    let transitions = query_sql(dir.path(), "SELECT count(*) FROM bones_transitions");
+    assert_eq!(transitions["rows"], serde_json::json!([[4]]));
+
+    let output = bn_cmd(dir.path())
+        .args(["metrics", "flow", "--weeks", "2", "--json"])
+        .output()
+        .expect("metrics should not crash");
+    assert!(
+        output.status.success(),
+        "bn metrics flow failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    let all = &report["groups"][0];
+    assert_eq!(all["group"], "all");
+    assert_eq!(all["completed"], 2);
+    assert_eq!(all["lead_time"]["count"], 2);
+    assert_eq!(all["cycle_time"]["count"], 1);
+    for key in ["p50_days", "p85_days", "p95_days"] {
+        assert!(all["lead_time"][key].is_number(), "missing {key}");
+    }
+    let weeks = all["weeks"].as_array().expect("weeks");
+    assert_eq!(weeks.len(), 2);
+    assert_eq!(weeks[1]["throughput"], 2);
+    assert_eq!(weeks[1]["wip"], 1);
+
+    let output = bn_cmd(dir.path())
+        .args(["metrics", "flow", "--by", "kind", "--json"])
+        .output()
+        .expect("metrics should not crash");
+    let report: Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(report["by"], "kind");
+    assert_eq!(report["groups"][0]["group"], "task");
+}
+
+// ---------------------------------------------------------------------------
+// Goal forecast tests
+// ---------------------------------------------------------------------------
+
+fn create_json(dir: &Path, args: &[&str]) -> String {
+    let output = bn_cmd(dir)
+        .arg("create")
+        .args(args)
+        .arg("--json")
+        .output()
+        .expect("create should not crash");
+    assert!(
+        output.status.success(),
+        "create failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: Value = serde_json::from_slice(&output.stdout).expect("valid JSON from create");
+    json["id"].as_str().expect("id must exist").to_string()
+}
+
+fn json_output(dir: &Path, args: &[&str]) -> Value {
+    let output = bn_cmd(dir).args(args).output().expect("should not crash");
+    assert!(
+        output.status.success(),
+        "{args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("valid JSON")
+}
+
+#[test]
+fn progress_and_plan_forecast_goal_completion() {
+    let dir = TempDir::new().unwrap();
+    init_project(dir.path());
+
+    let goal = create_json(dir.path(), &["--title", "Launch", "--kind", "goal"]);
+    let shipped = create_json(
+        dir.path(),
+        &["--title", "Spike", "--parent", &goal, "--size", "s"],
+    );
+    bn_cmd(dir.path()).args(["do", &shipped]).assert().success();
+    done_item(dir.path(), &shipped);
+    let first = create_json(
+        dir.path(),
+        &["--title", "Build", "--parent", &goal, "--size", "s"],
+    );
+    let second = create_json(
+        dir.path(),
+        &["--title", "Release", "--parent", &goal, "--size", "m"],
+    );
+    bn_cmd(dir.path())
+        .args(["dep", "add", &first, "--blocks", &second])
+        .assert()
+        .success();
+
+    // Without --forecast the payload is unchanged.
+    let progress = json_output(dir.path(), &["progress", &goal, "--json"]);
+    assert!(progress.get("forecast").is_none());
+
+    let progress = json_output(dir.path(), &["progress", &goal, "--forecast", "--json"]);
+    let forecast = &progress["forecast"];
+    assert_eq!(forecast["remaining"], 2);
+    assert_eq!(forecast["history_samples"], 1);
+    assert_eq!(forecast["simulations"], 2000);
+    let p50 = forecast["p50"]["date_us"].as_i64().expect("p50 date");
+    let p95 = forecast["p95"]["date_us"].as_i64().expect("p95 date");
+    assert!(p50 <= p95);
+
+    let plan = json_output(dir.path(), &["triage", "plan", &goal, "--json"]);
+    assert_eq!(plan["forecast"]["remaining"], 2);
+    assert!(plan["forecast"]["p85"]["days"].is_number());
+
+    let output = bn_cmd(dir.path())
+        .args(["progress", &goal, "--forecast"])
+        .output()
+        .expect("progress should not crash");
+    let text = String::from_utf8_lossy(&output.stdout);
+    assert!(text.contains("Forecast (2 remaining"), "{text}");
+    assert!(text.contains("P85"), "{text}");
+}
@@ -1,5 +1,6 @@
 //! E2E coverage for high-risk lifecycle and maintenance commands:
-//! `bn archive`, `bn undo`, `bn admin diagnose`, and `bn admin doctor`.
+//! `bn archive`, `bn undo`, `bn redact`, `bn admin diagnose`, and
+//! `bn admin doctor`.
 
 use assert_cmd::Command;
 use bones_core::event::parser::{ParsedLine, parse_line};
@@ -559,3 +560,100 @@ fn unstart_rejects_already_open() {
         "stderr should explain already-open state; got: {stderr}"
     );
 }
+
+#[test]
+fn redact_comment_by_index_scrubs_and_verifies() {
+    let dir = TempDir::new().unwrap();
+    init_project(dir.path());
+
+    let id = create_item(dir.path(), "Rotate credentials");
+    bn_cmd(dir.path())
+        .args(["bone", "comment", "add", &id, "first note"])
+        .assert()
+        .success();
+    bn_cmd(dir.path())
+        .args(["bone", "comment", "add", &id, "pasted swordfish passphrase"])
+        .assert()
+        .success();
+
+    let output = bn_cmd(dir.path())
+        .args([
+            "redact", &id, "--comment", "2", "--reason", "leaked secret", "--json",
+        ])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "redact failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(json["verified"], true);
+    assert_eq!(json["target_type"], "item.comment");
+    assert!(latest_event_hash_for_item_type(dir.path(), &id, "item.redact").is_some());
+
+    let comments = bn_cmd(dir.path())
+        .args(["bone", "comments", &id, "--json"])
+        .output()
+        .unwrap();
+    let text = String::from_utf8_lossy(&comments.stdout);
+    assert!(!text.contains("swordfish"), "comment still visible: {text}");
+    assert!(text.contains("first note"));
+
+    // A full rebuild must not resurrect the redacted content.
+    rebuild(dir.path());
+    bn_cmd(dir.path())
+        .args(["admin", "redact-verify", &id])
+        .assert()
+        .success();
+}
+
+#[test]
+fn redact_create_event_hides_title_after_rebuild() {
+    let dir = TempDir::new().unwrap();
+    init_project(dir.path());
+
+    let id = create_item(dir.path(), "Customer jdoe phone number");
+    let hash = latest_event_hash_for_item_type(dir.path(), &id, "item.create")
+        .expect("create event hash");
+
+    bn_cmd(dir.path())
+        .args(["redact", &hash, "--reason", "PII"])
+        .assert()
+        .success();
+    rebuild(dir.path());
+
+    let output = bn_cmd(dir.path())
+        .args(["show", &id, "--json"])
+        .output()
+        .unwrap();
+    let json: Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(json["title"], "[redacted]");
+}
+
+#[test]
+fn redact_rejects_already_redacted_target() {
+    let dir = TempDir::new().unwrap();
+    init_project(dir.path());
+
+    let id = create_item(dir.path(), "Twice");
+    bn_cmd(dir.path())
+        .args(["bone", "comment", "add", &id, "oops"])
+        .assert()
+        .success();
+    bn_cmd(dir.path())
+        .args(["redact", &id, "--comment", "1", "--reason", "leak"])
+        .assert()
+        .success();
+
+    let output = bn_cmd(dir.path())
+        .args(["redact", &id, "--comment", "1", "--reason", "leak"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success(), "second redact should fail");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("already redacted"),
+        "stderr should explain; got: {stderr}"
+    );
+}
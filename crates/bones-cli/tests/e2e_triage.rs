@@ -1150,3 +1150,68 @@ fn cycles_without_init_fails_with_message() {
                 .or(predicate::str::contains("init")),
         );
 }
+
+// ---------------------------------------------------------------------------
+// Due dates
+// ---------------------------------------------------------------------------
+
+#[test]
+fn next_prefers_item_with_imminent_deadline() {
+    let dir = TempDir::new().unwrap();
+    init_project(dir.path());
+    create_item(dir.path(), "Refactor logging");
+    create_item(dir.path(), "Tidy README");
+
+    let created = run_json(
+        dir.path(),
+        &["create", "--title", "Renew TLS certificate", "--due", "today"],
+    );
+    let due_id = created["id"].as_str().expect("create should return id");
+    assert!(
+        created["due"].is_string(),
+        "create --due should echo the due date"
+    );
+
+    let json = run_json(dir.path(), &["next"]);
+    assert_eq!(
+        json["assignments"][0]["id"].as_str(),
+        Some(due_id),
+        "item due today should outrank otherwise-equal items"
+    );
+}
+
+#[test]
+fn list_due_filters_and_sorts_by_deadline() {
+    let dir = TempDir::new().unwrap();
+    init_project(dir.path());
+    create_item(dir.path(), "No deadline");
+    let later = run_json(
+        dir.path(),
+        &["create", "--title", "Quarterly report", "--due", "2030-06-30"],
+    );
+    let sooner = run_json(
+        dir.path(),
+        &["create", "--title", "Board deck", "--due", "2030-06-01"],
+    );
+
+    let json = run_json(dir.path(), &["list", "--due", "2030-06-30", "--sort", "due"]);
+    let ids: Vec<&str> = json["items"]
+        .as_array()
+        .expect("list --json must have 'items'")
+        .iter()
+        .filter_map(|item| item["id"].as_str())
+        .collect();
+    assert_eq!(
+        ids,
+        [sooner["id"].as_str().unwrap(), later["id"].as_str().unwrap()]
+    );
+    assert_eq!(json["items"][0]["due"], "2030-06-01T23:59:59Z");
+
+    let cleared = later["id"].as_str().unwrap();
+    bn_cmd(dir.path())
+        .args(["update", cleared, "--due", "none"])
+        .assert()
+        .success();
+    let json = run_json(dir.path(), &["list", "--due", "2030-06-30"]);
+    assert_eq!(json["total"], 1);
+}
@@ -108,6 +108,7 @@ fn write_synthetic_events(
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         let line = write_event(&mut create).expect("write create event");
         shard_mgr
@@ -137,10 +138,12 @@ fn write_synthetic_events(
                             State::Open
                         },
                         reason: None,
+                        substate: None,
                         extra: BTreeMap::new(),
                     }),
                     1 => EventData::Comment(CommentData {
                         body: format!("Bench comment {e} on item {i}"),
+                        reply_to: None,
                         extra: BTreeMap::new(),
                     }),
                     2 => EventData::Assign(AssignData {
@@ -151,10 +154,12 @@ fn write_synthetic_events(
                     _ => EventData::Move(MoveData {
                         state: State::Open,
                         reason: None,
+                        substate: None,
                         extra: BTreeMap::new(),
                     }),
                 },
                 event_hash: String::new(),
+                signature: None,
             };
             let line = write_event(&mut mutation).expect("write mutation event");
             shard_mgr
@@ -253,9 +258,11 @@ fn bench_incremental_apply(c: &mut Criterion) {
                 item_id: ItemId::new_unchecked(format!("bn-{i:04x}")),
                 data: EventData::Comment(CommentData {
                     body: format!("Incremental bench comment {i}"),
+                    reply_to: None,
                     extra: BTreeMap::new(),
                 }),
                 event_hash: String::new(),
+                signature: None,
             };
             write_event(&mut e).expect("write new event")
         })
@@ -353,9 +360,11 @@ fn emit_large_repo_slo_report() {
                 item_id: ItemId::new_unchecked(format!("bn-{i:04x}")),
                 data: EventData::Comment(CommentData {
                     body: format!("SLO bench comment {i}"),
+                    reply_to: None,
                     extra: BTreeMap::new(),
                 }),
                 event_hash: String::new(),
+                signature: None,
             };
             write_event(&mut e).expect("write new event")
         })
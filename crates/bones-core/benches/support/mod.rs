@@ -1,9 +1,11 @@
 #![allow(dead_code)]
 
+use bones_core::crdt::text::{TextId, TextOp};
 use bones_core::event::writer::write_event;
 use bones_core::event::{
-    AssignAction, AssignData, CommentData, CompactData, CreateData, DeleteData, Event, EventData,
-    EventType, LinkData, MoveData, RedactData, SnapshotData, UnlinkData, UpdateData, parse_line,
+    AssignAction, AssignData, AttachData, CommentData, CommentEditData, CommentRetractData,
+    CompactData, CreateData, DeleteData, Event, EventData, EventType, LinkData, MoveData,
+    PatchData, RedactData, SnapshotData, UnlinkData, UpdateData, parse_line,
 };
 use bones_core::model::item::{Kind, Size, State, Urgency};
 use bones_core::model::item_id::{ItemId, generate_item_id};
@@ -137,6 +139,7 @@ pub fn generate_corpus_with_event_limit(
             item_id: item_id.clone(),
             data: build_event_data(event_type, &item_id, &item_ids, &mut prng),
             event_hash: String::new(),
+            signature: None,
         };
 
         let line_with_newline =
@@ -303,6 +306,7 @@ fn build_event_data(
             } else {
                 None
             },
+            substate: None,
             extra: BTreeMap::new(),
         }),
         EventType::Assign => EventData::Assign(AssignData {
@@ -316,6 +320,7 @@ fn build_event_data(
         }),
         EventType::Comment => EventData::Comment(CommentData {
             body: sample_description(prng),
+            reply_to: None,
             extra: BTreeMap::new(),
         }),
         EventType::Link => {
@@ -364,6 +369,32 @@ fn build_event_data(
             reason: "synthetic benchmark redaction".to_string(),
             extra: BTreeMap::new(),
         }),
+        EventType::Patch => EventData::Patch(PatchData {
+            base: format!("blake3:{:064x}", prng.next_u64()),
+            ops: vec![TextOp::Insert {
+                id: TextId::new(prng.next_u64() % 1_000 + 1, "bench"),
+                after: None,
+                text: make_text(prng, 2, 6),
+            }],
+            extra: BTreeMap::new(),
+        }),
+        EventType::CommentEdit => EventData::CommentEdit(CommentEditData {
+            comment: format!("blake3:{:064x}", prng.next_u64()),
+            body: sample_description(prng),
+            extra: BTreeMap::new(),
+        }),
+        EventType::CommentRetract => EventData::CommentRetract(CommentRetractData {
+            comment: format!("blake3:{:064x}", prng.next_u64()),
+            reason: None,
+            extra: BTreeMap::new(),
+        }),
+        EventType::Attach => EventData::Attach(AttachData {
+            blob: format!("blake3:{:064x}", prng.next_u64()),
+            name: format!("{}.log", make_text(prng, 1, 2).replace(' ', "-")),
+            mime: "text/plain".to_string(),
+            size: prng.next_u64() % 65_536,
+            extra: BTreeMap::new(),
+        }),
     }
 }
 
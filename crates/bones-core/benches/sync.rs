@@ -0,0 +1,160 @@
+//! Sync protocol cost: the level-by-level Prolly walk against the previous
+//! full event-hash-list exchange.
+//!
+//! Each tier's corpus is split into two replicas that share every event but
+//! the last 20, which are dealt out 10 per side. Besides timing, the bench
+//! prints the rounds and bytes each protocol needs, e.g.
+//!
+//! ```text
+//! SYNC tier=S events=50000 protocol=prolly_walk rounds=8 bytes=218334
+//! SYNC tier=S events=50000 protocol=hash_list rounds=3 bytes=5103266
+//! ```
+//!
+//! Run with:
+//! ```sh
+//! cargo bench --bench sync
+//! BONES_BENCH_MAX_EVENTS=5000000 cargo bench --bench sync  # full Tier L
+//! ```
+
+mod support;
+
+use bones_core::event::{Event, ParsedLine, parse_line};
+use bones_core::sync::prolly::ProllyTree;
+use bones_core::sync::protocol::sync_in_memory;
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use std::collections::HashSet;
+use support::{TIERS, generate_corpus_for_bench};
+
+/// Events unique to each replica.
+const DIVERGENT_PER_SIDE: usize = 10;
+
+struct Replicas {
+    local: Vec<Event>,
+    remote: Vec<Event>,
+}
+
+fn replicas(lines: &[String]) -> Replicas {
+    let mut events: Vec<Event> = lines
+        .iter()
+        .filter_map(|line| match parse_line(line).ok()? {
+            ParsedLine::Event(event) => Some(*event),
+            _ => None,
+        })
+        .collect();
+    let split = events.len().saturating_sub(2 * DIVERGENT_PER_SIDE);
+    let divergent = events.split_off(split);
+    let mut local = events.clone();
+    let mut remote = events;
+    for (i, event) in divergent.into_iter().enumerate() {
+        if i % 2 == 0 {
+            local.push(event);
+        } else {
+            remote.push(event);
+        }
+    }
+    Replicas { local, remote }
+}
+
+/// Rounds and bytes of one protocol run, counted as `SyncReport` does.
+struct Cost {
+    rounds: usize,
+    bytes: usize,
+}
+
+/// Same estimate `SyncReport::bytes_transferred` uses for events.
+fn event_size(event: &Event) -> usize {
+    event.event_hash.len()
+        + event.agent.len()
+        + event.itc.len()
+        + event.item_id.as_str().len()
+        + 128
+}
+
+/// The protocol before the tree walk: exchange roots, then every event
+/// hash, then the missing events.
+fn hash_list_sync(local: &[Event], remote: &[Event]) -> Cost {
+    let local_tree = ProllyTree::build(local);
+    let remote_tree = ProllyTree::build(remote);
+    let mut cost = Cost {
+        rounds: 1,
+        bytes: 64,
+    };
+    if local_tree.root.hash() == remote_tree.root.hash() {
+        return cost;
+    }
+
+    let local_hashes = local_tree.event_hashes();
+    let remote_hashes = remote_tree.event_hashes();
+    cost.rounds += 1;
+    cost.bytes += local_hashes
+        .iter()
+        .chain(&remote_hashes)
+        .map(|h| h.len() + 1)
+        .sum::<usize>();
+
+    let local_set: HashSet<&str> = local_hashes.iter().map(String::as_str).collect();
+    let remote_set: HashSet<&str> = remote_hashes.iter().map(String::as_str).collect();
+    cost.rounds += 1;
+    cost.bytes += local
+        .iter()
+        .filter(|e| !remote_set.contains(e.event_hash.as_str()))
+        .chain(
+            remote
+                .iter()
+                .filter(|e| !local_set.contains(e.event_hash.as_str())),
+        )
+        .map(event_size)
+        .sum::<usize>();
+    cost
+}
+
+fn prolly_walk_sync(local: &[Event], remote: &[Event]) -> Cost {
+    let result = sync_in_memory(local, remote).expect("in-memory sync");
+    Cost {
+        rounds: result.local_report.rounds,
+        bytes: result.local_report.bytes_transferred,
+    }
+}
+
+fn bench_sync(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sync.diff");
+    group.sample_size(10);
+
+    for tier in TIERS {
+        let corpus = generate_corpus_for_bench(tier, 0x5_1C_u64 + tier.event_count as u64);
+        let replicas = replicas(&corpus.lines);
+        let events = replicas.local.len() + DIVERGENT_PER_SIDE;
+
+        group.bench_with_input(
+            BenchmarkId::new("prolly_walk", tier.name),
+            &replicas,
+            |b, r| b.iter(|| black_box(prolly_walk_sync(&r.local, &r.remote))),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("hash_list", tier.name),
+            &replicas,
+            |b, r| b.iter(|| black_box(hash_list_sync(&r.local, &r.remote))),
+        );
+
+        for (name, cost) in [
+            (
+                "prolly_walk",
+                prolly_walk_sync(&replicas.local, &replicas.remote),
+            ),
+            (
+                "hash_list",
+                hash_list_sync(&replicas.local, &replicas.remote),
+            ),
+        ] {
+            eprintln!(
+                "SYNC tier={} events={events} protocol={name} rounds={} bytes={}",
+                tier.name, cost.rounds, cost.bytes
+            );
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_sync);
+criterion_main!(benches);
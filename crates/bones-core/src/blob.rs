@@ -0,0 +1,400 @@
+//! Content-addressed blob store for bone attachments.
+//!
+//! Attachment bytes live outside the event log in `.bones/blobs/`, keyed by
+//! their BLAKE3 digest in the same `blake3:<base64url>` text form used for
+//! event hashes (see [`crate::event::hash_text`]). An `item.attach` event
+//! only references the blob hash, so the log stays small and identical files
+//! attached to several bones are stored once.
+//!
+//! # Directory Layout
+//!
+//! ```text
+//! .bones/
+//!   blobs/
+//!     Xk/                 # first two characters of the encoded digest
+//!       9fQ...            # remaining characters; file holds the raw bytes
+//! ```
+//!
+//! # Invariants
+//!
+//! - A blob file's contents always hash to its path; writes go through a
+//!   temporary file and an atomic rename.
+//! - Blobs are immutable. The only removals are redaction of the last live
+//!   reference and pruning of unreferenced blobs during compaction.
+
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::io::{self, Write as IoWrite};
+use std::path::{Path, PathBuf};
+
+use crate::event::Event;
+use crate::event::data::EventData;
+use crate::event::hash_text::{decode_blake3_hash, encode_blake3_hash};
+use crate::redact::redaction_targets;
+
+/// Directory under `.bones/` holding blob files.
+pub const BLOBS_DIR: &str = "blobs";
+
+// ---------------------------------------------------------------------------
+// Error types
+// ---------------------------------------------------------------------------
+
+/// Errors that can occur during blob store operations.
+#[derive(Debug, thiserror::Error)]
+pub enum BlobError {
+    /// I/O error while reading or writing blob files.
+    #[error("blob I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    /// The hash is not a valid `blake3:` digest.
+    #[error("invalid blob hash: {0}")]
+    InvalidHash(String),
+
+    /// No blob with this hash exists in the store.
+    #[error("blob not found: {0}")]
+    NotFound(String),
+
+    /// The blob file exists but its contents no longer match its hash.
+    #[error("blob {hash} is corrupt (contents hash to {actual})")]
+    Corrupt {
+        /// Hash the blob is stored under.
+        hash: String,
+        /// Hash of the bytes actually on disk.
+        actual: String,
+    },
+}
+
+// ---------------------------------------------------------------------------
+// BlobStore
+// ---------------------------------------------------------------------------
+
+/// Compute the blob hash of `bytes` (`blake3:<base64url>`).
+#[must_use]
+pub fn hash_bytes(bytes: &[u8]) -> String {
+    encode_blake3_hash(&blake3::hash(bytes))
+}
+
+/// Handle to the `.bones/blobs/` directory.
+#[derive(Debug, Clone)]
+pub struct BlobStore {
+    root: PathBuf,
+}
+
+impl BlobStore {
+    /// Create a store rooted at `<bones_dir>/blobs`. The directory is created
+    /// lazily on the first write.
+    #[must_use]
+    pub fn new(bones_dir: &Path) -> Self {
+        Self {
+            root: bones_dir.join(BLOBS_DIR),
+        }
+    }
+
+    /// Path of the blob directory.
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Path at which the blob for `hash` is (or would be) stored.
+    ///
+    /// Legacy hex digests are normalized to the canonical base64url form.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlobError::InvalidHash`] if `hash` is not a `blake3:` digest.
+    pub fn path_for(&self, hash: &str) -> Result<PathBuf, BlobError> {
+        let digest =
+            decode_blake3_hash(hash).ok_or_else(|| BlobError::InvalidHash(hash.to_string()))?;
+        let canonical = encode_blake3_hash(&blake3::Hash::from(digest));
+        let encoded = canonical
+            .strip_prefix(crate::event::hash_text::BLAKE3_PREFIX)
+            .unwrap_or(&canonical);
+        let (fanout, rest) = encoded.split_at(2);
+        Ok(self.root.join(fanout).join(rest))
+    }
+
+    /// Store `bytes` and return their hash. Storing the same content twice
+    /// is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlobError::Io`] if the blob cannot be written.
+    pub fn put(&self, bytes: &[u8]) -> Result<String, BlobError> {
+        let hash = hash_bytes(bytes);
+        let path = self.path_for(&hash)?;
+        if path.is_file() {
+            return Ok(hash);
+        }
+
+        let dir = path.parent().unwrap_or(&self.root);
+        fs::create_dir_all(dir)?;
+        let tmp = dir.join(format!(
+            ".{}.tmp-{}",
+            path.file_name().and_then(|n| n.to_str()).unwrap_or("blob"),
+            std::process::id()
+        ));
+        {
+            let mut file = fs::File::create(&tmp)?;
+            file.write_all(bytes)?;
+            file.sync_all()?;
+        }
+        if let Err(e) = fs::rename(&tmp, &path) {
+            let _ = fs::remove_file(&tmp);
+            return Err(e.into());
+        }
+        Ok(hash)
+    }
+
+    /// Returns true if a blob for `hash` is present.
+    #[must_use]
+    pub fn contains(&self, hash: &str) -> bool {
+        self.path_for(hash).is_ok_and(|path| path.is_file())
+    }
+
+    /// Read a blob, verifying its contents against `hash`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlobError::NotFound`] if the blob is missing and
+    /// [`BlobError::Corrupt`] if its contents do not match the hash.
+    pub fn get(&self, hash: &str) -> Result<Vec<u8>, BlobError> {
+        let path = self.path_for(hash)?;
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Err(BlobError::NotFound(hash.to_string()));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let actual = hash_bytes(&bytes);
+        if decode_blake3_hash(&actual) != decode_blake3_hash(hash) {
+            return Err(BlobError::Corrupt {
+                hash: hash.to_string(),
+                actual,
+            });
+        }
+        Ok(bytes)
+    }
+
+    /// Delete the blob for `hash`. Returns `false` if it was not present.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlobError::Io`] if the file exists but cannot be removed.
+    pub fn remove(&self, hash: &str) -> Result<bool, BlobError> {
+        let path = self.path_for(hash)?;
+        match fs::remove_file(&path) {
+            Ok(()) => {
+                if let Some(dir) = path.parent() {
+                    // Only succeeds when the fan-out directory is now empty.
+                    let _ = fs::remove_dir(dir);
+                }
+                Ok(true)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// List the hashes of all stored blobs, sorted.
+    ///
+    /// Temporary files left behind by interrupted writes are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlobError::Io`] if the blob directory cannot be read.
+    pub fn list(&self) -> Result<Vec<String>, BlobError> {
+        let mut hashes = BTreeSet::new();
+        let fanouts = match fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        for fanout in fanouts {
+            let fanout = fanout?;
+            if !fanout.file_type()?.is_dir() {
+                continue;
+            }
+            let prefix = fanout.file_name().to_string_lossy().into_owned();
+            for entry in fs::read_dir(fanout.path())? {
+                let name = entry?.file_name().to_string_lossy().into_owned();
+                if name.starts_with('.') {
+                    continue;
+                }
+                let hash = format!("{}{prefix}{name}", crate::event::hash_text::BLAKE3_PREFIX);
+                if decode_blake3_hash(&hash).is_some() {
+                    hashes.insert(hash);
+                }
+            }
+        }
+        Ok(hashes.into_iter().collect())
+    }
+
+    /// Remove every blob not in `keep`. Returns the removed hashes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BlobError::Io`] if the store cannot be listed or a blob
+    /// cannot be removed.
+    pub fn prune<S: ::std::hash::BuildHasher>(
+        &self,
+        keep: &HashSet<String, S>,
+    ) -> Result<Vec<String>, BlobError> {
+        let mut removed = Vec::new();
+        for hash in self.list()? {
+            if !keep.contains(&hash) && self.remove(&hash)? {
+                removed.push(hash);
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// Blob hashes referenced by `item.attach` events in `events` that have not
+/// been redacted, including attachments carried inside `item.snapshot`
+/// state. These are the blobs [`BlobStore::prune`] must keep.
+#[must_use]
+pub fn referenced_blobs(events: &[Event]) -> HashSet<String> {
+    let redacted = redaction_targets(events);
+    let mut blobs = HashSet::new();
+    for event in events {
+        if redacted.contains(&event.event_hash) {
+            continue;
+        }
+        match &event.data {
+            EventData::Attach(data) => {
+                blobs.insert(data.blob.clone());
+            }
+            EventData::Snapshot(data) => {
+                let Some(attachments) = data.state.get("attachments").and_then(|a| a.as_object())
+                else {
+                    continue;
+                };
+                // Snapshot entries are keyed by the original attach event hash.
+                blobs.extend(
+                    attachments
+                        .iter()
+                        .filter(|(hash, _)| !redacted.contains(hash.as_str()))
+                        .filter_map(|(_, entry)| entry.get("blob")?.as_str().map(String::from)),
+                );
+            }
+            _ => {}
+        }
+    }
+    blobs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::EventType;
+    use crate::event::data::{AttachData, RedactData};
+    use crate::model::item_id::ItemId;
+    use std::collections::BTreeMap;
+    use tempfile::TempDir;
+
+    fn make_event(data: EventData, event_type: EventType, hash: &str) -> Event {
+        Event {
+            wall_ts_us: 1_000,
+            agent: "alice".into(),
+            itc: "itc:AQ".into(),
+            parents: vec![],
+            event_type,
+            item_id: ItemId::new_unchecked("bn-a7x"),
+            data,
+            event_hash: hash.into(),
+            signature: None,
+        }
+    }
+
+    fn attach(blob: &str, hash: &str) -> Event {
+        make_event(
+            EventData::Attach(AttachData {
+                blob: blob.into(),
+                name: "log.txt".into(),
+                mime: "text/plain".into(),
+                size: 3,
+                extra: BTreeMap::new(),
+            }),
+            EventType::Attach,
+            hash,
+        )
+    }
+
+    #[test]
+    fn put_is_content_addressed_and_idempotent() {
+        let dir = TempDir::new().expect("tmp");
+        let store = BlobStore::new(dir.path());
+
+        let first = store.put(b"hello").expect("put");
+        let second = store.put(b"hello").expect("put again");
+        assert_eq!(first, second);
+        assert_eq!(first, hash_bytes(b"hello"));
+        assert!(store.contains(&first));
+        assert_eq!(store.get(&first).expect("get"), b"hello");
+        assert_eq!(store.list().expect("list"), vec![first]);
+    }
+
+    #[test]
+    fn path_for_normalizes_legacy_hex() {
+        let dir = TempDir::new().expect("tmp");
+        let store = BlobStore::new(dir.path());
+        let digest = blake3::hash(b"bytes");
+        let hex = format!("blake3:{}", digest.to_hex());
+
+        assert_eq!(
+            store.path_for(&hex).expect("hex"),
+            store.path_for(&encode_blake3_hash(&digest)).expect("b64")
+        );
+        assert!(matches!(
+            store.path_for("sha256:nope"),
+            Err(BlobError::InvalidHash(_))
+        ));
+    }
+
+    #[test]
+    fn get_detects_corruption_and_missing_blobs() {
+        let dir = TempDir::new().expect("tmp");
+        let store = BlobStore::new(dir.path());
+        let hash = store.put(b"original").expect("put");
+        fs::write(store.path_for(&hash).expect("path"), b"tampered").expect("tamper");
+
+        assert!(matches!(store.get(&hash), Err(BlobError::Corrupt { .. })));
+        assert!(matches!(
+            store.get(&hash_bytes(b"absent")),
+            Err(BlobError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn prune_keeps_only_referenced_blobs() {
+        let dir = TempDir::new().expect("tmp");
+        let store = BlobStore::new(dir.path());
+        let kept = store.put(b"kept").expect("put");
+        let orphan = store.put(b"orphan").expect("put");
+        let redacted = store.put(b"redacted").expect("put");
+
+        let events = vec![
+            attach(&kept, "blake3:e1"),
+            attach(&redacted, "blake3:e2"),
+            make_event(
+                EventData::Redact(RedactData {
+                    target_hash: "blake3:e2".into(),
+                    reason: "leak".into(),
+                    extra: BTreeMap::new(),
+                }),
+                EventType::Redact,
+                "blake3:e3",
+            ),
+        ];
+        let keep = referenced_blobs(&events);
+        let mut removed = store.prune(&keep).expect("prune");
+        removed.sort();
+
+        let mut expected = vec![orphan, redacted];
+        expected.sort();
+        assert_eq!(removed, expected);
+        assert_eq!(store.list().expect("list"), vec![kept]);
+    }
+}
@@ -293,7 +293,7 @@ impl ColumnCodec for InternedStringCodec {
 
 /// 4-bit RLE-encoded event types.
 ///
-/// The 11 event types are mapped to values 0–10, packed two per byte (low
+/// The 12 event types are mapped to values 0–11, packed two per byte (low
 /// nibble first), then the packed bytes are RLE-compressed with
 /// `[run_length: u8] [packed_byte: u8]` pairs.
 ///
@@ -320,6 +320,10 @@ impl EventTypeCodec {
             EventType::Compact => 8,
             EventType::Snapshot => 9,
             EventType::Redact => 10,
+            EventType::Patch => 11,
+            EventType::CommentEdit => 12,
+            EventType::CommentRetract => 13,
+            EventType::Attach => 14,
         }
     }
 
@@ -336,6 +340,10 @@ impl EventTypeCodec {
             8 => Ok(EventType::Compact),
             9 => Ok(EventType::Snapshot),
             10 => Ok(EventType::Redact),
+            11 => Ok(EventType::Patch),
+            12 => Ok(EventType::CommentEdit),
+            13 => Ok(EventType::CommentRetract),
+            14 => Ok(EventType::Attach),
             _ => Err(CacheError::DataCorrupted(format!(
                 "unknown event type nibble: {nibble}"
             ))),
@@ -17,9 +17,10 @@ pub const COL_ITEM_IDS: usize = 3;
 pub const COL_PARENTS: usize = 4;
 pub const COL_ITC: usize = 5;
 pub const COL_VALUES: usize = 6;
+pub const COL_HASHES: usize = 7;
 
 /// Total number of columns in the cache format.
-pub const COLUMN_COUNT: usize = 7;
+pub const COLUMN_COUNT: usize = 8;
 
 /// All event columns for a batch of events, decomposed by type.
 ///
@@ -53,6 +54,10 @@ pub struct CacheColumns {
 
     /// JSON-serialised event payload strings.
     pub values: Vec<String>,
+
+    /// Event hashes as written to the log. Stored rather than recomputed
+    /// because redacted payloads are scrubbed before caching.
+    pub hashes: Vec<String>,
 }
 
 impl CacheColumns {
@@ -71,6 +76,7 @@ impl CacheColumns {
             parents: Vec::with_capacity(n),
             itc: Vec::with_capacity(n),
             values: Vec::with_capacity(n),
+            hashes: Vec::with_capacity(n),
         };
 
         for event in events {
@@ -81,6 +87,7 @@ impl CacheColumns {
             cols.parents.push(event.parents.join(","));
             cols.itc.push(event.itc.clone());
             cols.values.push(serde_json::to_string(&event.data)?);
+            cols.hashes.push(event.event_hash.clone());
         }
 
         Ok(cols)
@@ -114,6 +121,7 @@ impl CacheColumns {
         check_len("parents", self.parents.len())?;
         check_len("itc", self.itc.len())?;
         check_len("values", self.values.len())?;
+        check_len("hashes", self.hashes.len())?;
 
         let mut events = Vec::with_capacity(n);
 
@@ -139,10 +147,8 @@ impl CacheColumns {
                 event_type,
                 item_id,
                 data,
-                // event_hash is not stored in the cache columns (derived from
-                // content). Callers that need the hash must recompute it from
-                // the TSJSON writer or preserve it separately.
-                event_hash: String::new(),
+                event_hash: self.hashes[i].clone(),
+                signature: None,
             });
         }
 
@@ -181,6 +187,8 @@ pub struct ColumnRow {
     pub itc: String,
     /// JSON payload string.
     pub value: String,
+    /// Event hash.
+    pub event_hash: String,
 }
 
 impl CacheColumns {
@@ -200,6 +208,7 @@ impl CacheColumns {
             parents: self.parents[index].clone(),
             itc: self.itc[index].clone(),
             value: self.values[index].clone(),
+            event_hash: self.hashes[index].clone(),
         })
     }
 
@@ -249,6 +258,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: format!("blake3:{ts:016x}"),
+            signature: None,
         }
     }
 
@@ -263,9 +273,11 @@ mod tests {
             data: EventData::Move(MoveData {
                 state: State::Doing,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             event_hash: format!("blake3:move{ts:012x}"),
+            signature: None,
         }
     }
 
@@ -279,17 +291,19 @@ mod tests {
             item_id: ItemId::new_unchecked(item),
             data: EventData::Comment(CommentData {
                 body: body.to_string(),
+                reply_to: None,
                 extra: BTreeMap::new(),
             }),
             event_hash: format!("blake3:cmt{ts:013x}"),
+            signature: None,
         }
     }
 
     // === Column count constants ==========================================
 
     #[test]
-    fn column_count_is_seven() {
-        assert_eq!(COLUMN_COUNT, 7);
+    fn column_count_is_eight() {
+        assert_eq!(COLUMN_COUNT, 8);
     }
 
     #[test]
@@ -302,6 +316,7 @@ mod tests {
             COL_PARENTS,
             COL_ITC,
             COL_VALUES,
+            COL_HASHES,
         ];
         let set: std::collections::HashSet<_> = indices.iter().copied().collect();
         assert_eq!(set.len(), COLUMN_COUNT, "column indices must be distinct");
@@ -372,12 +387,8 @@ mod tests {
     fn into_events_roundtrip_single() {
         let event = make_create_event(1_700_000_000_000, "agent-a", "bn-a7x", "Do a thing");
         let cols = CacheColumns::from_events(std::slice::from_ref(&event)).unwrap();
-        let mut reconstructed = cols.into_events().unwrap();
-        assert_eq!(reconstructed.len(), 1);
-        let rec = &mut reconstructed[0];
-        // event_hash is not stored in cache columns — zero it for comparison
-        rec.event_hash = event.event_hash.clone();
-        assert_eq!(*rec, event);
+        let reconstructed = cols.into_events().unwrap();
+        assert_eq!(reconstructed, vec![event]);
     }
 
     #[test]
@@ -388,10 +399,9 @@ mod tests {
             make_comment_event(3_000, "alice", "bn-a7x", "Look at this"),
         ];
         let cols = CacheColumns::from_events(&events).unwrap();
-        let mut reconstructed = cols.into_events().unwrap();
+        let reconstructed = cols.into_events().unwrap();
         assert_eq!(reconstructed.len(), events.len());
-        for (i, (rec, orig)) in reconstructed.iter_mut().zip(events.iter()).enumerate() {
-            rec.event_hash = orig.event_hash.clone();
+        for (i, (rec, orig)) in reconstructed.iter().zip(events.iter()).enumerate() {
             assert_eq!(rec, orig, "mismatch at row {i}");
         }
     }
@@ -429,6 +439,7 @@ mod tests {
             r#"{"title":"T","kind":"task"}"#.to_string(),
             r#"{"title":"T","kind":"task"}"#.to_string(),
         ];
+        cols.hashes = vec!["blake3:a".to_string(), "blake3:b".to_string()];
         assert!(cols.into_events().is_err());
     }
 
@@ -447,6 +458,7 @@ mod tests {
         assert_eq!(row.event_type, EventType::Move);
         assert_eq!(row.item_id, "bn-b8y");
         assert_eq!(row.parents, "blake3:ref");
+        assert_eq!(row.event_hash, events[1].event_hash);
     }
 
     #[test]
@@ -202,7 +202,7 @@ impl CacheManager {
     }
 
     /// Parse TSJSON events from shards using the standard shard replay
-    /// pipeline.
+    /// pipeline, with redacted payloads scrubbed.
     fn parse_tsjson(&self) -> Result<Vec<Event>> {
         let bones_dir = self.events_dir.parent().unwrap_or_else(|| Path::new("."));
         let shard_mgr = ShardManager::new(bones_dir);
@@ -211,8 +211,9 @@ impl CacheManager {
             .replay()
             .map_err(|e| anyhow::anyhow!("replay shards: {e}"))?;
 
-        let events = parse_lines(&content)
+        let mut events = parse_lines(&content)
             .map_err(|(line, e)| anyhow::anyhow!("parse error at line {line}: {e}"))?;
+        crate::redact::scrub_redacted(&mut events);
 
         Ok(events)
     }
@@ -299,6 +300,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         writer::write_event(&mut event).unwrap();
         event
@@ -315,9 +317,11 @@ mod tests {
             data: EventData::Move(MoveData {
                 state: State::Doing,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         writer::write_event(&mut event).unwrap();
         event
@@ -521,9 +525,9 @@ mod tests {
         let r2 = mgr.load_events().unwrap();
         assert_eq!(r2.source, LoadSource::Cache);
 
-        // Compare event data (excluding event_hash which may differ)
         assert_eq!(r1.events.len(), r2.events.len());
         for (a, b) in r1.events.iter().zip(r2.events.iter()) {
+            assert_eq!(a.event_hash, b.event_hash);
             assert_eq!(a.wall_ts_us, b.wall_ts_us);
             assert_eq!(a.agent, b.agent);
             assert_eq!(a.event_type, b.event_type);
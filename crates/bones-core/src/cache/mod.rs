@@ -51,7 +51,8 @@ pub use writer::{CacheStats, CacheWriter, rebuild_cache};
 
 use crate::event::Event;
 use columns::{
-    COL_AGENTS, COL_EVENT_TYPES, COL_ITC, COL_ITEM_IDS, COL_PARENTS, COL_TIMESTAMPS, COL_VALUES,
+    COL_AGENTS, COL_EVENT_TYPES, COL_HASHES, COL_ITC, COL_ITEM_IDS, COL_PARENTS, COL_TIMESTAMPS,
+    COL_VALUES,
 };
 
 // ---------------------------------------------------------------------------
@@ -62,7 +63,7 @@ use columns::{
 pub const CACHE_MAGIC: [u8; 4] = *b"BNCH";
 
 /// The current format version written to new cache files.
-pub const CACHE_VERSION: u8 = 1;
+pub const CACHE_VERSION: u8 = 2;
 
 /// File header size in bytes (fixed).
 ///
@@ -201,7 +202,7 @@ pub(crate) fn fingerprint_dir(dir: &Path) -> Result<u64> {
 /// File header for the binary event cache.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CacheHeader {
-    /// Format version (currently 1).
+    /// Format version (currently 2).
     pub version: u8,
     /// Number of columns present.
     pub column_count: u8,
@@ -252,6 +253,7 @@ impl CacheHeader {
         InternedStringCodec::encode(&cols.parents, &mut col_bufs[COL_PARENTS])?;
         RawBytesCodec::encode(&cols.itc, &mut col_bufs[COL_ITC])?;
         ValueCodec::encode(&cols.values, &mut col_bufs[COL_VALUES])?;
+        RawBytesCodec::encode(&cols.hashes, &mut col_bufs[COL_HASHES])?;
 
         // Compute column offsets
         let offsets_section_size = COLUMN_COUNT * 8; // 8 bytes per u64 offset
@@ -397,6 +399,7 @@ impl CacheHeader {
         let (parents, _) = InternedStringCodec::decode(col_slice(COL_PARENTS)?, count)?;
         let (itc, _) = RawBytesCodec::decode(col_slice(COL_ITC)?, count)?;
         let (values, _) = ValueCodec::decode(col_slice(COL_VALUES)?, count)?;
+        let (hashes, _) = RawBytesCodec::decode(col_slice(COL_HASHES)?, count)?;
 
         let cols = CacheColumns {
             timestamps,
@@ -406,6 +409,7 @@ impl CacheHeader {
             parents,
             itc,
             values,
+            hashes,
         };
 
         let header = Self {
@@ -445,8 +449,8 @@ pub fn encode_events(events: &[Event], created_at_us: u64) -> Result<Vec<u8>, Ca
 /// Convenience wrapper around [`CacheHeader::decode`] +
 /// [`CacheColumns::into_events`].
 ///
-/// **Note**: The `event_hash` field of each reconstructed event will be empty.
-/// Callers that need hashes must recompute them from the TSJSON writer.
+/// Event hashes are the ones stored at write time; they are never
+/// recomputed, since redacted payloads are scrubbed before caching.
 ///
 /// # Errors
 ///
@@ -474,8 +478,9 @@ mod tests {
 
     fn make_event(ts: i64, agent: &str, et: EventType, item: &str) -> Event {
         use crate::event::data::{
-            AssignAction, AssignData, CommentData, CompactData, DeleteData, LinkData, RedactData,
-            SnapshotData, UnlinkData, UpdateData,
+            AssignAction, AssignData, AttachData, CommentData, CommentEditData, CommentRetractData,
+            CompactData, DeleteData, LinkData, PatchData, RedactData, SnapshotData, UnlinkData,
+            UpdateData,
         };
         let data = match et {
             EventType::Create => EventData::Create(CreateData {
@@ -497,6 +502,7 @@ mod tests {
             EventType::Move => EventData::Move(MoveData {
                 state: State::Doing,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             EventType::Assign => EventData::Assign(AssignData {
@@ -506,6 +512,7 @@ mod tests {
             }),
             EventType::Comment => EventData::Comment(CommentData {
                 body: "A comment".to_string(),
+                reply_to: None,
                 extra: BTreeMap::new(),
             }),
             EventType::Link => EventData::Link(LinkData {
@@ -535,6 +542,32 @@ mod tests {
                 reason: "oops".to_string(),
                 extra: BTreeMap::new(),
             }),
+            EventType::Patch => EventData::Patch(PatchData {
+                base: "blake3:abc".to_string(),
+                ops: vec![crate::crdt::text::TextOp::Insert {
+                    id: crate::crdt::text::TextId::new(1, agent),
+                    after: None,
+                    text: "edit".to_string(),
+                }],
+                extra: BTreeMap::new(),
+            }),
+            EventType::CommentEdit => EventData::CommentEdit(CommentEditData {
+                comment: "blake3:abc".to_string(),
+                body: "An edited comment".to_string(),
+                extra: BTreeMap::new(),
+            }),
+            EventType::CommentRetract => EventData::CommentRetract(CommentRetractData {
+                comment: "blake3:abc".to_string(),
+                reason: None,
+                extra: BTreeMap::new(),
+            }),
+            EventType::Attach => EventData::Attach(AttachData {
+                blob: "blake3:def".to_string(),
+                name: "trace.log".to_string(),
+                mime: "text/plain".to_string(),
+                size: 42,
+                extra: BTreeMap::new(),
+            }),
         };
         Event {
             wall_ts_us: ts,
@@ -545,6 +578,7 @@ mod tests {
             item_id: ItemId::new_unchecked(item),
             data,
             event_hash: format!("blake3:{ts:016x}"),
+            signature: None,
         }
     }
 
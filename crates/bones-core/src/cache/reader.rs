@@ -84,9 +84,6 @@ impl CacheReader {
 
     /// Decode all events from the cache.
     ///
-    /// **Note**: The `event_hash` field will be empty on reconstructed events.
-    /// Callers needing hashes must recompute them.
-    ///
     /// # Errors
     ///
     /// Returns [`CacheReaderError`] if column decoding fails.
@@ -187,6 +184,7 @@ mod tests {
             _ => EventData::Move(MoveData {
                 state: State::Doing,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
         };
@@ -199,6 +197,7 @@ mod tests {
             item_id: ItemId::new_unchecked(item),
             data,
             event_hash: format!("blake3:{ts:016x}"),
+            signature: None,
         }
     }
 
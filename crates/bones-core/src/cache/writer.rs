@@ -114,6 +114,7 @@ impl CacheWriter {
         };
 
         all_events.extend_from_slice(new_events);
+        crate::redact::scrub_redacted(&mut all_events);
 
         let writer = Self { events: all_events };
         writer.write_to_file(existing)
@@ -122,6 +123,9 @@ impl CacheWriter {
 
 /// Rebuild cache from `.bones/events` shards and write to `cache_path`.
 ///
+/// Payloads targeted by an `item.redact` event are scrubbed before encoding
+/// so the cache never holds redacted content.
+///
 /// # Errors
 ///
 /// Returns an error if shard replay, parsing, encoding, or file I/O fails.
@@ -133,8 +137,9 @@ pub fn rebuild_cache(events_dir: &Path, cache_path: &Path) -> Result<CacheStats>
         .replay()
         .map_err(|e| anyhow::anyhow!("replay shards: {e}"))?;
 
-    let events = parse_lines(&content)
+    let mut events = parse_lines(&content)
         .map_err(|(line, e)| anyhow::anyhow!("parse error at line {line}: {e}"))?;
+    crate::redact::scrub_redacted(&mut events);
 
     let mut writer = CacheWriter::new();
     for event in &events {
@@ -187,6 +192,7 @@ mod tests {
             _ => EventData::Move(MoveData {
                 state: State::Doing,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
         };
@@ -200,6 +206,7 @@ mod tests {
             item_id: ItemId::new_unchecked(item_id),
             data,
             event_hash: String::new(),
+            signature: None,
         };
         let _ = event::writer::write_event(&mut event);
         event
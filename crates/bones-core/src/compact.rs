@@ -19,6 +19,10 @@
 //! it would incorrectly dominate concurrent events that were not observed at
 //! compaction time, violating semantic preservation.
 //!
+//! Description edits (`item.patch`) are folded into the description
+//! register: the snapshot carries the rendered text under the clock of the
+//! latest patch, so the sequence itself never needs to be persisted.
+//!
 //! # Redaction Interaction
 //!
 //! Snapshots check the redaction set before including field values. Compaction
@@ -38,7 +42,7 @@ use serde::{Deserialize, Serialize};
 use crate::clock::itc::Stamp;
 use crate::crdt::OrSet;
 use crate::crdt::gset::GSet;
-use crate::crdt::item_state::WorkItemState;
+use crate::crdt::item_state::{AttachmentRef, SubState, WorkItemState};
 use crate::crdt::lww::LwwRegister;
 use crate::crdt::state::{EpochPhaseState, Phase};
 use crate::event::Event;
@@ -104,10 +108,20 @@ pub struct SnapshotPayload {
     pub size: LwwSnapshot<Option<Size>>,
     pub urgency: LwwSnapshot<Urgency>,
     pub parent: LwwSnapshot<String>,
+    /// Absent in snapshots written before due dates existed.
+    #[serde(default = "unset_due")]
+    pub due: LwwSnapshot<Option<u64>>,
     pub deleted: LwwSnapshot<bool>,
+    /// Custom field registers, keyed by field name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom: BTreeMap<String, LwwSnapshot<Option<serde_json::Value>>>,
 
     // -- Epoch+Phase lifecycle state --
     pub state: EpochPhaseState,
+    /// Workflow sub-state register. Absent in snapshots written before
+    /// sub-states existed.
+    #[serde(default = "unset_substate")]
+    pub substate: LwwSnapshot<Option<SubState>>,
 
     // -- OR-Set fields (full state with elements and tombstones) --
     pub assignees: OrSet<String>,
@@ -117,6 +131,19 @@ pub struct SnapshotPayload {
 
     // -- G-Set (grow-only comment hashes) --
     pub comments: GSet<String>,
+    /// Edited comment bodies, keyed by comment hash.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub comment_edits: BTreeMap<String, LwwSnapshot<String>>,
+    /// Retracted comment hashes. Absent in snapshots written before
+    /// retraction existed.
+    #[serde(default, skip_serializing_if = "GSet::is_empty")]
+    pub retracted_comments: GSet<String>,
+
+    // -- Grow-only attachment references --
+    /// Attachments keyed by `item.attach` event hash. Carrying the blob
+    /// references keeps their blobs live once the original events are gone.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attachments: BTreeMap<String, AttachmentRef>,
 
     // -- Timestamps --
     pub created_at: u64,
@@ -134,6 +161,28 @@ pub struct SnapshotPayload {
     pub latest_ts: i64,
 }
 
+/// Zero-clock `due` register for snapshots that predate the field.
+const fn unset_due() -> LwwSnapshot<Option<u64>> {
+    LwwSnapshot {
+        value: None,
+        stamp: Stamp::seed(),
+        wall_ts: 0,
+        agent_id: String::new(),
+        event_hash: String::new(),
+    }
+}
+
+/// Zero-clock `substate` register for snapshots that predate the field.
+const fn unset_substate() -> LwwSnapshot<Option<SubState>> {
+    LwwSnapshot {
+        value: None,
+        stamp: Stamp::seed(),
+        wall_ts: 0,
+        agent_id: String::new(),
+        event_hash: String::new(),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // WorkItemState ↔ SnapshotPayload conversion
 // ---------------------------------------------------------------------------
@@ -154,18 +203,32 @@ impl WorkItemState {
         SnapshotPayload {
             item_id: item_id.to_string(),
             title: LwwSnapshot::from(&self.title),
-            description: LwwSnapshot::from(&self.description),
+            description: LwwSnapshot::from(&self.fold_description()),
             kind: LwwSnapshot::from(&self.kind),
             size: LwwSnapshot::from(&self.size),
             urgency: LwwSnapshot::from(&self.urgency),
             parent: LwwSnapshot::from(&self.parent),
+            due: LwwSnapshot::from(&self.due),
             deleted: LwwSnapshot::from(&self.deleted),
+            custom: self
+                .custom
+                .iter()
+                .map(|(name, reg)| (name.clone(), LwwSnapshot::from(reg)))
+                .collect(),
             state: self.state.clone(),
+            substate: LwwSnapshot::from(&self.substate),
             assignees: self.assignees.clone(),
             labels: self.labels.clone(),
             blocked_by: self.blocked_by.clone(),
             related_to: self.related_to.clone(),
             comments: self.comments.clone(),
+            comment_edits: self
+                .comment_edits
+                .iter()
+                .map(|(hash, reg)| (hash.clone(), LwwSnapshot::from(reg)))
+                .collect(),
+            retracted_comments: self.retracted_comments.clone(),
+            attachments: self.attachments.clone(),
             created_at: self.created_at,
             updated_at: self.updated_at,
             compacted_from,
@@ -189,11 +252,26 @@ impl WorkItemState {
             size: LwwRegister::from(&payload.size),
             urgency: LwwRegister::from(&payload.urgency),
             parent: LwwRegister::from(&payload.parent),
+            due: LwwRegister::from(&payload.due),
+            substate: LwwRegister::from(&payload.substate),
+            custom: payload
+                .custom
+                .iter()
+                .map(|(name, snap)| (name.clone(), LwwRegister::from(snap)))
+                .collect(),
+            description_edits: BTreeMap::new(),
             assignees: payload.assignees.clone(),
             labels: payload.labels.clone(),
             blocked_by: payload.blocked_by.clone(),
             related_to: payload.related_to.clone(),
             comments: payload.comments.clone(),
+            comment_edits: payload
+                .comment_edits
+                .iter()
+                .map(|(hash, snap)| (hash.clone(), LwwRegister::from(snap)))
+                .collect(),
+            retracted_comments: payload.retracted_comments.clone(),
+            attachments: payload.attachments.clone(),
             deleted: LwwRegister::from(&payload.deleted),
             created_at: payload.created_at,
             updated_at: payload.updated_at,
@@ -312,6 +390,7 @@ pub fn compact_item<S: ::std::hash::BuildHasher>(
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(), // Will be computed
+        signature: None,
     };
 
     // Compute and set the event hash.
@@ -517,13 +596,17 @@ pub fn extract_snapshot_payload(event: &Event) -> Result<SnapshotPayload> {
 /// This checks all fields including CRDT metadata. It is used during
 /// verification to ensure compaction is semantics-preserving.
 fn states_match(a: &WorkItemState, b: &WorkItemState) -> bool {
+    // Description edits are compared through their folded form, which is
+    // what a snapshot carries.
+    let (a_desc, b_desc) = (a.fold_description(), b.fold_description());
+
     // LWW fields: compare value and clock metadata.
     a.title.value == b.title.value
         && a.title.wall_ts == b.title.wall_ts
         && a.title.agent_id == b.title.agent_id
         && a.title.event_hash == b.title.event_hash
-        && a.description.value == b.description.value
-        && a.description.wall_ts == b.description.wall_ts
+        && a_desc.value == b_desc.value
+        && a_desc.wall_ts == b_desc.wall_ts
         && a.kind.value == b.kind.value
         && a.kind.wall_ts == b.kind.wall_ts
         && a.size.value == b.size.value
@@ -534,15 +617,20 @@ fn states_match(a: &WorkItemState, b: &WorkItemState) -> bool {
         && a.parent.wall_ts == b.parent.wall_ts
         && a.deleted.value == b.deleted.value
         && a.deleted.wall_ts == b.deleted.wall_ts
+        && a.custom == b.custom
         // EpochPhaseState
         && a.state == b.state
+        && a.substate.value == b.substate.value
         // OR-Sets
         && a.assignees == b.assignees
         && a.labels == b.labels
         && a.blocked_by == b.blocked_by
         && a.related_to == b.related_to
-        // G-Set
+        // G-Sets and comment edits
         && a.comments == b.comments
+        && a.retracted_comments == b.retracted_comments
+        && a.attachments == b.attachments
+        && a.comment_edits == b.comment_edits
         // Timestamps
         && a.created_at == b.created_at
         && a.updated_at == b.updated_at
@@ -613,6 +701,7 @@ mod tests {
             item_id: ItemId::new_unchecked(item_id),
             data,
             event_hash: event_hash.to_string(),
+            signature: None,
         }
     }
 
@@ -643,6 +732,7 @@ mod tests {
             EventData::Move(MoveData {
                 state,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             wall_ts,
@@ -678,6 +768,7 @@ mod tests {
             EventType::Comment,
             EventData::Comment(CommentData {
                 body: body.to_string(),
+                reply_to: None,
                 extra: BTreeMap::new(),
             }),
             wall_ts,
@@ -851,6 +942,78 @@ mod tests {
         assert!(states_match(&original, &reconstructed));
     }
 
+    #[test]
+    fn snapshot_payload_roundtrips_custom_fields() {
+        let mut events = sample_item_events("bn-test1");
+        events.push(make_event(
+            EventType::Update,
+            EventData::Update(UpdateData {
+                field: "custom.severity".to_string(),
+                value: serde_json::json!(2),
+                extra: BTreeMap::new(),
+            }),
+            5_500_000,
+            "bob",
+            "blake3:e7",
+            "bn-test1",
+        ));
+
+        let mut original = WorkItemState::new();
+        for event in &events {
+            original.apply_event(event);
+        }
+
+        let payload = original.to_snapshot_payload("bn-test1", events.len(), 1_000_000, 6_000_000);
+        let json = serde_json::to_string(&payload).expect("serialize");
+        let roundtripped: SnapshotPayload = serde_json::from_str(&json).expect("deserialize");
+        let reconstructed = WorkItemState::from_snapshot_payload(&roundtripped);
+
+        assert_eq!(
+            reconstructed.custom["severity"].value,
+            Some(serde_json::json!(2))
+        );
+        assert!(states_match(&original, &reconstructed));
+    }
+
+    #[test]
+    fn snapshot_folds_description_patches() {
+        let mut events = sample_item_events("bn-test1");
+        let mut original = WorkItemState::new();
+        for event in &events {
+            original.apply_event(event);
+        }
+
+        let base = original.description.clone();
+        let ops =
+            crate::crdt::text::TextSeq::new().diff(&base.value, "A better description", "bob");
+        let patch = make_event(
+            EventType::Patch,
+            EventData::Patch(crate::event::data::PatchData {
+                base: base.event_hash.clone(),
+                ops,
+                extra: BTreeMap::new(),
+            }),
+            5_500_000,
+            "bob",
+            "blake3:p1",
+            "bn-test1",
+        );
+        original.apply_event(&patch);
+        events.push(patch);
+
+        let snapshot =
+            compact_item("bn-test1", &events, "compactor", &HashSet::new()).expect("snapshot");
+        let payload = extract_snapshot_payload(&snapshot).expect("payload");
+        assert_eq!(payload.description.value, "A better description");
+        assert_eq!(payload.description.event_hash, "blake3:p1");
+
+        let reconstructed = WorkItemState::from_snapshot_payload(&payload);
+        assert!(reconstructed.description_edits.is_empty());
+        assert_eq!(reconstructed.description_text(), "A better description");
+        assert!(verify_compaction("bn-test1", &events, &snapshot).expect("verify"));
+        assert!(verify_lattice_join(&events, &snapshot).expect("join"));
+    }
+
     // -----------------------------------------------------------------------
     // is_eligible
     // -----------------------------------------------------------------------
@@ -4,6 +4,10 @@ use std::env;
 use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 
+use crate::model::custom::{CustomFieldSchema, CustomFieldType, is_valid_field_name};
+use crate::model::item::Size;
+use crate::model::workflow::{WorkflowStates, validate_states};
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ProjectConfig {
     #[serde(default)]
@@ -14,6 +18,12 @@ pub struct ProjectConfig {
     pub triage: TriageConfig,
     #[serde(default)]
     pub done: DoneConfig,
+    #[serde(default)]
+    pub custom_fields: CustomFieldSchema,
+    #[serde(default)]
+    pub states: WorkflowStates,
+    #[serde(default)]
+    pub sync: SyncConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,14 +70,72 @@ impl Default for SearchConfig {
 pub struct TriageConfig {
     #[serde(default = "default_true")]
     pub feedback_learning: bool,
+    #[serde(default)]
+    pub size_durations: SizeDurations,
 }
 
 impl Default for TriageConfig {
     fn default() -> Self {
         Self {
             feedback_learning: default_true(),
+            size_durations: SizeDurations::default(),
+        }
+    }
+}
+
+/// Estimated duration of each item size, used to weight critical path
+/// analysis.
+///
+/// Units are whatever the team estimates in (ideal days by default); slack
+/// is reported in the same units. Items without a size use `unestimated`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SizeDurations {
+    pub xs: u32,
+    pub s: u32,
+    pub m: u32,
+    pub l: u32,
+    pub xl: u32,
+    pub unestimated: u32,
+}
+
+impl Default for SizeDurations {
+    fn default() -> Self {
+        Self {
+            xs: 1,
+            s: 2,
+            m: 3,
+            l: 5,
+            xl: 8,
+            unestimated: 3,
+        }
+    }
+}
+
+impl SizeDurations {
+    /// Duration for an item of the given size.
+    #[must_use]
+    pub const fn duration(&self, size: Option<Size>) -> u32 {
+        match size {
+            Some(Size::Xs) => self.xs,
+            Some(Size::S) => self.s,
+            Some(Size::M) => self.m,
+            Some(Size::L) => self.l,
+            Some(Size::Xl) => self.xl,
+            None => self.unestimated,
         }
     }
+
+    const fn entries(&self) -> [(&'static str, u32); 6] {
+        [
+            ("xs", self.xs),
+            ("s", self.s),
+            ("m", self.m),
+            ("l", self.l),
+            ("xl", self.xl),
+            ("unestimated", self.unestimated),
+        ]
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -76,6 +144,48 @@ pub struct DoneConfig {
     pub require_reason: bool,
 }
 
+/// How `bn sync` moves events between replicas through git.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncConfig {
+    /// Where the event log lives in git.
+    #[serde(default)]
+    pub events: EventStorage,
+    /// Git remote the event ref is fetched from and pushed to.
+    #[serde(default = "default_sync_remote")]
+    pub remote: String,
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            events: EventStorage::default(),
+            remote: default_sync_remote(),
+        }
+    }
+}
+
+/// Where the event log is stored in git.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventStorage {
+    /// Shards are tracked files under `.bones/events/`, merged by the
+    /// `bones-events` merge driver when branches are pulled.
+    #[default]
+    Files,
+    /// Shards are local-only; the event log is published as commits under
+    /// `refs/bones/events` and synced independently of branches.
+    Ref,
+}
+
+impl std::fmt::Display for EventStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Files => "files",
+            Self::Ref => "ref",
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RepoConfig {
     pub name: String,
@@ -123,7 +233,8 @@ pub fn load_project_config(project_root: &Path) -> Result<ProjectConfig> {
 /// # Errors
 ///
 /// Returns an error if numeric thresholds are non-finite, outside the
-/// normalized score range, or ordered inconsistently.
+/// normalized score range, or ordered inconsistently, if any size duration
+/// is zero, or if a custom field or workflow state declaration is malformed.
 pub fn validate_project_config(config: &ProjectConfig) -> Result<()> {
     validate_threshold(
         "search.duplicate_threshold",
@@ -139,6 +250,25 @@ pub fn validate_project_config(config: &ProjectConfig) -> Result<()> {
         );
     }
 
+    for (size, duration) in config.triage.size_durations.entries() {
+        if duration == 0 {
+            anyhow::bail!("triage.size_durations.{size} must be at least 1");
+        }
+    }
+
+    for (name, def) in &config.custom_fields {
+        if !is_valid_field_name(name) {
+            anyhow::bail!(
+                "custom_fields.{name}: name must start with a lowercase letter and contain only lowercase letters, digits, '_' or '-'"
+            );
+        }
+        if def.field_type == CustomFieldType::Enum && def.values.is_empty() {
+            anyhow::bail!("custom_fields.{name}: enum fields must declare `values`");
+        }
+    }
+
+    validate_states(&config.states).map_err(anyhow::Error::msg)?;
+
     Ok(())
 }
 
@@ -264,6 +394,10 @@ const fn default_true() -> bool {
     true
 }
 
+fn default_sync_remote() -> String {
+    "origin".to_string()
+}
+
 fn default_search_model() -> String {
     "minilm-l6-v2-int8".to_string()
 }
@@ -279,6 +413,7 @@ const fn default_related_threshold() -> f64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::item::State;
     use std::sync::atomic::{AtomicU64, Ordering};
 
     fn make_temp_dir(label: &str) -> std::path::PathBuf {
@@ -299,6 +434,23 @@ mod tests {
         assert_eq!(cfg.search.model, "minilm-l6-v2-int8");
         assert!(cfg.triage.feedback_learning);
         assert!(!cfg.done.require_reason);
+        assert_eq!(cfg.sync.events, EventStorage::Files);
+        assert_eq!(cfg.sync.remote, "origin");
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn project_config_reads_ref_event_storage() {
+        let root = make_temp_dir("project-sync-ref");
+        std::fs::create_dir_all(root.join(".bones")).expect("create .bones");
+        std::fs::write(
+            root.join(".bones/config.toml"),
+            "[sync]\nevents = \"ref\"\nremote = \"upstream\"\n",
+        )
+        .expect("write config");
+        let cfg = load_project_config(&root).expect("load should succeed");
+        assert_eq!(cfg.sync.events, EventStorage::Ref);
+        assert_eq!(cfg.sync.remote, "upstream");
         let _ = std::fs::remove_dir_all(&root);
     }
 
@@ -345,6 +497,143 @@ related_threshold = 0.7
         let _ = std::fs::remove_dir_all(&root);
     }
 
+    #[test]
+    fn project_config_parses_partial_size_durations() {
+        let root = make_temp_dir("project-size-durations");
+        std::fs::create_dir_all(root.join(".bones")).expect("create .bones");
+        std::fs::write(
+            root.join(".bones/config.toml"),
+            r#"
+[triage.size_durations]
+xl = 13
+"#,
+        )
+        .expect("write config");
+
+        let cfg = load_project_config(&root).expect("load should succeed");
+        let durations = cfg.triage.size_durations;
+        assert_eq!(durations.duration(Some(Size::Xl)), 13);
+        assert_eq!(durations.duration(Some(Size::M)), 3);
+        assert_eq!(durations.duration(None), 3);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn project_config_parses_custom_fields() {
+        let root = make_temp_dir("project-custom-fields");
+        std::fs::create_dir_all(root.join(".bones")).expect("create .bones");
+        std::fs::write(
+            root.join(".bones/config.toml"),
+            r#"
+[custom_fields.component]
+type = "enum"
+values = ["api", "cli"]
+
+[custom_fields.severity]
+type = "int"
+"#,
+        )
+        .expect("write config");
+
+        let cfg = load_project_config(&root).expect("load should succeed");
+        assert_eq!(cfg.custom_fields.len(), 2);
+        assert_eq!(
+            cfg.custom_fields["component"].field_type,
+            CustomFieldType::Enum
+        );
+        assert_eq!(
+            cfg.custom_fields["severity"].field_type,
+            CustomFieldType::Int
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn project_config_parses_workflow_states() {
+        let root = make_temp_dir("project-states");
+        std::fs::create_dir_all(root.join(".bones")).expect("create .bones");
+        std::fs::write(
+            root.join(".bones/config.toml"),
+            r#"
+[states.review]
+phase = "doing"
+from = ["doing"]
+
+[states.blocked]
+phase = "open"
+"#,
+        )
+        .expect("write config");
+
+        let cfg = load_project_config(&root).expect("load should succeed");
+        assert_eq!(cfg.states["review"].phase, State::Doing);
+        assert_eq!(cfg.states["review"].from, vec!["doing".to_string()]);
+        assert_eq!(cfg.states["blocked"].phase, State::Open);
+
+        std::fs::write(
+            root.join(".bones/config.toml"),
+            r#"
+[states.review]
+phase = "doing"
+from = ["qa"]
+"#,
+        )
+        .expect("write config");
+        let err = load_project_config(&root).expect_err("unknown from state should fail");
+        assert!(
+            err.chain()
+                .any(|cause| cause.to_string().contains("states.review.from"))
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn project_config_rejects_enum_without_values() {
+        let root = make_temp_dir("project-enum-no-values");
+        std::fs::create_dir_all(root.join(".bones")).expect("create .bones");
+        std::fs::write(
+            root.join(".bones/config.toml"),
+            r#"
+[custom_fields.component]
+type = "enum"
+"#,
+        )
+        .expect("write config");
+
+        let err = load_project_config(&root).expect_err("enum without values should fail");
+        assert!(
+            err.chain()
+                .any(|cause| cause.to_string().contains("custom_fields.component"))
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn project_config_rejects_zero_size_duration() {
+        let root = make_temp_dir("project-zero-duration");
+        std::fs::create_dir_all(root.join(".bones")).expect("create .bones");
+        std::fs::write(
+            root.join(".bones/config.toml"),
+            r#"
+[triage.size_durations]
+s = 0
+"#,
+        )
+        .expect("write config");
+
+        let err = load_project_config(&root).expect_err("zero duration should fail");
+        assert!(
+            err.chain()
+                .any(|cause| cause.to_string().contains("triage.size_durations.s"))
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
     #[test]
     fn cli_json_overrides_env_and_config() {
         let output =
@@ -0,0 +1,552 @@
+//! Detection of concurrent LWW writes that lost silently.
+//!
+//! LWW registers (title, urgency, parent, custom fields, ...) always
+//! converge, but when two replicas write the same field without seeing each
+//! other's write, the losing value vanishes from every view. To surface
+//! those writes the projection keeps the *heads* of every LWW field: writes
+//! that no later write has observed.
+//!
+//! A write observes an earlier one when its ITC stamp strictly dominates the
+//! earlier stamp, or when it lists the earlier event in `parents`. The CLI
+//! sets `parents` to the current heads whenever it writes a field, so a
+//! field only keeps several heads when replicas wrote it concurrently. The
+//! head that wins the [`LwwRegister`] tie-break chain is the visible value;
+//! the others are reported as losers.
+
+use anyhow::{Context, Result};
+use rusqlite::{Connection, params};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::clock::itc::Stamp;
+use crate::clock::text::stamp_from_text;
+use crate::crdt::item_state::derive_stamp_from_hash;
+use crate::crdt::lww::LwwRegister;
+use crate::event::Event;
+use crate::event::data::EventData;
+use crate::model::custom::custom_field_name;
+use crate::model::due::DUE_FIELD;
+use crate::redact::{REDACTED_PLACEHOLDER, scrub_payload};
+
+/// One write to an LWW field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldWrite {
+    /// Hash of the event that wrote the value.
+    pub event_hash: String,
+    /// Agent that wrote the value.
+    pub agent: String,
+    /// Wall-clock time of the write.
+    pub wall_ts_us: i64,
+    /// ITC stamp text of the write.
+    #[serde(skip)]
+    pub itc: String,
+    /// The written value, as carried by the event.
+    pub value: Value,
+}
+
+/// Concurrent writes to one field where some values were lost.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldConflict {
+    pub item_id: String,
+    pub field: String,
+    /// The write that is currently visible.
+    pub winner: FieldWrite,
+    /// Concurrent writes whose values are hidden by `winner`, best first.
+    pub losers: Vec<FieldWrite>,
+}
+
+/// Return `true` if `field` is an LWW register of a work item.
+#[must_use]
+pub fn is_lww_field(field: &str) -> bool {
+    matches!(
+        field,
+        "title" | "description" | "kind" | "size" | "urgency" | "parent" | DUE_FIELD
+    ) || custom_field_name(field).is_some()
+}
+
+/// LWW fields written by `event`, with the value each one receives.
+#[must_use]
+pub fn lww_writes(event: &Event) -> Vec<(String, Value)> {
+    match &event.data {
+        EventData::Create(d) => {
+            let mut writes = vec![
+                ("title".to_string(), Value::from(d.title.as_str())),
+                ("kind".to_string(), Value::from(d.kind.to_string())),
+                ("urgency".to_string(), Value::from(d.urgency.to_string())),
+            ];
+            if let Some(size) = d.size {
+                writes.push(("size".to_string(), Value::from(size.to_string())));
+            }
+            if let Some(description) = &d.description {
+                writes.push(("description".to_string(), Value::from(description.as_str())));
+            }
+            if let Some(parent) = &d.parent {
+                writes.push(("parent".to_string(), Value::from(parent.as_str())));
+            }
+            writes
+        }
+        EventData::Update(d) if is_lww_field(&d.field) => {
+            vec![(d.field.clone(), d.value.clone())]
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn stamp_of(itc: &str, event_hash: &str) -> Stamp {
+    stamp_from_text(itc).unwrap_or_else(|| derive_stamp_from_hash(event_hash))
+}
+
+fn strictly_before(a: &Stamp, b: &Stamp) -> bool {
+    a.leq(b) && !b.leq(a)
+}
+
+/// Current heads of `field` on `item_id`.
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub fn field_heads(conn: &Connection, item_id: &str, field: &str) -> Result<Vec<FieldWrite>> {
+    let mut stmt = conn.prepare(
+        "SELECT event_hash, agent, itc, wall_ts_us, value_json FROM item_field_heads
+         WHERE item_id = ?1 AND field = ?2
+         ORDER BY wall_ts_us, event_hash",
+    )?;
+    let rows = stmt.query_map(params![item_id, field], |row| {
+        Ok(FieldWrite {
+            event_hash: row.get(0)?,
+            agent: row.get(1)?,
+            itc: row.get(2)?,
+            wall_ts_us: row.get(3)?,
+            value: serde_json::from_str(&row.get::<_, String>(4)?).unwrap_or(Value::Null),
+        })
+    })?;
+    rows.collect::<rusqlite::Result<_>>()
+        .with_context(|| format!("load {field} heads for {item_id}"))
+}
+
+/// Parents for a new write to `field`: every current head, so the new
+/// write supersedes all values the writer could see.
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub fn observed_parents(conn: &Connection, item_id: &str, field: &str) -> Result<Vec<String>> {
+    Ok(field_heads(conn, item_id, field)?
+        .into_iter()
+        .map(|head| head.event_hash)
+        .collect())
+}
+
+/// Record the LWW writes of `event` as field heads, dropping the heads it
+/// observed. Writes of a redacted event are stored scrubbed.
+///
+/// # Errors
+///
+/// Returns an error if a projection query fails.
+pub fn record_heads(conn: &Connection, event: &Event, redacted: bool) -> Result<()> {
+    let scrubbed;
+    let event = if redacted {
+        let mut copy = event.clone();
+        scrub_payload(&mut copy);
+        scrubbed = copy;
+        &scrubbed
+    } else {
+        event
+    };
+
+    let item_id = event.item_id.as_str();
+    let stamp = stamp_of(&event.itc, &event.event_hash);
+
+    for (field, value) in lww_writes(event) {
+        let heads = field_heads(conn, item_id, &field)?;
+        // Replayed out of order: a head already causally follows this write.
+        if heads
+            .iter()
+            .any(|head| strictly_before(&stamp, &stamp_of(&head.itc, &head.event_hash)))
+        {
+            continue;
+        }
+
+        for head in &heads {
+            let observed = event.parents.contains(&head.event_hash)
+                || strictly_before(&stamp_of(&head.itc, &head.event_hash), &stamp);
+            if observed {
+                conn.execute(
+                    "DELETE FROM item_field_heads
+                     WHERE item_id = ?1 AND field = ?2 AND event_hash = ?3",
+                    params![item_id, field, head.event_hash],
+                )?;
+            }
+        }
+
+        conn.execute(
+            "INSERT OR REPLACE INTO item_field_heads
+                (item_id, field, event_hash, agent, itc, wall_ts_us, value_json)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                item_id,
+                field,
+                event.event_hash,
+                event.agent,
+                event.itc,
+                event.wall_ts_us,
+                value.to_string()
+            ],
+        )
+        .with_context(|| format!("record {field} head for {item_id}"))?;
+    }
+    Ok(())
+}
+
+/// Replace text values written by `target_hash` with the redaction
+/// placeholder. Returns the number of heads changed.
+///
+/// # Errors
+///
+/// Returns an error if the update fails.
+pub fn scrub_heads(conn: &Connection, target_hash: &str) -> Result<usize> {
+    let placeholder = Value::from(REDACTED_PLACEHOLDER).to_string();
+    conn.execute(
+        "UPDATE item_field_heads SET value_json = ?1
+         WHERE event_hash = ?2 AND field IN ('title', 'description') AND value_json <> ?1",
+        params![placeholder, target_hash],
+    )
+    .context("scrub redacted field heads")
+}
+
+/// Pick the LWW winner among `heads` using the register tie-break chain.
+pub(crate) fn winner_index(heads: &[FieldWrite]) -> Option<usize> {
+    heads
+        .iter()
+        .enumerate()
+        .map(|(idx, head)| {
+            LwwRegister::new(
+                idx,
+                stamp_of(&head.itc, &head.event_hash),
+                u64::try_from(head.wall_ts_us).unwrap_or(0),
+                head.agent.clone(),
+                head.event_hash.clone(),
+            )
+        })
+        .reduce(|mut acc, reg| {
+            acc.merge(&reg);
+            acc
+        })
+        .map(|reg| reg.value)
+}
+
+/// Group `heads` of one field into a conflict, if any concurrent write
+/// carries a value other than the winner's.
+#[must_use]
+pub fn conflict_from_heads(
+    item_id: &str,
+    field: &str,
+    mut heads: Vec<FieldWrite>,
+) -> Option<FieldConflict> {
+    let winner = heads.remove(winner_index(&heads)?);
+    let mut losers: Vec<FieldWrite> = heads
+        .into_iter()
+        .filter(|head| head.value != winner.value)
+        .collect();
+    if losers.is_empty() {
+        return None;
+    }
+    losers.sort_by(|a, b| {
+        b.wall_ts_us
+            .cmp(&a.wall_ts_us)
+            .then_with(|| b.agent.cmp(&a.agent))
+            .then_with(|| b.event_hash.cmp(&a.event_hash))
+    });
+    Some(FieldConflict {
+        item_id: item_id.to_string(),
+        field: field.to_string(),
+        winner,
+        losers,
+    })
+}
+
+/// List unresolved conflicts, for one item or for every live item.
+///
+/// # Errors
+///
+/// Returns an error if a projection query fails.
+pub fn list_conflicts(conn: &Connection, item_id: Option<&str>) -> Result<Vec<FieldConflict>> {
+    let mut stmt = conn.prepare(
+        "SELECT h.item_id, h.field FROM item_field_heads h
+         JOIN items i ON i.item_id = h.item_id
+         WHERE i.is_deleted = 0 AND (?1 IS NULL OR h.item_id = ?1)
+         GROUP BY h.item_id, h.field
+         HAVING COUNT(*) > 1
+         ORDER BY h.item_id, h.field",
+    )?;
+    let fields = stmt
+        .query_map(params![item_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .context("list conflicted fields")?;
+
+    let mut conflicts = Vec::new();
+    for (item_id, field) in fields {
+        let heads = field_heads(conn, &item_id, &field)?;
+        conflicts.extend(conflict_from_heads(&item_id, &field, heads));
+    }
+    Ok(conflicts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrations;
+    use crate::db::project::{Projector, ensure_tracking_table};
+    use crate::event::data::{CreateData, UpdateData};
+    use crate::event::types::EventType;
+    use crate::model::item::{Kind, Urgency};
+    use crate::model::item_id::ItemId;
+    use std::collections::BTreeMap;
+
+    fn test_db() -> Connection {
+        let mut conn = Connection::open_in_memory().expect("open in-memory db");
+        migrations::migrate(&mut conn).expect("migrate");
+        ensure_tracking_table(&conn).expect("tracking table");
+        conn
+    }
+
+    /// A stamp for `agent` after `n` local events. Different agents are
+    /// concurrent; the same agent's stamps are totally ordered.
+    fn itc(agent: &str, n: u32) -> String {
+        let (left, right) = Stamp::seed().fork();
+        let mut stamp = if agent == "alice" { left } else { right };
+        for _ in 0..n {
+            stamp.event();
+        }
+        crate::clock::text::stamp_to_text(&stamp)
+    }
+
+    fn event(agent: &str, n: u32, ts: i64, hash: &str, parents: &[&str], data: EventData) -> Event {
+        Event {
+            wall_ts_us: ts,
+            agent: agent.to_string(),
+            itc: itc(agent, n),
+            parents: parents.iter().map(ToString::to_string).collect(),
+            event_type: match data {
+                EventData::Create(_) => EventType::Create,
+                _ => EventType::Update,
+            },
+            item_id: ItemId::new_unchecked("bn-c1"),
+            data,
+            event_hash: hash.to_string(),
+            signature: None,
+        }
+    }
+
+    fn create(hash: &str) -> Event {
+        event(
+            "alice",
+            1,
+            1_000,
+            hash,
+            &[],
+            EventData::Create(CreateData {
+                title: "Original".into(),
+                kind: Kind::Task,
+                size: None,
+                urgency: Urgency::Default,
+                labels: vec![],
+                parent: None,
+                causation: None,
+                description: None,
+                extra: BTreeMap::new(),
+            }),
+        )
+    }
+
+    fn set(field: &str, value: &str) -> EventData {
+        EventData::Update(UpdateData {
+            field: field.into(),
+            value: Value::from(value),
+            extra: BTreeMap::new(),
+        })
+    }
+
+    fn project(conn: &Connection, events: &[Event]) {
+        let projector = Projector::new(conn);
+        for event in events {
+            projector.project_event(event).expect("project");
+        }
+    }
+
+    #[test]
+    fn lww_writes_cover_create_fields_and_skip_labels() {
+        let fields: Vec<String> = lww_writes(&create("blake3:c"))
+            .into_iter()
+            .map(|(field, _)| field)
+            .collect();
+        assert_eq!(fields, ["title", "kind", "urgency"]);
+
+        let labels = event("alice", 2, 2_000, "blake3:l", &[], set("labels", "x"));
+        assert!(lww_writes(&labels).is_empty());
+        assert!(is_lww_field("custom.component"));
+        assert!(!is_lww_field("labels"));
+    }
+
+    #[test]
+    fn sequential_writes_leave_no_conflict() {
+        let conn = test_db();
+        project(
+            &conn,
+            &[
+                create("blake3:c"),
+                event("alice", 2, 2_000, "blake3:a", &[], set("title", "Mine")),
+                // Bob saw Alice's write, so he lists it as a parent.
+                event(
+                    "bob",
+                    1,
+                    3_000,
+                    "blake3:b",
+                    &["blake3:a"],
+                    set("title", "Ours"),
+                ),
+            ],
+        );
+
+        let heads = field_heads(&conn, "bn-c1", "title").unwrap();
+        assert_eq!(heads.len(), 1);
+        assert_eq!(heads[0].value, Value::from("Ours"));
+        assert!(list_conflicts(&conn, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn concurrent_writes_report_winner_and_loser() {
+        let conn = test_db();
+        project(
+            &conn,
+            &[
+                create("blake3:c"),
+                event(
+                    "alice",
+                    2,
+                    2_000,
+                    "blake3:a",
+                    &["blake3:c"],
+                    set("title", "A"),
+                ),
+                event(
+                    "bob",
+                    1,
+                    3_000,
+                    "blake3:b",
+                    &["blake3:c"],
+                    set("title", "B"),
+                ),
+            ],
+        );
+
+        let conflicts = list_conflicts(&conn, Some("bn-c1")).unwrap();
+        assert_eq!(conflicts.len(), 1);
+        let conflict = &conflicts[0];
+        assert_eq!(conflict.field, "title");
+        assert_eq!(conflict.winner.agent, "bob");
+        assert_eq!(conflict.winner.value, Value::from("B"));
+        assert_eq!(conflict.losers.len(), 1);
+        assert_eq!(conflict.losers[0].agent, "alice");
+        assert_eq!(conflict.losers[0].value, Value::from("A"));
+    }
+
+    #[test]
+    fn write_observing_all_heads_resolves_conflict() {
+        let conn = test_db();
+        project(
+            &conn,
+            &[
+                create("blake3:c"),
+                event(
+                    "alice",
+                    2,
+                    2_000,
+                    "blake3:a",
+                    &["blake3:c"],
+                    set("urgency", "urgent"),
+                ),
+                event(
+                    "bob",
+                    1,
+                    3_000,
+                    "blake3:b",
+                    &["blake3:c"],
+                    set("urgency", "punt"),
+                ),
+            ],
+        );
+        assert_eq!(list_conflicts(&conn, None).unwrap().len(), 1);
+
+        let parents = observed_parents(&conn, "bn-c1", "urgency").unwrap();
+        let parents: Vec<&str> = parents.iter().map(String::as_str).collect();
+        project(
+            &conn,
+            &[event(
+                "bob",
+                2,
+                4_000,
+                "blake3:r",
+                &parents,
+                set("urgency", "urgent"),
+            )],
+        );
+        assert!(list_conflicts(&conn, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn concurrent_equal_values_are_not_reported() {
+        let conn = test_db();
+        project(
+            &conn,
+            &[
+                create("blake3:c"),
+                event(
+                    "alice",
+                    2,
+                    2_000,
+                    "blake3:a",
+                    &["blake3:c"],
+                    set("size", "m"),
+                ),
+                event("bob", 1, 3_000, "blake3:b", &["blake3:c"], set("size", "m")),
+            ],
+        );
+        assert_eq!(field_heads(&conn, "bn-c1", "size").unwrap().len(), 2);
+        assert!(list_conflicts(&conn, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn scrub_heads_hides_redacted_values() {
+        let conn = test_db();
+        project(
+            &conn,
+            &[
+                create("blake3:c"),
+                event(
+                    "alice",
+                    2,
+                    2_000,
+                    "blake3:a",
+                    &["blake3:c"],
+                    set("title", "secret"),
+                ),
+                event(
+                    "bob",
+                    1,
+                    3_000,
+                    "blake3:b",
+                    &["blake3:c"],
+                    set("title", "B"),
+                ),
+            ],
+        );
+
+        assert_eq!(scrub_heads(&conn, "blake3:a").unwrap(), 1);
+        assert_eq!(scrub_heads(&conn, "blake3:a").unwrap(), 0);
+        let conflict = &list_conflicts(&conn, None).unwrap()[0];
+        assert_eq!(conflict.losers[0].value, Value::from(REDACTED_PLACEHOLDER));
+    }
+}
@@ -4,10 +4,21 @@
 //! materializes and the CLI displays. Each field delegates to the appropriate
 //! CRDT primitive:
 //!
-//! - **LWW** ([`LwwRegister<T>`]): title, description, kind, size, urgency, parent
+//! - **LWW** ([`LwwRegister<T>`]): title, description, kind, size, urgency, parent, due
+//! - **LWW map** (`BTreeMap<String, LwwRegister<_>>`): custom fields, one
+//!   register per field name
+//! - **Sequence** ([`TextSeq`]): character edits to the description, keyed by
+//!   the hash of the description write they apply to
 //! - **OR-Set** ([`OrSet<String>`]): assignees, labels, `blocked_by`, `related_to`
-//! - **G-Set** ([`GSet<String>`]): comments (event hashes referencing comment content)
+//! - **G-Set** ([`GSet<String>`]): comments (event hashes referencing comment
+//!   content) and retracted comment hashes
+//! - **LWW map** (`BTreeMap<String, LwwRegister<String>>`): comment edits, one
+//!   register per comment hash
+//! - **Grow-only map** (`BTreeMap<String, AttachmentRef>`): attachments keyed
+//!   by the hash of their `item.attach` event
 //! - **Epoch+Phase** ([`EpochPhaseState`]): lifecycle state
+//! - **LWW** ([`LwwRegister<Option<SubState>>`]): configured sub-state,
+//!   visible only while the lifecycle phase matches the one it was set in
 //! - **LWW<bool>** ([`LwwRegister<bool>`]): soft-delete flag
 //!
 //! # Merge Semantics
@@ -44,7 +55,7 @@
     clippy::match_same_arms
 )]
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
 use crate::clock::itc::Stamp;
 use crate::clock::text::stamp_from_text;
@@ -53,9 +64,12 @@ use crate::crdt::gset::GSet;
 use crate::crdt::lww::LwwRegister;
 use crate::crdt::merge::Merge;
 use crate::crdt::state::{EpochPhaseState, Phase};
+use crate::crdt::text::TextSeq;
 use crate::event::Event;
 use crate::event::data::{AssignAction, EventData};
 use crate::event::types::EventType;
+use crate::model::custom::custom_field_name;
+use crate::model::due::{DUE_FIELD, due_from_value};
 use crate::model::item::{Kind, Size, State, Urgency};
 
 use super::Timestamp;
@@ -78,12 +92,24 @@ pub struct WorkItemState {
     pub kind: LwwRegister<Kind>,
     /// Lifecycle state (epoch+phase CRDT).
     pub state: EpochPhaseState,
+    /// Workflow sub-state written by the latest `item.move` (LWW register,
+    /// None = no sub-state). See [`WorkItemState::substate`].
+    pub substate: LwwRegister<Option<SubState>>,
     /// T-shirt size estimate (LWW register, None encoded as Size::M default).
     pub size: LwwRegister<Option<Size>>,
     /// Priority/urgency override (LWW register).
     pub urgency: LwwRegister<Urgency>,
     /// Parent item ID (LWW register, empty string = no parent).
     pub parent: LwwRegister<String>,
+    /// Due date in wall-clock microseconds (LWW register, None = no deadline).
+    pub due: LwwRegister<Option<u64>>,
+    /// Custom field values keyed by field name (per-key LWW registers,
+    /// None = cleared).
+    pub custom: BTreeMap<String, LwwRegister<Option<serde_json::Value>>>,
+    /// Sequence edits from `item.patch`, keyed by the hash of the
+    /// description write they apply to. Only the entry matching
+    /// `description.event_hash` is visible.
+    pub description_edits: BTreeMap<String, DescriptionEdits>,
     /// Assigned agents (OR-Set, add-wins).
     pub assignees: OrSet<String>,
     /// Labels (OR-Set, add-wins).
@@ -94,6 +120,12 @@ pub struct WorkItemState {
     pub related_to: OrSet<String>,
     /// Comment event hashes (G-Set, grow-only).
     pub comments: GSet<String>,
+    /// Edited comment bodies keyed by comment hash (per-key LWW registers).
+    pub comment_edits: BTreeMap<String, LwwRegister<String>>,
+    /// Retracted comment hashes (G-Set, grow-only).
+    pub retracted_comments: GSet<String>,
+    /// Attachments keyed by `item.attach` event hash (grow-only map).
+    pub attachments: BTreeMap<String, AttachmentRef>,
     /// Soft-delete flag (LWW register).
     pub deleted: LwwRegister<bool>,
     /// Wall-clock timestamp of the earliest event (for created_at).
@@ -157,11 +189,30 @@ impl WorkItemState {
                 zero_agent.clone(),
                 zero_hash.clone(),
             ),
+            due: LwwRegister::new(
+                None,
+                zero_stamp.clone(),
+                zero_ts,
+                zero_agent.clone(),
+                zero_hash.clone(),
+            ),
+            substate: LwwRegister::new(
+                None,
+                zero_stamp.clone(),
+                zero_ts,
+                zero_agent.clone(),
+                zero_hash.clone(),
+            ),
+            custom: BTreeMap::new(),
+            description_edits: BTreeMap::new(),
             assignees: OrSet::new(),
             labels: OrSet::new(),
             blocked_by: OrSet::new(),
             related_to: OrSet::new(),
             comments: GSet::new(),
+            comment_edits: BTreeMap::new(),
+            retracted_comments: GSet::new(),
+            attachments: BTreeMap::new(),
             deleted: LwwRegister::new(false, zero_stamp, zero_ts, zero_agent, zero_hash),
             created_at: 0,
             updated_at: 0,
@@ -181,6 +232,28 @@ impl WorkItemState {
         self.size.merge(&other.size);
         self.urgency.merge(&other.urgency);
         self.parent.merge(&other.parent);
+        self.due.merge(&other.due);
+        self.substate.merge(&other.substate);
+
+        // Custom fields: per-key LWW merge; keys only on one side are copied.
+        for (name, register) in &other.custom {
+            match self.custom.get_mut(name) {
+                Some(existing) => existing.merge(register),
+                None => {
+                    self.custom.insert(name.clone(), register.clone());
+                }
+            }
+        }
+
+        // Description edits: per-base sequence union.
+        for (base, edits) in &other.description_edits {
+            match self.description_edits.get_mut(base) {
+                Some(existing) => existing.merge(edits),
+                None => {
+                    self.description_edits.insert(base.clone(), edits.clone());
+                }
+            }
+        }
 
         // OR-Sets: merge via set union (takes ownership of clone)
         self.assignees.merge(other.assignees.clone());
@@ -190,6 +263,26 @@ impl WorkItemState {
 
         // G-Set: merge via set union
         self.comments.merge(other.comments.clone());
+        self.retracted_comments
+            .merge(other.retracted_comments.clone());
+
+        // Comment edits: per-comment LWW merge.
+        for (comment, register) in &other.comment_edits {
+            match self.comment_edits.get_mut(comment) {
+                Some(existing) => existing.merge(register),
+                None => {
+                    self.comment_edits.insert(comment.clone(), register.clone());
+                }
+            }
+        }
+
+        // Attachments: grow-only union. Keys are event hashes, so the same
+        // key always carries the same reference.
+        for (hash, attachment) in &other.attachments {
+            self.attachments
+                .entry(hash.clone())
+                .or_insert_with(|| attachment.clone());
+        }
 
         // Deleted: LWW merge
         self.deleted.merge(&other.deleted);
@@ -340,6 +433,10 @@ impl WorkItemState {
                             self.parent =
                                 LwwRegister::new(parent, stamp, wall_ts, agent_id, event_hash);
                         }
+                        DUE_FIELD => {
+                            let due = due_from_value(&data.value);
+                            self.due = LwwRegister::new(due, stamp, wall_ts, agent_id, event_hash);
+                        }
                         "labels" => {
                             // Labels update via OR-Set add/remove encoded in value.
                             if let Some(obj) = data.value.as_object() {
@@ -370,7 +467,17 @@ impl WorkItemState {
                                 }
                             }
                         }
-                        _ => {} // Unknown field — no-op.
+                        field => {
+                            // Custom fields get a register per name; any
+                            // other unknown field is a no-op.
+                            if let Some(name) = custom_field_name(field) {
+                                let value = (!data.value.is_null()).then(|| data.value.clone());
+                                self.custom.insert(
+                                    name.to_string(),
+                                    LwwRegister::new(value, stamp, wall_ts, agent_id, event_hash),
+                                );
+                            }
+                        }
                     }
                 }
             }
@@ -380,6 +487,12 @@ impl WorkItemState {
                     // Map the model::item::State to crdt::state::Phase.
                     let target_phase = state_to_phase(data.state);
                     apply_phase_transition(&mut self.state, target_phase);
+                    let substate = data.substate.clone().map(|name| SubState {
+                        phase: target_phase,
+                        name,
+                    });
+                    self.substate =
+                        LwwRegister::new(substate, stamp, wall_ts, agent_id, event_hash);
                 }
             }
 
@@ -404,6 +517,39 @@ impl WorkItemState {
                 }
             }
 
+            EventType::CommentEdit => {
+                if let EventData::CommentEdit(data) = &event.data {
+                    let edit =
+                        LwwRegister::new(data.body.clone(), stamp, wall_ts, agent_id, event_hash);
+                    match self.comment_edits.get_mut(&data.comment) {
+                        Some(existing) => existing.merge(&edit),
+                        None => {
+                            self.comment_edits.insert(data.comment.clone(), edit);
+                        }
+                    }
+                }
+            }
+
+            EventType::CommentRetract => {
+                if let EventData::CommentRetract(data) = &event.data {
+                    self.retracted_comments.insert(data.comment.clone());
+                }
+            }
+
+            EventType::Attach => {
+                if let EventData::Attach(data) = &event.data {
+                    self.attachments.insert(
+                        event.event_hash.clone(),
+                        AttachmentRef {
+                            blob: data.blob.clone(),
+                            name: data.name.clone(),
+                            mime: data.mime.clone(),
+                            size: data.size,
+                        },
+                    );
+                }
+            }
+
             EventType::Link => {
                 if let EventData::Link(data) = &event.data {
                     let tag = make_orset_tag(wall_ts, &agent_id, &event_hash, &data.target);
@@ -467,6 +613,54 @@ impl WorkItemState {
                 // Redaction targets a prior event — handled at the projection
                 // level by filtering event hashes. No CRDT state change.
             }
+
+            EventType::Patch => {
+                if let EventData::Patch(data) = &event.data {
+                    let latest = LwwRegister::new((), stamp, wall_ts, agent_id, event_hash);
+                    let edits = self
+                        .description_edits
+                        .entry(data.base.clone())
+                        .or_insert_with(|| DescriptionEdits {
+                            seq: TextSeq::new(),
+                            latest: latest.clone(),
+                        });
+                    for op in &data.ops {
+                        edits.seq.apply(op);
+                    }
+                    edits.latest.merge(&latest);
+                }
+            }
+        }
+    }
+
+    /// Return the visible description: the winning description write with
+    /// any sequence edits made against it.
+    pub fn description_text(&self) -> String {
+        self.description_edits
+            .get(&self.description.event_hash)
+            .map_or_else(
+                || self.description.value.clone(),
+                |edits| edits.seq.render(&self.description.value),
+            )
+    }
+
+    /// Fold sequence edits into a plain description register.
+    ///
+    /// The result carries the rendered text and the clock of the latest
+    /// patch, which causally follows the base write. A state rebuilt from
+    /// the folded register has no edits and renders the same text; patches
+    /// made against the old base that were not observed at fold time are
+    /// superseded, exactly as a concurrent whole-value write would be.
+    pub fn fold_description(&self) -> LwwRegister<String> {
+        match self.description_edits.get(&self.description.event_hash) {
+            Some(edits) if !edits.seq.is_empty() => LwwRegister::new(
+                edits.seq.render(&self.description.value),
+                edits.latest.stamp.clone(),
+                edits.latest.wall_ts,
+                edits.latest.agent_id.clone(),
+                edits.latest.event_hash.clone(),
+            ),
+            _ => self.description.clone(),
         }
     }
 
@@ -485,6 +679,18 @@ impl WorkItemState {
         self.state.epoch
     }
 
+    /// Return the current workflow sub-state, if any.
+    ///
+    /// A sub-state only applies to the phase it was set in, so a concurrent
+    /// move to another phase (which wins the epoch/phase merge) hides it.
+    pub fn substate(&self) -> Option<&str> {
+        self.substate
+            .value
+            .as_ref()
+            .filter(|sub| sub.phase == self.state.phase)
+            .map(|sub| sub.name.as_str())
+    }
+
     /// Return the set of current assignee names.
     pub fn assignee_names(&self) -> HashSet<&String> {
         self.assignees.values()
@@ -509,6 +715,26 @@ impl WorkItemState {
     pub const fn comment_hashes(&self) -> &HashSet<String> {
         &self.comments.elements
     }
+
+    /// Return the winning edited body of a comment, if it was edited.
+    pub fn comment_body_edit(&self, comment_hash: &str) -> Option<&str> {
+        self.comment_edits
+            .get(comment_hash)
+            .map(|edit| edit.value.as_str())
+    }
+
+    /// Check if a comment has been retracted.
+    pub fn is_comment_retracted(&self, comment_hash: &str) -> bool {
+        self.retracted_comments.elements.contains(comment_hash)
+    }
+
+    /// Return the blob hashes referenced by this item's attachments.
+    pub fn attachment_blobs(&self) -> HashSet<&str> {
+        self.attachments
+            .values()
+            .map(|attachment| attachment.blob.as_str())
+            .collect()
+    }
 }
 
 impl Default for WorkItemState {
@@ -517,6 +743,57 @@ impl Default for WorkItemState {
     }
 }
 
+// ---------------------------------------------------------------------------
+// AttachmentRef
+// ---------------------------------------------------------------------------
+
+/// Reference to a blob attached by an `item.attach` event.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AttachmentRef {
+    /// BLAKE3 hash of the blob in `.bones/blobs/`.
+    pub blob: String,
+    /// File name shown to users.
+    pub name: String,
+    /// MIME type.
+    pub mime: String,
+    /// Size in bytes.
+    pub size: u64,
+}
+
+// ---------------------------------------------------------------------------
+// SubState
+// ---------------------------------------------------------------------------
+
+/// Configured workflow sub-state, tagged with the phase it belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SubState {
+    /// Phase the sub-state maps onto.
+    pub phase: Phase,
+    /// Sub-state name from `[states]` in the project config.
+    pub name: String,
+}
+
+// ---------------------------------------------------------------------------
+// DescriptionEdits
+// ---------------------------------------------------------------------------
+
+/// Sequence edits applied to one description write.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescriptionEdits {
+    /// Character-level edits on top of the base description.
+    pub seq: TextSeq,
+    /// Clock metadata of the winning (latest) patch, used when folding.
+    pub latest: LwwRegister<()>,
+}
+
+impl DescriptionEdits {
+    /// Merge another set of edits to the same base.
+    pub fn merge(&mut self, other: &Self) {
+        self.seq.merge(&other.seq);
+        self.latest.merge(&other.latest);
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -557,7 +834,7 @@ fn apply_phase_transition(state: &mut EpochPhaseState, target: Phase) {
 /// Older events may carry non-decodable legacy ITC text. In that case,
 /// we preserve deterministic replay by deriving a stable fallback stamp
 /// from `event_hash`.
-fn derive_stamp_from_hash(event_hash: &str) -> Stamp {
+pub(crate) fn derive_stamp_from_hash(event_hash: &str) -> Stamp {
     use std::hash::{Hash, Hasher};
     let mut hasher = std::collections::hash_map::DefaultHasher::new();
     event_hash.hash(&mut hasher);
@@ -645,6 +922,7 @@ mod tests {
             item_id: ItemId::new_unchecked("bn-test1"),
             data,
             event_hash: event_hash.to_string(),
+            signature: None,
         }
     }
 
@@ -688,6 +966,7 @@ mod tests {
             EventData::Move(MoveData {
                 state,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             wall_ts,
@@ -721,6 +1000,7 @@ mod tests {
             EventType::Comment,
             EventData::Comment(CommentData {
                 body: body.to_string(),
+                reply_to: None,
                 extra: BTreeMap::new(),
             }),
             wall_ts,
@@ -937,6 +1217,223 @@ mod tests {
         assert_eq!(state.size.value, Some(Size::Xl));
     }
 
+    #[test]
+    fn apply_update_due_sets_and_clears() {
+        let mut state = WorkItemState::new();
+        let set = make_event(
+            EventType::Update,
+            EventData::Update(UpdateData {
+                field: "due".to_string(),
+                value: serde_json::Value::String("2026-03-15T23:59:59Z".to_string()),
+                extra: BTreeMap::new(),
+            }),
+            2000,
+            "alice",
+            "blake3:u5",
+        );
+        state.apply_event(&set);
+        assert_eq!(state.due.value, Some(1_773_619_199_000_000));
+
+        let clear = make_event(
+            EventType::Update,
+            EventData::Update(UpdateData {
+                field: "due".to_string(),
+                value: serde_json::Value::Null,
+                extra: BTreeMap::new(),
+            }),
+            3000,
+            "alice",
+            "blake3:u6",
+        );
+        state.apply_event(&clear);
+        assert_eq!(state.due.value, None);
+    }
+
+    #[test]
+    fn apply_update_custom_field_sets_and_clears() {
+        let update = |value: serde_json::Value, ts: i64, hash: &str| {
+            make_event(
+                EventType::Update,
+                EventData::Update(UpdateData {
+                    field: "custom.severity".to_string(),
+                    value,
+                    extra: BTreeMap::new(),
+                }),
+                ts,
+                "alice",
+                hash,
+            )
+        };
+
+        let mut state = WorkItemState::new();
+        state.apply_event(&update(serde_json::json!(3), 2000, "blake3:c1"));
+        assert_eq!(state.custom["severity"].value, Some(serde_json::json!(3)));
+
+        state.apply_event(&update(serde_json::Value::Null, 3000, "blake3:c2"));
+        assert_eq!(state.custom["severity"].value, None);
+    }
+
+    #[test]
+    fn merge_custom_fields_per_key() {
+        let update = |field: &str, value: &str, ts: i64, agent: &str, hash: &str| {
+            make_event(
+                EventType::Update,
+                EventData::Update(UpdateData {
+                    field: field.to_string(),
+                    value: serde_json::json!(value),
+                    extra: BTreeMap::new(),
+                }),
+                ts,
+                agent,
+                hash,
+            )
+        };
+
+        let mut a = WorkItemState::new();
+        a.apply_event(&update(
+            "custom.component",
+            "api",
+            1000,
+            "alice",
+            "blake3:a1",
+        ));
+        a.apply_event(&update(
+            "custom.customer",
+            "acme",
+            1500,
+            "alice",
+            "blake3:a2",
+        ));
+
+        let mut b = WorkItemState::new();
+        b.apply_event(&update("custom.component", "cli", 2000, "bob", "blake3:b1"));
+
+        let mut ab = a.clone();
+        ab.merge(&b);
+        let mut ba = b.clone();
+        ba.merge(&a);
+
+        assert!(states_equal(&ab, &ba));
+        assert_eq!(ab.custom["component"].value, Some(serde_json::json!("cli")));
+        assert_eq!(ab.custom["customer"].value, Some(serde_json::json!("acme")));
+    }
+
+    fn patch_event(
+        state: &WorkItemState,
+        target: &str,
+        wall_ts: i64,
+        agent: &str,
+        hash: &str,
+    ) -> Event {
+        let base = &state.description;
+        let seq = state
+            .description_edits
+            .get(&base.event_hash)
+            .map(|e| e.seq.clone())
+            .unwrap_or_default();
+        make_event(
+            EventType::Patch,
+            EventData::Patch(PatchData {
+                base: base.event_hash.clone(),
+                ops: seq.diff(&base.value, target, agent),
+                extra: BTreeMap::new(),
+            }),
+            wall_ts,
+            agent,
+            hash,
+        )
+    }
+
+    #[test]
+    fn apply_patch_edits_description() {
+        let mut state = WorkItemState::new();
+        state.apply_event(&create_event("T", 1000, "alice", "blake3:c1"));
+        let patch = patch_event(&state, "A longer description", 2000, "alice", "blake3:p1");
+        state.apply_event(&patch);
+
+        assert_eq!(state.description.value, "A description");
+        assert_eq!(state.description_text(), "A longer description");
+    }
+
+    #[test]
+    fn concurrent_patches_interleave_on_merge() {
+        let mut base = WorkItemState::new();
+        base.apply_event(&create_event("T", 1000, "alice", "blake3:c1"));
+
+        let mut a = base.clone();
+        a.apply_event(&patch_event(
+            &base,
+            "A short description",
+            2000,
+            "alice",
+            "blake3:pa",
+        ));
+        let mut b = base.clone();
+        b.apply_event(&patch_event(
+            &base,
+            "A description.",
+            2001,
+            "bob",
+            "blake3:pb",
+        ));
+
+        let mut ab = a.clone();
+        ab.merge(&b);
+        let mut ba = b.clone();
+        ba.merge(&a);
+
+        assert!(states_equal(&ab, &ba));
+        assert_eq!(ab.description_text(), "A short description.");
+    }
+
+    #[test]
+    fn whole_value_update_supersedes_patches() {
+        let mut state = WorkItemState::new();
+        state.apply_event(&create_event("T", 1000, "alice", "blake3:c1"));
+        state.apply_event(&patch_event(
+            &state,
+            "A patched one",
+            2000,
+            "bob",
+            "blake3:p1",
+        ));
+        state.apply_event(&make_event(
+            EventType::Update,
+            EventData::Update(UpdateData {
+                field: "description".to_string(),
+                value: serde_json::json!("Rewritten"),
+                extra: BTreeMap::new(),
+            }),
+            3000,
+            "carol",
+            "blake3:u1",
+        ));
+
+        assert_eq!(state.description_text(), "Rewritten");
+    }
+
+    #[test]
+    fn fold_description_renders_and_dominates_base() {
+        let mut state = WorkItemState::new();
+        state.apply_event(&create_event("T", 1000, "alice", "blake3:c1"));
+        assert_eq!(state.fold_description(), state.description);
+
+        state.apply_event(&patch_event(
+            &state,
+            "A description!",
+            2000,
+            "bob",
+            "blake3:p1",
+        ));
+        let folded = state.fold_description();
+        assert_eq!(folded.value, "A description!");
+        assert_eq!(folded.event_hash, "blake3:p1");
+
+        let mut merged = state.clone();
+        merged.description.merge(&folded);
+        assert_eq!(merged.description_text(), "A description!");
+    }
+
     #[test]
     fn apply_update_urgency() {
         let mut state = WorkItemState::new();
@@ -1045,6 +1542,43 @@ mod tests {
         assert_eq!(state.epoch(), 1);
     }
 
+    #[test]
+    fn apply_move_substate_follows_phase() {
+        let review = |wall_ts, hash| {
+            make_event(
+                EventType::Move,
+                EventData::Move(MoveData {
+                    state: State::Doing,
+                    substate: Some("review".into()),
+                    reason: None,
+                    extra: BTreeMap::new(),
+                }),
+                wall_ts,
+                "alice",
+                hash,
+            )
+        };
+
+        let mut state = WorkItemState::new();
+        state.apply_event(&move_event(State::Doing, 1000, "alice", "blake3:m1"));
+        state.apply_event(&review(2000, "blake3:m2"));
+        assert_eq!(state.phase(), Phase::Doing);
+        assert_eq!(state.substate(), Some("review"));
+
+        // A plain move back to doing clears the sub-state.
+        state.apply_event(&move_event(State::Doing, 3000, "alice", "blake3:m3"));
+        assert_eq!(state.substate(), None);
+
+        // A concurrent close wins the phase merge and hides the sub-state.
+        let mut a = WorkItemState::new();
+        a.apply_event(&review(4000, "blake3:a1"));
+        let mut b = WorkItemState::new();
+        b.apply_event(&move_event(State::Done, 3500, "bob", "blake3:b1"));
+        a.merge(&b);
+        assert_eq!(a.phase(), Phase::Done);
+        assert_eq!(a.substate(), None);
+    }
+
     // -----------------------------------------------------------------------
     // Event application: Assign
     // -----------------------------------------------------------------------
@@ -1389,11 +1923,18 @@ mod tests {
             && a.size.value == b.size.value
             && a.urgency.value == b.urgency.value
             && a.parent.value == b.parent.value
+            && a.due.value == b.due.value
+            && a.substate.value == b.substate.value
+            && a.custom == b.custom
+            && a.description_edits == b.description_edits
             && a.assignees == b.assignees
             && a.labels == b.labels
             && a.blocked_by == b.blocked_by
             && a.related_to == b.related_to
             && a.comments == b.comments
+            && a.comment_edits == b.comment_edits
+            && a.retracted_comments == b.retracted_comments
+            && a.attachments == b.attachments
             && a.deleted.value == b.deleted.value
             && a.created_at == b.created_at
             && a.updated_at == b.updated_at
@@ -980,6 +980,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: "hash".into(),
+            signature: None,
         };
 
         // Pass base_state so it knows what to remove.
@@ -1032,6 +1033,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: hash.into(),
+            signature: None,
         };
 
         dag.insert(make_evt(root_hash, vec![]));
@@ -1053,6 +1055,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: add_hash.into(),
+            signature: None,
         };
 
         // 2. Remove "alice" (concurrent)
@@ -1069,6 +1072,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: remove_hash.into(),
+            signature: None,
         };
 
         // Replay order: Add then Remove (linearized)
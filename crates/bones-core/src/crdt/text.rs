@@ -0,0 +1,689 @@
+//! Replicated text sequence (RGA) for collaborative description edits.
+//!
+//! A [`TextSeq`] records character-level edits made on top of a *base*
+//! string — the description value written by an `item.create` or
+//! `item.update` event. Every inserted character has a unique [`TextId`]
+//! and remembers the character it was typed after (its *origin*); deletes
+//! only tombstone ids. Rendering rebuilds the RGA tree, so concurrent
+//! inserts at different places both survive and concurrent inserts at the
+//! same place are ordered deterministically.
+//!
+//! Base characters get implicit ids `(k + 1, "")` for character index `k`,
+//! which means the base text itself never has to be stored in the sequence.
+//!
+//! # Merge Semantics
+//!
+//! The state is a pair of grow-only sets (runs and tombstones), so merge is
+//! set union and trivially commutative, associative, and idempotent. Ops can
+//! be applied in any order: an insert whose origin is unknown is rendered at
+//! the start of the text until its origin arrives.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Edit distance beyond which [`TextSeq::diff`] stops searching for a minimal
+/// script and replaces the whole changed region instead.
+const MAX_DIFF_DISTANCE: isize = 1_000;
+
+/// Upper bound on a single delete span. No description can be longer than
+/// an event payload, so larger spans are malformed and are clamped rather
+/// than allowed to allocate unbounded tombstones.
+const MAX_DELETE_SPAN: u64 = 1 << 20;
+
+// ---------------------------------------------------------------------------
+// TextId
+// ---------------------------------------------------------------------------
+
+/// Identifier of a single character in a [`TextSeq`].
+///
+/// Ordered by counter, then agent. On the wire it is the string
+/// `"<counter>:<agent>"`; base characters have an empty agent (`"3:"`).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TextId {
+    /// Lamport-style counter, unique per agent.
+    pub counter: u64,
+    /// Agent that typed the character (empty for base text).
+    pub agent: String,
+}
+
+impl TextId {
+    /// Create an id from a counter and agent.
+    pub fn new(counter: u64, agent: impl Into<String>) -> Self {
+        Self {
+            counter,
+            agent: agent.into(),
+        }
+    }
+
+    /// Id of the base character at char index `index`.
+    #[must_use]
+    pub fn base(index: usize) -> Self {
+        Self::new(index as u64 + 1, String::new())
+    }
+
+    fn offset(&self, delta: u64) -> Self {
+        Self::new(self.counter.saturating_add(delta), self.agent.clone())
+    }
+}
+
+impl fmt::Display for TextId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.counter, self.agent)
+    }
+}
+
+/// Error returned when a string is not a valid [`TextId`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseTextIdError(pub String);
+
+impl fmt::Display for ParseTextIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid text id '{}': expected <counter>:<agent>",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseTextIdError {}
+
+impl FromStr for TextId {
+    type Err = ParseTextIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (counter, agent) = s
+            .split_once(':')
+            .ok_or_else(|| ParseTextIdError(s.to_string()))?;
+        let counter = counter
+            .parse::<u64>()
+            .ok()
+            .filter(|c| *c > 0)
+            .ok_or_else(|| ParseTextIdError(s.to_string()))?;
+        Ok(Self::new(counter, agent))
+    }
+}
+
+impl Serialize for TextId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TextId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// TextOp
+// ---------------------------------------------------------------------------
+
+/// A single edit carried by an `item.patch` event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum TextOp {
+    /// Insert `text` after the character `after` (or at the start when
+    /// `None`). Character `i` of `text` gets id `id.counter + i`.
+    Insert {
+        id: TextId,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        after: Option<TextId>,
+        text: String,
+    },
+    /// Delete `len` characters with consecutive counters starting at `start`.
+    Delete { start: TextId, len: u64 },
+}
+
+// ---------------------------------------------------------------------------
+// TextSeq
+// ---------------------------------------------------------------------------
+
+/// A run of characters inserted by one [`TextOp::Insert`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct TextRun {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<TextId>,
+    pub text: String,
+}
+
+/// Character-level edits applied on top of a base string.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextSeq {
+    /// Inserted runs keyed by the id of their first character.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub runs: BTreeMap<TextId, TextRun>,
+    /// Tombstoned character ids (base or inserted).
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub deleted: BTreeSet<TextId>,
+}
+
+/// A visible character together with its id.
+type Visible = (TextId, char);
+
+impl TextSeq {
+    /// Create an empty sequence (renders to the base text unchanged).
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if no edits have been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty() && self.deleted.is_empty()
+    }
+
+    /// Apply a single op. Applying the same op twice is a no-op.
+    pub fn apply(&mut self, op: &TextOp) {
+        match op {
+            TextOp::Insert { id, after, text } => {
+                self.insert_run(
+                    id.clone(),
+                    TextRun {
+                        after: after.clone(),
+                        text: text.clone(),
+                    },
+                );
+            }
+            TextOp::Delete { start, len } => {
+                for i in 0..(*len).min(MAX_DELETE_SPAN) {
+                    self.deleted.insert(start.offset(i));
+                }
+            }
+        }
+    }
+
+    /// Merge another sequence into this one (set union).
+    pub fn merge(&mut self, other: &Self) {
+        for (id, run) in &other.runs {
+            self.insert_run(id.clone(), run.clone());
+        }
+        self.deleted.extend(other.deleted.iter().cloned());
+    }
+
+    /// Two runs claiming the same id only happen with malformed input; keep
+    /// the greater one so the outcome does not depend on arrival order.
+    /// Empty runs carry no characters and are dropped.
+    fn insert_run(&mut self, id: TextId, run: TextRun) {
+        if run.text.is_empty() {
+            return;
+        }
+        match self.runs.get_mut(&id) {
+            Some(existing) if *existing >= run => {}
+            Some(existing) => *existing = run,
+            None => {
+                self.runs.insert(id, run);
+            }
+        }
+    }
+
+    /// Smallest counter guaranteed not to collide with any id in the
+    /// sequence or the base text. Empty runs, which only a deserialized
+    /// sequence can hold, are ignored.
+    #[must_use]
+    pub fn next_counter(&self, base: &str) -> u64 {
+        let base_max = base.chars().count() as u64;
+        let run_max = self
+            .runs
+            .iter()
+            .filter_map(|(id, run)| {
+                let last = (run.text.chars().count() as u64).checked_sub(1)?;
+                Some(id.offset(last).counter)
+            })
+            .max()
+            .unwrap_or(0);
+        base_max.max(run_max).saturating_add(1)
+    }
+
+    /// Render the visible text on top of `base`.
+    #[must_use]
+    pub fn render(&self, base: &str) -> String {
+        self.visible(base).into_iter().map(|(_, c)| c).collect()
+    }
+
+    /// Visible characters in document order.
+    fn visible(&self, base: &str) -> Vec<Visible> {
+        // Each character's origin is the previous character of its run, or
+        // the run's anchor for the first character. The first origin wins
+        // if malformed input reuses an id.
+        let mut origins: HashMap<TextId, (Option<TextId>, char)> = HashMap::new();
+        let mut prev: Option<TextId> = None;
+        for (i, c) in base.chars().enumerate() {
+            let id = TextId::base(i);
+            origins.insert(id.clone(), (prev.replace(id), c));
+        }
+        for (start, run) in &self.runs {
+            let mut prev = run.after.clone();
+            for (i, c) in run.text.chars().enumerate() {
+                let id = start.offset(i as u64);
+                origins
+                    .entry(id.clone())
+                    .or_insert_with(|| (prev.clone(), c));
+                prev = Some(id);
+            }
+        }
+
+        // Children ordered ascending so the greatest is popped first. An
+        // origin that never arrived hangs its subtree off the root.
+        let mut children: HashMap<Option<&TextId>, Vec<&TextId>> = HashMap::new();
+        for (id, (origin, _)) in &origins {
+            let parent = origin.as_ref().filter(|o| origins.contains_key(*o));
+            children.entry(parent).or_default().push(id);
+        }
+        for list in children.values_mut() {
+            list.sort_unstable();
+        }
+
+        let mut out = Vec::new();
+        let mut stack: Vec<&TextId> = children.get(&None).cloned().unwrap_or_default();
+        while let Some(id) = stack.pop() {
+            if !self.deleted.contains(id) {
+                out.push((id.clone(), origins[id].1));
+            }
+            if let Some(kids) = children.get(&Some(id)) {
+                stack.extend(kids.iter().copied());
+            }
+        }
+        out
+    }
+
+    /// Compute the ops that turn the current rendering of `base` into
+    /// `target`, attributing inserted characters to `agent`.
+    ///
+    /// Inserts are anchored on the characters the editor saw, so edits
+    /// computed concurrently by different agents interleave on merge.
+    #[must_use]
+    pub fn diff(&self, base: &str, target: &str, agent: &str) -> Vec<TextOp> {
+        let old = self.visible(base);
+        let old_chars: Vec<char> = old.iter().map(|(_, c)| *c).collect();
+        let new_chars: Vec<char> = target.chars().collect();
+
+        let mut ops = Vec::new();
+        let mut counter = self.next_counter(base);
+        let mut deleted: Vec<&TextId> = Vec::new();
+        let mut anchor: Option<&TextId> = None;
+        let mut pending = String::new();
+        let mut pending_anchor: Option<&TextId> = None;
+        let (mut i, mut j) = (0, 0);
+
+        let mut flush = |pending: &mut String, pending_anchor: Option<&TextId>| {
+            if pending.is_empty() {
+                return;
+            }
+            let text = std::mem::take(pending);
+            let len = text.chars().count() as u64;
+            ops.push(TextOp::Insert {
+                id: TextId::new(counter, agent),
+                after: pending_anchor.cloned(),
+                text,
+            });
+            counter += len;
+        };
+
+        for step in edit_script(&old_chars, &new_chars) {
+            match step {
+                Step::Keep | Step::Remove => {
+                    flush(&mut pending, pending_anchor);
+                    if step == Step::Remove {
+                        deleted.push(&old[i].0);
+                    }
+                    anchor = Some(&old[i].0);
+                    i += 1;
+                    if step == Step::Keep {
+                        j += 1;
+                    }
+                }
+                Step::Add => {
+                    if pending.is_empty() {
+                        pending_anchor = anchor;
+                    }
+                    pending.push(new_chars[j]);
+                    j += 1;
+                }
+            }
+        }
+        flush(&mut pending, pending_anchor);
+
+        // Coalesce tombstones into spans of consecutive counters.
+        let mut spans: Vec<(TextId, u64)> = Vec::new();
+        for id in deleted {
+            match spans.last_mut() {
+                Some((start, len))
+                    if start.agent == id.agent && start.counter + *len == id.counter =>
+                {
+                    *len += 1;
+                }
+                _ => spans.push((id.clone(), 1)),
+            }
+        }
+        ops.extend(
+            spans
+                .into_iter()
+                .map(|(start, len)| TextOp::Delete { start, len }),
+        );
+        ops
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Character diff
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Keep,
+    Remove,
+    Add,
+}
+
+/// Edit script from `a` to `b`: common prefix and suffix are kept, the middle
+/// is diffed with Myers' algorithm, falling back to remove-all/add-all when
+/// the edit distance exceeds [`MAX_DIFF_DISTANCE`].
+fn edit_script(a: &[char], b: &[char]) -> Vec<Step> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (mid_a, mid_b) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+
+    let mut steps = vec![Step::Keep; prefix];
+    if let Some(middle) = myers(mid_a, mid_b) {
+        steps.extend(middle);
+    } else {
+        steps.extend(std::iter::repeat_n(Step::Remove, mid_a.len()));
+        steps.extend(std::iter::repeat_n(Step::Add, mid_b.len()));
+    }
+    steps.extend(std::iter::repeat_n(Step::Keep, suffix));
+    steps
+}
+
+/// Myers' O((N+M)·D) shortest edit script. Returns `None` when the distance
+/// exceeds [`MAX_DIFF_DISTANCE`].
+///
+/// Diagonals are signed; every index is offset into `0..` before use.
+#[allow(
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss,
+    clippy::many_single_char_names,
+    clippy::suspicious_operation_groupings
+)]
+fn myers(a: &[char], b: &[char]) -> Option<Vec<Step>> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max_d = (n + m).min(MAX_DIFF_DISTANCE);
+    let off = max_d + 1;
+    let mut v = vec![0isize; (2 * off + 1) as usize];
+    // trace[d] holds v[-d..=d] as it was before round d.
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    for d in 0..=max_d {
+        trace.push(v[(off - d) as usize..=(off + d) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let idx = (off + k) as usize;
+            let mut x = if k == -d || (k != d && v[idx - 1] < v[idx + 1]) {
+                v[idx + 1]
+            } else {
+                v[idx - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[idx] = x;
+            if x >= n && y >= m {
+                return Some(backtrack(&trace, n, m));
+            }
+        }
+    }
+    None
+}
+
+#[allow(
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss,
+    clippy::many_single_char_names
+)]
+fn backtrack(trace: &[Vec<isize>], n: isize, m: isize) -> Vec<Step> {
+    let (mut x, mut y) = (n, m);
+    let mut steps = Vec::new();
+    for (d, window) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        if d == 0 {
+            steps.extend(std::iter::repeat_n(Step::Keep, x as usize));
+            break;
+        }
+        let at = |k: isize| window[(k + d) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            steps.push(Step::Keep);
+            x -= 1;
+            y -= 1;
+        }
+        if x == prev_x {
+            steps.push(Step::Add);
+            y -= 1;
+        } else {
+            steps.push(Step::Remove);
+            x -= 1;
+        }
+    }
+    steps.reverse();
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patched(seq: &TextSeq, base: &str, target: &str, agent: &str) -> TextSeq {
+        let mut next = seq.clone();
+        for op in seq.diff(base, target, agent) {
+            next.apply(&op);
+        }
+        next
+    }
+
+    #[test]
+    fn text_id_string_roundtrip() {
+        let id = TextId::new(12, "alice");
+        assert_eq!(id.to_string(), "12:alice");
+        assert_eq!("12:alice".parse::<TextId>(), Ok(id));
+        assert_eq!("3:".parse::<TextId>(), Ok(TextId::base(2)));
+        assert!("alice".parse::<TextId>().is_err());
+        assert!("0:alice".parse::<TextId>().is_err());
+    }
+
+    #[test]
+    fn empty_seq_renders_base() {
+        assert_eq!(TextSeq::new().render("hello"), "hello");
+        assert_eq!(TextSeq::new().next_counter("hello"), 6);
+    }
+
+    #[test]
+    fn diff_then_apply_reaches_target() {
+        let base = "the quick brown fox";
+        for target in [
+            "the quick brown fox jumps",
+            "a quick red fox",
+            "",
+            "the quick brown fox",
+            "THE QUICK BROWN FOX",
+            "quick fox the brown",
+        ] {
+            let seq = patched(&TextSeq::new(), base, target, "alice");
+            assert_eq!(seq.render(base), target, "target {target:?}");
+        }
+    }
+
+    #[test]
+    fn diff_of_unchanged_text_is_empty() {
+        assert!(TextSeq::new().diff("same", "same", "alice").is_empty());
+    }
+
+    #[test]
+    fn sequential_edits_build_on_each_other() {
+        let base = "step one";
+        let first = patched(&TextSeq::new(), base, "step one, step two", "alice");
+        let second = patched(&first, base, "step one, step 2, step three", "bob");
+        assert_eq!(second.render(base), "step one, step 2, step three");
+    }
+
+    #[test]
+    fn concurrent_edits_interleave() {
+        let base = "hello world";
+        let alice = patched(&TextSeq::new(), base, "hello big world", "alice");
+        let bob = patched(&TextSeq::new(), base, "hello world!", "bob");
+
+        let mut ab = alice.clone();
+        ab.merge(&bob);
+        let mut ba = bob.clone();
+        ba.merge(&alice);
+
+        assert_eq!(ab, ba);
+        assert_eq!(ab.render(base), "hello big world!");
+    }
+
+    #[test]
+    fn concurrent_inserts_at_same_spot_do_not_interleave_chars() {
+        let base = "ab";
+        let alice = patched(&TextSeq::new(), base, "aXXXb", "alice");
+        let bob = patched(&TextSeq::new(), base, "aYYYb", "bob");
+
+        let mut merged = alice;
+        merged.merge(&bob);
+        let text = merged.render(base);
+        assert!(
+            text == "aXXXYYYb" || text == "aYYYXXXb",
+            "runs must stay contiguous, got {text:?}"
+        );
+    }
+
+    #[test]
+    fn concurrent_delete_and_insert() {
+        let base = "one two three";
+        let alice = patched(&TextSeq::new(), base, "one three", "alice");
+        let bob = patched(&TextSeq::new(), base, "one two three four", "bob");
+
+        let mut merged = alice;
+        merged.merge(&bob);
+        assert_eq!(merged.render(base), "one three four");
+    }
+
+    #[test]
+    fn insert_after_concurrently_deleted_char_survives() {
+        let base = "abc";
+        let alice = patched(&TextSeq::new(), base, "ac", "alice");
+        let bob = patched(&TextSeq::new(), base, "abXc", "bob");
+
+        let mut merged = alice;
+        merged.merge(&bob);
+        assert_eq!(merged.render(base), "aXc");
+    }
+
+    #[test]
+    fn apply_is_idempotent_and_order_free() {
+        let base = "abc";
+        let ops = TextSeq::new().diff(base, "zab!", "alice");
+
+        let mut forward = TextSeq::new();
+        for op in &ops {
+            forward.apply(op);
+            forward.apply(op);
+        }
+        let mut backward = TextSeq::new();
+        for op in ops.iter().rev() {
+            backward.apply(op);
+        }
+        assert_eq!(forward, backward);
+        assert_eq!(forward.render(base), "zab!");
+    }
+
+    #[test]
+    fn empty_runs_are_skipped() {
+        let json = r#"{"runs":{"9:bob":{"text":""}}}"#;
+        let empty: TextSeq = serde_json::from_str(json).expect("deserialize seq");
+        assert_eq!(empty.next_counter("abc"), 4);
+
+        let mut seq = TextSeq::new();
+        seq.merge(&empty);
+        seq.apply(&TextOp::Insert {
+            id: TextId::new(5, "alice"),
+            after: None,
+            text: String::new(),
+        });
+        assert!(seq.is_empty());
+        assert_eq!(seq.next_counter("abc"), 4);
+    }
+
+    #[test]
+    fn orphaned_insert_renders_at_start() {
+        let mut seq = TextSeq::new();
+        seq.apply(&TextOp::Insert {
+            id: TextId::new(40, "bob"),
+            after: Some(TextId::new(30, "carol")),
+            text: ">".into(),
+        });
+        assert_eq!(seq.render("ab"), ">ab");
+    }
+
+    #[test]
+    fn delete_spans_are_coalesced() {
+        let ops = TextSeq::new().diff("abcdef", "af", "alice");
+        assert_eq!(
+            ops,
+            vec![TextOp::Delete {
+                start: TextId::base(1),
+                len: 4,
+            }]
+        );
+    }
+
+    #[test]
+    fn large_rewrite_falls_back_to_replace() {
+        let base: String = (0..3_000)
+            .map(|i| if i % 2 == 0 { 'a' } else { 'b' })
+            .collect();
+        let target: String = (0..3_000)
+            .map(|i| if i % 3 == 0 { 'b' } else { 'c' })
+            .collect();
+        let seq = patched(&TextSeq::new(), &base, &target, "alice");
+        assert_eq!(seq.render(&base), target);
+    }
+
+    #[test]
+    fn op_serialization_shape() {
+        let op = TextOp::Insert {
+            id: TextId::new(7, "alice"),
+            after: Some(TextId::base(2)),
+            text: "hi".into(),
+        };
+        let json = serde_json::to_value(&op).expect("serialize");
+        assert_eq!(
+            json,
+            serde_json::json!({"op": "insert", "id": "7:alice", "after": "3:", "text": "hi"})
+        );
+        let back: TextOp = serde_json::from_value(json).expect("deserialize");
+        assert_eq!(back, op);
+
+        let seq = patched(&TextSeq::new(), "ab", "xab", "alice");
+        let json = serde_json::to_string(&seq).expect("serialize seq");
+        let back: TextSeq = serde_json::from_str(&json).expect("deserialize seq");
+        assert_eq!(back, seq);
+    }
+}
@@ -201,6 +201,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: "blake3:placeholder".into(),
+            signature: None,
         };
         // Compute and stamp the correct hash.
         write_event(&mut event).expect("write_event should not fail");
@@ -219,9 +220,11 @@ mod tests {
             data: EventData::Move(MoveData {
                 state: State::Doing,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             event_hash: "blake3:placeholder".into(),
+            signature: None,
         };
         write_event(&mut event).expect("write_event should not fail");
         event
@@ -400,9 +403,11 @@ mod tests {
             data: EventData::Move(MoveData {
                 state: State::Done,
                 reason: Some("merged".into()),
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             event_hash: "blake3:placeholder".into(),
+            signature: None,
         };
         write_event(&mut merge_event).expect("write merge event");
 
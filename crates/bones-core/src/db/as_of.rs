@@ -0,0 +1,442 @@
+//! As-of projections: the backlog as it looked at a point in the past.
+//!
+//! The on-disk projection only ever holds the current state. Every event
+//! carries its `wall_ts_us`, though, so a past state can be rebuilt by
+//! replaying a subset of the log into a throwaway in-memory database with
+//! the same schema. Read commands then run their usual queries against it.
+//!
+//! Events are loaded through [`CacheManager`], so the binary cache keeps
+//! repeated time-travel queries cheap on large logs.
+//!
+//! Redactions are applied from the *whole* log, not just the replayed
+//! subset: content redacted today must not reappear when looking at last
+//! week.
+
+use std::fmt;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rusqlite::Connection;
+use tracing::debug;
+
+use crate::cache::manager::CacheManager;
+use crate::db::{migrations, project};
+use crate::event::Event;
+use crate::event::hash_text::BLAKE3_PREFIX;
+use crate::model::due::parse_offset;
+use crate::redact::{redaction_targets, scrub_payload};
+
+/// Minimum number of hash characters after `blake3:` accepted as a prefix.
+const MIN_HASH_PREFIX: usize = 6;
+
+/// The point in history to reconstruct.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AsOf {
+    /// Every event with `wall_ts_us` at or before this instant.
+    Time(i64),
+    /// The log up to and including the event whose hash starts with this.
+    Event(String),
+}
+
+/// Error returned when an `--at` expression cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseAsOfError {
+    pub got: String,
+}
+
+impl fmt::Display for ParseAsOfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid --at value: '{}' (expected YYYY-MM-DD, RFC 3339, an offset like 3d/2w/12h, or an event hash)",
+            self.got
+        )
+    }
+}
+
+impl std::error::Error for ParseAsOfError {}
+
+impl AsOf {
+    /// Parse a user-supplied point in time relative to `now`.
+    ///
+    /// Accepted forms:
+    /// - RFC 3339 timestamp (`2026-03-06T17:00:00Z`)
+    /// - calendar date (`2026-03-06`) — the end of that UTC day
+    /// - offset into the past: `<n>h`, `<n>d`, `<n>w` (`2w` is two weeks ago)
+    /// - event hash or unique prefix (`blake3:Xk3v…`)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseAsOfError`] if the input matches none of the forms.
+    pub fn parse(input: &str, now: DateTime<Utc>) -> Result<Self, ParseAsOfError> {
+        let raw = input.trim();
+        let err = || ParseAsOfError {
+            got: input.to_string(),
+        };
+
+        if let Some(rest) = raw.strip_prefix(BLAKE3_PREFIX) {
+            if rest.len() < MIN_HASH_PREFIX {
+                return Err(err());
+            }
+            return Ok(Self::Event(raw.to_string()));
+        }
+
+        let instant = if let Ok(ts) = DateTime::parse_from_rfc3339(raw) {
+            ts.with_timezone(&Utc)
+        } else if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+            let next = date.succ_opt().ok_or_else(err)?;
+            next.and_time(chrono::NaiveTime::MIN).and_utc() - Duration::microseconds(1)
+        } else {
+            let lower = raw.to_ascii_lowercase();
+            now - parse_offset(lower.strip_prefix('-').unwrap_or(&lower)).ok_or_else(err)?
+        };
+
+        Ok(Self::Time(instant.timestamp_micros()))
+    }
+}
+
+/// An in-memory projection of the log at an [`AsOf`] point.
+pub struct AsOfProjection {
+    /// Connection to the in-memory database, with the full projection schema.
+    pub conn: Connection,
+    /// The instant the projection represents, in microseconds. For
+    /// [`AsOf::Event`] this is the event's own `wall_ts_us`.
+    pub at_us: i64,
+    /// Number of events replayed.
+    pub events: usize,
+    /// Hash of the last replayed event, if any.
+    pub last_event_hash: Option<String>,
+}
+
+impl fmt::Debug for AsOfProjection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsOfProjection")
+            .field("at_us", &self.at_us)
+            .field("events", &self.events)
+            .field("last_event_hash", &self.last_event_hash)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Replay the log in `bones_dir` up to `at` into an in-memory projection.
+///
+/// # Errors
+///
+/// Returns an error if events cannot be loaded, if an [`AsOf::Event`] hash
+/// matches no event or more than one, or if projecting fails.
+pub fn project_as_of(bones_dir: &Path, at: &AsOf) -> Result<AsOfProjection> {
+    let loaded = CacheManager::new(
+        bones_dir.join("events"),
+        bones_dir.join("cache").join("events.bin"),
+    )
+    .load_events()
+    .context("load events for as-of projection")?;
+
+    // Hashes come from the log (or the cache's copy of them), never from the
+    // payloads, which are already scrubbed for redacted events.
+    let (events, at_us) = select_events(loaded.events, at)?;
+    debug!(count = events.len(), at_us, "replaying as-of projection");
+
+    let conn = open_in_memory()?;
+    project::ensure_tracking_table(&conn).context("create tracking table")?;
+    if !events.is_empty() {
+        project::Projector::new(&conn)
+            .project_batch(&events)
+            .context("project as-of events")?;
+    }
+
+    Ok(AsOfProjection {
+        conn,
+        at_us,
+        events: events.len(),
+        last_event_hash: events.last().map(|e| e.event_hash.clone()),
+    })
+}
+
+/// Pick the events visible at `at`, scrubbed of content redacted anywhere
+/// in the full log, and the instant they represent.
+fn select_events(mut all: Vec<Event>, at: &AsOf) -> Result<(Vec<Event>, i64)> {
+    let targets = redaction_targets(&all);
+
+    let at_us = match at {
+        AsOf::Time(ts) => {
+            all.retain(|e| e.wall_ts_us <= *ts);
+            *ts
+        }
+        AsOf::Event(prefix) => {
+            let mut matches = all
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| e.event_hash.starts_with(prefix.as_str()));
+            let Some((index, event)) = matches.next() else {
+                anyhow::bail!("no event with hash '{prefix}'");
+            };
+            if matches.next().is_some() {
+                anyhow::bail!("event hash prefix '{prefix}' is ambiguous");
+            }
+            let at_us = event.wall_ts_us;
+            all.truncate(index + 1);
+            at_us
+        }
+    };
+
+    if !targets.is_empty() {
+        for event in all.iter_mut().filter(|e| targets.contains(&e.event_hash)) {
+            scrub_payload(event);
+        }
+    }
+
+    Ok((all, at_us))
+}
+
+fn open_in_memory() -> Result<Connection> {
+    if let Err(err) = bones_sqlite_vec::register_auto_extension() {
+        debug!(%err, "sqlite-vec auto-extension unavailable");
+    }
+    let mut conn = Connection::open_in_memory().context("open in-memory projection")?;
+    conn.pragma_update(None, "foreign_keys", "ON")
+        .context("PRAGMA foreign_keys = ON")?;
+    migrations::migrate(&mut conn).context("apply projection migrations")?;
+    Ok(conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{CacheManager, LoadSource};
+    use crate::db::query;
+    use crate::event::data::{
+        CommentData, CommentEditData, CreateData, EventData, MoveData, RedactData,
+    };
+    use crate::event::types::EventType;
+    use crate::event::writer;
+    use crate::model::item::{Kind, State, Urgency};
+    use crate::model::item_id::ItemId;
+    use crate::shard::ShardManager;
+    use chrono::TimeZone;
+    use std::collections::BTreeMap;
+
+    fn event(item: &str, ts: i64, hash: &str, data: EventData) -> Event {
+        Event {
+            wall_ts_us: ts,
+            agent: "alice".to_string(),
+            itc: "itc:AQ".to_string(),
+            parents: vec![],
+            event_type: match data {
+                EventData::Create(_) => EventType::Create,
+                EventData::Move(_) => EventType::Move,
+                EventData::Comment(_) => EventType::Comment,
+                EventData::CommentEdit(_) => EventType::CommentEdit,
+                _ => EventType::Redact,
+            },
+            item_id: ItemId::new_unchecked(item),
+            data,
+            event_hash: hash.to_string(),
+            signature: None,
+        }
+    }
+
+    fn create(item: &str, title: &str, ts: i64, hash: &str) -> Event {
+        event(
+            item,
+            ts,
+            hash,
+            EventData::Create(CreateData {
+                title: title.to_string(),
+                kind: Kind::Task,
+                size: None,
+                urgency: Urgency::Default,
+                labels: vec![],
+                parent: None,
+                causation: None,
+                description: None,
+                extra: BTreeMap::new(),
+            }),
+        )
+    }
+
+    fn moved(item: &str, state: State, ts: i64, hash: &str) -> Event {
+        event(
+            item,
+            ts,
+            hash,
+            EventData::Move(MoveData {
+                state,
+                substate: None,
+                reason: None,
+                extra: BTreeMap::new(),
+            }),
+        )
+    }
+
+    fn log() -> Vec<Event> {
+        vec![
+            create("bn-a1", "First", 1_000, "blake3:aaaaaa01"),
+            create("bn-b2", "Second", 2_000, "blake3:bbbbbb02"),
+            moved("bn-a1", State::Done, 3_000, "blake3:cccccc03"),
+        ]
+    }
+
+    fn project(events: &[Event]) -> Connection {
+        let conn = open_in_memory().expect("open");
+        project::Projector::new(&conn)
+            .project_batch(events)
+            .expect("project");
+        conn
+    }
+
+    #[test]
+    fn parses_dates_offsets_and_hashes() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+        let friday = Utc.with_ymd_and_hms(2026, 3, 7, 0, 0, 0).unwrap();
+        assert_eq!(
+            AsOf::parse("2026-03-06", now),
+            Ok(AsOf::Time(friday.timestamp_micros() - 1))
+        );
+        assert_eq!(
+            AsOf::parse("2026-03-06T17:00:00Z", now),
+            Ok(AsOf::Time(
+                Utc.with_ymd_and_hms(2026, 3, 6, 17, 0, 0)
+                    .unwrap()
+                    .timestamp_micros()
+            ))
+        );
+        assert_eq!(
+            AsOf::parse("2d", now),
+            Ok(AsOf::Time((now - Duration::days(2)).timestamp_micros()))
+        );
+        assert_eq!(
+            AsOf::parse("blake3:aaaaaa", now),
+            Ok(AsOf::Event("blake3:aaaaaa".to_string()))
+        );
+        assert!(AsOf::parse("blake3:ab", now).is_err());
+        assert!(AsOf::parse("last friday", now).is_err());
+    }
+
+    #[test]
+    fn time_selects_events_at_or_before_the_instant() {
+        let (events, at_us) = select_events(log(), &AsOf::Time(2_000)).expect("select");
+        assert_eq!(at_us, 2_000);
+        assert_eq!(events.len(), 2);
+
+        let conn = project(&events);
+        let first = query::get_item(&conn, "bn-a1", false)
+            .expect("query")
+            .expect("item");
+        assert_eq!(first.state, "open");
+        assert!(
+            query::get_item(&conn, "bn-b2", false)
+                .expect("query")
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn event_hash_selects_the_log_prefix() {
+        let (events, at_us) =
+            select_events(log(), &AsOf::Event("blake3:aaaaaa".to_string())).expect("select");
+        assert_eq!(at_us, 1_000);
+        assert_eq!(events.len(), 1);
+
+        let err = select_events(log(), &AsOf::Event("blake3:zzzzzz".to_string()))
+            .expect_err("unknown hash");
+        assert!(err.to_string().contains("no event"));
+    }
+
+    #[test]
+    fn later_redactions_hide_earlier_content() {
+        let mut all = log();
+        all.push(event(
+            "bn-b2",
+            9_000,
+            "blake3:dddddd04",
+            EventData::Redact(RedactData {
+                target_hash: "blake3:bbbbbb02".to_string(),
+                reason: "leaked secret".to_string(),
+                extra: BTreeMap::new(),
+            }),
+        ));
+
+        let (events, _) = select_events(all, &AsOf::Time(2_000)).expect("select");
+        let conn = project(&events);
+        let second = query::get_item(&conn, "bn-b2", false)
+            .expect("query")
+            .expect("item");
+        assert_eq!(second.title, crate::redact::REDACTED_PLACEHOLDER);
+    }
+
+    #[test]
+    fn redacted_events_keep_their_hashes_through_a_warm_cache() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let bones_dir = dir.path().join(".bones");
+        let shard_mgr = ShardManager::new(&bones_dir);
+        let mut append = |mut event: Event| -> String {
+            let line = writer::write_event(&mut event).expect("write event");
+            shard_mgr
+                .append(&line, false, std::time::Duration::from_secs(5))
+                .expect("append");
+            event.event_hash
+        };
+
+        append(create("bn-a1", "First", 1_000, ""));
+        let comment = append(event(
+            "bn-a1",
+            2_000,
+            "",
+            EventData::Comment(CommentData {
+                body: "token=hunter2".to_string(),
+                reply_to: None,
+                extra: BTreeMap::new(),
+            }),
+        ));
+        append(event(
+            "bn-a1",
+            3_000,
+            "",
+            EventData::CommentEdit(CommentEditData {
+                comment: comment.clone(),
+                body: "token rotated".to_string(),
+                extra: BTreeMap::new(),
+            }),
+        ));
+        let redact = append(event(
+            "bn-a1",
+            4_000,
+            "",
+            EventData::Redact(RedactData {
+                target_hash: comment.clone(),
+                reason: "leaked secret".to_string(),
+                extra: BTreeMap::new(),
+            }),
+        ));
+
+        // Warm the cache so the as-of reads below decode it.
+        let cache = CacheManager::new(
+            bones_dir.join("events"),
+            bones_dir.join("cache").join("events.bin"),
+        );
+        cache.load_events().expect("load");
+        assert_eq!(cache.load_events().expect("load").source, LoadSource::Cache);
+
+        let at_comment = project_as_of(&bones_dir, &AsOf::Event(comment[..20].to_string()))
+            .expect("redacted event resolves");
+        assert_eq!(at_comment.events, 2);
+        assert_eq!(
+            at_comment.last_event_hash.as_deref(),
+            Some(comment.as_str())
+        );
+
+        let latest = project_as_of(&bones_dir, &AsOf::Time(i64::MAX)).expect("project");
+        assert_eq!(latest.last_event_hash.as_deref(), Some(redact.as_str()));
+        let comments = query::get_comments(&latest.conn, "bn-a1", None, None).expect("comments");
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].event_hash, comment);
+        assert_eq!(
+            comments[0].original_body,
+            crate::redact::REDACTED_PLACEHOLDER
+        );
+        // The edit still finds the comment it targets.
+        assert_eq!(comments[0].body, "token rotated");
+    }
+}
@@ -0,0 +1,978 @@
+//! Structured filter queries over the projection.
+//!
+//! A small query language shared by `bn list --where`, `bn context --where`
+//! and the TUI `/` filter:
+//!
+//! ```text
+//! label:api AND NOT assignee:* AND size>=M
+//! (urgency:urgent OR due<=+3d) -state:done
+//! under:bn-goal has:blocker "login page"
+//! ```
+//!
+//! Adjacent terms are combined with AND; `AND`, `OR` and `NOT` (upper case),
+//! a leading `-`, and parentheses combine them explicitly. A term is either
+//! `field:value`, `field<op>value` with `<op>` one of `= != < <= > >=`, or
+//! free text matched against the title, description and ID.
+//!
+//! | Field | Values | Operators |
+//! |---|---|---|
+//! | `state` | built-in state or configured sub-state | `:` `=` `!=` |
+//! | `kind` | `task`, `goal`, `bug` | `:` `=` `!=` |
+//! | `urgency` | `punt` < `default` < `urgent` | all |
+//! | `size` | `xs` < `s` < `m` < `l` < `xl`, or `*` | all |
+//! | `label`, `assignee`, `parent` | exact value, or `*` for any | `:` `=` `!=` |
+//! | `id` | full bone ID | `:` `=` `!=` |
+//! | `title`, `description` | substring | `:` `=` `!=` |
+//! | `created`, `updated`, `due` | date, timestamp, `today`, `now`, `-7d`, `+3d` | all |
+//! | `custom.<name>` | field value, or `*` | all |
+//! | `has` | `blocker`, `dependents`, `children`, `parent`, `due`, `size`, `label`, `assignee`, `comments`, `description` | `:` |
+//! | `under` | goal ID; matches every descendant | `:` |
+//!
+//! Relative dates are resolved once, at parse time, so a parsed query is a
+//! plain value. [`FilterQuery::to_sql`] compiles it to a parameterized
+//! predicate over `items i`.
+
+use std::fmt;
+use std::fmt::Write as _;
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use rusqlite::types::ToSql;
+
+use crate::model::due::parse_offset;
+
+/// Error returned when a filter query cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterQueryError {
+    /// 1-based character column the error points at.
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for FilterQueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at column {})", self.message, self.column)
+    }
+}
+
+impl std::error::Error for FilterQueryError {}
+
+/// A parsed filter query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterQuery {
+    source: String,
+    expr: Expr,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    And(Vec<Self>),
+    Or(Vec<Self>),
+    Not(Box<Self>),
+    Pred(Pred),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Op {
+    const fn sql(self) -> &'static str {
+        match self {
+            Self::Eq => "=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Pred {
+    /// Free text: substring of title, description or ID.
+    Text(String),
+    /// Lifecycle state or configured sub-state.
+    State(String),
+    /// Exact match on an `items` column.
+    Column(&'static str, String),
+    /// Substring of an `items` text column.
+    Contains(&'static str, String),
+    /// Comparison on an ordered enum column (size, urgency).
+    Rank(Ranked, Op, i64),
+    /// Comparison on a timestamp column against a `[lo, hi)` range.
+    Time(&'static str, Op, i64, i64),
+    Label(String),
+    Assignee(String),
+    Under(String),
+    Custom(String, Op, String),
+    Has(Presence),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ranked {
+    Size,
+    Urgency,
+}
+
+impl Ranked {
+    const fn values(self) -> &'static [&'static str] {
+        match self {
+            Self::Size => &["xs", "s", "m", "l", "xl"],
+            Self::Urgency => &["punt", "default", "urgent"],
+        }
+    }
+
+    const fn column(self) -> &'static str {
+        match self {
+            Self::Size => "i.size",
+            Self::Urgency => "i.urgency",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Presence {
+    Blocker,
+    Dependents,
+    Children,
+    Parent,
+    Due,
+    Size,
+    Label,
+    Assignee,
+    Comments,
+    Description,
+    Custom(String),
+}
+
+const HAS_VALUES: &str =
+    "blocker, dependents, children, parent, due, size, label, assignee, comments, description";
+
+/// Unresolved `blocks` links, shared by `has:blocker` and `has:dependents`.
+const OPEN_LINK: &str = "d.link_type IN ('blocks', 'blocked_by') \
+     AND o.state NOT IN ('done', 'archived') AND o.is_deleted = 0";
+
+impl FilterQuery {
+    /// Parse a query, resolving relative dates against the current time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FilterQueryError`] for syntax errors, unknown fields,
+    /// unsupported operators, and invalid values.
+    pub fn parse(input: &str) -> Result<Self, FilterQueryError> {
+        Self::parse_at(input, Utc::now())
+    }
+
+    /// Parse a query, resolving relative dates against `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FilterQueryError`] for syntax errors, unknown fields,
+    /// unsupported operators, and invalid values.
+    pub fn parse_at(input: &str, now: DateTime<Utc>) -> Result<Self, FilterQueryError> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            now,
+            end: input.chars().count() + 1,
+        };
+        let expr = parser.parse_query()?;
+        Ok(Self {
+            source: input.trim().to_string(),
+            expr,
+        })
+    }
+
+    /// The query text as written.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether the query uses anything beyond free-text words — a field
+    /// predicate, `OR`, or negation. The TUI uses this to decide between
+    /// structured filtering and ranked search.
+    #[must_use]
+    pub fn is_structured(&self) -> bool {
+        match &self.expr {
+            Expr::Pred(Pred::Text(_)) => false,
+            Expr::And(terms) => !terms.iter().all(|t| matches!(t, Expr::Pred(Pred::Text(_)))),
+            _ => true,
+        }
+    }
+
+    /// Compile the query to a SQL predicate over `items i`, appending its
+    /// bound values to `params` and numbering placeholders to follow them.
+    pub fn to_sql(&self, params: &mut Vec<Box<dyn ToSql>>) -> String {
+        compile(&self.expr, params)
+    }
+}
+
+impl fmt::Display for FilterQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tokenizer
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    /// A bare word, possibly `field<op>value` with a quoted value.
+    Word(String),
+    /// A quoted phrase.
+    Phrase(String),
+}
+
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, FilterQueryError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let column = i + 1;
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' {
+            tokens.push((Token::LParen, column));
+            i += 1;
+        } else if c == ')' {
+            tokens.push((Token::RParen, column));
+            i += 1;
+        } else if c == '-' && chars.get(i + 1).is_some_and(|n| !n.is_whitespace()) {
+            tokens.push((Token::Not, column));
+            i += 1;
+        } else if c == '"' {
+            let (phrase, next) = read_quoted(&chars, i)?;
+            tokens.push((Token::Phrase(phrase), column));
+            i = next;
+        } else {
+            let mut word = String::new();
+            while i < chars.len() && !chars[i].is_whitespace() && chars[i] != '(' && chars[i] != ')'
+            {
+                if chars[i] == '"' {
+                    let (quoted, next) = read_quoted(&chars, i)?;
+                    word.push('"');
+                    word.push_str(&quoted);
+                    word.push('"');
+                    i = next;
+                } else {
+                    word.push(chars[i]);
+                    i += 1;
+                }
+            }
+            let token = match word.as_str() {
+                "AND" => Token::And,
+                "OR" => Token::Or,
+                "NOT" => Token::Not,
+                _ => Token::Word(word),
+            };
+            tokens.push((token, column));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Read a `"..."` run starting at `start`; returns the contents and the index
+/// after the closing quote.
+fn read_quoted(chars: &[char], start: usize) -> Result<(String, usize), FilterQueryError> {
+    let mut i = start + 1;
+    let mut out = String::new();
+    while i < chars.len() {
+        if chars[i] == '"' {
+            return Ok((out, i + 1));
+        }
+        out.push(chars[i]);
+        i += 1;
+    }
+    Err(FilterQueryError {
+        column: start + 1,
+        message: "unterminated quote".to_string(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Parser
+// ---------------------------------------------------------------------------
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    now: DateTime<Utc>,
+    /// Column reported for errors at the end of input.
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+
+    fn column(&self) -> usize {
+        self.tokens.get(self.pos).map_or(self.end, |(_, c)| *c)
+    }
+
+    fn error(&self, message: impl Into<String>) -> FilterQueryError {
+        FilterQueryError {
+            column: self.column(),
+            message: message.into(),
+        }
+    }
+
+    fn parse_query(&mut self) -> Result<Expr, FilterQueryError> {
+        if self.tokens.is_empty() {
+            return Err(self.error("empty query"));
+        }
+        let expr = self.parse_or()?;
+        if self.peek().is_some() {
+            return Err(self.error("unexpected ')'"));
+        }
+        Ok(expr)
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, FilterQueryError> {
+        let mut terms = vec![self.parse_and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            terms.push(self.parse_and()?);
+        }
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            Expr::Or(terms)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, FilterQueryError> {
+        let mut terms = vec![self.parse_unary()?];
+        loop {
+            match self.peek() {
+                None | Some(Token::Or | Token::RParen) => break,
+                Some(Token::And) => {
+                    self.pos += 1;
+                    terms.push(self.parse_unary()?);
+                }
+                Some(_) => terms.push(self.parse_unary()?),
+            }
+        }
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            Expr::And(terms)
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, FilterQueryError> {
+        let column = self.column();
+        let Some((token, _)) = self.tokens.get(self.pos).cloned() else {
+            return Err(self.error("expected a term"));
+        };
+        self.pos += 1;
+        match token {
+            Token::Not => Ok(Expr::Not(Box::new(self.parse_unary()?))),
+            Token::LParen => {
+                let inner = self.parse_or()?;
+                if self.peek() != Some(&Token::RParen) {
+                    return Err(FilterQueryError {
+                        column,
+                        message: "unclosed '('".to_string(),
+                    });
+                }
+                self.pos += 1;
+                Ok(inner)
+            }
+            Token::Phrase(text) => Ok(Expr::Pred(Pred::Text(text))),
+            Token::Word(word) => self.parse_term(&word, column),
+            Token::RParen | Token::And | Token::Or => {
+                self.pos -= 1;
+                Err(self.error("expected a term"))
+            }
+        }
+    }
+
+    fn parse_term(&self, word: &str, column: usize) -> Result<Expr, FilterQueryError> {
+        let Some((field, op, negate, raw_value)) = split_term(word) else {
+            return Ok(Expr::Pred(Pred::Text(word.to_string())));
+        };
+        let (value, quoted) = raw_value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .map_or_else(
+                || (raw_value.to_string(), false),
+                |inner| (inner.to_string(), true),
+            );
+
+        let pred = self
+            .predicate(&field, op, value, quoted)
+            .map_err(|message| FilterQueryError { column, message })?;
+        let expr = Expr::Pred(pred);
+        Ok(if negate {
+            Expr::Not(Box::new(expr))
+        } else {
+            expr
+        })
+    }
+
+    /// Build the predicate for one `field<op>value` term.
+    fn predicate(&self, field: &str, op: Op, value: String, quoted: bool) -> Result<Pred, String> {
+        if value.is_empty() {
+            return Err(format!("missing value for '{field}'"));
+        }
+        let any = value == "*" && !quoted;
+        let lower = value.to_ascii_lowercase();
+
+        let eq_only = || -> Result<(), String> {
+            if op == Op::Eq {
+                Ok(())
+            } else {
+                Err(format!("'{field}' only supports ':', '=' and '!='"))
+            }
+        };
+
+        Ok(match field {
+            "state" | "status" => {
+                eq_only()?;
+                Pred::State(lower)
+            }
+            "kind" => {
+                eq_only()?;
+                if !matches!(lower.as_str(), "task" | "goal" | "bug") {
+                    return Err(format!("invalid kind '{value}' (task, goal, bug)"));
+                }
+                Pred::Column("i.kind", lower)
+            }
+            "id" => {
+                eq_only()?;
+                Pred::Column("i.item_id", value)
+            }
+            "parent" => {
+                eq_only()?;
+                if any {
+                    Pred::Has(Presence::Parent)
+                } else {
+                    Pred::Column("i.parent_id", value)
+                }
+            }
+            "label" | "tag" => {
+                eq_only()?;
+                if any {
+                    Pred::Has(Presence::Label)
+                } else {
+                    Pred::Label(value)
+                }
+            }
+            "assignee" => {
+                eq_only()?;
+                if any {
+                    Pred::Has(Presence::Assignee)
+                } else {
+                    Pred::Assignee(value)
+                }
+            }
+            "title" => {
+                eq_only()?;
+                Pred::Contains("i.title", value)
+            }
+            "description" | "desc" => {
+                eq_only()?;
+                Pred::Contains("COALESCE(i.description, '')", value)
+            }
+            "size" | "due" if any => {
+                eq_only()?;
+                Pred::Has(presence(field).unwrap_or(Presence::Size))
+            }
+            "size" => rank(Ranked::Size, op, &lower)
+                .ok_or_else(|| format!("invalid size '{value}' (xs, s, m, l, xl)"))?,
+            "urgency" => rank(Ranked::Urgency, op, &lower)
+                .ok_or_else(|| format!("invalid urgency '{value}' (punt, default, urgent)"))?,
+            "created" => self.time(field, "i.created_at_us", op, &value)?,
+            "updated" => self.time(field, "i.updated_at_us", op, &value)?,
+            "due" => self.time(field, "i.due_at_us", op, &value)?,
+            "has" => {
+                eq_only()?;
+                Pred::Has(
+                    presence(&lower).ok_or_else(|| {
+                        format!("unknown has:{value} (expected one of {HAS_VALUES})")
+                    })?,
+                )
+            }
+            "under" => {
+                eq_only()?;
+                Pred::Under(value)
+            }
+            other => match other.strip_prefix("custom.") {
+                Some(name) if !name.is_empty() => {
+                    if any {
+                        eq_only()?;
+                        Pred::Has(Presence::Custom(name.to_string()))
+                    } else {
+                        Pred::Custom(name.to_string(), op, value)
+                    }
+                }
+                _ => {
+                    return Err(format!(
+                        "unknown field '{field}' (quote free text that contains ':')"
+                    ));
+                }
+            },
+        })
+    }
+
+    fn time(&self, field: &str, column: &'static str, op: Op, value: &str) -> Result<Pred, String> {
+        let (lo, hi) = time_range(&value.to_ascii_lowercase(), self.now).ok_or_else(|| {
+            format!(
+                "invalid date '{value}' for '{field}' (YYYY-MM-DD, RFC 3339, today, yesterday, tomorrow, now, or an offset like -7d/+3d)"
+            )
+        })?;
+        Ok(Pred::Time(column, op, lo, hi))
+    }
+}
+
+/// Split `field<op>value`. Returns `None` for words that are free text: no
+/// operator, or something other than a field name before it.
+fn split_term(word: &str) -> Option<(String, Op, bool, &str)> {
+    let idx = word.find([':', '=', '<', '>', '!'])?;
+    let field = &word[..idx];
+    if field.is_empty()
+        || !field
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '-')
+    {
+        return None;
+    }
+    let rest = &word[idx..];
+    let (op, negate, len) = if rest.starts_with("!=") {
+        (Op::Eq, true, 2)
+    } else if rest.starts_with(">=") {
+        (Op::Ge, false, 2)
+    } else if rest.starts_with("<=") {
+        (Op::Le, false, 2)
+    } else if rest.starts_with('>') {
+        (Op::Gt, false, 1)
+    } else if rest.starts_with('<') {
+        (Op::Lt, false, 1)
+    } else if rest.starts_with(':') || rest.starts_with('=') {
+        (Op::Eq, false, 1)
+    } else {
+        return None;
+    };
+    Some((field.to_ascii_lowercase(), op, negate, &rest[len..]))
+}
+
+fn rank(ranked: Ranked, op: Op, value: &str) -> Option<Pred> {
+    let idx = ranked.values().iter().position(|v| *v == value)?;
+    Some(Pred::Rank(ranked, op, i64::try_from(idx).ok()?))
+}
+
+fn presence(value: &str) -> Option<Presence> {
+    Some(match value {
+        "blocker" | "blockers" => Presence::Blocker,
+        "dependents" => Presence::Dependents,
+        "children" => Presence::Children,
+        "parent" => Presence::Parent,
+        "due" => Presence::Due,
+        "size" => Presence::Size,
+        "label" | "labels" => Presence::Label,
+        "assignee" | "assignees" => Presence::Assignee,
+        "comments" => Presence::Comments,
+        "description" => Presence::Description,
+        _ => return None,
+    })
+}
+
+/// Resolve a date expression to a half-open `[lo, hi)` range in µs. Calendar
+/// days cover the whole UTC day; instants are one microsecond wide.
+fn time_range(value: &str, now: DateTime<Utc>) -> Option<(i64, i64)> {
+    let day = |date: NaiveDate| {
+        let lo = date.and_time(NaiveTime::MIN).and_utc().timestamp_micros();
+        (lo, lo + Duration::days(1).num_microseconds().unwrap_or(0))
+    };
+    let instant = |t: DateTime<Utc>| {
+        let us = t.timestamp_micros();
+        (us, us + 1)
+    };
+    let today = now.date_naive();
+
+    Some(match value {
+        "now" => instant(now),
+        "today" => day(today),
+        "yesterday" => day(today.pred_opt()?),
+        "tomorrow" => day(today.succ_opt()?),
+        _ => {
+            if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+                day(date)
+            } else if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+                instant(ts.with_timezone(&Utc))
+            } else if let Some(offset) = value.strip_prefix('+') {
+                instant(now + parse_offset(offset)?)
+            } else if let Some(offset) = value.strip_prefix('-') {
+                instant(now - parse_offset(offset)?)
+            } else {
+                return None;
+            }
+        }
+    })
+}
+
+// ---------------------------------------------------------------------------
+// SQL compilation
+// ---------------------------------------------------------------------------
+
+fn bind(params: &mut Vec<Box<dyn ToSql>>, value: impl ToSql + 'static) -> String {
+    params.push(Box::new(value));
+    format!("?{}", params.len())
+}
+
+fn like_pattern(needle: &str) -> String {
+    let mut out = String::from("%");
+    for c in needle.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push('%');
+    out
+}
+
+fn compile(expr: &Expr, params: &mut Vec<Box<dyn ToSql>>) -> String {
+    match expr {
+        Expr::And(terms) | Expr::Or(terms) => {
+            let joiner = if matches!(expr, Expr::And(_)) {
+                " AND "
+            } else {
+                " OR "
+            };
+            let parts: Vec<String> = terms.iter().map(|t| compile(t, params)).collect();
+            format!("({})", parts.join(joiner))
+        }
+        // NULL comparisons (e.g. `size>=m` on an unsized bone) count as false,
+        // so their negation is true.
+        Expr::Not(inner) => format!("(NOT COALESCE({}, 0))", compile(inner, params)),
+        Expr::Pred(pred) => compile_pred(pred, params),
+    }
+}
+
+fn compile_pred(pred: &Pred, params: &mut Vec<Box<dyn ToSql>>) -> String {
+    match pred {
+        Pred::Text(text) => {
+            let p = bind(params, like_pattern(text));
+            format!(
+                "(i.title LIKE {p} ESCAPE '\\' OR COALESCE(i.description, '') LIKE {p} ESCAPE '\\' \
+                 OR i.item_id LIKE {p} ESCAPE '\\')"
+            )
+        }
+        Pred::State(state) => {
+            let p = bind(params, state.clone());
+            format!("(i.state = {p} OR i.substate = {p})")
+        }
+        Pred::Column(column, value) => {
+            let p = bind(params, value.clone());
+            format!("({column} = {p})")
+        }
+        Pred::Contains(column, needle) => {
+            let p = bind(params, like_pattern(needle));
+            format!("({column} LIKE {p} ESCAPE '\\')")
+        }
+        Pred::Rank(ranked, op, value) => {
+            let mut case = format!("CASE {}", ranked.column());
+            for (idx, name) in ranked.values().iter().enumerate() {
+                let _ = write!(case, " WHEN '{name}' THEN {idx}");
+            }
+            let p = bind(params, *value);
+            format!("({case} END {} {p})", op.sql())
+        }
+        Pred::Time(column, op, lo, hi) => match op {
+            Op::Eq => {
+                let lo = bind(params, *lo);
+                let hi = bind(params, *hi);
+                format!("({column} >= {lo} AND {column} < {hi})")
+            }
+            Op::Ge => format!("({column} >= {})", bind(params, *lo)),
+            Op::Gt => format!("({column} >= {})", bind(params, *hi)),
+            Op::Lt => format!("({column} < {})", bind(params, *lo)),
+            Op::Le => format!("({column} < {})", bind(params, *hi)),
+        },
+        Pred::Label(label) => {
+            let p = bind(params, label.clone());
+            format!(
+                "EXISTS (SELECT 1 FROM item_labels l WHERE l.item_id = i.item_id AND l.label = {p})"
+            )
+        }
+        Pred::Assignee(agent) => {
+            let p = bind(params, agent.clone());
+            format!(
+                "EXISTS (SELECT 1 FROM item_assignees a WHERE a.item_id = i.item_id AND a.agent = {p})"
+            )
+        }
+        Pred::Under(goal) => {
+            let p = bind(params, goal.clone());
+            format!(
+                "i.item_id IN (WITH RECURSIVE sub(id) AS (\
+                 SELECT item_id FROM items WHERE parent_id = {p} \
+                 UNION SELECT c.item_id FROM items c JOIN sub ON c.parent_id = sub.id) \
+                 SELECT id FROM sub)"
+            )
+        }
+        Pred::Custom(name, op, value) => {
+            let name = bind(params, name.clone());
+            let cmp = match (op, value.parse::<i64>()) {
+                (Op::Eq, _) => format!("f.value = {}", bind(params, value.clone())),
+                (_, Ok(num)) => format!("f.value_num {} {}", op.sql(), bind(params, num)),
+                (_, Err(_)) => format!("f.value {} {}", op.sql(), bind(params, value.clone())),
+            };
+            format!(
+                "EXISTS (SELECT 1 FROM item_custom_fields f \
+                 WHERE f.item_id = i.item_id AND f.name = {name} AND {cmp})"
+            )
+        }
+        Pred::Has(presence) => compile_presence(presence, params),
+    }
+}
+
+fn compile_presence(presence: &Presence, params: &mut Vec<Box<dyn ToSql>>) -> String {
+    match presence {
+        Presence::Blocker => format!(
+            "EXISTS (SELECT 1 FROM item_dependencies d JOIN items o ON o.item_id = d.depends_on_item_id \
+             WHERE d.item_id = i.item_id AND {OPEN_LINK})"
+        ),
+        Presence::Dependents => format!(
+            "EXISTS (SELECT 1 FROM item_dependencies d JOIN items o ON o.item_id = d.item_id \
+             WHERE d.depends_on_item_id = i.item_id AND {OPEN_LINK})"
+        ),
+        Presence::Children => {
+            "EXISTS (SELECT 1 FROM items c WHERE c.parent_id = i.item_id AND c.is_deleted = 0)"
+                .to_string()
+        }
+        Presence::Parent => "(i.parent_id IS NOT NULL)".to_string(),
+        Presence::Due => "(i.due_at_us IS NOT NULL)".to_string(),
+        Presence::Size => "(i.size IS NOT NULL)".to_string(),
+        Presence::Description => "(COALESCE(i.description, '') <> '')".to_string(),
+        Presence::Label => {
+            "EXISTS (SELECT 1 FROM item_labels l WHERE l.item_id = i.item_id)".to_string()
+        }
+        Presence::Assignee => {
+            "EXISTS (SELECT 1 FROM item_assignees a WHERE a.item_id = i.item_id)".to_string()
+        }
+        Presence::Comments => {
+            "EXISTS (SELECT 1 FROM item_comments m WHERE m.item_id = i.item_id)".to_string()
+        }
+        Presence::Custom(name) => {
+            let p = bind(params, name.clone());
+            format!(
+                "EXISTS (SELECT 1 FROM item_custom_fields f WHERE f.item_id = i.item_id AND f.name = {p})"
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrations;
+    use chrono::TimeZone;
+    use rusqlite::{Connection, params};
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap()
+    }
+
+    fn parse(input: &str) -> FilterQuery {
+        FilterQuery::parse_at(input, now()).expect("parse")
+    }
+
+    fn parse_err(input: &str) -> FilterQueryError {
+        FilterQuery::parse_at(input, now()).expect_err("should fail")
+    }
+
+    /// Items:
+    /// - bn-a1 "Fix API login", open, urgent, m, labels api, assigned alice,
+    ///   child of bn-g1, blocked by bn-b2
+    /// - bn-b2 "Write docs", doing, default, xs, label docs, created 2026-03-09
+    /// - bn-c3 "Refactor API client", open, punt, xl, label api, unassigned
+    /// - bn-g1 "Launch", goal, open, no size
+    fn fixture() -> Connection {
+        let mut conn = Connection::open_in_memory().expect("open");
+        migrations::migrate(&mut conn).expect("migrate");
+        let day = |d: u32| {
+            Utc.with_ymd_and_hms(2026, 3, d, 9, 0, 0)
+                .unwrap()
+                .timestamp_micros()
+        };
+        let items = [
+            (
+                "bn-g1",
+                "Launch",
+                "goal",
+                "open",
+                "default",
+                None,
+                None,
+                day(1),
+            ),
+            (
+                "bn-a1",
+                "Fix API login",
+                "task",
+                "open",
+                "urgent",
+                Some("m"),
+                Some("bn-g1"),
+                day(1),
+            ),
+            (
+                "bn-b2",
+                "Write docs",
+                "task",
+                "doing",
+                "default",
+                Some("xs"),
+                None,
+                day(9),
+            ),
+            (
+                "bn-c3",
+                "Refactor API client",
+                "task",
+                "open",
+                "punt",
+                Some("xl"),
+                None,
+                day(5),
+            ),
+        ];
+        for (id, title, kind, state, urgency, size, parent, created) in items {
+            conn.execute(
+                "INSERT INTO items (item_id, title, kind, state, urgency, size, parent_id, \
+                 is_deleted, search_labels, created_at_us, updated_at_us) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, '', ?8, ?8)",
+                params![id, title, kind, state, urgency, size, parent, created],
+            )
+            .expect("insert item");
+        }
+        for (id, label) in [("bn-a1", "api"), ("bn-b2", "docs"), ("bn-c3", "api")] {
+            conn.execute(
+                "INSERT INTO item_labels (item_id, label, created_at_us) VALUES (?1, ?2, 0)",
+                params![id, label],
+            )
+            .expect("insert label");
+        }
+        conn.execute(
+            "INSERT INTO item_assignees (item_id, agent, created_at_us) VALUES ('bn-a1', 'alice', 0)",
+            [],
+        )
+        .expect("insert assignee");
+        conn.execute(
+            "INSERT INTO item_dependencies (item_id, depends_on_item_id, link_type, created_at_us) \
+             VALUES ('bn-a1', 'bn-b2', 'blocks', 0)",
+            [],
+        )
+        .expect("insert dependency");
+        conn
+    }
+
+    fn matching(conn: &Connection, input: &str) -> Vec<String> {
+        let query = parse(input);
+        let mut params: Vec<Box<dyn ToSql>> = Vec::new();
+        let predicate = query.to_sql(&mut params);
+        let sql = format!("SELECT i.item_id FROM items i WHERE {predicate} ORDER BY i.item_id");
+        let mut stmt = conn.prepare(&sql).expect("prepare");
+        let refs: Vec<&dyn ToSql> = params.iter().map(AsRef::as_ref).collect();
+        stmt.query_map(refs.as_slice(), |row| row.get(0))
+            .expect("query")
+            .collect::<Result<_, _>>()
+            .expect("rows")
+    }
+
+    #[test]
+    fn example_from_the_docs_compiles_and_matches() {
+        let conn = fixture();
+        assert_eq!(
+            matching(&conn, "label:api AND NOT assignee:* AND size>=M"),
+            vec!["bn-c3"]
+        );
+    }
+
+    #[test]
+    fn boolean_operators_and_grouping() {
+        let conn = fixture();
+        assert_eq!(
+            matching(&conn, "state:doing OR kind:goal"),
+            vec!["bn-b2", "bn-g1"]
+        );
+        assert_eq!(matching(&conn, "label:api -urgency:urgent"), vec!["bn-c3"]);
+        assert_eq!(
+            matching(
+                &conn,
+                "(urgency>=default) AND NOT (kind:goal OR state:doing)"
+            ),
+            vec!["bn-a1"]
+        );
+        assert_eq!(matching(&conn, "label!=api"), vec!["bn-b2", "bn-g1"]);
+    }
+
+    #[test]
+    fn negated_comparisons_include_missing_values() {
+        let conn = fixture();
+        assert_eq!(matching(&conn, "NOT size>=m"), vec!["bn-b2", "bn-g1"]);
+    }
+
+    #[test]
+    fn dates_relationships_and_free_text() {
+        let conn = fixture();
+        assert_eq!(matching(&conn, "created:2026-03-09"), vec!["bn-b2"]);
+        assert_eq!(matching(&conn, "created>=-7d"), vec!["bn-b2", "bn-c3"]);
+        assert_eq!(
+            matching(&conn, "created<2026-03-05"),
+            vec!["bn-a1", "bn-g1"]
+        );
+        assert_eq!(matching(&conn, "has:blocker"), vec!["bn-a1"]);
+        assert_eq!(matching(&conn, "has:dependents"), vec!["bn-b2"]);
+        assert_eq!(matching(&conn, "under:bn-g1"), vec!["bn-a1"]);
+        assert_eq!(matching(&conn, "api"), vec!["bn-a1", "bn-c3"]);
+        assert_eq!(matching(&conn, "\"api client\""), vec!["bn-c3"]);
+        assert_eq!(matching(&conn, "title:\"write docs\""), vec!["bn-b2"]);
+    }
+
+    #[test]
+    fn free_text_only_queries_are_not_structured() {
+        assert!(!parse("fix login").is_structured());
+        assert!(parse("fix OR login").is_structured());
+        assert!(parse("label:api").is_structured());
+        assert!(parse("-wip").is_structured());
+    }
+
+    #[test]
+    fn errors_point_at_the_problem() {
+        let err = parse_err("label:api AND color:red");
+        assert_eq!(err.column, 15);
+        assert!(err.message.contains("unknown field 'color'"));
+
+        assert!(parse_err("size>=huge").message.contains("invalid size"));
+        assert!(parse_err("label>api").message.contains("only supports"));
+        assert!(parse_err("(state:open").message.contains("unclosed"));
+        assert!(parse_err("state:open)").message.contains("unexpected ')'"));
+        assert!(
+            parse_err("state:open AND")
+                .message
+                .contains("expected a term")
+        );
+        assert!(parse_err("due<3d").message.contains("invalid date"));
+        assert!(parse_err("").message.contains("empty query"));
+    }
+}
@@ -207,6 +207,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: format!("blake3:{hash}"),
+            signature: None,
         }
     }
 
@@ -297,6 +298,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: "blake3:del1".into(),
+            signature: None,
         })
         .unwrap();
 
@@ -238,7 +238,7 @@ pub fn incremental_apply(
     let mut total_byte_len = offset;
 
     let mut current_batch: Vec<Event> = Vec::with_capacity(1000);
-    let projector = project::Projector::new(&conn);
+    let projector = project::Projector::new(&conn).with_event_log(&shard_mgr);
 
     for line_res in line_iter {
         let (abs_offset, line): (usize, String) =
@@ -537,6 +537,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         writer::write_event(&mut event).expect("compute hash");
         event
@@ -678,6 +679,62 @@ mod tests {
         assert_eq!(count, 4);
     }
 
+    #[test]
+    fn incremental_redact_scrubs_item_fields() {
+        let (dir, shard_mgr) = setup_bones_dir();
+        let db_path = dir.path().join("bones.db");
+        let events_dir = dir.path().join("events");
+
+        let create = make_create_event("bn-001", "Rotate hunter2 key", 1000);
+        append_event(&shard_mgr, &create);
+        rebuild::rebuild(&events_dir, &db_path).unwrap();
+
+        // The redaction arrives later, as it would after a pull.
+        let mut redact = Event {
+            wall_ts_us: 2000,
+            agent: "test-agent".into(),
+            itc: "itc:AQ".into(),
+            parents: vec![create.event_hash.clone()],
+            event_type: EventType::Redact,
+            item_id: ItemId::new_unchecked("bn-001"),
+            data: EventData::Redact(RedactData {
+                target_hash: create.event_hash.clone(),
+                reason: "leaked secret".into(),
+                extra: BTreeMap::new(),
+            }),
+            event_hash: String::new(),
+            signature: None,
+        };
+        writer::write_event(&mut redact).expect("compute hash");
+        append_event(&shard_mgr, &redact);
+
+        let report = incremental_apply(&events_dir, &db_path, false).unwrap();
+        assert!(!report.full_rebuild_triggered);
+        assert_eq!(report.events_applied, 1);
+
+        let conn = open_projection(&db_path).unwrap();
+        let (title, description): (String, Option<String>) = conn
+            .query_row(
+                "SELECT title, description FROM items WHERE item_id = 'bn-001'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(title, crate::redact::REDACTED_PLACEHOLDER);
+        assert_eq!(
+            description.as_deref(),
+            Some(crate::redact::REDACTED_PLACEHOLDER)
+        );
+        let hits: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM items_fts WHERE items_fts MATCH 'hunter2'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(hits, 0);
+    }
+
     #[test]
     fn incremental_apply_matches_full_rebuild() {
         let (dir, shard_mgr) = setup_bones_dir();
@@ -823,7 +880,7 @@ mod tests {
     fn check_incremental_safety_fails_missing_tracking_table() {
         let mut conn = Connection::open_in_memory().unwrap();
         migrations::migrate(&mut conn).unwrap();
-        // Don't create the tracking table
+        conn.execute_batch("DROP TABLE projected_events").unwrap();
 
         let result = check_incremental_safety(&conn, Path::new("/nonexistent"));
         assert!(result.is_err());
@@ -4,9 +4,21 @@ use super::schema;
 use rusqlite::{Connection, types::Type};
 
 /// Latest schema version understood by this binary.
-pub const LATEST_SCHEMA_VERSION: u32 = 2;
-
-const MIGRATIONS: &[(u32, &str)] = &[(1, schema::MIGRATION_V1_SQL), (2, schema::MIGRATION_V2_SQL)];
+pub const LATEST_SCHEMA_VERSION: u32 = 11;
+
+const MIGRATIONS: &[(u32, &str)] = &[
+    (1, schema::MIGRATION_V1_SQL),
+    (2, schema::MIGRATION_V2_SQL),
+    (3, schema::MIGRATION_V3_SQL),
+    (4, schema::MIGRATION_V4_SQL),
+    (5, schema::MIGRATION_V5_SQL),
+    (6, schema::MIGRATION_V6_SQL),
+    (7, schema::MIGRATION_V7_SQL),
+    (8, schema::MIGRATION_V8_SQL),
+    (9, schema::MIGRATION_V9_SQL),
+    (10, schema::MIGRATION_V10_SQL),
+    (11, schema::MIGRATION_V11_SQL),
+];
 
 /// Read `PRAGMA user_version` and convert it to a Rust `u32`.
 ///
@@ -88,6 +100,22 @@ mod tests {
         assert!(sqlite_object_exists(&conn, "table", "item_dependencies")?);
         assert!(sqlite_object_exists(&conn, "table", "item_comments")?);
         assert!(sqlite_object_exists(&conn, "table", "event_redactions")?);
+        assert!(sqlite_object_exists(&conn, "table", "item_custom_fields")?);
+        assert!(sqlite_object_exists(
+            &conn,
+            "table",
+            "item_description_edits"
+        )?);
+        assert!(sqlite_object_exists(&conn, "table", "item_field_heads")?);
+        assert!(sqlite_object_exists(&conn, "table", "item_comment_edits")?);
+        assert!(sqlite_object_exists(
+            &conn,
+            "table",
+            "item_comment_retractions"
+        )?);
+        assert!(sqlite_object_exists(&conn, "table", "item_attachments")?);
+        assert!(sqlite_object_exists(&conn, "table", "item_transitions")?);
+        assert!(sqlite_object_exists(&conn, "table", "projected_events")?);
         assert!(sqlite_object_exists(&conn, "table", "projection_meta")?);
         assert!(sqlite_object_exists(&conn, "table", "items_fts")?);
 
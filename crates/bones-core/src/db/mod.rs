@@ -5,6 +5,8 @@
 //! - `busy_timeout = 5s` to reduce transient lock failures under contention
 //! - `foreign_keys = ON` to protect relational integrity in projection tables
 
+pub mod as_of;
+pub mod filter_query;
 pub mod fts;
 pub mod incremental;
 pub mod migrations;
@@ -12,6 +14,7 @@ pub mod project;
 pub mod query;
 pub mod rebuild;
 pub mod schema;
+pub mod sql_console;
 
 use anyhow::{Context, Result};
 use rusqlite::Connection;
@@ -248,6 +251,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         }
     }
 
@@ -341,6 +345,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         let line = writer::write_event(&mut create).expect("serialize create event");
         shard_mgr
@@ -2,7 +2,7 @@
 //!
 //! The [`Projector`] replays events from the TSJSON event log and upserts
 //! the resulting state into the `SQLite` projection database. It handles all
-//! 11 event types and supports both incremental (single-event) and full
+//! 15 event types and supports both incremental (single-event) and full
 //! rebuild modes.
 //!
 //! # Deduplication
@@ -20,10 +20,14 @@
 use anyhow::{Context, Result};
 use rusqlite::{Connection, params};
 
+use crate::conflicts;
 use crate::db::query;
 use crate::event::Event;
 use crate::event::data::{AssignAction, EventData};
 use crate::event::types::EventType;
+use crate::model::custom::{custom_field_name, value_to_text};
+use crate::model::due::{DUE_FIELD, due_from_value};
+use crate::redact::scrub_payload;
 use crate::shard::ShardManager;
 
 // ---------------------------------------------------------------------------
@@ -52,6 +56,7 @@ pub struct ProjectionStats {
 pub struct Projector<'conn> {
     conn: &'conn Connection,
     has_agent_column: bool,
+    event_log: Option<&'conn ShardManager>,
 }
 
 impl<'conn> Projector<'conn> {
@@ -67,9 +72,22 @@ impl<'conn> Projector<'conn> {
         Self {
             conn,
             has_agent_column,
+            event_log: None,
         }
     }
 
+    /// Let the projector read back redacted events from the event log.
+    ///
+    /// With a log attached, projecting an `item.redact` also scrubs the
+    /// title, description, and summary the target wrote, not just comment
+    /// bodies. Without one, the caller is responsible for running
+    /// [`crate::redact::scrub_projection`] itself.
+    #[must_use]
+    pub const fn with_event_log(mut self, shard_mgr: &'conn ShardManager) -> Self {
+        self.event_log = Some(shard_mgr);
+        self
+    }
+
     /// Project a batch of events, returning aggregate statistics.
     ///
     /// Events are applied inside a single transaction for performance.
@@ -191,6 +209,10 @@ impl<'conn> Projector<'conn> {
             EventType::Compact => self.project_compact(event)?,
             EventType::Snapshot => self.project_snapshot(event)?,
             EventType::Redact => self.project_redact(event)?,
+            EventType::Patch => self.project_patch(event)?,
+            EventType::CommentEdit => self.project_comment_edit(event)?,
+            EventType::CommentRetract => self.project_comment_retract(event)?,
+            EventType::Attach => self.project_attach(event)?,
         }
 
         // Record that this event hash has been projected
@@ -368,6 +390,23 @@ impl<'conn> Projector<'conn> {
             )
             .with_context(|| format!("project create for {}", event.item_id))?;
 
+        if let Some(description) = description {
+            self.conn
+                .execute(
+                    "INSERT INTO item_description_edits
+                        (item_id, base_hash, base_text, updated_at_us)
+                     VALUES (?1, ?2, ?3, ?4)
+                     ON CONFLICT(item_id) DO NOTHING",
+                    params![
+                        event.item_id.as_str(),
+                        event.event_hash,
+                        description,
+                        event.wall_ts_us
+                    ],
+                )
+                .with_context(|| format!("record description base for {}", event.item_id))?;
+        }
+
         // Insert initial labels
         if !is_redacted {
             for label in &data.labels {
@@ -382,6 +421,8 @@ impl<'conn> Projector<'conn> {
             self.refresh_search_labels(event.item_id.as_str(), event.wall_ts_us)?;
         }
 
+        conflicts::record_heads(self.conn, event, is_redacted)?;
+
         Ok(())
     }
 
@@ -417,6 +458,7 @@ impl<'conn> Projector<'conn> {
                     "UPDATE items SET description = ?1, updated_at_us = ?2 WHERE item_id = ?3",
                     params![value, event.wall_ts_us, event.item_id.as_str()],
                 )?;
+                self.set_description_base(event, value.filter(|_| !is_redacted).as_deref())?;
             }
             "kind" => {
                 let value = data.value.as_str().unwrap_or("task");
@@ -446,6 +488,13 @@ impl<'conn> Projector<'conn> {
                     params![value, event.wall_ts_us, event.item_id.as_str()],
                 )?;
             }
+            DUE_FIELD => {
+                let value = due_from_value(&data.value).and_then(|us| i64::try_from(us).ok());
+                self.conn.execute(
+                    "UPDATE items SET due_at_us = ?1, updated_at_us = ?2 WHERE item_id = ?3",
+                    params![value, event.wall_ts_us, event.item_id.as_str()],
+                )?;
+            }
             "labels" => {
                 // Labels update: supports both legacy array replacement
                 // and new add/remove action format (CRDT-friendly).
@@ -499,6 +548,10 @@ impl<'conn> Projector<'conn> {
                     self.refresh_search_labels(event.item_id.as_str(), event.wall_ts_us)?;
                 }
             }
+            field if custom_field_name(field).is_some() => {
+                let name = custom_field_name(field).unwrap_or_default();
+                self.project_custom_field(event, name, &data.value)?;
+            }
             _ => {
                 // Unknown field — just bump updated_at
                 tracing::debug!(
@@ -513,6 +566,37 @@ impl<'conn> Projector<'conn> {
             }
         }
 
+        conflicts::record_heads(self.conn, event, is_redacted)?;
+
+        Ok(())
+    }
+
+    /// Upsert or clear a custom field row. Values without a scalar text form
+    /// (including `null`) clear the field.
+    fn project_custom_field(
+        &self,
+        event: &Event,
+        name: &str,
+        value: &serde_json::Value,
+    ) -> Result<()> {
+        let item_id = event.item_id.as_str();
+        if let Some(text) = value_to_text(value) {
+            self.conn.execute(
+                "INSERT OR REPLACE INTO item_custom_fields
+                 (item_id, name, value, value_num, updated_at_us)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![item_id, name, text, value.as_i64(), event.wall_ts_us],
+            )?;
+        } else {
+            self.conn.execute(
+                "DELETE FROM item_custom_fields WHERE item_id = ?1 AND name = ?2",
+                params![item_id, name],
+            )?;
+        }
+        self.conn.execute(
+            "UPDATE items SET updated_at_us = ?1 WHERE item_id = ?2",
+            params![event.wall_ts_us, item_id],
+        )?;
         Ok(())
     }
 
@@ -523,11 +607,37 @@ impl<'conn> Projector<'conn> {
 
         self.ensure_item_exists(event)?;
 
+        let from_state: String = self
+            .conn
+            .query_row(
+                "SELECT state FROM items WHERE item_id = ?1",
+                params![event.item_id.as_str()],
+                |row| row.get(0),
+            )
+            .with_context(|| format!("read state for {}", event.item_id))?;
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO item_transitions
+                 (event_hash, item_id, from_state, to_state, substate, agent, at_us)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    event.event_hash,
+                    event.item_id.as_str(),
+                    from_state,
+                    data.state.to_string(),
+                    data.substate,
+                    event.agent,
+                    event.wall_ts_us,
+                ],
+            )
+            .with_context(|| format!("record transition for {}", event.item_id))?;
+
         self.conn
             .execute(
-                "UPDATE items SET state = ?1, updated_at_us = ?2 WHERE item_id = ?3",
+                "UPDATE items SET state = ?1, substate = ?2, updated_at_us = ?3 WHERE item_id = ?4",
                 params![
                     data.state.to_string(),
+                    data.substate,
                     event.wall_ts_us,
                     event.item_id.as_str(),
                 ],
@@ -589,14 +699,16 @@ impl<'conn> Projector<'conn> {
 
         self.conn
             .execute(
-                "INSERT OR IGNORE INTO item_comments (item_id, event_hash, author, body, created_at_us)
-                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                "INSERT OR IGNORE INTO item_comments
+                 (item_id, event_hash, author, body, created_at_us, reply_to)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
                 params![
                     event.item_id.as_str(),
                     event.event_hash,
                     event.agent,
                     body,
                     event.wall_ts_us,
+                    data.reply_to,
                 ],
             )
             .with_context(|| format!("project comment for {}", event.item_id))?;
@@ -610,6 +722,151 @@ impl<'conn> Projector<'conn> {
         Ok(())
     }
 
+    /// Record an `item.comment_edit` and re-flag the LWW winner among all
+    /// edits of the same comment.
+    fn project_comment_edit(&self, event: &Event) -> Result<()> {
+        let EventData::CommentEdit(ref data) = event.data else {
+            anyhow::bail!("expected CommentEdit data for item.comment_edit event");
+        };
+
+        self.ensure_item_exists(event)?;
+
+        let body = if self.is_event_redacted(&event.event_hash)? {
+            "[redacted]"
+        } else {
+            &data.body
+        };
+
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO item_comment_edits
+                 (event_hash, comment_hash, item_id, author, body, itc, created_at_us)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    event.event_hash,
+                    data.comment,
+                    event.item_id.as_str(),
+                    event.agent,
+                    body,
+                    event.itc,
+                    event.wall_ts_us,
+                ],
+            )
+            .with_context(|| format!("project comment edit for {}", event.item_id))?;
+
+        let edits = query::get_edits_for_comment(self.conn, &data.comment)?;
+        let writes: Vec<conflicts::FieldWrite> = edits
+            .into_iter()
+            .map(|edit| conflicts::FieldWrite {
+                event_hash: edit.event_hash,
+                agent: edit.author,
+                wall_ts_us: edit.created_at_us,
+                itc: edit.itc,
+                value: serde_json::Value::Null,
+            })
+            .collect();
+        if let Some(winner) = conflicts::winner_index(&writes) {
+            self.conn
+                .execute(
+                    "UPDATE item_comment_edits SET is_current = (event_hash = ?1)
+                     WHERE comment_hash = ?2",
+                    params![writes[winner].event_hash, data.comment],
+                )
+                .context("flag current comment edit")?;
+        }
+
+        self.conn.execute(
+            "UPDATE items SET updated_at_us = MAX(updated_at_us, ?1) WHERE item_id = ?2",
+            params![event.wall_ts_us, event.item_id.as_str()],
+        )?;
+
+        Ok(())
+    }
+
+    /// Record an `item.comment_retract`. The earliest retraction of a
+    /// comment is kept.
+    fn project_comment_retract(&self, event: &Event) -> Result<()> {
+        let EventData::CommentRetract(ref data) = event.data else {
+            anyhow::bail!("expected CommentRetract data for item.comment_retract event");
+        };
+
+        self.ensure_item_exists(event)?;
+
+        let reason = if self.is_event_redacted(&event.event_hash)? {
+            data.reason.as_ref().map(|_| "[redacted]".to_string())
+        } else {
+            data.reason.clone()
+        };
+
+        self.conn
+            .execute(
+                "INSERT INTO item_comment_retractions
+                 (comment_hash, item_id, event_hash, retracted_by, reason, retracted_at_us)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                 ON CONFLICT(comment_hash) DO UPDATE SET
+                     event_hash = excluded.event_hash,
+                     retracted_by = excluded.retracted_by,
+                     reason = excluded.reason,
+                     retracted_at_us = excluded.retracted_at_us
+                 WHERE excluded.retracted_at_us < item_comment_retractions.retracted_at_us",
+                params![
+                    data.comment,
+                    event.item_id.as_str(),
+                    event.event_hash,
+                    event.agent,
+                    reason,
+                    event.wall_ts_us,
+                ],
+            )
+            .with_context(|| format!("project comment retraction for {}", event.item_id))?;
+
+        self.conn.execute(
+            "UPDATE items SET updated_at_us = MAX(updated_at_us, ?1) WHERE item_id = ?2",
+            params![event.wall_ts_us, event.item_id.as_str()],
+        )?;
+
+        Ok(())
+    }
+
+    fn project_attach(&self, event: &Event) -> Result<()> {
+        let EventData::Attach(ref data) = event.data else {
+            anyhow::bail!("expected Attach data for item.attach event");
+        };
+
+        self.ensure_item_exists(event)?;
+
+        let name = if self.is_event_redacted(&event.event_hash)? {
+            "[redacted]"
+        } else {
+            &data.name
+        };
+
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO item_attachments
+                 (event_hash, item_id, blob_hash, name, mime, size_bytes, author, created_at_us)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    event.event_hash,
+                    event.item_id.as_str(),
+                    data.blob,
+                    name,
+                    data.mime,
+                    i64::try_from(data.size).unwrap_or(i64::MAX),
+                    event.agent,
+                    event.wall_ts_us,
+                ],
+            )
+            .with_context(|| format!("project attachment for {}", event.item_id))?;
+
+        self.conn.execute(
+            "UPDATE items SET updated_at_us = MAX(updated_at_us, ?1) WHERE item_id = ?2",
+            params![event.wall_ts_us, event.item_id.as_str()],
+        )?;
+
+        Ok(())
+    }
+
     fn project_link(&self, event: &Event) -> Result<()> {
         let EventData::Link(ref data) = event.data else {
             anyhow::bail!("expected Link data for item.link event");
@@ -733,6 +990,68 @@ impl<'conn> Projector<'conn> {
         Ok(())
     }
 
+    /// Apply an `item.patch` to the description if it targets the current
+    /// base. Patches against a superseded base lost to a later whole-value
+    /// write and only bump `updated_at_us`.
+    fn project_patch(&self, event: &Event) -> Result<()> {
+        let EventData::Patch(ref data) = event.data else {
+            anyhow::bail!("expected Patch data for item.patch event");
+        };
+
+        self.ensure_item_exists(event)?;
+
+        let item_id = event.item_id.as_str();
+        let edits = query::get_description_edits(self.conn, item_id)?
+            .filter(|edits| edits.base_hash == data.base);
+        let Some(mut edits) = edits else {
+            tracing::debug!(
+                item_id,
+                base = %data.base,
+                "skipping description patch against a superseded base"
+            );
+            self.conn.execute(
+                "UPDATE items SET updated_at_us = MAX(updated_at_us, ?1) WHERE item_id = ?2",
+                params![event.wall_ts_us, item_id],
+            )?;
+            return Ok(());
+        };
+
+        let ops = if self.is_event_redacted(&event.event_hash)? {
+            let mut scrubbed = event.clone();
+            scrub_payload(&mut scrubbed);
+            match scrubbed.data {
+                EventData::Patch(d) => d.ops,
+                _ => Vec::new(),
+            }
+        } else {
+            data.ops.clone()
+        };
+        for op in &ops {
+            edits.seq.apply(op);
+        }
+
+        let seq_json = serde_json::to_string(&edits.seq).context("serialize description edits")?;
+        self.conn
+            .execute(
+                "UPDATE item_description_edits SET seq_json = ?1, updated_at_us = ?2
+                 WHERE item_id = ?3",
+                params![seq_json, event.wall_ts_us, item_id],
+            )
+            .with_context(|| format!("store description edits for {item_id}"))?;
+        self.conn
+            .execute(
+                "UPDATE items SET description = ?1, updated_at_us = ?2 WHERE item_id = ?3",
+                params![
+                    edits.seq.render(&edits.base_text),
+                    event.wall_ts_us,
+                    item_id
+                ],
+            )
+            .with_context(|| format!("project patch for {item_id}"))?;
+
+        Ok(())
+    }
+
     fn project_redact(&self, event: &Event) -> Result<()> {
         let EventData::Redact(ref data) = event.data else {
             anyhow::bail!("expected Redact data for item.redact event");
@@ -766,6 +1085,36 @@ impl<'conn> Projector<'conn> {
                 params![data.target_hash],
             )
             .context("redact comment body")?;
+        self.conn
+            .execute(
+                "UPDATE item_comment_edits SET body = '[redacted]' WHERE event_hash = ?1",
+                params![data.target_hash],
+            )
+            .context("redact comment edit body")?;
+        self.conn
+            .execute(
+                "UPDATE item_comment_retractions SET reason = '[redacted]'
+                 WHERE event_hash = ?1 AND reason IS NOT NULL",
+                params![data.target_hash],
+            )
+            .context("redact comment retraction reason")?;
+        self.conn
+            .execute(
+                "UPDATE item_attachments SET name = '[redacted]' WHERE event_hash = ?1",
+                params![data.target_hash],
+            )
+            .context("redact attachment name")?;
+
+        conflicts::scrub_heads(self.conn, &data.target_hash)?;
+
+        // Item fields need the target's payload to know which values to
+        // replace, so they are only scrubbed when the log is at hand.
+        if let Some(shard_mgr) = self.event_log {
+            let targets = std::collections::HashSet::from([data.target_hash.clone()]);
+            for target in crate::redact::load_targets(shard_mgr, &targets)? {
+                crate::redact::scrub_projection(self.conn, &target)?;
+            }
+        }
 
         Ok(())
     }
@@ -774,6 +1123,30 @@ impl<'conn> Projector<'conn> {
     // Helpers
     // -----------------------------------------------------------------------
 
+    /// Make `event` the base for subsequent description patches, or forget
+    /// the base when the description was cleared or redacted.
+    fn set_description_base(&self, event: &Event, text: Option<&str>) -> Result<()> {
+        let item_id = event.item_id.as_str();
+        text.map_or_else(
+            || {
+                self.conn.execute(
+                    "DELETE FROM item_description_edits WHERE item_id = ?1",
+                    params![item_id],
+                )
+            },
+            |text| {
+                self.conn.execute(
+                    "INSERT OR REPLACE INTO item_description_edits
+                        (item_id, base_hash, base_text, updated_at_us)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![item_id, event.event_hash, text, event.wall_ts_us],
+                )
+            },
+        )
+        .with_context(|| format!("record description base for {item_id}"))?;
+        Ok(())
+    }
+
     /// Ensure the item exists in the projection. If not, create a placeholder
     /// row so that subsequent operations (UPDATE, foreign keys) succeed.
     ///
@@ -899,8 +1272,9 @@ fn projected_events_has_agent_column(conn: &Connection) -> Result<bool> {
 
 /// Drop all projection data for a full rebuild.
 ///
-/// Clears all items, edge tables, comments, redactions, FTS index, and the
-/// projected events tracking table. Schema structure is preserved.
+/// Clears all items, edge tables, comments, redactions, transitions, FTS
+/// index, and the projected events tracking table. Schema structure is
+/// preserved.
 ///
 /// # Errors
 ///
@@ -908,6 +1282,13 @@ fn projected_events_has_agent_column(conn: &Connection) -> Result<bool> {
 pub fn clear_projection(conn: &Connection) -> Result<()> {
     conn.execute_batch(
         "DELETE FROM event_redactions;
+         DELETE FROM item_custom_fields;
+         DELETE FROM item_description_edits;
+         DELETE FROM item_field_heads;
+         DELETE FROM item_comment_edits;
+         DELETE FROM item_comment_retractions;
+         DELETE FROM item_attachments;
+         DELETE FROM item_transitions;
          DELETE FROM item_comments;
          DELETE FROM item_dependencies;
          DELETE FROM item_assignees;
@@ -927,6 +1308,7 @@ pub fn clear_projection(conn: &Connection) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crdt::text::{TextId, TextOp, TextSeq};
     use crate::db::{migrations, query};
     use crate::event::data::*;
     use crate::event::types::EventType;
@@ -961,9 +1343,24 @@ mod tests {
             item_id: ItemId::new_unchecked(item_id),
             data,
             event_hash: format!("blake3:{hash}"),
+            signature: None,
         }
     }
 
+    fn make_patch(item_id: &str, base: &str, ops: Vec<TextOp>, hash: &str, ts: i64) -> Event {
+        make_event(
+            EventType::Patch,
+            item_id,
+            EventData::Patch(PatchData {
+                base: base.into(),
+                ops,
+                extra: BTreeMap::new(),
+            }),
+            hash,
+            ts,
+        )
+    }
+
     fn make_create(id: &str, title: &str, hash: &str, ts: i64) -> Event {
         make_event(
             EventType::Create,
@@ -1107,6 +1504,71 @@ mod tests {
         assert_eq!(item.description.as_deref(), Some("Updated description"));
     }
 
+    #[test]
+    fn project_patch_edits_description_on_current_base() {
+        let conn = test_db();
+        let projector = Projector::new(&conn);
+        projector
+            .project_event(&make_create("bn-001", "Item", "c1", 1000))
+            .unwrap();
+
+        let base = "A detailed description";
+        let seq = TextSeq::new();
+        let alice = seq.diff(base, "A detailed description.", "alice");
+        let bob = seq.diff(base, "A short description", "bob");
+        projector
+            .project_event(&make_patch("bn-001", "blake3:c1", alice, "p1", 2000))
+            .unwrap();
+        projector
+            .project_event(&make_patch("bn-001", "blake3:c1", bob, "p2", 2001))
+            .unwrap();
+
+        let item = query::get_item(&conn, "bn-001", false).unwrap().unwrap();
+        assert_eq!(item.description.as_deref(), Some("A short description."));
+        assert_eq!(item.updated_at_us, 2001);
+
+        let edits = query::get_description_edits(&conn, "bn-001")
+            .unwrap()
+            .expect("edits row");
+        assert_eq!(edits.base_hash, "blake3:c1");
+        assert_eq!(edits.seq.render(&edits.base_text), "A short description.");
+    }
+
+    #[test]
+    fn project_patch_against_superseded_base_is_ignored() {
+        let conn = test_db();
+        let projector = Projector::new(&conn);
+        projector
+            .project_event(&make_create("bn-001", "Item", "c1", 1000))
+            .unwrap();
+        projector
+            .project_event(&make_event(
+                EventType::Update,
+                "bn-001",
+                EventData::Update(UpdateData {
+                    field: "description".into(),
+                    value: serde_json::json!("Rewritten"),
+                    extra: BTreeMap::new(),
+                }),
+                "u1",
+                2000,
+            ))
+            .unwrap();
+
+        let stale = TextSeq::new().diff("A detailed description", "Stale edit", "bob");
+        projector
+            .project_event(&make_patch("bn-001", "blake3:c1", stale, "p1", 3000))
+            .unwrap();
+
+        let item = query::get_item(&conn, "bn-001", false).unwrap().unwrap();
+        assert_eq!(item.description.as_deref(), Some("Rewritten"));
+        assert_eq!(item.updated_at_us, 3000);
+        let edits = query::get_description_edits(&conn, "bn-001")
+            .unwrap()
+            .expect("edits row");
+        assert_eq!(edits.base_hash, "blake3:u1");
+    }
+
     #[test]
     fn project_update_labels() {
         let conn = test_db();
@@ -1134,6 +1596,89 @@ mod tests {
         assert_eq!(labels[1].label, "urgent");
     }
 
+    #[test]
+    fn project_update_due_sets_and_clears() {
+        let conn = test_db();
+        let projector = Projector::new(&conn);
+        projector
+            .project_event(&make_create("bn-001", "Item", "aaa", 1000))
+            .unwrap();
+
+        let set_due = make_event(
+            EventType::Update,
+            "bn-001",
+            EventData::Update(UpdateData {
+                field: "due".into(),
+                value: serde_json::json!("2026-03-15T23:59:59Z"),
+                extra: BTreeMap::new(),
+            }),
+            "bbb",
+            2000,
+        );
+        projector.project_event(&set_due).unwrap();
+
+        let item = query::get_item(&conn, "bn-001", false).unwrap().unwrap();
+        assert_eq!(item.due_at_us, Some(1_773_619_199_000_000));
+
+        let clear_due = make_event(
+            EventType::Update,
+            "bn-001",
+            EventData::Update(UpdateData {
+                field: "due".into(),
+                value: serde_json::Value::Null,
+                extra: BTreeMap::new(),
+            }),
+            "ccc",
+            3000,
+        );
+        projector.project_event(&clear_due).unwrap();
+
+        let item = query::get_item(&conn, "bn-001", false).unwrap().unwrap();
+        assert_eq!(item.due_at_us, None);
+        assert_eq!(item.updated_at_us, 3000);
+    }
+
+    #[test]
+    fn project_update_custom_field_sets_and_clears() {
+        let conn = test_db();
+        let projector = Projector::new(&conn);
+        projector
+            .project_event(&make_create("bn-001", "Item", "aaa", 1000))
+            .unwrap();
+
+        let update = |value: serde_json::Value, hash: &str, ts: i64| {
+            make_event(
+                EventType::Update,
+                "bn-001",
+                EventData::Update(UpdateData {
+                    field: "custom.severity".into(),
+                    value,
+                    extra: BTreeMap::new(),
+                }),
+                hash,
+                ts,
+            )
+        };
+
+        projector
+            .project_event(&update(serde_json::json!(3), "bbb", 2000))
+            .unwrap();
+        let fields = query::get_custom_fields(&conn, "bn-001").unwrap();
+        assert_eq!(fields.get("severity"), Some(&serde_json::json!(3)));
+
+        projector
+            .project_event(&update(serde_json::Value::Null, "ccc", 3000))
+            .unwrap();
+        assert!(
+            query::get_custom_fields(&conn, "bn-001")
+                .unwrap()
+                .is_empty()
+        );
+
+        let item = query::get_item(&conn, "bn-001", false).unwrap().unwrap();
+        assert_eq!(item.updated_at_us, 3000);
+    }
+
     #[test]
     fn project_update_unknown_field_bumps_updated() {
         let conn = test_db();
@@ -1177,6 +1722,7 @@ mod tests {
             EventData::Move(MoveData {
                 state: State::Doing,
                 reason: Some("Starting work".into()),
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             "bbb",
@@ -1186,6 +1732,94 @@ mod tests {
 
         let item = query::get_item(&conn, "bn-001", false).unwrap().unwrap();
         assert_eq!(item.state, "doing");
+        assert_eq!(item.substate, None);
+    }
+
+    #[test]
+    fn project_move_records_transitions() {
+        let conn = test_db();
+        let projector = Projector::new(&conn);
+        projector
+            .project_event(&make_create("bn-001", "Item", "aaa", 1000))
+            .unwrap();
+        let mv = |state, hash, ts| {
+            make_event(
+                EventType::Move,
+                "bn-001",
+                EventData::Move(MoveData {
+                    state,
+                    reason: None,
+                    substate: None,
+                    extra: BTreeMap::new(),
+                }),
+                hash,
+                ts,
+            )
+        };
+        projector
+            .project_event(&mv(State::Doing, "bbb", 2000))
+            .unwrap();
+        projector
+            .project_event(&mv(State::Done, "ccc", 3000))
+            .unwrap();
+
+        let transitions: Vec<(String, String, i64)> = conn
+            .prepare(
+                "SELECT from_state, to_state, at_us FROM item_transitions
+                 WHERE item_id = 'bn-001' ORDER BY at_us",
+            )
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            transitions,
+            vec![
+                ("open".to_string(), "doing".to_string(), 2000),
+                ("doing".to_string(), "done".to_string(), 3000),
+            ]
+        );
+    }
+
+    #[test]
+    fn project_move_records_and_clears_substate() {
+        let conn = test_db();
+        let projector = Projector::new(&conn);
+        projector
+            .project_event(&make_create("bn-001", "Item", "aaa", 1000))
+            .unwrap();
+        let mv = |substate: Option<&str>, hash, ts| {
+            make_event(
+                EventType::Move,
+                "bn-001",
+                EventData::Move(MoveData {
+                    state: State::Doing,
+                    substate: substate.map(str::to_string),
+                    reason: None,
+                    extra: BTreeMap::new(),
+                }),
+                hash,
+                ts,
+            )
+        };
+
+        projector
+            .project_event(&mv(Some("review"), "bbb", 2000))
+            .unwrap();
+        let item = query::get_item(&conn, "bn-001", false).unwrap().unwrap();
+        assert_eq!(item.state, "doing");
+        assert_eq!(item.substate.as_deref(), Some("review"));
+
+        let filter = query::ItemFilter {
+            substate: Some("review".into()),
+            ..Default::default()
+        };
+        assert_eq!(query::list_items(&conn, &filter).unwrap().len(), 1);
+
+        projector.project_event(&mv(None, "ccc", 3000)).unwrap();
+        let item = query::get_item(&conn, "bn-001", false).unwrap().unwrap();
+        assert_eq!(item.substate, None);
     }
 
     // -----------------------------------------------------------------------
@@ -1253,6 +1887,7 @@ mod tests {
             "bn-001",
             EventData::Comment(CommentData {
                 body: "This is a comment".into(),
+                reply_to: None,
                 extra: BTreeMap::new(),
             }),
             "bbb",
@@ -1267,6 +1902,153 @@ mod tests {
         assert_eq!(comments[0].event_hash, "blake3:bbb");
     }
 
+    fn make_comment_edit(comment: &str, body: &str, hash: &str, ts: i64) -> Event {
+        make_event(
+            EventType::CommentEdit,
+            "bn-001",
+            EventData::CommentEdit(CommentEditData {
+                comment: comment.into(),
+                body: body.into(),
+                extra: BTreeMap::new(),
+            }),
+            hash,
+            ts,
+        )
+    }
+
+    #[test]
+    fn project_comment_edit_latest_wins_regardless_of_order() {
+        let conn = test_db();
+        let projector = Projector::new(&conn);
+        projector
+            .project_event(&make_create("bn-001", "Item", "aaa", 1000))
+            .unwrap();
+        projector
+            .project_event(&make_event(
+                EventType::Comment,
+                "bn-001",
+                EventData::Comment(CommentData {
+                    body: "v1".into(),
+                    reply_to: None,
+                    extra: BTreeMap::new(),
+                }),
+                "bbb",
+                2000,
+            ))
+            .unwrap();
+
+        // The newer edit arrives first (e.g. after a sync).
+        projector
+            .project_event(&make_comment_edit("blake3:bbb", "v3", "ddd", 4000))
+            .unwrap();
+        projector
+            .project_event(&make_comment_edit("blake3:bbb", "v2", "ccc", 3000))
+            .unwrap();
+
+        let comments = query::get_comments(&conn, "bn-001", None, None).unwrap();
+        assert_eq!(comments[0].body, "v3");
+        assert_eq!(comments[0].original_body, "v1");
+        assert_eq!(comments[0].edited_at_us, Some(4000));
+        assert_eq!(query::get_comment_edits(&conn, "bn-001").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn project_comment_retract_marks_comment() {
+        let conn = test_db();
+        let projector = Projector::new(&conn);
+        projector
+            .project_event(&make_create("bn-001", "Item", "aaa", 1000))
+            .unwrap();
+        projector
+            .project_event(&make_event(
+                EventType::Comment,
+                "bn-001",
+                EventData::Comment(CommentData {
+                    body: "oops".into(),
+                    reply_to: None,
+                    extra: BTreeMap::new(),
+                }),
+                "bbb",
+                2000,
+            ))
+            .unwrap();
+        projector
+            .project_event(&make_event(
+                EventType::CommentRetract,
+                "bn-001",
+                EventData::CommentRetract(CommentRetractData {
+                    comment: "blake3:bbb".into(),
+                    reason: Some("wrong bone".into()),
+                    extra: BTreeMap::new(),
+                }),
+                "ccc",
+                3000,
+            ))
+            .unwrap();
+
+        let comments = query::get_comments(&conn, "bn-001", None, None).unwrap();
+        assert_eq!(comments[0].retracted_by.as_deref(), Some("test-agent"));
+        assert!(query::thread_comments(comments).is_empty());
+    }
+
+    #[test]
+    fn project_attach_and_redact_attachment() {
+        let conn = test_db();
+        let projector = Projector::new(&conn);
+        projector
+            .project_event(&make_create("bn-001", "Item", "aaa", 1000))
+            .unwrap();
+        let attach = |hash: &str, ts: i64| {
+            make_event(
+                EventType::Attach,
+                "bn-001",
+                EventData::Attach(AttachData {
+                    blob: "blake3:blob1".into(),
+                    name: "ci.log".into(),
+                    mime: "text/plain".into(),
+                    size: 12,
+                    extra: BTreeMap::new(),
+                }),
+                hash,
+                ts,
+            )
+        };
+        projector.project_event(&attach("bbb", 2000)).unwrap();
+        projector.project_event(&attach("ccc", 3000)).unwrap();
+
+        let attachments = query::get_attachments(&conn, "bn-001").unwrap();
+        assert_eq!(attachments.len(), 2);
+        assert_eq!(attachments[0].name, "ci.log");
+        assert_eq!(attachments[0].size_bytes, 12);
+        assert_eq!(
+            query::count_live_blob_references(&conn, "blake3:blob1").unwrap(),
+            2
+        );
+
+        projector
+            .project_event(&make_event(
+                EventType::Redact,
+                "bn-001",
+                EventData::Redact(RedactData {
+                    target_hash: "blake3:bbb".into(),
+                    reason: "leaked token".into(),
+                    extra: BTreeMap::new(),
+                }),
+                "ddd",
+                4000,
+            ))
+            .unwrap();
+
+        let attachments = query::get_attachments(&conn, "bn-001").unwrap();
+        assert!(attachments[0].redacted);
+        assert_eq!(attachments[0].name, "[redacted]");
+        assert!(!attachments[1].redacted);
+        assert_eq!(
+            query::count_live_blob_references(&conn, "blake3:blob1").unwrap(),
+            1
+        );
+    }
+
     // -----------------------------------------------------------------------
     // Link / Unlink
     // -----------------------------------------------------------------------
@@ -1431,6 +2213,7 @@ mod tests {
             "bn-001",
             EventData::Comment(CommentData {
                 body: "Secret password: hunter2".into(),
+                reply_to: None,
                 extra: BTreeMap::new(),
             }),
             "comment_hash",
@@ -1523,6 +2306,7 @@ mod tests {
                 EventData::Move(MoveData {
                     state: State::Doing,
                     reason: None,
+                    substate: None,
                     extra: BTreeMap::new(),
                 }),
                 "h2",
@@ -1544,6 +2328,7 @@ mod tests {
                 "bn-001",
                 EventData::Comment(CommentData {
                     body: "Working on it".into(),
+                    reply_to: None,
                     extra: BTreeMap::new(),
                 }),
                 "h4",
@@ -1566,6 +2351,7 @@ mod tests {
                 EventData::Move(MoveData {
                     state: State::Done,
                     reason: Some("Shipped".into()),
+                    substate: None,
                     extra: BTreeMap::new(),
                 }),
                 "h6",
@@ -1670,6 +2456,7 @@ mod tests {
             "bn-ghost",
             EventData::Comment(CommentData {
                 body: "Comment on missing item".into(),
+                reply_to: None,
                 extra: BTreeMap::new(),
             }),
             "ccc",
@@ -1757,7 +2544,7 @@ mod tests {
     }
 
     // -----------------------------------------------------------------------
-    // All 11 event types in sequence
+    // All 12 event types in sequence
     // -----------------------------------------------------------------------
 
     #[test]
@@ -1792,6 +2579,7 @@ mod tests {
             EventData::Move(MoveData {
                 state: State::Doing,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             "h04",
@@ -1817,6 +2605,7 @@ mod tests {
             "bn-001",
             EventData::Comment(CommentData {
                 body: "Found root cause".into(),
+                reply_to: None,
                 extra: BTreeMap::new(),
             }),
             "h06",
@@ -1886,7 +2675,20 @@ mod tests {
             10000,
         ));
 
-        // 11. Delete
+        // 11. Patch the description
+        events.push(make_patch(
+            "bn-001",
+            "blake3:h01",
+            vec![TextOp::Insert {
+                id: TextId::new(100, "test-agent"),
+                after: Some(TextId::base(1)),
+                text: "very ".into(),
+            }],
+            "h13",
+            10500,
+        ));
+
+        // 12. Delete
         events.push(make_event(
             EventType::Delete,
             "bn-001",
@@ -1899,13 +2701,17 @@ mod tests {
         ));
 
         let stats = projector.project_batch(&events).unwrap();
-        assert_eq!(stats.projected, 12); // 2 creates + 10 mutations
+        assert_eq!(stats.projected, 13); // 2 creates + 11 mutations
         assert_eq!(stats.duplicates, 0);
         assert_eq!(stats.errors, 0);
 
         // Verify final state
         let item = query::get_item(&conn, "bn-001", true).unwrap().unwrap();
         assert_eq!(item.title, "Auth timeout bug");
+        assert_eq!(
+            item.description.as_deref(),
+            Some("A very detailed description")
+        );
         assert_eq!(item.state, "doing");
         assert!(item.is_deleted);
         assert_eq!(
@@ -1950,6 +2756,7 @@ mod tests {
             "bn-late",
             EventData::Comment(CommentData {
                 body: "Comment first".into(),
+                reply_to: None,
                 extra: BTreeMap::new(),
             }),
             "h1",
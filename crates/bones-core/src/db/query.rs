@@ -9,10 +9,13 @@
 
 use anyhow::{Context, Result, bail};
 use rusqlite::{Connection, params, params_from_iter};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::{self, Write as _};
 use std::str::FromStr;
 
+use crate::crdt::text::TextSeq;
+use crate::db::filter_query::FilterQuery;
+
 // ---------------------------------------------------------------------------
 // Result types
 // ---------------------------------------------------------------------------
@@ -34,9 +37,15 @@ pub struct QueryItem {
     pub search_labels: String,
     pub created_at_us: i64,
     pub updated_at_us: i64,
+    pub due_at_us: Option<i64>,
+    /// Configured workflow sub-state within `state`, if any.
+    pub substate: Option<String>,
 }
 
 /// A comment attached to a work item.
+///
+/// `body` is the current text: the winning `item.comment_edit` if the
+/// comment was edited, otherwise the original.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct QueryComment {
     pub comment_id: i64,
@@ -45,6 +54,42 @@ pub struct QueryComment {
     pub author: String,
     pub body: String,
     pub created_at_us: i64,
+    /// Body as originally written, before any edit.
+    pub original_body: String,
+    /// Hash of the comment this one replies to.
+    pub reply_to: Option<String>,
+    /// Timestamp of the winning edit, if the comment was edited.
+    pub edited_at_us: Option<i64>,
+    /// Agent that retracted the comment, if it was retracted.
+    pub retracted_by: Option<String>,
+}
+
+/// One `item.comment_edit` of a comment, for edit history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryCommentEdit {
+    pub event_hash: String,
+    pub comment_hash: String,
+    pub author: String,
+    pub body: String,
+    pub itc: String,
+    pub created_at_us: i64,
+    /// Whether this edit is the LWW winner for its comment.
+    pub is_current: bool,
+}
+
+/// An attachment on an item (`item.attach`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryAttachment {
+    pub event_hash: String,
+    pub item_id: String,
+    pub blob_hash: String,
+    pub name: String,
+    pub mime: String,
+    pub size_bytes: i64,
+    pub author: String,
+    pub created_at_us: i64,
+    /// Whether the attach event has been redacted (its blob may be gone).
+    pub redacted: bool,
 }
 
 /// A dependency edge between two items.
@@ -87,6 +132,17 @@ pub struct SearchHit {
     pub rank: f64,
 }
 
+/// The description write that `item.patch` events currently apply to, with
+/// the sequence edits accumulated on top of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryDescriptionEdits {
+    pub item_id: String,
+    /// Hash of the `item.create` / `item.update` that wrote the base text.
+    pub base_hash: String,
+    pub base_text: String,
+    pub seq: TextSeq,
+}
+
 /// Aggregate counters for project-level stats used by reporting commands.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProjectStats {
@@ -120,22 +176,25 @@ pub enum SortOrder {
     UpdatedAsc,
     /// Urgency order: urgent > default > punt, then `updated_at` DESC.
     Priority,
+    /// Earliest due date first; items without a due date sort last.
+    DueAsc,
 }
 
 impl SortOrder {
     const fn sql_clause(self) -> &'static str {
         match self {
-            Self::CreatedDesc => "ORDER BY created_at_us DESC, i.item_id ASC",
-            Self::CreatedAsc => "ORDER BY created_at_us ASC, i.item_id ASC",
-            Self::UpdatedDesc => "ORDER BY updated_at_us DESC, i.item_id ASC",
-            Self::UpdatedAsc => "ORDER BY updated_at_us ASC, i.item_id ASC",
+            Self::CreatedDesc => "ORDER BY i.created_at_us DESC, i.item_id ASC",
+            Self::CreatedAsc => "ORDER BY i.created_at_us ASC, i.item_id ASC",
+            Self::UpdatedDesc => "ORDER BY i.updated_at_us DESC, i.item_id ASC",
+            Self::UpdatedAsc => "ORDER BY i.updated_at_us ASC, i.item_id ASC",
             Self::Priority => {
-                "ORDER BY CASE urgency \
+                "ORDER BY CASE i.urgency \
                  WHEN 'urgent' THEN 0 \
                  WHEN 'default' THEN 1 \
                  WHEN 'punt' THEN 2 \
-                 END ASC, updated_at_us DESC, item_id ASC"
+                 END ASC, i.updated_at_us DESC, i.item_id ASC"
             }
+            Self::DueAsc => "ORDER BY i.due_at_us IS NULL, i.due_at_us ASC, i.item_id ASC",
         }
     }
 }
@@ -148,6 +207,7 @@ impl fmt::Display for SortOrder {
             Self::UpdatedDesc => f.write_str("updated_desc"),
             Self::UpdatedAsc => f.write_str("updated_asc"),
             Self::Priority => f.write_str("priority"),
+            Self::DueAsc => f.write_str("due"),
         }
     }
 }
@@ -162,8 +222,9 @@ impl FromStr for SortOrder {
             "updated_desc" | "updated-desc" | "recent" => Ok(Self::UpdatedDesc),
             "updated_asc" | "updated-asc" | "stale" => Ok(Self::UpdatedAsc),
             "priority" | "triage" => Ok(Self::Priority),
+            "due" | "due_asc" | "due-asc" | "deadline" => Ok(Self::DueAsc),
             other => bail!(
-                "unknown sort order '{other}': expected one of created_desc, created_asc, updated_desc, updated_asc, priority"
+                "unknown sort order '{other}': expected one of created_desc, created_asc, updated_desc, updated_asc, priority, due"
             ),
         }
     }
@@ -181,6 +242,8 @@ impl FromStr for SortOrder {
 pub struct ItemFilter {
     /// Filter by lifecycle state (exact match).
     pub state: Option<String>,
+    /// Filter by workflow sub-state (exact match).
+    pub substate: Option<String>,
     /// Filter by item kind (exact match).
     pub kind: Option<String>,
     /// Filter by urgency (exact match).
@@ -191,6 +254,12 @@ pub struct ItemFilter {
     pub assignee: Option<String>,
     /// Filter by `parent_id` (exact match).
     pub parent_id: Option<String>,
+    /// Only items due at or before this instant (µs since epoch).
+    pub due_before: Option<i64>,
+    /// Custom field equality filters as `(name, canonical text)` pairs.
+    pub custom_fields: Vec<(String, String)>,
+    /// Structured filter query (`bn list --where`), combined with the other fields using AND.
+    pub query: Option<FilterQuery>,
     /// Include soft-deleted items (default: false).
     pub include_deleted: bool,
     /// Maximum number of results.
@@ -199,6 +268,20 @@ pub struct ItemFilter {
     pub offset: Option<u32>,
     /// Sort order.
     pub sort: SortOrder,
+    /// Sort by a custom field first, falling back to `sort` for ties.
+    pub sort_custom: Option<CustomFieldSort>,
+}
+
+/// Ordering by a custom field value.
+///
+/// Integer fields sort numerically; everything else sorts by text. Items
+/// without the field always sort last.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomFieldSort {
+    /// Custom field name (without the `custom.` prefix).
+    pub name: String,
+    /// Sort highest values first.
+    pub descending: bool,
 }
 
 // ---------------------------------------------------------------------------
@@ -274,12 +357,12 @@ pub fn get_item(
     let sql = if include_deleted {
         "SELECT item_id, title, description, kind, state, urgency, size, \
          parent_id, compact_summary, is_deleted, deleted_at_us, \
-         search_labels, created_at_us, updated_at_us \
+         search_labels, created_at_us, updated_at_us, due_at_us, substate \
          FROM items WHERE item_id = ?1"
     } else {
         "SELECT item_id, title, description, kind, state, urgency, size, \
          parent_id, compact_summary, is_deleted, deleted_at_us, \
-         search_labels, created_at_us, updated_at_us \
+         search_labels, created_at_us, updated_at_us, due_at_us, substate \
          FROM items WHERE item_id = ?1 AND is_deleted = 0"
     };
 
@@ -315,6 +398,11 @@ pub fn list_items(conn: &Connection, filter: &ItemFilter) -> Result<Vec<QueryIte
         conditions.push(format!("i.state = ?{}", param_values.len()));
     }
 
+    if let Some(ref substate) = filter.substate {
+        param_values.push(Box::new(substate.clone()));
+        conditions.push(format!("i.substate = ?{}", param_values.len()));
+    }
+
     if let Some(ref kind) = filter.kind {
         param_values.push(Box::new(kind.clone()));
         conditions.push(format!("i.kind = ?{}", param_values.len()));
@@ -330,6 +418,18 @@ pub fn list_items(conn: &Connection, filter: &ItemFilter) -> Result<Vec<QueryIte
         conditions.push(format!("i.parent_id = ?{}", param_values.len()));
     }
 
+    if let Some(due_before) = filter.due_before {
+        param_values.push(Box::new(due_before));
+        conditions.push(format!(
+            "i.due_at_us IS NOT NULL AND i.due_at_us <= ?{}",
+            param_values.len()
+        ));
+    }
+
+    if let Some(ref query) = filter.query {
+        conditions.push(query.to_sql(&mut param_values));
+    }
+
     // Label and assignee filters require JOINs
     let mut joins = String::new();
     if let Some(ref label) = filter.label {
@@ -350,14 +450,32 @@ pub fn list_items(conn: &Connection, filter: &ItemFilter) -> Result<Vec<QueryIte
         );
     }
 
+    push_custom_field_joins(filter, &mut joins, &mut param_values);
+
+    let base_sort = filter.sort.sql_clause();
+    let sort_clause = filter.sort_custom.as_ref().map_or_else(
+        || base_sort.to_string(),
+        |custom| {
+            param_values.push(Box::new(custom.name.clone()));
+            let _ = write!(
+                joins,
+                " LEFT JOIN item_custom_fields cs ON cs.item_id = i.item_id AND cs.name = ?{}",
+                param_values.len()
+            );
+            let dir = if custom.descending { "DESC" } else { "ASC" };
+            format!(
+                "ORDER BY cs.item_id IS NULL, cs.value_num {dir}, cs.value {dir}, {}",
+                base_sort.strip_prefix("ORDER BY ").unwrap_or(base_sort)
+            )
+        },
+    );
+
     let where_clause = if conditions.is_empty() {
         String::new()
     } else {
         format!(" WHERE {}", conditions.join(" AND "))
     };
 
-    let sort_clause = filter.sort.sql_clause();
-
     let limit_clause = match (filter.limit, filter.offset) {
         (Some(limit), Some(offset)) => format!(" LIMIT {limit} OFFSET {offset}"),
         (Some(limit), None) => format!(" LIMIT {limit}"),
@@ -368,7 +486,7 @@ pub fn list_items(conn: &Connection, filter: &ItemFilter) -> Result<Vec<QueryIte
     let sql = format!(
         "SELECT i.item_id, i.title, i.description, i.kind, i.state, i.urgency, i.size, \
          i.parent_id, i.compact_summary, i.is_deleted, i.deleted_at_us, \
-         i.search_labels, i.created_at_us, i.updated_at_us \
+         i.search_labels, i.created_at_us, i.updated_at_us, i.due_at_us, i.substate \
          FROM items i{joins}{where_clause} {sort_clause}{limit_clause}"
     );
 
@@ -390,6 +508,25 @@ pub fn list_items(conn: &Connection, filter: &ItemFilter) -> Result<Vec<QueryIte
     Ok(items)
 }
 
+/// Append one `INNER JOIN` per custom field equality filter.
+fn push_custom_field_joins(
+    filter: &ItemFilter,
+    joins: &mut String,
+    param_values: &mut Vec<Box<dyn rusqlite::types::ToSql>>,
+) {
+    for (idx, (name, value)) in filter.custom_fields.iter().enumerate() {
+        param_values.push(Box::new(name.clone()));
+        param_values.push(Box::new(value.clone()));
+        let _ = write!(
+            joins,
+            " INNER JOIN item_custom_fields cf{idx} ON cf{idx}.item_id = i.item_id \
+             AND cf{idx}.name = ?{} AND cf{idx}.value = ?{}",
+            param_values.len() - 1,
+            param_values.len()
+        );
+    }
+}
+
 /// Search items via FTS5 full-text search with BM25 ranking.
 ///
 /// Column weights: title 3×, description 2×, labels 1×.
@@ -454,6 +591,81 @@ pub fn get_labels(conn: &Connection, item_id: &str) -> Result<Vec<QueryLabel>> {
     Ok(labels)
 }
 
+/// Get all custom field values for an item, keyed by field name.
+///
+/// Integer fields come back as JSON numbers, everything else as strings.
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub fn get_custom_fields(
+    conn: &Connection,
+    item_id: &str,
+) -> Result<BTreeMap<String, serde_json::Value>> {
+    let sql = "SELECT name, value, value_num \
+               FROM item_custom_fields WHERE item_id = ?1 \
+               ORDER BY name";
+
+    let mut stmt = conn.prepare(sql).context("prepare get_custom_fields")?;
+    let rows = stmt
+        .query_map(params![item_id], |row| {
+            let name: String = row.get(0)?;
+            let value: String = row.get(1)?;
+            let num: Option<i64> = row.get(2)?;
+            Ok((
+                name,
+                num.map_or(serde_json::Value::String(value), serde_json::Value::from),
+            ))
+        })
+        .context("execute get_custom_fields")?;
+
+    let mut fields = BTreeMap::new();
+    for row in rows {
+        let (name, value) = row.context("read custom field row")?;
+        fields.insert(name, value);
+    }
+    Ok(fields)
+}
+
+/// Get the description edit state for an item, if its description has a
+/// patchable base.
+///
+/// # Errors
+///
+/// Returns an error if the query fails or the stored sequence is corrupt.
+pub fn get_description_edits(
+    conn: &Connection,
+    item_id: &str,
+) -> Result<Option<QueryDescriptionEdits>> {
+    let result = conn.query_row(
+        "SELECT base_hash, base_text, seq_json \
+         FROM item_description_edits WHERE item_id = ?1",
+        params![item_id],
+        |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        },
+    );
+
+    let (base_hash, base_text, seq_json) = match result {
+        Ok(row) => row,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+        Err(e) => return Err(e).context(format!("get_description_edits for '{item_id}'")),
+    };
+    let seq = serde_json::from_str(&seq_json)
+        .with_context(|| format!("decode description edits for '{item_id}'"))?;
+
+    Ok(Some(QueryDescriptionEdits {
+        item_id: item_id.to_string(),
+        base_hash,
+        base_text,
+        seq,
+    }))
+}
+
 /// List global label usage counts across all items.
 ///
 /// # Errors
@@ -543,9 +755,13 @@ pub fn get_comments(
     };
 
     let sql = format!(
-        "SELECT comment_id, item_id, event_hash, author, body, created_at_us \
-         FROM item_comments WHERE item_id = ?1 \
-         ORDER BY created_at_us DESC{limit_clause}"
+        "SELECT c.comment_id, c.item_id, c.event_hash, c.author, COALESCE(e.body, c.body), \
+                c.created_at_us, c.body, c.reply_to, e.created_at_us, r.retracted_by \
+         FROM item_comments c \
+         LEFT JOIN item_comment_edits e ON e.comment_hash = c.event_hash AND e.is_current = 1 \
+         LEFT JOIN item_comment_retractions r ON r.comment_hash = c.event_hash \
+         WHERE c.item_id = ?1 \
+         ORDER BY c.created_at_us DESC{limit_clause}"
     );
 
     let mut stmt = conn.prepare(&sql).context("prepare get_comments")?;
@@ -558,6 +774,10 @@ pub fn get_comments(
                 author: row.get(3)?,
                 body: row.get(4)?,
                 created_at_us: row.get(5)?,
+                original_body: row.get(6)?,
+                reply_to: row.get(7)?,
+                edited_at_us: row.get(8)?,
+                retracted_by: row.get(9)?,
             })
         })
         .context("execute get_comments")?;
@@ -569,6 +789,185 @@ pub fn get_comments(
     Ok(comments)
 }
 
+fn query_comment_edits(
+    conn: &Connection,
+    filter_column: &str,
+    value: &str,
+) -> Result<Vec<QueryCommentEdit>> {
+    let sql = format!(
+        "SELECT event_hash, comment_hash, author, body, itc, created_at_us, is_current \
+         FROM item_comment_edits WHERE {filter_column} = ?1 \
+         ORDER BY created_at_us ASC, event_hash ASC"
+    );
+    let mut stmt = conn.prepare(&sql).context("prepare comment edits")?;
+    let rows = stmt
+        .query_map(params![value], |row| {
+            Ok(QueryCommentEdit {
+                event_hash: row.get(0)?,
+                comment_hash: row.get(1)?,
+                author: row.get(2)?,
+                body: row.get(3)?,
+                itc: row.get(4)?,
+                created_at_us: row.get(5)?,
+                is_current: row.get(6)?,
+            })
+        })
+        .context("execute comment edits")?;
+
+    let mut edits = Vec::new();
+    for row in rows {
+        edits.push(row.context("read comment edit row")?);
+    }
+    Ok(edits)
+}
+
+/// Get every attachment on an item, oldest first.
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub fn get_attachments(conn: &Connection, item_id: &str) -> Result<Vec<QueryAttachment>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT a.event_hash, a.item_id, a.blob_hash, a.name, a.mime, a.size_bytes, \
+                    a.author, a.created_at_us, r.target_event_hash IS NOT NULL \
+             FROM item_attachments a \
+             LEFT JOIN event_redactions r ON r.target_event_hash = a.event_hash \
+             WHERE a.item_id = ?1 \
+             ORDER BY a.created_at_us ASC, a.event_hash ASC",
+        )
+        .context("prepare attachments")?;
+    let rows = stmt
+        .query_map(params![item_id], |row| {
+            Ok(QueryAttachment {
+                event_hash: row.get(0)?,
+                item_id: row.get(1)?,
+                blob_hash: row.get(2)?,
+                name: row.get(3)?,
+                mime: row.get(4)?,
+                size_bytes: row.get(5)?,
+                author: row.get(6)?,
+                created_at_us: row.get(7)?,
+                redacted: row.get(8)?,
+            })
+        })
+        .context("execute attachments")?;
+
+    let mut attachments = Vec::new();
+    for row in rows {
+        attachments.push(row.context("read attachment row")?);
+    }
+    Ok(attachments)
+}
+
+/// Count the unredacted attachments, on any item, that reference `blob_hash`.
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub fn count_live_blob_references(conn: &Connection, blob_hash: &str) -> Result<usize> {
+    let count: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM item_attachments a \
+             WHERE a.blob_hash = ?1 AND NOT EXISTS ( \
+                 SELECT 1 FROM event_redactions r WHERE r.target_event_hash = a.event_hash)",
+            params![blob_hash],
+            |row| row.get(0),
+        )
+        .context("count blob references")?;
+    Ok(usize::try_from(count).unwrap_or(0))
+}
+
+/// Get every comment edit on an item, oldest first.
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub fn get_comment_edits(conn: &Connection, item_id: &str) -> Result<Vec<QueryCommentEdit>> {
+    query_comment_edits(conn, "item_id", item_id)
+}
+
+/// Get the edits of a single comment, oldest first.
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub fn get_edits_for_comment(
+    conn: &Connection,
+    comment_hash: &str,
+) -> Result<Vec<QueryCommentEdit>> {
+    query_comment_edits(conn, "comment_hash", comment_hash)
+}
+
+/// Arrange comments into reply threads.
+///
+/// Returns `(depth, comment)` pairs in display order: each root comment
+/// (one that is not a reply, or whose parent is not in `comments`) is
+/// followed depth-first by its replies, oldest first at every level.
+/// Retracted comments are dropped unless a visible reply hangs off them, in
+/// which case they stay as placeholders so the thread keeps its shape.
+#[must_use]
+pub fn thread_comments(mut comments: Vec<QueryComment>) -> Vec<(usize, QueryComment)> {
+    comments.sort_by(|a, b| {
+        a.created_at_us
+            .cmp(&b.created_at_us)
+            .then_with(|| a.comment_id.cmp(&b.comment_id))
+    });
+
+    let index: HashMap<&str, usize> = comments
+        .iter()
+        .enumerate()
+        .map(|(idx, c)| (c.event_hash.as_str(), idx))
+        .collect();
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); comments.len()];
+    let mut roots = Vec::new();
+    for (idx, comment) in comments.iter().enumerate() {
+        match comment.reply_to.as_deref().and_then(|p| index.get(p)) {
+            Some(&parent) if parent != idx => children[parent].push(idx),
+            _ => roots.push(idx),
+        }
+    }
+
+    // Depth-first walk; (index, depth, position of parent in `order`).
+    let mut order: Vec<(usize, usize, Option<usize>)> = Vec::with_capacity(comments.len());
+    let mut visited = vec![false; comments.len()];
+    // Comments caught in a reply cycle are never reached from a root; walk
+    // them as roots of their own.
+    for root in roots.into_iter().chain(0..comments.len()) {
+        let mut stack = vec![(root, 0, None)];
+        while let Some((idx, depth, parent)) = stack.pop() {
+            if std::mem::replace(&mut visited[idx], true) {
+                continue;
+            }
+            let pos = order.len();
+            order.push((idx, depth, parent));
+            for &child in children[idx].iter().rev() {
+                stack.push((child, depth + 1, Some(pos)));
+            }
+        }
+    }
+
+    // Keep a node if it is visible or any descendant is kept. Children
+    // always follow their parent in `order`, so one reverse pass suffices.
+    let mut keep: Vec<bool> = order
+        .iter()
+        .map(|&(idx, _, _)| comments[idx].retracted_by.is_none())
+        .collect();
+    for pos in (0..order.len()).rev() {
+        if let (true, Some(parent)) = (keep[pos], order[pos].2) {
+            keep[parent] = true;
+        }
+    }
+
+    let mut slots: Vec<Option<QueryComment>> = comments.into_iter().map(Some).collect();
+    order
+        .into_iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .filter_map(|((idx, depth, _), _)| slots[idx].take().map(|c| (depth, c)))
+        .collect()
+}
+
 /// Get items that a given item depends on (its blockers).
 ///
 /// # Errors
@@ -635,7 +1034,7 @@ pub fn get_dependents(conn: &Connection, item_id: &str) -> Result<Vec<QueryDepen
 pub fn get_children(conn: &Connection, parent_id: &str) -> Result<Vec<QueryItem>> {
     let sql = "SELECT item_id, title, description, kind, state, urgency, size, \
                parent_id, compact_summary, is_deleted, deleted_at_us, \
-               search_labels, created_at_us, updated_at_us \
+               search_labels, created_at_us, updated_at_us, due_at_us, substate \
                FROM items WHERE parent_id = ?1 AND is_deleted = 0 \
                ORDER BY created_at_us ASC";
 
@@ -669,6 +1068,11 @@ pub fn count_items(conn: &Connection, filter: &ItemFilter) -> Result<u64> {
         conditions.push(format!("i.state = ?{}", param_values.len()));
     }
 
+    if let Some(ref substate) = filter.substate {
+        param_values.push(Box::new(substate.clone()));
+        conditions.push(format!("i.substate = ?{}", param_values.len()));
+    }
+
     if let Some(ref kind) = filter.kind {
         param_values.push(Box::new(kind.clone()));
         conditions.push(format!("i.kind = ?{}", param_values.len()));
@@ -684,6 +1088,18 @@ pub fn count_items(conn: &Connection, filter: &ItemFilter) -> Result<u64> {
         conditions.push(format!("i.parent_id = ?{}", param_values.len()));
     }
 
+    if let Some(due_before) = filter.due_before {
+        param_values.push(Box::new(due_before));
+        conditions.push(format!(
+            "i.due_at_us IS NOT NULL AND i.due_at_us <= ?{}",
+            param_values.len()
+        ));
+    }
+
+    if let Some(ref query) = filter.query {
+        conditions.push(query.to_sql(&mut param_values));
+    }
+
     let mut joins = String::new();
     if let Some(ref label) = filter.label {
         param_values.push(Box::new(label.clone()));
@@ -703,6 +1119,8 @@ pub fn count_items(conn: &Connection, filter: &ItemFilter) -> Result<u64> {
         );
     }
 
+    push_custom_field_joins(filter, &mut joins, &mut param_values);
+
     let where_clause = if conditions.is_empty() {
         String::new()
     } else {
@@ -871,6 +1289,8 @@ fn row_to_query_item(row: &rusqlite::Row<'_>) -> rusqlite::Result<QueryItem> {
         search_labels: row.get(11)?,
         created_at_us: row.get(12)?,
         updated_at_us: row.get(13)?,
+        due_at_us: row.get(14)?,
+        substate: row.get(15)?,
     })
 }
 
@@ -1338,6 +1758,88 @@ mod tests {
         assert_eq!(items[2].urgency, "punt");
     }
 
+    #[test]
+    fn list_items_due_filter_and_sort() {
+        let conn = test_db();
+        insert_item(&conn, "bn-001", "No deadline", "open", "default");
+        insert_item(&conn, "bn-002", "Due later", "open", "default");
+        insert_item(&conn, "bn-003", "Due soon", "open", "default");
+        conn.execute_batch(
+            "UPDATE items SET due_at_us = 9000 WHERE item_id = 'bn-002';
+             UPDATE items SET due_at_us = 5000 WHERE item_id = 'bn-003';",
+        )
+        .unwrap();
+
+        let filter = ItemFilter {
+            sort: SortOrder::DueAsc,
+            ..Default::default()
+        };
+        let ids: Vec<_> = list_items(&conn, &filter)
+            .unwrap()
+            .into_iter()
+            .map(|i| i.item_id)
+            .collect();
+        assert_eq!(ids, ["bn-003", "bn-002", "bn-001"]);
+
+        let filter = ItemFilter {
+            due_before: Some(6000),
+            ..Default::default()
+        };
+        let items = list_items(&conn, &filter).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].item_id, "bn-003");
+        assert_eq!(items[0].due_at_us, Some(5000));
+        assert_eq!(count_items(&conn, &filter).unwrap(), 1);
+    }
+
+    #[test]
+    fn list_items_custom_field_filter_and_sort() {
+        let conn = test_db();
+        insert_item(&conn, "bn-001", "No severity", "open", "default");
+        insert_item(&conn, "bn-002", "Minor", "open", "default");
+        insert_item(&conn, "bn-003", "Major", "open", "default");
+        conn.execute_batch(
+            "INSERT INTO item_custom_fields VALUES ('bn-002', 'severity', '9', 9, 1);
+             INSERT INTO item_custom_fields VALUES ('bn-003', 'severity', '10', 10, 1);
+             INSERT INTO item_custom_fields VALUES ('bn-003', 'component', 'api', NULL, 1);",
+        )
+        .unwrap();
+
+        let sorted = |descending| {
+            let filter = ItemFilter {
+                sort_custom: Some(CustomFieldSort {
+                    name: "severity".to_string(),
+                    descending,
+                }),
+                ..Default::default()
+            };
+            list_items(&conn, &filter)
+                .unwrap()
+                .into_iter()
+                .map(|i| i.item_id)
+                .collect::<Vec<_>>()
+        };
+        // Numeric, not lexicographic; missing values last in both directions.
+        assert_eq!(sorted(false), ["bn-002", "bn-003", "bn-001"]);
+        assert_eq!(sorted(true), ["bn-003", "bn-002", "bn-001"]);
+
+        let filter = ItemFilter {
+            custom_fields: vec![
+                ("component".to_string(), "api".to_string()),
+                ("severity".to_string(), "10".to_string()),
+            ],
+            ..Default::default()
+        };
+        let items = list_items(&conn, &filter).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].item_id, "bn-003");
+        assert_eq!(count_items(&conn, &filter).unwrap(), 1);
+
+        let fields = get_custom_fields(&conn, "bn-003").unwrap();
+        assert_eq!(fields["severity"], serde_json::json!(10));
+        assert_eq!(fields["component"], serde_json::json!("api"));
+    }
+
     #[test]
     fn sort_order_parse_roundtrip() {
         for order in [
@@ -1346,6 +1848,7 @@ mod tests {
             SortOrder::UpdatedDesc,
             SortOrder::UpdatedAsc,
             SortOrder::Priority,
+            SortOrder::DueAsc,
         ] {
             let s = order.to_string();
             let parsed: SortOrder = s.parse().unwrap();
@@ -1580,6 +2083,103 @@ mod tests {
         assert_eq!(comments[1].body, "First comment");
     }
 
+    #[test]
+    fn get_comments_applies_current_edit_and_retraction() {
+        let conn = test_db();
+        insert_item(&conn, "bn-001", "Item", "open", "default");
+        conn.execute(
+            "INSERT INTO item_comments (item_id, event_hash, author, body, created_at_us, reply_to) \
+             VALUES ('bn-001', 'hash1', 'alice', 'frist', 100, NULL), \
+                    ('bn-001', 'hash2', 'bob', 'reply', 200, 'hash1')",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO item_comment_edits \
+             (event_hash, comment_hash, item_id, author, body, itc, created_at_us, is_current) \
+             VALUES ('edit1', 'hash1', 'bn-001', 'alice', 'first', 'itc:AQ', 150, 1)",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO item_comment_retractions \
+             (comment_hash, item_id, event_hash, retracted_by, reason, retracted_at_us) \
+             VALUES ('hash2', 'bn-001', 'retract1', 'bob', NULL, 250)",
+            [],
+        )
+        .unwrap();
+
+        let comments = get_comments(&conn, "bn-001", None, None).unwrap();
+        let first = comments.iter().find(|c| c.event_hash == "hash1").unwrap();
+        assert_eq!(first.body, "first");
+        assert_eq!(first.original_body, "frist");
+        assert_eq!(first.edited_at_us, Some(150));
+        let reply = comments.iter().find(|c| c.event_hash == "hash2").unwrap();
+        assert_eq!(reply.reply_to.as_deref(), Some("hash1"));
+        assert_eq!(reply.retracted_by.as_deref(), Some("bob"));
+
+        let edits = get_edits_for_comment(&conn, "hash1").unwrap();
+        assert_eq!(edits.len(), 1);
+        assert!(edits[0].is_current);
+    }
+
+    fn thread_node(id: i64, ts: i64, reply_to: Option<&str>, retracted: bool) -> QueryComment {
+        QueryComment {
+            comment_id: id,
+            item_id: "bn-001".into(),
+            event_hash: format!("h{id}"),
+            author: "alice".into(),
+            body: String::new(),
+            created_at_us: ts,
+            original_body: String::new(),
+            reply_to: reply_to.map(str::to_string),
+            edited_at_us: None,
+            retracted_by: retracted.then(|| "alice".to_string()),
+        }
+    }
+
+    #[test]
+    fn thread_comments_orders_replies_under_parents() {
+        let threaded = thread_comments(vec![
+            thread_node(4, 400, Some("h1"), false),
+            thread_node(2, 200, None, false),
+            thread_node(1, 100, None, false),
+            thread_node(3, 300, Some("h1"), false),
+            thread_node(5, 500, Some("h3"), false),
+        ]);
+        let order: Vec<(usize, &str)> = threaded
+            .iter()
+            .map(|(depth, c)| (*depth, c.event_hash.as_str()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![(0, "h1"), (1, "h3"), (2, "h5"), (1, "h4"), (0, "h2")]
+        );
+    }
+
+    #[test]
+    fn thread_comments_keeps_retracted_parents_with_replies() {
+        let threaded = thread_comments(vec![
+            thread_node(1, 100, None, true),
+            thread_node(2, 200, Some("h1"), false),
+            thread_node(3, 300, None, true),
+        ]);
+        let hashes: Vec<&str> = threaded
+            .iter()
+            .map(|(_, c)| c.event_hash.as_str())
+            .collect();
+        assert_eq!(hashes, vec!["h1", "h2"]);
+    }
+
+    #[test]
+    fn thread_comments_survives_reply_cycles() {
+        let threaded = thread_comments(vec![
+            thread_node(1, 100, Some("h2"), false),
+            thread_node(2, 200, Some("h1"), false),
+        ]);
+        assert_eq!(threaded.len(), 2);
+    }
+
     #[test]
     fn get_dependencies_and_dependents() {
         let conn = test_db();
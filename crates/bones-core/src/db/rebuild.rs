@@ -3,6 +3,7 @@
 //! `bn admin rebuild` drops and recreates the entire `SQLite` DB from the canonical
 //! event log, proving the projection is disposable and reproducible.
 
+use std::collections::HashSet;
 use std::path::Path;
 use std::time::Instant;
 
@@ -158,6 +159,19 @@ fn check_sealed_shard_integrity(shard_mgr: &ShardManager) -> Result<()> {
     Ok(())
 }
 
+/// Re-read the events targeted by redactions and scrub their content from
+/// the freshly rebuilt projection.
+fn scrub_redacted_targets(
+    conn: &rusqlite::Connection,
+    shard_mgr: &ShardManager,
+    targets: &HashSet<String>,
+) -> Result<()> {
+    for event in crate::redact::load_targets(shard_mgr, targets)? {
+        crate::redact::scrub_projection(conn, &event)?;
+    }
+    Ok(())
+}
+
 /// Drop the existing DB and rebuild it from the canonical event log.
 ///
 /// 1. Deletes the existing database file (if any)
@@ -221,6 +235,7 @@ pub fn rebuild(events_dir: &Path, db_path: &Path) -> Result<RebuildReport> {
     let mut total_errors = 0;
     let mut last_event_hash = None;
     let mut total_byte_len = 0;
+    let mut redacted_hashes: HashSet<String> = HashSet::new();
 
     let batch_size = rebuild_batch_size();
     let mut current_batch: Vec<Event> = Vec::with_capacity(batch_size);
@@ -247,6 +262,9 @@ pub fn rebuild(events_dir: &Path, db_path: &Path) -> Result<RebuildReport> {
                     .map_err(|e| anyhow::anyhow!("migration failed at line {line_no}: {e}"))?;
 
                 last_event_hash = Some(event.event_hash.clone());
+                if let crate::event::data::EventData::Redact(ref data) = event.data {
+                    redacted_hashes.insert(data.target_hash.clone());
+                }
                 current_batch.push(event);
 
                 if current_batch.len() >= batch_size {
@@ -280,6 +298,14 @@ pub fn rebuild(events_dir: &Path, db_path: &Path) -> Result<RebuildReport> {
         total_errors += stats.errors;
     }
 
+    // 4a. Replay only blanks comment bodies for redactions; item fields
+    // written by a redacted event were projected before the redaction was
+    // seen, so scrub them in a second pass over just the targeted lines.
+    if !redacted_hashes.is_empty() {
+        scrub_redacted_targets(&conn, &shard_mgr, &redacted_hashes)
+            .context("scrub redacted content after rebuild")?;
+    }
+
     // 4b. Rebuild the FTS5 index in bulk now that all items are in place,
     // then recreate the maintenance triggers so subsequent incremental
     // projections keep the FTS5 index in sync row-by-row.
@@ -364,6 +390,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         // Compute hash
         writer::write_event(&mut event).expect("compute hash");
@@ -386,9 +413,11 @@ mod tests {
             data: EventData::Move(MoveData {
                 state,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         writer::write_event(&mut event).expect("compute hash");
         event
@@ -550,6 +579,53 @@ mod tests {
         assert_eq!(hits, 1);
     }
 
+    #[test]
+    fn rebuild_scrubs_redacted_create() {
+        let (dir, shard_mgr) = setup_bones_dir();
+        let db_path = dir.path().join("bones.db");
+        let events_dir = dir.path().join("events");
+
+        let create = make_create_event("bn-001", "Leaked hunter2 password", 1000);
+        append_event(&shard_mgr, &create);
+        let mut redact = Event {
+            wall_ts_us: 2000,
+            agent: "test-agent".into(),
+            itc: "itc:AQ".into(),
+            parents: vec![create.event_hash.clone()],
+            event_type: EventType::Redact,
+            item_id: ItemId::new_unchecked("bn-001"),
+            data: EventData::Redact(RedactData {
+                target_hash: create.event_hash.clone(),
+                reason: "secret in title".into(),
+                extra: BTreeMap::new(),
+            }),
+            event_hash: String::new(),
+            signature: None,
+        };
+        writer::write_event(&mut redact).expect("compute hash");
+        append_event(&shard_mgr, &redact);
+
+        rebuild(&events_dir, &db_path).unwrap();
+
+        let conn = open_projection(&db_path).unwrap();
+        let title: String = conn
+            .query_row(
+                "SELECT title FROM items WHERE item_id = 'bn-001'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(title, crate::redact::REDACTED_PLACEHOLDER);
+        let hits: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM items_fts WHERE items_fts MATCH 'hunter2'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(hits, 0);
+    }
+
     #[test]
     fn rebuild_updates_projection_cursor() {
         let (dir, shard_mgr) = setup_bones_dir();
@@ -172,6 +172,213 @@ SET schema_version = 2
 WHERE id = 1;
 ";
 
+/// Migration v3: due dates for time-bound items.
+pub const MIGRATION_V3_SQL: &str = r"
+ALTER TABLE items ADD COLUMN due_at_us INTEGER;
+
+CREATE INDEX IF NOT EXISTS idx_items_due
+    ON items(due_at_us)
+    WHERE due_at_us IS NOT NULL;
+
+UPDATE projection_meta
+SET schema_version = 3
+WHERE id = 1;
+";
+
+/// Migration v4: typed custom fields.
+///
+/// `value` holds the canonical text form used for equality filters;
+/// `value_num` is set for integer fields so sorting is numeric.
+pub const MIGRATION_V4_SQL: &str = r"
+CREATE TABLE IF NOT EXISTS item_custom_fields (
+    item_id TEXT NOT NULL REFERENCES items(item_id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    value TEXT NOT NULL,
+    value_num INTEGER,
+    updated_at_us INTEGER NOT NULL,
+    PRIMARY KEY (item_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_item_custom_fields_name_value
+    ON item_custom_fields(name, value);
+
+UPDATE projection_meta
+SET schema_version = 4
+WHERE id = 1;
+";
+
+/// Migration v5: collaborative description edits.
+///
+/// One row per item holding the description write that `item.patch` events
+/// currently apply to (`base_hash`/`base_text`) and the accumulated
+/// sequence CRDT state as JSON. `items.description` holds the rendering.
+pub const MIGRATION_V5_SQL: &str = r"
+CREATE TABLE IF NOT EXISTS item_description_edits (
+    item_id TEXT PRIMARY KEY REFERENCES items(item_id) ON DELETE CASCADE,
+    base_hash TEXT NOT NULL,
+    base_text TEXT NOT NULL,
+    seq_json TEXT NOT NULL DEFAULT '{}',
+    updated_at_us INTEGER NOT NULL
+);
+
+UPDATE projection_meta
+SET schema_version = 5
+WHERE id = 1;
+";
+
+/// Migration v6: LWW field heads for conflict detection.
+///
+/// One row per write to an LWW field that no later write has observed.
+/// A field with more than one row had concurrent writes; see
+/// [`crate::conflicts`].
+pub const MIGRATION_V6_SQL: &str = r"
+CREATE TABLE IF NOT EXISTS item_field_heads (
+    item_id TEXT NOT NULL REFERENCES items(item_id) ON DELETE CASCADE,
+    field TEXT NOT NULL,
+    event_hash TEXT NOT NULL,
+    agent TEXT NOT NULL,
+    itc TEXT NOT NULL,
+    wall_ts_us INTEGER NOT NULL,
+    value_json TEXT NOT NULL,
+    PRIMARY KEY (item_id, field, event_hash)
+);
+
+UPDATE projection_meta
+SET schema_version = 6
+WHERE id = 1;
+";
+
+/// Migration v7: comment threads, edits, and retractions.
+///
+/// `item_comments.reply_to` links a reply to its parent comment. Every
+/// `item.comment_edit` gets a row in `item_comment_edits`; the LWW winner per
+/// comment is flagged `is_current`. Retractions are keyed by comment hash so
+/// they apply regardless of projection order.
+pub const MIGRATION_V7_SQL: &str = r"
+ALTER TABLE item_comments ADD COLUMN reply_to TEXT;
+
+CREATE TABLE IF NOT EXISTS item_comment_edits (
+    event_hash TEXT PRIMARY KEY,
+    comment_hash TEXT NOT NULL,
+    item_id TEXT NOT NULL REFERENCES items(item_id) ON DELETE CASCADE,
+    author TEXT NOT NULL,
+    body TEXT NOT NULL,
+    itc TEXT NOT NULL,
+    created_at_us INTEGER NOT NULL,
+    is_current INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_item_comment_edits_comment
+    ON item_comment_edits(comment_hash, created_at_us);
+
+CREATE TABLE IF NOT EXISTS item_comment_retractions (
+    comment_hash TEXT PRIMARY KEY,
+    item_id TEXT NOT NULL REFERENCES items(item_id) ON DELETE CASCADE,
+    event_hash TEXT NOT NULL,
+    retracted_by TEXT NOT NULL,
+    reason TEXT,
+    retracted_at_us INTEGER NOT NULL
+);
+
+UPDATE projection_meta
+SET schema_version = 7
+WHERE id = 1;
+";
+
+/// Migration v8: attachments.
+///
+/// One row per `item.attach` event. The bytes live in the `.bones/blobs/`
+/// store; the row records which blob, under what name and type.
+pub const MIGRATION_V8_SQL: &str = r"
+CREATE TABLE IF NOT EXISTS item_attachments (
+    event_hash TEXT PRIMARY KEY,
+    item_id TEXT NOT NULL REFERENCES items(item_id) ON DELETE CASCADE,
+    blob_hash TEXT NOT NULL,
+    name TEXT NOT NULL,
+    mime TEXT NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    author TEXT NOT NULL,
+    created_at_us INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_item_attachments_item
+    ON item_attachments(item_id, created_at_us);
+
+CREATE INDEX IF NOT EXISTS idx_item_attachments_blob
+    ON item_attachments(blob_hash);
+
+UPDATE projection_meta
+SET schema_version = 8
+WHERE id = 1;
+";
+
+/// Migration v9: workflow sub-states.
+///
+/// `items.state` keeps the lifecycle phase so triage and scheduling are
+/// unchanged; `items.substate` names the configured sub-state, if any.
+pub const MIGRATION_V9_SQL: &str = r"
+ALTER TABLE items ADD COLUMN substate TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_items_substate
+    ON items(substate)
+    WHERE substate IS NOT NULL;
+
+UPDATE projection_meta
+SET schema_version = 9
+WHERE id = 1;
+";
+
+/// Migration v10: state-transition history.
+///
+/// One row per `item.move`, recording the state it left and entered, for
+/// flow metrics. Resetting the cursor makes the next read rebuild the
+/// projection so existing moves are recorded too.
+pub const MIGRATION_V10_SQL: &str = r"
+CREATE TABLE IF NOT EXISTS item_transitions (
+    event_hash TEXT PRIMARY KEY,
+    item_id TEXT NOT NULL REFERENCES items(item_id) ON DELETE CASCADE,
+    from_state TEXT NOT NULL,
+    to_state TEXT NOT NULL,
+    substate TEXT,
+    agent TEXT NOT NULL,
+    at_us INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_item_transitions_item
+    ON item_transitions(item_id, at_us);
+
+CREATE INDEX IF NOT EXISTS idx_item_transitions_to_state
+    ON item_transitions(to_state, at_us);
+
+UPDATE projection_meta
+SET schema_version = 10,
+    last_event_offset = 0,
+    last_event_hash = NULL
+WHERE id = 1;
+";
+
+/// Migration v11: event tracking table in the schema.
+///
+/// `projected_events` used to be created lazily by the projector, so a
+/// database nothing had projected into yet lacked it. Read-only consumers
+/// such as the SQL console's `bones_events` view need it to exist up front.
+pub const MIGRATION_V11_SQL: &str = r"
+CREATE TABLE IF NOT EXISTS projected_events (
+    event_hash TEXT PRIMARY KEY,
+    item_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    projected_at_us INTEGER NOT NULL,
+    agent TEXT NOT NULL DEFAULT ''
+);
+
+CREATE INDEX IF NOT EXISTS idx_projected_events_item
+    ON projected_events(item_id);
+
+UPDATE projection_meta
+SET schema_version = 11
+WHERE id = 1;
+";
+
 /// Indexes expected by list/filter/triage query paths.
 pub const REQUIRED_INDEXES: &[&str] = &[
     "idx_items_state_urgency_updated",
@@ -183,6 +390,14 @@ pub const REQUIRED_INDEXES: &[&str] = &[
     "idx_item_dependencies_target_type",
     "idx_item_comments_item_created",
     "idx_event_redactions_item",
+    "idx_items_due",
+    "idx_item_custom_fields_name_value",
+    "idx_item_comment_edits_comment",
+    "idx_item_attachments_item",
+    "idx_item_attachments_blob",
+    "idx_items_substate",
+    "idx_item_transitions_item",
+    "idx_item_transitions_to_state",
 ];
 
 #[cfg(test)]
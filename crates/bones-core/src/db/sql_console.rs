@@ -0,0 +1,512 @@
+//! Read-only SQL over the projection, for `bn query sql`.
+//!
+//! Internal tables change whenever a migration does, so ad-hoc queries
+//! against them break between releases. This module exposes a small set of
+//! documented views instead, versioned by [`SQL_API_VERSION`] rather than by
+//! the projection schema:
+//!
+//! ```text
+//! bones_items         one row per live bone
+//! bones_labels        (item_id, label)
+//! bones_assignees     (item_id, agent)
+//! bones_dependencies  (item_id, depends_on_id, link_type)
+//! bones_comments      current comment text, retracted comments excluded
+//! bones_events        one row per projected event
+//! bones_transitions   one row per state change
+//! ```
+//!
+//! The projection is opened read-only and the views are created as `TEMP`
+//! views on the console connection, so they never touch the projection
+//! file. The connection is then switched to `query_only`, and [`run_sql`]
+//! accepts a single `SELECT`, `WITH`, `VALUES`
+//! or `EXPLAIN` statement that `SQLite` reports as read-only.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags};
+use serde::Serialize;
+
+use super::{migrations, query};
+
+/// Version of the `bones_*` views. Bumped only when a column is renamed,
+/// removed or changes meaning; adding a column or a view does not bump it.
+pub const SQL_API_VERSION: u32 = 1;
+
+/// A documented view available to `bn query sql`.
+#[derive(Debug, Clone, Copy)]
+pub struct SqlView {
+    /// View name, e.g. `bones_items`.
+    pub name: &'static str,
+    /// One-line description.
+    pub description: &'static str,
+    /// Column names and what they hold, in view order.
+    pub columns: &'static [(&'static str, &'static str)],
+    select: &'static str,
+}
+
+/// The stable views, in documentation order.
+pub const SQL_VIEWS: &[SqlView] = &[
+    SqlView {
+        name: "bones_items",
+        description: "One row per bone that is not deleted",
+        columns: &[
+            ("id", "bone ID"),
+            ("title", "title"),
+            ("description", "description, or NULL"),
+            ("kind", "task, goal or bug"),
+            ("state", "open, doing, done or archived"),
+            ("substate", "configured workflow sub-state, or NULL"),
+            ("urgency", "urgent, default or punt"),
+            ("size", "xs, s, m, l, xl, or NULL"),
+            ("parent_id", "parent bone ID, or NULL"),
+            ("labels", "comma-separated labels, sorted"),
+            ("assignees", "comma-separated assignees, sorted"),
+            ("due_at_us", "due time in Unix microseconds, or NULL"),
+            ("created_at_us", "creation time in Unix microseconds"),
+            ("updated_at_us", "last update time in Unix microseconds"),
+        ],
+        select: "SELECT i.item_id AS id, i.title, i.description, i.kind, i.state, \
+                 i.substate, i.urgency, i.size, i.parent_id, \
+                 COALESCE((SELECT group_concat(label, ',') FROM \
+                     (SELECT label FROM main.item_labels l \
+                      WHERE l.item_id = i.item_id ORDER BY label)), '') AS labels, \
+                 COALESCE((SELECT group_concat(agent, ',') FROM \
+                     (SELECT agent FROM main.item_assignees a \
+                      WHERE a.item_id = i.item_id ORDER BY agent)), '') AS assignees, \
+                 i.due_at_us, i.created_at_us, i.updated_at_us \
+                 FROM main.items i WHERE i.is_deleted = 0",
+    },
+    SqlView {
+        name: "bones_labels",
+        description: "One row per label on a bone",
+        columns: &[
+            ("item_id", "bone ID"),
+            ("label", "label"),
+            (
+                "created_at_us",
+                "when the label was added, Unix microseconds",
+            ),
+        ],
+        select: "SELECT l.item_id, l.label, l.created_at_us \
+                 FROM main.item_labels l \
+                 JOIN main.items i ON i.item_id = l.item_id AND i.is_deleted = 0",
+    },
+    SqlView {
+        name: "bones_assignees",
+        description: "One row per assignee of a bone",
+        columns: &[
+            ("item_id", "bone ID"),
+            ("agent", "assigned agent"),
+            (
+                "created_at_us",
+                "when the agent was assigned, Unix microseconds",
+            ),
+        ],
+        select: "SELECT a.item_id, a.agent, a.created_at_us \
+                 FROM main.item_assignees a \
+                 JOIN main.items i ON i.item_id = a.item_id AND i.is_deleted = 0",
+    },
+    SqlView {
+        name: "bones_dependencies",
+        description: "One row per link; `blocks` means item_id waits on depends_on_id",
+        columns: &[
+            ("item_id", "bone that has the link"),
+            ("depends_on_id", "bone it points at"),
+            ("link_type", "blocks, related, ..."),
+            (
+                "created_at_us",
+                "when the link was added, Unix microseconds",
+            ),
+        ],
+        select: "SELECT d.item_id, d.depends_on_item_id AS depends_on_id, d.link_type, \
+                 d.created_at_us \
+                 FROM main.item_dependencies d \
+                 JOIN main.items i ON i.item_id = d.item_id AND i.is_deleted = 0",
+    },
+    SqlView {
+        name: "bones_comments",
+        description: "One row per comment that is not retracted, with its current text",
+        columns: &[
+            ("comment_id", "hash of the comment event"),
+            ("item_id", "bone ID"),
+            ("author", "agent that wrote the comment"),
+            ("body", "current text, after edits"),
+            ("reply_to", "comment_id this replies to, or NULL"),
+            ("edited", "1 if the comment was edited, else 0"),
+            (
+                "created_at_us",
+                "when the comment was written, Unix microseconds",
+            ),
+        ],
+        select: "SELECT c.event_hash AS comment_id, c.item_id, c.author, \
+                 COALESCE(e.body, c.body) AS body, c.reply_to, \
+                 CASE WHEN e.body IS NULL THEN 0 ELSE 1 END AS edited, c.created_at_us \
+                 FROM main.item_comments c \
+                 JOIN main.items i ON i.item_id = c.item_id AND i.is_deleted = 0 \
+                 LEFT JOIN main.item_comment_edits e \
+                     ON e.comment_hash = c.event_hash AND e.is_current = 1 \
+                 WHERE NOT EXISTS (SELECT 1 FROM main.item_comment_retractions r \
+                                   WHERE r.comment_hash = c.event_hash)",
+    },
+    SqlView {
+        name: "bones_events",
+        description: "One row per event applied to the projection",
+        columns: &[
+            ("event_hash", "event hash"),
+            ("item_id", "bone ID"),
+            ("event_type", "item.create, item.move, ..."),
+            ("agent", "agent that wrote the event"),
+            ("wall_ts_us", "event wall-clock time, Unix microseconds"),
+        ],
+        select: "SELECT event_hash, item_id, event_type, agent, \
+                 projected_at_us AS wall_ts_us \
+                 FROM main.projected_events",
+    },
+    SqlView {
+        name: "bones_transitions",
+        description: "One row per state change (item.move)",
+        columns: &[
+            ("event_hash", "hash of the move event"),
+            ("item_id", "bone ID"),
+            ("from_state", "state before the move"),
+            ("to_state", "state after the move"),
+            ("substate", "workflow sub-state entered, or NULL"),
+            ("agent", "agent that moved the bone"),
+            ("at_us", "event wall-clock time, Unix microseconds"),
+        ],
+        select: "SELECT t.event_hash, t.item_id, t.from_state, t.to_state, t.substate, \
+                 t.agent, t.at_us \
+                 FROM main.item_transitions t \
+                 JOIN main.items i ON i.item_id = t.item_id AND i.is_deleted = 0",
+    },
+];
+
+/// Why [`run_sql`] refused or failed a statement.
+#[derive(Debug, thiserror::Error)]
+pub enum SqlError {
+    #[error("empty SQL statement")]
+    Empty,
+    #[error("only one statement may be run at a time")]
+    MultipleStatements,
+    #[error("only read-only SELECT, WITH, VALUES and EXPLAIN statements are allowed")]
+    NotReadOnly,
+    #[error("{0}")]
+    Sqlite(#[from] rusqlite::Error),
+}
+
+/// Rows returned by [`run_sql`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SqlResult {
+    pub columns: Vec<String>,
+    /// Values in column order. Blobs are rendered as lowercase hex.
+    pub rows: Vec<Vec<serde_json::Value>>,
+    /// Whether rows past the limit were dropped.
+    pub truncated: bool,
+}
+
+/// Open the projection at `db_path` read-only for the SQL console: install
+/// the `bones_*` views and switch the connection to `query_only`.
+///
+/// No migrations run here; callers bring the projection up to date first
+/// (e.g. via [`super::ensure_projection`]).
+///
+/// Returns `Ok(None)` if the projection is missing, corrupt, or on an older
+/// schema.
+///
+/// # Errors
+///
+/// Returns an error if the views cannot be created.
+pub fn open_sql_console(db_path: &Path) -> Result<Option<Connection>> {
+    if !db_path.exists() {
+        return Ok(None);
+    }
+    let Ok(conn) = Connection::open_with_flags(
+        db_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    ) else {
+        return Ok(None);
+    };
+    conn.busy_timeout(super::DEFAULT_BUSY_TIMEOUT)
+        .context("busy_timeout")?;
+    let current = migrations::current_schema_version(&conn).ok();
+    if current != Some(migrations::LATEST_SCHEMA_VERSION)
+        || query::get_projection_cursor(&conn).is_err()
+    {
+        return Ok(None);
+    }
+    for view in SQL_VIEWS {
+        conn.execute_batch(&format!(
+            "CREATE TEMP VIEW IF NOT EXISTS {} AS {}",
+            view.name, view.select
+        ))
+        .with_context(|| format!("create view {}", view.name))?;
+    }
+    conn.pragma_update(None, "query_only", true)
+        .context("set query_only")?;
+    Ok(Some(conn))
+}
+
+/// Run one read-only statement, returning at most `max_rows` rows.
+///
+/// # Errors
+///
+/// Returns [`SqlError`] if the input is not a single read-only statement or
+/// `SQLite` rejects it.
+pub fn run_sql(conn: &Connection, sql: &str, max_rows: usize) -> Result<SqlResult, SqlError> {
+    let sql = single_statement(sql)?;
+    let keyword: String = sql
+        .chars()
+        .take_while(char::is_ascii_alphabetic)
+        .collect::<String>()
+        .to_ascii_uppercase();
+    if !matches!(keyword.as_str(), "SELECT" | "WITH" | "VALUES" | "EXPLAIN") {
+        return Err(SqlError::NotReadOnly);
+    }
+
+    let mut stmt = conn.prepare(sql)?;
+    if !stmt.readonly() {
+        return Err(SqlError::NotReadOnly);
+    }
+    let columns: Vec<String> = stmt
+        .column_names()
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+
+    let mut rows = Vec::new();
+    let mut truncated = false;
+    let mut cursor = stmt.query([])?;
+    while let Some(row) = cursor.next()? {
+        if rows.len() == max_rows {
+            truncated = true;
+            break;
+        }
+        let values = (0..columns.len())
+            .map(|idx| row.get_ref(idx).map(json_value))
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows.push(values);
+    }
+
+    Ok(SqlResult {
+        columns,
+        rows,
+        truncated,
+    })
+}
+
+fn json_value(value: ValueRef<'_>) -> serde_json::Value {
+    match value {
+        ValueRef::Null => serde_json::Value::Null,
+        ValueRef::Integer(n) => n.into(),
+        ValueRef::Real(f) => {
+            serde_json::Number::from_f64(f).map_or(serde_json::Value::Null, Into::into)
+        }
+        ValueRef::Text(bytes) => String::from_utf8_lossy(bytes).into_owned().into(),
+        ValueRef::Blob(bytes) => {
+            let mut hex = String::with_capacity(bytes.len() * 2);
+            for b in bytes {
+                let _ = write!(hex, "{b:02x}");
+            }
+            hex.into()
+        }
+    }
+}
+
+/// Strip leading comments and one trailing `;`, rejecting input that holds
+/// more than one statement. `SQLite` would silently ignore the rest.
+fn single_statement(sql: &str) -> Result<&str, SqlError> {
+    let start = skip_trivia(sql, 0);
+    let bytes = sql.as_bytes();
+    let mut idx = start;
+    let mut end = sql.len();
+    while idx < bytes.len() {
+        match bytes[idx] {
+            quote @ (b'\'' | b'"' | b'`') => {
+                idx += 1;
+                while idx < bytes.len() && bytes[idx] != quote {
+                    idx += 1;
+                }
+                idx += 1;
+            }
+            b'[' => {
+                while idx < bytes.len() && bytes[idx] != b']' {
+                    idx += 1;
+                }
+                idx += 1;
+            }
+            b'-' if bytes.get(idx + 1) == Some(&b'-') => idx = skip_trivia(sql, idx),
+            b'/' if bytes.get(idx + 1) == Some(&b'*') => idx = skip_trivia(sql, idx),
+            b';' => {
+                if skip_trivia(sql, idx + 1) < sql.len() {
+                    return Err(SqlError::MultipleStatements);
+                }
+                end = idx;
+                break;
+            }
+            _ => idx += 1,
+        }
+    }
+
+    let statement = sql[start..end].trim_end();
+    if statement.is_empty() {
+        return Err(SqlError::Empty);
+    }
+    Ok(statement)
+}
+
+/// Index of the first byte at or after `idx` that is not whitespace or
+/// part of a comment.
+fn skip_trivia(sql: &str, mut idx: usize) -> usize {
+    let bytes = sql.as_bytes();
+    loop {
+        while idx < bytes.len() && bytes[idx].is_ascii_whitespace() {
+            idx += 1;
+        }
+        if sql[idx..].starts_with("--") {
+            idx = sql[idx..].find('\n').map_or(sql.len(), |n| idx + n + 1);
+        } else if sql[idx..].starts_with("/*") {
+            idx = sql[idx + 2..].find("*/").map_or(sql.len(), |n| idx + n + 4);
+        } else {
+            return idx;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::open_projection;
+    use serde_json::json;
+
+    fn console() -> (tempfile::TempDir, Connection) {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("bones.db");
+        let conn = open_projection(&db_path).expect("open projection");
+        conn.execute_batch(
+            "INSERT INTO items (item_id, title, kind, state, urgency, is_deleted, \
+                 search_labels, created_at_us, updated_at_us) \
+             VALUES ('bn-001', 'Api work', 'task', 'open', 'default', 0, '', 1, 2), \
+                    ('bn-002', 'Gone', 'task', 'open', 'default', 1, '', 1, 2); \
+             INSERT INTO item_labels (item_id, label, created_at_us) \
+             VALUES ('bn-001', 'backend', 1), ('bn-001', 'api', 1), ('bn-002', 'api', 1); \
+             INSERT INTO item_comments (item_id, event_hash, author, body, created_at_us) \
+             VALUES ('bn-001', 'blake3:c1', 'alice', 'first', 3), \
+                    ('bn-001', 'blake3:c2', 'bob', 'second', 4); \
+             INSERT INTO item_comment_edits (event_hash, comment_hash, item_id, author, \
+                 body, itc, created_at_us, is_current) \
+             VALUES ('blake3:e1', 'blake3:c1', 'bn-001', 'alice', 'first, edited', 'itc', 5, 1); \
+             INSERT INTO item_comment_retractions (comment_hash, item_id, event_hash, \
+                 retracted_by, retracted_at_us) \
+             VALUES ('blake3:c2', 'bn-001', 'blake3:r1', 'bob', 6);",
+        )
+        .expect("seed");
+        drop(conn);
+        let conn = open_sql_console(&db_path)
+            .expect("open console")
+            .expect("projection exists");
+        (dir, conn)
+    }
+
+    #[test]
+    fn views_expose_documented_columns() {
+        let (_dir, conn) = console();
+        for view in SQL_VIEWS {
+            let result = run_sql(&conn, &format!("SELECT * FROM {}", view.name), 10)
+                .unwrap_or_else(|e| panic!("{}: {e}", view.name));
+            let documented: Vec<&str> = view.columns.iter().map(|(name, _)| *name).collect();
+            assert_eq!(result.columns, documented, "{}", view.name);
+        }
+    }
+
+    #[test]
+    fn console_opens_projection_read_only() {
+        let (_dir, conn) = console();
+        assert!(
+            conn.is_readonly(rusqlite::DatabaseName::Main)
+                .expect("readonly flag")
+        );
+    }
+
+    #[test]
+    fn console_refuses_outdated_schema() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db_path = dir.path().join("bones.db");
+        let conn = open_projection(&db_path).expect("open projection");
+        conn.pragma_update(None, "user_version", 1)
+            .expect("downgrade");
+        drop(conn);
+        assert!(open_sql_console(&db_path).expect("open console").is_none());
+    }
+
+    #[test]
+    fn views_hide_deleted_items_and_retracted_comments() {
+        let (_dir, conn) = console();
+        let items = run_sql(&conn, "SELECT id, labels FROM bones_items", 10).expect("items");
+        assert_eq!(
+            items.rows,
+            vec![vec![json!("bn-001"), json!("api,backend")]]
+        );
+
+        let comments =
+            run_sql(&conn, "SELECT body, edited FROM bones_comments;", 10).expect("comments");
+        assert_eq!(comments.rows, vec![vec![json!("first, edited"), json!(1)]]);
+    }
+
+    #[test]
+    fn rejects_writes_and_multiple_statements() {
+        let (_dir, conn) = console();
+        for sql in [
+            "DELETE FROM items",
+            "UPDATE items SET title = 'x'",
+            "ATTACH DATABASE 'other.db' AS other",
+            "PRAGMA query_only = OFF",
+            "WITH x AS (SELECT 1) DELETE FROM items",
+        ] {
+            let err = run_sql(&conn, sql, 10).expect_err(sql);
+            assert!(
+                matches!(err, SqlError::NotReadOnly | SqlError::Sqlite(_)),
+                "{sql}: {err}"
+            );
+        }
+        assert!(matches!(
+            run_sql(&conn, "SELECT 1; DELETE FROM items", 10),
+            Err(SqlError::MultipleStatements)
+        ));
+        assert!(matches!(
+            run_sql(&conn, " -- nothing\n", 10),
+            Err(SqlError::Empty)
+        ));
+
+        let count = run_sql(&conn, "SELECT count(*) FROM items", 10).expect("count");
+        assert_eq!(count.rows, vec![vec![json!(2)]]);
+    }
+
+    #[test]
+    fn semicolons_in_strings_and_comments_are_one_statement() {
+        let (_dir, conn) = console();
+        let result = run_sql(
+            &conn,
+            "/* lead */ SELECT 'a;b' AS s -- trailing; comment\n;",
+            10,
+        )
+        .expect("select");
+        assert_eq!(result.rows, vec![vec![json!("a;b")]]);
+    }
+
+    #[test]
+    fn row_limit_marks_truncation() {
+        let (_dir, conn) = console();
+        let result = run_sql(
+            &conn,
+            "WITH RECURSIVE n(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n LIMIT 5) \
+             SELECT x FROM n",
+            3,
+        )
+        .expect("select");
+        assert_eq!(result.rows.len(), 3);
+        assert!(result.truncated);
+    }
+}
@@ -0,0 +1,474 @@
+//! Field-level backlog diff between two event sets.
+//!
+//! `bn diff main..HEAD` reads the event shards at two git revisions and asks
+//! how the backlog changed, rather than how the `.events` files changed. The
+//! events only the newer side has are found by hash; for every item they
+//! touch, the events both sides share are folded into a "before"
+//! [`WorkItemState`] and all of the newer side's events into an "after"
+//! state, and the two states are compared field by field.
+//!
+//! Folding both states through [`WorkItemState::apply_event`] means the diff
+//! reports what the newer side *converges to*, not a list of raw events: a
+//! label added and removed again on the branch does not show up, and a move
+//! that loses the epoch/phase merge to a shared later move is not reported.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::crdt::item_state::WorkItemState;
+use crate::event::Event;
+use crate::model::custom::custom_field_key;
+use crate::sync::merge::merge_event_sets;
+
+/// One field-level change to an item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum Change {
+    /// The item did not exist on the older side.
+    Created {
+        kind: String,
+        /// Workflow state (or sub-state) the item ends up in.
+        state: String,
+    },
+    /// The workflow state (or sub-state) changed.
+    Moved { from: String, to: String },
+    /// Parent, `blocked_by`, or `related_to` links changed.
+    Relinked {
+        /// `parent`, `blocked_by`, or `related_to`.
+        link: String,
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+    /// Labels were added or removed.
+    Relabelled {
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+    /// Assignees were added or removed.
+    Reassigned {
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+    /// An LWW field (title, urgency, due, `custom.<name>`, ...) changed.
+    Updated {
+        field: String,
+        from: Value,
+        to: Value,
+    },
+    /// Comments were added.
+    Commented { count: usize },
+    /// Attachments were added.
+    Attached { count: usize },
+    /// The item was soft-deleted.
+    Deleted,
+}
+
+/// How one item changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ItemDiff {
+    pub item_id: String,
+    /// Title on the newer side.
+    pub title: String,
+    /// Number of events only the newer side has for this item.
+    pub events: usize,
+    /// Changes in a fixed order: creation, state, links, labels, assignees,
+    /// other fields, comments, attachments, deletion.
+    pub changes: Vec<Change>,
+}
+
+/// How the backlog changed between two event sets.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BacklogDiff {
+    /// Events only the newer side has.
+    pub added_events: usize,
+    /// Events only the older side has, e.g. when both sides moved on since
+    /// they diverged. They are not part of the diff.
+    pub missing_events: usize,
+    /// Changed items, ordered by ID. Items whose new events cancel out are
+    /// omitted.
+    pub items: Vec<ItemDiff>,
+}
+
+/// Compare the backlog described by `old` with the one described by `new`.
+#[must_use]
+pub fn diff_event_sets(old: &[Event], new: &[Event]) -> BacklogDiff {
+    let old_hashes: HashSet<&str> = old.iter().map(|e| e.event_hash.as_str()).collect();
+    let new_hashes: HashSet<&str> = new.iter().map(|e| e.event_hash.as_str()).collect();
+
+    let new_sorted = merge_event_sets(new, &[]).events;
+    let mut added: BTreeMap<&str, usize> = BTreeMap::new();
+    for event in &new_sorted {
+        if !old_hashes.contains(event.event_hash.as_str()) {
+            *added.entry(event.item_id.as_str()).or_default() += 1;
+        }
+    }
+    let mut by_item: BTreeMap<&str, Vec<&Event>> = BTreeMap::new();
+    for event in &new_sorted {
+        if added.contains_key(event.item_id.as_str()) {
+            by_item
+                .entry(event.item_id.as_str())
+                .or_default()
+                .push(event);
+        }
+    }
+
+    let mut items = Vec::new();
+    for (item_id, events) in by_item {
+        let mut before = WorkItemState::new();
+        let mut after = WorkItemState::new();
+        let mut existed = false;
+        for event in events {
+            if old_hashes.contains(event.event_hash.as_str()) {
+                before.apply_event(event);
+                existed = true;
+            }
+            after.apply_event(event);
+        }
+        let changes = diff_states(existed.then_some(&before), &after);
+        if changes.is_empty() {
+            continue;
+        }
+        items.push(ItemDiff {
+            item_id: item_id.to_string(),
+            title: after.title.value.clone(),
+            events: added.get(item_id).copied().unwrap_or_default(),
+            changes,
+        });
+    }
+
+    BacklogDiff {
+        added_events: added.values().sum(),
+        missing_events: old_hashes.difference(&new_hashes).count(),
+        items,
+    }
+}
+
+/// Field-level changes from `before` (`None` for a new item) to `after`.
+fn diff_states(before: Option<&WorkItemState>, after: &WorkItemState) -> Vec<Change> {
+    let empty = WorkItemState::new();
+    let created = before.is_none();
+    let before = before.unwrap_or(&empty);
+    let mut changes = Vec::new();
+
+    if created {
+        changes.push(Change::Created {
+            kind: after.kind.value.to_string(),
+            state: state_name(after),
+        });
+    } else if state_name(before) != state_name(after) {
+        changes.push(Change::Moved {
+            from: state_name(before),
+            to: state_name(after),
+        });
+    }
+
+    let parent = |state: &WorkItemState| {
+        Some(state.parent.value.clone())
+            .filter(|p| !p.is_empty())
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+    };
+    let links = [
+        ("parent", parent(before), parent(after)),
+        (
+            "blocked_by",
+            owned(before.blocked_by_ids()),
+            owned(after.blocked_by_ids()),
+        ),
+        (
+            "related_to",
+            owned(before.related_to_ids()),
+            owned(after.related_to_ids()),
+        ),
+    ];
+    for (link, old, new) in links {
+        if let Some((added, removed)) = set_change(&old, &new) {
+            changes.push(Change::Relinked {
+                link: link.to_string(),
+                added,
+                removed,
+            });
+        }
+    }
+
+    if let Some((added, removed)) =
+        set_change(&owned(before.label_names()), &owned(after.label_names()))
+    {
+        changes.push(Change::Relabelled { added, removed });
+    }
+    if let Some((added, removed)) = set_change(
+        &owned(before.assignee_names()),
+        &owned(after.assignee_names()),
+    ) {
+        changes.push(Change::Reassigned { added, removed });
+    }
+
+    let old_fields = lww_fields(before);
+    for (field, to) in lww_fields(after) {
+        // A new item's title and kind are already part of the diff.
+        if created && (field == "title" || field == "kind") {
+            continue;
+        }
+        let from = old_fields.get(&field).cloned().unwrap_or(Value::Null);
+        if from != to {
+            changes.push(Change::Updated { field, from, to });
+        }
+    }
+
+    let comments = after.comments.len().saturating_sub(before.comments.len());
+    if comments > 0 {
+        changes.push(Change::Commented { count: comments });
+    }
+    let attachments = after
+        .attachments
+        .len()
+        .saturating_sub(before.attachments.len());
+    if attachments > 0 {
+        changes.push(Change::Attached { count: attachments });
+    }
+    if after.deleted.value && !before.deleted.value {
+        changes.push(Change::Deleted);
+    }
+    changes
+}
+
+/// The state shown for an item: its sub-state if it has one.
+fn state_name(state: &WorkItemState) -> String {
+    state
+        .substate()
+        .map_or_else(|| state.state.phase.to_string(), str::to_string)
+}
+
+/// LWW field values as JSON, with unset values as `null`.
+fn lww_fields(state: &WorkItemState) -> BTreeMap<String, Value> {
+    let mut fields = BTreeMap::from([
+        ("title".to_string(), Value::from(state.title.value.as_str())),
+        (
+            "kind".to_string(),
+            Value::from(state.kind.value.to_string()),
+        ),
+        (
+            "urgency".to_string(),
+            Value::from(state.urgency.value.to_string()),
+        ),
+        (
+            "size".to_string(),
+            state
+                .size
+                .value
+                .map_or(Value::Null, |size| Value::from(size.to_string())),
+        ),
+        (
+            "due".to_string(),
+            state.due.value.map_or(Value::Null, Value::from),
+        ),
+    ]);
+    let description = state.description_text();
+    fields.insert(
+        "description".to_string(),
+        if description.is_empty() {
+            Value::Null
+        } else {
+            Value::from(description)
+        },
+    );
+    for (name, register) in &state.custom {
+        fields.insert(
+            custom_field_key(name),
+            register.value.clone().unwrap_or(Value::Null),
+        );
+    }
+    fields
+}
+
+fn owned(set: HashSet<&String>) -> BTreeSet<String> {
+    set.into_iter().cloned().collect()
+}
+
+/// Added and removed members, or `None` if the sets are equal.
+fn set_change(
+    old: &BTreeSet<String>,
+    new: &BTreeSet<String>,
+) -> Option<(Vec<String>, Vec<String>)> {
+    let added: Vec<String> = new.difference(old).cloned().collect();
+    let removed: Vec<String> = old.difference(new).cloned().collect();
+    (!added.is_empty() || !removed.is_empty()).then_some((added, removed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::EventType;
+    use crate::event::data::{CreateData, EventData, LinkData, MoveData, UpdateData};
+    use crate::event::writer::write_event;
+    use crate::model::item::{Kind, State, Urgency};
+    use crate::model::item_id::ItemId;
+    use serde_json::json;
+
+    fn make_event(item: &str, ts: i64, event_type: EventType, data: EventData) -> Event {
+        let mut event = Event {
+            wall_ts_us: ts,
+            agent: "alice".to_string(),
+            itc: "itc:AQ".to_string(),
+            parents: vec![],
+            event_type,
+            item_id: ItemId::new_unchecked(item),
+            data,
+            event_hash: String::new(),
+            signature: None,
+        };
+        write_event(&mut event).expect("hash");
+        event
+    }
+
+    fn create(item: &str, ts: i64, labels: &[&str], parent: Option<&str>) -> Event {
+        make_event(
+            item,
+            ts,
+            EventType::Create,
+            EventData::Create(CreateData {
+                title: format!("Title {item}"),
+                kind: Kind::Task,
+                size: None,
+                urgency: Urgency::Default,
+                labels: labels.iter().map(ToString::to_string).collect(),
+                parent: parent.map(str::to_string),
+                causation: None,
+                description: None,
+                extra: BTreeMap::new(),
+            }),
+        )
+    }
+
+    fn move_to(item: &str, ts: i64, state: State) -> Event {
+        make_event(
+            item,
+            ts,
+            EventType::Move,
+            EventData::Move(MoveData {
+                state,
+                substate: None,
+                reason: None,
+                extra: BTreeMap::new(),
+            }),
+        )
+    }
+
+    fn update(item: &str, ts: i64, field: &str, value: Value) -> Event {
+        make_event(
+            item,
+            ts,
+            EventType::Update,
+            EventData::Update(UpdateData {
+                field: field.to_string(),
+                value,
+                extra: BTreeMap::new(),
+            }),
+        )
+    }
+
+    fn label(item: &str, ts: i64, action: &str, name: &str) -> Event {
+        update(item, ts, "labels", json!({"action": action, "label": name}))
+    }
+
+    #[test]
+    fn new_item_is_reported_as_created_with_its_links() {
+        let new = create("bn-a1", 1_000, &["backend"], Some("bn-g1"));
+        let diff = diff_event_sets(&[], std::slice::from_ref(&new));
+
+        assert_eq!(diff.added_events, 1);
+        assert_eq!(diff.items.len(), 1);
+        let item = &diff.items[0];
+        assert_eq!(item.title, "Title bn-a1");
+        assert_eq!(
+            item.changes,
+            [
+                Change::Created {
+                    kind: "task".to_string(),
+                    state: "open".to_string(),
+                },
+                Change::Relinked {
+                    link: "parent".to_string(),
+                    added: vec!["bn-g1".to_string()],
+                    removed: vec![],
+                },
+                Change::Relabelled {
+                    added: vec!["backend".to_string()],
+                    removed: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn existing_item_changes_are_field_level() {
+        let base = vec![create("bn-a1", 1_000, &["frontend"], None)];
+        let mut head = base.clone();
+        head.extend([
+            move_to("bn-a1", 2_000, State::Doing),
+            label("bn-a1", 3_000, "add", "backend"),
+            label("bn-a1", 3_001, "remove", "frontend"),
+            update("bn-a1", 4_000, "parent", Value::from("bn-g2")),
+            update("bn-a1", 5_000, "urgency", Value::from("urgent")),
+            make_event(
+                "bn-a1",
+                6_000,
+                EventType::Link,
+                EventData::Link(LinkData {
+                    target: "bn-b2".to_string(),
+                    link_type: "blocks".to_string(),
+                    extra: BTreeMap::new(),
+                }),
+            ),
+        ]);
+
+        let diff = diff_event_sets(&base, &head);
+        assert_eq!(diff.added_events, 6);
+        let changes = &diff.items[0].changes;
+        assert_eq!(
+            changes[0],
+            Change::Moved {
+                from: "open".to_string(),
+                to: "doing".to_string(),
+            }
+        );
+        assert!(changes.contains(&Change::Relinked {
+            link: "parent".to_string(),
+            added: vec!["bn-g2".to_string()],
+            removed: vec![],
+        }));
+        assert!(changes.contains(&Change::Relinked {
+            link: "blocked_by".to_string(),
+            added: vec!["bn-b2".to_string()],
+            removed: vec![],
+        }));
+        assert!(changes.contains(&Change::Relabelled {
+            added: vec!["backend".to_string()],
+            removed: vec!["frontend".to_string()],
+        }));
+        assert!(changes.contains(&Change::Updated {
+            field: "urgency".to_string(),
+            from: Value::from("default"),
+            to: Value::from("urgent"),
+        }));
+    }
+
+    #[test]
+    fn changes_that_cancel_out_and_older_only_events_are_not_items() {
+        let shared = create("bn-a1", 1_000, &[], None);
+        let old_only = move_to("bn-a1", 1_500, State::Done);
+        let base = vec![shared.clone(), old_only];
+        let head = vec![
+            shared,
+            label("bn-a1", 2_000, "add", "tmp"),
+            label("bn-a1", 3_000, "remove", "tmp"),
+        ];
+
+        let diff = diff_event_sets(&base, &head);
+        assert_eq!(diff.added_events, 2);
+        assert_eq!(diff.missing_events, 1);
+        assert!(diff.items.is_empty());
+    }
+}
@@ -4,6 +4,7 @@
 //! payload schema. Unknown fields are preserved via `#[serde(flatten)]`
 //! for forward compatibility.
 
+use crate::crdt::text::TextOp;
 use crate::model::item::{Kind, Size, State, Urgency};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -48,6 +49,14 @@ pub enum EventData {
     Snapshot(SnapshotData),
     /// Payload for `item.redact`.
     Redact(RedactData),
+    /// Payload for `item.patch`.
+    Patch(PatchData),
+    /// Payload for `item.comment_edit`.
+    CommentEdit(CommentEditData),
+    /// Payload for `item.comment_retract`.
+    CommentRetract(CommentRetractData),
+    /// Payload for `item.attach`.
+    Attach(AttachData),
 }
 
 impl EventData {
@@ -76,6 +85,14 @@ impl EventData {
                 serde_json::from_str::<SnapshotData>(json).map(EventData::Snapshot)
             }
             EventType::Redact => serde_json::from_str::<RedactData>(json).map(EventData::Redact),
+            EventType::Patch => serde_json::from_str::<PatchData>(json).map(EventData::Patch),
+            EventType::CommentEdit => {
+                serde_json::from_str::<CommentEditData>(json).map(EventData::CommentEdit)
+            }
+            EventType::CommentRetract => {
+                serde_json::from_str::<CommentRetractData>(json).map(EventData::CommentRetract)
+            }
+            EventType::Attach => serde_json::from_str::<AttachData>(json).map(EventData::Attach),
         };
 
         result.map_err(|source| DataParseError { event_type, source })
@@ -100,6 +117,10 @@ impl EventData {
             Self::Compact(d) => serde_json::to_value(d),
             Self::Snapshot(d) => serde_json::to_value(d),
             Self::Redact(d) => serde_json::to_value(d),
+            Self::Patch(d) => serde_json::to_value(d),
+            Self::CommentEdit(d) => serde_json::to_value(d),
+            Self::CommentRetract(d) => serde_json::to_value(d),
+            Self::Attach(d) => serde_json::to_value(d),
         }
     }
 }
@@ -118,6 +139,10 @@ impl Serialize for EventData {
             Self::Compact(d) => d.serialize(serializer),
             Self::Snapshot(d) => d.serialize(serializer),
             Self::Redact(d) => d.serialize(serializer),
+            Self::Patch(d) => d.serialize(serializer),
+            Self::CommentEdit(d) => d.serialize(serializer),
+            Self::CommentRetract(d) => d.serialize(serializer),
+            Self::Attach(d) => d.serialize(serializer),
         }
     }
 }
@@ -285,6 +310,11 @@ pub struct MoveData {
     /// Target state.
     pub state: State,
 
+    /// Configured sub-state within `state` (e.g. `review` within `doing`).
+    /// Absent for moves to a built-in state.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub substate: Option<String>,
+
     /// Optional reason for the transition (e.g. "Shipped in commit 9f3a2b1").
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reason: Option<String>,
@@ -312,12 +342,17 @@ pub struct AssignData {
 
 /// Payload for `item.comment`.
 ///
-/// Adds a comment or note to a work item.
+/// Adds a comment or note to a work item, optionally as a reply to an
+/// earlier comment.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CommentData {
     /// Comment body text.
     pub body: String,
 
+    /// Event hash of the comment this one answers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<String>,
+
     /// Unknown fields preserved for forward compatibility.
     #[serde(flatten)]
     pub extra: BTreeMap<String, serde_json::Value>,
@@ -415,6 +450,87 @@ pub struct RedactData {
     pub extra: BTreeMap<String, serde_json::Value>,
 }
 
+/// Payload for `item.patch`.
+///
+/// Character-level edits to the description, expressed as sequence CRDT ops
+/// (see [`crate::crdt::text`]) against the description written by the `base`
+/// event. Concurrent patches against the same base interleave; a later
+/// whole-value `item.update` of the description starts a new base and
+/// supersedes them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatchData {
+    /// Hash of the `item.create` / `item.update` event whose description
+    /// these ops edit.
+    pub base: String,
+
+    /// Text ops, applied in order.
+    pub ops: Vec<TextOp>,
+
+    /// Unknown fields preserved for forward compatibility.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+/// Payload for `item.comment_edit`.
+///
+/// Supersedes the body of an earlier comment. Edits to the same comment form
+/// an LWW register keyed by the comment hash; the original `item.comment`
+/// event is never rewritten.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentEditData {
+    /// Event hash of the `item.comment` being edited.
+    pub comment: String,
+
+    /// Replacement body text.
+    pub body: String,
+
+    /// Unknown fields preserved for forward compatibility.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+/// Payload for `item.comment_retract`.
+///
+/// Hides an earlier comment from timelines. Unlike `item.redact` the
+/// original text stays in the log and in the projection; retraction is
+/// grow-only and cannot be undone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentRetractData {
+    /// Event hash of the `item.comment` being retracted.
+    pub comment: String,
+
+    /// Optional human-readable reason.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// Unknown fields preserved for forward compatibility.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+/// Payload for `item.attach`.
+///
+/// References a file stored in the `.bones/blobs/` content-addressed store
+/// (see [`crate::blob`]). The bytes never enter the event log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttachData {
+    /// BLAKE3 hash of the file contents (`blake3:<base64url>`).
+    pub blob: String,
+
+    /// File name shown to users (no directory components).
+    pub name: String,
+
+    /// MIME type, e.g. `text/plain` or `image/png`.
+    pub mime: String,
+
+    /// Size of the file in bytes.
+    pub size: u64,
+
+    /// Unknown fields preserved for forward compatibility.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -530,6 +646,7 @@ mod tests {
         let data = MoveData {
             state: State::Archived,
             reason: Some("No longer needed".into()),
+            substate: None,
             extra: BTreeMap::new(),
         };
         let json = serde_json::to_string(&data).expect("serialize");
@@ -565,6 +682,7 @@ mod tests {
     fn comment_data_roundtrip() {
         let data = CommentData {
             body: "Root cause is a race in token refresh.".into(),
+            reply_to: None,
             extra: BTreeMap::new(),
         };
         let json = serde_json::to_string(&data).expect("serialize");
@@ -572,6 +690,69 @@ mod tests {
         assert_eq!(data, deser);
     }
 
+    #[test]
+    fn comment_data_reply_to_is_optional() {
+        let data: CommentData = serde_json::from_str(r#"{"body":"hi"}"#).expect("deserialize");
+        assert_eq!(data.reply_to, None);
+
+        let reply = CommentData {
+            body: "Agreed".into(),
+            reply_to: Some("blake3:c1".into()),
+            extra: BTreeMap::new(),
+        };
+        let json = serde_json::to_string(&reply).expect("serialize");
+        assert!(json.contains(r#""reply_to":"blake3:c1""#));
+        let deser: CommentData = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(reply, deser);
+    }
+
+    // === CommentEditData / CommentRetractData ===============================
+
+    #[test]
+    fn comment_edit_data_roundtrip() {
+        let data = CommentEditData {
+            comment: "blake3:c1".into(),
+            body: "Root cause is a race in session refresh.".into(),
+            extra: BTreeMap::new(),
+        };
+        let json = serde_json::to_string(&data).expect("serialize");
+        let deser: CommentEditData = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(data, deser);
+    }
+
+    #[test]
+    fn comment_retract_data_omits_missing_reason() {
+        let data = CommentRetractData {
+            comment: "blake3:c1".into(),
+            reason: None,
+            extra: BTreeMap::new(),
+        };
+        let json = serde_json::to_string(&data).expect("serialize");
+        assert_eq!(json, r#"{"comment":"blake3:c1"}"#);
+        let deser: CommentRetractData = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(data, deser);
+    }
+
+    // === AttachData =========================================================
+
+    #[test]
+    fn attach_data_roundtrip() {
+        let data = AttachData {
+            blob: "blake3:abc".into(),
+            name: "repro.log".into(),
+            mime: "text/plain".into(),
+            size: 2048,
+            extra: BTreeMap::new(),
+        };
+        let json = serde_json::to_string(&data).expect("serialize");
+        assert_eq!(
+            json,
+            r#"{"blob":"blake3:abc","name":"repro.log","mime":"text/plain","size":2048}"#
+        );
+        let deser: AttachData = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(data, deser);
+    }
+
     // === LinkData ===========================================================
 
     #[test]
@@ -688,6 +869,33 @@ mod tests {
         assert_eq!(data, deser);
     }
 
+    // === PatchData ==========================================================
+
+    #[test]
+    fn patch_data_roundtrip() {
+        use crate::crdt::text::TextId;
+
+        let data = PatchData {
+            base: "blake3:a1b2c3".into(),
+            ops: vec![
+                TextOp::Insert {
+                    id: TextId::new(12, "alice"),
+                    after: Some(TextId::base(4)),
+                    text: "big ".into(),
+                },
+                TextOp::Delete {
+                    start: TextId::base(0),
+                    len: 2,
+                },
+            ],
+            extra: BTreeMap::new(),
+        };
+        let json = serde_json::to_string(&data).expect("serialize");
+        assert!(json.contains(r#""op":"insert""#));
+        let deser: PatchData = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(data, deser);
+    }
+
     // === EventData::deserialize_for =========================================
 
     #[test]
@@ -767,6 +975,25 @@ mod tests {
         assert!(matches!(data, EventData::Redact(_)));
     }
 
+    #[test]
+    fn deserialize_for_patch() {
+        let json = r#"{"base":"blake3:abc","ops":[{"op":"delete","start":"1:","len":1}]}"#;
+        let data = EventData::deserialize_for(EventType::Patch, json).expect("should parse");
+        assert!(matches!(data, EventData::Patch(_)));
+    }
+
+    #[test]
+    fn deserialize_for_comment_edit_and_retract() {
+        let json = r#"{"comment":"blake3:c1","body":"fixed"}"#;
+        let data = EventData::deserialize_for(EventType::CommentEdit, json).expect("should parse");
+        assert!(matches!(data, EventData::CommentEdit(_)));
+
+        let json = r#"{"comment":"blake3:c1","reason":"wrong item"}"#;
+        let data =
+            EventData::deserialize_for(EventType::CommentRetract, json).expect("should parse");
+        assert!(matches!(data, EventData::CommentRetract(_)));
+    }
+
     #[test]
     fn deserialize_for_error_includes_event_type() {
         let err =
@@ -824,6 +1051,16 @@ mod tests {
                 r#"{"target_hash":"h","reason":"r","x":1}"#,
                 EventType::Redact,
             ),
+            (r#"{"base":"h","ops":[],"x":1}"#, EventType::Patch),
+            (
+                r#"{"comment":"h","body":"b","x":1}"#,
+                EventType::CommentEdit,
+            ),
+            (r#"{"comment":"h","x":1}"#, EventType::CommentRetract),
+            (
+                r#"{"blob":"h","name":"n","mime":"m","size":1,"x":1}"#,
+                EventType::Attach,
+            ),
         ];
 
         for (json_str, event_type) in test_cases {
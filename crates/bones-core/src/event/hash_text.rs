@@ -36,7 +36,7 @@ pub fn is_valid_blake3_hash(raw: &str) -> bool {
     decode_blake3_hash(raw).is_some()
 }
 
-fn encode_base64_url_no_pad(bytes: &[u8]) -> String {
+pub(crate) fn encode_base64_url_no_pad(bytes: &[u8]) -> String {
     let mut out = String::with_capacity((bytes.len() * 4).div_ceil(3));
     let mut idx = 0usize;
 
@@ -73,7 +73,7 @@ fn encode_base64_url_no_pad(bytes: &[u8]) -> String {
     out
 }
 
-fn decode_base64_url_no_pad(raw: &str) -> Option<Vec<u8>> {
+pub(crate) fn decode_base64_url_no_pad(raw: &str) -> Option<Vec<u8>> {
     let input = raw.as_bytes();
     if input.len() % 4 == 1 {
         return None;
@@ -63,6 +63,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: "blake3:deadbeef".into(),
+            signature: None,
         }
     }
 
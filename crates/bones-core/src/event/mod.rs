@@ -1,7 +1,7 @@
 //! Event data model for the bones event log.
 //!
 //! This module defines the core `Event` struct, the `EventType` enum covering
-//! all 11 event types, typed payload data structs, and the canonical JSON
+//! all 12 event types, typed payload data structs, and the canonical JSON
 //! serialization helper needed for deterministic event hashing.
 //!
 //! # TSJSON Format
@@ -9,9 +9,11 @@
 //! Events are stored in TSJSON (tab-separated fields with JSON payload):
 //!
 //! ```text
-//! wall_ts_us \t agent \t itc \t parents \t type \t item_id \t data \t event_hash
+//! wall_ts_us \t agent \t itc \t parents \t type \t item_id \t data \t event_hash [\t signature]
 //! ```
 //!
+//! The trailing `signature` field is optional; see [`crate::signing`].
+//!
 //! The `Event` struct maps 1:1 to a TSJSON line. Parsing and writing TSJSON
 //! lines is handled by the parser/writer modules (separate beads).
 
@@ -26,8 +28,9 @@ pub mod writer;
 
 pub use canonical::{canonicalize_json, canonicalize_json_str};
 pub use data::{
-    AssignAction, AssignData, CommentData, CompactData, CreateData, DataParseError, DeleteData,
-    EventData, LinkData, MoveData, RedactData, SnapshotData, UnlinkData, UpdateData,
+    AssignAction, AssignData, AttachData, CommentData, CommentEditData, CommentRetractData,
+    CompactData, CreateData, DataParseError, DeleteData, EventData, LinkData, MoveData, PatchData,
+    RedactData, SnapshotData, UnlinkData, UpdateData,
 };
 pub use migrate::{RawEvent, migrate_event};
 pub use parser::{
@@ -51,10 +54,11 @@ use serde::{Deserialize, Serialize};
 /// 2. `agent` — identifier of the agent/user that produced the event
 /// 3. `itc` — Interval Tree Clock stamp (canonical text encoding)
 /// 4. `parents` — parent event hashes (blake3:...), sorted lexicographically
-/// 5. `event_type` — one of the 11 event types
+/// 5. `event_type` — one of the 12 event types
 /// 6. `item_id` — the work item this event mutates
 /// 7. `data` — typed payload (JSON in TSJSON, deserialized here)
 /// 8. `event_hash` — BLAKE3 hash of fields 1–7
+/// 9. `signature` — optional Ed25519 signature over `event_hash`
 ///
 /// # Serde
 ///
@@ -98,6 +102,12 @@ pub struct Event {
     /// Merkle-DAG and is used for parent references, shard manifests,
     /// and sync diffing.
     pub event_hash: String,
+
+    /// Detached Ed25519 signature over `event_hash` (`ed25519:<base64url>`).
+    ///
+    /// Not covered by the hash, so signing never changes an event's identity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
 }
 
 impl<'de> Deserialize<'de> for Event {
@@ -117,6 +127,8 @@ impl<'de> Deserialize<'de> for Event {
             item_id: ItemId,
             data: serde_json::Value,
             event_hash: String,
+            #[serde(default)]
+            signature: Option<String>,
         }
 
         let raw = EventRaw::deserialize(deserializer)?;
@@ -133,6 +145,7 @@ impl<'de> Deserialize<'de> for Event {
             item_id: raw.item_id,
             data,
             event_hash: raw.event_hash,
+            signature: raw.signature,
         })
     }
 }
@@ -186,6 +199,14 @@ impl std::fmt::Display for Event {
                 }
                 EventData::Snapshot(_) => "snapshot".to_string(),
                 EventData::Redact(d) => format!("redact: {}", d.target_hash),
+                EventData::Patch(d) => format!("patch: {} op(s) on {}", d.ops.len(), d.base),
+                EventData::CommentEdit(d) => {
+                    let preview = truncate_for_display(&d.body, 40);
+                    format!("comment edit: {} {preview}", d.comment)
+                }
+                EventData::CommentRetract(d) => format!("comment retract: {}", d.comment),
+                EventData::Attach(d) =>
+                    format!("attach: {} ({}, {} bytes)", d.name, d.mime, d.size),
             }
         )
     }
@@ -221,6 +242,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: "blake3:a1b2c3d4e5f6".into(),
+            signature: None,
         }
     }
 
@@ -235,9 +257,11 @@ mod tests {
             data: EventData::Move(MoveData {
                 state: crate::model::item::State::Doing,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             event_hash: "blake3:d4e5f6789abc".into(),
+            signature: None,
         }
     }
 
@@ -290,6 +314,7 @@ mod tests {
         event.event_type = EventType::Comment;
         event.data = EventData::Comment(CommentData {
             body: "é".repeat(60),
+            reply_to: None,
             extra: BTreeMap::new(),
         });
 
@@ -349,6 +374,7 @@ mod tests {
                         extra: BTreeMap::new(),
                     }),
                     event_hash: hash,
+                    signature: None,
                 }
             },
             {
@@ -366,6 +392,7 @@ mod tests {
                         extra: BTreeMap::new(),
                     }),
                     event_hash: hash,
+                    signature: None,
                 }
             },
             {
@@ -380,9 +407,11 @@ mod tests {
                     data: EventData::Move(MoveData {
                         state: crate::model::item::State::Done,
                         reason: Some("done".into()),
+                        substate: None,
                         extra: BTreeMap::new(),
                     }),
                     event_hash: hash,
+                    signature: None,
                 }
             },
             {
@@ -400,6 +429,7 @@ mod tests {
                         extra: BTreeMap::new(),
                     }),
                     event_hash: hash,
+                    signature: None,
                 }
             },
             {
@@ -413,9 +443,11 @@ mod tests {
                     item_id,
                     data: EventData::Comment(CommentData {
                         body: "Note".into(),
+                        reply_to: None,
                         extra: BTreeMap::new(),
                     }),
                     event_hash: hash,
+                    signature: None,
                 }
             },
             {
@@ -433,6 +465,7 @@ mod tests {
                         extra: BTreeMap::new(),
                     }),
                     event_hash: hash,
+                    signature: None,
                 }
             },
             {
@@ -450,6 +483,7 @@ mod tests {
                         extra: BTreeMap::new(),
                     }),
                     event_hash: hash,
+                    signature: None,
                 }
             },
             {
@@ -466,6 +500,7 @@ mod tests {
                         extra: BTreeMap::new(),
                     }),
                     event_hash: hash,
+                    signature: None,
                 }
             },
             {
@@ -482,6 +517,7 @@ mod tests {
                         extra: BTreeMap::new(),
                     }),
                     event_hash: hash,
+                    signature: None,
                 }
             },
             {
@@ -498,6 +534,7 @@ mod tests {
                         extra: BTreeMap::new(),
                     }),
                     event_hash: hash,
+                    signature: None,
                 }
             },
             {
@@ -515,11 +552,34 @@ mod tests {
                         extra: BTreeMap::new(),
                     }),
                     event_hash: hash,
+                    signature: None,
+                }
+            },
+            {
+                let (ts, agent, itc, parents, item_id, hash) = base();
+                Event {
+                    wall_ts_us: ts,
+                    agent,
+                    itc,
+                    parents,
+                    event_type: EventType::Patch,
+                    item_id,
+                    data: EventData::Patch(PatchData {
+                        base: "blake3:xyz".into(),
+                        ops: vec![crate::crdt::text::TextOp::Insert {
+                            id: crate::crdt::text::TextId::new(4, "alice"),
+                            after: Some(crate::crdt::text::TextId::base(0)),
+                            text: "héllo".into(),
+                        }],
+                        extra: BTreeMap::new(),
+                    }),
+                    event_hash: hash,
+                    signature: None,
                 }
             },
         ];
 
-        assert_eq!(events.len(), 11, "should cover all 11 event types");
+        assert_eq!(events.len(), 12, "should cover all 12 event types");
 
         for event in &events {
             let json = serde_json::to_string(event)
@@ -7,12 +7,13 @@
 //! # TSJSON Format (v1, 8-field)
 //!
 //! ```text
-//! wall_ts_us \t agent \t itc \t parents \t type \t item_id \t data \t event_hash
+//! wall_ts_us \t agent \t itc \t parents \t type \t item_id \t data \t event_hash [\t signature]
 //! ```
 //!
 //! - Comment lines start with `#` and are returned as [`ParsedLine::Comment`].
 //! - Blank/whitespace-only lines are returned as [`ParsedLine::Blank`].
-//! - Data lines are split on exactly 7 tab characters (yielding 8 fields).
+//! - Data lines are split on exactly 7 tab characters (yielding 8 fields),
+//!   or 8 when the optional signature field is present.
 //!
 //! # Zero-copy
 //!
@@ -30,6 +31,7 @@ use crate::event::hash_text::{decode_blake3_hash, encode_blake3_hash, is_valid_b
 use crate::event::migrate_event;
 use crate::event::types::EventType;
 use crate::model::item_id::ItemId;
+use crate::signing::is_valid_signature_text;
 
 // ---------------------------------------------------------------------------
 // Shard header constants
@@ -84,6 +86,8 @@ pub enum ParseError {
     },
     /// The `event_hash` field has an invalid format.
     InvalidEventHash(String),
+    /// The optional `signature` field is not `ed25519:<base64url>`.
+    InvalidSignature(String),
     /// The computed hash does not match `event_hash`.
     HashMismatch {
         /// Expected (from the line).
@@ -131,6 +135,9 @@ impl fmt::Display for ParseError {
             Self::InvalidEventHash(raw) => {
                 write!(f, "invalid event_hash format: '{raw}'")
             }
+            Self::InvalidSignature(raw) => {
+                write!(f, "invalid signature format: '{raw}'")
+            }
             Self::HashMismatch { expected, computed } => {
                 write!(
                     f,
@@ -243,6 +250,8 @@ pub struct PartialEvent<'a> {
     pub data_raw: &'a str,
     /// Raw event hash.
     pub event_hash_raw: &'a str,
+    /// Raw signature field, if present (not validated).
+    pub signature_raw: Option<&'a str>,
 }
 
 /// The result of partially parsing a single line.
@@ -339,7 +348,7 @@ pub fn parse_line_partial(line: &str) -> Result<PartialParsedLine<'_>, ParseErro
 
     // Split on tabs
     let fields: Vec<&str> = split_fields(trimmed).collect();
-    if fields.len() != 8 {
+    if !matches!(fields.len(), 8 | 9) {
         return Err(ParseError::FieldCount {
             found: fields.len(),
             expected: 8,
@@ -365,6 +374,7 @@ pub fn parse_line_partial(line: &str) -> Result<PartialParsedLine<'_>, ParseErro
         item_id_raw: fields[5],
         data_raw: fields[6],
         event_hash_raw: fields[7],
+        signature_raw: fields.get(8).copied(),
     }))
 }
 
@@ -375,7 +385,7 @@ pub fn parse_line_partial(line: &str) -> Result<PartialParsedLine<'_>, ParseErro
 /// Fully parse and validate a TSJSON line into a [`ParsedLine`].
 ///
 /// Performs all validations including:
-/// - Field count (exactly 8 tab-separated fields)
+/// - Field count (8 tab-separated fields, or 9 with a signature)
 /// - `wall_ts_us` is a valid i64
 /// - `agent` is non-empty and contains no whitespace
 /// - `itc` is non-empty
@@ -384,6 +394,8 @@ pub fn parse_line_partial(line: &str) -> Result<PartialParsedLine<'_>, ParseErro
 /// - `item_id` is a valid bones ID
 /// - `data` is valid JSON matching the event type schema
 /// - `event_hash` is `blake3:<payload>` and matches the recomputed hash
+/// - `signature`, if present, is a well-formed `ed25519:` signature (it is
+///   not verified here; that needs the keyring, see [`crate::signing`])
 ///
 /// # Errors
 ///
@@ -403,7 +415,7 @@ pub fn parse_line(line: &str) -> Result<ParsedLine, ParseError> {
 
     // Split on tabs
     let fields: Vec<&str> = split_fields(trimmed).collect();
-    if fields.len() != 8 {
+    if !matches!(fields.len(), 8 | 9) {
         return Err(ParseError::FieldCount {
             found: fields.len(),
             expected: 8,
@@ -491,6 +503,14 @@ pub fn parse_line(line: &str) -> Result<ParsedLine, ParseError> {
         });
     }
 
+    // --- Field 9: signature (optional) ---
+    let signature = match fields.get(8) {
+        Some(raw) if !is_valid_signature_text(raw) => {
+            return Err(ParseError::InvalidSignature((*raw).to_string()));
+        }
+        other => other.map(|raw| (*raw).to_string()),
+    };
+
     Ok(ParsedLine::Event(Box::new(Event {
         wall_ts_us,
         agent: agent.to_string(),
@@ -500,6 +520,7 @@ pub fn parse_line(line: &str) -> Result<ParsedLine, ParseError> {
         item_id,
         data,
         event_hash: event_hash.to_string(),
+        signature,
     })))
 }
 
@@ -798,6 +819,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_signed_event_keeps_signature_out_of_hash() {
+        let line = make_line(
+            1_708_012_200_123_456,
+            "claude-abc",
+            "itc:AQ",
+            "",
+            "item.create",
+            "bn-a7x",
+            &sample_create_json(),
+        );
+        let hash = line.rsplit('\t').next().expect("hash field");
+        let key = crate::signing::SigningKey::from_bytes(&[7; 32]);
+        let signature = crate::signing::sign_event_hash(&key, hash).expect("sign");
+        let signed = format!("{line}\t{signature}");
+
+        let ParsedLine::Event(event) = parse_line(&signed).expect("should parse") else {
+            panic!("expected event");
+        };
+        assert_eq!(event.event_hash, hash);
+        assert_eq!(event.signature.as_deref(), Some(signature.as_str()));
+
+        let PartialParsedLine::Event(partial) = parse_line_partial(&signed).expect("partial")
+        else {
+            panic!("expected event");
+        };
+        assert_eq!(partial.signature_raw, Some(signature.as_str()));
+
+        let err = parse_line(&format!("{line}\ted25519:bogus")).expect_err("bad signature");
+        assert!(matches!(err, ParseError::InvalidSignature(_)));
+    }
+
     #[test]
     fn parse_valid_move_event_with_parent() {
         let parent_hash = "blake3:a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6abcd";
@@ -915,11 +968,11 @@ mod tests {
 
     #[test]
     fn parse_wrong_field_count_too_many() {
-        let err = parse_line("1\t2\t3\t4\t5\t6\t7\t8\t9").expect_err("should fail");
+        let err = parse_line("1\t2\t3\t4\t5\t6\t7\t8\t9\t10").expect_err("should fail");
         assert!(matches!(
             err,
             ParseError::FieldCount {
-                found: 9,
+                found: 10,
                 expected: 8
             }
         ));
@@ -1072,6 +1125,7 @@ mod tests {
         let data = MoveData {
             state: State::Doing,
             reason: None,
+            substate: None,
             extra: BTreeMap::new(),
         };
 
@@ -1436,7 +1490,7 @@ mod tests {
     }
 
     // -----------------------------------------------------------------------
-    // All 11 event types parse successfully
+    // All 12 event types parse successfully
     // -----------------------------------------------------------------------
 
     #[test]
@@ -1456,6 +1510,10 @@ mod tests {
                 "item.redact",
                 r#"{"target_hash":"blake3:abc","reason":"oops"}"#,
             ),
+            (
+                "item.patch",
+                r#"{"base":"blake3:abc","ops":[{"op":"insert","id":"5:alice","text":"hi"}]}"#,
+            ),
         ];
 
         for (event_type, data_json) in test_cases {
@@ -1,4 +1,4 @@
-//! Event type enum covering all 11 TSJSON event types.
+//! Event type enum covering all 15 TSJSON event types.
 //!
 //! Each event type corresponds to a specific work-item mutation. The string
 //! representation uses the `item.<verb>` dotted format used in the TSJSON
@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
 
-/// The 11 event types in the bones event catalog.
+/// The 15 event types in the bones event catalog.
 ///
 /// String representation follows the `item.<verb>` convention used in the
 /// TSJSON event log format.
@@ -36,6 +36,14 @@ pub enum EventType {
     Snapshot,
     /// Replace event payload with [redacted] in projection.
     Redact,
+    /// Character-level edit of the description (sequence CRDT).
+    Patch,
+    /// Replace the body of an earlier comment (LWW per comment).
+    CommentEdit,
+    /// Hide an earlier comment without redacting it.
+    CommentRetract,
+    /// Attach a content-addressed blob (file) to the item.
+    Attach,
 }
 
 /// Error returned when parsing an unknown event type string.
@@ -51,7 +59,8 @@ impl fmt::Display for UnknownEventType {
             f,
             "unknown event type '{}': expected one of item.create, item.update, \
              item.move, item.assign, item.comment, item.link, item.unlink, \
-             item.delete, item.compact, item.snapshot, item.redact",
+             item.delete, item.compact, item.snapshot, item.redact, item.patch, \
+             item.comment_edit, item.comment_retract, item.attach",
             self.raw
         )
     }
@@ -61,7 +70,7 @@ impl std::error::Error for UnknownEventType {}
 
 impl EventType {
     /// All known event types in catalog order.
-    pub const ALL: [Self; 11] = [
+    pub const ALL: [Self; 15] = [
         Self::Create,
         Self::Update,
         Self::Move,
@@ -73,6 +82,10 @@ impl EventType {
         Self::Compact,
         Self::Snapshot,
         Self::Redact,
+        Self::Patch,
+        Self::CommentEdit,
+        Self::CommentRetract,
+        Self::Attach,
     ];
 
     /// Return the canonical `item.<verb>` string representation.
@@ -90,6 +103,10 @@ impl EventType {
             Self::Compact => "item.compact",
             Self::Snapshot => "item.snapshot",
             Self::Redact => "item.redact",
+            Self::Patch => "item.patch",
+            Self::CommentEdit => "item.comment_edit",
+            Self::CommentRetract => "item.comment_retract",
+            Self::Attach => "item.attach",
         }
     }
 }
@@ -116,6 +133,10 @@ impl FromStr for EventType {
             "item.compact" => Ok(Self::Compact),
             "item.snapshot" => Ok(Self::Snapshot),
             "item.redact" => Ok(Self::Redact),
+            "item.patch" => Ok(Self::Patch),
+            "item.comment_edit" => Ok(Self::CommentEdit),
+            "item.comment_retract" => Ok(Self::CommentRetract),
+            "item.attach" => Ok(Self::Attach),
             _ => Err(UnknownEventType { raw: s.to_string() }),
         }
     }
@@ -153,6 +174,10 @@ mod tests {
             (EventType::Compact, "item.compact"),
             (EventType::Snapshot, "item.snapshot"),
             (EventType::Redact, "item.redact"),
+            (EventType::Patch, "item.patch"),
+            (EventType::CommentEdit, "item.comment_edit"),
+            (EventType::CommentRetract, "item.comment_retract"),
+            (EventType::Attach, "item.attach"),
         ];
 
         for (et, s) in expected {
@@ -216,8 +241,8 @@ mod tests {
     }
 
     #[test]
-    fn all_contains_exactly_11_types() {
-        assert_eq!(EventType::ALL.len(), 11);
+    fn all_contains_exactly_15_types() {
+        assert_eq!(EventType::ALL.len(), 15);
     }
 
     #[test]
@@ -10,6 +10,10 @@
 //! 3. **Semantic validation** — Enum constraint checks (kind, urgency, size,
 //!    state values), item ID format, link target format.
 //!
+//! Custom field updates are additionally checked against the project schema
+//! at write time by [`validate_custom_field`]; the schema lives in project
+//! config, so shard-level validation does not apply it.
+//!
 //! # Shard-level checks
 //!
 //! - Verifies Merkle hash chains against shard manifests.
@@ -36,7 +40,10 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::event::Event;
+use crate::event::data::EventData;
 use crate::event::parser::{self, ParseError, ParsedLine};
+use crate::model::custom::{CustomFieldError, CustomFieldSchema, custom_field_name};
 use crate::shard::ShardManifest;
 
 // ---------------------------------------------------------------------------
@@ -98,6 +105,8 @@ pub enum ValidationErrorKind {
     EmptyItc,
     /// A parent hash has an invalid format.
     InvalidParentHash,
+    /// The optional signature field is not `ed25519:<base64url>`.
+    InvalidSignature,
     /// Shard file BLAKE3 hash does not match manifest.
     ManifestMismatch,
     /// Shard file event count does not match manifest.
@@ -169,7 +178,7 @@ pub fn validate_event(line: &str, line_num: usize) -> Result<(), ValidationError
 
     // Check payload size before full parse
     let fields: Vec<&str> = trimmed.split('\t').collect();
-    if fields.len() != 8 {
+    if !matches!(fields.len(), 8 | 9) {
         return Err(ValidationError {
             line_num,
             kind: ValidationErrorKind::BadFieldCount,
@@ -199,6 +208,35 @@ pub fn validate_event(line: &str, line_num: usize) -> Result<(), ValidationError
     }
 }
 
+/// Check a `custom.<name>` update against the project's custom field schema.
+///
+/// Events that do not update a custom field pass unchanged. The field must
+/// be declared in `schema` and its value must match the declared type;
+/// `null` (clear) is accepted for any declared field.
+///
+/// # Errors
+///
+/// Returns a [`CustomFieldError`] describing the unknown field or the
+/// mismatched value.
+pub fn validate_custom_field(
+    event: &Event,
+    schema: &CustomFieldSchema,
+) -> Result<(), CustomFieldError> {
+    let EventData::Update(data) = &event.data else {
+        return Ok(());
+    };
+    let Some(name) = custom_field_name(&data.field) else {
+        return Ok(());
+    };
+
+    let def = schema
+        .get(name)
+        .ok_or_else(|| CustomFieldError::UnknownField {
+            name: name.to_string(),
+        })?;
+    def.check_value(name, &data.value)
+}
+
 // ---------------------------------------------------------------------------
 // Shard-level validation
 // ---------------------------------------------------------------------------
@@ -387,6 +425,10 @@ fn parse_error_to_validation(err: &ParseError, line_num: usize, raw: &str) -> Va
             ValidationErrorKind::InvalidHashFormat,
             format!("invalid event_hash format: '{raw_hash}'"),
         ),
+        ParseError::InvalidSignature(raw_sig) => (
+            ValidationErrorKind::InvalidSignature,
+            format!("invalid signature format: '{raw_sig}'"),
+        ),
         ParseError::HashMismatch { expected, computed } => (
             ValidationErrorKind::HashChainBroken,
             format!("event_hash mismatch: line has '{expected}', computed '{computed}'"),
@@ -1062,4 +1104,72 @@ mod tests {
         };
         assert!(!report.is_ok()); // truncation makes it not OK
     }
+
+    // -----------------------------------------------------------------------
+    // Custom field validation
+    // -----------------------------------------------------------------------
+
+    fn custom_update(field: &str, value: serde_json::Value) -> Event {
+        use crate::event::data::UpdateData;
+        use crate::event::types::EventType;
+        use crate::model::item_id::ItemId;
+
+        Event {
+            wall_ts_us: 1,
+            agent: "alice".into(),
+            itc: "itc:AQ".into(),
+            parents: vec![],
+            event_type: EventType::Update,
+            item_id: ItemId::new_unchecked("bn-a7x"),
+            data: EventData::Update(UpdateData {
+                field: field.into(),
+                value,
+                extra: std::collections::BTreeMap::new(),
+            }),
+            event_hash: "blake3:x".into(),
+            signature: None,
+        }
+    }
+
+    fn severity_schema() -> CustomFieldSchema {
+        use crate::model::custom::{CustomFieldDef, CustomFieldType};
+
+        CustomFieldSchema::from([(
+            "severity".to_string(),
+            CustomFieldDef {
+                field_type: CustomFieldType::Int,
+                values: Vec::new(),
+            },
+        )])
+    }
+
+    #[test]
+    fn validate_custom_field_accepts_typed_value_and_clear() {
+        let schema = severity_schema();
+        let set = custom_update("custom.severity", serde_json::json!(3));
+        let clear = custom_update("custom.severity", serde_json::Value::Null);
+        let title = custom_update("title", serde_json::json!("anything"));
+
+        assert!(validate_custom_field(&set, &schema).is_ok());
+        assert!(validate_custom_field(&clear, &schema).is_ok());
+        assert!(validate_custom_field(&title, &schema).is_ok());
+    }
+
+    #[test]
+    fn validate_custom_field_rejects_unknown_and_mistyped() {
+        let schema = severity_schema();
+        let unknown = custom_update("custom.customer", serde_json::json!("acme"));
+        let mistyped = custom_update("custom.severity", serde_json::json!("high"));
+
+        assert_eq!(
+            validate_custom_field(&unknown, &schema),
+            Err(CustomFieldError::UnknownField {
+                name: "customer".into()
+            })
+        );
+        assert!(matches!(
+            validate_custom_field(&mistyped, &schema),
+            Err(CustomFieldError::InvalidValue { .. })
+        ));
+    }
 }
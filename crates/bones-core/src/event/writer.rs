@@ -6,11 +6,12 @@
 //! - One-line invariant: no literal `\n` in the serialized JSON.
 //! - Deterministic: same event always produces the same output bytes.
 //! - Event hash is BLAKE3 of fields 1–7 joined by tabs, newline-terminated.
+//! - The optional signature field is appended only when present.
 //!
 //! # TSJSON Format
 //!
 //! ```text
-//! {wall_ts_us}\t{agent}\t{itc}\t{parents}\t{type}\t{item_id}\t{data_json}\t{event_hash}\n
+//! {wall_ts_us}\t{agent}\t{itc}\t{parents}\t{type}\t{item_id}\t{data_json}\t{event_hash}[\t{signature}]\n
 //! ```
 
 use super::Event;
@@ -55,7 +56,7 @@ pub fn shard_header() -> String {
 /// Serialize an [`Event`] to a single TSJSON line (without trailing newline).
 ///
 /// The data payload is serialized as canonical JSON (sorted keys, compact).
-/// The `event_hash` field on the Event is included as-is.
+/// The `event_hash` and `signature` fields on the Event are included as-is.
 ///
 /// # Errors
 ///
@@ -73,7 +74,7 @@ pub fn to_tsjson_line(event: &Event) -> Result<String, WriteError> {
 
     let parents = event.parents_str();
 
-    Ok(format!(
+    let mut line = format!(
         "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
         event.wall_ts_us,
         event.agent,
@@ -83,7 +84,12 @@ pub fn to_tsjson_line(event: &Event) -> Result<String, WriteError> {
         event.item_id,
         data_json,
         event.event_hash,
-    ))
+    );
+    if let Some(signature) = &event.signature {
+        line.push('\t');
+        line.push_str(signature);
+    }
+    Ok(line)
 }
 
 /// Serialize an [`Event`] to a TSJSON line with trailing newline.
@@ -132,6 +138,8 @@ pub fn compute_event_hash(event: &Event) -> Result<String, WriteError> {
 ///
 /// This is the primary write path: it computes the content hash, stores it
 /// in `event.event_hash`, and returns the full TSJSON line (with newline).
+/// `event.signature` is written as-is; signing is up to the caller (see
+/// [`crate::signing::sign_as_author`]).
 ///
 /// # Errors
 ///
@@ -183,6 +191,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: "blake3:placeholder".into(),
+            signature: None,
         }
     }
 
@@ -197,9 +206,11 @@ mod tests {
             data: EventData::Move(MoveData {
                 state: crate::model::item::State::Doing,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             event_hash: "blake3:d4e5f6789abc".into(),
+            signature: None,
         }
     }
 
@@ -345,6 +356,31 @@ mod tests {
         assert!(line.contains(&event.event_hash));
     }
 
+    #[test]
+    fn write_event_never_signs() {
+        let keys = tempfile::tempdir().expect("tempdir");
+        let key = crate::signing::generate_signing_key(keys.path(), "claude-abc", false)
+            .expect("generate key");
+
+        let mut event = sample_create_event();
+        write_event(&mut event).expect("write");
+        assert_eq!(event.signature, None);
+
+        // A signature set by the author survives re-serialization.
+        let signature = crate::signing::sign_as_author(keys.path(), &event)
+            .expect("sign")
+            .expect("agent has a key");
+        assert!(crate::signing::verify_event_hash(
+            &key.verifying_key(),
+            &event.event_hash,
+            &signature
+        ));
+        event.signature = Some(signature.clone());
+        let line = write_event(&mut event).expect("write");
+        assert_eq!(event.signature, Some(signature.clone()));
+        assert!(line.trim_end().ends_with(&signature));
+    }
+
     #[test]
     fn deterministic_output() {
         let event = sample_create_event();
@@ -367,6 +403,7 @@ mod tests {
             item_id: ItemId::new_unchecked("bn-a7x"),
             data,
             event_hash: "blake3:000".into(),
+            signature: None,
         };
 
         let events = vec![
@@ -397,6 +434,7 @@ mod tests {
                 EventData::Move(MoveData {
                     state: State::Done,
                     reason: Some("done".into()),
+                    substate: None,
                     extra: BTreeMap::new(),
                 }),
             ),
@@ -412,6 +450,7 @@ mod tests {
                 EventType::Comment,
                 EventData::Comment(CommentData {
                     body: "Note".into(),
+                    reply_to: None,
                     extra: BTreeMap::new(),
                 }),
             ),
@@ -460,9 +499,20 @@ mod tests {
                     extra: BTreeMap::new(),
                 }),
             ),
+            base_event(
+                EventType::Patch,
+                EventData::Patch(PatchData {
+                    base: "blake3:xyz".into(),
+                    ops: vec![crate::crdt::text::TextOp::Delete {
+                        start: crate::crdt::text::TextId::base(0),
+                        len: 2,
+                    }],
+                    extra: BTreeMap::new(),
+                }),
+            ),
         ];
 
-        assert_eq!(events.len(), 11, "should cover all 11 event types");
+        assert_eq!(events.len(), 12, "should cover all 12 event types");
 
         for event in &events {
             let result = to_tsjson_line(event);
@@ -506,6 +556,23 @@ mod tests {
         assert_eq!(recomputed, event.event_hash);
     }
 
+    #[test]
+    fn signature_is_appended_as_ninth_field() {
+        let mut event = sample_create_event();
+        event.event_hash = compute_event_hash(&event).expect("hash");
+        let key = crate::signing::SigningKey::from_bytes(&[3; 32]);
+        event.signature =
+            Some(crate::signing::sign_event_hash(&key, &event.event_hash).expect("sign"));
+
+        let line = write_line(&event).expect("write");
+        let fields: Vec<&str> = line.trim_end().split('\t').collect();
+        assert_eq!(fields.len(), 9);
+        assert_eq!(fields[7], event.event_hash);
+
+        let parsed = crate::event::parse_line(&line).expect("parse");
+        assert_eq!(parsed, crate::event::ParsedLine::Event(Box::new(event)));
+    }
+
     #[test]
     fn empty_extra_fields_not_in_json() {
         // BTreeMap extras should not appear when empty
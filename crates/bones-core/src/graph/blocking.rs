@@ -281,6 +281,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: hash.to_string(),
+            signature: None,
         }
     }
 
@@ -306,6 +307,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: hash.to_string(),
+            signature: None,
         }
     }
 
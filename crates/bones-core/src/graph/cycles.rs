@@ -421,6 +421,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: hash.to_string(),
+            signature: None,
         }
     }
 
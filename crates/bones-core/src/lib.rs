@@ -1,25 +1,32 @@
 #![deny(unsafe_code)]
 //! bones-core library.
 
+pub mod blob;
 pub mod cache;
 pub mod capabilities;
 pub mod clock;
 pub mod compact;
 pub mod config;
+pub mod conflicts;
 pub mod crdt;
 pub mod dag;
 pub mod db;
+pub mod diff;
 pub mod error;
 pub mod event;
 pub mod graph;
 pub mod lock;
 pub mod model;
+pub mod policy;
 pub mod recovery;
+pub mod redact;
 pub mod shard;
+pub mod signing;
 pub mod sync;
 pub mod timing;
 pub mod undo;
 pub mod verify;
+pub mod views;
 
 use tracing::{info, instrument};
 
@@ -40,6 +47,7 @@ pub fn init() {
     let gitattributes_path = bones_dir.join(".gitattributes");
     let events_entry = "events/*.events merge=union";
     let manifest_entry = "events/*.manifest -merge";
+    let blobs_entry = "blobs/** binary";
     // Patterns superseded by the scoped entries above. `events merge=union`
     // matched only a file literally named `events`; `events/** merge=union`
     // was over-broad and (wrongly) union-merged the `.manifest` snapshots.
@@ -55,6 +63,10 @@ events/*.events merge=union
 # Never union them — that corrupts the integrity record. Let merges surface a
 # conflict; regenerate with `bn verify` / rebuild after merging a sealed shard.
 events/*.manifest -merge
+
+# Attachment blobs are content-addressed: a path never changes contents, so
+# they never need a merge or a text diff.
+blobs/** binary
 ";
 
     let existing = if gitattributes_path.exists() {
@@ -65,12 +77,13 @@ events/*.manifest -merge
 
     let has_events = existing.lines().any(|line| line.trim() == events_entry);
     let has_manifest = existing.lines().any(|line| line.trim() == manifest_entry);
+    let has_blobs = existing.lines().any(|line| line.trim() == blobs_entry);
     let has_legacy = existing.lines().any(|line| {
         let t = line.trim();
         t == legacy_bare || t == legacy_glob
     });
 
-    if has_events && has_manifest && !has_legacy {
+    if has_events && has_manifest && has_blobs && !has_legacy {
         return;
     }
 
@@ -0,0 +1,373 @@
+//! Typed custom fields on work items.
+//!
+//! Projects declare extra per-item attributes in `.bones/config.toml`:
+//!
+//! ```toml
+//! [custom_fields.component]
+//! type = "enum"
+//! values = ["api", "cli", "tui"]
+//!
+//! [custom_fields.severity]
+//! type = "int"
+//! ```
+//!
+//! Values travel as `item.update` events whose field is `custom.<name>`, so
+//! every field is an independent LWW register and replicas that predate
+//! custom fields simply ignore them. A `null` value clears the field.
+//!
+//! The schema is enforced when events are written (see
+//! [`crate::event::validate::validate_custom_field`]). Replay accepts any
+//! scalar so replicas with diverging schemas still converge.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Prefix of `item.update` fields that carry custom field values.
+pub const CUSTOM_FIELD_PREFIX: &str = "custom.";
+
+/// Maximum length of a custom field name.
+const MAX_NAME_LEN: usize = 64;
+
+/// Value type of a declared custom field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CustomFieldType {
+    /// Free-form text.
+    String,
+    /// Signed 64-bit integer.
+    Int,
+    /// One of a declared set of strings.
+    Enum,
+    /// Calendar date, stored as `YYYY-MM-DD`.
+    Date,
+}
+
+impl fmt::Display for CustomFieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::String => "string",
+            Self::Int => "int",
+            Self::Enum => "enum",
+            Self::Date => "date",
+        })
+    }
+}
+
+/// Declaration of a single custom field in the project schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomFieldDef {
+    #[serde(rename = "type")]
+    pub field_type: CustomFieldType,
+    /// Allowed values for `enum` fields.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<String>,
+}
+
+/// Project-level custom field schema, keyed by field name.
+pub type CustomFieldSchema = BTreeMap<String, CustomFieldDef>;
+
+/// Error returned when a custom field name or value does not fit the schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CustomFieldError {
+    /// The input is not of the form `name=value`.
+    Malformed { input: String },
+    /// The name is not a valid custom field identifier.
+    InvalidName { name: String },
+    /// The field is not declared in the project schema.
+    UnknownField { name: String },
+    /// The value does not match the declared type.
+    InvalidValue {
+        name: String,
+        expected: String,
+        got: String,
+    },
+}
+
+impl fmt::Display for CustomFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed { input } => write!(f, "expected name=value, got '{input}'"),
+            Self::InvalidName { name } => write!(
+                f,
+                "invalid custom field name '{name}' (use lowercase letters, digits, '_' or '-')"
+            ),
+            Self::UnknownField { name } => {
+                write!(
+                    f,
+                    "unknown custom field '{name}' (declare it under [custom_fields])"
+                )
+            }
+            Self::InvalidValue {
+                name,
+                expected,
+                got,
+            } => write!(
+                f,
+                "invalid value {got} for custom field '{name}': expected {expected}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CustomFieldError {}
+
+/// Return the custom field name carried by an `item.update` field, if any.
+#[must_use]
+pub fn custom_field_name(field: &str) -> Option<&str> {
+    field
+        .strip_prefix(CUSTOM_FIELD_PREFIX)
+        .filter(|name| !name.is_empty())
+}
+
+/// Build the `item.update` field for a custom field name.
+#[must_use]
+pub fn custom_field_key(name: &str) -> String {
+    format!("{CUSTOM_FIELD_PREFIX}{name}")
+}
+
+/// Return `true` if `name` is a valid custom field identifier: a lowercase
+/// letter followed by lowercase letters, digits, `_`, or `-`.
+#[must_use]
+pub fn is_valid_field_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    name.len() <= MAX_NAME_LEN
+        && chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+}
+
+/// Canonical text form of a custom field value.
+///
+/// Used for projection storage and equality filters. Returns `None` for
+/// `null` and for non-scalar values, which clear the field on replay.
+#[must_use]
+pub fn value_to_text(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Parse a `name=value` assignment against `schema`.
+///
+/// An empty value yields `null`, which clears the field.
+///
+/// # Errors
+///
+/// Returns a [`CustomFieldError`] if the input has no `=`, names a field
+/// that is not declared, or carries a value of the wrong type.
+pub fn parse_assignment(
+    schema: &CustomFieldSchema,
+    input: &str,
+) -> Result<(String, serde_json::Value), CustomFieldError> {
+    let (name, raw) = input
+        .split_once('=')
+        .ok_or_else(|| CustomFieldError::Malformed {
+            input: input.to_string(),
+        })?;
+    let name = name.trim();
+    let name = name.strip_prefix(CUSTOM_FIELD_PREFIX).unwrap_or(name);
+    if !is_valid_field_name(name) {
+        return Err(CustomFieldError::InvalidName {
+            name: name.to_string(),
+        });
+    }
+    let def = schema
+        .get(name)
+        .ok_or_else(|| CustomFieldError::UnknownField {
+            name: name.to_string(),
+        })?;
+
+    let value = if raw.trim().is_empty() {
+        serde_json::Value::Null
+    } else {
+        def.parse_input(name, raw)?
+    };
+    Ok((name.to_string(), value))
+}
+
+impl CustomFieldDef {
+    /// Parse a command-line value into the canonical JSON value for this
+    /// field.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CustomFieldError::InvalidValue`] if `raw` does not parse as
+    /// the declared type.
+    pub fn parse_input(
+        &self,
+        name: &str,
+        raw: &str,
+    ) -> Result<serde_json::Value, CustomFieldError> {
+        let raw = raw.trim();
+        let value = match self.field_type {
+            CustomFieldType::Int => raw.parse::<i64>().ok().map(serde_json::Value::from),
+            CustomFieldType::String | CustomFieldType::Enum | CustomFieldType::Date => {
+                Some(serde_json::Value::String(raw.to_string()))
+            }
+        };
+
+        let value = value.ok_or_else(|| self.invalid(name, &format!("'{raw}'")))?;
+        self.check_value(name, &value)?;
+        Ok(value)
+    }
+
+    /// Check a JSON value against the declared type. `null` always passes
+    /// because it clears the field.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CustomFieldError::InvalidValue`] if the value has the wrong
+    /// type, is not an allowed enum member, or is not a valid date.
+    pub fn check_value(
+        &self,
+        name: &str,
+        value: &serde_json::Value,
+    ) -> Result<(), CustomFieldError> {
+        let ok = match (self.field_type, value) {
+            (_, serde_json::Value::Null) => true,
+            (CustomFieldType::String, serde_json::Value::String(s)) => !s.trim().is_empty(),
+            (CustomFieldType::Int, serde_json::Value::Number(n)) => n.is_i64(),
+            (CustomFieldType::Enum, serde_json::Value::String(s)) => self.values.contains(s),
+            (CustomFieldType::Date, serde_json::Value::String(s)) => {
+                NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok()
+            }
+            _ => false,
+        };
+
+        if ok {
+            Ok(())
+        } else {
+            Err(self.invalid(name, &value.to_string()))
+        }
+    }
+
+    fn invalid(&self, name: &str, got: &str) -> CustomFieldError {
+        let expected = match self.field_type {
+            CustomFieldType::String => "a non-empty string".to_string(),
+            CustomFieldType::Int => "an integer".to_string(),
+            CustomFieldType::Enum => format!("one of {}", self.values.join(", ")),
+            CustomFieldType::Date => "a date (YYYY-MM-DD)".to_string(),
+        };
+        CustomFieldError::InvalidValue {
+            name: name.to_string(),
+            expected,
+            got: got.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn def(field_type: CustomFieldType, values: &[&str]) -> CustomFieldDef {
+        CustomFieldDef {
+            field_type,
+            values: values.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn field_name_roundtrip() {
+        assert_eq!(custom_field_name("custom.component"), Some("component"));
+        assert_eq!(custom_field_name("custom."), None);
+        assert_eq!(custom_field_name("title"), None);
+        assert_eq!(custom_field_key("severity"), "custom.severity");
+    }
+
+    #[test]
+    fn valid_field_names() {
+        assert!(is_valid_field_name("component"));
+        assert!(is_valid_field_name("ticket_url-2"));
+        assert!(!is_valid_field_name("Component"));
+        assert!(!is_valid_field_name("2fast"));
+        assert!(!is_valid_field_name(""));
+        assert!(!is_valid_field_name("has.dot"));
+    }
+
+    #[test]
+    fn parse_input_by_type() {
+        let int = def(CustomFieldType::Int, &[]);
+        assert_eq!(int.parse_input("severity", " 3 "), Ok(json!(3)));
+        assert!(int.parse_input("severity", "high").is_err());
+
+        let date = def(CustomFieldType::Date, &[]);
+        assert_eq!(
+            date.parse_input("since", "2026-03-01"),
+            Ok(json!("2026-03-01"))
+        );
+        assert!(date.parse_input("since", "March 1").is_err());
+
+        let component = def(CustomFieldType::Enum, &["api", "cli"]);
+        assert_eq!(component.parse_input("component", "cli"), Ok(json!("cli")));
+        let err = component.parse_input("component", "web").unwrap_err();
+        assert!(err.to_string().contains("one of api, cli"));
+    }
+
+    #[test]
+    fn check_value_rejects_wrong_json_type() {
+        let int = def(CustomFieldType::Int, &[]);
+        assert!(int.check_value("severity", &json!("3")).is_err());
+        assert!(int.check_value("severity", &json!(1.5)).is_err());
+        assert!(
+            int.check_value("severity", &serde_json::Value::Null)
+                .is_ok()
+        );
+
+        let text = def(CustomFieldType::String, &[]);
+        assert!(text.check_value("customer", &json!("acme")).is_ok());
+        assert!(text.check_value("customer", &json!(" ")).is_err());
+    }
+
+    #[test]
+    fn parse_assignment_against_schema() {
+        let schema: CustomFieldSchema = [
+            ("severity".to_string(), def(CustomFieldType::Int, &[])),
+            (
+                "component".to_string(),
+                def(CustomFieldType::Enum, &["api"]),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            parse_assignment(&schema, "severity=2"),
+            Ok(("severity".to_string(), json!(2)))
+        );
+        assert_eq!(
+            parse_assignment(&schema, "custom.component=api"),
+            Ok(("component".to_string(), json!("api")))
+        );
+        assert_eq!(
+            parse_assignment(&schema, "severity="),
+            Ok(("severity".to_string(), serde_json::Value::Null))
+        );
+        assert!(matches!(
+            parse_assignment(&schema, "severity"),
+            Err(CustomFieldError::Malformed { .. })
+        ));
+        assert!(matches!(
+            parse_assignment(&schema, "owner=bob"),
+            Err(CustomFieldError::UnknownField { .. })
+        ));
+        assert!(matches!(
+            parse_assignment(&schema, "component=web"),
+            Err(CustomFieldError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn value_text_forms() {
+        assert_eq!(value_to_text(&json!("api")), Some("api".to_string()));
+        assert_eq!(value_to_text(&json!(42)), Some("42".to_string()));
+        assert_eq!(value_to_text(&serde_json::Value::Null), None);
+        assert_eq!(value_to_text(&json!(["a"])), None);
+    }
+}
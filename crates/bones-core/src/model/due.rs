@@ -0,0 +1,184 @@
+//! Due dates for time-bound work items.
+//!
+//! A due date is stored as a UTC wall-clock instant in microseconds. On the
+//! wire it travels as an `item.update` with `field = "due"` and an RFC 3339
+//! string value (or `null` to clear), so replicas that predate due dates
+//! simply ignore the field.
+//!
+//! [`parse_due`] accepts the looser forms people type on the command line:
+//! absolute timestamps, calendar dates, and offsets relative to now.
+
+use std::fmt;
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, SecondsFormat, Utc};
+
+/// Name of the `item.update` field that carries a due date.
+pub const DUE_FIELD: &str = "due";
+
+/// Error returned when a due-date expression cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDueError {
+    pub got: String,
+}
+
+impl fmt::Display for ParseDueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid due date: '{}' (expected YYYY-MM-DD, RFC 3339, today, tomorrow, or an offset like 3d/2w/12h)",
+            self.got
+        )
+    }
+}
+
+impl std::error::Error for ParseDueError {}
+
+/// Parse a user-supplied due-date expression relative to `now`.
+///
+/// Accepted forms:
+/// - RFC 3339 timestamp (`2026-03-01T17:00:00Z`)
+/// - calendar date (`2026-03-01`) — due at the end of that UTC day
+/// - `today` / `tomorrow` — end of the corresponding UTC day
+/// - offset from now: `<n>h`, `<n>d`, `<n>w`, optionally prefixed with `+`
+///
+/// # Errors
+///
+/// Returns [`ParseDueError`] if the input matches none of the forms above or
+/// resolves to an instant before the Unix epoch.
+pub fn parse_due(input: &str, now: DateTime<Utc>) -> Result<u64, ParseDueError> {
+    let raw = input.trim();
+    let err = || ParseDueError {
+        got: input.to_string(),
+    };
+
+    let instant = match raw.to_ascii_lowercase().as_str() {
+        "today" => end_of_day(now.date_naive()),
+        "tomorrow" => end_of_day(now.date_naive().succ_opt().ok_or_else(err)?),
+        lower => {
+            if let Ok(ts) = DateTime::parse_from_rfc3339(raw) {
+                ts.with_timezone(&Utc)
+            } else if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+                end_of_day(date)
+            } else {
+                now + parse_offset(lower.strip_prefix('+').unwrap_or(lower)).ok_or_else(err)?
+            }
+        }
+    };
+
+    u64::try_from(instant.timestamp_micros()).map_err(|_| err())
+}
+
+/// Decode the `value` of a `due` update into microseconds.
+///
+/// Returns `None` for `null`, malformed strings, and negative instants —
+/// all of which clear the due date on replay.
+#[must_use]
+pub fn due_from_value(value: &serde_json::Value) -> Option<u64> {
+    match value {
+        serde_json::Value::String(s) => DateTime::parse_from_rfc3339(s)
+            .ok()
+            .and_then(|ts| u64::try_from(ts.timestamp_micros()).ok()),
+        serde_json::Value::Number(n) => n.as_u64(),
+        _ => None,
+    }
+}
+
+/// Encode a due date as the canonical `item.update` value.
+#[must_use]
+pub fn due_to_value(due_us: Option<u64>) -> serde_json::Value {
+    due_us.map_or(serde_json::Value::Null, |us| {
+        serde_json::Value::String(format_due(us))
+    })
+}
+
+/// Format a due date as an RFC 3339 UTC timestamp with second precision.
+#[must_use]
+pub fn format_due(due_us: u64) -> String {
+    i64::try_from(due_us)
+        .ok()
+        .and_then(DateTime::<Utc>::from_timestamp_micros)
+        .map_or_else(
+            || due_us.to_string(),
+            |ts| ts.to_rfc3339_opts(SecondsFormat::Secs, true),
+        )
+}
+
+fn end_of_day(date: NaiveDate) -> DateTime<Utc> {
+    let last_second = NaiveTime::from_hms_opt(23, 59, 59).unwrap_or(NaiveTime::MIN);
+    date.and_time(last_second).and_utc()
+}
+
+pub(crate) fn parse_offset(raw: &str) -> Option<Duration> {
+    let unit = raw.chars().last()?;
+    let amount: i64 = raw[..raw.len() - unit.len_utf8()].parse().ok()?;
+    match unit {
+        'h' => Duration::try_hours(amount),
+        'd' => Duration::try_days(amount),
+        'w' => Duration::try_weeks(amount),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0)
+            .single()
+            .expect("valid timestamp")
+    }
+
+    fn us(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> u64 {
+        let ts = Utc
+            .with_ymd_and_hms(y, m, d, h, min, s)
+            .single()
+            .expect("valid timestamp");
+        u64::try_from(ts.timestamp_micros()).expect("post-epoch")
+    }
+
+    #[test]
+    fn parse_due_calendar_date_is_end_of_day() {
+        assert_eq!(
+            parse_due("2026-03-15", now()),
+            Ok(us(2026, 3, 15, 23, 59, 59))
+        );
+    }
+
+    #[test]
+    fn parse_due_rfc3339_normalizes_to_utc() {
+        assert_eq!(
+            parse_due("2026-03-15T09:00:00-05:00", now()),
+            Ok(us(2026, 3, 15, 14, 0, 0))
+        );
+    }
+
+    #[test]
+    fn parse_due_relative_forms() {
+        assert_eq!(parse_due("today", now()), Ok(us(2026, 3, 10, 23, 59, 59)));
+        assert_eq!(
+            parse_due("Tomorrow", now()),
+            Ok(us(2026, 3, 11, 23, 59, 59))
+        );
+        assert_eq!(parse_due("3d", now()), Ok(us(2026, 3, 13, 12, 0, 0)));
+        assert_eq!(parse_due("+2w", now()), Ok(us(2026, 3, 24, 12, 0, 0)));
+        assert_eq!(parse_due("12h", now()), Ok(us(2026, 3, 11, 0, 0, 0)));
+    }
+
+    #[test]
+    fn parse_due_rejects_garbage() {
+        assert!(parse_due("soon", now()).is_err());
+        assert!(parse_due("3y", now()).is_err());
+        assert!(parse_due("", now()).is_err());
+    }
+
+    #[test]
+    fn due_value_roundtrip() {
+        let due = us(2026, 3, 15, 23, 59, 59);
+        let value = due_to_value(Some(due));
+        assert_eq!(value, serde_json::json!("2026-03-15T23:59:59Z"));
+        assert_eq!(due_from_value(&value), Some(due));
+        assert_eq!(due_from_value(&due_to_value(None)), None);
+    }
+}
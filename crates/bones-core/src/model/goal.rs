@@ -411,9 +411,11 @@ fn system_move_event(goal_id: &str, target_state: State, reason: &str) -> Event
         data: EventData::Move(MoveData {
             state: target_state,
             reason: Some(reason.to_string()),
+            substate: None,
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     }
 }
 
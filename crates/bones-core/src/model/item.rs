@@ -31,7 +31,9 @@ pub enum State {
 }
 
 impl State {
-    const fn as_str(self) -> &'static str {
+    /// Canonical lowercase name.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
         match self {
             Self::Open => "open",
             Self::Doing => "doing",
@@ -162,6 +164,8 @@ pub struct WorkItemFields {
     pub urgency: Urgency,
     pub size: Option<Size>,
     pub parent_id: Option<String>,
+    /// Due date in microseconds since the Unix epoch (UTC).
+    pub due_at: Option<u64>,
     pub assignees: Vec<String>,
     pub labels: Vec<String>,
     pub blocked_by: Vec<String>,
@@ -181,6 +185,7 @@ impl Default for WorkItemFields {
             urgency: Urgency::Default,
             size: None,
             parent_id: None,
+            due_at: None,
             assignees: Vec::new(),
             labels: Vec::new(),
             blocked_by: Vec::new(),
@@ -416,6 +421,7 @@ mod tests {
         assert!(fields.description.is_none());
         assert!(fields.size.is_none());
         assert!(fields.parent_id.is_none());
+        assert!(fields.due_at.is_none());
         assert!(fields.assignees.is_empty());
         assert!(fields.labels.is_empty());
         assert!(fields.blocked_by.is_empty());
@@ -1,3 +1,6 @@
+pub mod custom;
+pub mod due;
 pub mod goal;
 pub mod item;
 pub mod item_id;
+pub mod workflow;
@@ -0,0 +1,318 @@
+//! Configurable workflow sub-states.
+//!
+//! The lifecycle CRDT only knows four phases (open/doing/done/archived).
+//! Projects that need finer-grained states declare them in
+//! `.bones/config.toml`, each mapped onto one of those phases:
+//!
+//! ```toml
+//! [states.review]
+//! phase = "doing"
+//! from = ["doing"]          # optional: states allowed to enter review
+//! to = ["doing", "done"]    # optional: states review may move to
+//!
+//! [states.blocked]
+//! phase = "doing"
+//! ```
+//!
+//! A move into a sub-state is an ordinary `item.move` whose `state` is the
+//! mapped phase and whose `substate` names the sub-state, so the epoch/phase
+//! merge rules are unchanged and replicas without the config still see the
+//! phase. Triage and scheduling only ever look at the phase.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::custom::is_valid_field_name;
+use crate::model::item::State;
+
+/// Declaration of a single sub-state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubStateDef {
+    /// Lifecycle phase this sub-state belongs to.
+    pub phase: State,
+    /// States allowed to move into this one. Empty means any state whose
+    /// phase may transition to `phase`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub from: Vec<String>,
+    /// States this one may move to. Empty means no extra restriction.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub to: Vec<String>,
+}
+
+/// Project-level sub-state declarations, keyed by name.
+pub type WorkflowStates = BTreeMap<String, SubStateDef>;
+
+/// A resolved workflow state: a phase plus an optional sub-state name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkflowState {
+    pub phase: State,
+    pub substate: Option<String>,
+}
+
+impl WorkflowState {
+    /// The state name users type and see: the sub-state if set, else the
+    /// phase.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        self.substate
+            .as_deref()
+            .unwrap_or_else(|| self.phase.as_str())
+    }
+}
+
+impl fmt::Display for WorkflowState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Error returned when a state name or transition does not fit the workflow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkflowError {
+    /// The name is neither a built-in state nor a declared sub-state.
+    UnknownState { name: String },
+    /// The item is already in the requested state.
+    NoOp { state: String },
+    /// The transition is not allowed by the lifecycle or `from`/`to` rules.
+    NotAllowed {
+        from: String,
+        to: String,
+        reason: String,
+    },
+}
+
+impl fmt::Display for WorkflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownState { name } => write!(
+                f,
+                "unknown state '{name}' (use open, doing, done, archived, or declare it under [states])"
+            ),
+            Self::NoOp { state } => write!(f, "already in state '{state}'"),
+            Self::NotAllowed { from, to, reason } => {
+                write!(f, "cannot move from {from} to {to}: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WorkflowError {}
+
+/// Resolve a user-supplied state name against the built-in states and the
+/// declared sub-states.
+///
+/// # Errors
+///
+/// Returns [`WorkflowError::UnknownState`] if `name` is neither.
+pub fn resolve_state(states: &WorkflowStates, name: &str) -> Result<WorkflowState, WorkflowError> {
+    let name = name.trim().to_ascii_lowercase();
+    if let Ok(phase) = name.parse::<State>() {
+        return Ok(WorkflowState {
+            phase,
+            substate: None,
+        });
+    }
+    states
+        .get(&name)
+        .map(|def| WorkflowState {
+            phase: def.phase,
+            substate: Some(name.clone()),
+        })
+        .ok_or(WorkflowError::UnknownState { name })
+}
+
+/// Validate a move from `current` to `target`.
+///
+/// Within one phase, moving between the base state and its sub-states (or
+/// between sub-states) is allowed; across phases the built-in lifecycle
+/// rules apply. The `from` list of the target and the `to` list of the
+/// current sub-state narrow this further. A sub-state that is no longer
+/// declared behaves like its phase.
+///
+/// # Errors
+///
+/// Returns [`WorkflowError::NoOp`] if the states are equal and
+/// [`WorkflowError::NotAllowed`] if the rules reject the move.
+pub fn check_transition(
+    states: &WorkflowStates,
+    current: &WorkflowState,
+    target: &WorkflowState,
+) -> Result<(), WorkflowError> {
+    let from = current.name();
+    let to = target.name();
+    if from == to {
+        return Err(WorkflowError::NoOp {
+            state: to.to_string(),
+        });
+    }
+    let not_allowed = |reason: String| WorkflowError::NotAllowed {
+        from: from.to_string(),
+        to: to.to_string(),
+        reason,
+    };
+
+    if current.phase != target.phase
+        && let Err(e) = current.phase.can_transition_to(target.phase)
+    {
+        return Err(not_allowed(format!(
+            "{} -> {} is {}",
+            e.from, e.to, e.reason
+        )));
+    }
+
+    if let Some(def) = target.substate.as_ref().and_then(|s| states.get(s))
+        && !def.from.is_empty()
+        && !def.from.iter().any(|allowed| allowed == from)
+    {
+        return Err(not_allowed(format!(
+            "{to} can only be entered from {}",
+            def.from.join(", ")
+        )));
+    }
+
+    if let Some(def) = current.substate.as_ref().and_then(|s| states.get(s))
+        && !def.to.is_empty()
+        && !def.to.iter().any(|allowed| allowed == to)
+    {
+        return Err(not_allowed(format!(
+            "{from} can only move to {}",
+            def.to.join(", ")
+        )));
+    }
+
+    Ok(())
+}
+
+/// Check the declarations themselves: names must be valid identifiers that
+/// do not shadow a built-in state, and `from`/`to` must name known states.
+///
+/// # Errors
+///
+/// Returns a message naming the offending `states.<name>` entry.
+pub fn validate_states(states: &WorkflowStates) -> Result<(), String> {
+    for (name, def) in states {
+        if !is_valid_field_name(name) {
+            return Err(format!(
+                "states.{name}: name must start with a lowercase letter and contain only lowercase letters, digits, '_' or '-'"
+            ));
+        }
+        if name.parse::<State>().is_ok() {
+            return Err(format!(
+                "states.{name}: built-in states cannot be redeclared"
+            ));
+        }
+        for (key, list) in [("from", &def.from), ("to", &def.to)] {
+            if let Some(unknown) = list
+                .iter()
+                .find(|s| s.parse::<State>().is_err() && !states.contains_key(s.as_str()))
+            {
+                return Err(format!("states.{name}.{key}: unknown state '{unknown}'"));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn review_workflow() -> WorkflowStates {
+        let mut states = WorkflowStates::new();
+        states.insert(
+            "review".into(),
+            SubStateDef {
+                phase: State::Doing,
+                from: vec!["doing".into()],
+                to: vec!["doing".into(), "done".into()],
+            },
+        );
+        states.insert(
+            "blocked".into(),
+            SubStateDef {
+                phase: State::Doing,
+                from: vec![],
+                to: vec![],
+            },
+        );
+        states
+    }
+
+    fn state(states: &WorkflowStates, name: &str) -> WorkflowState {
+        resolve_state(states, name).expect("known state")
+    }
+
+    #[test]
+    fn resolve_builtin_and_substates() {
+        let states = review_workflow();
+        assert_eq!(
+            resolve_state(&states, "Done").unwrap(),
+            WorkflowState {
+                phase: State::Done,
+                substate: None
+            }
+        );
+        let review = state(&states, "review");
+        assert_eq!(review.phase, State::Doing);
+        assert_eq!(review.name(), "review");
+        assert!(matches!(
+            resolve_state(&states, "qa"),
+            Err(WorkflowError::UnknownState { .. })
+        ));
+    }
+
+    #[test]
+    fn transitions_within_phase_follow_from_and_to() {
+        let states = review_workflow();
+        let doing = state(&states, "doing");
+        let review = state(&states, "review");
+        let blocked = state(&states, "blocked");
+
+        assert!(check_transition(&states, &doing, &review).is_ok());
+        assert!(check_transition(&states, &review, &doing).is_ok());
+        // review only from doing
+        assert!(check_transition(&states, &blocked, &review).is_err());
+        // review only to doing/done
+        assert!(check_transition(&states, &review, &blocked).is_err());
+        assert!(check_transition(&states, &review, &state(&states, "done")).is_ok());
+        assert!(matches!(
+            check_transition(&states, &review, &review),
+            Err(WorkflowError::NoOp { .. })
+        ));
+    }
+
+    #[test]
+    fn transitions_across_phases_use_lifecycle_rules() {
+        let states = review_workflow();
+        let open = state(&states, "open");
+        let blocked = state(&states, "blocked");
+        let archived = state(&states, "archived");
+
+        assert!(check_transition(&states, &open, &blocked).is_ok());
+        assert!(check_transition(&states, &blocked, &archived).is_err());
+        // review is doing-phase but may only be entered from doing
+        assert!(check_transition(&states, &open, &state(&states, "review")).is_err());
+    }
+
+    #[test]
+    fn validate_rejects_bad_declarations() {
+        assert!(validate_states(&review_workflow()).is_ok());
+
+        let mut shadow = WorkflowStates::new();
+        shadow.insert(
+            "done".into(),
+            SubStateDef {
+                phase: State::Done,
+                from: vec![],
+                to: vec![],
+            },
+        );
+        assert!(validate_states(&shadow).unwrap_err().contains("built-in"));
+
+        let mut dangling = review_workflow();
+        dangling.get_mut("blocked").unwrap().to = vec!["qa".into()];
+        assert!(validate_states(&dangling).unwrap_err().contains("'qa'"));
+    }
+}
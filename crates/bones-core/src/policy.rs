@@ -0,0 +1,764 @@
+//! Write-authorization policy for events received from other replicas.
+//!
+//! `.bones/policy.toml` restricts which agents may write which event types on
+//! which items. Every replica can still write anything locally; the policy is
+//! enforced where events *arrive*: the union merge ([`crate::sync::merge`]),
+//! the git merge driver, `bn sync` after a `git pull`, remote and bundle
+//! sync, and `bn import --jsonl`.
+//!
+//! Git only calls the merge driver for a true merge, so a pull that
+//! fast-forwards brings remote events straight into the shards. `bn sync`
+//! screens those afterwards and removes refused ones with [`expel`]; a bare
+//! `git pull` is not screened at all.
+//!
+//! # Format
+//!
+//! ```toml
+//! [groups]
+//! humans = ["alice", "bob"]
+//!
+//! # Only humans may delete items.
+//! [[rule]]
+//! events = ["item.delete"]
+//! agents = ["@humans"]
+//!
+//! # Only the release bot may archive.
+//! [[rule]]
+//! events = ["item.move"]
+//! states = ["archived"]
+//! agents = ["release-bot"]
+//!
+//! # Security items are human-only.
+//! [[rule]]
+//! items = ["bn-sec*"]
+//! agents = ["@humans", "audit-*"]
+//! ```
+//!
+//! A rule applies to an event when every selector it sets matches: `events`
+//! (event types), `items` (item ID patterns), and `states` (target states of
+//! `item.move`, including configured sub-states). An applicable rule rejects
+//! the event unless its author matches one of `agents` — a pattern or an
+//! `@group` — and the event is signed by that author. Patterns may use `*`
+//! as a wildcard. Events no rule applies to are accepted, so an absent or
+//! empty policy accepts everything.
+//!
+//! # Signatures
+//!
+//! The `agent` field of an event is self-asserted, so a name alone proves
+//! nothing. An event a rule applies to must also carry a valid signature
+//! from one of its author's public keys under `.bones/keys/` (see
+//! [`crate::signing`]); unsigned events, and events signed by an
+//! unregistered or wrong key, are rejected even when the author is allowed.
+//! Every agent a policy allows therefore needs `bn keys generate` and its
+//! public key committed.
+//!
+//! # Quarantine
+//!
+//! Rejected events are never dropped silently. Following the
+//! [`crate::recovery`] conventions, they are appended to a sibling of their
+//! month's shard with a `.rejected` extension (e.g.
+//! `events/2026-02.rejected`), each preceded by a comment giving the reason,
+//! so they can be inspected and re-imported if the policy was wrong.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write as _};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike as _};
+use serde::{Deserialize, Serialize};
+
+use crate::event::parser::{PartialParsedLine, parse_line_partial};
+use crate::event::writer::{WriteError, shard_header, write_line};
+use crate::event::{Event, EventData, EventType};
+use crate::lock::ShardLock;
+use crate::shard::{ShardError, ShardManager};
+use crate::signing::{Keyring, SignatureStatus, SigningError};
+
+/// Policy file name under `.bones/`.
+pub const POLICY_FILE: &str = "policy.toml";
+
+/// Extension of quarantine files next to each shard.
+pub const QUARANTINE_EXTENSION: &str = "rejected";
+
+// ---------------------------------------------------------------------------
+// Error types
+// ---------------------------------------------------------------------------
+
+/// Errors that can occur while loading a policy or quarantining events.
+#[derive(Debug, thiserror::Error)]
+pub enum PolicyError {
+    /// I/O error on the policy or a quarantine file.
+    #[error("policy I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    /// The policy file is not valid TOML or has unknown fields.
+    #[error("invalid {POLICY_FILE}: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    /// A rule names an event type bones does not know.
+    #[error("{POLICY_FILE} rule {rule}: unknown event type '{value}'")]
+    UnknownEventType {
+        /// 1-based rule number.
+        rule: usize,
+        /// The offending entry.
+        value: String,
+    },
+
+    /// A rule references a group that is not defined under `[groups]`.
+    #[error("{POLICY_FILE} rule {rule}: unknown group '@{group}'")]
+    UnknownGroup {
+        /// 1-based rule number.
+        rule: usize,
+        /// Group name without the `@`.
+        group: String,
+    },
+
+    /// A rule sets `states` without limiting `events` to `item.move`.
+    #[error("{POLICY_FILE} rule {rule}: `states` only applies to item.move rules")]
+    StatesWithoutMove {
+        /// 1-based rule number.
+        rule: usize,
+    },
+
+    /// A rejected event could not be serialized for quarantine.
+    #[error("failed to serialize rejected event: {0}")]
+    Write(#[from] WriteError),
+
+    /// The public key registry could not be loaded.
+    #[error("failed to load public keys: {0}")]
+    Keys(#[from] SigningError),
+
+    /// A shard could not be listed, locked or rewritten.
+    #[error("shard error: {0}")]
+    Shard(#[from] ShardError),
+}
+
+// ---------------------------------------------------------------------------
+// Policy
+// ---------------------------------------------------------------------------
+
+/// One restriction in `.bones/policy.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyRule {
+    /// Event types the rule covers; empty means all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+    /// Item ID patterns the rule covers; empty means all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<String>,
+    /// Target states of `item.move` the rule covers; empty means all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub states: Vec<String>,
+    /// Agents (patterns or `@group`) allowed to write covered events.
+    #[serde(default)]
+    pub agents: Vec<String>,
+}
+
+/// Parsed `.bones/policy.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    /// Named agent lists, referenced from rules as `@name`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub groups: BTreeMap<String, Vec<String>>,
+    /// Restrictions, checked in order.
+    #[serde(default, rename = "rule", skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<PolicyRule>,
+    /// Public keys that signatures on gated events are checked against.
+    #[serde(skip)]
+    keyring: Keyring,
+}
+
+/// An event the policy refused.
+#[derive(Debug, Clone)]
+pub struct Rejection {
+    /// The refused event.
+    pub event: Event,
+    /// 1-based number of the rule that refused it.
+    pub rule: usize,
+    /// Why it was refused.
+    pub reason: String,
+}
+
+impl Policy {
+    /// Load `.bones/policy.toml` together with the public keys under
+    /// `.bones/keys/`; a missing file yields the empty policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PolicyError`] if the file cannot be read, does not parse,
+    /// names unknown event types or groups, or a public key is malformed.
+    pub fn load(bones_dir: &Path) -> Result<Self, PolicyError> {
+        let path = bones_dir.join(POLICY_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let policy = Self::parse(&fs::read_to_string(path)?)?;
+        Ok(policy.with_keyring(Keyring::load(bones_dir)?))
+    }
+
+    /// Parse and validate policy text.
+    ///
+    /// The parsed policy has no public keys, so it rejects every event a
+    /// rule applies to until [`Self::with_keyring`] supplies them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PolicyError`] if the text does not parse or names unknown
+    /// event types or groups.
+    pub fn parse(text: &str) -> Result<Self, PolicyError> {
+        let policy: Self = toml::from_str(text)?;
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    fn validate(&self) -> Result<(), PolicyError> {
+        for (i, rule) in self.rules.iter().enumerate() {
+            let number = i + 1;
+            for value in &rule.events {
+                if value.parse::<EventType>().is_err() {
+                    return Err(PolicyError::UnknownEventType {
+                        rule: number,
+                        value: value.clone(),
+                    });
+                }
+            }
+            if !rule.states.is_empty() && rule.events.iter().all(|e| e != "item.move") {
+                return Err(PolicyError::StatesWithoutMove { rule: number });
+            }
+            for agent in &rule.agents {
+                if let Some(group) = agent.strip_prefix('@')
+                    && !self.groups.contains_key(group)
+                {
+                    return Err(PolicyError::UnknownGroup {
+                        rule: number,
+                        group: group.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Use `keyring` to verify the signatures of gated events.
+    #[must_use]
+    pub fn with_keyring(mut self, keyring: Keyring) -> Self {
+        self.keyring = keyring;
+        self
+    }
+
+    /// Whether the policy has no rules (and so accepts every event).
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Check one event, returning the rejection if a rule refuses it.
+    #[must_use]
+    pub fn check(&self, event: &Event) -> Option<Rejection> {
+        self.rules.iter().enumerate().find_map(|(i, rule)| {
+            if !rule.applies_to(event) {
+                return None;
+            }
+            let reason = if self.allows(rule, &event.agent) {
+                let problem = match self.keyring.check(
+                    &event.agent,
+                    &event.event_hash,
+                    event.signature.as_deref(),
+                ) {
+                    SignatureStatus::Valid => return None,
+                    SignatureStatus::Unsigned => "it is unsigned",
+                    SignatureStatus::UnknownKey => "no public key is registered for the agent",
+                    SignatureStatus::Invalid => "its signature does not match the agent's keys",
+                };
+                format!(
+                    "{} on {} by '{}' lacks a valid signature: {problem} (rule {})",
+                    event.event_type,
+                    event.item_id,
+                    event.agent,
+                    i + 1,
+                )
+            } else {
+                format!(
+                    "agent '{}' may not write {} on {} (rule {} allows {})",
+                    event.agent,
+                    event.event_type,
+                    event.item_id,
+                    i + 1,
+                    if rule.agents.is_empty() {
+                        "no one".to_string()
+                    } else {
+                        rule.agents.join(", ")
+                    }
+                )
+            };
+            Some(Rejection {
+                event: event.clone(),
+                rule: i + 1,
+                reason,
+            })
+        })
+    }
+
+    /// Split `events` into those the policy accepts and those it refuses.
+    #[must_use]
+    pub fn partition(&self, events: Vec<Event>) -> (Vec<Event>, Vec<Rejection>) {
+        if self.is_empty() {
+            return (events, Vec::new());
+        }
+        let mut accepted = Vec::with_capacity(events.len());
+        let mut rejected = Vec::new();
+        for event in events {
+            match self.check(&event) {
+                Some(rejection) => rejected.push(rejection),
+                None => accepted.push(event),
+            }
+        }
+        (accepted, rejected)
+    }
+
+    fn allows(&self, rule: &PolicyRule, agent: &str) -> bool {
+        rule.agents.iter().any(|entry| {
+            entry.strip_prefix('@').map_or_else(
+                || wildcard_match(entry, agent),
+                |group| {
+                    self.groups
+                        .get(group)
+                        .is_some_and(|members| members.iter().any(|m| wildcard_match(m, agent)))
+                },
+            )
+        })
+    }
+}
+
+impl PolicyRule {
+    fn applies_to(&self, event: &Event) -> bool {
+        if !self.events.is_empty() && !self.events.iter().any(|e| e == event.event_type.as_str()) {
+            return false;
+        }
+        if !self.items.is_empty()
+            && !self
+                .items
+                .iter()
+                .any(|pattern| wildcard_match(pattern, event.item_id.as_str()))
+        {
+            return false;
+        }
+        if self.states.is_empty() {
+            return true;
+        }
+        let EventData::Move(data) = &event.data else {
+            return false;
+        };
+        self.states.iter().any(|state| {
+            state == data.state.as_str() || data.substate.as_deref() == Some(state.as_str())
+        })
+    }
+}
+
+/// Match `value` against `pattern`, where `*` matches any run of characters.
+fn wildcard_match(pattern: &str, value: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = value.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+// ---------------------------------------------------------------------------
+// Quarantine
+// ---------------------------------------------------------------------------
+
+/// Quarantine file for events written in the month of `wall_ts_us`.
+#[must_use]
+pub fn quarantine_path(bones_dir: &Path, wall_ts_us: i64) -> PathBuf {
+    let when = DateTime::from_timestamp_micros(wall_ts_us).unwrap_or_default();
+    ShardManager::new(bones_dir)
+        .shard_path(when.year(), when.month())
+        .with_extension(QUARANTINE_EXTENSION)
+}
+
+/// Append rejected events to their months' quarantine files and return the
+/// files written.
+///
+/// Events already quarantined are skipped, so re-receiving the same events
+/// on every sync does not grow the files.
+///
+/// # Errors
+///
+/// Returns [`PolicyError`] if an event cannot be serialized or a file
+/// cannot be written.
+pub fn quarantine(bones_dir: &Path, rejected: &[Rejection]) -> Result<Vec<PathBuf>, PolicyError> {
+    let mut by_file: BTreeMap<PathBuf, Vec<&Rejection>> = BTreeMap::new();
+    for rejection in rejected {
+        by_file
+            .entry(quarantine_path(bones_dir, rejection.event.wall_ts_us))
+            .or_default()
+            .push(rejection);
+    }
+
+    let mut written = Vec::new();
+    for (path, rejections) in by_file {
+        let existing = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let mut out = String::new();
+        let mut added = 0;
+        for rejection in rejections {
+            if existing.contains(&rejection.event.event_hash) {
+                continue;
+            }
+            let _ = writeln!(out, "# rejected: {}", rejection.reason);
+            out.push_str(&write_line(&rejection.event)?);
+            added += 1;
+        }
+        if added == 0 {
+            continue;
+        }
+        if existing.is_empty() {
+            out.insert_str(0, &shard_header());
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?
+            .write_all(out.as_bytes())?;
+        tracing::warn!(
+            path = %path.display(),
+            events = added,
+            "events rejected by policy were quarantined"
+        );
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Remove rejected events from the shards they reached without being
+/// screened, such as a `git pull` that fast-forwarded past the merge driver,
+/// and return the shards rewritten.
+///
+/// Lines are matched by event hash, and sealed shards get a fresh manifest.
+/// Call [`quarantine`] first so the events are kept for inspection. The
+/// rewritten shards are left uncommitted, as a merge that refused the events
+/// would have left them.
+///
+/// # Errors
+///
+/// Returns [`PolicyError`] if the shard lock cannot be taken or a shard
+/// cannot be read or written.
+pub fn expel(bones_dir: &Path, rejected: &[Rejection]) -> Result<Vec<PathBuf>, PolicyError> {
+    let hashes: HashSet<&str> = rejected
+        .iter()
+        .map(|rejection| rejection.event.event_hash.as_str())
+        .collect();
+    if hashes.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let shards = ShardManager::new(bones_dir);
+    let _lock = ShardLock::acquire(&shards.lock_path(), Duration::from_secs(5))
+        .map_err(ShardError::from)?;
+    let mut rewritten = Vec::new();
+    for (year, month) in shards.list_shards()? {
+        let path = shards.shard_path(year, month);
+        let content = fs::read_to_string(&path)?;
+        let mut kept = String::with_capacity(content.len());
+        for line in content.split_inclusive('\n') {
+            let refused = matches!(
+                parse_line_partial(line),
+                Ok(PartialParsedLine::Event(partial)) if hashes.contains(partial.event_hash_raw)
+            );
+            if !refused {
+                kept.push_str(line);
+            }
+        }
+        if kept.len() == content.len() {
+            continue;
+        }
+        fs::write(&path, kept)?;
+        if shards.read_manifest(year, month)?.is_some() {
+            shards.write_manifest(year, month)?;
+        }
+        tracing::warn!(
+            path = %path.display(),
+            "events rejected by policy were removed from the shard"
+        );
+        rewritten.push(path);
+    }
+    Ok(rewritten)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::data::{DeleteData, MoveData};
+    use crate::event::parser::parse_lines;
+    use crate::event::writer::write_event;
+    use crate::model::item::State;
+    use crate::model::item_id::ItemId;
+    use crate::signing::{SigningKey, register_public_key, sign_event_hash};
+    use std::collections::BTreeMap;
+    use tempfile::TempDir;
+
+    const POLICY: &str = r#"
+[groups]
+humans = ["alice", "bob"]
+
+[[rule]]
+events = ["item.delete"]
+agents = ["@humans"]
+
+[[rule]]
+events = ["item.move"]
+states = ["archived"]
+agents = ["release-bot"]
+
+[[rule]]
+items = ["bn-sec*"]
+agents = ["@humans", "audit-*"]
+"#;
+
+    /// Deterministic signing key for `agent`.
+    fn key_for(agent: &str) -> SigningKey {
+        let mut seed = [7u8; 32];
+        for (slot, byte) in seed.iter_mut().zip(agent.bytes()) {
+            *slot = byte;
+        }
+        SigningKey::from_bytes(&seed)
+    }
+
+    /// [`POLICY`] with public keys registered for the agents it allows.
+    fn signed_policy() -> (TempDir, Policy) {
+        let dir = TempDir::new().expect("tempdir");
+        for agent in ["alice", "bob", "release-bot", "audit-3"] {
+            register_public_key(dir.path(), agent, &key_for(agent).verifying_key())
+                .expect("register key");
+        }
+        let keyring = Keyring::load(dir.path()).expect("keyring");
+        let policy = Policy::parse(POLICY).expect("parse").with_keyring(keyring);
+        (dir, policy)
+    }
+
+    fn sign(mut event: Event, agent: &str) -> Event {
+        event.signature = Some(sign_event_hash(&key_for(agent), &event.event_hash).expect("sign"));
+        event
+    }
+
+    fn make_event(agent: &str, item: &str, data: EventData) -> Event {
+        let event_type = match &data {
+            EventData::Delete(_) => EventType::Delete,
+            EventData::Move(_) => EventType::Move,
+            _ => unreachable!("tests only build deletes and moves"),
+        };
+        let mut event = Event {
+            wall_ts_us: 1_770_000_000_000_000,
+            agent: agent.to_string(),
+            itc: "itc:AQ".to_string(),
+            parents: vec![],
+            event_type,
+            item_id: ItemId::new_unchecked(item),
+            data,
+            event_hash: String::new(),
+            signature: None,
+        };
+        write_event(&mut event).expect("hash");
+        sign(event, agent)
+    }
+
+    fn delete(agent: &str, item: &str) -> Event {
+        make_event(
+            agent,
+            item,
+            EventData::Delete(DeleteData {
+                reason: None,
+                extra: BTreeMap::new(),
+            }),
+        )
+    }
+
+    fn move_to(agent: &str, item: &str, state: State) -> Event {
+        make_event(
+            agent,
+            item,
+            EventData::Move(MoveData {
+                state,
+                substate: None,
+                reason: None,
+                extra: BTreeMap::new(),
+            }),
+        )
+    }
+
+    #[test]
+    fn rules_restrict_event_types_states_and_items() {
+        let (_dir, policy) = signed_policy();
+
+        assert!(policy.check(&delete("alice", "bn-a1")).is_none());
+        let rejected = policy.check(&delete("bot-7", "bn-a1")).expect("rejected");
+        assert_eq!(rejected.rule, 1);
+        assert!(rejected.reason.contains("@humans"), "{}", rejected.reason);
+
+        assert!(
+            policy
+                .check(&move_to("bot-7", "bn-a1", State::Done))
+                .is_none()
+        );
+        assert!(
+            policy
+                .check(&move_to("release-bot", "bn-a1", State::Archived))
+                .is_none()
+        );
+        assert_eq!(
+            policy
+                .check(&move_to("alice", "bn-a1", State::Archived))
+                .map(|r| r.rule),
+            Some(2)
+        );
+
+        assert!(
+            policy
+                .check(&move_to("audit-3", "bn-sec9", State::Done))
+                .is_none()
+        );
+        assert_eq!(
+            policy
+                .check(&move_to("bot-7", "bn-sec9", State::Done))
+                .map(|r| r.rule),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn gated_events_need_a_valid_signature_from_their_author() {
+        let (_dir, policy) = signed_policy();
+
+        let mut unsigned = delete("alice", "bn-a1");
+        unsigned.signature = None;
+        let rejected = policy.check(&unsigned).expect("unsigned is rejected");
+        assert_eq!(rejected.rule, 1);
+        assert!(rejected.reason.contains("unsigned"), "{}", rejected.reason);
+
+        let forged = sign(delete("alice", "bn-a1"), "bob");
+        let rejected = policy.check(&forged).expect("forged is rejected");
+        assert!(
+            rejected.reason.contains("does not match"),
+            "{}",
+            rejected.reason
+        );
+
+        let without_keys = Policy::parse(POLICY).expect("parse");
+        let rejected = without_keys
+            .check(&delete("alice", "bn-a1"))
+            .expect("unverifiable is rejected");
+        assert!(
+            rejected.reason.contains("no public key"),
+            "{}",
+            rejected.reason
+        );
+
+        // Events no rule covers need no signature.
+        let mut ungated = move_to("bot-7", "bn-a1", State::Done);
+        ungated.signature = None;
+        assert!(policy.check(&ungated).is_none());
+    }
+
+    #[test]
+    fn invalid_policies_are_refused() {
+        assert!(matches!(
+            Policy::parse("[[rule]]\nevents = [\"item.destroy\"]\nagents = [\"a\"]\n"),
+            Err(PolicyError::UnknownEventType { rule: 1, .. })
+        ));
+        assert!(matches!(
+            Policy::parse("[[rule]]\nagents = [\"@ops\"]\n"),
+            Err(PolicyError::UnknownGroup { rule: 1, .. })
+        ));
+        assert!(matches!(
+            Policy::parse("[[rule]]\nstates = [\"done\"]\nagents = [\"a\"]\n"),
+            Err(PolicyError::StatesWithoutMove { rule: 1 })
+        ));
+        assert!(Policy::parse("[[rule]]\nagent = [\"a\"]\n").is_err());
+        assert!(Policy::parse("").expect("empty").is_empty());
+    }
+
+    #[test]
+    fn wildcards_match_prefixes_suffixes_and_infixes() {
+        assert!(wildcard_match("*", "anything"));
+        assert!(wildcard_match("bot-*", "bot-7"));
+        assert!(wildcard_match("*-bot", "release-bot"));
+        assert!(wildcard_match("a*c*e", "abcde"));
+        assert!(!wildcard_match("a*c*e", "abcd"));
+        assert!(!wildcard_match("ab*ba", "aba"));
+        assert!(!wildcard_match("alice", "alice2"));
+    }
+
+    #[test]
+    fn quarantine_appends_each_rejection_once() {
+        let dir = TempDir::new().expect("tempdir");
+        let (_keys, policy) = signed_policy();
+        let events = vec![delete("alice", "bn-a1"), delete("bot-7", "bn-a2")];
+        let (accepted, rejected) = policy.partition(events);
+        assert_eq!(accepted.len(), 1);
+        assert_eq!(rejected.len(), 1);
+
+        let written = quarantine(dir.path(), &rejected).expect("quarantine");
+        assert_eq!(written.len(), 1);
+        assert_eq!(
+            written[0].extension().and_then(|e| e.to_str()),
+            Some(QUARANTINE_EXTENSION)
+        );
+        assert!(quarantine(dir.path(), &rejected).expect("again").is_empty());
+
+        let content = fs::read_to_string(&written[0]).expect("read");
+        assert_eq!(content.matches("# rejected: ").count(), 1);
+        let parsed = parse_lines(&content).expect("quarantine holds valid event lines");
+        assert_eq!(parsed[0].event_hash, rejected[0].event.event_hash);
+    }
+
+    #[test]
+    fn expel_removes_only_rejected_lines_from_shards() {
+        let dir = TempDir::new().expect("tempdir");
+        let shards = ShardManager::new(dir.path());
+        shards.init().expect("init");
+        let (year, month) = shards.active_shard().expect("active").expect("shard");
+        let (_keys, policy) = signed_policy();
+        let events = vec![delete("alice", "bn-a1"), delete("bot-7", "bn-a2")];
+        for event in &events {
+            shards
+                .append_raw(year, month, &write_line(event).expect("line"))
+                .expect("append");
+        }
+
+        let (_, rejected) = policy.partition(events.clone());
+        let rewritten = expel(dir.path(), &rejected).expect("expel");
+        assert_eq!(rewritten, vec![shards.shard_path(year, month)]);
+        assert!(expel(dir.path(), &rejected).expect("again").is_empty());
+
+        let remaining = parse_lines(&shards.replay().expect("replay")).expect("parse");
+        let hashes: Vec<&str> = remaining.iter().map(|e| e.event_hash.as_str()).collect();
+        assert_eq!(hashes, vec![events[0].event_hash.as_str()]);
+    }
+}
@@ -823,6 +823,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         writer::write_event(&mut event).expect("hash");
         let line = writer::write_line(&event).expect("serialize");
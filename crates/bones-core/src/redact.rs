@@ -0,0 +1,675 @@
+//! Redaction scrubbing for derived stores.
+//!
+//! An `item.redact` event never rewrites the event log — the targeted event
+//! stays in its shard so Merkle hashes and parent links remain valid. What
+//! *does* change is everything derived from the log: the `SQLite` projection
+//! (item fields and comment bodies), the FTS5 index that mirrors it, the
+//! semantic embedding rows computed from it, and the binary event cache.
+//!
+//! This module owns the payload-level scrubbing rules so every derived store
+//! hides the same content. Verification of the result lives in
+//! [`crate::verify::redact`].
+
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::Serialize;
+
+use crate::crdt::text::TextOp;
+use crate::db::query;
+use crate::event::Event;
+use crate::event::data::EventData;
+use crate::event::parser::{ParsedLine, PartialParsedLine, parse_line, parse_line_partial};
+use crate::shard::ShardManager;
+
+/// Placeholder text substituted for redacted content.
+pub const REDACTED_PLACEHOLDER: &str = "[redacted]";
+
+/// Character substituted for each character of a redacted description run.
+///
+/// Later patches anchor on and delete run characters by id, so a redacted
+/// run keeps its length instead of taking [`REDACTED_PLACEHOLDER`].
+pub const REDACTED_MASK: char = '\u{2588}';
+
+/// Replace every character of `text` with [`REDACTED_MASK`]. Returns `true`
+/// if `text` changed.
+fn mask_run(text: &mut String) -> bool {
+    if text.chars().all(|c| c == REDACTED_MASK) {
+        return false;
+    }
+    *text = text.chars().map(|_| REDACTED_MASK).collect();
+    true
+}
+
+/// Summary of projection rows touched by [`scrub_projection`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ProjectionScrub {
+    /// Comment, comment edit, and retraction rows whose text was replaced
+    /// with the placeholder.
+    pub comments: usize,
+    /// Item fields (title, description, compact summary) replaced with the
+    /// placeholder because the redacted event was still their last writer.
+    pub item_fields: usize,
+    /// Attachment rows whose name was replaced with the placeholder. The
+    /// blob itself is removed by the caller, which owns the `.bones` path.
+    pub attachments: usize,
+    /// Semantic embedding rows removed so they are recomputed from the
+    /// scrubbed projection.
+    pub embeddings: usize,
+}
+
+/// Collect the target hashes of every `item.redact` event in `events`.
+#[must_use]
+pub fn redaction_targets(events: &[Event]) -> HashSet<String> {
+    events
+        .iter()
+        .filter_map(|event| match &event.data {
+            EventData::Redact(data) => Some(data.target_hash.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Re-read the events whose hashes are in `targets` from the event log.
+///
+/// Only lines whose hash matches are fully parsed, so the scan stays cheap
+/// even over a long log.
+///
+/// # Errors
+///
+/// Returns an error if the shards cannot be read.
+pub fn load_targets<S: std::hash::BuildHasher>(
+    shard_mgr: &ShardManager,
+    targets: &HashSet<String, S>,
+) -> Result<Vec<Event>> {
+    let mut found = Vec::new();
+    if targets.is_empty() {
+        return Ok(found);
+    }
+    for line_res in shard_mgr.replay_lines()? {
+        let (_, line) = line_res.context("read shard line")?;
+        let Ok(PartialParsedLine::Event(partial)) = parse_line_partial(&line) else {
+            continue;
+        };
+        if !targets.contains(partial.event_hash_raw) {
+            continue;
+        }
+        if let Ok(ParsedLine::Event(event)) = parse_line(&line) {
+            found.push(*event);
+        }
+    }
+    Ok(found)
+}
+
+/// Replace the free-text payload of `event` with [`REDACTED_PLACEHOLDER`].
+/// Text inserted by a description patch is masked with [`REDACTED_MASK`]
+/// instead, one mask character per original character.
+///
+/// Structural fields (state, kind, links, agents) are left intact so the
+/// scrubbed event still replays to the same shape. Returns `true` if any
+/// field changed.
+pub fn scrub_payload(event: &mut Event) -> bool {
+    fn scrub(text: &mut String) -> bool {
+        if text == REDACTED_PLACEHOLDER {
+            return false;
+        }
+        REDACTED_PLACEHOLDER.clone_into(text);
+        true
+    }
+
+    fn scrub_opt(text: &mut Option<String>) -> bool {
+        text.as_mut().is_some_and(scrub)
+    }
+
+    match &mut event.data {
+        EventData::Comment(d) => scrub(&mut d.body),
+        EventData::CommentEdit(d) => scrub(&mut d.body),
+        EventData::CommentRetract(d) => scrub_opt(&mut d.reason),
+        EventData::Attach(d) => scrub(&mut d.name),
+        EventData::Create(d) => {
+            let title = scrub(&mut d.title);
+            let description = scrub_opt(&mut d.description);
+            title || description
+        }
+        EventData::Update(d) => match d.field.as_str() {
+            "title" | "description" if d.value.is_string() => {
+                let changed = d.value.as_str() != Some(REDACTED_PLACEHOLDER);
+                d.value = serde_json::Value::String(REDACTED_PLACEHOLDER.to_string());
+                changed
+            }
+            _ => false,
+        },
+        EventData::Compact(d) => scrub(&mut d.summary),
+        EventData::Move(d) => scrub_opt(&mut d.reason),
+        EventData::Delete(d) => scrub_opt(&mut d.reason),
+        EventData::Patch(d) => {
+            let mut changed = false;
+            for op in &mut d.ops {
+                if let TextOp::Insert { text, .. } = op {
+                    changed |= mask_run(text);
+                }
+            }
+            changed
+        }
+        EventData::Assign(_)
+        | EventData::Link(_)
+        | EventData::Unlink(_)
+        | EventData::Snapshot(_)
+        | EventData::Redact(_) => false,
+    }
+}
+
+/// Scrub the payload of every event in `events` targeted by a redaction in
+/// the same slice. Returns the number of events whose payload changed.
+pub fn scrub_redacted(events: &mut [Event]) -> usize {
+    let targets = redaction_targets(events);
+    if targets.is_empty() {
+        return 0;
+    }
+
+    events
+        .iter_mut()
+        .filter(|event| targets.contains(&event.event_hash))
+        .map(scrub_payload)
+        .filter(|changed| *changed)
+        .count()
+}
+
+/// Remove the content of a redacted `target` event from the projection.
+///
+/// The projector blanks comment bodies when it sees an `item.redact`, and
+/// calls this as well when it has the event log to look the target up in.
+/// Item fields written by the target (title,
+/// description, compact summary) are only replaced when the projection still
+/// holds the exact redacted value — a later legitimate write must not be
+/// clobbered. FTS5 rows follow automatically via the `items_au` trigger; the
+/// index is then optimized so the deleted tokens are merged out of its
+/// segments. Description edits follow the same rule: a redacted patch has
+/// its inserted runs masked in place, and a redacted description base
+/// takes its accumulated edits with it. The item's semantic embedding is dropped so the next semantic
+/// sync recomputes it from the scrubbed text.
+///
+/// # Errors
+///
+/// Returns an error if any projection update fails.
+pub fn scrub_projection(conn: &Connection, target: &Event) -> Result<ProjectionScrub> {
+    let mut report = ProjectionScrub::default();
+    let item_id = target.item_id.as_str();
+
+    report.comments = conn
+        .execute(
+            "UPDATE item_comments SET body = ?1 WHERE event_hash = ?2 AND body <> ?1",
+            params![REDACTED_PLACEHOLDER, target.event_hash],
+        )
+        .context("scrub redacted comment body")?;
+    report.comments += conn
+        .execute(
+            "UPDATE item_comment_edits SET body = ?1 WHERE event_hash = ?2 AND body <> ?1",
+            params![REDACTED_PLACEHOLDER, target.event_hash],
+        )
+        .context("scrub redacted comment edit body")?;
+    report.comments += conn
+        .execute(
+            "UPDATE item_comment_retractions SET reason = ?1
+             WHERE event_hash = ?2 AND reason IS NOT NULL AND reason <> ?1",
+            params![REDACTED_PLACEHOLDER, target.event_hash],
+        )
+        .context("scrub redacted comment retraction reason")?;
+    report.attachments = conn
+        .execute(
+            "UPDATE item_attachments SET name = ?1 WHERE event_hash = ?2 AND name <> ?1",
+            params![REDACTED_PLACEHOLDER, target.event_hash],
+        )
+        .context("scrub redacted attachment name")?;
+
+    let mut scrub_field = |column: &str, value: &str| -> Result<()> {
+        if value.is_empty() || value == REDACTED_PLACEHOLDER {
+            return Ok(());
+        }
+        let sql = format!("UPDATE items SET {column} = ?1 WHERE item_id = ?2 AND {column} = ?3");
+        report.item_fields += conn
+            .execute(&sql, params![REDACTED_PLACEHOLDER, item_id, value])
+            .with_context(|| format!("scrub redacted {column} for {item_id}"))?;
+        Ok(())
+    };
+
+    match &target.data {
+        EventData::Create(d) => {
+            scrub_field("title", &d.title)?;
+            if let Some(description) = &d.description {
+                scrub_field("description", description)?;
+            }
+        }
+        EventData::Update(d) if d.field == "title" || d.field == "description" => {
+            if let Some(value) = d.value.as_str() {
+                scrub_field(&d.field, value)?;
+            }
+        }
+        EventData::Compact(d) => scrub_field("compact_summary", &d.summary)?,
+        _ => {}
+    }
+    report.item_fields += scrub_description_edits(conn, target)?;
+
+    if report.item_fields > 0 {
+        conn.execute_batch("INSERT INTO items_fts(items_fts) VALUES('optimize')")
+            .context("optimize FTS5 index after redaction")?;
+    }
+
+    let has_embeddings: Option<i64> = conn
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'item_embeddings'",
+            [],
+            |row| row.get(0),
+        )
+        .optional()
+        .context("check for semantic embedding table")?;
+    if has_embeddings.is_some() {
+        report.embeddings = conn
+            .execute(
+                "DELETE FROM item_embeddings WHERE item_id = ?1",
+                params![item_id],
+            )
+            .context("drop semantic embedding for redacted item")?;
+    }
+
+    Ok(report)
+}
+
+/// Scrub the `item_description_edits` row for `target`'s item if the target
+/// is its base write or one of the patches applied to it. Returns `1` if the
+/// visible description changed.
+fn scrub_description_edits(conn: &Connection, target: &Event) -> Result<usize> {
+    let item_id = target.item_id.as_str();
+    let Some(mut edits) = query::get_description_edits(conn, item_id)? else {
+        return Ok(0);
+    };
+
+    let description = if edits.base_hash == target.event_hash {
+        edits.base_text = REDACTED_PLACEHOLDER.to_string();
+        edits.seq = crate::crdt::text::TextSeq::new();
+        REDACTED_PLACEHOLDER.to_string()
+    } else if let EventData::Patch(d) = &target.data
+        && d.base == edits.base_hash
+    {
+        for op in &d.ops {
+            if let TextOp::Insert { id, .. } = op
+                && let Some(run) = edits.seq.runs.get_mut(id)
+            {
+                mask_run(&mut run.text);
+            }
+        }
+        edits.seq.render(&edits.base_text)
+    } else {
+        return Ok(0);
+    };
+
+    let seq_json = serde_json::to_string(&edits.seq).context("serialize description edits")?;
+    conn.execute(
+        "UPDATE item_description_edits SET base_text = ?1, seq_json = ?2 WHERE item_id = ?3",
+        params![edits.base_text, seq_json, item_id],
+    )
+    .with_context(|| format!("scrub description edits for {item_id}"))?;
+    let changed = conn
+        .execute(
+            "UPDATE items SET description = ?1 WHERE item_id = ?2 AND description IS NOT ?1",
+            params![description, item_id],
+        )
+        .with_context(|| format!("scrub patched description for {item_id}"))?;
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crdt::text::{TextId, TextSeq};
+    use crate::db::{migrations, project};
+    use crate::event::data::{CommentData, CreateData, PatchData, RedactData, UpdateData};
+    use crate::event::types::EventType;
+    use crate::model::item::{Kind, Urgency};
+    use crate::model::item_id::ItemId;
+    use std::collections::BTreeMap;
+
+    fn event(hash: &str, event_type: EventType, data: EventData) -> Event {
+        Event {
+            wall_ts_us: 1_000,
+            agent: "alice".into(),
+            itc: "itc:AQ".into(),
+            parents: vec![],
+            event_type,
+            item_id: ItemId::new_unchecked("bn-r1"),
+            data,
+            event_hash: hash.into(),
+            signature: None,
+        }
+    }
+
+    fn create(hash: &str, title: &str) -> Event {
+        event(
+            hash,
+            EventType::Create,
+            EventData::Create(CreateData {
+                title: title.into(),
+                kind: Kind::Task,
+                size: None,
+                urgency: Urgency::Default,
+                labels: vec!["secret-label".into()],
+                parent: None,
+                causation: None,
+                description: Some("token=abc123".into()),
+                extra: BTreeMap::new(),
+            }),
+        )
+    }
+
+    fn comment(hash: &str, body: &str) -> Event {
+        event(
+            hash,
+            EventType::Comment,
+            EventData::Comment(CommentData {
+                body: body.into(),
+                reply_to: None,
+                extra: BTreeMap::new(),
+            }),
+        )
+    }
+
+    fn redact(hash: &str, target: &str) -> Event {
+        event(
+            hash,
+            EventType::Redact,
+            EventData::Redact(RedactData {
+                target_hash: target.into(),
+                reason: "leaked token".into(),
+                extra: BTreeMap::new(),
+            }),
+        )
+    }
+
+    #[test]
+    fn test_scrub_redacted_replaces_only_targets() {
+        let mut events = vec![
+            comment("blake3:c1", "token=abc123"),
+            comment("blake3:c2", "harmless"),
+            redact("blake3:r1", "blake3:c1"),
+        ];
+
+        assert_eq!(scrub_redacted(&mut events), 1);
+        let EventData::Comment(ref first) = events[0].data else {
+            panic!("expected comment");
+        };
+        let EventData::Comment(ref second) = events[1].data else {
+            panic!("expected comment");
+        };
+        assert_eq!(first.body, REDACTED_PLACEHOLDER);
+        assert_eq!(second.body, "harmless");
+
+        // Idempotent: a second pass has nothing left to change.
+        assert_eq!(scrub_redacted(&mut events), 0);
+    }
+
+    #[test]
+    fn test_scrub_payload_create_clears_text_keeps_labels() {
+        let mut ev = create("blake3:c", "Leaky title");
+        assert!(scrub_payload(&mut ev));
+        let EventData::Create(ref d) = ev.data else {
+            panic!("expected create");
+        };
+        assert_eq!(d.title, REDACTED_PLACEHOLDER);
+        assert_eq!(d.description.as_deref(), Some(REDACTED_PLACEHOLDER));
+        assert_eq!(d.labels, vec!["secret-label".to_string()]);
+        assert_eq!(d.kind, Kind::Task);
+    }
+
+    #[test]
+    fn test_scrubbed_replay_matches_scrubbed_projection() {
+        fn snapshot(conn: &Connection) -> (String, Option<String>, Vec<String>) {
+            let (title, description) = conn
+                .query_row(
+                    "SELECT title, description FROM items WHERE item_id = 'bn-r1'",
+                    [],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .expect("read item");
+            let mut stmt = conn
+                .prepare("SELECT label FROM item_labels WHERE item_id = 'bn-r1' ORDER BY label")
+                .expect("prepare labels");
+            let labels = stmt
+                .query_map([], |row| row.get(0))
+                .expect("query labels")
+                .collect::<Result<Vec<String>, _>>()
+                .expect("collect labels");
+            (title, description, labels)
+        }
+
+        let created = create("blake3:create", "Rotate token abc123");
+
+        // Live path: project the original, then scrub the projection.
+        let mut live = Connection::open_in_memory().expect("open");
+        migrations::migrate(&mut live).expect("migrate");
+        project::Projector::new(&live)
+            .project_event(&created)
+            .expect("project create");
+        scrub_projection(&live, &created).expect("scrub create");
+
+        // Rebuild path: project the scrubbed payload, as the cache holds it.
+        let mut rebuilt = Connection::open_in_memory().expect("open");
+        migrations::migrate(&mut rebuilt).expect("migrate");
+        let mut scrubbed = created.clone();
+        scrub_payload(&mut scrubbed);
+        project::Projector::new(&rebuilt)
+            .project_event(&scrubbed)
+            .expect("project scrubbed create");
+
+        let live = snapshot(&live);
+        assert_eq!(live, snapshot(&rebuilt));
+        assert_eq!(live.2, vec!["secret-label".to_string()]);
+    }
+
+    #[test]
+    fn test_scrub_payload_update_leaves_structural_fields() {
+        let mut ev = event(
+            "blake3:u",
+            EventType::Update,
+            EventData::Update(UpdateData {
+                field: "size".into(),
+                value: serde_json::json!("m"),
+                extra: BTreeMap::new(),
+            }),
+        );
+        assert!(!scrub_payload(&mut ev));
+    }
+
+    #[test]
+    fn test_scrub_projection_comment_title_and_embedding() {
+        let mut conn = Connection::open_in_memory().expect("open");
+        migrations::migrate(&mut conn).expect("migrate");
+        let projector = project::Projector::new(&conn);
+
+        let created = create("blake3:create", "Rotate token abc123");
+        let leaked = comment("blake3:comment", "token=abc123");
+        projector.project_event(&created).expect("project create");
+        projector.project_event(&leaked).expect("project comment");
+
+        conn.execute_batch(
+            "CREATE TABLE item_embeddings (
+                item_id TEXT PRIMARY KEY,
+                content_hash TEXT NOT NULL,
+                embedding_json TEXT NOT NULL
+            );
+            INSERT INTO item_embeddings VALUES ('bn-r1', 'h', '[]');",
+        )
+        .expect("seed embeddings");
+
+        let report = scrub_projection(&conn, &leaked).expect("scrub comment");
+        assert_eq!(report.comments, 1);
+        assert_eq!(report.embeddings, 1);
+
+        let report = scrub_projection(&conn, &created).expect("scrub create");
+        assert_eq!(report.item_fields, 2);
+
+        let (title, body): (String, String) = conn
+            .query_row(
+                "SELECT i.title, c.body FROM items i JOIN item_comments c USING (item_id)",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("read scrubbed rows");
+        assert_eq!(title, REDACTED_PLACEHOLDER);
+        assert_eq!(body, REDACTED_PLACEHOLDER);
+
+        let hits: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM items_fts WHERE items_fts MATCH 'abc123'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("fts query");
+        assert_eq!(hits, 0);
+    }
+
+    fn patch(hash: &str, base: &str, ops: Vec<TextOp>) -> Event {
+        event(
+            hash,
+            EventType::Patch,
+            EventData::Patch(PatchData {
+                base: base.into(),
+                ops,
+                extra: BTreeMap::new(),
+            }),
+        )
+    }
+
+    #[test]
+    fn test_scrub_payload_patch_replaces_inserted_text() {
+        let mut ev = patch(
+            "blake3:p",
+            "blake3:c",
+            vec![
+                TextOp::Insert {
+                    id: TextId::new(20, "alice"),
+                    after: None,
+                    text: "password=hunter2".into(),
+                },
+                TextOp::Delete {
+                    start: TextId::base(0),
+                    len: 1,
+                },
+            ],
+        );
+        assert!(scrub_payload(&mut ev));
+        let EventData::Patch(ref d) = ev.data else {
+            panic!("expected patch");
+        };
+        assert!(matches!(&d.ops[0], TextOp::Insert { text, .. } if text == &"\u{2588}".repeat(16)));
+        assert!(matches!(&d.ops[1], TextOp::Delete { len: 1, .. }));
+        assert!(!scrub_payload(&mut ev));
+    }
+
+    #[test]
+    fn test_scrub_projection_patch_and_base() {
+        let mut conn = Connection::open_in_memory().expect("open");
+        migrations::migrate(&mut conn).expect("migrate");
+        let projector = project::Projector::new(&conn);
+
+        let created = create("blake3:create", "Rotate token");
+        projector.project_event(&created).expect("project create");
+        let ops = TextSeq::new().diff("token=abc123", "token=abc123 see vault42", "bob");
+        let leaked = patch("blake3:patch", "blake3:create", ops);
+        projector.project_event(&leaked).expect("project patch");
+
+        let report = scrub_projection(&conn, &leaked).expect("scrub patch");
+        assert_eq!(report.item_fields, 1);
+        let description: String = conn
+            .query_row("SELECT description FROM items", [], |row| row.get(0))
+            .expect("description");
+        assert_eq!(
+            description,
+            format!("token=abc123{}", "\u{2588}".repeat(12))
+        );
+
+        let report = scrub_projection(&conn, &created).expect("scrub base");
+        assert_eq!(report.item_fields, 2, "title and patched description");
+        let edits = query::get_description_edits(&conn, "bn-r1")
+            .expect("query")
+            .expect("edits row");
+        assert_eq!(edits.base_text, REDACTED_PLACEHOLDER);
+        assert!(edits.seq.is_empty());
+
+        let hits: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM items_fts WHERE items_fts MATCH 'vault42 OR abc123'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("fts query");
+        assert_eq!(hits, 0);
+    }
+
+    #[test]
+    fn test_scrub_projection_patch_keeps_run_ids_for_later_edits() {
+        let mut conn = Connection::open_in_memory().expect("open");
+        migrations::migrate(&mut conn).expect("migrate");
+        let projector = project::Projector::new(&conn);
+
+        let created = create("blake3:create", "Rotate token");
+        projector.project_event(&created).expect("project create");
+        let ops = TextSeq::new().diff("token=abc123", "token=abc123 see vault42", "bob");
+        let Some(TextOp::Insert { id: run, .. }) = ops.first().cloned() else {
+            panic!("expected an insert");
+        };
+        let leaked = patch("blake3:patch", "blake3:create", ops);
+        projector.project_event(&leaked).expect("project patch");
+        scrub_projection(&conn, &leaked).expect("scrub patch");
+
+        // Trim the last four chars of the redacted run and append after it.
+        let later = patch(
+            "blake3:later",
+            "blake3:create",
+            vec![
+                TextOp::Delete {
+                    start: TextId::new(run.counter + 8, "bob"),
+                    len: 4,
+                },
+                TextOp::Insert {
+                    id: TextId::new(run.counter + 12, "carol"),
+                    after: Some(TextId::new(run.counter + 11, "bob")),
+                    text: " (rotated)".into(),
+                },
+            ],
+        );
+        projector
+            .project_event(&later)
+            .expect("project later patch");
+
+        let description: String = conn
+            .query_row("SELECT description FROM items", [], |row| row.get(0))
+            .expect("description");
+        assert_eq!(
+            description,
+            format!("token=abc123{} (rotated)", "\u{2588}".repeat(8))
+        );
+    }
+
+    #[test]
+    fn test_scrub_projection_keeps_later_title() {
+        let mut conn = Connection::open_in_memory().expect("open");
+        migrations::migrate(&mut conn).expect("migrate");
+        let projector = project::Projector::new(&conn);
+
+        let created = create("blake3:create", "Leaky title");
+        projector.project_event(&created).expect("project create");
+        conn.execute(
+            "UPDATE items SET title = 'Clean title' WHERE item_id = 'bn-r1'",
+            [],
+        )
+        .expect("retitle");
+
+        let report = scrub_projection(&conn, &created).expect("scrub");
+        assert_eq!(report.item_fields, 1, "only description still matches");
+        let title: String = conn
+            .query_row("SELECT title FROM items", [], |row| row.get(0))
+            .expect("title");
+        assert_eq!(title, "Clean title");
+    }
+}
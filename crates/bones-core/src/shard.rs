@@ -29,6 +29,7 @@
 use std::fs::{self, OpenOptions};
 use std::io::{self, Write as IoWrite};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use chrono::Datelike;
@@ -153,6 +154,9 @@ impl ShardManifest {
 pub struct ShardManager {
     /// Root of the `.bones` directory.
     bones_dir: PathBuf,
+    /// The last shard appended to and the offset it starts at in the
+    /// concatenated shards, so index journaling only stats that shard.
+    tail_start: Mutex<Option<((i32, u32), usize)>>,
 }
 
 impl ShardManager {
@@ -164,6 +168,7 @@ impl ShardManager {
     pub fn new(bones_dir: impl Into<PathBuf>) -> Self {
         Self {
             bones_dir: bones_dir.into(),
+            tail_start: Mutex::new(None),
         }
     }
 
@@ -467,7 +472,8 @@ impl ShardManager {
     /// 3. Reads and updates the monotonic clock.
     /// 4. Appends the line using `O_APPEND` + `write_all` + `flush`.
     /// 5. Optionally calls `sync_data` if `durable` is true.
-    /// 6. Releases the lock.
+    /// 6. Journals the line for the prolly index, if one has been written.
+    /// 7. Releases the lock.
     ///
     /// The `line` must be a complete TSJSON line ending with `\n`.
     ///
@@ -499,6 +505,9 @@ impl ShardManager {
         // Update monotonic clock
         let ts = self.next_timestamp()?;
 
+        // Note where the line lands for the prolly index journal
+        let index_start = self.index_journal_start(year, month)?;
+
         // Append with O_APPEND
         let mut file = OpenOptions::new()
             .create(true)
@@ -512,19 +521,23 @@ impl ShardManager {
             file.sync_data()?;
         }
 
+        self.journal_for_index(index_start, line);
+
         Ok(ts)
     }
 
     /// Append a raw line without locking or clock update.
     ///
     /// Used internally and in tests. The caller is responsible for
-    /// holding the lock and managing the clock.
+    /// holding the lock and managing the clock. Like [`Self::append`], the
+    /// line is journaled for the prolly index if one exists.
     ///
     /// # Errors
     ///
     /// Returns [`ShardError::Io`] on write failure.
     pub fn append_raw(&self, year: i32, month: u32, line: &str) -> Result<(), ShardError> {
         let shard_path = self.shard_path(year, month);
+        let index_start = self.index_journal_start(year, month)?;
 
         let mut file = OpenOptions::new()
             .create(true)
@@ -533,9 +546,67 @@ impl ShardManager {
 
         file.write_all(line.as_bytes())?;
         file.flush()?;
+
+        self.journal_for_index(index_start, line);
         Ok(())
     }
 
+    /// Offset a line appended to the given shard will land at, if a prolly
+    /// index exists to journal it for.
+    ///
+    /// Only appends to the last shard are journaled: a line added to an
+    /// earlier shard shifts everything after it, so the next refresh has to
+    /// read the shards anyway. Where the last shard starts is remembered
+    /// between appends, so only the first append to a shard lists and stats
+    /// the others.
+    fn index_journal_start(&self, year: i32, month: u32) -> Result<Option<usize>, ShardError> {
+        if !self.bones_dir.join(crate::sync::index::INDEX_PATH).exists() {
+            return Ok(None);
+        }
+
+        let cached = *self
+            .tail_start
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let start = match cached {
+            Some((shard, start)) if shard == (year, month) => start,
+            _ => {
+                let shards = self.list_shards()?;
+                if shards.last().is_some_and(|&last| last > (year, month)) {
+                    return Ok(None);
+                }
+                let mut start = 0usize;
+                for &(y, m) in shards.iter().filter(|&&shard| shard < (year, month)) {
+                    let len = fs::metadata(self.shard_path(y, m))?.len();
+                    start = start.saturating_add(usize::try_from(len).unwrap_or(usize::MAX));
+                }
+                *self
+                    .tail_start
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner) =
+                    Some(((year, month), start));
+                start
+            }
+        };
+
+        let tail_len = match fs::metadata(self.shard_path(year, month)) {
+            Ok(meta) => usize::try_from(meta.len()).unwrap_or(usize::MAX),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Some(start.saturating_add(tail_len)))
+    }
+
+    /// Journal an appended line for the prolly index. Failures only cost the
+    /// next refresh a read of the shards, so they are logged, not returned.
+    fn journal_for_index(&self, start: Option<usize>, line: &str) {
+        if let Some(start) = start
+            && let Err(e) = crate::sync::index::record_append(&self.bones_dir, start, line)
+        {
+            tracing::warn!("failed to journal appended event for the prolly index: {e}");
+        }
+    }
+
     // -----------------------------------------------------------------------
     // Monotonic clock
     // -----------------------------------------------------------------------
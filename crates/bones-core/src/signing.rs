@@ -0,0 +1,732 @@
+//! Ed25519 signatures over event hashes.
+//!
+//! The event hash already commits to every content field, so a signature only
+//! needs to cover the hash. It is stored as an optional ninth TSJSON field and
+//! is excluded from the hash itself: signing an event never changes its
+//! identity, and unsigned events stay valid.
+//!
+//! # Key layout
+//!
+//! ```text
+//! .bones/
+//!   keys/
+//!     alice.pub           # public keys, one `ed25519:<base64url>` per line
+//! <config-dir>/bones/keys/
+//!   alice.key             # secret key seed, never committed
+//! ```
+//!
+//! Public keys are part of the repository so every replica can verify
+//! offline. An agent may list several keys (for example after rotating); a
+//! signature is valid if any of them verifies it. Secret keys live in the
+//! user's config directory (or `$BONES_KEY_DIR`).
+//!
+//! # Signing
+//!
+//! Serializing an event never signs it. Code that authors a new event signs
+//! it with [`sign_as_author`] before writing the line; events read from
+//! elsewhere (imports, pulls, bundles) keep the signature they arrived with.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write as IoWrite};
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::{Signature, Signer, Verifier};
+use rand::RngCore;
+
+pub use ed25519_dalek::{SigningKey, VerifyingKey};
+
+use crate::event::Event;
+use crate::event::hash_text::{
+    decode_base64_url_no_pad, decode_blake3_hash, encode_base64_url_no_pad,
+};
+use crate::shard::{ShardError, ShardManager};
+
+/// Directory under `.bones/` holding the public key registry.
+pub const KEYS_DIR: &str = "keys";
+
+/// Prefix for encoded keys and signatures.
+pub const ED25519_PREFIX: &str = "ed25519:";
+
+/// Environment variable overriding the secret key directory.
+pub const KEY_DIR_ENV: &str = "BONES_KEY_DIR";
+
+/// Domain separator prepended to the hash bytes before signing.
+const SIGNING_CONTEXT: &[u8] = b"bones-event-signature-v1\n";
+
+// ---------------------------------------------------------------------------
+// Error types
+// ---------------------------------------------------------------------------
+
+/// Errors that can occur while managing keys or signing events.
+#[derive(Debug, thiserror::Error)]
+pub enum SigningError {
+    /// I/O error while reading or writing key files.
+    #[error("key I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    /// Error while reading event shards.
+    #[error("shard error: {0}")]
+    Shard(#[from] ShardError),
+
+    /// A key file or argument is not a valid encoded Ed25519 key.
+    #[error("invalid key in {source_name}: {reason}")]
+    InvalidKey {
+        /// File or argument the key came from.
+        source_name: String,
+        /// What was wrong with it.
+        reason: String,
+    },
+
+    /// The agent name cannot be used as a key file name.
+    #[error("agent '{0}' cannot be used as a key file name")]
+    InvalidAgent(String),
+
+    /// The event hash is not a valid `blake3:` digest.
+    #[error("invalid event hash: {0}")]
+    InvalidHash(String),
+
+    /// A secret key already exists and overwriting was not requested.
+    #[error("secret key already exists at {}", .0.display())]
+    KeyExists(PathBuf),
+
+    /// No config directory is available and `$BONES_KEY_DIR` is unset.
+    #[error("no key directory: set {KEY_DIR_ENV}")]
+    NoKeyDir,
+}
+
+// ---------------------------------------------------------------------------
+// Encoding
+// ---------------------------------------------------------------------------
+
+/// Encode a public key as `ed25519:<base64url-no-pad>`.
+#[must_use]
+pub fn encode_public_key(key: &VerifyingKey) -> String {
+    format!(
+        "{ED25519_PREFIX}{}",
+        encode_base64_url_no_pad(key.as_bytes())
+    )
+}
+
+/// Decode `ed25519:<base64url-no-pad>` into a public key.
+#[must_use]
+pub fn decode_public_key(raw: &str) -> Option<VerifyingKey> {
+    let bytes: [u8; 32] = decode_prefixed(raw)?.try_into().ok()?;
+    VerifyingKey::from_bytes(&bytes).ok()
+}
+
+/// Decode an `ed25519:<base64url-no-pad>` signature field.
+#[must_use]
+pub fn decode_signature(raw: &str) -> Option<Signature> {
+    let bytes: [u8; 64] = decode_prefixed(raw)?.try_into().ok()?;
+    Some(Signature::from_bytes(&bytes))
+}
+
+/// Return `true` if `raw` is a well-formed signature field.
+#[must_use]
+pub fn is_valid_signature_text(raw: &str) -> bool {
+    decode_signature(raw).is_some()
+}
+
+fn decode_prefixed(raw: &str) -> Option<Vec<u8>> {
+    decode_base64_url_no_pad(raw.trim().strip_prefix(ED25519_PREFIX)?)
+}
+
+fn signing_message(event_hash: &str) -> Option<Vec<u8>> {
+    let digest = decode_blake3_hash(event_hash)?;
+    let mut message = Vec::with_capacity(SIGNING_CONTEXT.len() + digest.len());
+    message.extend_from_slice(SIGNING_CONTEXT);
+    message.extend_from_slice(&digest);
+    Some(message)
+}
+
+// ---------------------------------------------------------------------------
+// Signing and verification
+// ---------------------------------------------------------------------------
+
+/// Sign an event hash and return the encoded signature field.
+///
+/// Legacy hex and base64url encodings of the same hash produce the same
+/// signature, since the raw digest bytes are signed.
+///
+/// # Errors
+///
+/// Returns [`SigningError::InvalidHash`] if `event_hash` is not a `blake3:`
+/// digest.
+pub fn sign_event_hash(key: &SigningKey, event_hash: &str) -> Result<String, SigningError> {
+    let message =
+        signing_message(event_hash).ok_or_else(|| SigningError::InvalidHash(event_hash.into()))?;
+    let signature = key.sign(&message);
+    Ok(format!(
+        "{ED25519_PREFIX}{}",
+        encode_base64_url_no_pad(&signature.to_bytes())
+    ))
+}
+
+/// Check `signature` over `event_hash` against one public key.
+#[must_use]
+pub fn verify_event_hash(key: &VerifyingKey, event_hash: &str, signature: &str) -> bool {
+    let (Some(message), Some(signature)) =
+        (signing_message(event_hash), decode_signature(signature))
+    else {
+        return false;
+    };
+    key.verify(&message, &signature).is_ok()
+}
+
+/// Outcome of checking one event's signature against the keyring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// Signed by one of the agent's registered keys.
+    Valid,
+    /// No signature field.
+    Unsigned,
+    /// Signed, but the agent has no registered public key.
+    UnknownKey,
+    /// The signature does not verify against any of the agent's keys.
+    Invalid,
+}
+
+impl SignatureStatus {
+    /// Stable lowercase name used in reports.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Valid => "valid",
+            Self::Unsigned => "unsigned",
+            Self::UnknownKey => "unknown_key",
+            Self::Invalid => "invalid",
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Public key registry
+// ---------------------------------------------------------------------------
+
+/// Public keys registered under `.bones/keys/`, grouped by agent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Keyring {
+    keys: BTreeMap<String, Vec<VerifyingKey>>,
+}
+
+impl Keyring {
+    /// Load every `<agent>.pub` file under `<bones_dir>/keys`.
+    ///
+    /// A missing directory yields an empty keyring.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SigningError::Io`] if a key file cannot be read, or
+    /// [`SigningError::InvalidKey`] if a line is not an encoded public key.
+    pub fn load(bones_dir: &Path) -> Result<Self, SigningError> {
+        let dir = bones_dir.join(KEYS_DIR);
+        let mut keys = BTreeMap::new();
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self { keys }),
+            Err(e) => return Err(e.into()),
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("pub") {
+                continue;
+            }
+            let Some(agent) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let agent_keys = read_public_keys(&path)?;
+            if !agent_keys.is_empty() {
+                keys.insert(agent.to_string(), agent_keys);
+            }
+        }
+        Ok(Self { keys })
+    }
+
+    /// Registered keys for `agent` (empty if none).
+    #[must_use]
+    pub fn keys_for(&self, agent: &str) -> &[VerifyingKey] {
+        self.keys.get(agent).map_or(&[], Vec::as_slice)
+    }
+
+    /// Iterate over agents and their keys in name order.
+    pub fn agents(&self) -> impl Iterator<Item = (&str, &[VerifyingKey])> {
+        self.keys.iter().map(|(a, k)| (a.as_str(), k.as_slice()))
+    }
+
+    /// Check an event's signature field against `agent`'s keys.
+    #[must_use]
+    pub fn check(&self, agent: &str, event_hash: &str, signature: Option<&str>) -> SignatureStatus {
+        let Some(signature) = signature else {
+            return SignatureStatus::Unsigned;
+        };
+        let keys = self.keys_for(agent);
+        if keys.is_empty() {
+            return SignatureStatus::UnknownKey;
+        }
+        if keys
+            .iter()
+            .any(|key| verify_event_hash(key, event_hash, signature))
+        {
+            SignatureStatus::Valid
+        } else {
+            SignatureStatus::Invalid
+        }
+    }
+}
+
+fn read_public_keys(path: &Path) -> Result<Vec<VerifyingKey>, SigningError> {
+    let content = fs::read_to_string(path)?;
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            decode_public_key(line).ok_or_else(|| SigningError::InvalidKey {
+                source_name: path.display().to_string(),
+                reason: format!("'{line}' is not an ed25519 public key"),
+            })
+        })
+        .collect()
+}
+
+fn key_file_name(agent: &str, extension: &str) -> Result<String, SigningError> {
+    if agent.is_empty()
+        || agent.starts_with('.')
+        || agent
+            .chars()
+            .any(|c| matches!(c, '/' | '\\' | '\0') || c.is_whitespace())
+    {
+        return Err(SigningError::InvalidAgent(agent.to_string()));
+    }
+    Ok(format!("{agent}.{extension}"))
+}
+
+/// Path of `agent`'s public key file under `<bones_dir>/keys`.
+///
+/// # Errors
+///
+/// Returns [`SigningError::InvalidAgent`] if the agent name is not a safe
+/// file name.
+pub fn public_key_path(bones_dir: &Path, agent: &str) -> Result<PathBuf, SigningError> {
+    Ok(bones_dir.join(KEYS_DIR).join(key_file_name(agent, "pub")?))
+}
+
+/// Add `key` to `agent`'s public key file.
+///
+/// Returns `false` if the key was already registered.
+///
+/// # Errors
+///
+/// Returns [`SigningError`] if the agent name is unusable or the file cannot
+/// be read or written.
+pub fn register_public_key(
+    bones_dir: &Path,
+    agent: &str,
+    key: &VerifyingKey,
+) -> Result<bool, SigningError> {
+    let path = public_key_path(bones_dir, agent)?;
+    let existing = if path.is_file() {
+        read_public_keys(&path)?
+    } else {
+        Vec::new()
+    };
+    if existing.contains(key) {
+        return Ok(false);
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    writeln!(file, "{}", encode_public_key(key))?;
+    Ok(true)
+}
+
+/// Remove `key` (or every key, if `None`) from `agent`'s public key file.
+///
+/// Returns the number of keys removed. The file is deleted once empty.
+///
+/// # Errors
+///
+/// Returns [`SigningError`] if the agent name is unusable or the file cannot
+/// be read or written.
+pub fn remove_public_key(
+    bones_dir: &Path,
+    agent: &str,
+    key: Option<&VerifyingKey>,
+) -> Result<usize, SigningError> {
+    let path = public_key_path(bones_dir, agent)?;
+    if !path.is_file() {
+        return Ok(0);
+    }
+    let existing = read_public_keys(&path)?;
+    let kept: Vec<&VerifyingKey> = existing
+        .iter()
+        .filter(|k| key.is_some_and(|target| target != *k))
+        .collect();
+    let removed = existing.len() - kept.len();
+    if kept.is_empty() {
+        fs::remove_file(&path)?;
+    } else if removed > 0 {
+        let content = kept.iter().fold(String::new(), |mut acc, k| {
+            acc.push_str(&encode_public_key(k));
+            acc.push('\n');
+            acc
+        });
+        fs::write(&path, content)?;
+    }
+    Ok(removed)
+}
+
+// ---------------------------------------------------------------------------
+// Secret keys
+// ---------------------------------------------------------------------------
+
+/// Directory holding secret keys: `$BONES_KEY_DIR`, else
+/// `<config-dir>/bones/keys`.
+#[must_use]
+pub fn default_key_dir() -> Option<PathBuf> {
+    std::env::var_os(KEY_DIR_ENV)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::config_dir().map(|dir| dir.join("bones").join(KEYS_DIR)))
+}
+
+/// Path of `agent`'s secret key file inside `key_dir`.
+///
+/// # Errors
+///
+/// Returns [`SigningError::InvalidAgent`] if the agent name is not a safe
+/// file name.
+pub fn secret_key_path(key_dir: &Path, agent: &str) -> Result<PathBuf, SigningError> {
+    Ok(key_dir.join(key_file_name(agent, "key")?))
+}
+
+/// Load `agent`'s secret key from `key_dir`, if one exists.
+///
+/// # Errors
+///
+/// Returns [`SigningError`] if the file exists but cannot be read or does
+/// not hold an encoded key.
+pub fn load_signing_key(key_dir: &Path, agent: &str) -> Result<Option<SigningKey>, SigningError> {
+    let path = secret_key_path(key_dir, agent)?;
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let seed: [u8; 32] = decode_prefixed(&content)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| SigningError::InvalidKey {
+            source_name: path.display().to_string(),
+            reason: "expected ed25519:<base64url seed>".into(),
+        })?;
+    Ok(Some(SigningKey::from_bytes(&seed)))
+}
+
+/// Generate a new secret key for `agent` and write it to `key_dir`.
+///
+/// The file is created with owner-only permissions on Unix.
+///
+/// # Errors
+///
+/// Returns [`SigningError::KeyExists`] if a key exists and `overwrite` is
+/// false, or [`SigningError::Io`] if the file cannot be written.
+pub fn generate_signing_key(
+    key_dir: &Path,
+    agent: &str,
+    overwrite: bool,
+) -> Result<SigningKey, SigningError> {
+    let path = secret_key_path(key_dir, agent)?;
+    if path.exists() && !overwrite {
+        return Err(SigningError::KeyExists(path));
+    }
+    fs::create_dir_all(key_dir)?;
+
+    let mut seed = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut seed);
+    let key = SigningKey::from_bytes(&seed);
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path)?;
+    writeln!(
+        file,
+        "{ED25519_PREFIX}{}",
+        encode_base64_url_no_pad(key.as_bytes())
+    )?;
+    file.sync_all()?;
+    Ok(key)
+}
+
+// ---------------------------------------------------------------------------
+// Signing authored events
+// ---------------------------------------------------------------------------
+
+/// Signature for `event` from its agent's secret key in `key_dir`, or `None`
+/// if the agent has no key there. `event.event_hash` must already be set.
+///
+/// # Errors
+///
+/// Returns [`SigningError`] if the agent's key file cannot be read or the
+/// event hash is malformed.
+pub fn sign_as_author(key_dir: &Path, event: &Event) -> Result<Option<String>, SigningError> {
+    load_signing_key(key_dir, &event.agent)?
+        .map(|key| sign_event_hash(&key, &event.event_hash))
+        .transpose()
+}
+
+// ---------------------------------------------------------------------------
+// Repository verification
+// ---------------------------------------------------------------------------
+
+/// Signature counts for one agent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgentSignatures {
+    /// Agent name.
+    pub agent: String,
+    /// Number of registered public keys.
+    pub keys: usize,
+    /// Events with a valid signature.
+    pub valid: usize,
+    /// Events without a signature.
+    pub unsigned: usize,
+    /// Signed events whose agent has no registered key.
+    pub unknown_key: usize,
+    /// Events whose signature does not verify.
+    pub invalid: usize,
+}
+
+/// One event that failed a signature check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureIssue {
+    /// Hash of the event.
+    pub event_hash: String,
+    /// Agent named on the event.
+    pub agent: String,
+    /// Item the event mutates.
+    pub item_id: String,
+    /// Why the event was flagged.
+    pub status: SignatureStatus,
+}
+
+/// Result of checking every event signature in a repository.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SignatureReport {
+    /// Per-agent counts, sorted by agent name. Includes agents with keys but
+    /// no events.
+    pub agents: Vec<AgentSignatures>,
+    /// Events with invalid or unverifiable signatures, plus unsigned events
+    /// from agents that have registered keys.
+    pub issues: Vec<SignatureIssue>,
+}
+
+impl SignatureReport {
+    /// Return `true` if no signature failed to verify.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.agents.iter().all(|a| a.invalid == 0)
+    }
+
+    /// Return `true` if every event carries a valid signature.
+    #[must_use]
+    pub fn all_signed(&self) -> bool {
+        self.agents
+            .iter()
+            .all(|a| a.unsigned == 0 && a.unknown_key == 0 && a.invalid == 0)
+    }
+}
+
+/// Check the signature of every event in `<bones_dir>/events` against the
+/// public key registry. Runs entirely offline.
+///
+/// # Errors
+///
+/// Returns [`SigningError`] if the keyring or shards cannot be read. Lines
+/// that fail to parse are skipped; `bn verify` reports those separately.
+pub fn verify_signatures(bones_dir: &Path) -> Result<SignatureReport, SigningError> {
+    let keyring = Keyring::load(bones_dir)?;
+    let mut agents: BTreeMap<String, AgentSignatures> = keyring
+        .agents()
+        .map(|(agent, keys)| {
+            (
+                agent.to_string(),
+                AgentSignatures {
+                    agent: agent.to_string(),
+                    keys: keys.len(),
+                    ..AgentSignatures::default()
+                },
+            )
+        })
+        .collect();
+    let mut issues = Vec::new();
+
+    let shards = ShardManager::new(bones_dir);
+    for line in shards.replay_lines()? {
+        let (_, line) = line?;
+        let Ok(crate::event::PartialParsedLine::Event(event)) =
+            crate::event::parse_line_partial(&line)
+        else {
+            continue;
+        };
+        // The signature covers the claimed hash; it only vouches for the
+        // line's content if that hash also matches fields 1–7.
+        let status = match keyring.check(event.agent, event.event_hash_raw, event.signature_raw) {
+            SignatureStatus::Valid if crate::event::parse_line(&line).is_err() => {
+                SignatureStatus::Invalid
+            }
+            status => status,
+        };
+        let counts = agents
+            .entry(event.agent.to_string())
+            .or_insert_with(|| AgentSignatures {
+                agent: event.agent.to_string(),
+                ..AgentSignatures::default()
+            });
+        match status {
+            SignatureStatus::Valid => counts.valid += 1,
+            SignatureStatus::Unsigned => counts.unsigned += 1,
+            SignatureStatus::UnknownKey => counts.unknown_key += 1,
+            SignatureStatus::Invalid => counts.invalid += 1,
+        }
+        let flagged = match status {
+            SignatureStatus::Valid => false,
+            SignatureStatus::Unsigned => counts.keys > 0,
+            SignatureStatus::UnknownKey | SignatureStatus::Invalid => true,
+        };
+        if flagged {
+            issues.push(SignatureIssue {
+                event_hash: event.event_hash_raw.to_string(),
+                agent: event.agent.to_string(),
+                item_id: event.item_id_raw.to_string(),
+                status,
+            });
+        }
+    }
+
+    Ok(SignatureReport {
+        agents: agents.into_values().collect(),
+        issues,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::event::hash_text::encode_blake3_hash;
+
+    fn event_hash() -> String {
+        encode_blake3_hash(&blake3::hash(b"event"))
+    }
+
+    fn key(byte: u8) -> SigningKey {
+        SigningKey::from_bytes(&[byte; 32])
+    }
+
+    #[test]
+    fn sign_and_verify_round_trip() {
+        let signer = key(1);
+        let hash = event_hash();
+        let sig = sign_event_hash(&signer, &hash).unwrap();
+        assert!(sig.starts_with(ED25519_PREFIX));
+        assert!(is_valid_signature_text(&sig));
+        assert!(verify_event_hash(&signer.verifying_key(), &hash, &sig));
+        assert!(!verify_event_hash(&key(2).verifying_key(), &hash, &sig));
+
+        let other = encode_base64_url_no_pad(&[9u8; 32]);
+        assert!(!verify_event_hash(
+            &signer.verifying_key(),
+            &format!("blake3:{other}"),
+            &sig
+        ));
+    }
+
+    #[test]
+    fn public_keys_round_trip_through_text() {
+        let public = key(3).verifying_key();
+        let text = encode_public_key(&public);
+        assert_eq!(decode_public_key(&text), Some(public));
+        assert_eq!(decode_public_key("ed25519:nope"), None);
+        assert_eq!(
+            decode_public_key(&text.replace(ED25519_PREFIX, "rsa:")),
+            None
+        );
+    }
+
+    #[test]
+    fn keyring_registers_and_removes_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let (a, b) = (key(4).verifying_key(), key(5).verifying_key());
+
+        assert!(register_public_key(dir.path(), "alice", &a).unwrap());
+        assert!(!register_public_key(dir.path(), "alice", &a).unwrap());
+        assert!(register_public_key(dir.path(), "alice", &b).unwrap());
+
+        let keyring = Keyring::load(dir.path()).unwrap();
+        assert_eq!(keyring.keys_for("alice"), [a, b]);
+        assert!(keyring.keys_for("bob").is_empty());
+
+        assert_eq!(remove_public_key(dir.path(), "alice", Some(&a)).unwrap(), 1);
+        assert_eq!(Keyring::load(dir.path()).unwrap().keys_for("alice"), [b]);
+        assert_eq!(remove_public_key(dir.path(), "alice", None).unwrap(), 1);
+        assert!(!public_key_path(dir.path(), "alice").unwrap().exists());
+
+        assert!(matches!(
+            register_public_key(dir.path(), "../evil", &a),
+            Err(SigningError::InvalidAgent(_))
+        ));
+    }
+
+    #[test]
+    fn keyring_checks_signatures_per_agent() {
+        let dir = tempfile::tempdir().unwrap();
+        let alice = key(6);
+        register_public_key(dir.path(), "alice", &alice.verifying_key()).unwrap();
+        let keyring = Keyring::load(dir.path()).unwrap();
+        let hash = event_hash();
+        let sig = sign_event_hash(&alice, &hash).unwrap();
+        let forged = sign_event_hash(&key(7), &hash).unwrap();
+
+        assert_eq!(
+            keyring.check("alice", &hash, Some(&sig)),
+            SignatureStatus::Valid
+        );
+        assert_eq!(
+            keyring.check("alice", &hash, None),
+            SignatureStatus::Unsigned
+        );
+        assert_eq!(
+            keyring.check("alice", &hash, Some(&forged)),
+            SignatureStatus::Invalid
+        );
+        assert_eq!(
+            keyring.check("bob", &hash, Some(&sig)),
+            SignatureStatus::UnknownKey
+        );
+    }
+
+    #[test]
+    fn secret_keys_are_generated_once() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_signing_key(dir.path(), "alice").unwrap().is_none());
+
+        let generated = generate_signing_key(dir.path(), "alice", false).unwrap();
+        let loaded = load_signing_key(dir.path(), "alice").unwrap().unwrap();
+        assert_eq!(generated.to_bytes(), loaded.to_bytes());
+        assert!(matches!(
+            generate_signing_key(dir.path(), "alice", false),
+            Err(SigningError::KeyExists(_))
+        ));
+        let rotated = generate_signing_key(dir.path(), "alice", true).unwrap();
+        assert_ne!(rotated.to_bytes(), generated.to_bytes());
+    }
+}
@@ -0,0 +1,678 @@
+//! Offline sync bundles for air-gapped replicas.
+//!
+//! A bundle is a self-contained text file carrying the events one replica has
+//! and another lacks, so two machines that never share a git remote can sync
+//! over a USB stick or an email attachment.
+//!
+//! # Format
+//!
+//! ```text
+//! # bones bundle v1
+//! # base: <prolly root hex | none>
+//! # head: <prolly root hex>
+//! # events: <count>
+//! <TSJSON event line>
+//! ...
+//! ```
+//!
+//! Event lines are byte-for-byte shard lines (including optional signatures),
+//! so every event hash is re-verified when the bundle is parsed.
+//!
+//! - `base` is the Prolly root the bundle was built against: the bundle holds
+//!   every event of the creating replica that is not under that root.
+//! - `head` is the creating replica's root at creation time. A receiver that
+//!   held exactly the `base` events ends up at `head` after applying.
+//!
+//! # Known roots
+//!
+//! A root hash alone does not say which events it covers, so each replica
+//! keeps the trees it has produced or reached in `.bones/cache/sync-roots/`
+//! ([`RootStore`]). `bn bundle create --since <root>` resolves the root there;
+//! the usual incremental handoff passes the `head` of the previous bundle.
+//! Only the most recently recorded or resolved roots are kept
+//! ([`DEFAULT_KEPT_ROOTS`]). `--since` a pruned root is an unknown root, and a
+//! bundle built against one applies with a "base not seen" warning.
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::event::Event;
+use crate::event::parser::{ParseError, ParsedLine, parse_line};
+use crate::event::writer::{WriteError, write_line};
+use crate::sync::merge::merge_event_sets;
+use crate::sync::prolly::{Hash, ProllyTree};
+
+/// Current bundle format version.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Prefix of the first line of every bundle.
+const HEADER_PREFIX: &str = "# bones bundle v";
+
+/// Directory under `.bones/` holding known Prolly roots.
+const ROOTS_DIR: &str = "cache/sync-roots";
+
+/// Shortest root prefix accepted by [`RootStore::resolve`].
+pub const MIN_ROOT_PREFIX: usize = 8;
+
+/// Roots a [`RootStore`] keeps before pruning the least recently used.
+pub const DEFAULT_KEPT_ROOTS: usize = 32;
+
+// ---------------------------------------------------------------------------
+// Error types
+// ---------------------------------------------------------------------------
+
+/// Errors that can occur while reading, writing, or applying bundles.
+#[derive(Debug, thiserror::Error)]
+pub enum BundleError {
+    /// I/O error on the bundle file or root store.
+    #[error("bundle I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    /// The input does not start with a bundle header.
+    #[error("not a bones bundle (missing '{HEADER_PREFIX}N' header)")]
+    MissingHeader,
+
+    /// The bundle was written by a newer version of bones.
+    #[error(
+        "bundle version {0} is newer than this version of bones (supports up to v{BUNDLE_VERSION})"
+    )]
+    UnsupportedVersion(u32),
+
+    /// A header line is missing or malformed.
+    #[error("invalid bundle header '{name}': {value}")]
+    InvalidHeader {
+        /// Header name (`base`, `head`, or `events`).
+        name: &'static str,
+        /// The offending line, or a description of what was missing.
+        value: String,
+    },
+
+    /// An event line failed to parse or its hash did not verify.
+    #[error("bundle line {line}: {source}")]
+    Parse {
+        /// 1-based line number within the bundle.
+        line: usize,
+        /// Underlying parse error.
+        source: ParseError,
+    },
+
+    /// The number of event lines differs from the `events` header, which
+    /// usually means the file was truncated.
+    #[error("bundle declares {expected} events but contains {found}")]
+    CountMismatch {
+        /// Count from the header.
+        expected: usize,
+        /// Event lines actually present.
+        found: usize,
+    },
+
+    /// An event could not be serialized.
+    #[error("failed to serialize event: {0}")]
+    Write(#[from] WriteError),
+
+    /// A stored Prolly tree could not be decoded.
+    #[error("corrupt sync root {path}: {source}")]
+    CorruptRoot {
+        /// File holding the tree.
+        path: PathBuf,
+        /// Decode error.
+        source: serde_json::Error,
+    },
+
+    /// No known root matches the requested hash or prefix.
+    #[error("unknown sync root '{0}'")]
+    UnknownRoot(String),
+
+    /// A root prefix matches several known roots.
+    #[error("sync root prefix '{0}' is ambiguous")]
+    AmbiguousRoot(String),
+}
+
+// ---------------------------------------------------------------------------
+// Bundle
+// ---------------------------------------------------------------------------
+
+/// A parsed or freshly created sync bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bundle {
+    /// Root the bundle was built against; `None` for a full bundle.
+    pub base: Option<Hash>,
+    /// Root of the creating replica when the bundle was written.
+    pub head: Hash,
+    /// Events missing from `base`, in canonical merge order.
+    pub events: Vec<Event>,
+}
+
+impl Bundle {
+    /// Bundle the events in `events` that are not covered by `base`.
+    ///
+    /// With no base, every event is included.
+    #[must_use]
+    pub fn create(events: &[Event], base: Option<&ProllyTree>) -> Self {
+        let head = ProllyTree::build(events).root.hash();
+        let known: HashSet<String> = base
+            .map(ProllyTree::event_hashes)
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        let missing: Vec<Event> = events
+            .iter()
+            .filter(|e| !known.contains(&e.event_hash))
+            .cloned()
+            .collect();
+        Self {
+            base: base.map(|tree| tree.root.hash()),
+            head,
+            events: merge_event_sets(&missing, &[]).events,
+        }
+    }
+
+    /// Render the bundle in its on-disk text format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BundleError::Write`] if an event fails to serialize.
+    pub fn to_text(&self) -> Result<String, BundleError> {
+        let mut out = String::new();
+        let base = self
+            .base
+            .map_or_else(|| "none".to_string(), |h| h.to_string());
+        let _ = writeln!(out, "{HEADER_PREFIX}{BUNDLE_VERSION}");
+        let _ = writeln!(out, "# base: {base}");
+        let _ = writeln!(out, "# head: {}", self.head);
+        let _ = writeln!(out, "# events: {}", self.events.len());
+        for event in &self.events {
+            out.push_str(&write_line(event)?);
+        }
+        Ok(out)
+    }
+
+    /// Parse a bundle, verifying the header and every event hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BundleError`] if the header is missing or malformed, an
+    /// event line fails to parse, or the event count does not match.
+    pub fn parse(text: &str) -> Result<Self, BundleError> {
+        let mut lines = text.lines();
+
+        let version = lines
+            .next()
+            .and_then(|line| line.trim_end().strip_prefix(HEADER_PREFIX))
+            .and_then(|v| v.parse::<u32>().ok())
+            .ok_or(BundleError::MissingHeader)?;
+        if version > BUNDLE_VERSION {
+            return Err(BundleError::UnsupportedVersion(version));
+        }
+
+        let base = match header_value(lines.next(), "base")? {
+            "none" => None,
+            raw => Some(parse_root(raw, "base")?),
+        };
+        let head = parse_root(header_value(lines.next(), "head")?, "head")?;
+        let raw_count = header_value(lines.next(), "events")?;
+        let expected: usize = raw_count.parse().map_err(|_| BundleError::InvalidHeader {
+            name: "events",
+            value: raw_count.to_string(),
+        })?;
+
+        let mut events = Vec::with_capacity(expected);
+        for (idx, line) in lines.enumerate() {
+            match parse_line(line) {
+                Ok(ParsedLine::Event(event)) => events.push(*event),
+                Ok(ParsedLine::Comment(_) | ParsedLine::Blank) => {}
+                Err(source) => {
+                    return Err(BundleError::Parse {
+                        line: idx + 5,
+                        source,
+                    });
+                }
+            }
+        }
+        if events.len() != expected {
+            return Err(BundleError::CountMismatch {
+                expected,
+                found: events.len(),
+            });
+        }
+
+        Ok(Self { base, head, events })
+    }
+
+    /// Read and parse a bundle file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BundleError`] if the file cannot be read or parsed.
+    pub fn read(path: &Path) -> Result<Self, BundleError> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Write the bundle to `path`, replacing any existing file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BundleError`] if serialization or the write fails.
+    pub fn write(&self, path: &Path) -> Result<(), BundleError> {
+        fs::write(path, self.to_text()?)?;
+        Ok(())
+    }
+}
+
+fn header_value<'a>(line: Option<&'a str>, name: &'static str) -> Result<&'a str, BundleError> {
+    let line = line.ok_or_else(|| BundleError::InvalidHeader {
+        name,
+        value: "missing".to_string(),
+    })?;
+    line.trim_end()
+        .strip_prefix("# ")
+        .and_then(|rest| rest.strip_prefix(name))
+        .and_then(|rest| rest.strip_prefix(": "))
+        .ok_or_else(|| BundleError::InvalidHeader {
+            name,
+            value: line.to_string(),
+        })
+}
+
+fn parse_root(raw: &str, name: &'static str) -> Result<Hash, BundleError> {
+    Hash::from_hex(raw).ok_or_else(|| BundleError::InvalidHeader {
+        name,
+        value: raw.to_string(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Applying
+// ---------------------------------------------------------------------------
+
+/// Outcome of merging a bundle into a local event set.
+#[derive(Debug, Clone)]
+pub struct BundleMerge {
+    /// Bundle events the local replica did not have, in canonical merge
+    /// order. These are the lines to append.
+    pub new_events: Vec<Event>,
+    /// Bundle events the local replica already had.
+    pub already_present: usize,
+    /// Local root after the merge.
+    pub root: ProllyTree,
+    /// Whether the local root now equals the bundle's `head`.
+    pub in_sync: bool,
+    /// The creator's tree at `head`, when it could be rebuilt from the base
+    /// tree and the bundle and its root matched. Recording it lets this
+    /// replica build bundles back `--since` that head.
+    pub head_tree: Option<ProllyTree>,
+}
+
+/// Merge `bundle` into `local` through [`merge_event_sets`].
+///
+/// `base_tree` is this replica's stored tree for `bundle.base`, if it has
+/// one; it is only used to rebuild [`BundleMerge::head_tree`].
+#[must_use]
+pub fn merge_bundle(
+    local: &[Event],
+    bundle: &Bundle,
+    base_tree: Option<&ProllyTree>,
+) -> BundleMerge {
+    let merged = merge_event_sets(local, &bundle.events);
+    let local_hashes: HashSet<&str> = local.iter().map(|e| e.event_hash.as_str()).collect();
+    let new_events: Vec<Event> = merged
+        .events
+        .iter()
+        .filter(|e| !local_hashes.contains(e.event_hash.as_str()))
+        .cloned()
+        .collect();
+    let root = ProllyTree::build(&merged.events);
+    let in_sync = root.root.hash() == bundle.head;
+
+    // The creator held the base events plus everything in the bundle.
+    let base_hashes = match (bundle.base, base_tree) {
+        (None, _) => Some(Vec::new()),
+        (Some(base), Some(tree)) if tree.root.hash() == base => Some(tree.event_hashes()),
+        (Some(_), _) => None,
+    };
+    let head_tree = base_hashes.and_then(|base_hashes| {
+        let mut wanted: HashSet<&str> = base_hashes.iter().map(String::as_str).collect();
+        wanted.extend(bundle.events.iter().map(|e| e.event_hash.as_str()));
+        let head_events: Vec<Event> = merged
+            .events
+            .iter()
+            .filter(|e| wanted.contains(e.event_hash.as_str()))
+            .cloned()
+            .collect();
+        let tree = ProllyTree::build(&head_events);
+        (tree.root.hash() == bundle.head).then_some(tree)
+    });
+
+    BundleMerge {
+        already_present: bundle.events.len() - new_events.len(),
+        new_events,
+        root,
+        in_sync,
+        head_tree,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Root store
+// ---------------------------------------------------------------------------
+
+/// Prolly trees this replica has produced or reached, keyed by root hash.
+///
+/// Each tree is a full copy of the index at that root, so the store keeps
+/// only the roots recorded or resolved most recently.
+#[derive(Debug, Clone)]
+pub struct RootStore {
+    dir: PathBuf,
+    keep: usize,
+}
+
+impl RootStore {
+    /// Root store for the project at `bones_dir`.
+    #[must_use]
+    pub fn new(bones_dir: &Path) -> Self {
+        Self {
+            dir: bones_dir.join(ROOTS_DIR),
+            keep: DEFAULT_KEPT_ROOTS,
+        }
+    }
+
+    /// Keep at most `keep` roots (at least one) instead of
+    /// [`DEFAULT_KEPT_ROOTS`].
+    #[must_use]
+    pub fn keep_last(mut self, keep: usize) -> Self {
+        self.keep = keep.max(1);
+        self
+    }
+
+    fn path_for(&self, root: &Hash) -> PathBuf {
+        self.dir.join(format!("{root}.prolly"))
+    }
+
+    /// Remember `tree` so later bundles can be built against its root, and
+    /// prune the least recently used roots beyond the store's limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BundleError::Io`] if the tree cannot be written or old
+    /// roots cannot be pruned.
+    pub fn record(&self, tree: &ProllyTree) -> Result<Hash, BundleError> {
+        let root = tree.root.hash();
+        let path = self.path_for(&root);
+        if path.exists() {
+            touch(&path)?;
+        } else {
+            fs::create_dir_all(&self.dir)?;
+            let tmp = path.with_extension("prolly.tmp");
+            fs::write(&tmp, tree.to_bytes())?;
+            fs::rename(&tmp, &path)?;
+        }
+        self.prune(&path)?;
+        Ok(root)
+    }
+
+    /// Remove all but the `keep` most recently used roots. `current` is
+    /// never removed.
+    fn prune(&self, current: &Path) -> Result<(), BundleError> {
+        let mut roots = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "prolly") && path != current {
+                roots.push((entry.metadata()?.modified()?, path));
+            }
+        }
+        if roots.len() < self.keep {
+            return Ok(());
+        }
+        roots.sort_unstable_by(|a, b| b.cmp(a));
+        for (_, path) in roots.drain(self.keep - 1..) {
+            match fs::remove_file(&path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolve a full root hash or a unique prefix of at least
+    /// [`MIN_ROOT_PREFIX`] hex digits to a stored tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BundleError::UnknownRoot`] if nothing matches,
+    /// [`BundleError::AmbiguousRoot`] if several roots share the prefix, or
+    /// an I/O or decode error.
+    pub fn resolve(&self, prefix: &str) -> Result<ProllyTree, BundleError> {
+        let prefix = prefix.trim().to_ascii_lowercase();
+        if prefix.len() < MIN_ROOT_PREFIX || !prefix.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(BundleError::UnknownRoot(prefix));
+        }
+
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Err(BundleError::UnknownRoot(prefix));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let mut matches = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            let is_match = path.extension().is_some_and(|ext| ext == "prolly")
+                && path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .is_some_and(|stem| stem.starts_with(&prefix));
+            if is_match {
+                matches.push(path);
+            }
+        }
+
+        match matches.as_slice() {
+            [] => Err(BundleError::UnknownRoot(prefix)),
+            [path] => {
+                // A root used as a base is likely to be used again.
+                touch(path)?;
+                let bytes = fs::read(path)?;
+                ProllyTree::from_bytes(&bytes).map_err(|source| BundleError::CorruptRoot {
+                    path: path.clone(),
+                    source,
+                })
+            }
+            _ => Err(BundleError::AmbiguousRoot(prefix)),
+        }
+    }
+
+    /// Whether `root` is a known root.
+    #[must_use]
+    pub fn contains(&self, root: &Hash) -> bool {
+        self.path_for(root).exists()
+    }
+}
+
+/// Mark a stored root as just used.
+fn touch(path: &Path) -> io::Result<()> {
+    fs::File::options()
+        .write(true)
+        .open(path)?
+        .set_modified(SystemTime::now())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::EventType;
+    use crate::event::data::{CreateData, EventData};
+    use crate::event::writer::compute_event_hash;
+    use crate::model::item::{Kind, Urgency};
+    use crate::model::item_id::ItemId;
+    use std::collections::BTreeMap;
+
+    fn make_event(item: &str, ts: i64) -> Event {
+        let mut event = Event {
+            wall_ts_us: ts,
+            agent: "alice".to_string(),
+            itc: "itc:AQ".to_string(),
+            parents: vec![],
+            event_type: EventType::Create,
+            item_id: ItemId::new_unchecked(item),
+            data: EventData::Create(CreateData {
+                title: format!("Item {item}"),
+                kind: Kind::Task,
+                size: None,
+                urgency: Urgency::Default,
+                labels: vec![],
+                parent: None,
+                causation: None,
+                description: None,
+                extra: BTreeMap::new(),
+            }),
+            event_hash: String::new(),
+            signature: None,
+        };
+        event.event_hash = compute_event_hash(&event).expect("hash");
+        event
+    }
+
+    #[test]
+    fn roundtrips_through_text() {
+        let events = vec![make_event("bn-bbb", 2), make_event("bn-aaa", 1)];
+        let bundle = Bundle::create(&events, None);
+        assert_eq!(bundle.base, None);
+        assert_eq!(bundle.events[0].wall_ts_us, 1);
+
+        let text = bundle.to_text().expect("render");
+        assert!(text.starts_with("# bones bundle v1\n# base: none\n"));
+        assert_eq!(Bundle::parse(&text).expect("parse"), bundle);
+    }
+
+    #[test]
+    fn includes_only_events_missing_from_base() {
+        let old = vec![make_event("bn-aaa", 1)];
+        let mut all = old.clone();
+        all.push(make_event("bn-bbb", 2));
+        let base = ProllyTree::build(&old);
+
+        let bundle = Bundle::create(&all, Some(&base));
+        assert_eq!(bundle.base, Some(base.root.hash()));
+        assert_eq!(bundle.head, ProllyTree::build(&all).root.hash());
+        assert_eq!(bundle.events, vec![all[1].clone()]);
+
+        let merge = merge_bundle(&old, &bundle, Some(&base));
+        assert_eq!(merge.new_events, vec![all[1].clone()]);
+        assert_eq!(merge.already_present, 0);
+        assert!(merge.in_sync);
+        assert_eq!(
+            merge.head_tree.map(|tree| tree.root.hash()),
+            Some(bundle.head)
+        );
+    }
+
+    #[test]
+    fn rebuilds_head_tree_when_receiver_has_extra_events() {
+        let sender = vec![make_event("bn-aaa", 1)];
+        let mut receiver = vec![make_event("bn-bbb", 2)];
+        let bundle = Bundle::create(&sender, None);
+
+        let merge = merge_bundle(&receiver, &bundle, None);
+        assert!(!merge.in_sync);
+        let head_tree = merge.head_tree.expect("head tree");
+        assert_eq!(head_tree.root.hash(), bundle.head);
+
+        // The receiver can now send back exactly what the sender lacks.
+        receiver.extend(merge.new_events);
+        let reply = Bundle::create(&receiver, Some(&head_tree));
+        assert_eq!(reply.events, vec![make_event("bn-bbb", 2)]);
+
+        // Without the base tree the reply's head cannot be rebuilt.
+        assert!(merge_bundle(&sender, &reply, None).head_tree.is_none());
+    }
+
+    #[test]
+    fn rejects_tampered_and_truncated_bundles() {
+        let bundle = Bundle::create(&[make_event("bn-aaa", 1), make_event("bn-bbb", 2)], None);
+        let text = bundle.to_text().expect("render");
+
+        let tampered = text.replace("Item bn-aaa", "Item bn-zzz");
+        assert!(matches!(
+            Bundle::parse(&tampered),
+            Err(BundleError::Parse { line: 5, .. })
+        ));
+
+        let truncated: String = text.lines().take(5).map(|l| format!("{l}\n")).collect();
+        assert!(matches!(
+            Bundle::parse(&truncated),
+            Err(BundleError::CountMismatch {
+                expected: 2,
+                found: 1
+            })
+        ));
+
+        assert!(matches!(
+            Bundle::parse("# bones bundle v9\n"),
+            Err(BundleError::UnsupportedVersion(9))
+        ));
+        assert!(matches!(
+            Bundle::parse("hello\n"),
+            Err(BundleError::MissingHeader)
+        ));
+    }
+
+    #[test]
+    fn root_store_resolves_prefixes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = RootStore::new(dir.path());
+        let tree = ProllyTree::build(&[make_event("bn-aaa", 1)]);
+        let root = store.record(&tree).expect("record");
+        assert!(store.contains(&root));
+
+        let hex = root.to_string();
+        let resolved = store.resolve(&hex[..12]).expect("resolve");
+        assert_eq!(resolved.root.hash(), root);
+        assert!(matches!(
+            store.resolve(&hex[..4]),
+            Err(BundleError::UnknownRoot(_))
+        ));
+        assert!(matches!(
+            store.resolve("00000000"),
+            Err(BundleError::UnknownRoot(_))
+        ));
+    }
+
+    #[test]
+    fn root_store_prunes_least_recently_used_roots() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = RootStore::new(dir.path()).keep_last(2);
+        let record = |item: &str| {
+            let root = store
+                .record(&ProllyTree::build(&[make_event(item, 1)]))
+                .expect("record");
+            // Keep modification times apart on coarse-grained filesystems.
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            root
+        };
+
+        let first = record("bn-aaa");
+        let second = record("bn-bbb");
+        // Resolving the first root makes it the most recently used.
+        store.resolve(&first.to_string()).expect("resolve");
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let third = record("bn-ccc");
+
+        assert!(store.contains(&first));
+        assert!(!store.contains(&second));
+        assert!(store.contains(&third));
+        let stored = fs::read_dir(dir.path().join(ROOTS_DIR))
+            .expect("read roots")
+            .count();
+        assert_eq!(stored, 2);
+    }
+}
@@ -0,0 +1,468 @@
+//! Replication filters for partial replicas.
+//!
+//! An agent that only works on one goal does not need the whole backlog. A
+//! [`ReplicationFilter`] names the items a replica wants — goal subtrees,
+//! labels, or explicit item IDs — and [`ReplicationFilter::resolve`] turns it
+//! into an item set on a replica that has the full graph. The set also holds
+//! every item those items' events reference (parents, blockers, other link
+//! targets), transitively, so the partial projection never points at an item
+//! it does not have.
+//!
+//! Syncing a filtered replica is the ordinary protocol over trees restricted
+//! to the resolved set ([`ProllyIndex::restricted`]): events for other items
+//! are neither offered nor requested on either side.
+//!
+//! A replica that pulled through a filter records it in
+//! `.bones/replication.toml` ([`ReplicationFilter::save_marker`]), so
+//! `bn verify` and `bn triage` can say that the graph they see is partial.
+//!
+//! [`ProllyIndex::restricted`]: crate::sync::index::ProllyIndex::restricted
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use rusqlite::{Connection, params};
+use serde::{Deserialize, Serialize};
+
+use crate::db::query;
+use crate::event::data::EventData;
+use crate::event::parser::{
+    ParseError, ParsedLine, PartialParsedLine, parse_line, parse_line_partial,
+};
+use crate::event::{Event, EventType};
+use crate::graph::hierarchy::{HierarchyError, get_subtree_ids};
+use crate::shard::{ShardError, ShardManager};
+
+/// Partial-replica marker, relative to `.bones/`.
+pub const MARKER_PATH: &str = "replication.toml";
+
+const MARKER_HEADER: &str = "# Written by `bn sync --remote`: this replica only holds events for the\n\
+     # items matching this filter. `bn sync --remote <COMMAND> --full` pulls the rest.\n";
+
+// ---------------------------------------------------------------------------
+// Error types
+// ---------------------------------------------------------------------------
+
+/// Errors that can occur while resolving or persisting a filter.
+#[derive(Debug, thiserror::Error)]
+pub enum FilterError {
+    /// A goal or item named by the filter does not exist.
+    #[error("unknown item in replication filter: {0}")]
+    UnknownItem(String),
+
+    /// A projection query failed.
+    #[error("replication filter query failed: {0}")]
+    Db(#[from] HierarchyError),
+
+    /// A projection query failed.
+    #[error("replication filter query failed: {0}")]
+    Sql(#[from] rusqlite::Error),
+
+    /// The event shards could not be read.
+    #[error(transparent)]
+    Shard(#[from] ShardError),
+
+    /// I/O error on the shards or the marker file.
+    #[error("replication filter I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    /// An event of a selected item failed to parse.
+    #[error("failed to parse event at shard offset {offset}: {source}")]
+    Parse {
+        /// Absolute byte offset of the line in the concatenated shards.
+        offset: usize,
+        /// Underlying parse error.
+        source: ParseError,
+    },
+
+    /// The marker file is malformed.
+    #[error("invalid {MARKER_PATH}: {0}")]
+    InvalidMarker(String),
+}
+
+// ---------------------------------------------------------------------------
+// ReplicationFilter
+// ---------------------------------------------------------------------------
+
+/// Which items a partial replica holds. An item matches if it is in the
+/// subtree of any goal, carries any label, or is listed explicitly; an empty
+/// filter means the whole project.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplicationFilter {
+    /// Goals whose subtrees (the goal included) match.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub goals: Vec<String>,
+    /// Labels; items carrying any of them match.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Explicit item IDs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<String>,
+}
+
+impl ReplicationFilter {
+    /// Whether the filter selects the whole project.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.goals.is_empty() && self.labels.is_empty() && self.items.is_empty()
+    }
+
+    /// A filter matching everything either filter matches.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let merge = |a: &[String], b: &[String]| -> Vec<String> {
+            a.iter()
+                .chain(b)
+                .cloned()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect()
+        };
+        Self {
+            goals: merge(&self.goals, &other.goals),
+            labels: merge(&self.labels, &other.labels),
+            items: merge(&self.items, &other.items),
+        }
+    }
+
+    /// Resolve the filter against a complete replica's projection and shards.
+    ///
+    /// Returns the matching items plus, transitively, every item their events
+    /// reference: parents, blockers, and other link targets. The shards are
+    /// read once; the closure is walked in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FilterError::UnknownItem`] if a goal or item does not exist,
+    /// or another [`FilterError`] if the projection or shards cannot be read.
+    pub fn resolve(
+        &self,
+        conn: &Connection,
+        shards: &ShardManager,
+    ) -> Result<BTreeSet<String>, FilterError> {
+        let mut items = self.matching_items(conn)?;
+        let graph = reference_graph(shards)?;
+        let mut frontier: Vec<String> = items.iter().cloned().collect();
+        while let Some(item) = frontier.pop() {
+            for target in graph.get(&item).into_iter().flatten() {
+                if items.insert(target.clone()) {
+                    frontier.push(target.clone());
+                }
+            }
+        }
+        Ok(items)
+    }
+
+    fn matching_items(&self, conn: &Connection) -> Result<BTreeSet<String>, FilterError> {
+        let mut items = BTreeSet::new();
+        for goal in &self.goals {
+            require_item(conn, goal)?;
+            items.extend(get_subtree_ids(conn, goal)?);
+        }
+        if !self.labels.is_empty() {
+            let mut stmt = conn.prepare("SELECT item_id FROM item_labels WHERE label = ?1")?;
+            for label in &self.labels {
+                for id in stmt.query_map(params![label], |row| row.get::<_, String>(0))? {
+                    items.insert(id?);
+                }
+            }
+        }
+        for item in &self.items {
+            require_item(conn, item)?;
+            items.insert(item.clone());
+        }
+        Ok(items)
+    }
+
+    // -----------------------------------------------------------------------
+    // Marker
+    // -----------------------------------------------------------------------
+
+    /// The filter this replica was pulled through, if it is partial.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FilterError`] if the marker exists but cannot be read.
+    pub fn load_marker(bones_dir: &Path) -> Result<Option<Self>, FilterError> {
+        let text = match fs::read_to_string(bones_dir.join(MARKER_PATH)) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let filter: Self =
+            toml::from_str(&text).map_err(|e| FilterError::InvalidMarker(e.to_string()))?;
+        Ok((!filter.is_empty()).then_some(filter))
+    }
+
+    /// Mark the replica as partial, holding the items this filter matches.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FilterError::Io`] if the marker cannot be written.
+    pub fn save_marker(&self, bones_dir: &Path) -> Result<(), FilterError> {
+        let body = toml::to_string(self).map_err(|e| FilterError::InvalidMarker(e.to_string()))?;
+        fs::write(
+            bones_dir.join(MARKER_PATH),
+            format!("{MARKER_HEADER}{body}"),
+        )?;
+        Ok(())
+    }
+
+    /// Mark the replica as complete.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FilterError::Io`] if the marker exists but cannot be removed.
+    pub fn clear_marker(bones_dir: &Path) -> Result<(), FilterError> {
+        match fs::remove_file(bones_dir.join(MARKER_PATH)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for ReplicationFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("all items");
+        }
+        let parts: Vec<String> = self
+            .goals
+            .iter()
+            .map(|g| format!("goal {g}"))
+            .chain(self.labels.iter().map(|l| format!("label {l}")))
+            .chain(self.items.iter().map(|i| format!("item {i}")))
+            .collect();
+        f.write_str(&parts.join(", "))
+    }
+}
+
+fn require_item(conn: &Connection, item_id: &str) -> Result<(), FilterError> {
+    if query::item_exists(conn, item_id).map_err(HierarchyError::from)? {
+        Ok(())
+    } else {
+        Err(FilterError::UnknownItem(item_id.to_string()))
+    }
+}
+
+/// Every item's references, read from the shards in one pass.
+fn reference_graph(shards: &ShardManager) -> Result<HashMap<String, Vec<String>>, FilterError> {
+    let mut graph: HashMap<String, Vec<String>> = HashMap::new();
+    for line in shards.replay_lines()? {
+        let (offset, line) = line?;
+        match parse_line_partial(&line) {
+            Ok(PartialParsedLine::Event(partial))
+                if matches!(
+                    partial.event_type,
+                    EventType::Create | EventType::Update | EventType::Link
+                ) => {}
+            Ok(_) => continue,
+            Err(source) => return Err(FilterError::Parse { offset, source }),
+        }
+        match parse_line(line.trim_end_matches(['\n', '\r'])) {
+            Ok(ParsedLine::Event(event)) => {
+                let referenced = references(&event);
+                if !referenced.is_empty() {
+                    graph
+                        .entry(event.item_id.to_string())
+                        .or_default()
+                        .extend(referenced);
+                }
+            }
+            Ok(_) => {}
+            Err(source) => return Err(FilterError::Parse { offset, source }),
+        }
+    }
+    Ok(graph)
+}
+
+/// Items an event makes its own item point at in the projection.
+fn references(event: &Event) -> Vec<String> {
+    match &event.data {
+        EventData::Create(data) => data.parent.iter().cloned().collect(),
+        EventData::Update(data) if data.field == "parent" => data
+            .value
+            .as_str()
+            .filter(|parent| !parent.is_empty())
+            .map(str::to_string)
+            .into_iter()
+            .collect(),
+        EventData::Link(data) => vec![data.target.clone()],
+        _ => Vec::new(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrations;
+    use crate::db::project::{Projector, ensure_tracking_table};
+    use crate::event::EventType;
+    use crate::event::data::{CreateData, LinkData};
+    use crate::event::writer::{write_event, write_line};
+    use crate::model::item::{Kind, Urgency};
+    use crate::model::item_id::ItemId;
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    fn create(item: &str, ts: i64, kind: Kind, parent: Option<&str>, labels: &[&str]) -> Event {
+        let mut event = Event {
+            wall_ts_us: ts,
+            agent: "alice".to_string(),
+            itc: "itc:AQ".to_string(),
+            parents: vec![],
+            event_type: EventType::Create,
+            item_id: ItemId::new_unchecked(item),
+            data: EventData::Create(CreateData {
+                title: format!("Item {item}"),
+                kind,
+                size: None,
+                urgency: Urgency::Default,
+                labels: labels.iter().map(ToString::to_string).collect(),
+                parent: parent.map(str::to_string),
+                causation: None,
+                description: None,
+                extra: BTreeMap::new(),
+            }),
+            event_hash: String::new(),
+            signature: None,
+        };
+        write_event(&mut event).expect("hash");
+        event
+    }
+
+    fn link(item: &str, target: &str, ts: i64) -> Event {
+        let mut event = Event {
+            wall_ts_us: ts,
+            agent: "alice".to_string(),
+            itc: "itc:AQ".to_string(),
+            parents: vec![],
+            event_type: EventType::Link,
+            item_id: ItemId::new_unchecked(item),
+            data: EventData::Link(LinkData {
+                target: target.to_string(),
+                link_type: "blocks".to_string(),
+                extra: BTreeMap::new(),
+            }),
+            event_hash: String::new(),
+            signature: None,
+        };
+        write_event(&mut event).expect("hash");
+        event
+    }
+
+    /// Goal bn-g1 holds bn-a1 (labelled `api`), which is blocked by bn-b1,
+    /// itself blocked by bn-c1 under goal bn-g2. bn-z1 is unrelated.
+    fn project() -> (tempfile::TempDir, Connection, ShardManager) {
+        let events = vec![
+            create("bn-g1", 1, Kind::Goal, None, &[]),
+            create("bn-g2", 2, Kind::Goal, None, &[]),
+            create("bn-a1", 3, Kind::Task, Some("bn-g1"), &["api"]),
+            create("bn-b1", 4, Kind::Task, None, &[]),
+            create("bn-c1", 5, Kind::Task, Some("bn-g2"), &[]),
+            create("bn-z1", 6, Kind::Task, None, &["ui"]),
+            link("bn-a1", "bn-b1", 7),
+            link("bn-b1", "bn-c1", 8),
+        ];
+        let dir = tempfile::tempdir().expect("tempdir");
+        let bones_dir = dir.path().join(".bones");
+        let shards = ShardManager::new(&bones_dir);
+        shards.init().expect("init");
+        let mut conn = Connection::open_in_memory().expect("db");
+        migrations::migrate(&mut conn).expect("migrate");
+        ensure_tracking_table(&conn).expect("tracking");
+        let projector = Projector::new(&conn);
+        for event in &events {
+            shards
+                .append(
+                    &write_line(event).expect("line"),
+                    false,
+                    Duration::from_secs(1),
+                )
+                .expect("append");
+            projector.project_event(event).expect("project");
+        }
+        (dir, conn, shards)
+    }
+
+    fn ids(items: &[&str]) -> BTreeSet<String> {
+        items.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn goal_filter_pulls_transitive_blockers_and_their_parents() {
+        let (_dir, conn, shards) = project();
+        let filter = ReplicationFilter {
+            goals: vec!["bn-g1".to_string()],
+            ..ReplicationFilter::default()
+        };
+        assert_eq!(
+            filter.resolve(&conn, &shards).expect("resolve"),
+            ids(&["bn-g1", "bn-a1", "bn-b1", "bn-c1", "bn-g2"])
+        );
+    }
+
+    #[test]
+    fn label_and_item_filters_match_their_items() {
+        let (_dir, conn, shards) = project();
+        let filter = ReplicationFilter {
+            labels: vec!["ui".to_string()],
+            items: vec!["bn-c1".to_string()],
+            ..ReplicationFilter::default()
+        };
+        assert_eq!(
+            filter.resolve(&conn, &shards).expect("resolve"),
+            ids(&["bn-z1", "bn-c1", "bn-g2"])
+        );
+
+        let unknown = ReplicationFilter {
+            goals: vec!["bn-nope".to_string()],
+            ..ReplicationFilter::default()
+        };
+        assert!(matches!(
+            unknown.resolve(&conn, &shards),
+            Err(FilterError::UnknownItem(id)) if id == "bn-nope"
+        ));
+    }
+
+    #[test]
+    fn marker_round_trips_and_clears() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert_eq!(
+            ReplicationFilter::load_marker(dir.path()).expect("load"),
+            None
+        );
+
+        let filter = ReplicationFilter {
+            goals: vec!["bn-g1".to_string()],
+            labels: vec!["api".to_string()],
+            items: vec![],
+        };
+        filter.save_marker(dir.path()).expect("save");
+        assert_eq!(
+            ReplicationFilter::load_marker(dir.path()).expect("load"),
+            Some(filter.clone())
+        );
+        assert_eq!(filter.to_string(), "goal bn-g1, label api");
+
+        let wider = filter.union(&ReplicationFilter {
+            goals: vec!["bn-g1".to_string(), "bn-g2".to_string()],
+            ..ReplicationFilter::default()
+        });
+        assert_eq!(wider.goals, vec!["bn-g1", "bn-g2"]);
+        assert_eq!(wider.labels, vec!["api"]);
+
+        ReplicationFilter::clear_marker(dir.path()).expect("clear");
+        assert_eq!(
+            ReplicationFilter::load_marker(dir.path()).expect("load"),
+            None
+        );
+    }
+}
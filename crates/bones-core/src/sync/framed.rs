@@ -0,0 +1,580 @@
+//! Framed byte-stream transport for the sync protocol.
+//!
+//! [`FramedTransport`] implements [`SyncTransport`] over any `Read`/`Write`
+//! pair — pipes to a child process, stdin/stdout of `bn sync-serve --stdio`
+//! under SSH, or a socket. It works the way `git-upload-pack` does: the
+//! client spawns the server command and talks to it over its standard
+//! streams.
+//!
+//! # Wire format
+//!
+//! Every message is one frame:
+//!
+//! ```text
+//! kind: u8 | length: u32 (big-endian) | payload: [u8; length]
+//! ```
+//!
+//! | Kind | Payload |
+//! |------|---------|
+//! | `H` hello | `bones-sync/<version>` |
+//! | `F` replication filter | JSON [`ReplicationFilter`]; `{}` for everything |
+//! | `I` item IDs | the filter's resolved items, joined by `\n` |
+//! | `R` root hash | 32 raw bytes |
+//! | `N` node hashes | one tree level: 32 raw bytes per node, concatenated |
+//! | `L` event hash list | hashes joined by `\n` |
+//! | `E` events | TSJSON lines, exactly as in a shard |
+//! | `X` error | UTF-8 message; the sender gives up after sending it |
+//!
+//! Both sides open with a hello frame (client first) and refuse to continue
+//! if the versions differ. `bn sync --remote` then sends a filter frame; for
+//! a non-empty filter the server answers with the resolved item IDs and both
+//! sides sync only those items' events. Event frames are re-parsed on
+//! receipt, so every event hash is verified before the events reach the
+//! caller.
+
+use std::collections::BTreeSet;
+use std::io::{self, BufRead, Read, Write};
+
+use crate::event::Event;
+use crate::event::parser::{ParseError, ParsedLine, parse_line};
+use crate::event::writer::{WriteError, write_line};
+use crate::sync::filter::ReplicationFilter;
+use crate::sync::prolly::Hash;
+use crate::sync::protocol::SyncTransport;
+
+/// Version of the framing carried in the hello frame. Version 2 added the
+/// level-by-level tree walk (`N` frames); version 3 the replication filter
+/// exchange (`F` and `I` frames).
+pub const PROTOCOL_VERSION: u32 = 3;
+
+/// Protocol name carried in the hello frame.
+const PROTOCOL_NAME: &str = "bones-sync";
+
+/// Largest accepted frame payload (1 GiB).
+pub const MAX_FRAME_LEN: u32 = 1 << 30;
+
+const KIND_HELLO: u8 = b'H';
+const KIND_FILTER: u8 = b'F';
+const KIND_ITEMS: u8 = b'I';
+const KIND_ROOT: u8 = b'R';
+const KIND_NODES: u8 = b'N';
+const KIND_HASHES: u8 = b'L';
+const KIND_EVENTS: u8 = b'E';
+const KIND_ERROR: u8 = b'X';
+
+// ---------------------------------------------------------------------------
+// Error types
+// ---------------------------------------------------------------------------
+
+/// Errors that can occur on a framed sync connection.
+#[derive(Debug, thiserror::Error)]
+pub enum FramedError {
+    /// I/O error on the underlying stream.
+    #[error("sync connection I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    /// The peer closed the connection mid-protocol.
+    #[error("sync peer closed the connection")]
+    Closed,
+
+    /// The peer did not open with a bones hello frame (for example, a shell
+    /// banner was printed on stdout).
+    #[error("sync peer did not speak the bones sync protocol: {0}")]
+    BadHandshake(String),
+
+    /// The peer speaks a different protocol version.
+    #[error("sync protocol version mismatch: ours v{ours}, theirs v{theirs}")]
+    VersionMismatch {
+        /// Our version.
+        ours: u32,
+        /// The peer's version.
+        theirs: u32,
+    },
+
+    /// A frame of one kind arrived when another was expected.
+    #[error("unexpected sync frame '{found}' (expected '{expected}')")]
+    UnexpectedFrame {
+        /// Expected frame kind.
+        expected: char,
+        /// Kind that arrived.
+        found: char,
+    },
+
+    /// A frame exceeds [`MAX_FRAME_LEN`].
+    #[error("sync frame of {0} bytes exceeds the limit")]
+    FrameTooLarge(u64),
+
+    /// A frame payload is malformed.
+    #[error("invalid sync frame payload: {0}")]
+    InvalidPayload(String),
+
+    /// An event line in an events frame failed to parse or verify.
+    #[error("received invalid event: {0}")]
+    Parse(#[from] ParseError),
+
+    /// An outgoing event could not be serialized.
+    #[error("failed to serialize event: {0}")]
+    Write(#[from] WriteError),
+
+    /// The peer reported an error and gave up.
+    #[error("remote: {0}")]
+    Remote(String),
+}
+
+// ---------------------------------------------------------------------------
+// Transport
+// ---------------------------------------------------------------------------
+
+/// [`SyncTransport`] over a framed byte stream.
+#[derive(Debug)]
+pub struct FramedTransport<R, W> {
+    reader: R,
+    writer: W,
+    bytes_sent: u64,
+    bytes_received: u64,
+}
+
+impl<R: BufRead, W: Write> FramedTransport<R, W> {
+    /// Wrap a reader/writer pair. Call [`Self::handshake_client`] or
+    /// [`Self::handshake_server`] before syncing.
+    pub const fn new(reader: R, writer: W) -> Self {
+        Self {
+            reader,
+            writer,
+            bytes_sent: 0,
+            bytes_received: 0,
+        }
+    }
+
+    /// Total frame bytes written so far, including headers.
+    #[must_use]
+    pub const fn bytes_sent(&self) -> u64 {
+        self.bytes_sent
+    }
+
+    /// Total frame bytes read so far, including headers.
+    #[must_use]
+    pub const fn bytes_received(&self) -> u64 {
+        self.bytes_received
+    }
+
+    /// Open the connection as the initiating side.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FramedError`] if the peer does not answer with a matching
+    /// hello frame.
+    pub fn handshake_client(&mut self) -> Result<(), FramedError> {
+        match self.send_hello() {
+            // A peer that is not a bones server may exit before reading our
+            // hello; whatever it printed explains the failure better.
+            Err(FramedError::Io(e)) if e.kind() == io::ErrorKind::BrokenPipe => {
+                Err(self.expect_hello().err().unwrap_or(FramedError::Io(e)))
+            }
+            sent => sent.and_then(|()| self.expect_hello()),
+        }
+    }
+
+    /// Open the connection as the serving side. On a version mismatch an
+    /// error frame is sent to the client before returning.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FramedError`] if the client does not open with a matching
+    /// hello frame.
+    pub fn handshake_server(&mut self) -> Result<(), FramedError> {
+        match self.expect_hello() {
+            Ok(()) => self.send_hello(),
+            Err(e) => {
+                if matches!(e, FramedError::VersionMismatch { .. }) {
+                    let _ = self.send_error(&e.to_string());
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Tell the peer this side failed and is giving up.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FramedError::Io`] if the frame cannot be written.
+    pub fn send_error(&mut self, message: &str) -> Result<(), FramedError> {
+        self.write_frame(KIND_ERROR, message.as_bytes())
+    }
+
+    /// Ask for the items matching `filter` (client side, after the
+    /// handshake).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FramedError::Io`] if the frame cannot be written.
+    pub fn send_filter(&mut self, filter: &ReplicationFilter) -> Result<(), FramedError> {
+        let payload =
+            serde_json::to_vec(filter).map_err(|e| FramedError::InvalidPayload(e.to_string()))?;
+        self.write_frame(KIND_FILTER, &payload)
+    }
+
+    /// Receive the client's replication filter.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FramedError`] if the frame is missing or malformed.
+    pub fn recv_filter(&mut self) -> Result<ReplicationFilter, FramedError> {
+        let payload = self.read_frame(KIND_FILTER)?;
+        serde_json::from_slice(&payload).map_err(|e| FramedError::InvalidPayload(e.to_string()))
+    }
+
+    /// Send the items a filter resolved to (server side).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FramedError::Io`] if the frame cannot be written.
+    pub fn send_item_ids(&mut self, items: &BTreeSet<String>) -> Result<(), FramedError> {
+        let payload: Vec<&str> = items.iter().map(String::as_str).collect();
+        self.write_frame(KIND_ITEMS, payload.join("\n").as_bytes())
+    }
+
+    /// Receive the items the server resolved the filter to.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FramedError`] if the frame is missing or malformed, or the
+    /// server could not resolve the filter.
+    pub fn recv_item_ids(&mut self) -> Result<BTreeSet<String>, FramedError> {
+        let text = self.read_text_frame(KIND_ITEMS)?;
+        Ok(text
+            .split('\n')
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn send_hello(&mut self) -> Result<(), FramedError> {
+        let hello = format!("{PROTOCOL_NAME}/{PROTOCOL_VERSION}");
+        self.write_frame(KIND_HELLO, hello.as_bytes())
+    }
+
+    fn expect_hello(&mut self) -> Result<(), FramedError> {
+        let payload = self.read_frame(KIND_HELLO).map_err(|e| match e {
+            FramedError::UnexpectedFrame { found, .. } => {
+                FramedError::BadHandshake(format!("first byte was {found:?}"))
+            }
+            FramedError::FrameTooLarge(_) => {
+                FramedError::BadHandshake("oversized first frame".to_string())
+            }
+            other => other,
+        })?;
+        let text = String::from_utf8_lossy(&payload);
+        let theirs = text
+            .strip_prefix(PROTOCOL_NAME)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .and_then(|v| v.parse::<u32>().ok())
+            .ok_or_else(|| FramedError::BadHandshake(text.to_string()))?;
+        if theirs != PROTOCOL_VERSION {
+            return Err(FramedError::VersionMismatch {
+                ours: PROTOCOL_VERSION,
+                theirs,
+            });
+        }
+        Ok(())
+    }
+
+    fn write_frame(&mut self, kind: u8, payload: &[u8]) -> Result<(), FramedError> {
+        let len = u32::try_from(payload.len())
+            .ok()
+            .filter(|len| *len <= MAX_FRAME_LEN)
+            .ok_or(FramedError::FrameTooLarge(payload.len() as u64))?;
+        self.writer.write_all(&[kind])?;
+        self.writer.write_all(&len.to_be_bytes())?;
+        self.writer.write_all(payload)?;
+        self.writer.flush()?;
+        self.bytes_sent += 5 + u64::from(len);
+        Ok(())
+    }
+
+    fn read_frame(&mut self, expected: u8) -> Result<Vec<u8>, FramedError> {
+        let mut header = [0u8; 5];
+        self.reader.read_exact(&mut header).map_err(|e| {
+            if e.kind() == io::ErrorKind::UnexpectedEof {
+                FramedError::Closed
+            } else {
+                FramedError::Io(e)
+            }
+        })?;
+        let kind = header[0];
+        let len = u32::from_be_bytes([header[1], header[2], header[3], header[4]]);
+        if kind != expected && kind != KIND_ERROR {
+            return Err(FramedError::UnexpectedFrame {
+                expected: char::from(expected),
+                found: char::from(kind),
+            });
+        }
+        if len > MAX_FRAME_LEN {
+            return Err(FramedError::FrameTooLarge(u64::from(len)));
+        }
+
+        let mut payload = Vec::new();
+        (&mut self.reader)
+            .take(u64::from(len))
+            .read_to_end(&mut payload)?;
+        if payload.len() as u64 != u64::from(len) {
+            return Err(FramedError::Closed);
+        }
+        self.bytes_received += 5 + u64::from(len);
+
+        if kind == KIND_ERROR {
+            return Err(FramedError::Remote(
+                String::from_utf8_lossy(&payload).into_owned(),
+            ));
+        }
+        Ok(payload)
+    }
+
+    fn read_text_frame(&mut self, expected: u8) -> Result<String, FramedError> {
+        String::from_utf8(self.read_frame(expected)?)
+            .map_err(|e| FramedError::InvalidPayload(e.to_string()))
+    }
+}
+
+impl<R: BufRead, W: Write> SyncTransport for FramedTransport<R, W> {
+    type Error = FramedError;
+
+    fn send_hash(&mut self, hash: &Hash) -> Result<(), Self::Error> {
+        self.write_frame(KIND_ROOT, &hash.0)
+    }
+
+    fn recv_hash(&mut self) -> Result<Hash, Self::Error> {
+        let payload = self.read_frame(KIND_ROOT)?;
+        let bytes: [u8; 32] = payload
+            .try_into()
+            .map_err(|_| FramedError::InvalidPayload("root hash must be 32 bytes".into()))?;
+        Ok(Hash(bytes))
+    }
+
+    fn send_node_hashes(&mut self, hashes: &[Hash]) -> Result<(), Self::Error> {
+        let payload: Vec<u8> = hashes.iter().flat_map(|h| h.0).collect();
+        self.write_frame(KIND_NODES, &payload)
+    }
+
+    fn recv_node_hashes(&mut self) -> Result<Vec<Hash>, Self::Error> {
+        let payload = self.read_frame(KIND_NODES)?;
+        if !payload.len().is_multiple_of(32) {
+            return Err(FramedError::InvalidPayload(
+                "node hash list must be a multiple of 32 bytes".into(),
+            ));
+        }
+        Ok(payload
+            .chunks_exact(32)
+            .map(|chunk| {
+                let mut bytes = [0u8; 32];
+                bytes.copy_from_slice(chunk);
+                Hash(bytes)
+            })
+            .collect())
+    }
+
+    fn send_event_hashes(&mut self, hashes: &[String]) -> Result<(), Self::Error> {
+        self.write_frame(KIND_HASHES, hashes.join("\n").as_bytes())
+    }
+
+    fn recv_event_hashes(&mut self) -> Result<Vec<String>, Self::Error> {
+        let text = self.read_text_frame(KIND_HASHES)?;
+        Ok(text
+            .split('\n')
+            .filter(|h| !h.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn send_events(&mut self, events: &[Event]) -> Result<(), Self::Error> {
+        let mut payload = String::new();
+        for event in events {
+            payload.push_str(&write_line(event)?);
+        }
+        self.write_frame(KIND_EVENTS, payload.as_bytes())
+    }
+
+    fn recv_events(&mut self) -> Result<Vec<Event>, Self::Error> {
+        let text = self.read_text_frame(KIND_EVENTS)?;
+        let mut events = Vec::new();
+        for line in text.lines() {
+            match parse_line(line)? {
+                ParsedLine::Event(event) => events.push(*event),
+                ParsedLine::Comment(_) | ParsedLine::Blank => {}
+            }
+        }
+        Ok(events)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::EventType;
+    use crate::event::data::{CreateData, EventData};
+    use crate::event::writer::compute_event_hash;
+    use crate::model::item::{Kind, Urgency};
+    use crate::model::item_id::ItemId;
+    use crate::sync::protocol::{serve_sync, sync};
+    use std::collections::BTreeMap;
+    use std::io::BufReader;
+
+    fn make_event(item: &str, ts: i64) -> Event {
+        let mut event = Event {
+            wall_ts_us: ts,
+            agent: "alice".to_string(),
+            itc: "itc:AQ".to_string(),
+            parents: vec![],
+            event_type: EventType::Create,
+            item_id: ItemId::new_unchecked(item),
+            data: EventData::Create(CreateData {
+                title: format!("Item {item}"),
+                kind: Kind::Task,
+                size: None,
+                urgency: Urgency::Default,
+                labels: vec![],
+                parent: None,
+                causation: None,
+                description: None,
+                extra: BTreeMap::new(),
+            }),
+            event_hash: String::new(),
+            signature: None,
+        };
+        event.event_hash = compute_event_hash(&event).expect("hash");
+        event
+    }
+
+    type PipeTransport = FramedTransport<BufReader<io::PipeReader>, io::PipeWriter>;
+
+    /// Two transports connected by OS pipes, like a client and the child
+    /// process it spawned.
+    fn pipe_pair() -> (PipeTransport, PipeTransport) {
+        let (client_rx, server_tx) = io::pipe().expect("pipe");
+        let (server_rx, client_tx) = io::pipe().expect("pipe");
+        (
+            FramedTransport::new(BufReader::new(client_rx), client_tx),
+            FramedTransport::new(BufReader::new(server_rx), server_tx),
+        )
+    }
+
+    #[test]
+    fn syncs_over_pipes() {
+        let shared = make_event("bn-aaa", 1);
+        let client_only = make_event("bn-bbb", 2);
+        let server_only = make_event("bn-ccc", 3);
+        let client_events = vec![shared.clone(), client_only.clone()];
+        let server_events = vec![shared, server_only.clone()];
+
+        let (mut client, mut server) = pipe_pair();
+        let server_thread = std::thread::spawn(move || {
+            server.handshake_server().expect("server handshake");
+            serve_sync(&server_events, &mut server).expect("serve")
+        });
+
+        client.handshake_client().expect("client handshake");
+        let (received, report) = sync(&client_events, &mut client).expect("sync");
+        let (served, _) = server_thread.join().expect("server thread");
+
+        assert_eq!(received, vec![server_only]);
+        assert_eq!(served, vec![client_only]);
+        assert_eq!(report.rounds, 4);
+        assert!(client.bytes_sent() > 0 && client.bytes_received() > 0);
+    }
+
+    #[test]
+    fn exchanges_filters_and_item_ids() {
+        let filter = ReplicationFilter {
+            goals: vec!["bn-goal".to_string()],
+            labels: vec!["api".to_string()],
+            items: vec![],
+        };
+        let items: BTreeSet<String> = ["bn-goal", "bn-task"].map(String::from).into();
+
+        let (mut client, mut server) = pipe_pair();
+        let expected = items.clone();
+        let server_thread = std::thread::spawn(move || {
+            let filter = server.recv_filter().expect("filter");
+            server.send_item_ids(&expected).expect("items");
+            filter
+        });
+        client.send_filter(&filter).expect("filter");
+        assert_eq!(client.recv_item_ids().expect("items"), items);
+        assert_eq!(server_thread.join().expect("server thread"), filter);
+    }
+
+    #[test]
+    fn remote_errors_and_version_mismatch_surface() {
+        let (mut client, mut server) = pipe_pair();
+        let server_thread = std::thread::spawn(move || {
+            server.expect_hello().expect("hello");
+            server.send_error("not a bones project").expect("send");
+        });
+        client.send_hello().expect("hello");
+        let err = client.expect_hello().expect_err("remote error");
+        assert!(matches!(err, FramedError::Remote(ref msg) if msg == "not a bones project"));
+        server_thread.join().expect("server thread");
+
+        let (mut client, mut server) = pipe_pair();
+        let server_thread = std::thread::spawn(move || server.handshake_server());
+        client
+            .write_frame(KIND_HELLO, b"bones-sync/99")
+            .expect("write");
+        assert!(matches!(
+            server_thread.join().expect("server thread"),
+            Err(FramedError::VersionMismatch { theirs: 99, .. })
+        ));
+        assert!(matches!(client.expect_hello(), Err(FramedError::Remote(_))));
+    }
+
+    #[test]
+    fn rejects_non_protocol_output() {
+        let mut transport = FramedTransport::new(&b"Welcome to host!\n"[..], Vec::new());
+        assert!(matches!(
+            transport.handshake_client(),
+            Err(FramedError::BadHandshake(_))
+        ));
+    }
+
+    /// Writer for a peer that exited without reading anything.
+    struct ClosedPipe;
+
+    impl Write for ClosedPipe {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+    }
+
+    #[test]
+    fn reports_peer_output_when_hello_hits_a_closed_pipe() {
+        let mut transport = FramedTransport::new(&b"not-bones\n"[..], ClosedPipe);
+        assert!(matches!(
+            transport.handshake_client(),
+            Err(FramedError::BadHandshake(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_tampered_events() {
+        let event = make_event("bn-aaa", 1);
+        let mut sender = FramedTransport::new(&b""[..], Vec::new());
+        sender.send_events(&[event]).expect("send");
+        let wire = String::from_utf8(sender.writer).expect("utf8");
+        let tampered = wire.replace("Item bn-aaa", "Item bn-zzz");
+
+        let mut receiver = FramedTransport::new(tampered.as_bytes(), Vec::new());
+        assert!(matches!(
+            receiver.recv_events(),
+            Err(FramedError::Parse(ParseError::HashMismatch { .. }))
+        ));
+    }
+}
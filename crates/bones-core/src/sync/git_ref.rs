@@ -0,0 +1,702 @@
+//! Event storage on a dedicated git ref.
+//!
+//! With `[sync] events = "ref"` in `.bones/config.toml`, event shards are not
+//! committed on branches. Each replica instead publishes its event log as a
+//! commit under [`EVENTS_REF`], much like `git notes` keeps notes under
+//! `refs/notes/*`. The ref is fetched and pushed on its own, so feature-branch
+//! diffs never carry `.events` changes and no merge driver is involved.
+//!
+//! # Layout
+//!
+//! The commit's tree holds one blob per month, named like a shard
+//! (`YYYY-MM.events`, by event wall-clock time). Each blob is a shard header
+//! followed by that month's events in canonical merge order, so two replicas
+//! holding the same events write identical trees.
+//!
+//! # Merging
+//!
+//! [`merge_into_ref`] unions the local shards, the local ref, and a fetched
+//! remote ref through [`merge_event_sets_with_policy`], so remote events the
+//! project's [`Policy`] refuses never reach the local ref. When the remote
+//! ref has moved independently the new commit takes it as a second parent,
+//! so pushing it is always a fast-forward.
+//!
+//! Everything goes through the `git` command line; no git library is linked.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write as _;
+use std::io::{self, Write as _};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use chrono::{DateTime, Datelike as _};
+
+use crate::event::Event;
+use crate::event::parser::{ParseError, parse_lines};
+use crate::event::writer::{WriteError, shard_header, write_line};
+use crate::policy::{Policy, Rejection};
+use crate::sync::merge::{merge_event_sets, merge_event_sets_with_policy};
+
+/// Ref holding this replica's event log.
+pub const EVENTS_REF: &str = "refs/bones/events";
+
+/// Identity recorded on event-log commits, which are written by bones
+/// rather than by a person.
+const COMMIT_IDENTITY: (&str, &str) = ("bones", "bones@localhost");
+
+/// Ref where the event log fetched from `remote` is kept.
+#[must_use]
+pub fn remote_tracking_ref(remote: &str) -> String {
+    format!("refs/bones/remotes/{remote}/events")
+}
+
+// ---------------------------------------------------------------------------
+// Error types
+// ---------------------------------------------------------------------------
+
+/// Errors that can occur while reading or writing the event ref.
+#[derive(Debug, thiserror::Error)]
+pub enum GitRefError {
+    /// `git` could not be started.
+    #[error("failed to run git: {0}")]
+    Spawn(#[from] io::Error),
+
+    /// A `git` command exited unsuccessfully.
+    #[error("`git {command}` failed: {stderr}")]
+    Git {
+        /// Subcommand and arguments.
+        command: String,
+        /// Trimmed standard error.
+        stderr: String,
+    },
+
+    /// A blob on the ref holds a malformed event line.
+    #[error("{commit}:{path} line {line}: {source}")]
+    Parse {
+        /// Commit being read.
+        commit: String,
+        /// Blob path within the commit's tree.
+        path: String,
+        /// 1-based line number within the blob.
+        line: usize,
+        /// Underlying parse error.
+        source: ParseError,
+    },
+
+    /// An event could not be serialized.
+    #[error("failed to serialize event: {0}")]
+    Write(#[from] WriteError),
+}
+
+// ---------------------------------------------------------------------------
+// GitRefStore
+// ---------------------------------------------------------------------------
+
+/// Reads and writes event-log commits in one git repository.
+#[derive(Debug, Clone)]
+pub struct GitRefStore {
+    repo: PathBuf,
+}
+
+impl GitRefStore {
+    /// Store for the repository containing `repo`.
+    pub fn new(repo: impl Into<PathBuf>) -> Self {
+        Self { repo: repo.into() }
+    }
+
+    /// Repository directory.
+    #[must_use]
+    pub fn repo(&self) -> &Path {
+        &self.repo
+    }
+
+    /// Commit `refname` points at, or `None` if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitRefError`] if git cannot be run.
+    pub fn resolve(&self, refname: &str) -> Result<Option<String>, GitRefError> {
+        let spec = format!("{refname}^{{commit}}");
+        let output = self.command(&["rev-parse", "--verify", "--quiet", &spec])?;
+        if !output.status.success() {
+            return Ok(None);
+        }
+        Ok(Some(
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ))
+    }
+
+    /// Every event stored in `commit`, in canonical order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitRefError`] if the tree cannot be read or a blob holds a
+    /// malformed line.
+    pub fn read_events(&self, commit: &str) -> Result<Vec<Event>, GitRefError> {
+        let listing = self.git(&["ls-tree", "-z", commit], None)?;
+        self.read_listed_events(commit, &listing)
+    }
+
+    /// Every event in the `.events` shards under `dir` at revision `rev`,
+    /// in canonical order.
+    ///
+    /// This reads shards committed on ordinary branches, e.g. `.bones/events`
+    /// at `main`. `dir` is relative to the store's directory; a revision
+    /// without it yields no events.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitRefError`] if `rev` does not name a commit or a shard
+    /// holds a malformed line.
+    pub fn read_shards_at(&self, rev: &str, dir: &str) -> Result<Vec<Event>, GitRefError> {
+        let commit = self.resolve(rev)?.ok_or_else(|| GitRefError::Git {
+            command: format!("rev-parse {rev}"),
+            stderr: format!("unknown revision '{rev}'"),
+        })?;
+        let pathspec = format!("{}/", dir.trim_end_matches('/'));
+        let listing = self.git(&["ls-tree", "-r", "-z", &commit, "--", &pathspec], None)?;
+        self.read_listed_events(&commit, &listing)
+    }
+
+    /// Best common ancestor of two revisions, or `None` if they share no
+    /// history.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitRefError`] if git cannot be run.
+    pub fn merge_base(&self, a: &str, b: &str) -> Result<Option<String>, GitRefError> {
+        let output = self.command(&["merge-base", a, b])?;
+        if !output.status.success() {
+            return Ok(None);
+        }
+        Ok(Some(
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ))
+    }
+
+    /// Parse the `.events` blobs named in `ls-tree -z` output.
+    fn read_listed_events(&self, commit: &str, listing: &str) -> Result<Vec<Event>, GitRefError> {
+        let mut events = Vec::new();
+        for entry in listing.split('\0').filter(|e| !e.is_empty()) {
+            let Some((meta, path)) = entry.split_once('\t') else {
+                continue;
+            };
+            if !path.ends_with(".events") {
+                continue;
+            }
+            let mut fields = meta.split_whitespace();
+            let (Some(_mode), Some("blob"), Some(oid)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+            let content = self.git(&["cat-file", "blob", oid], None)?;
+            let parsed = parse_lines(&content).map_err(|(line, source)| GitRefError::Parse {
+                commit: commit.to_string(),
+                path: path.to_string(),
+                line,
+                source,
+            })?;
+            events.extend(parsed);
+        }
+        Ok(merge_event_sets(&events, &[]).events)
+    }
+
+    /// Write `events` as a new commit with the given parents and return its
+    /// id. No ref is updated.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitRefError`] if an event fails to serialize or git fails.
+    pub fn write_events(&self, events: &[Event], parents: &[&str]) -> Result<String, GitRefError> {
+        let mut months: BTreeMap<String, String> = BTreeMap::new();
+        for event in &merge_event_sets(events, &[]).events {
+            let blob = months
+                .entry(month_blob_name(event.wall_ts_us))
+                .or_insert_with(shard_header);
+            blob.push_str(&write_line(event)?);
+        }
+
+        let mut tree_listing = String::new();
+        for (name, content) in &months {
+            let oid = self.git(&["hash-object", "-w", "--stdin"], Some(content.as_bytes()))?;
+            let _ = writeln!(tree_listing, "100644 blob {}\t{name}", oid.trim());
+        }
+        let tree = self.git(&["mktree"], Some(tree_listing.as_bytes()))?;
+
+        let message = format!("bones: {} events\n", events.len());
+        let mut args = vec!["commit-tree", tree.trim()];
+        for parent in parents {
+            args.extend(["-p", parent]);
+        }
+        let commit = self.git(&args, Some(message.as_bytes()))?;
+        Ok(commit.trim().to_string())
+    }
+
+    /// Point `refname` at `new`, provided it still points at `old` (`None`
+    /// meaning it must not exist yet).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitRefError::Git`] if the ref moved concurrently.
+    pub fn update_ref(
+        &self,
+        refname: &str,
+        new: &str,
+        old: Option<&str>,
+    ) -> Result<(), GitRefError> {
+        self.git(
+            &[
+                "update-ref",
+                "-m",
+                "bones: sync events",
+                refname,
+                new,
+                old.unwrap_or(""),
+            ],
+            None,
+        )?;
+        Ok(())
+    }
+
+    /// Whether `ancestor` is reachable from `descendant`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitRefError`] if git cannot be run.
+    pub fn is_ancestor(&self, ancestor: &str, descendant: &str) -> Result<bool, GitRefError> {
+        let output = self.command(&["merge-base", "--is-ancestor", ancestor, descendant])?;
+        Ok(output.status.success())
+    }
+
+    /// Whether a git remote named `remote` is configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitRefError`] if git cannot be run.
+    pub fn has_remote(&self, remote: &str) -> Result<bool, GitRefError> {
+        let output = self.command(&["remote", "get-url", remote])?;
+        Ok(output.status.success())
+    }
+
+    /// Fetch the remote's event ref into [`remote_tracking_ref`] and return
+    /// the fetched commit, or `None` if the remote has not published one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitRefError`] if the remote cannot be reached.
+    pub fn fetch(&self, remote: &str) -> Result<Option<String>, GitRefError> {
+        let advertised = self.git(&["ls-remote", remote, EVENTS_REF], None)?;
+        if advertised.trim().is_empty() {
+            return Ok(None);
+        }
+        let tracking = remote_tracking_ref(remote);
+        let refspec = format!("+{EVENTS_REF}:{tracking}");
+        self.git(&["fetch", "--quiet", "--no-tags", remote, &refspec], None)?;
+        self.resolve(&tracking)
+    }
+
+    /// Push the local event ref to `remote` and record it as fetched.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitRefError::Git`] if the push is rejected, for instance
+    /// because the remote ref moved since the last fetch.
+    pub fn push(&self, remote: &str) -> Result<(), GitRefError> {
+        let refspec = format!("{EVENTS_REF}:{EVENTS_REF}");
+        self.git(&["push", "--quiet", remote, &refspec], None)?;
+        if let Some(head) = self.resolve(EVENTS_REF)? {
+            self.git(&["update-ref", &remote_tracking_ref(remote), &head], None)?;
+        }
+        Ok(())
+    }
+
+    fn command(&self, args: &[&str]) -> Result<std::process::Output, GitRefError> {
+        Ok(Command::new("git")
+            .args(args)
+            .current_dir(&self.repo)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()?)
+    }
+
+    fn git(&self, args: &[&str], stdin: Option<&[u8]>) -> Result<String, GitRefError> {
+        let mut child = Command::new("git")
+            .args(args)
+            .current_dir(&self.repo)
+            .env("GIT_AUTHOR_NAME", COMMIT_IDENTITY.0)
+            .env("GIT_AUTHOR_EMAIL", COMMIT_IDENTITY.1)
+            .env("GIT_COMMITTER_NAME", COMMIT_IDENTITY.0)
+            .env("GIT_COMMITTER_EMAIL", COMMIT_IDENTITY.1)
+            .stdin(if stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+            pipe.write_all(input)?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(GitRefError::Git {
+                command: args.join(" "),
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// Blob name for events written in the month of `wall_ts_us`.
+fn month_blob_name(wall_ts_us: i64) -> String {
+    let when = DateTime::from_timestamp_micros(wall_ts_us).unwrap_or_default();
+    format!("{:04}-{:02}.events", when.year(), when.month())
+}
+
+// ---------------------------------------------------------------------------
+// Merging
+// ---------------------------------------------------------------------------
+
+/// Outcome of [`merge_into_ref`].
+#[derive(Debug, Clone, Default)]
+pub struct RefMerge {
+    /// Commit [`EVENTS_REF`] points at afterwards, if any.
+    pub head: Option<String>,
+    /// Whether [`EVENTS_REF`] moved.
+    pub updated: bool,
+    /// Events on the ref that the local shards lack, in canonical order.
+    pub missing_locally: Vec<Event>,
+    /// Events on the ref that the fetched remote ref lacks.
+    pub unpublished: usize,
+    /// Remote events the policy refused; the caller quarantines them.
+    pub rejected: Vec<Rejection>,
+}
+
+/// Union `local` shard events, the local [`EVENTS_REF`], and the fetched
+/// `remote` commit, and move [`EVENTS_REF`] to a commit holding the result.
+///
+/// Events only the remote has are screened through `policy` first.
+///
+/// The ref fast-forwards to `remote` when that already holds everything;
+/// otherwise a new commit is written whose parents are the previous local
+/// head and, if it has diverged, `remote`.
+///
+/// # Errors
+///
+/// Returns [`GitRefError`] if either ref cannot be read or written.
+pub fn merge_into_ref(
+    store: &GitRefStore,
+    local: &[Event],
+    remote: Option<&str>,
+    policy: &Policy,
+) -> Result<RefMerge, GitRefError> {
+    let head = store.resolve(EVENTS_REF)?;
+    let ref_events = match &head {
+        Some(commit) => store.read_events(commit)?,
+        None => Vec::new(),
+    };
+    let remote = remote.filter(|r| head.as_deref() != Some(*r));
+    let remote_events = match remote {
+        Some(commit) => store.read_events(commit)?,
+        None => Vec::new(),
+    };
+
+    let screened = merge_event_sets_with_policy(
+        &merge_event_sets(local, &ref_events).events,
+        &remote_events,
+        policy,
+    );
+    let merged = screened.merge.events;
+    let merged_hashes = hash_set(&merged);
+
+    let remote_included = match (remote, &head) {
+        (None, _) => true,
+        (Some(remote), Some(head)) => store.is_ancestor(remote, head)?,
+        (Some(_), None) => false,
+    };
+    let head_included = match (remote, &head) {
+        (_, None) => true,
+        (Some(remote), Some(head)) => store.is_ancestor(head, remote)?,
+        (None, Some(_)) => false,
+    };
+
+    let new_head = if remote_included {
+        if merged_hashes == hash_set(&ref_events) {
+            head.clone()
+        } else {
+            let parents: Vec<&str> = head.iter().map(String::as_str).collect();
+            Some(store.write_events(&merged, &parents)?)
+        }
+    } else if head_included && merged_hashes == hash_set(&remote_events) {
+        remote.map(str::to_string)
+    } else {
+        let parents: Vec<&str> = head.iter().map(String::as_str).chain(remote).collect();
+        Some(store.write_events(&merged, &parents)?)
+    };
+
+    let updated = new_head != head;
+    if let (true, Some(new)) = (updated, &new_head) {
+        store.update_ref(EVENTS_REF, new, head.as_deref())?;
+    }
+
+    let local_hashes = hash_set(local);
+    let remote_hashes = if remote.is_some() {
+        hash_set(&remote_events)
+    } else {
+        hash_set(&ref_events)
+    };
+    Ok(RefMerge {
+        head: new_head,
+        updated,
+        unpublished: merged_hashes.difference(&remote_hashes).count(),
+        rejected: screened.rejected,
+        missing_locally: merged
+            .into_iter()
+            .filter(|e| !local_hashes.contains(&e.event_hash))
+            .collect(),
+    })
+}
+
+fn hash_set(events: &[Event]) -> HashSet<String> {
+    events.iter().map(|e| e.event_hash.clone()).collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::data::{CreateData, EventData};
+    use crate::event::types::EventType;
+    use crate::event::writer;
+    use crate::model::item::{Kind, Urgency};
+    use crate::model::item_id::ItemId;
+    use std::collections::BTreeMap;
+    use tempfile::TempDir;
+
+    fn make_event(item: &str, ts: i64) -> Event {
+        let mut event = Event {
+            wall_ts_us: ts,
+            agent: "test-agent".into(),
+            itc: "itc:AQ".into(),
+            parents: vec![],
+            event_type: EventType::Create,
+            item_id: ItemId::new_unchecked(item),
+            data: EventData::Create(CreateData {
+                title: format!("Item {item}"),
+                kind: Kind::Task,
+                size: None,
+                urgency: Urgency::Default,
+                labels: vec![],
+                parent: None,
+                causation: None,
+                description: None,
+                extra: BTreeMap::new(),
+            }),
+            event_hash: String::new(),
+            signature: None,
+        };
+        writer::write_event(&mut event).expect("hash");
+        event
+    }
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .expect("git");
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    /// A bare remote plus two clones of it.
+    fn setup() -> (TempDir, GitRefStore, GitRefStore) {
+        let dir = TempDir::new().expect("tempdir");
+        let bare = dir.path().join("remote.git");
+        git(dir.path(), &["init", "--bare", "--quiet", "remote.git"]);
+        let bare = bare.to_str().expect("utf-8 path");
+        for name in ["a", "b"] {
+            git(dir.path(), &["clone", "--quiet", bare, name]);
+        }
+        let a = GitRefStore::new(dir.path().join("a"));
+        let b = GitRefStore::new(dir.path().join("b"));
+        (dir, a, b)
+    }
+
+    #[test]
+    fn events_round_trip_through_a_commit() {
+        let (_dir, a, _b) = setup();
+        let events = vec![
+            make_event("bn-b", 1_700_000_000_000_000),
+            make_event("bn-a", 1_710_000_000_000_000),
+        ];
+        let commit = a.write_events(&events, &[]).expect("write");
+        let listing = a
+            .git(&["ls-tree", "--name-only", &commit], None)
+            .expect("ls-tree");
+        assert_eq!(listing, "2023-11.events\n2024-03.events\n");
+
+        let read = a.read_events(&commit).expect("read");
+        let hashes: Vec<_> = read.iter().map(|e| e.event_hash.clone()).collect();
+        assert_eq!(
+            hashes,
+            vec![events[0].event_hash.clone(), events[1].event_hash.clone()]
+        );
+
+        // The same events always produce the same tree.
+        let again = a
+            .write_events(&[events[1].clone(), events[0].clone()], &[])
+            .expect("write");
+        let tree = |c: &str| {
+            a.git(&["rev-parse", &format!("{c}^{{tree}}")], None)
+                .expect("tree")
+        };
+        assert_eq!(tree(&commit), tree(&again));
+    }
+
+    #[test]
+    fn diverged_refs_merge_and_push_as_fast_forward() {
+        let (_dir, a, b) = setup();
+        let open = Policy::default();
+        let ea = make_event("bn-a", 1_700_000_000_000_000);
+        let eb = make_event("bn-b", 1_700_000_000_000_001);
+
+        // A publishes first.
+        let merged = merge_into_ref(
+            &a,
+            &[ea.clone()],
+            a.fetch("origin").expect("fetch").as_deref(),
+            &open,
+        )
+        .expect("merge a");
+        assert!(merged.updated);
+        assert_eq!(merged.unpublished, 1);
+        a.push("origin").expect("push a");
+
+        // B has its own event and the remote has A's: both end up on B's ref.
+        let remote = b.fetch("origin").expect("fetch b");
+        assert!(remote.is_some());
+        let merged = merge_into_ref(&b, &[eb.clone()], remote.as_deref(), &open).expect("merge b");
+        assert_eq!(merged.missing_locally.len(), 1);
+        assert_eq!(merged.missing_locally[0].event_hash, ea.event_hash);
+        assert_eq!(merged.unpublished, 1);
+        b.push("origin").expect("push b");
+
+        // A fast-forwards to B's commit.
+        let remote = a.fetch("origin").expect("fetch a");
+        let merged =
+            merge_into_ref(&a, &[ea.clone()], remote.as_deref(), &open).expect("merge a again");
+        assert_eq!(merged.head, remote);
+        assert_eq!(merged.missing_locally.len(), 1);
+        assert_eq!(merged.missing_locally[0].event_hash, eb.event_hash);
+        assert_eq!(merged.unpublished, 0);
+
+        // Nothing new on either side: the ref stays put.
+        let merged =
+            merge_into_ref(&a, &[ea, eb], remote.as_deref(), &open).expect("merge a idempotent");
+        assert!(!merged.updated);
+        assert!(merged.missing_locally.is_empty());
+    }
+
+    #[test]
+    fn concurrent_publishes_produce_a_merge_commit() {
+        let (_dir, a, b) = setup();
+        let open = Policy::default();
+        let ea = make_event("bn-a", 1_700_000_000_000_000);
+        let eb = make_event("bn-b", 1_700_000_000_000_001);
+
+        merge_into_ref(&b, &[eb.clone()], None, &open).expect("merge b");
+        merge_into_ref(&a, &[ea.clone()], None, &open).expect("merge a");
+        a.push("origin").expect("push a");
+
+        // B committed locally before seeing A's push, so the histories diverge.
+        let remote = b.fetch("origin").expect("fetch").expect("remote ref");
+        let merged = merge_into_ref(&b, &[eb], Some(&remote), &open).expect("merge");
+        let head = merged.head.expect("head");
+        let parents = b
+            .git(&["rev-list", "--parents", "-n", "1", &head], None)
+            .expect("rev-list");
+        assert_eq!(
+            parents.split_whitespace().count(),
+            3,
+            "merge commit has two parents"
+        );
+        assert_eq!(b.read_events(&head).expect("read").len(), 2);
+        b.push("origin").expect("fast-forward push");
+    }
+
+    #[test]
+    fn policy_keeps_refused_remote_events_off_the_local_ref() {
+        let (_dir, a, b) = setup();
+        let ea = make_event("bn-a", 1_700_000_000_000_000);
+        let policy = Policy::parse("[[rule]]\nevents = [\"item.create\"]\nagents = [\"lead\"]\n")
+            .expect("policy");
+
+        merge_into_ref(&a, &[ea], None, &Policy::default()).expect("merge a");
+        a.push("origin").expect("push a");
+
+        let remote = b.fetch("origin").expect("fetch");
+        let merged = merge_into_ref(&b, &[], remote.as_deref(), &policy).expect("merge b");
+        assert_eq!(merged.rejected.len(), 1);
+        assert!(merged.missing_locally.is_empty());
+        let head = merged.head.expect("b follows the remote history");
+        assert!(b.read_events(&head).expect("read").is_empty());
+    }
+
+    #[test]
+    fn shards_are_read_at_branch_revisions() {
+        let (_dir, a, _b) = setup();
+        let events_dir = a.repo().join(".bones/events");
+        std::fs::create_dir_all(&events_dir).expect("mkdir");
+        let shard = events_dir.join("2023-11.events");
+        let commit = |message: &str| {
+            git(a.repo(), &["add", "-A"]);
+            git(
+                a.repo(),
+                &[
+                    "-c",
+                    "user.name=t",
+                    "-c",
+                    "user.email=t@t",
+                    "commit",
+                    "-qm",
+                    message,
+                ],
+            );
+        };
+
+        let ea = make_event("bn-a", 1_700_000_000_000_000);
+        let eb = make_event("bn-b", 1_700_000_000_000_001);
+        let mut content = shard_header();
+        content.push_str(&write_line(&ea).expect("line"));
+        std::fs::write(&shard, &content).expect("write");
+        commit("first");
+        content.push_str(&write_line(&eb).expect("line"));
+        std::fs::write(&shard, &content).expect("write");
+        std::fs::write(events_dir.join("2023-11.manifest"), "not events\n").expect("write");
+        commit("second");
+
+        let before = a.read_shards_at("HEAD~1", ".bones/events").expect("read");
+        let after = a.read_shards_at("HEAD", ".bones/events/").expect("read");
+        assert_eq!(before.len(), 1);
+        assert_eq!(after.len(), 2);
+        assert_eq!(after[1].event_hash, eb.event_hash);
+        assert!(
+            a.read_shards_at("HEAD", "elsewhere")
+                .expect("read")
+                .is_empty()
+        );
+        assert!(a.read_shards_at("no-such-branch", ".bones/events").is_err());
+
+        let base = a.merge_base("HEAD", "HEAD~1").expect("merge-base");
+        assert_eq!(base, a.resolve("HEAD~1").expect("resolve"));
+    }
+}
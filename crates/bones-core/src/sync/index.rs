@@ -0,0 +1,1024 @@
+//! Persisted, incrementally updated Prolly Tree index.
+//!
+//! [`ProllyTree::build`] sorts and chunks every event, which means parsing
+//! the whole log before each sync. The index keeps the tree's leaf level on
+//! disk instead — each event's sort key and hash, grouped into the same
+//! content-defined chunks — so a replica only does work for events it has
+//! not indexed yet.
+//!
+//! # Files
+//!
+//! ```text
+//! .bones/cache/prolly.idx      # base: leaf chunks plus the shard cursor
+//! .bones/cache/prolly.journal  # entries appended since the base was written
+//! ```
+//!
+//! [`ShardManager::append`](crate::shard::ShardManager::append) and
+//! [`append_raw`](crate::shard::ShardManager::append_raw) add one journal
+//! record per event they write to the last shard, once a base exists.
+//! [`refresh`] folds the journal into the base, catches up on events that
+//! reached the shards some other way (a `git pull`, for example), and rebuilds from
+//! scratch if the shards no longer match the cursor. Inserting an event only
+//! re-chunks the leaves around it; interior levels are rebuilt from leaf
+//! hashes, which is cheap next to the leaves themselves.
+//!
+//! Like the binary cache, the index is derived data: deleting it is always
+//! safe, and `bn verify` checks it against the shards ([`check`]).
+
+use std::collections::{BTreeSet, HashSet};
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use crate::event::Event;
+use crate::event::parser::{ParseError, ParsedLine, parse_line};
+use crate::shard::{ShardError, ShardManager};
+use crate::sync::prolly::{Hash, ProllyNode, ProllyTree, hash_leaf_chunk, leaf_boundary};
+use crate::sync::protocol::EventStore;
+
+/// Index file, relative to `.bones/`.
+pub const INDEX_PATH: &str = "cache/prolly.idx";
+
+/// Journal file, relative to `.bones/`.
+pub const JOURNAL_PATH: &str = "cache/prolly.journal";
+
+/// First line of every index file.
+const INDEX_HEADER: &str = "# bones prolly index v1";
+
+/// Prefix of the cursor line.
+const CURSOR_PREFIX: &str = "# cursor: ";
+
+// ---------------------------------------------------------------------------
+// Error types
+// ---------------------------------------------------------------------------
+
+/// Errors that can occur while reading or maintaining the index.
+#[derive(Debug, thiserror::Error)]
+pub enum IndexError {
+    /// I/O error on the index or journal.
+    #[error("prolly index I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    /// The event shards could not be read.
+    #[error(transparent)]
+    Shard(#[from] ShardError),
+
+    /// The index file is malformed or from a newer version of bones.
+    #[error("corrupt prolly index at line {line}: {reason}")]
+    Corrupt {
+        /// 1-based line number in the index file.
+        line: usize,
+        /// What is wrong with it.
+        reason: String,
+    },
+
+    /// An event line in the shards is malformed.
+    #[error("malformed event line at shard offset {offset}")]
+    MalformedLine {
+        /// Absolute byte offset of the line in the concatenated shards.
+        offset: usize,
+    },
+
+    /// An event selected for sync failed to parse.
+    #[error("failed to parse event at shard offset {offset}: {source}")]
+    Parse {
+        /// Absolute byte offset of the line in the concatenated shards.
+        offset: usize,
+        /// Underlying parse error.
+        source: ParseError,
+    },
+}
+
+// ---------------------------------------------------------------------------
+// Entries and chunks
+// ---------------------------------------------------------------------------
+
+/// Sort key and hash of one indexed event. Entries order the same way
+/// [`ProllyTree::build`] orders events.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct IndexEntry {
+    /// Item the event belongs to.
+    pub item_id: String,
+    /// Event wall-clock timestamp.
+    pub wall_ts_us: i64,
+    /// Event content hash.
+    pub event_hash: String,
+}
+
+impl IndexEntry {
+    /// Entry for an event.
+    #[must_use]
+    pub fn from_event(event: &Event) -> Self {
+        Self {
+            item_id: event.item_id.as_str().to_string(),
+            wall_ts_us: event.wall_ts_us,
+            event_hash: event.event_hash.clone(),
+        }
+    }
+
+    /// Entry for a TSJSON shard line without parsing its payload. Returns
+    /// `Ok(None)` for comments and blank lines.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(())` if the line is not a well-formed event line.
+    #[allow(clippy::result_unit_err)]
+    pub fn from_line(line: &str) -> Result<Option<Self>, ()> {
+        let line = line.trim_end_matches(['\n', '\r']);
+        if line.trim().is_empty() || line.starts_with('#') {
+            return Ok(None);
+        }
+        // wall_ts_us, agent, itc, parents, type, item_id, data, event_hash
+        let fields: Vec<&str> = line.splitn(9, '\t').collect();
+        if fields.len() < 8 {
+            return Err(());
+        }
+        let wall_ts_us = fields[0].parse().map_err(|_| ())?;
+        Ok(Some(Self {
+            item_id: fields[5].to_string(),
+            wall_ts_us,
+            event_hash: fields[7].to_string(),
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Chunk {
+    hash: Hash,
+    entries: Vec<IndexEntry>,
+}
+
+impl Chunk {
+    fn new(entries: Vec<IndexEntry>) -> Self {
+        Self {
+            hash: hash_leaf_chunk(entries.iter().map(|e| e.event_hash.as_str())),
+            entries,
+        }
+    }
+
+    fn last(&self) -> &IndexEntry {
+        self.entries.last().expect("index chunks are never empty")
+    }
+}
+
+/// How far into the concatenated shards the index reaches.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Cursor {
+    /// Byte offset just past the last indexed line.
+    end: usize,
+    /// Start offset and hash of the last indexed event line, used to detect
+    /// shards rewritten under the index.
+    last: Option<(usize, String)>,
+}
+
+// ---------------------------------------------------------------------------
+// ProllyIndex
+// ---------------------------------------------------------------------------
+
+/// The leaf level of a Prolly Tree, kept sorted and chunked so new events
+/// can be inserted without rebuilding the tree.
+#[derive(Debug, Clone, Default)]
+pub struct ProllyIndex {
+    chunks: Vec<Chunk>,
+    event_count: usize,
+    cursor: Cursor,
+}
+
+impl ProllyIndex {
+    /// Index a set of events.
+    #[must_use]
+    pub fn from_events(events: &[Event]) -> Self {
+        let mut index = Self::default();
+        index.insert(events.iter().map(IndexEntry::from_event).collect());
+        index
+    }
+
+    /// Number of indexed events.
+    #[must_use]
+    pub const fn event_count(&self) -> usize {
+        self.event_count
+    }
+
+    /// Build the Prolly Tree. Its root equals that of [`ProllyTree::build`]
+    /// over the same events.
+    #[must_use]
+    pub fn tree(&self) -> ProllyTree {
+        let leaves = self
+            .chunks
+            .iter()
+            .map(|chunk| ProllyNode::Leaf {
+                hash: chunk.hash,
+                event_hashes: chunk.entries.iter().map(|e| e.event_hash.clone()).collect(),
+            })
+            .collect();
+        ProllyTree::from_leaves(leaves, self.event_count)
+    }
+
+    /// An in-memory index of only the events for `items`, for syncing
+    /// through a [`ReplicationFilter`](crate::sync::filter::ReplicationFilter).
+    #[must_use]
+    pub fn restricted(&self, items: &BTreeSet<String>) -> Self {
+        let mut index = Self::default();
+        index.insert(
+            self.chunks
+                .iter()
+                .flat_map(|chunk| &chunk.entries)
+                .filter(|entry| items.contains(&entry.item_id))
+                .cloned()
+                .collect(),
+        );
+        index
+    }
+
+    /// Whether an event is indexed.
+    #[must_use]
+    pub fn contains(&self, entry: &IndexEntry) -> bool {
+        let at = self.chunks.partition_point(|c| c.last() < entry);
+        self.chunks
+            .get(at)
+            .is_some_and(|c| c.entries.binary_search(entry).is_ok())
+    }
+
+    /// Insert entries, re-chunking only the leaves they land in and the ones
+    /// after them until chunk boundaries line up again. Entries already
+    /// indexed are skipped. Returns the number inserted.
+    pub fn insert(&mut self, mut entries: Vec<IndexEntry>) -> usize {
+        if entries.is_empty() {
+            return 0;
+        }
+        entries.sort_unstable();
+        entries.dedup();
+
+        let old = std::mem::take(&mut self.chunks);
+        let old_len = old.len();
+        let mut out = Vec::with_capacity(old_len + 1);
+        // Entries from a chunk boundary onward that are not chunked yet.
+        let mut stream: Vec<IndexEntry> = Vec::new();
+        let mut new = entries.into_iter().peekable();
+        let mut inserted = 0;
+
+        for (i, chunk) in old.into_iter().enumerate() {
+            let is_last = i + 1 == old_len;
+            let mut landing = Vec::new();
+            while let Some(entry) = new.next_if(|e| is_last || e <= chunk.last()) {
+                landing.push(entry);
+            }
+            if landing.is_empty() && stream.is_empty() {
+                // Starts on a boundary and gains nothing: still a valid chunk.
+                out.push(chunk);
+                continue;
+            }
+            inserted += merge_into(&mut stream, chunk.entries, landing);
+            drain_chunks(&mut stream, &mut out);
+        }
+        if old_len == 0 {
+            stream.extend(new);
+            inserted = stream.len();
+        }
+        drain_chunks(&mut stream, &mut out);
+        if !stream.is_empty() {
+            out.push(Chunk::new(stream));
+        }
+
+        self.chunks = out;
+        self.event_count += inserted;
+        inserted
+    }
+
+    // -----------------------------------------------------------------------
+    // Persistence
+    // -----------------------------------------------------------------------
+
+    /// Load the index and apply its journal. Returns `Ok(None)` if no index
+    /// has been written.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IndexError`] if the files cannot be read or the index is
+    /// malformed.
+    pub fn load(bones_dir: &Path) -> Result<Option<Self>, IndexError> {
+        let file = match fs::File::open(bones_dir.join(INDEX_PATH)) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let mut index = Self::parse(BufReader::new(file))?;
+        index.apply_journal(bones_dir)?;
+        Ok(Some(index))
+    }
+
+    fn parse(reader: impl BufRead) -> Result<Self, IndexError> {
+        let corrupt = |line: usize, reason: &str| IndexError::Corrupt {
+            line,
+            reason: reason.to_string(),
+        };
+        let mut lines = reader.lines();
+        if lines.next().transpose()?.as_deref() != Some(INDEX_HEADER) {
+            return Err(corrupt(1, "missing or unsupported header"));
+        }
+        let cursor_line = lines.next().transpose()?.unwrap_or_default();
+        let cursor = parse_cursor(&cursor_line).ok_or_else(|| corrupt(2, "invalid cursor"))?;
+
+        let mut index = Self {
+            cursor,
+            ..Self::default()
+        };
+        let mut current: Option<(Hash, Vec<IndexEntry>)> = None;
+        for (n, line) in lines.enumerate() {
+            let line = line?;
+            let line_no = n + 3;
+            if let Some(hex) = line.strip_prefix('@') {
+                let hash = Hash::from_hex(hex).ok_or_else(|| corrupt(line_no, "invalid hash"))?;
+                index.push_parsed(current.replace((hash, Vec::new())), line_no)?;
+                continue;
+            }
+            let entry = IndexEntry::from_index_line(&line)
+                .ok_or_else(|| corrupt(line_no, "invalid entry"))?;
+            let (_, entries) = current
+                .as_mut()
+                .ok_or_else(|| corrupt(line_no, "entry outside a chunk"))?;
+            entries.push(entry);
+        }
+        index.push_parsed(current, 0)?;
+        Ok(index)
+    }
+
+    fn push_parsed(
+        &mut self,
+        chunk: Option<(Hash, Vec<IndexEntry>)>,
+        line: usize,
+    ) -> Result<(), IndexError> {
+        let Some((hash, entries)) = chunk else {
+            return Ok(());
+        };
+        if entries.is_empty() {
+            return Err(IndexError::Corrupt {
+                line,
+                reason: "empty chunk".to_string(),
+            });
+        }
+        self.event_count += entries.len();
+        self.chunks.push(Chunk { hash, entries });
+        Ok(())
+    }
+
+    /// Apply journal records that continue the cursor without a gap. A gap
+    /// (an append whose record was lost) stops replay; [`refresh`] then
+    /// catches up from the shards.
+    fn apply_journal(&mut self, bones_dir: &Path) -> Result<(), IndexError> {
+        let file = match fs::File::open(bones_dir.join(JOURNAL_PATH)) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut entries = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            let Some((start, end, entry)) = parse_journal_line(&line) else {
+                break;
+            };
+            if start != self.cursor.end {
+                break;
+            }
+            self.cursor = Cursor {
+                end,
+                last: Some((start, entry.event_hash.clone())),
+            };
+            entries.push(entry);
+        }
+        self.insert(entries);
+        Ok(())
+    }
+
+    /// Write the index atomically and clear the journal.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IndexError::Io`] if the files cannot be written.
+    pub fn save(&self, bones_dir: &Path) -> Result<(), IndexError> {
+        let path = bones_dir.join(INDEX_PATH);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("idx.tmp");
+        {
+            let mut out = io::BufWriter::new(fs::File::create(&tmp)?);
+            writeln!(out, "{INDEX_HEADER}")?;
+            match &self.cursor.last {
+                Some((start, hash)) => {
+                    writeln!(out, "{CURSOR_PREFIX}{} {start} {hash}", self.cursor.end)?;
+                }
+                None => writeln!(out, "{CURSOR_PREFIX}{}", self.cursor.end)?,
+            }
+            for chunk in &self.chunks {
+                writeln!(out, "@{}", chunk.hash)?;
+                for e in &chunk.entries {
+                    writeln!(out, "{}\t{}\t{}", e.item_id, e.wall_ts_us, e.event_hash)?;
+                }
+            }
+            out.flush()?;
+        }
+        fs::rename(&tmp, &path)?;
+        match fs::remove_file(bones_dir.join(JOURNAL_PATH)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Shard catch-up
+    // -----------------------------------------------------------------------
+
+    /// Index every event in the shards.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IndexError`] if the shards cannot be read or hold a
+    /// malformed event line.
+    pub fn build(shards: &ShardManager) -> Result<Self, IndexError> {
+        let mut index = Self::default();
+        index.catch_up(shards)?;
+        Ok(index)
+    }
+
+    /// Index complete lines after the cursor. Returns the number of new
+    /// events.
+    fn catch_up(&mut self, shards: &ShardManager) -> Result<usize, IndexError> {
+        let mut entries = Vec::new();
+        for line in shards.replay_lines_from_offset(self.cursor.end)? {
+            let (offset, line) = line?;
+            if !line.ends_with('\n') {
+                // Torn write in progress; leave it for next time.
+                break;
+            }
+            let entry =
+                IndexEntry::from_line(&line).map_err(|()| IndexError::MalformedLine { offset })?;
+            if let Some(entry) = entry {
+                self.cursor.last = Some((offset, entry.event_hash.clone()));
+                entries.push(entry);
+            }
+            self.cursor.end = offset + line.len();
+        }
+        Ok(self.insert(entries))
+    }
+
+    /// Whether the shards still hold the indexed prefix: the last indexed
+    /// line must sit where the cursor says.
+    fn matches_shards(&self, shards: &ShardManager) -> Result<bool, IndexError> {
+        if self.cursor.end > shards.total_content_len()? {
+            return Ok(false);
+        }
+        let Some((start, hash)) = &self.cursor.last else {
+            return Ok(self.event_count == 0);
+        };
+        let content = shards.read_content_range(*start, self.cursor.end)?;
+        let first_line = content.split_inclusive('\n').next().unwrap_or_default();
+        Ok(matches!(
+            IndexEntry::from_line(first_line),
+            Ok(Some(entry)) if entry.event_hash == *hash
+        ))
+    }
+}
+
+/// Merge sorted `existing` and `landing` entries onto `stream`, skipping
+/// entries already present. Returns how many landing entries were added.
+fn merge_into(
+    stream: &mut Vec<IndexEntry>,
+    existing: Vec<IndexEntry>,
+    landing: Vec<IndexEntry>,
+) -> usize {
+    let mut added = 0;
+    let mut landing = landing.into_iter().peekable();
+    for entry in existing {
+        while let Some(new) = landing.next_if(|new| *new < entry) {
+            stream.push(new);
+            added += 1;
+        }
+        landing.next_if(|new| *new == entry);
+        stream.push(entry);
+    }
+    for new in landing {
+        stream.push(new);
+        added += 1;
+    }
+    added
+}
+
+/// Move every complete chunk at the front of `stream` to `out`.
+fn drain_chunks(stream: &mut Vec<IndexEntry>, out: &mut Vec<Chunk>) {
+    let mut start = 0;
+    while let Some(len) = leaf_boundary(stream[start..].iter().map(|e| e.event_hash.as_str())) {
+        out.push(Chunk::new(stream[start..start + len].to_vec()));
+        start += len;
+    }
+    stream.drain(..start);
+}
+
+impl IndexEntry {
+    fn from_index_line(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(3, '\t');
+        let item_id = fields.next()?.to_string();
+        let wall_ts_us = fields.next()?.parse().ok()?;
+        let event_hash = fields.next()?.to_string();
+        Some(Self {
+            item_id,
+            wall_ts_us,
+            event_hash,
+        })
+    }
+}
+
+fn parse_cursor(line: &str) -> Option<Cursor> {
+    let mut parts = line.strip_prefix(CURSOR_PREFIX)?.split(' ');
+    let end = parts.next()?.parse().ok()?;
+    let last = match (parts.next(), parts.next()) {
+        (Some(start), Some(hash)) => Some((start.parse().ok()?, hash.to_string())),
+        (None, None) => None,
+        _ => return None,
+    };
+    Some(Cursor { end, last })
+}
+
+fn parse_journal_line(line: &str) -> Option<(usize, usize, IndexEntry)> {
+    let (start, rest) = line.split_once('\t')?;
+    let (end, entry) = rest.split_once('\t')?;
+    Some((
+        start.parse().ok()?,
+        end.parse().ok()?,
+        IndexEntry::from_index_line(entry)?,
+    ))
+}
+
+// ---------------------------------------------------------------------------
+// Maintenance entry points
+// ---------------------------------------------------------------------------
+
+/// What [`refresh`] had to do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexRefresh {
+    /// Events added from the shards beyond the base and journal.
+    pub caught_up: usize,
+    /// Why the index was rebuilt from scratch, if it was.
+    pub rebuilt: Option<String>,
+}
+
+/// Bring the index up to date with the shards and persist it.
+///
+/// # Errors
+///
+/// Returns [`IndexError`] if the shards cannot be read or the index cannot
+/// be written.
+pub fn refresh(bones_dir: &Path) -> Result<(ProllyIndex, IndexRefresh), IndexError> {
+    let shards = ShardManager::new(bones_dir);
+    let (index, report) = refreshed(bones_dir, &shards)?;
+    index.save(bones_dir)?;
+    Ok((index, report))
+}
+
+fn refreshed(
+    bones_dir: &Path,
+    shards: &ShardManager,
+) -> Result<(ProllyIndex, IndexRefresh), IndexError> {
+    let loaded = match ProllyIndex::load(bones_dir) {
+        Ok(Some(index)) => {
+            if index.matches_shards(shards)? {
+                Ok(index)
+            } else {
+                Err("the event shards were rewritten".to_string())
+            }
+        }
+        Ok(None) => Err("no index yet".to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    match loaded {
+        Ok(mut index) => {
+            let caught_up = index.catch_up(shards)?;
+            Ok((
+                index,
+                IndexRefresh {
+                    caught_up,
+                    rebuilt: None,
+                },
+            ))
+        }
+        Err(reason) => {
+            let index = ProllyIndex::build(shards)?;
+            Ok((
+                index,
+                IndexRefresh {
+                    caught_up: 0,
+                    rebuilt: Some(reason),
+                },
+            ))
+        }
+    }
+}
+
+/// Record an event line just appended at `start` of the concatenated shards.
+/// Does nothing until an index has been written, so appends stay cheap in
+/// projects that never sync.
+///
+/// # Errors
+///
+/// Returns [`IndexError::Io`] if the journal cannot be written.
+pub fn record_append(bones_dir: &Path, start: usize, line: &str) -> Result<(), IndexError> {
+    if !bones_dir.join(INDEX_PATH).exists() {
+        return Ok(());
+    }
+    let Ok(Some(entry)) = IndexEntry::from_line(line) else {
+        // Not an event; the gap makes the next refresh read the shards.
+        return Ok(());
+    };
+    let mut journal = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(bones_dir.join(JOURNAL_PATH))?;
+    writeln!(
+        journal,
+        "{start}\t{}\t{}\t{}\t{}",
+        start + line.len(),
+        entry.item_id,
+        entry.wall_ts_us,
+        entry.event_hash
+    )?;
+    Ok(())
+}
+
+/// Result of checking the index against the shards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexCheck {
+    /// No index has been written.
+    Absent,
+    /// The index matches the shards once pending events are applied.
+    Consistent {
+        /// Indexed events.
+        events: usize,
+        /// Events in the shards the index has not seen yet.
+        pending: usize,
+    },
+    /// The index disagrees with the shards.
+    Mismatch(String),
+}
+
+/// Compare the index with a tree built from the shards. Nothing is written.
+///
+/// # Errors
+///
+/// Returns [`IndexError`] if the shards cannot be read.
+pub fn check(bones_dir: &Path) -> Result<IndexCheck, IndexError> {
+    let shards = ShardManager::new(bones_dir);
+    let mut index = match ProllyIndex::load(bones_dir) {
+        Ok(Some(index)) => index,
+        Ok(None) => return Ok(IndexCheck::Absent),
+        Err(e) => return Ok(IndexCheck::Mismatch(e.to_string())),
+    };
+    if !index.matches_shards(&shards)? {
+        return Ok(IndexCheck::Mismatch(
+            "cursor does not match the event shards".to_string(),
+        ));
+    }
+    let events = index.event_count;
+    let pending = index.catch_up(&shards)?;
+
+    let fresh = ProllyIndex::build(&shards)?;
+    if fresh.event_count != index.event_count {
+        return Ok(IndexCheck::Mismatch(format!(
+            "index holds {} events, shards hold {}",
+            index.event_count, fresh.event_count
+        )));
+    }
+    if let Some(i) = (0..fresh.chunks.len().max(index.chunks.len()))
+        .find(|&i| fresh.chunks.get(i).map(|c| c.hash) != index.chunks.get(i).map(|c| c.hash))
+    {
+        return Ok(IndexCheck::Mismatch(format!("leaf chunk {i} differs")));
+    }
+    Ok(IndexCheck::Consistent { events, pending })
+}
+
+// ---------------------------------------------------------------------------
+// Event store
+// ---------------------------------------------------------------------------
+
+/// [`EventStore`] over the event shards, answering membership from the
+/// index and parsing only the events a sync sends.
+pub struct ShardEventStore<'a> {
+    index: &'a ProllyIndex,
+    shards: ShardManager,
+}
+
+impl<'a> ShardEventStore<'a> {
+    /// Store over `bones_dir`'s shards, which `index` must be up to date
+    /// with (see [`refresh`]).
+    #[must_use]
+    pub fn new(bones_dir: &Path, index: &'a ProllyIndex) -> Self {
+        Self {
+            index,
+            shards: ShardManager::new(bones_dir),
+        }
+    }
+}
+
+impl EventStore for ShardEventStore<'_> {
+    type Error = IndexError;
+
+    fn contains(&self, event: &Event) -> bool {
+        self.index.contains(&IndexEntry::from_event(event))
+    }
+
+    fn load(&mut self, event_hashes: &HashSet<&str>) -> Result<Vec<Event>, Self::Error> {
+        let mut events = Vec::with_capacity(event_hashes.len());
+        for line in self.shards.replay_lines()? {
+            let (offset, line) = line?;
+            let Ok(Some(entry)) = IndexEntry::from_line(&line) else {
+                continue;
+            };
+            if !event_hashes.contains(entry.event_hash.as_str()) {
+                continue;
+            }
+            match parse_line(line.trim_end_matches(['\n', '\r'])) {
+                Ok(ParsedLine::Event(event)) => events.push(*event),
+                Ok(_) => {}
+                Err(source) => return Err(IndexError::Parse { offset, source }),
+            }
+            if events.len() == event_hashes.len() {
+                break;
+            }
+        }
+        Ok(events)
+    }
+}
+
+/// Path of the index file for a `.bones` directory.
+#[must_use]
+pub fn index_path(bones_dir: &Path) -> PathBuf {
+    bones_dir.join(INDEX_PATH)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::EventType;
+    use crate::event::data::{CreateData, EventData};
+    use crate::event::writer::write_event;
+    use crate::model::item::{Kind, Urgency};
+    use crate::model::item_id::ItemId;
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    fn make_event(item: &str, ts: i64) -> Event {
+        let mut event = Event {
+            wall_ts_us: ts,
+            agent: "alice".to_string(),
+            itc: "itc:AQ".to_string(),
+            parents: vec![],
+            event_type: EventType::Create,
+            item_id: ItemId::new_unchecked(item),
+            data: EventData::Create(CreateData {
+                title: format!("Item {item}"),
+                kind: Kind::Task,
+                size: None,
+                urgency: Urgency::Default,
+                labels: vec![],
+                parent: None,
+                causation: None,
+                description: None,
+                extra: BTreeMap::new(),
+            }),
+            event_hash: String::new(),
+            signature: None,
+        };
+        write_event(&mut event).expect("hash");
+        event
+    }
+
+    fn events(range: std::ops::Range<i64>) -> Vec<Event> {
+        range
+            .map(|i| make_event(&format!("bn-{:03}", i % 97), i))
+            .collect()
+    }
+
+    fn append(shards: &ShardManager, events: &[Event]) {
+        for event in events {
+            let line = crate::event::writer::write_line(event).expect("line");
+            shards
+                .append(&line, false, Duration::from_secs(1))
+                .expect("append");
+        }
+    }
+
+    #[test]
+    fn incremental_inserts_match_a_full_build() {
+        let all = events(0..3000);
+        let mut index = ProllyIndex::default();
+        // Interleaved batches land all over the key space.
+        for batch in [0..1, 1..700, 700..710, 710..2000, 2000..3000] {
+            let slice: Vec<Event> = all[batch].iter().rev().step_by(1).cloned().collect();
+            let before = index.event_count();
+            let inserted = index.insert(slice.iter().map(IndexEntry::from_event).collect());
+            assert_eq!(inserted, slice.len());
+            assert_eq!(index.event_count(), before + slice.len());
+
+            let indexed: Vec<Event> = all[..index.event_count()].to_vec();
+            assert_eq!(
+                index.tree().root.hash(),
+                ProllyTree::build(&indexed).root.hash()
+            );
+        }
+
+        // Re-inserting is a no-op.
+        assert_eq!(index.insert(vec![IndexEntry::from_event(&all[5])]), 0);
+        assert!(index.contains(&IndexEntry::from_event(&all[2999])));
+        assert!(!index.contains(&IndexEntry::from_event(&make_event("bn-zzz", 1))));
+    }
+
+    #[test]
+    fn restricted_index_matches_a_tree_of_the_selected_items() {
+        let all = events(0..500);
+        let items: BTreeSet<String> = ["bn-003", "bn-042"].map(String::from).into();
+        let selected: Vec<Event> = all
+            .iter()
+            .filter(|e| items.contains(e.item_id.as_str()))
+            .cloned()
+            .collect();
+
+        let restricted = ProllyIndex::from_events(&all).restricted(&items);
+        assert_eq!(restricted.event_count(), selected.len());
+        assert_eq!(
+            restricted.tree().root.hash(),
+            ProllyTree::build(&selected).root.hash()
+        );
+    }
+
+    #[test]
+    fn append_journal_keeps_the_index_current() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let bones_dir = dir.path().join(".bones");
+        let shards = ShardManager::new(&bones_dir);
+        shards.init().expect("init");
+        let all = events(0..300);
+        append(&shards, &all[..200]);
+
+        // No index yet: appends leave no journal.
+        assert!(!bones_dir.join(JOURNAL_PATH).exists());
+        let (index, report) = refresh(&bones_dir).expect("refresh");
+        assert!(report.rebuilt.is_some());
+        assert_eq!(index.event_count(), 200);
+
+        append(&shards, &all[200..]);
+        assert!(bones_dir.join(JOURNAL_PATH).exists());
+        let loaded = ProllyIndex::load(&bones_dir).expect("load").expect("index");
+        assert_eq!(loaded.event_count(), 300);
+        assert_eq!(
+            loaded.tree().root.hash(),
+            ProllyTree::build(&all).root.hash()
+        );
+
+        let (index, report) = refresh(&bones_dir).expect("refresh");
+        assert_eq!(report.caught_up, 0);
+        assert_eq!(report.rebuilt, None);
+        assert_eq!(index.event_count(), 300);
+        assert!(!bones_dir.join(JOURNAL_PATH).exists());
+        assert_eq!(
+            check(&bones_dir).expect("check"),
+            IndexCheck::Consistent {
+                events: 300,
+                pending: 0
+            }
+        );
+    }
+
+    #[test]
+    fn appends_to_an_earlier_shard_are_not_journaled() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let bones_dir = dir.path().join(".bones");
+        let shards = ShardManager::new(&bones_dir);
+        shards.ensure_dirs().expect("dirs");
+        shards.create_shard(2026, 1).expect("create");
+        shards.create_shard(2026, 2).expect("create");
+        let all = events(0..30);
+        let line = |event: &Event| crate::event::writer::write_line(event).expect("line");
+        for event in &all[..10] {
+            shards.append_raw(2026, 1, &line(event)).expect("append");
+        }
+        for event in &all[10..20] {
+            shards.append_raw(2026, 2, &line(event)).expect("append");
+        }
+        refresh(&bones_dir).expect("refresh");
+
+        // The tail shard journals at the end of the concatenated shards.
+        shards.append_raw(2026, 2, &line(&all[20])).expect("append");
+        let journal = fs::read_to_string(bones_dir.join(JOURNAL_PATH)).expect("journal");
+        let (start, end, _) = parse_journal_line(journal.trim_end()).expect("record");
+        assert_eq!(end, shards.total_content_len().expect("len"));
+        assert_eq!(start, end - line(&all[20]).len());
+
+        // An earlier shard does not: the line shifts the tail.
+        for event in &all[21..] {
+            shards.append_raw(2026, 1, &line(event)).expect("append");
+        }
+        let journal = fs::read_to_string(bones_dir.join(JOURNAL_PATH)).expect("journal");
+        assert_eq!(journal.lines().count(), 1);
+
+        let (index, report) = refresh(&bones_dir).expect("refresh");
+        assert!(report.rebuilt.is_some());
+        assert_eq!(
+            index.tree().root.hash(),
+            ProllyTree::build(&all).root.hash()
+        );
+    }
+
+    #[test]
+    fn refresh_catches_up_and_detects_rewritten_shards() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let bones_dir = dir.path().join(".bones");
+        let shards = ShardManager::new(&bones_dir);
+        let (year, month) = shards.init().expect("init");
+        let all = events(0..50);
+        append(&shards, &all[..40]);
+        refresh(&bones_dir).expect("refresh");
+
+        // Lines that arrive without going through the shard manager (a git
+        // pull).
+        let mut shard = OpenOptions::new()
+            .append(true)
+            .open(shards.shard_path(year, month))
+            .expect("open shard");
+        for event in &all[40..] {
+            let line = crate::event::writer::write_line(event).expect("line");
+            shard.write_all(line.as_bytes()).expect("append");
+        }
+        assert_eq!(
+            check(&bones_dir).expect("check"),
+            IndexCheck::Consistent {
+                events: 40,
+                pending: 10
+            }
+        );
+        let (index, report) = refresh(&bones_dir).expect("refresh");
+        assert_eq!(report.caught_up, 10);
+        assert_eq!(
+            index.tree().root.hash(),
+            ProllyTree::build(&all).root.hash()
+        );
+
+        // Rewrite the shard under the index.
+        let path = shards.shard_path(year, month);
+        let content = fs::read_to_string(&path).expect("read");
+        let mut lines: Vec<&str> = content.lines().collect();
+        lines.pop();
+        fs::write(&path, lines.join("\n") + "\n").expect("write");
+        assert!(matches!(
+            check(&bones_dir).expect("check"),
+            IndexCheck::Mismatch(_)
+        ));
+        let (index, report) = refresh(&bones_dir).expect("refresh");
+        assert!(report.rebuilt.is_some());
+        assert_eq!(index.event_count(), 49);
+    }
+
+    #[test]
+    fn check_flags_a_tampered_index() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let bones_dir = dir.path().join(".bones");
+        let shards = ShardManager::new(&bones_dir);
+        shards.init().expect("init");
+        append(&shards, &events(0..30));
+        refresh(&bones_dir).expect("refresh");
+
+        let path = bones_dir.join(INDEX_PATH);
+        let text = fs::read_to_string(&path).expect("read");
+        let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+        let victim = lines.len() - 1;
+        lines.remove(victim);
+        fs::write(&path, lines.join("\n") + "\n").expect("write");
+
+        assert!(matches!(
+            check(&bones_dir).expect("check"),
+            IndexCheck::Mismatch(_)
+        ));
+    }
+
+    #[test]
+    fn shard_store_loads_only_requested_events() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let bones_dir = dir.path().join(".bones");
+        let shards = ShardManager::new(&bones_dir);
+        shards.init().expect("init");
+        let all = events(0..20);
+        append(&shards, &all);
+        let (index, _) = refresh(&bones_dir).expect("refresh");
+
+        let mut store = ShardEventStore::new(&bones_dir, &index);
+        let wanted: HashSet<&str> = [all[3].event_hash.as_str(), all[17].event_hash.as_str()]
+            .into_iter()
+            .collect();
+        let loaded = store.load(&wanted).expect("load");
+        assert_eq!(loaded, vec![all[3].clone(), all[17].clone()]);
+        assert!(store.contains(&all[0]));
+        assert!(!store.contains(&make_event("bn-new", 99)));
+    }
+}
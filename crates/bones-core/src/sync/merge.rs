@@ -29,6 +29,7 @@
 use std::collections::HashSet;
 
 use crate::event::Event;
+use crate::policy::{Policy, Rejection};
 
 // ---------------------------------------------------------------------------
 // Public types
@@ -49,6 +50,16 @@ pub struct MergeResult {
     pub duplicates_skipped: usize,
 }
 
+/// The result of a merge whose incoming events were screened by a
+/// [`Policy`].
+#[derive(Debug, Clone)]
+pub struct ScreenedMerge {
+    /// Merge of `local` with the accepted incoming events.
+    pub merge: MergeResult,
+    /// Incoming events the policy refused; the caller quarantines them.
+    pub rejected: Vec<Rejection>,
+}
+
 // ---------------------------------------------------------------------------
 // Public API
 // ---------------------------------------------------------------------------
@@ -119,6 +130,40 @@ pub fn merge_event_sets(local: &[Event], remote: &[Event]) -> MergeResult {
     }
 }
 
+/// Merge like [`merge_event_sets`], but only admit `remote` events that
+/// `policy` accepts.
+///
+/// `local` events are trusted: they were written or admitted by this replica
+/// already. Remote events `local` lacks are checked against the policy, and
+/// refused ones are returned instead of merged.
+#[must_use]
+pub fn merge_event_sets_with_policy(
+    local: &[Event],
+    remote: &[Event],
+    policy: &Policy,
+) -> ScreenedMerge {
+    if policy.is_empty() {
+        return ScreenedMerge {
+            merge: merge_event_sets(local, remote),
+            rejected: Vec::new(),
+        };
+    }
+    let local_hashes: HashSet<&str> = local
+        .iter()
+        .map(|event| event.event_hash.as_str())
+        .collect();
+    let (known, incoming): (Vec<Event>, Vec<Event>) = remote
+        .iter()
+        .cloned()
+        .partition(|event| local_hashes.contains(event.event_hash.as_str()));
+    let (mut accepted, rejected) = policy.partition(incoming);
+    accepted.extend(known);
+    ScreenedMerge {
+        merge: merge_event_sets(local, &accepted),
+        rejected,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -148,9 +193,11 @@ mod tests {
             item_id: ItemId::new_unchecked("bn-a7x"),
             data: EventData::Comment(CommentData {
                 body: format!("Event {hash_suffix}"),
+                reply_to: None,
                 extra: BTreeMap::new(),
             }),
             event_hash: format!("blake3:{hash_suffix}"),
+            signature: None,
         }
     }
 
@@ -174,6 +221,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: format!("blake3:{hash_suffix}"),
+            signature: None,
         }
     }
 
@@ -188,9 +236,11 @@ mod tests {
             data: EventData::Move(MoveData {
                 state: State::Doing,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             event_hash: format!("blake3:{hash_suffix}"),
+            signature: None,
         }
     }
 
@@ -436,4 +486,33 @@ mod tests {
         let h2: Vec<&str> = r2.events.iter().map(|e| e.event_hash.as_str()).collect();
         assert_eq!(h1, h2, "merge should be idempotent");
     }
+
+    #[test]
+    fn policy_screens_only_events_local_lacks() {
+        let policy = Policy::parse("[[rule]]\nevents = [\"item.comment\"]\nagents = [\"alice\"]\n")
+            .expect("policy");
+        let local = vec![make_event(1, "bob", "aa")];
+        let remote = vec![
+            make_event(1, "bob", "aa"),
+            make_event(2, "alice", "bb"),
+            make_event(3, "bob", "cc"),
+        ];
+
+        let screened = merge_event_sets_with_policy(&local, &remote, &policy);
+        let hashes: Vec<&str> = screened
+            .merge
+            .events
+            .iter()
+            .map(|e| e.event_hash.as_str())
+            .collect();
+        // `aa` is already local, so it is trusted even though bob is not
+        // allowed; alice's `bb` is unsigned, so it is refused like bob's `cc`.
+        assert_eq!(hashes, vec!["blake3:aa"]);
+        let rejected: Vec<&str> = screened
+            .rejected
+            .iter()
+            .map(|r| r.event.event_hash.as_str())
+            .collect();
+        assert_eq!(rejected, vec!["blake3:bb", "blake3:cc"]);
+    }
 }
@@ -0,0 +1,558 @@
+//! Semantic conflict reports written by the git merge driver.
+//!
+//! The merge driver unions both sides of an `.events` shard, which always
+//! succeeds, so contradictory changes merge silently: two branches moving the
+//! same item to different states, or re-parenting it differently, converge
+//! on whichever write wins the LWW tie-break. After the union the driver
+//! replays both sides from their lowest common ancestor
+//! ([`crate::dag::replay`]) and records every field that both sides wrote
+//! with different values in a JSON report under `.bones/merge-reports/`.
+//!
+//! Reports stay *pending* until acknowledged, which moves them into
+//! `merge-reports/acknowledged/`. `bn status` lists pending reports and
+//! `bn status --ack <id>` acknowledges them.
+//!
+//! Report IDs are derived from the overlapping writes, so the same merge
+//! replayed twice (e.g. during a rebase) produces the same report instead of
+//! a duplicate, and an acknowledged report is not raised again.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::conflicts::{FieldWrite, lww_writes};
+use crate::dag::graph::EventDag;
+use crate::dag::replay::replay_divergent_for_item;
+use crate::event::{Event, EventData};
+
+/// Directory under `.bones/` holding pending merge reports.
+pub const MERGE_REPORTS_DIR: &str = "merge-reports";
+
+/// Sub-directory of [`MERGE_REPORTS_DIR`] holding acknowledged reports.
+pub const ACKNOWLEDGED_DIR: &str = "acknowledged";
+
+// ---------------------------------------------------------------------------
+// Error types
+// ---------------------------------------------------------------------------
+
+/// Errors that can occur while reading, writing, or acknowledging reports.
+#[derive(Debug, thiserror::Error)]
+pub enum MergeReportError {
+    /// I/O error on a report file or directory.
+    #[error("merge report I/O error at {path}: {source}")]
+    Io {
+        /// The file or directory involved.
+        path: PathBuf,
+        /// The underlying error.
+        source: io::Error,
+    },
+
+    /// A report file is not valid JSON.
+    #[error("invalid merge report {path}: {source}")]
+    Parse {
+        /// The offending report file.
+        path: PathBuf,
+        /// The underlying error.
+        source: serde_json::Error,
+    },
+
+    /// No pending report has the given ID.
+    #[error("no pending merge report with id '{0}'")]
+    NotFound(String),
+}
+
+// ---------------------------------------------------------------------------
+// Report types
+// ---------------------------------------------------------------------------
+
+/// A field both sides of a merge wrote with different values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OverlappingWrite {
+    pub item_id: String,
+    /// The field, e.g. `state`, `parent`, `title`, or a custom field.
+    pub field: String,
+    /// The last write to the field on our side since the common ancestor.
+    pub ours: FieldWrite,
+    /// The last write to the field on their side since the common ancestor.
+    pub theirs: FieldWrite,
+}
+
+/// A machine-readable report of one merge's overlapping field writes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeReport {
+    /// Content-derived ID, also the report's file stem.
+    pub id: String,
+    /// When the merge driver wrote the report.
+    pub created_at_us: i64,
+    /// Overlapping writes, ordered by item and field.
+    pub conflicts: Vec<OverlappingWrite>,
+}
+
+impl MergeReport {
+    /// Build a report for `conflicts`, deriving its ID from their content.
+    #[must_use]
+    pub fn new(conflicts: Vec<OverlappingWrite>, created_at_us: i64) -> Self {
+        let mut hasher = blake3::Hasher::new();
+        for conflict in &conflicts {
+            for part in [
+                &conflict.item_id,
+                &conflict.field,
+                &conflict.ours.event_hash,
+                &conflict.theirs.event_hash,
+            ] {
+                hasher.update(part.as_bytes());
+                hasher.update(b"\n");
+            }
+        }
+        let id = hasher.finalize().to_hex()[..12].to_string();
+        Self {
+            id,
+            created_at_us,
+            conflicts,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Detection
+// ---------------------------------------------------------------------------
+
+/// Find fields that `ours` and `theirs` both wrote since `base` with
+/// different final values.
+///
+/// For every item both sides touched, each pair of per-side tips is replayed
+/// from its LCA with [`replay_divergent_for_item`], so writes one side had
+/// already seen are not counted against it. When a pair has no common
+/// ancestor in the DAG (events whose `parents` do not reach back into the
+/// shard), the side's events that `base` lacks are used instead — git's
+/// merge base is itself the common ancestor of the two shard versions.
+#[must_use]
+pub fn detect_overlapping_writes(
+    base: &[Event],
+    ours: &[Event],
+    theirs: &[Event],
+) -> Vec<OverlappingWrite> {
+    let base_hashes: HashSet<&str> = base.iter().map(|e| e.event_hash.as_str()).collect();
+    let ours_new = new_events_by_item(ours, &base_hashes);
+    let theirs_new = new_events_by_item(theirs, &base_hashes);
+
+    let mut dag = EventDag::with_capacity(base.len() + ours.len() + theirs.len());
+    for event in base.iter().chain(ours).chain(theirs) {
+        dag.insert(event.clone());
+    }
+
+    let mut overlaps = Vec::new();
+    for (item_id, ours_events) in &ours_new {
+        let Some(theirs_events) = theirs_new.get(item_id) else {
+            continue;
+        };
+        let (ours_side, theirs_side) =
+            replay_item(&dag, item_id, ours_events, theirs_events, &base_hashes);
+        let ours_fields = last_writes(&ours_side);
+        let theirs_fields = last_writes(&theirs_side);
+        for (field, ours_write) in ours_fields {
+            let Some(theirs_write) = theirs_fields.get(&field) else {
+                continue;
+            };
+            if ours_write.value != theirs_write.value {
+                overlaps.push(OverlappingWrite {
+                    item_id: item_id.clone(),
+                    field,
+                    ours: ours_write,
+                    theirs: theirs_write.clone(),
+                });
+            }
+        }
+    }
+    overlaps
+}
+
+/// Events not in `base`, grouped by item.
+fn new_events_by_item<'a>(
+    events: &'a [Event],
+    base_hashes: &HashSet<&str>,
+) -> BTreeMap<String, Vec<&'a Event>> {
+    let mut by_item: BTreeMap<String, Vec<&Event>> = BTreeMap::new();
+    for event in events {
+        if !base_hashes.contains(event.event_hash.as_str()) {
+            by_item
+                .entry(event.item_id.as_str().to_string())
+                .or_default()
+                .push(event);
+        }
+    }
+    by_item
+}
+
+/// Replay one item's divergent events, returning each side's exclusive
+/// events in replay order.
+fn replay_item(
+    dag: &EventDag,
+    item_id: &str,
+    ours: &[&Event],
+    theirs: &[&Event],
+    base_hashes: &HashSet<&str>,
+) -> (Vec<Event>, Vec<Event>) {
+    let is_new = |e: &Event| !base_hashes.contains(e.event_hash.as_str());
+    let mut ours_side: BTreeMap<(i64, String, String), Event> = BTreeMap::new();
+    let mut theirs_side: BTreeMap<(i64, String, String), Event> = BTreeMap::new();
+    for tip_a in side_tips(ours) {
+        for tip_b in side_tips(theirs) {
+            let Ok(replay) = replay_divergent_for_item(dag, tip_a, tip_b, item_id) else {
+                return (in_replay_order(ours), in_replay_order(theirs));
+            };
+            for event in replay.branch_a.into_iter().filter(is_new) {
+                ours_side.insert(replay_key(&event), event);
+            }
+            for event in replay.branch_b.into_iter().filter(is_new) {
+                theirs_side.insert(replay_key(&event), event);
+            }
+        }
+    }
+    (
+        ours_side.into_values().collect(),
+        theirs_side.into_values().collect(),
+    )
+}
+
+/// Events of one side that no other event of that side lists as a parent.
+fn side_tips<'a>(events: &[&'a Event]) -> Vec<&'a str> {
+    let referenced: HashSet<&str> = events
+        .iter()
+        .flat_map(|e| e.parents.iter().map(String::as_str))
+        .collect();
+    events
+        .iter()
+        .map(|e| e.event_hash.as_str())
+        .filter(|hash| !referenced.contains(hash))
+        .collect()
+}
+
+fn in_replay_order(events: &[&Event]) -> Vec<Event> {
+    let mut sorted: Vec<Event> = events.iter().map(|e| (*e).clone()).collect();
+    sorted.sort_by_key(replay_key);
+    sorted
+}
+
+fn replay_key(event: &Event) -> (i64, String, String) {
+    (
+        event.wall_ts_us,
+        event.agent.clone(),
+        event.event_hash.clone(),
+    )
+}
+
+/// The final write to each field in a replayed event sequence.
+fn last_writes(events: &[Event]) -> BTreeMap<String, FieldWrite> {
+    let mut fields = BTreeMap::new();
+    for event in events {
+        for (field, value) in field_writes(event) {
+            fields.insert(
+                field,
+                FieldWrite {
+                    event_hash: event.event_hash.clone(),
+                    agent: event.agent.clone(),
+                    wall_ts_us: event.wall_ts_us,
+                    itc: event.itc.clone(),
+                    value,
+                },
+            );
+        }
+    }
+    fields
+}
+
+/// LWW field writes plus the workflow state set by `item.move`.
+///
+/// A move into a configured sub-state is recorded by the sub-state name, so
+/// moving to `doing/review` on one side and `doing/qa` on the other counts
+/// as a contradiction.
+fn field_writes(event: &Event) -> Vec<(String, Value)> {
+    match &event.data {
+        EventData::Move(d) => {
+            let state = d.substate.clone().unwrap_or_else(|| d.state.to_string());
+            vec![("state".to_string(), Value::from(state))]
+        }
+        _ => lww_writes(event),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Storage
+// ---------------------------------------------------------------------------
+
+/// Directory of pending reports for a `.bones` directory.
+#[must_use]
+pub fn reports_dir(bones_dir: &Path) -> PathBuf {
+    bones_dir.join(MERGE_REPORTS_DIR)
+}
+
+/// Write `report` as `merge-reports/<id>.json` and return its path.
+///
+/// Returns `Ok(None)` without writing when a report with the same ID was
+/// already acknowledged.
+///
+/// # Errors
+///
+/// Returns [`MergeReportError::Io`] if the report cannot be written.
+pub fn write_report(
+    bones_dir: &Path,
+    report: &MergeReport,
+) -> Result<Option<PathBuf>, MergeReportError> {
+    let dir = reports_dir(bones_dir);
+    let file_name = format!("{}.json", report.id);
+    if dir.join(ACKNOWLEDGED_DIR).join(&file_name).exists() {
+        return Ok(None);
+    }
+    fs::create_dir_all(&dir).map_err(|source| MergeReportError::Io {
+        path: dir.clone(),
+        source,
+    })?;
+    let path = dir.join(file_name);
+    let json = serde_json::to_string_pretty(report).map_err(|source| MergeReportError::Parse {
+        path: path.clone(),
+        source,
+    })?;
+    fs::write(&path, json + "\n").map_err(|source| MergeReportError::Io {
+        path: path.clone(),
+        source,
+    })?;
+    Ok(Some(path))
+}
+
+/// Pending (unacknowledged) reports, oldest first.
+///
+/// # Errors
+///
+/// Returns [`MergeReportError`] if the directory or a report cannot be read.
+pub fn pending_reports(bones_dir: &Path) -> Result<Vec<MergeReport>, MergeReportError> {
+    let dir = reports_dir(bones_dir);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => return Err(MergeReportError::Io { path: dir, source }),
+    };
+    let mut reports = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|source| MergeReportError::Io {
+                path: dir.clone(),
+                source,
+            })?
+            .path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let content = fs::read_to_string(&path).map_err(|source| MergeReportError::Io {
+            path: path.clone(),
+            source,
+        })?;
+        let report: MergeReport = serde_json::from_str(&content)
+            .map_err(|source| MergeReportError::Parse { path, source })?;
+        reports.push(report);
+    }
+    reports.sort_by(|a, b| {
+        a.created_at_us
+            .cmp(&b.created_at_us)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    Ok(reports)
+}
+
+/// Acknowledge the pending report `id` by moving it to
+/// `merge-reports/acknowledged/`.
+///
+/// # Errors
+///
+/// Returns [`MergeReportError::NotFound`] if no pending report has that ID,
+/// or [`MergeReportError::Io`] if it cannot be moved.
+pub fn acknowledge(bones_dir: &Path, id: &str) -> Result<(), MergeReportError> {
+    let dir = reports_dir(bones_dir);
+    let file_name = format!("{id}.json");
+    let pending = dir.join(&file_name);
+    if id.is_empty() || id.contains(['/', '\\', '.']) || !pending.is_file() {
+        return Err(MergeReportError::NotFound(id.to_string()));
+    }
+    let acknowledged = dir.join(ACKNOWLEDGED_DIR);
+    fs::create_dir_all(&acknowledged).map_err(|source| MergeReportError::Io {
+        path: acknowledged.clone(),
+        source,
+    })?;
+    fs::rename(&pending, acknowledged.join(file_name)).map_err(|source| MergeReportError::Io {
+        path: pending,
+        source,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::EventType;
+    use crate::event::data::{CreateData, MoveData, UpdateData};
+    use crate::event::writer::write_event;
+    use crate::model::item::{Kind, State, Urgency};
+    use crate::model::item_id::ItemId;
+    use tempfile::TempDir;
+
+    fn make_event(ts: i64, agent: &str, parents: &[&Event], data: EventData) -> Event {
+        let event_type = match &data {
+            EventData::Create(_) => EventType::Create,
+            EventData::Move(_) => EventType::Move,
+            EventData::Update(_) => EventType::Update,
+            _ => unreachable!("tests only build creates, moves, and updates"),
+        };
+        let mut event = Event {
+            wall_ts_us: ts,
+            agent: agent.to_string(),
+            itc: "itc:AQ".to_string(),
+            parents: parents.iter().map(|p| p.event_hash.clone()).collect(),
+            event_type,
+            item_id: ItemId::new_unchecked("bn-a7x"),
+            data,
+            event_hash: String::new(),
+            signature: None,
+        };
+        write_event(&mut event).expect("hash");
+        event
+    }
+
+    fn create() -> Event {
+        make_event(
+            1_000,
+            "alice",
+            &[],
+            EventData::Create(CreateData {
+                title: "Shared".to_string(),
+                kind: Kind::Task,
+                size: None,
+                urgency: Urgency::Default,
+                labels: vec![],
+                parent: None,
+                causation: None,
+                description: None,
+                extra: BTreeMap::new(),
+            }),
+        )
+    }
+
+    fn move_to(ts: i64, agent: &str, parent: &Event, state: State) -> Event {
+        make_event(
+            ts,
+            agent,
+            &[parent],
+            EventData::Move(MoveData {
+                state,
+                substate: None,
+                reason: None,
+                extra: BTreeMap::new(),
+            }),
+        )
+    }
+
+    fn set_parent(ts: i64, agent: &str, parent: &Event, value: &str) -> Event {
+        make_event(
+            ts,
+            agent,
+            &[parent],
+            EventData::Update(UpdateData {
+                field: "parent".to_string(),
+                value: Value::from(value),
+                extra: BTreeMap::new(),
+            }),
+        )
+    }
+
+    #[test]
+    fn contradictory_moves_and_reparents_are_reported() {
+        let root = create();
+        let ours_move = move_to(2_000, "alice", &root, State::Doing);
+        let ours_parent = set_parent(2_500, "alice", &ours_move, "bn-g1");
+        let theirs_move = move_to(3_000, "bob", &root, State::Done);
+        let theirs_parent = set_parent(3_500, "bob", &theirs_move, "bn-g2");
+
+        let overlaps = detect_overlapping_writes(
+            std::slice::from_ref(&root),
+            &[root.clone(), ours_move.clone(), ours_parent],
+            &[root.clone(), theirs_move.clone(), theirs_parent],
+        );
+
+        let fields: Vec<&str> = overlaps.iter().map(|o| o.field.as_str()).collect();
+        assert_eq!(fields, ["parent", "state"]);
+        let state = &overlaps[1];
+        assert_eq!(state.item_id, "bn-a7x");
+        assert_eq!(state.ours.event_hash, ours_move.event_hash);
+        assert_eq!(state.ours.value, Value::from("doing"));
+        assert_eq!(state.theirs.event_hash, theirs_move.event_hash);
+        assert_eq!(state.theirs.value, Value::from("done"));
+    }
+
+    #[test]
+    fn agreeing_or_disjoint_writes_are_not_reported() {
+        let root = create();
+        let ours_move = move_to(2_000, "alice", &root, State::Done);
+        let theirs_move = move_to(3_000, "bob", &root, State::Done);
+        let theirs_parent = set_parent(3_500, "bob", &theirs_move, "bn-g2");
+
+        let overlaps = detect_overlapping_writes(
+            std::slice::from_ref(&root),
+            &[root.clone(), ours_move],
+            &[root.clone(), theirs_move, theirs_parent],
+        );
+        assert!(overlaps.is_empty());
+    }
+
+    #[test]
+    fn writes_one_side_already_merged_are_not_reported() {
+        let root = create();
+        let first = move_to(2_000, "alice", &root, State::Doing);
+        // Theirs saw `first` before moving the item on.
+        let later = move_to(3_000, "bob", &first, State::Done);
+
+        let overlaps = detect_overlapping_writes(
+            std::slice::from_ref(&root),
+            &[root.clone(), first.clone()],
+            &[root.clone(), first, later],
+        );
+        assert!(overlaps.is_empty());
+    }
+
+    #[test]
+    fn reports_are_written_listed_and_acknowledged() {
+        let dir = TempDir::new().expect("tempdir");
+        let root = create();
+        let overlaps = detect_overlapping_writes(
+            std::slice::from_ref(&root),
+            &[root.clone(), move_to(2_000, "alice", &root, State::Doing)],
+            &[root.clone(), move_to(3_000, "bob", &root, State::Done)],
+        );
+        let report = MergeReport::new(overlaps, 5_000);
+
+        let path = write_report(dir.path(), &report)
+            .expect("write")
+            .expect("path");
+        assert!(path.starts_with(reports_dir(dir.path())));
+        let pending = pending_reports(dir.path()).expect("list");
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, report.id);
+        assert_eq!(pending[0].conflicts[0].theirs.value, Value::from("done"));
+
+        acknowledge(dir.path(), &report.id).expect("ack");
+        assert!(pending_reports(dir.path()).expect("list").is_empty());
+        assert!(matches!(
+            acknowledge(dir.path(), &report.id),
+            Err(MergeReportError::NotFound(_))
+        ));
+
+        // Replaying the same merge does not raise an acknowledged report again.
+        assert_eq!(write_report(dir.path(), &report).expect("write"), None);
+        assert!(pending_reports(dir.path()).expect("list").is_empty());
+    }
+}
@@ -2,15 +2,30 @@
 //!
 //! This module provides:
 //!
+//! - [`bundle`] — self-contained bundle files for offline (air-gapped) sync.
+//! - [`filter`] — replication filters for partial replicas.
+//! - [`framed`] — [`protocol::SyncTransport`] over a framed byte stream.
+//! - [`git_ref`] — event log kept on `refs/bones/events` instead of branches.
+//! - [`index`] — persisted prolly tree leaves, updated as events are appended.
 //! - [`merge`] — logic for combining divergent `.events` shard files.
+//! - [`merge_report`] — overlapping field writes found by the git merge driver.
 //! - [`prolly`] — content-defined Merkle tree for O(log N) event set diffing.
-//! - [`protocol`] — transport-agnostic 3-round sync protocol built on prolly trees.
+//! - [`protocol`] — transport-agnostic sync protocol that walks prolly trees level by level.
 //!
 //! The prolly tree and protocol modules are **library APIs** for external sync
 //! tools. bones does not own transport — tools like `maw`, custom MCP servers,
 //! or direct TCP/HTTP services implement [`protocol::SyncTransport`] and call
-//! the sync functions.
+//! the sync functions. Two transports are built in: [`framed`], which
+//! `bn sync --remote` runs over the stdin/stdout of `bn sync-serve --stdio`
+//! (typically under SSH), and [`bundle`], a file format for replicas that
+//! cannot reach each other at all (`bn bundle`).
 
+pub mod bundle;
+pub mod filter;
+pub mod framed;
+pub mod git_ref;
+pub mod index;
 pub mod merge;
+pub mod merge_report;
 pub mod prolly;
 pub mod protocol;
@@ -3,11 +3,13 @@
 //! Events are keyed by `(item_id, wall_ts_us)`, sorted, then split into chunks
 //! using a rolling hash (Gear hash) for content-defined boundaries. A balanced
 //! Merkle tree is built over the chunks so that two replicas can diff in
-//! O(log N) time by comparing hashes top-down.
+//! O(log N) time by comparing hashes top-down. [`ProllyTree::diff`] does this
+//! with both trees in hand; [`TreeWalk`] does it across the wire, one level
+//! per round, for [`crate::sync::protocol`].
 //!
 //! This is a **library module** intended for use by external sync tools (e.g.
-//! transport layers over TCP, HTTP, MCP, or file-based exchange). It is not
-//! exposed as a CLI command — bones does not own transport.
+//! transport layers over TCP, HTTP, MCP, or file-based exchange). In the CLI
+//! it backs `bn sync --remote` and `bn bundle`.
 //!
 //! # Usage
 //!
@@ -36,7 +38,9 @@
 
 use blake3::Hasher as Blake3;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fmt;
+use std::sync::LazyLock;
 
 use crate::event::Event;
 
@@ -80,6 +84,8 @@ fn gear_table() -> [u64; 256] {
     table
 }
 
+static GEAR_TABLE: LazyLock<[u64; 256]> = LazyLock::new(gear_table);
+
 // ---------------------------------------------------------------------------
 // Hash newtype
 // ---------------------------------------------------------------------------
@@ -100,6 +106,15 @@ impl fmt::Display for Hash {
     }
 }
 
+impl Hash {
+    /// Parse a hash from its 64-digit hex [`Display`](fmt::Display) form.
+    #[must_use]
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let bytes = hex::decode(hex.trim())?;
+        bytes.try_into().ok().map(Self)
+    }
+}
+
 fn hash_bytes(data: &[u8]) -> Hash {
     Hash(*blake3::hash(data).as_bytes())
 }
@@ -147,6 +162,15 @@ impl ProllyNode {
         *hash
     }
 
+    /// Child nodes of an interior node; empty for a leaf.
+    #[must_use]
+    pub fn children(&self) -> &[Self] {
+        match self {
+            Self::Leaf { .. } => &[],
+            Self::Interior { children, .. } => children,
+        }
+    }
+
     /// Collect all event hashes reachable from this node.
     pub fn collect_event_hashes(&self, out: &mut Vec<String>) {
         match self {
@@ -185,14 +209,7 @@ impl ProllyTree {
     #[must_use]
     pub fn build(events: &[Event]) -> Self {
         if events.is_empty() {
-            let empty_hash = hash_bytes(b"prolly:empty");
-            return Self {
-                root: ProllyNode::Leaf {
-                    hash: empty_hash,
-                    event_hashes: vec![],
-                },
-                event_count: 0,
-            };
+            return Self::from_leaves(vec![], 0);
         }
 
         // Sort events deterministically.
@@ -212,6 +229,23 @@ impl ProllyTree {
         }
     }
 
+    /// Assemble a tree from already-chunked leaves, in key order.
+    pub(crate) fn from_leaves(leaves: Vec<ProllyNode>, event_count: usize) -> Self {
+        if leaves.is_empty() {
+            return Self {
+                root: ProllyNode::Leaf {
+                    hash: hash_bytes(b"prolly:empty"),
+                    event_hashes: vec![],
+                },
+                event_count: 0,
+            };
+        }
+        Self {
+            root: build_interior(leaves),
+            event_count,
+        }
+    }
+
     /// Compute the diff between `self` (local) and `other` (remote).
     ///
     /// Returns event hashes that are in `other` but not in `self`.
@@ -223,6 +257,26 @@ impl ProllyTree {
         missing
     }
 
+    /// Start a level-by-level walk of this tree against a remote replica.
+    /// See [`TreeWalk`].
+    #[must_use]
+    pub fn walk(&self) -> TreeWalk<'_> {
+        TreeWalk::new(self)
+    }
+
+    /// Number of node levels from the root down to the leaves (1 for a
+    /// single-leaf tree).
+    #[must_use]
+    pub fn height(&self) -> usize {
+        let mut height = 1;
+        let mut node = &self.root;
+        while let Some(child) = node.children().first() {
+            height += 1;
+            node = child;
+        }
+        height
+    }
+
     /// All event hashes stored in this tree.
     #[must_use]
     pub fn event_hashes(&self) -> Vec<String> {
@@ -261,39 +315,43 @@ impl ProllyTree {
 
 /// Split a sorted list of event hashes into content-defined leaf chunks.
 fn chunk_leaf(event_hashes: &[String]) -> Vec<ProllyNode> {
-    let table = gear_table();
     let mut chunks = Vec::new();
-    let mut chunk_start = 0;
-    let mut gear: u64 = 0;
+    let mut rest = event_hashes;
+    while !rest.is_empty() {
+        let len = leaf_boundary(rest.iter().map(String::as_str)).unwrap_or(rest.len());
+        let (chunk, tail) = rest.split_at(len);
+        chunks.push(ProllyNode::Leaf {
+            hash: hash_leaf_chunk(chunk.iter().map(String::as_str)),
+            event_hashes: chunk.to_vec(),
+        });
+        rest = tail;
+    }
+    chunks
+}
 
-    for (i, eh) in event_hashes.iter().enumerate() {
+/// Length of the first leaf chunk of a sorted run of event hashes that
+/// starts on a chunk boundary, or `None` if the run ends before a boundary
+/// fires (the final chunk of a tree simply ends with its events).
+pub(crate) fn leaf_boundary<'a>(event_hashes: impl IntoIterator<Item = &'a str>) -> Option<usize> {
+    let table = &*GEAR_TABLE;
+    let mut gear: u64 = 0;
+    for (i, eh) in event_hashes.into_iter().enumerate() {
         // Feed event hash bytes into the Gear hash.
         for &b in eh.as_bytes() {
             gear = (gear << 1).wrapping_add(table[b as usize]);
         }
 
-        let chunk_len = i - chunk_start + 1;
-
+        let chunk_len = i + 1;
         let at_boundary = chunk_len >= MIN_CHUNK_SIZE && (gear & BOUNDARY_MASK) == 0;
         let at_max = chunk_len >= MAX_CHUNK_SIZE;
-        let at_end = i == event_hashes.len() - 1;
-
-        if at_boundary || at_max || at_end {
-            let slice = &event_hashes[chunk_start..=i];
-            let hash = hash_leaf_chunk(slice);
-            chunks.push(ProllyNode::Leaf {
-                hash,
-                event_hashes: slice.to_vec(),
-            });
-            chunk_start = i + 1;
-            gear = 0;
+        if at_boundary || at_max {
+            return Some(chunk_len);
         }
     }
-
-    chunks
+    None
 }
 
-fn hash_leaf_chunk(event_hashes: &[String]) -> Hash {
+pub(crate) fn hash_leaf_chunk<'a>(event_hashes: impl IntoIterator<Item = &'a str>) -> Hash {
     let mut hasher = Blake3::new();
     hasher.update(b"prolly:leaf:");
     for eh in event_hashes {
@@ -314,7 +372,7 @@ fn build_interior(mut nodes: Vec<ProllyNode>) -> ProllyNode {
     }
 
     // Group nodes into interior chunks using Gear hash on child hashes.
-    let table = gear_table();
+    let table = &*GEAR_TABLE;
     let mut groups: Vec<Vec<ProllyNode>> = Vec::new();
     let mut current_group: Vec<ProllyNode> = Vec::new();
     let mut gear: u64 = 0;
@@ -431,6 +489,123 @@ fn diff_nodes(local: &ProllyNode, other: &ProllyNode, missing: &mut Vec<String>)
     }
 }
 
+// ---------------------------------------------------------------------------
+// Level-by-level walk
+// ---------------------------------------------------------------------------
+
+/// A node still under comparison, with a link to the expanded parent it came
+/// from.
+#[derive(Debug, Clone, Copy)]
+struct WalkEntry<'a> {
+    node: &'a ProllyNode,
+    parent: Option<usize>,
+}
+
+/// One replica's side of a top-down tree comparison.
+///
+/// Both replicas walk their own trees in lock step. Each round, a side sends
+/// the hashes of the nodes one level below its unresolved nodes
+/// ([`expand`](Self::expand)) and feeds the hashes the peer sent into
+/// [`receive`](Self::receive). A node is resolved once the peer has sent its
+/// hash, or the hash of one of its ancestors: the peer then holds that whole
+/// subtree. Subtrees shared by both replicas are never descended into, so a
+/// walk costs O(k log N) hashes for k differing chunks.
+///
+/// The walk ends when both sides expand to nothing in the same round. The
+/// leaves still unresolved then hold every event the peer may be missing
+/// ([`unresolved_event_hashes`](Self::unresolved_event_hashes)).
+#[derive(Debug)]
+pub struct TreeWalk<'a> {
+    /// Unresolved nodes whose hashes the peer has already been sent.
+    pending: Vec<WalkEntry<'a>>,
+    /// Hashes of expanded nodes, with their parent's index.
+    expanded: Vec<(Hash, Option<usize>)>,
+    /// Every node hash the peer has sent.
+    peer_seen: HashSet<Hash>,
+}
+
+impl<'a> TreeWalk<'a> {
+    /// Start a walk at `tree`'s root. The root hash itself is exchanged
+    /// separately, so the first [`expand`](Self::expand) returns the level
+    /// below it.
+    #[must_use]
+    pub fn new(tree: &'a ProllyTree) -> Self {
+        Self {
+            pending: vec![WalkEntry {
+                node: &tree.root,
+                parent: None,
+            }],
+            expanded: Vec::new(),
+            peer_seen: HashSet::new(),
+        }
+    }
+
+    /// Replace every unresolved interior node by its children and return the
+    /// children's hashes, to be sent to the peer. Returns an empty list once
+    /// only leaves remain.
+    pub fn expand(&mut self) -> Vec<Hash> {
+        let mut sent = Vec::new();
+        let mut next = Vec::with_capacity(self.pending.len());
+        for entry in std::mem::take(&mut self.pending) {
+            let children = entry.node.children();
+            if children.is_empty() {
+                next.push(entry);
+                continue;
+            }
+            let index = self.expanded.len();
+            self.expanded.push((entry.node.hash(), entry.parent));
+            for child in children {
+                let hash = child.hash();
+                sent.push(hash);
+                // The peer still needs this hash to resolve its own copy, but
+                // a node it has already sent is resolved on our side.
+                if !self.peer_seen.contains(&hash) {
+                    next.push(WalkEntry {
+                        node: child,
+                        parent: Some(index),
+                    });
+                }
+            }
+        }
+        self.pending = next;
+        sent
+    }
+
+    /// Record node hashes sent by the peer and drop every unresolved node the
+    /// peer is now known to hold.
+    pub fn receive(&mut self, peer_hashes: &[Hash]) {
+        self.peer_seen.extend(peer_hashes.iter().copied());
+        let mut pending = std::mem::take(&mut self.pending);
+        pending.retain(|entry| !self.is_shared(entry));
+        self.pending = pending;
+    }
+
+    /// Event hashes under the nodes that are still unresolved.
+    #[must_use]
+    pub fn unresolved_event_hashes(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        for entry in &self.pending {
+            entry.node.collect_event_hashes(&mut out);
+        }
+        out
+    }
+
+    fn is_shared(&self, entry: &WalkEntry<'_>) -> bool {
+        if self.peer_seen.contains(&entry.node.hash()) {
+            return true;
+        }
+        let mut parent = entry.parent;
+        while let Some(index) = parent {
+            let (hash, up) = self.expanded[index];
+            if self.peer_seen.contains(&hash) {
+                return true;
+            }
+            parent = up;
+        }
+        false
+    }
+}
+
 // ---------------------------------------------------------------------------
 // hex helper (avoid adding a crate dep for this)
 // ---------------------------------------------------------------------------
@@ -445,6 +620,20 @@ mod hex {
         }
         s
     }
+
+    pub fn decode(text: &str) -> Option<Vec<u8>> {
+        if !text.len().is_multiple_of(2) {
+            return None;
+        }
+        text.as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                let hi = char::from(pair[0]).to_digit(16)?;
+                let lo = char::from(pair[1]).to_digit(16)?;
+                u8::try_from(hi << 4 | lo).ok()
+            })
+            .collect()
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -480,6 +669,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: format!("blake3:{item_id}_{ts}_{hash_suffix}"),
+            signature: None,
         }
     }
 
@@ -739,6 +929,85 @@ mod tests {
         assert!(e1.diff(&e2).is_empty());
     }
 
+    /// Walk two trees in lock step and return each side's unresolved event
+    /// hashes plus the number of node hashes exchanged.
+    fn walk_pair(a: &ProllyTree, b: &ProllyTree) -> (Vec<String>, Vec<String>, usize) {
+        let mut walk_a = a.walk();
+        let mut walk_b = b.walk();
+        walk_a.receive(&[b.root.hash()]);
+        walk_b.receive(&[a.root.hash()]);
+        let mut exchanged = 2;
+        loop {
+            let from_a = walk_a.expand();
+            let from_b = walk_b.expand();
+            exchanged += from_a.len() + from_b.len();
+            if from_a.is_empty() && from_b.is_empty() {
+                break;
+            }
+            walk_a.receive(&from_b);
+            walk_b.receive(&from_a);
+        }
+        (
+            walk_a.unresolved_event_hashes(),
+            walk_b.unresolved_event_hashes(),
+            exchanged,
+        )
+    }
+
+    #[test]
+    fn walk_prunes_shared_subtrees() {
+        let shared: Vec<Event> = (0..3000)
+            .map(|i| make_event(&format!("w{i:05}"), i, &format!("w{i}")))
+            .collect();
+        let mut a = shared.clone();
+        a.push(make_event("w01500x", 1500, "a"));
+        let b = shared;
+
+        let tree_a = ProllyTree::build(&a);
+        let tree_b = ProllyTree::build(&b);
+        assert!(tree_a.height() >= 3);
+
+        let (unresolved_a, unresolved_b, exchanged) = walk_pair(&tree_a, &tree_b);
+        assert!(unresolved_a.contains(&"blake3:w01500x_1500_a".to_string()));
+        // Only the chunks around the new event remain, not the whole tree.
+        assert!(
+            unresolved_a.len() < 600,
+            "{} unresolved",
+            unresolved_a.len()
+        );
+        assert!(
+            unresolved_b.len() < 600,
+            "{} unresolved",
+            unresolved_b.len()
+        );
+        assert!(exchanged < 300, "{exchanged} node hashes exchanged");
+    }
+
+    #[test]
+    fn walk_resolves_a_tree_nested_in_the_peer() {
+        // `small`'s root is a single leaf that also appears in `large`.
+        let large: Vec<Event> = (0..2000)
+            .map(|i| make_event(&format!("n{i:05}"), i, &format!("n{i}")))
+            .collect();
+        let large_tree = ProllyTree::build(&large);
+        let ProllyNode::Interior { children, .. } = &large_tree.root else {
+            panic!("expected an interior root");
+        };
+        let mut first_leaf = &children[0];
+        while let Some(child) = first_leaf.children().first() {
+            first_leaf = child;
+        }
+        let mut leaf_hashes = Vec::new();
+        first_leaf.collect_event_hashes(&mut leaf_hashes);
+        let small: Vec<Event> = large[..leaf_hashes.len()].to_vec();
+        let small_tree = ProllyTree::build(&small);
+        assert_eq!(small_tree.root.hash(), first_leaf.hash());
+
+        let (unresolved_large, unresolved_small, _) = walk_pair(&large_tree, &small_tree);
+        assert!(unresolved_small.is_empty());
+        assert_eq!(unresolved_large.len(), large.len() - small.len());
+    }
+
     #[test]
     fn serialization_preserves_event_count() {
         let events: Vec<Event> = (0..50)
@@ -1,20 +1,29 @@
 //! Prolly Tree sync protocol for non-git event replication.
 //!
-//! Two replicas exchange Prolly Tree root hashes, identify divergent subtrees
-//! in O(log N), and transfer only the missing events.
+//! Two replicas exchange Prolly Tree root hashes, walk both trees top-down to
+//! find the subtrees that differ, and transfer only the missing events.
 //!
 //! The protocol is transport-agnostic: any type implementing [`SyncTransport`]
 //! can be used (TCP, HTTP, MCP, USB drive via file exchange, etc.).
 //!
 //! This is a **library module** — bones does not own transport. External tools
 //! implement [`SyncTransport`] for their chosen medium and call [`sync`] /
-//! [`serve_sync`] to run the 3-round protocol.
+//! [`serve_sync`] to run the protocol.
 //!
 //! # Protocol rounds
 //!
 //! 1. **Root hash exchange** — if hashes match, replicas are identical (fast path).
-//! 2. **Event hash exchange** — each side sends its full event hash list.
-//! 3. **Event transfer** — each side sends events the other is missing.
+//! 2. **Tree walk** — one round per tree level. Each side sends the child
+//!    hashes of its nodes the peer has not matched yet (see
+//!    [`TreeWalk`](crate::sync::prolly::TreeWalk)); shared subtrees are
+//!    pruned. The walk ends when neither
+//!    side has anything left to expand.
+//! 3. **Leaf exchange** — each side sends the event hashes under its
+//!    unmatched leaves.
+//! 4. **Event transfer** — each side sends events the other is missing.
+//!
+//! A sync of k differing chunks in a tree of N events therefore costs about
+//! `log N + 3` rounds and O(k log N) hashes, instead of every event hash.
 //!
 //! # Example (in-memory, for testing)
 //!
@@ -39,6 +48,9 @@ use std::collections::HashSet;
 use crate::event::Event;
 use crate::sync::prolly::{Hash, ProllyTree};
 
+/// Wire size of one root or node hash.
+const NODE_HASH_SIZE: usize = 32;
+
 // ---------------------------------------------------------------------------
 // Transport trait
 // ---------------------------------------------------------------------------
@@ -66,6 +78,21 @@ pub trait SyncTransport {
     /// Returns `Self::Error` if the receive fails.
     fn recv_hash(&mut self) -> Result<Hash, Self::Error>;
 
+    /// Send one level of Prolly Tree node hashes during the tree walk. The
+    /// list may be empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Self::Error` if the send fails.
+    fn send_node_hashes(&mut self, hashes: &[Hash]) -> Result<(), Self::Error>;
+
+    /// Receive one level of Prolly Tree node hashes from the remote.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Self::Error` if the receive fails.
+    fn recv_node_hashes(&mut self) -> Result<Vec<Hash>, Self::Error>;
+
     /// Send a list of event hashes that we want the remote to check.
     ///
     /// # Errors
@@ -106,9 +133,11 @@ pub struct SyncReport {
     pub events_sent: usize,
     /// Number of events received from the remote.
     pub events_received: usize,
-    /// Total bytes transferred (approximate, based on serialized event size).
+    /// Total bytes exchanged in both directions: root and node hashes, event
+    /// hash lists, and events (events are estimated from their size).
     pub bytes_transferred: usize,
-    /// Number of hash-exchange rounds used during the diff phase.
+    /// Number of request/response rounds, including the root exchange, every
+    /// tree level walked, and the event transfer.
     pub rounds: usize,
 }
 
@@ -118,8 +147,95 @@ impl SyncReport {
     pub const fn is_noop(&self) -> bool {
         self.events_sent == 0 && self.events_received == 0
     }
+
+    const fn empty() -> Self {
+        Self {
+            events_sent: 0,
+            events_received: 0,
+            bytes_transferred: 0,
+            rounds: 0,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Event stores
+// ---------------------------------------------------------------------------
+
+/// Access to the events under a replica's Prolly tree.
+///
+/// The protocol only needs whole events for the ones it sends, so a store
+/// backed by a persisted index (see [`crate::sync::index`]) lets a replica
+/// sync without loading its full event log.
+pub trait EventStore {
+    /// Error type for loading events.
+    type Error: std::fmt::Debug + std::fmt::Display;
+
+    /// Whether the store already holds `event`.
+    fn contains(&self, event: &Event) -> bool;
+
+    /// Load the events with the given hashes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Self::Error` if the events cannot be read.
+    fn load(&mut self, event_hashes: &HashSet<&str>) -> Result<Vec<Event>, Self::Error>;
+}
+
+/// [`EventStore`] over events held in memory.
+#[derive(Debug)]
+pub struct SliceStore<'a> {
+    events: &'a [Event],
+    hashes: HashSet<&'a str>,
+}
+
+impl<'a> SliceStore<'a> {
+    /// Wrap an event slice.
+    #[must_use]
+    pub fn new(events: &'a [Event]) -> Self {
+        Self {
+            events,
+            hashes: events.iter().map(|e| e.event_hash.as_str()).collect(),
+        }
+    }
+}
+
+impl EventStore for SliceStore<'_> {
+    type Error = std::convert::Infallible;
+
+    fn contains(&self, event: &Event) -> bool {
+        self.hashes.contains(event.event_hash.as_str())
+    }
+
+    fn load(&mut self, event_hashes: &HashSet<&str>) -> Result<Vec<Event>, Self::Error> {
+        Ok(self
+            .events
+            .iter()
+            .filter(|e| event_hashes.contains(e.event_hash.as_str()))
+            .cloned()
+            .collect())
+    }
+}
+
+/// Errors from [`sync_with_store`] and [`serve_sync_with_store`].
+#[derive(Debug, thiserror::Error)]
+pub enum SyncError<T, S> {
+    /// A transport operation failed.
+    #[error("{0}")]
+    Transport(T),
+
+    /// Local events could not be loaded from the store.
+    #[error("failed to load local events: {0}")]
+    Store(S),
 }
 
+/// Result of [`sync_with_store`] and [`serve_sync_with_store`]: the events
+/// received and a report, or the failure.
+pub type StoreSyncResult<T, S> = Result<
+    (Vec<Event>, SyncReport),
+    SyncError<<T as SyncTransport>::Error, <S as EventStore>::Error>,
+>;
+
 // ---------------------------------------------------------------------------
 // Sync function
 // ---------------------------------------------------------------------------
@@ -131,9 +247,10 @@ impl SyncReport {
 /// 1. Build a Prolly Tree from `local_events`.
 /// 2. Exchange root hashes with the remote.
 /// 3. If hashes match: replicas are identical — return early.
-/// 4. Diff the trees to identify event hashes missing from each side.
-/// 5. Send local events that the remote is missing.
-/// 6. Receive remote events that we are missing.
+/// 4. Walk both trees level by level until the differing leaves are known.
+/// 5. Exchange the event hashes under those leaves.
+/// 6. Send local events that the remote is missing and receive the ones we
+///    are missing.
 /// 7. Return a [`SyncReport`] summarising the exchange.
 ///
 /// After sync, the caller is responsible for persisting the received events
@@ -146,82 +263,17 @@ pub fn sync<T: SyncTransport>(
     local_events: &[Event],
     transport: &mut T,
 ) -> Result<(Vec<Event>, SyncReport), T::Error> {
-    let local_tree = ProllyTree::build(local_events);
-    let mut report = SyncReport {
-        events_sent: 0,
-        events_received: 0,
-        bytes_transferred: 0,
-        rounds: 0,
-    };
-
-    // Round 1: exchange root hashes.
-    transport.send_hash(&local_tree.root.hash())?;
-    let remote_root_hash = transport.recv_hash()?;
-    report.rounds += 1;
-
-    // Fast path: if root hashes match, replicas are identical.
-    if local_tree.root.hash() == remote_root_hash {
-        return Ok((vec![], report));
-    }
-
-    // Round 2: exchange event hash lists for diff.
-    // Send our event hashes so the remote can figure out what we're missing.
-    let local_hashes = local_tree.event_hashes();
-    transport.send_event_hashes(&local_hashes)?;
-
-    // Receive the remote's event hashes so we know what they have.
-    let remote_hashes = transport.recv_event_hashes()?;
-    report.rounds += 1;
-
-    // Compute what's missing on each side.
-    let local_set: HashSet<&str> = local_hashes
-        .iter()
-        .map(std::string::String::as_str)
-        .collect();
-    let remote_set: HashSet<&str> = remote_hashes
-        .iter()
-        .map(std::string::String::as_str)
-        .collect();
-
-    // Event hashes the remote has that we don't.
-    let need_from_remote: HashSet<&str> = remote_hashes
-        .iter()
-        .map(std::string::String::as_str)
-        .filter(|h| !local_set.contains(h))
-        .collect();
-
-    // Round 3: exchange missing events.
-    // Send our events that the remote lacks.
-    let events_to_send: Vec<Event> = local_events
-        .iter()
-        .filter(|e| !remote_set.contains(e.event_hash.as_str()))
-        .cloned()
-        .collect();
-    let send_size: usize = events_to_send.iter().map(estimate_event_size).sum();
-    transport.send_events(&events_to_send)?;
-    report.events_sent = events_to_send.len();
-    report.bytes_transferred += send_size;
-
-    // Receive events from the remote that we lack.
-    let received = transport.recv_events()?;
-    let recv_size: usize = received.iter().map(estimate_event_size).sum();
-    report.bytes_transferred += recv_size;
-    report.rounds += 1;
-
-    // Filter received events to only those we actually need (defence in depth).
-    let new_events: Vec<Event> = received
-        .into_iter()
-        .filter(|e| need_from_remote.contains(e.event_hash.as_str()))
-        .collect();
-    report.events_received = new_events.len();
-
-    Ok((new_events, report))
+    let tree = ProllyTree::build(local_events);
+    run_protocol(&tree, &mut SliceStore::new(local_events), transport, true).map_err(|e| match e {
+        SyncError::Transport(e) => e,
+        SyncError::Store(never) => match never {},
+    })
 }
 
 /// Respond to a sync request as the remote side.
 ///
-/// This is the mirror of [`sync`]: it receives the initiator's data and
-/// sends back what they need.
+/// This is the mirror of [`sync`]: in every round it receives the
+/// initiator's message before sending its own.
 ///
 /// # Errors
 ///
@@ -230,66 +282,119 @@ pub fn serve_sync<T: SyncTransport>(
     local_events: &[Event],
     transport: &mut T,
 ) -> Result<(Vec<Event>, SyncReport), T::Error> {
-    let local_tree = ProllyTree::build(local_events);
-    let local_hashes = local_tree.event_hashes();
-    let mut report = SyncReport {
-        events_sent: 0,
-        events_received: 0,
-        bytes_transferred: 0,
-        rounds: 0,
-    };
-
-    // Round 1: exchange root hashes (receive first, then send).
-    let remote_root_hash = transport.recv_hash()?;
-    transport.send_hash(&local_tree.root.hash())?;
-    report.rounds += 1;
+    let tree = ProllyTree::build(local_events);
+    run_protocol(&tree, &mut SliceStore::new(local_events), transport, false).map_err(|e| match e {
+        SyncError::Transport(e) => e,
+        SyncError::Store(never) => match never {},
+    })
+}
 
-    // Fast path.
-    if local_tree.root.hash() == remote_root_hash {
-        return Ok((vec![], report));
-    }
+/// Like [`sync`], but starting from a prebuilt tree.
+///
+/// The tree typically comes from a persisted
+/// [`ProllyIndex`](crate::sync::index::ProllyIndex); events are loaded from
+/// `store` only when they must be sent.
+///
+/// # Errors
+///
+/// Returns [`SyncError`] if a transport operation or an event load fails.
+pub fn sync_with_store<T: SyncTransport, S: EventStore>(
+    tree: &ProllyTree,
+    store: &mut S,
+    transport: &mut T,
+) -> StoreSyncResult<T, S> {
+    run_protocol(tree, store, transport, true)
+}
 
-    // Round 2: exchange event hash lists.
-    let remote_hashes = transport.recv_event_hashes()?;
-    transport.send_event_hashes(&local_hashes)?;
-    report.rounds += 1;
+/// The serving side of [`sync_with_store`].
+///
+/// # Errors
+///
+/// Returns [`SyncError`] if a transport operation or an event load fails.
+pub fn serve_sync_with_store<T: SyncTransport, S: EventStore>(
+    tree: &ProllyTree,
+    store: &mut S,
+    transport: &mut T,
+) -> StoreSyncResult<T, S> {
+    run_protocol(tree, store, transport, false)
+}
 
-    // Compute diffs.
-    let local_set: HashSet<&str> = local_hashes
-        .iter()
-        .map(std::string::String::as_str)
-        .collect();
-    let remote_set: HashSet<&str> = remote_hashes
-        .iter()
-        .map(std::string::String::as_str)
-        .collect();
+fn run_protocol<T: SyncTransport, S: EventStore>(
+    local_tree: &ProllyTree,
+    store: &mut S,
+    transport: &mut T,
+    initiator: bool,
+) -> StoreSyncResult<T, S> {
+    let local_root = local_tree.root.hash();
+    let mut report = SyncReport::empty();
 
-    let need_from_remote: HashSet<&str> = remote_hashes
-        .iter()
-        .map(std::string::String::as_str)
-        .filter(|h| !local_set.contains(h))
-        .collect();
+    // Round 1: exchange root hashes.
+    let remote_root = exchange(
+        transport,
+        initiator,
+        |t| t.send_hash(&local_root),
+        T::recv_hash,
+    )
+    .map_err(SyncError::Transport)?;
+    report.rounds += 1;
+    report.bytes_transferred += 2 * NODE_HASH_SIZE;
 
-    let to_send: Vec<Event> = local_events
-        .iter()
-        .filter(|e| !remote_set.contains(e.event_hash.as_str()))
-        .cloned()
-        .collect();
+    // Fast path: if root hashes match, replicas are identical.
+    if local_root == remote_root {
+        return Ok((vec![], report));
+    }
 
-    // Round 3: exchange events (receive first, then send).
-    let received = transport.recv_events()?;
-    let recv_size: usize = received.iter().map(estimate_event_size).sum();
-    report.bytes_transferred += recv_size;
+    // Walk both trees one level per round until neither side can descend.
+    let mut walk = local_tree.walk();
+    walk.receive(&[remote_root]);
+    loop {
+        let ours = walk.expand();
+        let theirs = exchange(
+            transport,
+            initiator,
+            |t| t.send_node_hashes(&ours),
+            T::recv_node_hashes,
+        )
+        .map_err(SyncError::Transport)?;
+        report.rounds += 1;
+        report.bytes_transferred += (ours.len() + theirs.len()) * NODE_HASH_SIZE;
+        if ours.is_empty() && theirs.is_empty() {
+            break;
+        }
+        walk.receive(&theirs);
+    }
 
-    let send_size: usize = to_send.iter().map(estimate_event_size).sum();
-    transport.send_events(&to_send)?;
-    report.events_sent = to_send.len();
-    report.bytes_transferred += send_size;
+    // Exchange the event hashes under the leaves that are still unmatched.
+    let local_hashes = walk.unresolved_event_hashes();
+    let remote_hashes = exchange(
+        transport,
+        initiator,
+        |t| t.send_event_hashes(&local_hashes),
+        T::recv_event_hashes,
+    )
+    .map_err(SyncError::Transport)?;
+    report.rounds += 1;
+    report.bytes_transferred += hash_list_size(&local_hashes) + hash_list_size(&remote_hashes);
+
+    // Exchange the events each side is missing.
+    let (to_send, offered) =
+        settle(store, &local_hashes, &remote_hashes).map_err(SyncError::Store)?;
+    let received = exchange(
+        transport,
+        initiator,
+        |t| t.send_events(&to_send),
+        T::recv_events,
+    )
+    .map_err(SyncError::Transport)?;
     report.rounds += 1;
+    report.bytes_transferred += to_send.iter().map(estimate_event_size).sum::<usize>()
+        + received.iter().map(estimate_event_size).sum::<usize>();
+    report.events_sent = to_send.len();
 
+    // Filter received events to only those we actually need (defence in depth).
     let new_events: Vec<Event> = received
         .into_iter()
-        .filter(|e| need_from_remote.contains(e.event_hash.as_str()))
+        .filter(|e| offered.contains(e.event_hash.as_str()) && !store.contains(e))
         .collect();
     report.events_received = new_events.len();
 
@@ -300,6 +405,51 @@ pub fn serve_sync<T: SyncTransport>(
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Run one round: the initiator sends first, the server receives first.
+fn exchange<T: SyncTransport, V>(
+    transport: &mut T,
+    initiator: bool,
+    send: impl FnOnce(&mut T) -> Result<(), T::Error>,
+    recv: impl FnOnce(&mut T) -> Result<V, T::Error>,
+) -> Result<V, T::Error> {
+    if initiator {
+        send(transport)?;
+        recv(transport)
+    } else {
+        let value = recv(transport)?;
+        send(transport)?;
+        Ok(value)
+    }
+}
+
+/// Load the local events the remote lacks, given both sides' unmatched leaf
+/// event hashes. Also returns the remote's hashes: received events must be
+/// among them, and may still be ones we hold in a subtree whose chunking
+/// differs on our side.
+fn settle<'a, S: EventStore>(
+    store: &mut S,
+    local_hashes: &[String],
+    remote_hashes: &'a [String],
+) -> Result<(Vec<Event>, HashSet<&'a str>), S::Error> {
+    let remote_set: HashSet<&str> = remote_hashes.iter().map(String::as_str).collect();
+    let wanted: HashSet<&str> = local_hashes
+        .iter()
+        .map(String::as_str)
+        .filter(|h| !remote_set.contains(h))
+        .collect();
+    let to_send = if wanted.is_empty() {
+        Vec::new()
+    } else {
+        store.load(&wanted)?
+    };
+    Ok((to_send, remote_set))
+}
+
+/// Wire size of an event hash list (newline-separated).
+fn hash_list_size(hashes: &[String]) -> usize {
+    hashes.iter().map(|h| h.len() + 1).sum()
+}
+
 /// Rough estimate of serialized event size (for reporting, not billing).
 fn estimate_event_size(event: &Event) -> usize {
     // event_hash + agent + itc + item_id + overhead
@@ -324,6 +474,10 @@ pub struct InMemoryTransport {
     tx_hashes: Vec<Hash>,
     /// Incoming hash queue.
     rx_hashes: Vec<Hash>,
+    /// Outgoing node-hash-list queue.
+    tx_node_hash_lists: Vec<Vec<Hash>>,
+    /// Incoming node-hash-list queue.
+    rx_node_hash_lists: Vec<Vec<Hash>>,
     /// Outgoing event-hash-list queue.
     tx_event_hash_lists: Vec<Vec<String>>,
     /// Incoming event-hash-list queue.
@@ -350,6 +504,8 @@ impl InMemoryTransport {
         Self {
             tx_hashes: Vec::new(),
             rx_hashes: Vec::new(),
+            tx_node_hash_lists: Vec::new(),
+            rx_node_hash_lists: Vec::new(),
             tx_event_hash_lists: Vec::new(),
             rx_event_hash_lists: Vec::new(),
             tx_events: Vec::new(),
@@ -361,11 +517,13 @@ impl InMemoryTransport {
     pub fn wire(a: &mut Self, b: &mut Self) {
         // Move A's sent data to B's receive queues.
         b.rx_hashes.append(&mut a.tx_hashes);
+        b.rx_node_hash_lists.append(&mut a.tx_node_hash_lists);
         b.rx_event_hash_lists.append(&mut a.tx_event_hash_lists);
         b.rx_events.append(&mut a.tx_events);
 
         // Move B's sent data to A's receive queues.
         a.rx_hashes.append(&mut b.tx_hashes);
+        a.rx_node_hash_lists.append(&mut b.tx_node_hash_lists);
         a.rx_event_hash_lists.append(&mut b.tx_event_hash_lists);
         a.rx_events.append(&mut b.tx_events);
     }
@@ -386,6 +544,18 @@ impl SyncTransport for InMemoryTransport {
         Ok(self.rx_hashes.remove(0))
     }
 
+    fn send_node_hashes(&mut self, hashes: &[Hash]) -> Result<(), Self::Error> {
+        self.tx_node_hash_lists.push(hashes.to_vec());
+        Ok(())
+    }
+
+    fn recv_node_hashes(&mut self) -> Result<Vec<Hash>, Self::Error> {
+        if self.rx_node_hash_lists.is_empty() {
+            return Err(InMemoryError("no node hash list to receive".into()));
+        }
+        Ok(self.rx_node_hash_lists.remove(0))
+    }
+
     fn send_event_hashes(&mut self, hashes: &[String]) -> Result<(), Self::Error> {
         self.tx_event_hash_lists.push(hashes.to_vec());
         Ok(())
@@ -418,7 +588,7 @@ impl SyncTransport for InMemoryTransport {
 /// Run a full sync between two event sets using in-memory transport.
 ///
 /// Returns the new events each side received and their respective reports.
-/// This simulates the 3-round protocol by manually wiring each round.
+/// This simulates the protocol by manually wiring each round.
 ///
 /// # Errors
 ///
@@ -443,65 +613,70 @@ pub fn sync_in_memory(
     InMemoryTransport::wire(&mut local_tx, &mut remote_tx);
 
     let remote_root = local_tx.recv_hash()?;
-    let _local_root = remote_tx.recv_hash()?;
+    let local_root = remote_tx.recv_hash()?;
 
     let mut rounds = 1;
+    let mut bytes = 2 * NODE_HASH_SIZE;
 
     // Fast path: identical.
-    if local_tree.root.hash() == remote_root {
+    if local_root == remote_root {
+        let report = SyncReport {
+            rounds,
+            bytes_transferred: bytes,
+            ..SyncReport::empty()
+        };
         return Ok(SyncInMemoryResult {
             local_received: vec![],
             remote_received: vec![],
-            local_report: SyncReport {
-                events_sent: 0,
-                events_received: 0,
-                bytes_transferred: 0,
-                rounds,
-            },
-            remote_report: SyncReport {
-                events_sent: 0,
-                events_received: 0,
-                bytes_transferred: 0,
-                rounds,
-            },
+            local_report: report.clone(),
+            remote_report: report,
         });
     }
 
-    // --- Round 2: event hash exchange ---
-    let local_hashes = local_tree.event_hashes();
-    let remote_hashes = remote_tree.event_hashes();
+    // --- Tree walk: one round per level ---
+    let mut local_walk = local_tree.walk();
+    let mut remote_walk = remote_tree.walk();
+    local_walk.receive(&[remote_root]);
+    remote_walk.receive(&[local_root]);
+    loop {
+        let local_nodes = local_walk.expand();
+        let remote_nodes = remote_walk.expand();
+        local_tx.send_node_hashes(&local_nodes)?;
+        remote_tx.send_node_hashes(&remote_nodes)?;
+        InMemoryTransport::wire(&mut local_tx, &mut remote_tx);
+
+        let from_remote = local_tx.recv_node_hashes()?;
+        let from_local = remote_tx.recv_node_hashes()?;
+        rounds += 1;
+        bytes += (local_nodes.len() + remote_nodes.len()) * NODE_HASH_SIZE;
+        if from_remote.is_empty() && from_local.is_empty() {
+            break;
+        }
+        local_walk.receive(&from_remote);
+        remote_walk.receive(&from_local);
+    }
+
+    // --- Leaf event hash exchange ---
+    let local_hashes = local_walk.unresolved_event_hashes();
+    let remote_hashes = remote_walk.unresolved_event_hashes();
 
     local_tx.send_event_hashes(&local_hashes)?;
     remote_tx.send_event_hashes(&remote_hashes)?;
     InMemoryTransport::wire(&mut local_tx, &mut remote_tx);
 
-    let _remote_hash_list = local_tx.recv_event_hashes()?;
-    let _local_hash_list = remote_tx.recv_event_hashes()?;
+    let remote_hash_list = local_tx.recv_event_hashes()?;
+    let local_hash_list = remote_tx.recv_event_hashes()?;
     rounds += 1;
+    bytes += hash_list_size(&local_hashes) + hash_list_size(&remote_hashes);
 
-    // Compute diffs.
-    let local_set: HashSet<&str> = local_hashes
-        .iter()
-        .map(std::string::String::as_str)
-        .collect();
-    let remote_set: HashSet<&str> = remote_hashes
-        .iter()
-        .map(std::string::String::as_str)
-        .collect();
+    let mut local_store = SliceStore::new(local_events);
+    let mut remote_store = SliceStore::new(remote_events);
+    let Ok((local_to_send, local_offered)) =
+        settle(&mut local_store, &local_hashes, &remote_hash_list);
+    let Ok((remote_to_send, remote_offered)) =
+        settle(&mut remote_store, &remote_hashes, &local_hash_list);
 
-    let local_to_send: Vec<Event> = local_events
-        .iter()
-        .filter(|e| !remote_set.contains(e.event_hash.as_str()))
-        .cloned()
-        .collect();
-
-    let remote_to_send: Vec<Event> = remote_events
-        .iter()
-        .filter(|e| !local_set.contains(e.event_hash.as_str()))
-        .cloned()
-        .collect();
-
-    // --- Round 3: event exchange ---
+    // --- Final round: event exchange ---
     let local_send_size: usize = local_to_send.iter().map(estimate_event_size).sum();
     let remote_send_size: usize = remote_to_send.iter().map(estimate_event_size).sum();
 
@@ -509,21 +684,30 @@ pub fn sync_in_memory(
     remote_tx.send_events(&remote_to_send)?;
     InMemoryTransport::wire(&mut local_tx, &mut remote_tx);
 
-    let local_received = local_tx.recv_events()?;
-    let remote_received = remote_tx.recv_events()?;
+    let local_received: Vec<Event> = local_tx
+        .recv_events()?
+        .into_iter()
+        .filter(|e| local_offered.contains(e.event_hash.as_str()) && !local_store.contains(e))
+        .collect();
+    let remote_received: Vec<Event> = remote_tx
+        .recv_events()?
+        .into_iter()
+        .filter(|e| remote_offered.contains(e.event_hash.as_str()) && !remote_store.contains(e))
+        .collect();
     rounds += 1;
+    bytes += local_send_size + remote_send_size;
 
     Ok(SyncInMemoryResult {
         local_report: SyncReport {
             events_sent: local_to_send.len(),
             events_received: local_received.len(),
-            bytes_transferred: local_send_size + remote_send_size,
+            bytes_transferred: bytes,
             rounds,
         },
         remote_report: SyncReport {
             events_sent: remote_to_send.len(),
             events_received: remote_received.len(),
-            bytes_transferred: local_send_size + remote_send_size,
+            bytes_transferred: bytes,
             rounds,
         },
         local_received,
@@ -578,6 +762,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: format!("blake3:{item_id}_{ts}_{hash_suffix}"),
+            signature: None,
         }
     }
 
@@ -716,7 +901,82 @@ mod tests {
         let result = sync_in_memory(&a, &b).unwrap();
         assert_eq!(result.local_received.len(), 50);
         assert_eq!(result.remote_received.len(), 50);
-        assert_eq!(result.local_report.rounds, 3);
+
+        // Root, one round per level below it, an empty closing round, the
+        // leaf exchange and the event transfer.
+        let height = ProllyTree::build(&a)
+            .height()
+            .max(ProllyTree::build(&b).height());
+        assert_eq!(result.local_report.rounds, height + 3);
+    }
+
+    #[test]
+    fn sync_small_divergence_sends_few_hashes() {
+        let shared: Vec<Event> = (0..20_000)
+            .map(|i| make_event(&format!("s{i:05}"), i, &format!("s{i}")))
+            .collect();
+        let mut a = shared.clone();
+        a.push(make_event("s05000x", 5000, "a"));
+        let mut b = shared;
+        b.push(make_event("s15000x", 15_000, "b"));
+
+        let result = sync_in_memory(&a, &b).unwrap();
+        assert_eq!(result.local_received.len(), 1);
+        assert_eq!(result.remote_received.len(), 1);
+        assert_eq!(result.local_report.events_sent, 1);
+
+        // Sending every event hash alone would cost about 500 KB.
+        let full_list: usize = a.iter().map(|e| e.event_hash.len() + 1).sum();
+        assert!(
+            result.local_report.bytes_transferred * 10 < full_list,
+            "walk cost {} bytes vs {full_list} for the full list",
+            result.local_report.bytes_transferred
+        );
+    }
+
+    #[test]
+    fn sync_matches_set_difference_for_mixed_overlaps() {
+        let all: Vec<Event> = (0..1200)
+            .map(|i| make_event(&format!("m{i:05}"), i, &format!("m{i}")))
+            .collect();
+        let cases: [(Vec<Event>, Vec<Event>); 3] = [
+            // Strict subset, with a much shorter tree.
+            (all[..40].to_vec(), all.clone()),
+            // Interleaved: even indices vs multiples of three.
+            (
+                all.iter().step_by(2).cloned().collect(),
+                all.iter().step_by(3).cloned().collect(),
+            ),
+            // Overlapping ranges.
+            (all[..800].to_vec(), all[300..].to_vec()),
+        ];
+
+        for (a, b) in cases {
+            let a_set: HashSet<&str> = a.iter().map(|e| e.event_hash.as_str()).collect();
+            let b_set: HashSet<&str> = b.iter().map(|e| e.event_hash.as_str()).collect();
+
+            let result = sync_in_memory(&a, &b).unwrap();
+            let mut local: Vec<&str> = result
+                .local_received
+                .iter()
+                .map(|e| e.event_hash.as_str())
+                .collect();
+            local.sort_unstable();
+            let mut expected: Vec<&str> = b_set.difference(&a_set).copied().collect();
+            expected.sort_unstable();
+            assert_eq!(local, expected);
+
+            let mut remote: Vec<&str> = result
+                .remote_received
+                .iter()
+                .map(|e| e.event_hash.as_str())
+                .collect();
+            remote.sort_unstable();
+            let mut expected: Vec<&str> = a_set.difference(&b_set).copied().collect();
+            expected.sort_unstable();
+            assert_eq!(remote, expected);
+            assert_eq!(result.local_report.events_sent, expected.len());
+        }
     }
 
     #[test]
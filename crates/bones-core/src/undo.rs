@@ -13,16 +13,20 @@
 //! | `item.link` | `item.unlink` |
 //! | `item.unlink` | `item.link` |
 //! | `item.delete` | `item.create` (reconstruct from history) |
+//! | `item.comment_edit` | `item.comment_edit` with prior body |
 //!
 //! Events that **cannot** be undone (grow-only by design):
 //! - `item.comment` — G-Set: comments are permanent
 //! - `item.compact` — compaction is not reversible without original events
 //! - `item.snapshot` — same as compact
 //! - `item.redact` — intentionally permanent
+//! - `item.patch` — sequence tombstones cannot be revived; undo by editing
+//! - `item.comment_retract` — retraction is grow-only
+//! - `item.attach` — attachments are grow-only; redact to remove a blob
 
 use crate::event::data::{
-    AssignAction, AssignData, CreateData, DeleteData, EventData, LinkData, MoveData, UnlinkData,
-    UpdateData,
+    AssignAction, AssignData, CommentEditData, CreateData, DeleteData, EventData, LinkData,
+    MoveData, UnlinkData, UpdateData,
 };
 use crate::event::{Event, EventType};
 use crate::model::item::State;
@@ -77,9 +81,11 @@ impl std::error::Error for UndoError {}
 /// # Errors
 ///
 /// - [`UndoError::GrowOnly`] — for `item.comment`, `item.compact`,
-///   `item.snapshot`, and `item.redact`.
+///   `item.snapshot`, `item.redact`, `item.patch`,
+///   `item.comment_retract`, and `item.attach`.
 /// - [`UndoError::NoPriorState`] — when prior event context is needed but
 ///   cannot be found (e.g. undo `item.delete` with no prior `item.create`).
+#[allow(clippy::too_many_lines)]
 pub fn compensating_event(
     original: &Event,
     prior_events: &[&Event],
@@ -131,6 +137,7 @@ pub fn compensating_event(
                         "undo move from {} (compensating for {})",
                         d.state, original.event_hash
                     )),
+                    substate: None,
                     extra: BTreeMap::new(),
                 }),
             )
@@ -175,6 +182,24 @@ pub fn compensating_event(
             }),
         ),
 
+        // item.comment_edit → item.comment_edit with the previous body
+        EventData::CommentEdit(d) => {
+            let prev = find_previous_comment_body(prior_events, &d.comment).ok_or_else(|| {
+                UndoError::NoPriorState(format!(
+                    "no prior body for comment '{}' found in event history",
+                    d.comment
+                ))
+            })?;
+            (
+                EventType::CommentEdit,
+                EventData::CommentEdit(CommentEditData {
+                    comment: d.comment.clone(),
+                    body: prev,
+                    extra: BTreeMap::new(),
+                }),
+            )
+        }
+
         // item.delete → item.create (reconstruct from history)
         EventData::Delete(_) => {
             let create_data = build_create_from_history(prior_events).ok_or_else(|| {
@@ -190,6 +215,11 @@ pub fn compensating_event(
         EventData::Compact(_) => return Err(UndoError::GrowOnly(EventType::Compact)),
         EventData::Snapshot(_) => return Err(UndoError::GrowOnly(EventType::Snapshot)),
         EventData::Redact(_) => return Err(UndoError::GrowOnly(EventType::Redact)),
+        EventData::Patch(_) => return Err(UndoError::GrowOnly(EventType::Patch)),
+        EventData::CommentRetract(_) => {
+            return Err(UndoError::GrowOnly(EventType::CommentRetract));
+        }
+        EventData::Attach(_) => return Err(UndoError::GrowOnly(EventType::Attach)),
     };
 
     Ok(Event {
@@ -201,6 +231,7 @@ pub fn compensating_event(
         item_id: ItemId::new_unchecked(item_id.as_str()),
         data,
         event_hash: String::new(), // filled by write_event
+        signature: None,
     })
 }
 
@@ -239,6 +270,21 @@ fn find_previous_field_value(prior_events: &[&Event], field: &str) -> Option<ser
     None
 }
 
+/// Find the body a comment had before the most recent prior edit.
+///
+/// Scans backwards for an earlier `item.comment_edit` of the same comment,
+/// then falls back to the original `item.comment` body.
+fn find_previous_comment_body(prior_events: &[&Event], comment: &str) -> Option<String> {
+    for event in prior_events.iter().rev() {
+        match &event.data {
+            EventData::CommentEdit(d) if d.comment == comment => return Some(d.body.clone()),
+            EventData::Comment(d) if event.event_hash == comment => return Some(d.body.clone()),
+            _ => {}
+        }
+    }
+    None
+}
+
 /// Extract the initial value for `field` from an `item.create` payload.
 fn initial_create_field_value(create: &CreateData, field: &str) -> Option<serde_json::Value> {
     match field {
@@ -344,6 +390,7 @@ mod tests {
             item_id: ItemId::new_unchecked("bn-test"),
             data,
             event_hash: hash.to_string(),
+            signature: None,
         }
     }
 
@@ -475,6 +522,7 @@ mod tests {
             EventData::Move(MoveData {
                 state: State::Doing,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             "blake3:move001",
@@ -499,6 +547,7 @@ mod tests {
             EventData::Move(MoveData {
                 state: State::Done,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
             "blake3:move002",
@@ -597,6 +646,7 @@ mod tests {
             EventType::Comment,
             EventData::Comment(CommentData {
                 body: "A comment".into(),
+                reply_to: None,
                 extra: BTreeMap::new(),
             }),
             "blake3:comment001",
@@ -609,6 +659,47 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn undo_comment_edit_restores_previous_body() {
+        let comment_event = make_event(
+            EventType::Comment,
+            EventData::Comment(CommentData {
+                body: "Original".into(),
+                reply_to: None,
+                extra: BTreeMap::new(),
+            }),
+            "blake3:comment002",
+        );
+        let edit = |body: &str, hash: &str| {
+            make_event(
+                EventType::CommentEdit,
+                EventData::CommentEdit(CommentEditData {
+                    comment: "blake3:comment002".into(),
+                    body: body.into(),
+                    extra: BTreeMap::new(),
+                }),
+                hash,
+            )
+        };
+        let first = edit("First edit", "blake3:edit001");
+        let second = edit("Second edit", "blake3:edit002");
+
+        let comp = compensating_event(&second, &[&comment_event, &first], "undoer", 3_000_000)
+            .expect("undo edit");
+        assert_eq!(comp.event_type, EventType::CommentEdit);
+        let EventData::CommentEdit(d) = &comp.data else {
+            panic!("expected CommentEdit data");
+        };
+        assert_eq!(d.body, "First edit");
+
+        let comp = compensating_event(&first, &[&comment_event], "undoer", 3_000_000)
+            .expect("undo first edit");
+        let EventData::CommentEdit(d) = &comp.data else {
+            panic!("expected CommentEdit data");
+        };
+        assert_eq!(d.body, "Original");
+    }
+
     #[test]
     fn undo_redact_is_grow_only() {
         let redact_event = make_event(
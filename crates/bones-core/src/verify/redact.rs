@@ -8,16 +8,20 @@
 //!
 //! 1. Replay the event log to find all `item.redact` events and their targets.
 //! 2. For each redaction, look up the original event content.
-//! 3. Check every query surface (projection, FTS5, comments) for residual content.
+//! 3. Check every query surface (projection, FTS5, comments, attachment
+//!    blobs) for residual content.
 //! 4. Report any failures with precise location information.
 
 use std::collections::HashMap;
 use std::path::Path;
 
 use anyhow::{Context, Result};
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params};
 use serde::Serialize;
 
+use crate::blob::BlobStore;
+use crate::crdt::text::TextOp;
+use crate::db::query;
 use crate::event::Event;
 use crate::event::data::EventData;
 use crate::event::parser::parse_lines;
@@ -70,6 +74,22 @@ pub enum ResidualLocation {
         /// The comment ID in the projection.
         comment_id: i64,
     },
+    /// Comment edit body not replaced with `[redacted]`.
+    CommentEditNotRedacted {
+        /// Hash of the `item.comment_edit` event.
+        event_hash: String,
+    },
+    /// Attachment name not replaced with `[redacted]`.
+    AttachmentNotRedacted {
+        /// Hash of the `item.attach` event.
+        event_hash: String,
+    },
+    /// The redacted attachment's blob is still in `.bones/blobs/` although
+    /// no unredacted attachment references it.
+    AttachmentBlobPresent {
+        /// Hash of the orphaned blob.
+        blob_hash: String,
+    },
     /// Content found in FTS5 index (the redacted text is still searchable).
     Fts5Index {
         /// The search term that matched.
@@ -94,6 +114,8 @@ struct RedactionTarget {
     original_event_type: Option<EventType>,
     /// Searchable text from the original event (for FTS residual checks).
     original_text: Option<String>,
+    /// Blob referenced by the original event, if it was an `item.attach`.
+    attached_blob: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -116,10 +138,11 @@ struct RedactionTarget {
 pub fn verify_redactions(events_dir: &Path, db: &Connection) -> Result<RedactionReport> {
     let targets = collect_redaction_targets(events_dir)?;
     let total = targets.len();
+    let blobs = blob_store_for(events_dir);
 
     let mut failures = Vec::new();
     for target in &targets {
-        let locs = check_residuals(db, target)?;
+        let locs = check_residuals(db, &blobs, target)?;
         if !locs.is_empty() {
             failures.push(RedactionFailure {
                 item_id: target.item_id.clone(),
@@ -162,10 +185,11 @@ pub fn verify_item_redaction(
         .into_iter()
         .filter(|t| t.item_id == item_id)
         .collect();
+    let blobs = blob_store_for(events_dir);
 
     let mut failures = Vec::new();
     for target in &item_targets {
-        let locs = check_residuals(db, target)?;
+        let locs = check_residuals(db, &blobs, target)?;
         if !locs.is_empty() {
             failures.push(RedactionFailure {
                 item_id: target.item_id.clone(),
@@ -181,6 +205,11 @@ pub fn verify_item_redaction(
 // Internal: collect redaction targets from the event log
 // ---------------------------------------------------------------------------
 
+/// The blob store that lives next to `events_dir`.
+fn blob_store_for(events_dir: &Path) -> BlobStore {
+    BlobStore::new(events_dir.parent().unwrap_or_else(|| Path::new(".")))
+}
+
 /// Parse all events and build a map of redaction targets with their
 /// original content.
 fn collect_redaction_targets(events_dir: &Path) -> Result<Vec<RedactionTarget>> {
@@ -224,6 +253,10 @@ fn collect_redaction_targets(events_dir: &Path) -> Result<Vec<RedactionTarget>>
             _reason: redact_data.reason.clone(),
             original_event_type,
             original_text,
+            attached_blob: original.and_then(|orig| match &orig.data {
+                EventData::Attach(d) => Some(d.blob.clone()),
+                _ => None,
+            }),
         });
     }
 
@@ -253,6 +286,17 @@ fn extract_searchable_text(event: &Event) -> Option<String> {
             )
         }
         EventData::Compact(d) => Some(d.summary.clone()),
+        EventData::Patch(d) => {
+            let inserted: Vec<&str> = d
+                .ops
+                .iter()
+                .filter_map(|op| match op {
+                    TextOp::Insert { text, .. } => Some(text.as_str()),
+                    TextOp::Delete { .. } => None,
+                })
+                .collect();
+            (!inserted.is_empty()).then(|| inserted.join(" "))
+        }
         _ => None,
     }
 }
@@ -262,7 +306,11 @@ fn extract_searchable_text(event: &Event) -> Option<String> {
 // ---------------------------------------------------------------------------
 
 /// Check all projection surfaces for residual un-redacted content.
-fn check_residuals(db: &Connection, target: &RedactionTarget) -> Result<Vec<ResidualLocation>> {
+fn check_residuals(
+    db: &Connection,
+    blobs: &BlobStore,
+    target: &RedactionTarget,
+) -> Result<Vec<ResidualLocation>> {
     let mut locations = Vec::new();
 
     // 1. Check that the redaction record exists in event_redactions table.
@@ -272,7 +320,11 @@ fn check_residuals(db: &Connection, target: &RedactionTarget) -> Result<Vec<Resi
     //    has been replaced with '[redacted]'.
     check_comment_redacted(db, target, &mut locations)?;
 
-    // 3. If we have the original text, check FTS5 for residual content.
+    // 3. If the target was an attachment, check its name and that the blob
+    //    was removed once nothing else references it.
+    check_attachment_redacted(db, blobs, target, &mut locations)?;
+
+    // 4. If we have the original text, check FTS5 for residual content.
     check_fts5_residual(db, target, &mut locations)?;
 
     Ok(locations)
@@ -322,6 +374,53 @@ fn check_comment_redacted(
             locations.push(ResidualLocation::CommentNotRedacted { comment_id });
         }
     }
+
+    let edit_body: Option<String> = db
+        .query_row(
+            "SELECT body FROM item_comment_edits WHERE event_hash = ?1",
+            params![target.target_hash],
+            |row| row.get(0),
+        )
+        .optional()
+        .context("query comment edit by event_hash")?;
+    if edit_body.is_some_and(|body| body != "[redacted]") {
+        locations.push(ResidualLocation::CommentEditNotRedacted {
+            event_hash: target.target_hash.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// If the targeted event was an attachment, verify its name is `[redacted]`
+/// and its blob is gone unless another unredacted attachment shares it.
+fn check_attachment_redacted(
+    db: &Connection,
+    blobs: &BlobStore,
+    target: &RedactionTarget,
+    locations: &mut Vec<ResidualLocation>,
+) -> Result<()> {
+    let name: Option<String> = db
+        .query_row(
+            "SELECT name FROM item_attachments WHERE event_hash = ?1",
+            params![target.target_hash],
+            |row| row.get(0),
+        )
+        .optional()
+        .context("query attachment by event_hash")?;
+    if name.is_some_and(|name| name != "[redacted]") {
+        locations.push(ResidualLocation::AttachmentNotRedacted {
+            event_hash: target.target_hash.clone(),
+        });
+    }
+
+    if let Some(blob_hash) = &target.attached_blob
+        && blobs.contains(blob_hash)
+        && query::count_live_blob_references(db, blob_hash)? == 0
+    {
+        locations.push(ResidualLocation::AttachmentBlobPresent {
+            blob_hash: blob_hash.clone(),
+        });
+    }
     Ok(())
 }
 
@@ -464,6 +563,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         write_event(&mut event).expect("compute hash");
         event
@@ -479,9 +579,11 @@ mod tests {
             item_id: ItemId::new_unchecked(item_id),
             data: EventData::Comment(CommentData {
                 body: body.into(),
+                reply_to: None,
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         write_event(&mut event).expect("compute hash");
         event
@@ -501,6 +603,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         write_event(&mut event).expect("compute hash");
         event
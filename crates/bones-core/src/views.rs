@@ -0,0 +1,439 @@
+//! Saved list views.
+//!
+//! `.bones/views.toml` names combinations of list filters, sort order,
+//! columns and output mode so teams stop retyping them. The file lives next
+//! to `config.toml` and is committed with the project, so views sync through
+//! git like everything else under `.bones/`.
+//!
+//! # Format
+//!
+//! ```toml
+//! [view.triage-api]
+//! description = "Unassigned API work worth picking up"
+//! state = ["open", "doing"]
+//! label = ["api"]
+//! where = "NOT assignee:* AND size>=M"
+//! sort = "priority"
+//! columns = ["id", "urgency", "size", "title"]
+//! format = "text"
+//! ```
+//!
+//! Filter keys mirror the `bn list` flags: `state`, `kind`, `label`,
+//! `urgency`, `parent`, `assignee`, `due`, `field` (`name=value`) and
+//! `where` (a [`FilterQuery`]). Presentation keys are `sort`, `columns`,
+//! `format` (`pretty`, `text` or `json`) and `limit`.
+//!
+//! `bn list --view <name>` applies the flags directly. Consumers that only
+//! take a query (`bn context`, the TUI) use [`SavedView::to_query`].
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::filter_query::{FilterQuery, FilterQueryError};
+use crate::model::due::parse_offset;
+
+/// Views file name under `.bones/`.
+pub const VIEWS_FILE: &str = "views.toml";
+
+/// Columns a view may select for table output, in default order.
+pub const VIEW_COLUMNS: &[&str] = &[
+    "id",
+    "kind",
+    "state",
+    "urgency",
+    "size",
+    "assignees",
+    "labels",
+    "due",
+    "parent",
+    "updated",
+    "title",
+];
+
+const VIEW_FORMATS: &[&str] = &["pretty", "text", "json"];
+
+/// Errors that can occur while loading or saving views.
+#[derive(Debug, thiserror::Error)]
+pub enum ViewsError {
+    /// I/O error on the views file.
+    #[error("views I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    /// The views file is not valid TOML or has unknown fields.
+    #[error("invalid {VIEWS_FILE}: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    /// The views could not be serialized.
+    #[error("failed to serialize views: {0}")]
+    Serialize(#[from] toml::ser::Error),
+
+    /// A view name contains characters other than letters, digits, `-`
+    /// and `_`.
+    #[error("invalid view name '{name}': use letters, digits, '-' and '_'")]
+    InvalidName {
+        /// The offending name.
+        name: String,
+    },
+
+    /// A view's `where` query does not parse.
+    #[error("{VIEWS_FILE} view '{view}': invalid where query: {source}")]
+    InvalidQuery {
+        /// View name.
+        view: String,
+        /// Parse failure.
+        source: FilterQueryError,
+    },
+
+    /// A view selects a column `bn list` cannot show.
+    #[error("{VIEWS_FILE} view '{view}': unknown column '{column}' (expected one of {})", VIEW_COLUMNS.join(", "))]
+    UnknownColumn {
+        /// View name.
+        view: String,
+        /// The offending column.
+        column: String,
+    },
+
+    /// A view names an output mode other than pretty, text or json.
+    #[error("{VIEWS_FILE} view '{view}': unknown format '{value}' (expected pretty, text or json)")]
+    InvalidFormat {
+        /// View name.
+        view: String,
+        /// The offending value.
+        value: String,
+    },
+}
+
+/// One named view in `.bones/views.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SavedView {
+    /// What the view is for, shown by `bn view list`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// States or sub-states (`bn list --state`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub state: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Labels the bone must all carry.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub label: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub urgency: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    /// Due at or before (`bn list --due`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<String>,
+    /// Custom field filters as `name=value`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub field: Vec<String>,
+    /// Filter query (`bn list --where`).
+    #[serde(default, rename = "where", skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// Sort order (`bn list --sort`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+    /// Table columns, from [`VIEW_COLUMNS`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<String>,
+    /// Output mode used unless `--format` is given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Maximum number of bones to show.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+impl SavedView {
+    /// The view's filters as a single [`FilterQuery`] string, or `None` when
+    /// it filters nothing. Sort, columns, format and limit are not part of
+    /// the query.
+    ///
+    /// `state = ["blocked"]` keeps its `bn list` meaning: the `blocked`
+    /// sub-state if one is configured, otherwise bones with an open blocker.
+    #[must_use]
+    pub fn to_query(&self) -> Option<String> {
+        let mut terms = Vec::new();
+
+        if !self.state.is_empty() {
+            let states: Vec<String> = self
+                .state
+                .iter()
+                .map(|state| {
+                    if state.eq_ignore_ascii_case("blocked") {
+                        "state:blocked OR (has:blocker AND NOT state:done AND NOT state:archived)"
+                            .to_string()
+                    } else {
+                        format!("state:{}", quote(state))
+                    }
+                })
+                .collect();
+            terms.push(if states.len() == 1 && !states[0].contains(' ') {
+                states[0].clone()
+            } else {
+                format!("({})", states.join(" OR "))
+            });
+        }
+        for (field, value) in [
+            ("kind", &self.kind),
+            ("urgency", &self.urgency),
+            ("parent", &self.parent),
+            ("assignee", &self.assignee),
+        ] {
+            if let Some(value) = value {
+                terms.push(format!("{field}:{}", quote(value)));
+            }
+        }
+        for label in &self.label {
+            terms.push(format!("label:{}", quote(label)));
+        }
+        if let Some(due) = &self.due {
+            // `bn list --due 3d` means three days from now; queries spell
+            // future offsets with a sign.
+            let due = if due.starts_with(|c: char| c.is_ascii_digit())
+                && parse_offset(&due.to_ascii_lowercase()).is_some()
+            {
+                format!("+{due}")
+            } else {
+                due.clone()
+            };
+            terms.push(format!("due<={}", quote(&due)));
+        }
+        for assignment in &self.field {
+            if let Some((name, value)) = assignment.split_once('=') {
+                terms.push(format!("custom.{}:{}", name.trim(), quote(value.trim())));
+            }
+        }
+        if let Some(query) = &self.query {
+            terms.push(format!("({query})"));
+        }
+
+        if terms.is_empty() {
+            None
+        } else {
+            Some(terms.join(" AND "))
+        }
+    }
+
+    fn validate(&self, name: &str) -> Result<(), ViewsError> {
+        if let Some(query) = &self.query {
+            FilterQuery::parse(query).map_err(|source| ViewsError::InvalidQuery {
+                view: name.to_string(),
+                source,
+            })?;
+        }
+        if let Some(column) = self
+            .columns
+            .iter()
+            .find(|c| !VIEW_COLUMNS.contains(&c.as_str()))
+        {
+            return Err(ViewsError::UnknownColumn {
+                view: name.to_string(),
+                column: column.clone(),
+            });
+        }
+        if let Some(format) = &self.format
+            && !VIEW_FORMATS.contains(&format.as_str())
+        {
+            return Err(ViewsError::InvalidFormat {
+                view: name.to_string(),
+                value: format.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Parsed `.bones/views.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Views {
+    /// Views by name.
+    #[serde(default, rename = "view", skip_serializing_if = "BTreeMap::is_empty")]
+    pub views: BTreeMap<String, SavedView>,
+}
+
+impl Views {
+    /// Load `.bones/views.toml`; a missing file yields no views.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ViewsError`] if the file cannot be read, does not parse, or
+    /// a view is invalid.
+    pub fn load(bones_dir: &Path) -> Result<Self, ViewsError> {
+        let path = bones_dir.join(VIEWS_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Parse and validate views text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ViewsError`] if the text does not parse or a view is
+    /// invalid.
+    pub fn parse(text: &str) -> Result<Self, ViewsError> {
+        let views: Self = toml::from_str(text)?;
+        for (name, view) in &views.views {
+            validate_name(name)?;
+            view.validate(name)?;
+        }
+        Ok(views)
+    }
+
+    /// Add or replace a view after validating it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ViewsError`] if the name or view is invalid.
+    pub fn insert(&mut self, name: &str, view: SavedView) -> Result<(), ViewsError> {
+        validate_name(name)?;
+        view.validate(name)?;
+        self.views.insert(name.to_string(), view);
+        Ok(())
+    }
+
+    /// Write the views to `.bones/views.toml`, returning its path.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ViewsError`] if serializing or writing fails.
+    pub fn save(&self, bones_dir: &Path) -> Result<PathBuf, ViewsError> {
+        let path = bones_dir.join(VIEWS_FILE);
+        fs::write(&path, toml::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+
+    /// Look up a view by name.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&SavedView> {
+        self.views.get(name)
+    }
+}
+
+fn validate_name(name: &str) -> Result<(), ViewsError> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(ViewsError::InvalidName {
+            name: name.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Quote a query value that would otherwise split into several tokens.
+fn quote(value: &str) -> String {
+    if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '(' || c == ')') {
+        format!("\"{value}\"")
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+[view.triage-api]
+description = "Unassigned API work"
+state = ["open", "doing"]
+label = ["api"]
+where = "NOT assignee:* AND size>=M"
+sort = "priority"
+columns = ["id", "urgency", "title"]
+format = "text"
+
+[view.mine]
+assignee = "alice"
+due = "3d"
+field = ["team=core platform"]
+"#;
+
+    #[test]
+    fn parses_views_and_round_trips() {
+        let views = Views::parse(SAMPLE).expect("parse");
+        let api = views.get("triage-api").expect("view");
+        assert_eq!(api.state, ["open", "doing"]);
+        assert_eq!(api.query.as_deref(), Some("NOT assignee:* AND size>=M"));
+        assert_eq!(api.columns, ["id", "urgency", "title"]);
+
+        let text = toml::to_string_pretty(&views).expect("serialize");
+        assert_eq!(Views::parse(&text).expect("reparse"), views);
+    }
+
+    #[test]
+    fn to_query_combines_every_filter() {
+        let views = Views::parse(SAMPLE).expect("parse");
+        assert_eq!(
+            views
+                .get("triage-api")
+                .and_then(SavedView::to_query)
+                .as_deref(),
+            Some("(state:open OR state:doing) AND label:api AND (NOT assignee:* AND size>=M)")
+        );
+        let mine = views
+            .get("mine")
+            .and_then(SavedView::to_query)
+            .expect("query");
+        assert_eq!(
+            mine,
+            "assignee:alice AND due<=+3d AND custom.team:\"core platform\""
+        );
+        for query in [mine, views.get("triage-api").unwrap().to_query().unwrap()] {
+            FilterQuery::parse(&query).expect("generated query parses");
+        }
+        assert_eq!(SavedView::default().to_query(), None);
+    }
+
+    #[test]
+    fn rejects_invalid_views() {
+        let err = Views::parse("[view.x]\nwhere = \"colour:red\"").expect_err("bad query");
+        assert!(matches!(err, ViewsError::InvalidQuery { .. }));
+        let err = Views::parse("[view.x]\ncolumns = [\"owner\"]").expect_err("bad column");
+        assert!(matches!(err, ViewsError::UnknownColumn { .. }));
+        let err = Views::parse("[view.x]\nformat = \"yaml\"").expect_err("bad format");
+        assert!(matches!(err, ViewsError::InvalidFormat { .. }));
+        let err = Views::parse("[view.\"a b\"]\nkind = \"bug\"").expect_err("bad name");
+        assert!(matches!(err, ViewsError::InvalidName { .. }));
+        let err = Views::parse("[view.x]\ncolour = \"red\"").expect_err("unknown key");
+        assert!(matches!(err, ViewsError::Parse(_)));
+    }
+
+    #[test]
+    fn load_and_save_use_the_views_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert!(
+            Views::load(dir.path())
+                .expect("missing file")
+                .views
+                .is_empty()
+        );
+
+        let mut views = Views::default();
+        views
+            .insert(
+                "bugs",
+                SavedView {
+                    kind: Some("bug".to_string()),
+                    ..SavedView::default()
+                },
+            )
+            .expect("insert");
+        let path = views.save(dir.path()).expect("save");
+        assert_eq!(path, dir.path().join(VIEWS_FILE));
+        assert_eq!(Views::load(dir.path()).expect("load"), views);
+    }
+}
@@ -27,6 +27,7 @@ fn make_event(
         item_id: ItemId::new_unchecked(item_id),
         data,
         event_hash: event_hash.to_string(),
+        signature: None,
     }
 }
 
@@ -94,6 +95,7 @@ fn move_event(item_id: &str, state: State, wall_ts: i64, agent: &str, hash: &str
         EventData::Move(MoveData {
             state,
             reason: None,
+            substate: None,
             extra: BTreeMap::new(),
         }),
         wall_ts,
@@ -213,6 +215,7 @@ fn three_agents_converge_all_merge_orderings() {
         EventType::Comment,
         EventData::Comment(CommentData {
             body: "done".to_string(),
+            reply_to: None,
             extra: BTreeMap::new(),
         }),
         3_200,
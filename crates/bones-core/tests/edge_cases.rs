@@ -61,6 +61,7 @@ fn make_create(id: &str, title: &str, agent: &str, ts: i64) -> Event {
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
     write_event(&mut e).expect("compute hash");
     e
@@ -86,6 +87,7 @@ fn make_create_goal(id: &str, title: &str, agent: &str, ts: i64) -> Event {
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
     write_event(&mut e).expect("compute hash");
     e
@@ -111,6 +113,7 @@ fn make_create_child(id: &str, title: &str, parent_id: &str, agent: &str, ts: i6
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
     write_event(&mut e).expect("compute hash");
     e
@@ -127,9 +130,11 @@ fn make_move(id: &str, state: State, agent: &str, ts: i64) -> Event {
         data: EventData::Move(MoveData {
             state,
             reason: None,
+            substate: None,
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
     write_event(&mut e).expect("compute hash");
     e
@@ -155,6 +160,7 @@ fn make_crdt_event(
         item_id: ItemId::new_unchecked(item_id),
         data,
         event_hash: event_hash.to_string(),
+        signature: None,
     }
 }
 
@@ -194,6 +200,7 @@ fn crdt_move(item_id: &str, state: State, ts: i64, agent: &str, hash: &str) -> E
         EventData::Move(MoveData {
             state,
             reason: None,
+            substate: None,
             extra: BTreeMap::new(),
         }),
         ts,
@@ -663,6 +670,7 @@ fn item_with_64kb_description_round_trips() {
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
     write_event(&mut e).expect("hash");
     let conn = project_events(&[e]);
@@ -708,6 +716,7 @@ fn item_with_1000_labels_all_preserved() {
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
     write_event(&mut label_event).expect("compute hash");
 
@@ -57,6 +57,7 @@ fn make_create_event(seq: usize) -> Event {
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
 
     writer::write_event(&mut event).expect("compute event hash");
@@ -53,6 +53,7 @@ fn base_event(event_type: EventType, item_id_raw: &str, data: EventData) -> Even
         item_id: item_id(item_id_raw),
         data,
         event_hash: String::new(),
+        signature: None,
     }
 }
 
@@ -181,6 +182,7 @@ fn build_v1_corpus() -> Vec<Event> {
             EventData::Move(MoveData {
                 state: State::Doing,
                 reason: Some("Starting implementation".into()),
+                substate: None,
                 extra: BTreeMap::new(),
             }),
         ),
@@ -191,6 +193,7 @@ fn build_v1_corpus() -> Vec<Event> {
             EventData::Move(MoveData {
                 state: State::Done,
                 reason: Some("Shipped in commit 9f3a2b1".into()),
+                substate: None,
                 extra: BTreeMap::new(),
             }),
         ),
@@ -201,6 +204,7 @@ fn build_v1_corpus() -> Vec<Event> {
             EventData::Move(MoveData {
                 state: State::Archived,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
         ),
@@ -232,6 +236,7 @@ fn build_v1_corpus() -> Vec<Event> {
             "bn-a7x",
             EventData::Comment(CommentData {
                 body: "Root cause: race in token expiry check.".into(),
+                reply_to: None,
                 extra: BTreeMap::new(),
             }),
         ),
@@ -241,6 +246,7 @@ fn build_v1_corpus() -> Vec<Event> {
             "bn-b8y",
             EventData::Comment(CommentData {
                 body: "バグ発見: セッション初期化の競合状態 🐛".into(),
+                reply_to: None,
                 extra: BTreeMap::new(),
             }),
         ),
@@ -250,6 +256,7 @@ fn build_v1_corpus() -> Vec<Event> {
             "bn-b8y",
             EventData::Comment(CommentData {
                 body: "تم إيجاد السبب الجذري في منطق المصادقة".into(),
+                reply_to: None,
                 extra: BTreeMap::new(),
             }),
         ),
@@ -259,6 +266,7 @@ fn build_v1_corpus() -> Vec<Event> {
             "bn-a7x",
             EventData::Comment(CommentData {
                 body: "🚀 Deployed! ✅ Tests pass. 🎉 Congrats team! 🦀 Rust FTW.".into(),
+                reply_to: None,
                 extra: BTreeMap::new(),
             }),
         ),
@@ -520,6 +528,7 @@ fn unknown_event_type_warns_not_errors() {
         "bn-a7x",
         EventData::Comment(CommentData {
             body: "Known event".into(),
+            reply_to: None,
             extra: BTreeMap::new(),
         }),
     );
@@ -552,6 +561,7 @@ fn unknown_event_type_warns_not_errors() {
         "bn-b8y",
         EventData::Comment(CommentData {
             body: "Another known event".into(),
+            reply_to: None,
             extra: BTreeMap::new(),
         }),
     );
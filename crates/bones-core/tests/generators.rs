@@ -1,11 +1,13 @@
 use bones_core::clock::itc::Stamp;
-use bones_core::crdt::item_state::WorkItemState;
+use bones_core::crdt::item_state::{AttachmentRef, DescriptionEdits, SubState, WorkItemState};
 use bones_core::crdt::lww::LwwRegister;
 use bones_core::crdt::state::{EpochPhaseState, Phase as LifecyclePhase};
+use bones_core::crdt::text::{TextId, TextOp, TextSeq};
 use bones_core::crdt::*;
 use bones_core::model::item::{Kind, Size, Urgency};
 use chrono::{TimeZone, Utc};
 use proptest::prelude::*;
+use std::collections::BTreeMap;
 use std::hash::Hash;
 
 pub fn arb_timestamp() -> impl Strategy<Value = Timestamp> + Clone {
@@ -142,6 +144,106 @@ fn arb_lww_register_parent() -> impl Strategy<Value = LwwRegister<String>> + Clo
     })
 }
 
+fn arb_lww_register_due() -> impl Strategy<Value = LwwRegister<Option<u64>>> + Clone {
+    any::<u8>().prop_map(|token| {
+        let value = (token % 3 != 0).then(|| 1_700_000_000_000_000 + u64::from(token));
+        lww_from_token(token, value)
+    })
+}
+
+fn arb_lww_register_substate() -> impl Strategy<Value = LwwRegister<Option<SubState>>> + Clone {
+    any::<u8>().prop_map(|token| {
+        let value = (token % 3 != 0).then(|| SubState {
+            phase: if token % 2 == 0 {
+                LifecyclePhase::Doing
+            } else {
+                LifecyclePhase::Open
+            },
+            name: format!("sub-{}", token % 5),
+        });
+        lww_from_token(token, value)
+    })
+}
+
+fn arb_custom_fields()
+-> impl Strategy<Value = BTreeMap<String, LwwRegister<Option<serde_json::Value>>>> + Clone {
+    proptest::collection::btree_map(
+        prop_oneof![Just("component"), Just("severity"), Just("customer")].prop_map(str::to_string),
+        any::<u8>().prop_map(|token| {
+            let value = (token % 4 != 0).then(|| serde_json::Value::from(token));
+            lww_from_token(token, value)
+        }),
+        0..3,
+    )
+}
+
+fn arb_text_op() -> impl Strategy<Value = TextOp> + Clone {
+    let agent = prop_oneof![Just(""), Just("alice"), Just("bob")].prop_map(str::to_string);
+    prop_oneof![
+        (
+            1u64..40,
+            agent.clone(),
+            proptest::option::of(1u64..40),
+            "[a-z ]{1,4}",
+        )
+            .prop_map(|(counter, agent, after, text)| TextOp::Insert {
+                id: TextId::new(counter, agent),
+                after: after.map(|c| TextId::new(c, "")),
+                text,
+            }),
+        (1u64..40, agent, 1u64..4).prop_map(|(counter, agent, len)| TextOp::Delete {
+            start: TextId::new(counter, agent),
+            len,
+        }),
+    ]
+}
+
+fn arb_description_edits() -> impl Strategy<Value = BTreeMap<String, DescriptionEdits>> + Clone {
+    proptest::collection::btree_map(
+        (0u8..4).prop_map(|token| format!("blake3:{token:02x}")),
+        (any::<u8>(), proptest::collection::vec(arb_text_op(), 0..6)).prop_map(|(token, ops)| {
+            let mut seq = TextSeq::new();
+            for op in &ops {
+                seq.apply(op);
+            }
+            DescriptionEdits {
+                seq,
+                latest: lww_from_token(token, ()),
+            }
+        }),
+        0..2,
+    )
+}
+
+fn arb_comment_edits() -> impl Strategy<Value = BTreeMap<String, LwwRegister<String>>> + Clone {
+    proptest::collection::btree_map(
+        (0u8..4).prop_map(|token| format!("c{token}")),
+        any::<u8>().prop_map(|token| lww_from_token(token, format!("edit-{token:02x}"))),
+        0..3,
+    )
+}
+
+/// Attachment values are derived from the key: an event hash always names
+/// the same attachment.
+fn arb_attachments() -> impl Strategy<Value = BTreeMap<String, AttachmentRef>> + Clone {
+    proptest::collection::btree_set(0u8..6, 0..3).prop_map(|tokens| {
+        tokens
+            .into_iter()
+            .map(|token| {
+                (
+                    format!("blake3:a{token}"),
+                    AttachmentRef {
+                        blob: format!("blake3:b{}", token % 3),
+                        name: format!("file-{token}.log"),
+                        mime: "text/plain".to_string(),
+                        size: u64::from(token) * 100,
+                    },
+                )
+            })
+            .collect()
+    })
+}
+
 fn arb_lww_register_bool() -> impl Strategy<Value = LwwRegister<bool>> + Clone {
     any::<u8>().prop_map(|token| lww_from_token(token, token % 2 == 0))
 }
@@ -194,6 +296,7 @@ pub fn arb_work_item_state() -> impl Strategy<Value = WorkItemState> + Clone {
             arb_lww_register_size(),
             arb_lww_register_urgency(),
             arb_lww_register_parent(),
+            arb_lww_register_due(),
         ),
         (
             arb_orset_string(),
@@ -203,13 +306,29 @@ pub fn arb_work_item_state() -> impl Strategy<Value = WorkItemState> + Clone {
             arb_gset_string(),
             arb_lww_register_bool(),
         ),
+        (
+            arb_custom_fields(),
+            arb_description_edits(),
+            arb_comment_edits(),
+            arb_gset_string(),
+            arb_attachments(),
+            arb_lww_register_substate(),
+        ),
         0u64..100_000,
         0u64..10_000,
     )
         .prop_map(
             |(
-                (title, description, kind, state, size, urgency, parent),
+                (title, description, kind, state, size, urgency, parent, due),
                 (assignees, labels, blocked_by, related_to, comments, deleted),
+                (
+                    custom,
+                    description_edits,
+                    comment_edits,
+                    retracted_comments,
+                    attachments,
+                    substate,
+                ),
                 created_at,
                 delta,
             )| WorkItemState {
@@ -217,14 +336,21 @@ pub fn arb_work_item_state() -> impl Strategy<Value = WorkItemState> + Clone {
                 description,
                 kind,
                 state,
+                substate,
                 size,
                 urgency,
                 parent,
+                due,
+                custom,
+                description_edits,
                 assignees,
                 labels,
                 blocked_by,
                 related_to,
                 comments,
+                comment_edits,
+                retracted_comments,
+                attachments,
                 deleted,
                 created_at,
                 updated_at: created_at.saturating_add(delta),
@@ -36,6 +36,7 @@ fn make_create_event(item_id: &str, ts: i64, agent: &str) -> Event {
             extra: BTreeMap::new(),
         }),
         event_hash: format!("blake3:{item_id}_{ts}_{agent}"),
+        signature: None,
     }
 }
 
@@ -53,6 +54,7 @@ fn make_update_event(item_id: &str, ts: i64, agent: &str, field: &str, value: &s
             extra: BTreeMap::new(),
         }),
         event_hash: format!("blake3:{item_id}_{ts}_{agent}_{field}"),
+        signature: None,
     }
 }
 
@@ -185,7 +187,8 @@ fn sync_identical_repos_is_noop() {
     assert!(result.local_report.is_noop());
     assert!(result.remote_report.is_noop());
     assert_eq!(result.local_report.rounds, 1);
-    assert_eq!(result.local_report.bytes_transferred, 0);
+    // Only the two root hashes crossed the wire.
+    assert_eq!(result.local_report.bytes_transferred, 64);
 }
 
 /// Sync handles concurrent events on same item.
@@ -236,7 +239,9 @@ fn sync_report_has_correct_counts() {
     assert_eq!(result.remote_report.events_sent, 1);
     assert_eq!(result.remote_report.events_received, 2);
     assert!(result.local_report.bytes_transferred > 0);
-    assert_eq!(result.local_report.rounds, 3); // full protocol
+    // Root, one (empty) walk round for the single-leaf trees, leaf hashes,
+    // and events.
+    assert_eq!(result.local_report.rounds, 4);
 }
 
 /// Large-scale sync: 500 shared + 100 diverged on each side.
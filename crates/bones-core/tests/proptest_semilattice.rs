@@ -39,11 +39,18 @@ fn work_item_states_equal(a: &WorkItemState, b: &WorkItemState) -> bool {
         && a.size == b.size
         && a.urgency == b.urgency
         && a.parent == b.parent
+        && a.due == b.due
+        && a.substate == b.substate
+        && a.custom == b.custom
+        && a.description_edits == b.description_edits
         && a.assignees == b.assignees
         && a.labels == b.labels
         && a.blocked_by == b.blocked_by
         && a.related_to == b.related_to
         && a.comments == b.comments
+        && a.comment_edits == b.comment_edits
+        && a.retracted_comments == b.retracted_comments
+        && a.attachments == b.attachments
         && a.deleted == b.deleted
         && a.created_at == b.created_at
         && a.updated_at == b.updated_at
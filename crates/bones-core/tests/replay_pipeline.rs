@@ -61,6 +61,7 @@ fn make_create(id: &str, title: &str, agent: &str, ts: i64, parents: &[&str]) ->
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
     write_event(&mut e).expect("compute hash");
     e
@@ -81,6 +82,7 @@ fn make_update_title(id: &str, title: &str, agent: &str, ts: i64, parents: &[&st
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
     write_event(&mut e).expect("compute hash");
     e
@@ -98,9 +100,11 @@ fn make_move(id: &str, state: State, agent: &str, ts: i64, parents: &[&str]) ->
         data: EventData::Move(MoveData {
             state,
             reason: None,
+            substate: None,
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
     write_event(&mut e).expect("compute hash");
     e
@@ -128,6 +132,7 @@ fn make_assign(
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
     write_event(&mut e).expect("compute hash");
     e
@@ -144,9 +149,11 @@ fn make_comment(id: &str, body: &str, agent: &str, ts: i64, parents: &[&str]) ->
         item_id: ItemId::new_unchecked(id),
         data: EventData::Comment(CommentData {
             body: body.into(),
+            reply_to: None,
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
     write_event(&mut e).expect("compute hash");
     e
@@ -174,6 +181,7 @@ fn make_link(
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
     write_event(&mut e).expect("compute hash");
     e
@@ -201,6 +209,7 @@ fn make_unlink(
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
     write_event(&mut e).expect("compute hash");
     e
@@ -220,6 +229,7 @@ fn make_delete(id: &str, agent: &str, ts: i64, parents: &[&str]) -> Event {
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
     write_event(&mut e).expect("compute hash");
     e
@@ -239,6 +249,7 @@ fn make_compact(id: &str, summary: &str, agent: &str, ts: i64, parents: &[&str])
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
     write_event(&mut e).expect("compute hash");
     e
@@ -264,6 +275,7 @@ fn make_snapshot(
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
     write_event(&mut e).expect("compute hash");
     e
@@ -291,6 +303,7 @@ fn make_redact(
             extra: BTreeMap::new(),
         }),
         event_hash: String::new(),
+        signature: None,
     };
     write_event(&mut e).expect("compute hash");
     e
@@ -334,6 +347,7 @@ fn linear_create_update_move_done_projects_to_sqlite() {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         write_event(&mut e).expect("hash");
         e
@@ -746,6 +760,7 @@ fn two_agents_concurrent_label_adds_both_preserved() {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         write_event(&mut e).expect("hash");
         e
@@ -765,6 +780,7 @@ fn two_agents_concurrent_label_adds_both_preserved() {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         write_event(&mut e).expect("hash");
         e
@@ -1496,6 +1512,7 @@ fn replay_20_mixed_events_correct_sqlite_state() {
                 extra: BTreeMap::new(),
             }),
             event_hash: String::new(),
+            signature: None,
         };
         write_event(&mut e).expect("hash");
         e
@@ -40,6 +40,7 @@ fn base_event(event_type: EventType, item_id_raw: &str, data: EventData) -> Even
         item_id: item_id(item_id_raw),
         data,
         event_hash: String::new(),
+        signature: None,
     }
 }
 
@@ -74,6 +75,7 @@ fn create_data(title: &str) -> EventData {
 fn comment_data(body: &str) -> EventData {
     EventData::Comment(CommentData {
         body: body.into(),
+        reply_to: None,
         extra: BTreeMap::new(),
     })
 }
@@ -82,6 +84,7 @@ fn move_data(state: State) -> EventData {
     EventData::Move(MoveData {
         state,
         reason: None,
+        substate: None,
         extra: BTreeMap::new(),
     })
 }
@@ -152,6 +155,7 @@ fn roundtrip_item_move() {
         EventData::Move(MoveData {
             state: State::Doing,
             reason: Some("Starting implementation".into()),
+            substate: None,
             extra: BTreeMap::new(),
         }),
     );
@@ -798,6 +802,7 @@ fn roundtrip_extra_fields_preserved() {
         "bn-a7x",
         EventData::Comment(CommentData {
             body: "Comment with extra fields".into(),
+            reply_to: None,
             extra,
         }),
     );
@@ -866,12 +871,12 @@ fn malformed_wrong_field_count_3() {
 }
 
 #[test]
-fn malformed_wrong_field_count_9() {
-    let err = parse_line("1\t2\t3\t4\t5\t6\t7\t8\t9").expect_err("should fail");
+fn malformed_wrong_field_count_10() {
+    let err = parse_line("1\t2\t3\t4\t5\t6\t7\t8\t9\t10").expect_err("should fail");
     assert!(matches!(
         err,
         ParseError::FieldCount {
-            found: 9,
+            found: 10,
             expected: 8
         }
     ));
@@ -1138,6 +1143,7 @@ fn build_golden_corpus() -> Vec<Event> {
             EventData::Move(MoveData {
                 state: State::Doing,
                 reason: Some("Starting implementation".into()),
+                substate: None,
                 extra: BTreeMap::new(),
             }),
         ),
@@ -1148,6 +1154,7 @@ fn build_golden_corpus() -> Vec<Event> {
             EventData::Move(MoveData {
                 state: State::Done,
                 reason: Some("Shipped in commit 9f3a2b1".into()),
+                substate: None,
                 extra: BTreeMap::new(),
             }),
         ),
@@ -1158,6 +1165,7 @@ fn build_golden_corpus() -> Vec<Event> {
             EventData::Move(MoveData {
                 state: State::Archived,
                 reason: None,
+                substate: None,
                 extra: BTreeMap::new(),
             }),
         ),
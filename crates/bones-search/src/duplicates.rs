@@ -149,6 +149,7 @@ mod tests {
                 extra: BTreeMap::new(),
             }),
             event_hash: format!("blake3:{hash}"),
+            signature: None,
         }
     }
 
@@ -85,6 +85,7 @@ fn build_db_with_items() -> Connection {
                 extra: BTreeMap::new(),
             }),
             event_hash: format!("blake3:degradhash{i:04}"),
+            signature: None,
         };
         proj.project_event(&event)
             .unwrap_or_else(|e| panic!("failed to project {id}: {e:#}"));
@@ -125,6 +125,7 @@ fn build_and_populate_index(items: &[GoldItem]) -> Connection {
                 extra: BTreeMap::new(),
             }),
             event_hash: format!("blake3:goldhash{i:04}"),
+            signature: None,
         };
 
         proj.project_event(&event)
@@ -78,6 +78,7 @@ fn build_index(items: &[GoldItem]) -> Connection {
                 extra: BTreeMap::new(),
             }),
             event_hash: format!("blake3:search-quality-{i:04}"),
+            signature: None,
         };
 
         projector
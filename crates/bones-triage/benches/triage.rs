@@ -147,6 +147,7 @@ fn bench_composite(c: &mut Criterion) {
                     Urgency::Default
                 },
                 decay_days: (i % 30) as f64,
+                due_in_days: None,
             })
             .collect();
         group.bench_with_input(BenchmarkId::new("bulk_score", n), &inputs, |b, inputs| {
@@ -204,6 +205,7 @@ fn bench_end_to_end(c: &mut Criterion) {
                                 Urgency::Default
                             },
                             decay_days: (i % 30) as f64,
+                            due_in_days: None,
                         };
                         acc += composite_score(&inputs, &weights);
                     }
@@ -0,0 +1,441 @@
+//! Flow metrics from the state-transition history.
+//!
+//! Every `item.move` is projected into `item_transitions`. From that history
+//! this module derives, over a window of whole weeks ending now:
+//!
+//! - **Lead time**: creation to completion.
+//! - **Cycle time**: first move into `doing` to completion.
+//! - **Throughput**: bones completed per week.
+//! - **WIP**: bones in `doing` at the end of each week.
+//!
+//! A bone's completion is its last move into `done`, counted only while the
+//! bone is still `done` or `archived`. Reopened bones therefore drop out until
+//! they are closed again. Bones whose history was compacted away have no
+//! transitions and are not counted.
+//!
+//! Results can be sliced by kind, label, assignee or goal; a bone with two
+//! labels counts towards both.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+use serde::Serialize;
+
+pub(crate) const US_PER_DAY: i64 = 86_400_000_000;
+pub(crate) const US_PER_WEEK: i64 = 7 * US_PER_DAY;
+
+/// Group name for bones with no value in the sliced dimension.
+pub const NO_GROUP: &str = "(none)";
+
+/// A state change recorded for a bone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transition {
+    pub to_state: String,
+    pub at_us: i64,
+}
+
+/// The history of one bone, as needed for flow metrics.
+#[derive(Debug, Clone, Default)]
+pub struct FlowItem {
+    pub id: String,
+    pub kind: String,
+    pub state: String,
+    pub size: Option<String>,
+    pub parent_id: Option<String>,
+    pub created_at_us: i64,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    /// Transitions in time order.
+    pub transitions: Vec<Transition>,
+}
+
+impl FlowItem {
+    /// When the bone was completed, if it is currently done or archived and
+    /// was ever moved to `done`.
+    #[must_use]
+    pub fn completed_at_us(&self) -> Option<i64> {
+        if self.state != "done" && self.state != "archived" {
+            return None;
+        }
+        self.transitions
+            .iter()
+            .rev()
+            .find(|t| t.to_state == "done")
+            .map(|t| t.at_us)
+    }
+
+    /// When work on the bone first started.
+    #[must_use]
+    pub fn started_at_us(&self) -> Option<i64> {
+        self.transitions
+            .iter()
+            .find(|t| t.to_state == "doing")
+            .map(|t| t.at_us)
+    }
+
+    /// The bone's state at `at_us`, or `None` if it did not exist yet.
+    #[must_use]
+    pub fn state_at(&self, at_us: i64) -> Option<&str> {
+        if self.created_at_us > at_us {
+            return None;
+        }
+        Some(
+            self.transitions
+                .iter()
+                .take_while(|t| t.at_us <= at_us)
+                .last()
+                .map_or("open", |t| t.to_state.as_str()),
+        )
+    }
+}
+
+/// Dimension to slice flow metrics by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowSlice {
+    Kind,
+    Label,
+    Assignee,
+    /// Nearest ancestor of kind `goal`.
+    Goal,
+}
+
+/// Distribution of a duration, in days.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DurationStats {
+    pub count: usize,
+    pub mean_days: f64,
+    pub p50_days: f64,
+    pub p85_days: f64,
+    pub p95_days: f64,
+    pub max_days: f64,
+}
+
+/// One week of the window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WeekFlow {
+    /// Start of the week, Unix microseconds.
+    pub start_us: i64,
+    /// Bones completed during the week.
+    pub throughput: usize,
+    /// Bones in `doing` at the end of the week.
+    pub wip: usize,
+}
+
+/// Flow metrics for one group of bones.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FlowGroup {
+    /// Slice value, or `all` when not sliced.
+    pub group: String,
+    /// Bones completed in the window.
+    pub completed: usize,
+    pub lead_time: Option<DurationStats>,
+    pub cycle_time: Option<DurationStats>,
+    pub weeks: Vec<WeekFlow>,
+}
+
+/// Load every non-deleted bone with its transitions.
+///
+/// # Errors
+///
+/// Returns an error if a projection query fails.
+pub fn load_flow_items(conn: &Connection) -> Result<Vec<FlowItem>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT item_id, kind, state, size, parent_id, created_at_us
+             FROM items WHERE is_deleted = 0 ORDER BY item_id",
+        )
+        .context("prepare flow items query")?;
+    let mut items: BTreeMap<String, FlowItem> = stmt
+        .query_map([], |row| {
+            Ok(FlowItem {
+                id: row.get(0)?,
+                kind: row.get(1)?,
+                state: row.get(2)?,
+                size: row.get(3)?,
+                parent_id: row.get(4)?,
+                created_at_us: row.get(5)?,
+                ..FlowItem::default()
+            })
+        })
+        .context("execute flow items query")?
+        .map(|row| row.map(|item| (item.id.clone(), item)))
+        .collect::<Result<_, _>>()
+        .context("collect flow items")?;
+
+    for (id, label) in load_pairs(
+        conn,
+        "SELECT item_id, label FROM item_labels ORDER BY label",
+    )? {
+        if let Some(item) = items.get_mut(&id) {
+            item.labels.push(label);
+        }
+    }
+    for (id, agent) in load_pairs(
+        conn,
+        "SELECT item_id, agent FROM item_assignees ORDER BY agent",
+    )? {
+        if let Some(item) = items.get_mut(&id) {
+            item.assignees.push(agent);
+        }
+    }
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT item_id, to_state, at_us FROM item_transitions
+             ORDER BY at_us, event_hash",
+        )
+        .context("prepare transitions query")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                Transition {
+                    to_state: row.get(1)?,
+                    at_us: row.get(2)?,
+                },
+            ))
+        })
+        .context("execute transitions query")?;
+    for row in rows {
+        let (id, transition) = row.context("read transition")?;
+        if let Some(item) = items.get_mut(&id) {
+            item.transitions.push(transition);
+        }
+    }
+
+    Ok(items.into_values().collect())
+}
+
+fn load_pairs(conn: &Connection, sql: &str) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare(sql).context("prepare flow query")?;
+    let pairs = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .context("execute flow query")?
+        .collect::<Result<Vec<_>, _>>()
+        .context("collect flow rows")?;
+    Ok(pairs)
+}
+
+/// Start of a window of `weeks` whole weeks ending at `now_us`.
+#[must_use]
+pub const fn window_start_us(now_us: i64, weeks: u32) -> i64 {
+    now_us - weeks as i64 * US_PER_WEEK
+}
+
+/// Compute flow metrics over the `weeks` whole weeks ending at `now_us`.
+///
+/// Without a slice the result is a single `all` group; with one, a group
+/// per slice value in name order.
+#[must_use]
+pub fn flow_metrics(
+    items: &[FlowItem],
+    now_us: i64,
+    weeks: u32,
+    slice: Option<FlowSlice>,
+) -> Vec<FlowGroup> {
+    let Some(slice) = slice else {
+        let all: Vec<&FlowItem> = items.iter().collect();
+        return vec![group_metrics("all", &all, now_us, weeks)];
+    };
+
+    let by_id: HashMap<&str, &FlowItem> = items.iter().map(|i| (i.id.as_str(), i)).collect();
+    let mut groups: BTreeMap<String, Vec<&FlowItem>> = BTreeMap::new();
+    for item in items {
+        for key in slice_keys(item, slice, &by_id) {
+            groups.entry(key).or_default().push(item);
+        }
+    }
+    groups
+        .iter()
+        .map(|(name, members)| group_metrics(name, members, now_us, weeks))
+        .collect()
+}
+
+fn slice_keys(item: &FlowItem, slice: FlowSlice, by_id: &HashMap<&str, &FlowItem>) -> Vec<String> {
+    let keys = match slice {
+        FlowSlice::Kind => vec![item.kind.clone()],
+        FlowSlice::Label => item.labels.clone(),
+        FlowSlice::Assignee => item.assignees.clone(),
+        FlowSlice::Goal => nearest_goal(item, by_id).into_iter().collect(),
+    };
+    if keys.is_empty() {
+        vec![NO_GROUP.to_string()]
+    } else {
+        keys
+    }
+}
+
+fn nearest_goal(item: &FlowItem, by_id: &HashMap<&str, &FlowItem>) -> Option<String> {
+    let mut parent = item.parent_id.as_deref();
+    // Bounded walk: a parent cycle must not hang the report.
+    for _ in 0..by_id.len() {
+        let node = by_id.get(parent?)?;
+        if node.kind == "goal" {
+            return Some(node.id.clone());
+        }
+        parent = node.parent_id.as_deref();
+    }
+    None
+}
+
+fn group_metrics(name: &str, items: &[&FlowItem], now_us: i64, weeks: u32) -> FlowGroup {
+    let window_start = window_start_us(now_us, weeks);
+    let mut lead = Vec::new();
+    let mut cycle = Vec::new();
+    let mut week_flow: Vec<WeekFlow> = (0..weeks)
+        .map(|week| WeekFlow {
+            start_us: window_start + i64::from(week) * US_PER_WEEK,
+            throughput: 0,
+            wip: 0,
+        })
+        .collect();
+
+    for item in items {
+        if let Some(done_us) = item.completed_at_us()
+            && done_us > window_start
+            && done_us <= now_us
+        {
+            lead.push(done_us - item.created_at_us);
+            if let Some(start_us) = item.started_at_us()
+                && start_us <= done_us
+            {
+                cycle.push(done_us - start_us);
+            }
+            let week = usize::try_from((done_us - window_start - 1) / US_PER_WEEK).unwrap_or(0);
+            if let Some(bucket) = week_flow.get_mut(week) {
+                bucket.throughput += 1;
+            }
+        }
+        for bucket in &mut week_flow {
+            let end_us = (bucket.start_us + US_PER_WEEK).min(now_us);
+            if item.state_at(end_us) == Some("doing") {
+                bucket.wip += 1;
+            }
+        }
+    }
+
+    FlowGroup {
+        group: name.to_string(),
+        completed: lead.len(),
+        lead_time: duration_stats(lead),
+        cycle_time: duration_stats(cycle),
+        weeks: week_flow,
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn duration_stats(mut durations_us: Vec<i64>) -> Option<DurationStats> {
+    if durations_us.is_empty() {
+        return None;
+    }
+    durations_us.sort_unstable();
+    let days = |us: i64| us as f64 / US_PER_DAY as f64;
+    let count = durations_us.len();
+    let total: i64 = durations_us.iter().sum();
+    Some(DurationStats {
+        count,
+        mean_days: days(total) / count as f64,
+        p50_days: days(percentile(&durations_us, 50)),
+        p85_days: days(percentile(&durations_us, 85)),
+        p95_days: days(percentile(&durations_us, 95)),
+        max_days: days(durations_us[count - 1]),
+    })
+}
+
+/// Nearest-rank percentile of a sorted, non-empty slice.
+pub(crate) fn percentile(sorted: &[i64], pct: usize) -> i64 {
+    let rank = (pct * sorted.len()).div_ceil(100);
+    sorted[rank.saturating_sub(1).min(sorted.len() - 1)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 100 * US_PER_DAY;
+
+    fn item(id: &str, kind: &str, created_day: i64, moves: &[(&str, i64)]) -> FlowItem {
+        let transitions: Vec<Transition> = moves
+            .iter()
+            .map(|&(state, day)| Transition {
+                to_state: state.to_string(),
+                at_us: day * US_PER_DAY,
+            })
+            .collect();
+        FlowItem {
+            id: id.to_string(),
+            kind: kind.to_string(),
+            state: transitions
+                .last()
+                .map_or("open".to_string(), |t| t.to_state.clone()),
+            created_at_us: created_day * US_PER_DAY,
+            transitions,
+            ..FlowItem::default()
+        }
+    }
+
+    #[test]
+    fn lead_cycle_and_throughput_over_weeks() {
+        let items = vec![
+            item("bn-1", "task", 80, &[("doing", 90), ("done", 92)]),
+            item("bn-2", "bug", 85, &[("doing", 86), ("done", 99)]),
+            item("bn-3", "task", 60, &[("done", 70)]),
+            item("bn-4", "task", 95, &[("doing", 96)]),
+            item("bn-5", "task", 50, &[("done", 88), ("open", 89)]),
+        ];
+        let groups = flow_metrics(&items, NOW, 2, None);
+        assert_eq!(groups.len(), 1);
+        let all = &groups[0];
+        assert_eq!(all.group, "all");
+        // bn-3 finished before the window; bn-5 was reopened.
+        assert_eq!(all.completed, 2);
+
+        let lead = all.lead_time.as_ref().expect("lead time");
+        assert!((lead.p50_days - 12.0).abs() < 1e-9);
+        assert!((lead.max_days - 14.0).abs() < 1e-9);
+        let cycle = all.cycle_time.as_ref().expect("cycle time");
+        assert!((cycle.p50_days - 2.0).abs() < 1e-9);
+        assert!((cycle.p95_days - 13.0).abs() < 1e-9);
+
+        let throughput: Vec<usize> = all.weeks.iter().map(|w| w.throughput).collect();
+        assert_eq!(throughput, vec![1, 1]);
+        // End of week one (day 93): bn-2 is in doing. End of week two: bn-4.
+        let wip: Vec<usize> = all.weeks.iter().map(|w| w.wip).collect();
+        assert_eq!(wip, vec![1, 1]);
+    }
+
+    #[test]
+    fn slices_by_kind_label_and_goal() {
+        let mut goal = item("bn-g", "goal", 1, &[]);
+        goal.parent_id = None;
+        let mut child = item("bn-1", "task", 80, &[("doing", 90), ("done", 92)]);
+        child.parent_id = Some("bn-g".to_string());
+        child.labels = vec!["api".to_string(), "ui".to_string()];
+        let bug = item("bn-2", "bug", 85, &[("done", 99)]);
+        let items = vec![goal, child, bug];
+
+        let by_kind = flow_metrics(&items, NOW, 4, Some(FlowSlice::Kind));
+        let names: Vec<&str> = by_kind.iter().map(|g| g.group.as_str()).collect();
+        assert_eq!(names, vec!["bug", "goal", "task"]);
+        assert_eq!(by_kind[0].completed, 1);
+        assert!(by_kind[0].cycle_time.is_none());
+
+        let by_label = flow_metrics(&items, NOW, 4, Some(FlowSlice::Label));
+        let names: Vec<&str> = by_label.iter().map(|g| g.group.as_str()).collect();
+        assert_eq!(names, vec![NO_GROUP, "api", "ui"]);
+
+        let by_goal = flow_metrics(&items, NOW, 4, Some(FlowSlice::Goal));
+        let goal_group = by_goal.iter().find(|g| g.group == "bn-g").expect("goal");
+        assert_eq!(goal_group.completed, 1);
+    }
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        let sorted = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+        assert_eq!(percentile(&sorted, 50), 5);
+        assert_eq!(percentile(&sorted, 85), 9);
+        assert_eq!(percentile(&sorted, 95), 10);
+        assert_eq!(percentile(&[7], 50), 7);
+    }
+}
@@ -0,0 +1,555 @@
+//! Monte-Carlo completion forecasts.
+//!
+//! Given the remaining bones of a goal and the blocking DAG between them,
+//! each simulation run:
+//!
+//! 1. Samples a duration for every remaining bone from the historical cycle
+//!    times of completed non-goal bones of the same size (all sizes when that size
+//!    has no history). A bone already in `doing` draws only from durations
+//!    longer than its elapsed time, minus that time.
+//! 2. Samples a week of historical throughput and turns it into a number of
+//!    parallel workers with Little's law: `workers = throughput × mean cycle
+//!    time`, at least one.
+//! 3. List-schedules the bones over the DAG: a bone starts once all its
+//!    blockers are finished and a worker is free. Bones already in `doing`
+//!    start first; ready bones are then taken in order of their latest start
+//!    on the size-weighted critical path, so critical work is never starved.
+//!
+//! The forecast reports the P50, P85 and P95 of the simulated finish times.
+//! Runs are seeded, so the same history gives the same forecast.
+//!
+//! Dependency cycles cannot be scheduled as-is; when the simulation stalls on
+//! one, the stalled bone with the earliest latest start is released.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap};
+
+use petgraph::Direction;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+
+use crate::flow::{self, FlowItem, US_PER_DAY, US_PER_WEEK};
+use crate::graph::{NormalizedGraph, RawGraph, compute_weighted_critical_path};
+
+/// Simulation runs per forecast.
+pub const DEFAULT_SIMULATIONS: usize = 2000;
+
+/// Weeks of history sampled for throughput.
+pub const DEFAULT_HISTORY_WEEKS: u32 = 12;
+
+const DEFAULT_SEED: u64 = 0x626f_6e65_7366_6374;
+
+const US_PER_HOUR: i64 = 3_600_000_000;
+
+/// Tuning for [`forecast_completion`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForecastOptions {
+    pub simulations: usize,
+    pub history_weeks: u32,
+    pub seed: u64,
+}
+
+impl Default for ForecastOptions {
+    fn default() -> Self {
+        Self {
+            simulations: DEFAULT_SIMULATIONS,
+            history_weeks: DEFAULT_HISTORY_WEEKS,
+            seed: DEFAULT_SEED,
+        }
+    }
+}
+
+/// One forecast percentile.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ForecastPoint {
+    /// Days from now until completion.
+    pub days: f64,
+    /// Completion date, Unix microseconds.
+    pub date_us: i64,
+}
+
+/// Completion forecast for a set of remaining bones.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Forecast {
+    /// Bones still to finish.
+    pub remaining: usize,
+    pub simulations: usize,
+    /// Completed bones whose cycle times were sampled.
+    pub history_samples: usize,
+    /// Mean bones completed per week over the history window.
+    pub weekly_throughput: f64,
+    /// Percentiles, or `None` when work remains but nothing has ever been
+    /// completed to learn from.
+    pub p50: Option<ForecastPoint>,
+    pub p85: Option<ForecastPoint>,
+    pub p95: Option<ForecastPoint>,
+}
+
+/// Unfinished, non-goal descendants of `goal_id`.
+#[must_use]
+pub fn goal_remaining(items: &[FlowItem], goal_id: &str) -> BTreeSet<String> {
+    let mut children: HashMap<&str, Vec<&FlowItem>> = HashMap::new();
+    for item in items {
+        if let Some(parent) = item.parent_id.as_deref() {
+            children.entry(parent).or_default().push(item);
+        }
+    }
+
+    let mut remaining = BTreeSet::new();
+    let mut seen = BTreeSet::from([goal_id]);
+    let mut stack = vec![goal_id];
+    while let Some(id) = stack.pop() {
+        for child in children.get(id).into_iter().flatten() {
+            if !seen.insert(child.id.as_str()) {
+                continue;
+            }
+            stack.push(child.id.as_str());
+            if child.kind != "goal" && child.state != "done" && child.state != "archived" {
+                remaining.insert(child.id.clone());
+            }
+        }
+    }
+    remaining
+}
+
+/// Cycle-time samples, in microseconds, sorted, per size.
+struct History {
+    by_size: HashMap<Option<String>, Vec<i64>>,
+    all: Vec<i64>,
+    weekly: Vec<usize>,
+}
+
+impl History {
+    fn from_items(items: &[FlowItem], now_us: i64, weeks: u32) -> Self {
+        let window_start = flow::window_start_us(now_us, weeks);
+        let mut by_size: HashMap<Option<String>, Vec<i64>> = HashMap::new();
+        let mut all = Vec::new();
+        let mut weekly = vec![0; weeks as usize];
+        for item in items {
+            // Goals close when their children do; they are not work.
+            if item.kind == "goal" {
+                continue;
+            }
+            let Some(done_us) = item.completed_at_us().filter(|&at| at <= now_us) else {
+                continue;
+            };
+            let start_us = item
+                .started_at_us()
+                .filter(|&at| at <= done_us)
+                .unwrap_or(item.created_at_us);
+            let duration = (done_us - start_us).max(0);
+            by_size.entry(item.size.clone()).or_default().push(duration);
+            all.push(duration);
+            if done_us > window_start {
+                let week = usize::try_from((done_us - window_start - 1) / US_PER_WEEK).unwrap_or(0);
+                if let Some(count) = weekly.get_mut(week) {
+                    *count += 1;
+                }
+            }
+        }
+        for samples in by_size.values_mut() {
+            samples.sort_unstable();
+        }
+        all.sort_unstable();
+        Self {
+            by_size,
+            all,
+            weekly,
+        }
+    }
+
+    fn samples(&self, size: Option<&String>) -> &[i64] {
+        self.by_size
+            .get(&size.cloned())
+            .filter(|samples| !samples.is_empty())
+            .map_or(&self.all, Vec::as_slice)
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn mean_us(samples: &[i64]) -> f64 {
+        samples.iter().sum::<i64>() as f64 / samples.len().max(1) as f64
+    }
+}
+
+/// One remaining bone, ready to simulate.
+struct Task<'a> {
+    item: &'a FlowItem,
+    /// Time already spent in `doing`, if started.
+    elapsed_us: Option<i64>,
+    successors: Vec<usize>,
+    blockers: usize,
+}
+
+/// Forecast when every bone in `remaining` will be finished.
+///
+/// `items` supplies both the history and the remaining bones; `raw` supplies
+/// the blocking edges between them.
+#[must_use]
+pub fn forecast_completion(
+    items: &[FlowItem],
+    remaining: &BTreeSet<String>,
+    raw: &RawGraph,
+    now_us: i64,
+    options: ForecastOptions,
+) -> Forecast {
+    let history = History::from_items(items, now_us, options.history_weeks.max(1));
+    let tasks = build_tasks(items, remaining, raw, now_us);
+    #[allow(clippy::cast_precision_loss)]
+    let weekly_throughput =
+        history.weekly.iter().sum::<usize>() as f64 / history.weekly.len().max(1) as f64;
+    let mut forecast = Forecast {
+        remaining: tasks.len(),
+        simulations: 0,
+        history_samples: history.all.len(),
+        weekly_throughput,
+        p50: None,
+        p85: None,
+        p95: None,
+    };
+
+    let point = |us: i64| {
+        #[allow(clippy::cast_precision_loss)]
+        let days = us as f64 / US_PER_DAY as f64;
+        Some(ForecastPoint {
+            days,
+            date_us: now_us + us,
+        })
+    };
+    if tasks.is_empty() {
+        forecast.p50 = point(0);
+        forecast.p85 = point(0);
+        forecast.p95 = point(0);
+        return forecast;
+    }
+    if history.all.is_empty() || options.simulations == 0 {
+        return forecast;
+    }
+
+    let order = priority_order(&tasks, &history);
+    let mut rng = StdRng::seed_from_u64(options.seed);
+    let mut finishes: Vec<i64> = (0..options.simulations)
+        .map(|_| simulate(&tasks, &order, &history, &mut rng))
+        .collect();
+    finishes.sort_unstable();
+
+    forecast.simulations = options.simulations;
+    forecast.p50 = point(flow::percentile(&finishes, 50));
+    forecast.p85 = point(flow::percentile(&finishes, 85));
+    forecast.p95 = point(flow::percentile(&finishes, 95));
+    forecast
+}
+
+fn build_tasks<'a>(
+    items: &'a [FlowItem],
+    remaining: &BTreeSet<String>,
+    raw: &RawGraph,
+    now_us: i64,
+) -> Vec<Task<'a>> {
+    let mut tasks: Vec<Task<'a>> = items
+        .iter()
+        .filter(|item| remaining.contains(&item.id))
+        .map(|item| Task {
+            item,
+            elapsed_us: (item.state == "doing")
+                .then(|| item.started_at_us().map_or(0, |at| (now_us - at).max(0))),
+            successors: Vec::new(),
+            blockers: 0,
+        })
+        .collect();
+    let index: HashMap<&str, usize> = tasks
+        .iter()
+        .enumerate()
+        .map(|(idx, task)| (task.item.id.as_str(), idx))
+        .collect();
+
+    for from in 0..tasks.len() {
+        let Some(node) = raw.node_index(&tasks[from].item.id) else {
+            continue;
+        };
+        let mut successors: Vec<usize> = raw
+            .graph
+            .neighbors_directed(node, Direction::Outgoing)
+            .filter_map(|n| raw.graph.node_weight(n))
+            .filter_map(|id| index.get(id.as_str()).copied())
+            .filter(|&to| to != from)
+            .collect();
+        successors.sort_unstable();
+        successors.dedup();
+        for &to in &successors {
+            tasks[to].blockers += 1;
+        }
+        tasks[from].successors = successors;
+    }
+    tasks
+}
+
+/// Task indices by latest start on the size-weighted critical path, then ID.
+fn priority_order(tasks: &[Task<'_>], history: &History) -> Vec<usize> {
+    let mut graph = crate::graph::DiGraph::new();
+    let nodes: Vec<_> = tasks
+        .iter()
+        .map(|task| graph.add_node(task.item.id.clone()))
+        .collect();
+    for (from, task) in tasks.iter().enumerate() {
+        for &to in &task.successors {
+            graph.add_edge(nodes[from], nodes[to], ());
+        }
+    }
+    let node_map = tasks
+        .iter()
+        .zip(&nodes)
+        .map(|(task, &node)| (task.item.id.clone(), node))
+        .collect();
+    let normalized = NormalizedGraph::from_raw(RawGraph {
+        graph,
+        node_map,
+        content_hash: String::new(),
+    });
+
+    let hours: HashMap<&str, usize> = tasks
+        .iter()
+        .map(|task| {
+            let mean = History::mean_us(history.samples(task.item.size.as_ref()));
+            #[allow(
+                clippy::cast_possible_truncation,
+                clippy::cast_sign_loss,
+                clippy::cast_precision_loss
+            )]
+            let hours = (mean / US_PER_HOUR as f64).ceil() as usize;
+            (task.item.id.as_str(), hours.max(1))
+        })
+        .collect();
+    let result =
+        compute_weighted_critical_path(&normalized, |id| hours.get(id).copied().unwrap_or(1));
+
+    let mut order: Vec<usize> = (0..tasks.len()).collect();
+    order.sort_by_key(|&idx| {
+        let id = tasks[idx].item.id.as_str();
+        let latest = result
+            .item_timings
+            .get(id)
+            .map_or(usize::MAX, |t| t.latest_start);
+        (latest, id)
+    });
+    order
+}
+
+/// Sample a duration for `task`, in microseconds.
+fn sample_duration(task: &Task<'_>, history: &History, rng: &mut StdRng) -> i64 {
+    let samples = history.samples(task.item.size.as_ref());
+    if let Some(elapsed) = task.elapsed_us {
+        let longer = samples.partition_point(|&s| s <= elapsed);
+        if longer < samples.len() {
+            return samples[rng.gen_range(longer..samples.len())] - elapsed;
+        }
+    }
+    samples[rng.gen_range(0..samples.len())]
+}
+
+/// Sample a worker count from one historical week of throughput.
+fn sample_workers(history: &History, rng: &mut StdRng) -> usize {
+    let week = history.weekly[rng.gen_range(0..history.weekly.len())];
+    #[allow(clippy::cast_precision_loss)]
+    let per_day = week as f64 / 7.0;
+    #[allow(clippy::cast_precision_loss)]
+    let mean_days = History::mean_us(&history.all) / US_PER_DAY as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let workers = (per_day * mean_days).round() as usize;
+    workers.max(1)
+}
+
+/// One simulation run; returns microseconds from now until the last bone
+/// finishes.
+fn simulate(tasks: &[Task<'_>], order: &[usize], history: &History, rng: &mut StdRng) -> i64 {
+    let durations: Vec<i64> = tasks
+        .iter()
+        .map(|task| sample_duration(task, history, rng))
+        .collect();
+    let in_progress = tasks.iter().filter(|t| t.elapsed_us.is_some()).count();
+    let workers = sample_workers(history, rng).max(in_progress);
+
+    // Ready queue keyed by (not yet started, priority rank).
+    let mut rank = vec![0; tasks.len()];
+    for (position, &idx) in order.iter().enumerate() {
+        rank[idx] = position;
+    }
+    let mut blockers: Vec<usize> = tasks.iter().map(|t| t.blockers).collect();
+    let mut started = vec![false; tasks.len()];
+    let mut ready: BinaryHeap<Reverse<(bool, usize)>> = order
+        .iter()
+        .filter(|&&idx| tasks[idx].elapsed_us.is_some() || blockers[idx] == 0)
+        .map(|&idx| Reverse((tasks[idx].elapsed_us.is_none(), rank[idx])))
+        .collect();
+    let mut running: BinaryHeap<Reverse<(i64, usize)>> = BinaryHeap::new();
+    let mut now = 0;
+    let mut finished = 0;
+
+    while finished < tasks.len() {
+        while running.len() < workers
+            && let Some(Reverse((_, position))) = ready.pop()
+        {
+            let idx = order[position];
+            if !std::mem::replace(&mut started[idx], true) {
+                running.push(Reverse((now + durations[idx], idx)));
+            }
+        }
+        let Some(Reverse((end, idx))) = running.pop() else {
+            // Stalled on a dependency cycle: release the most urgent bone.
+            if let Some(&idx) = order.iter().find(|&&idx| !started[idx]) {
+                ready.push(Reverse((true, rank[idx])));
+                continue;
+            }
+            break;
+        };
+        now = end;
+        finished += 1;
+        for &next in &tasks[idx].successors {
+            blockers[next] = blockers[next].saturating_sub(1);
+            if blockers[next] == 0 && !started[next] {
+                ready.push(Reverse((true, rank[next])));
+            }
+        }
+    }
+    now
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flow::Transition;
+
+    const NOW: i64 = 100 * US_PER_DAY;
+
+    fn item(id: &str, state: &str, size: Option<&str>, moves: &[(&str, i64)]) -> FlowItem {
+        FlowItem {
+            id: id.to_string(),
+            kind: "task".to_string(),
+            state: state.to_string(),
+            size: size.map(str::to_string),
+            parent_id: Some("bn-goal".to_string()),
+            created_at_us: 0,
+            transitions: moves
+                .iter()
+                .map(|&(to, day)| Transition {
+                    to_state: to.to_string(),
+                    at_us: day * US_PER_DAY,
+                })
+                .collect(),
+            ..FlowItem::default()
+        }
+    }
+
+    fn graph(items: &[FlowItem], edges: &[(&str, &str)]) -> RawGraph {
+        let mut graph = crate::graph::DiGraph::new();
+        let mut node_map = HashMap::new();
+        for item in items {
+            node_map.insert(item.id.clone(), graph.add_node(item.id.clone()));
+        }
+        for (from, to) in edges {
+            graph.add_edge(node_map[*from], node_map[*to], ());
+        }
+        RawGraph {
+            graph,
+            node_map,
+            content_hash: String::new(),
+        }
+    }
+
+    /// History of one-day small bones, one finished per week.
+    fn history() -> Vec<FlowItem> {
+        (0..12)
+            .map(|week| {
+                let done = 99 - week * 7;
+                item(
+                    &format!("bn-h{week}"),
+                    "done",
+                    Some("s"),
+                    &[("doing", done - 1), ("done", done)],
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn chained_bones_finish_after_their_combined_durations() {
+        let mut items = history();
+        items.push(item("bn-a", "open", Some("s"), &[]));
+        items.push(item("bn-b", "open", Some("s"), &[]));
+        items.push(item("bn-c", "open", Some("s"), &[]));
+        let raw = graph(&items, &[("bn-a", "bn-b"), ("bn-b", "bn-c")]);
+        let remaining = goal_remaining(&items, "bn-goal");
+        assert_eq!(remaining.len(), 3);
+
+        let forecast =
+            forecast_completion(&items, &remaining, &raw, NOW, ForecastOptions::default());
+        assert_eq!(forecast.remaining, 3);
+        assert_eq!(forecast.history_samples, 12);
+        assert!((forecast.weekly_throughput - 1.0).abs() < 1e-9);
+        for point in [&forecast.p50, &forecast.p85, &forecast.p95] {
+            let point = point.as_ref().expect("forecast");
+            assert!((point.days - 3.0).abs() < 1e-9);
+            assert_eq!(point.date_us, NOW + 3 * US_PER_DAY);
+        }
+    }
+
+    #[test]
+    fn percentiles_spread_with_varied_history() {
+        let mut items = history();
+        items.push(item(
+            "bn-slow",
+            "done",
+            Some("s"),
+            &[("doing", 80), ("done", 90)],
+        ));
+        items.push(item("bn-a", "open", Some("s"), &[]));
+        items.push(item("bn-b", "doing", Some("s"), &[("doing", 99)]));
+        let raw = graph(&items, &[]);
+        let remaining = goal_remaining(&items, "bn-goal");
+
+        let forecast =
+            forecast_completion(&items, &remaining, &raw, NOW, ForecastOptions::default());
+        // bn-b has been in doing for a day, so only the ten-day sample is
+        // longer; one worker then runs bn-a after it.
+        let p50 = forecast.p50.expect("p50").days;
+        let p95 = forecast.p95.expect("p95").days;
+        assert!(p50 >= 10.0 - 1e-9);
+        assert!(p50 <= p95);
+        assert!(p95 <= 19.0 + 1e-9);
+        // Same seed, same answer.
+        let again = forecast_completion(&items, &remaining, &raw, NOW, ForecastOptions::default());
+        assert_eq!(again.p95.expect("p95").days, p95);
+    }
+
+    #[test]
+    fn no_history_gives_no_percentiles() {
+        let items = vec![item("bn-a", "open", None, &[])];
+        let raw = graph(&items, &[]);
+        let remaining = goal_remaining(&items, "bn-goal");
+        let forecast =
+            forecast_completion(&items, &remaining, &raw, NOW, ForecastOptions::default());
+        assert_eq!(forecast.remaining, 1);
+        assert!(forecast.p50.is_none());
+
+        let done = forecast_completion(
+            &items,
+            &BTreeSet::new(),
+            &raw,
+            NOW,
+            ForecastOptions::default(),
+        );
+        assert_eq!(done.p95.expect("p95").date_us, NOW);
+    }
+
+    #[test]
+    fn dependency_cycles_do_not_stall_the_simulation() {
+        let mut items = history();
+        items.push(item("bn-a", "open", Some("s"), &[]));
+        items.push(item("bn-b", "open", Some("s"), &[]));
+        let raw = graph(&items, &[("bn-a", "bn-b"), ("bn-b", "bn-a")]);
+        let remaining = goal_remaining(&items, "bn-goal");
+        let forecast =
+            forecast_completion(&items, &remaining, &raw, NOW, ForecastOptions::default());
+        assert!((forecast.p50.expect("p50").days - 2.0).abs() < 1e-9);
+    }
+}
@@ -8,20 +8,25 @@
 //!
 //! # Definitions
 //!
-//! All durations are measured in "steps" where each item contributes 1 step.
+//! Every item has a non-negative duration supplied by the caller (typically
+//! derived from its size via the project's size → duration table).
+//! [`compute_critical_path`] is the unweighted special case where each item
+//! contributes 1 step.  All timings and slack are reported in the same units
+//! as the durations.
 //!
 //! | Term              | Definition |
 //! |-------------------|------------|
-//! | `earliest_start`  | Earliest step at which an item can begin (all predecessors done). |
-//! | `earliest_finish` | `earliest_start + 1`. |
-//! | `latest_start`    | Latest step at which the item can begin without delaying the project. |
-//! | `latest_finish`   | `latest_start + 1`. |
+//! | `earliest_start`  | Earliest time at which an item can begin (all predecessors done). |
+//! | `earliest_finish` | `earliest_start + duration`. |
+//! | `latest_start`    | Latest time at which the item can begin without delaying the project. |
+//! | `latest_finish`   | `latest_start + duration`. |
 //! | `slack`           | `latest_start - earliest_start` — zero on the critical path. |
 //!
 //! # Algorithm
 //!
 //! 1. Work on the **condensed DAG** so cycles are handled (each SCC becomes
-//!    one super-node; its members are all reported as critical together).
+//!    one super-node whose duration is the sum of its members' durations;
+//!    its members are all reported as critical together).
 //! 2. **Forward pass** in topological order: compute `earliest_start` /
 //!    `earliest_finish` for every condensed node.
 //! 3. **Backward pass** in reverse topological order: compute `latest_finish`
@@ -47,15 +52,20 @@ use crate::graph::normalize::NormalizedGraph;
 // ---------------------------------------------------------------------------
 
 /// Per-item timing computed during critical path analysis.
+///
+/// Members of a cycle share the timing of their SCC, including its combined
+/// duration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ItemTiming {
-    /// Earliest step at which the item can start (0-based).
+    /// Duration of the item (or of its SCC when part of a cycle).
+    pub duration: usize,
+    /// Earliest time at which the item can start (0-based).
     pub earliest_start: usize,
-    /// Earliest step at which the item finishes (`earliest_start + 1`).
+    /// Earliest time at which the item finishes (`earliest_start + duration`).
     pub earliest_finish: usize,
-    /// Latest step at which the item can start without delaying the project.
+    /// Latest time at which the item can start without delaying the project.
     pub latest_start: usize,
-    /// Latest step at which the item finishes (`latest_start + 1`).
+    /// Latest time at which the item finishes (`latest_start + duration`).
     pub latest_finish: usize,
     /// Total float (slack): `latest_start - earliest_start`.
     ///
@@ -79,6 +89,10 @@ pub struct CriticalPathResult {
     pub item_timings: HashMap<String, ItemTiming>,
     /// Length of the critical path (number of items).
     pub total_length: usize,
+    /// Earliest project completion time, in duration units.
+    ///
+    /// Equals `total_length` for [`compute_critical_path`].
+    pub total_duration: usize,
 }
 
 impl CriticalPathResult {
@@ -90,6 +104,7 @@ impl CriticalPathResult {
             critical_items: HashSet::new(),
             item_timings: HashMap::new(),
             total_length: 0,
+            total_duration: 0,
         }
     }
 
@@ -104,7 +119,8 @@ impl CriticalPathResult {
 // Core computation
 // ---------------------------------------------------------------------------
 
-/// Compute the critical path for the dependency graph described by `ng`.
+/// Compute the critical path for the dependency graph described by `ng`,
+/// counting every item as 1 step.
 ///
 /// Uses the **condensed DAG** so the computation is correct even when the
 /// raw graph contains dependency cycles.  Members of cycle SCCs are assigned
@@ -120,6 +136,21 @@ impl CriticalPathResult {
 /// - The total project length in steps.
 #[must_use]
 pub fn compute_critical_path(ng: &NormalizedGraph) -> CriticalPathResult {
+    compute_weighted_critical_path(ng, |_| 1)
+}
+
+/// Compute the critical path with per-item durations.
+///
+/// `duration` maps an item ID to its duration; a cycle SCC takes the sum of
+/// its members' durations.  Zero is allowed (e.g. for completed work that
+/// still appears in the graph) and makes the item transparent to the
+/// schedule.  Timings and slack in the result are in the same units as
+/// `duration`.
+#[must_use]
+pub fn compute_weighted_critical_path<F>(ng: &NormalizedGraph, duration: F) -> CriticalPathResult
+where
+    F: Fn(&str) -> usize,
+{
     let condensed = &ng.condensed;
 
     if condensed.node_count() == 0 {
@@ -133,6 +164,16 @@ pub fn compute_critical_path(ng: &NormalizedGraph) -> CriticalPathResult {
         condensed.node_indices().collect()
     });
 
+    let node_duration: HashMap<NodeIndex, usize> = topo
+        .iter()
+        .map(|&v| {
+            let d = condensed
+                .node_weight(v)
+                .map_or(0, |n| n.members.iter().map(|m| duration(m)).sum());
+            (v, d)
+        })
+        .collect();
+
     // --- Forward pass: earliest_start / earliest_finish ---
     let mut earliest_finish: HashMap<NodeIndex, usize> = HashMap::with_capacity(topo.len());
 
@@ -142,11 +183,11 @@ pub fn compute_critical_path(ng: &NormalizedGraph) -> CriticalPathResult {
             .map(|e| earliest_finish.get(&e.source()).copied().unwrap_or(0))
             .max()
             .unwrap_or(0);
-        earliest_finish.insert(v, max_pred_finish + 1);
+        earliest_finish.insert(v, max_pred_finish + node_duration[&v]);
     }
 
     // Project duration = max earliest_finish over all nodes.
-    let project_finish = earliest_finish.values().copied().max().unwrap_or(1);
+    let project_finish = earliest_finish.values().copied().max().unwrap_or(0);
 
     // --- Backward pass: latest_finish / latest_start ---
     let mut latest_finish: HashMap<NodeIndex, usize> = HashMap::with_capacity(topo.len());
@@ -155,11 +196,10 @@ pub fn compute_critical_path(ng: &NormalizedGraph) -> CriticalPathResult {
         let min_succ_start = condensed
             .edges_directed(v, Direction::Outgoing)
             .map(|e| {
-                let lf = latest_finish
-                    .get(&e.target())
-                    .copied()
-                    .unwrap_or(project_finish);
-                lf - 1 // latest_start of successor = latest_finish[succ] - 1
+                let succ = e.target();
+                let lf = latest_finish.get(&succ).copied().unwrap_or(project_finish);
+                // latest_start of successor = latest_finish[succ] - duration[succ]
+                lf.saturating_sub(node_duration[&succ])
             })
             .min()
             .unwrap_or(project_finish);
@@ -175,15 +215,17 @@ pub fn compute_critical_path(ng: &NormalizedGraph) -> CriticalPathResult {
     let mut node_slack: HashMap<NodeIndex, usize> = HashMap::with_capacity(topo.len());
 
     for &v in &topo {
+        let d = node_duration[&v];
         let ef = earliest_finish[&v];
-        let es = ef.saturating_sub(1);
+        let es = ef.saturating_sub(d);
         let lf = latest_finish[&v];
-        let ls = lf.saturating_sub(1);
+        let ls = lf.saturating_sub(d);
         let slack = ls.saturating_sub(es);
 
         node_slack.insert(v, slack);
 
         let timing = ItemTiming {
+            duration: d,
             earliest_start: es,
             earliest_finish: ef,
             latest_start: ls,
@@ -225,6 +267,7 @@ pub fn compute_critical_path(ng: &NormalizedGraph) -> CriticalPathResult {
         critical_items,
         item_timings,
         total_length,
+        total_duration: project_finish,
     }
 }
 
@@ -556,4 +599,73 @@ mod tests {
         assert!(result.critical_items.contains("A"));
         assert!(result.critical_items.contains("C"));
     }
+
+    // -----------------------------------------------------------------------
+    // Weighted durations
+    // -----------------------------------------------------------------------
+
+    fn durations(pairs: &[(&str, usize)]) -> impl Fn(&str) -> usize {
+        let map: HashMap<String, usize> =
+            pairs.iter().map(|(k, v)| ((*k).to_string(), *v)).collect();
+        move |id| map.get(id).copied().unwrap_or(1)
+    }
+
+    #[test]
+    fn weighted_long_item_beats_longer_chain() {
+        // A → B → C → D (each 1) vs A → E → D with E = 5
+        let ng = make_normalized(&[("A", "B"), ("B", "C"), ("C", "D"), ("A", "E"), ("E", "D")]);
+        let result = compute_weighted_critical_path(&ng, durations(&[("E", 5)]));
+
+        assert_eq!(result.critical_path, vec!["A", "E", "D"]);
+        assert_eq!(result.total_duration, 7);
+        assert_eq!(result.item_timings["E"].earliest_finish, 6);
+        // B and C together take 2 units against E's 5: 3 units of slack.
+        assert_eq!(result.item_timings["B"].slack, 3);
+        assert_eq!(result.item_timings["C"].slack, 3);
+        assert!(!result.critical_items.contains("B"));
+    }
+
+    #[test]
+    fn weighted_timing_invariants_hold() {
+        let ng = make_normalized(&[("A", "B"), ("B", "C"), ("C", "D"), ("A", "E"), ("E", "D")]);
+        let result =
+            compute_weighted_critical_path(&ng, durations(&[("A", 2), ("B", 3), ("E", 8)]));
+
+        for (id, t) in &result.item_timings {
+            assert_eq!(t.earliest_finish, t.earliest_start + t.duration, "{id}: ef");
+            assert_eq!(t.latest_finish, t.latest_start + t.duration, "{id}: lf");
+            assert!(t.latest_finish <= result.total_duration, "{id}: lf <= end");
+        }
+        assert_eq!(result.total_duration, 11);
+    }
+
+    #[test]
+    fn weighted_zero_duration_items_are_transparent() {
+        // A (done, 0) → B (3); C (2) alone
+        let ng = make_normalized_nodes(&["A", "B", "C"], &[("A", "B")]);
+        let result =
+            compute_weighted_critical_path(&ng, durations(&[("A", 0), ("B", 3), ("C", 2)]));
+
+        assert_eq!(result.total_duration, 3);
+        assert_eq!(result.item_timings["B"].earliest_start, 0);
+        assert_eq!(result.item_timings["C"].slack, 1);
+    }
+
+    #[test]
+    fn weighted_cycle_sums_member_durations() {
+        // {A, B} cycle → C
+        let ng = make_normalized(&[("A", "B"), ("B", "A"), ("B", "C")]);
+        let result = compute_weighted_critical_path(&ng, durations(&[("A", 2), ("B", 3)]));
+
+        assert_eq!(result.item_timings["A"].duration, 5);
+        assert_eq!(result.item_timings["C"].earliest_start, 5);
+        assert_eq!(result.total_duration, 6);
+    }
+
+    #[test]
+    fn unweighted_total_duration_matches_length() {
+        let ng = make_normalized(&[("A", "B"), ("B", "C"), ("X", "Y")]);
+        let result = compute_critical_path(&ng);
+        assert_eq!(result.total_duration, result.total_length);
+    }
 }
@@ -49,7 +49,9 @@ pub mod stats;
 
 // Re-export primary types at module level for convenience.
 pub use build::RawGraph;
-pub use critical_path::{CriticalPathResult, ItemTiming, compute_critical_path};
+pub use critical_path::{
+    CriticalPathResult, ItemTiming, compute_critical_path, compute_weighted_critical_path,
+};
 pub use cycles::{CycleReport, find_all_cycles, report_cycles_with_breaks, would_create_cycle};
 pub use diagnostics::{DiGraph, HealthMetrics, find_sccs, health_metrics, topological_layers};
 pub use normalize::{NormalizedGraph, SccNode};
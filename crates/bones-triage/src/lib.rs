@@ -7,6 +7,8 @@
 //! - **Logging**: Use `tracing` macros (`info!`, `warn!`, `error!`, `debug!`, `trace!`).
 
 pub mod feedback;
+pub mod flow;
+pub mod forecast;
 pub mod graph;
 pub mod metrics;
 pub mod schedule;
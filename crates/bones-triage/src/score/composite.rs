@@ -2,6 +2,13 @@ use bones_core::model::item::Urgency;
 use serde::{Deserialize, Serialize};
 
 const DECAY_WINDOW_DAYS: f64 = 14.0;
+const DEADLINE_WINDOW_DAYS: f64 = 14.0;
+
+/// Fixed weight of the deadline component `T`.
+///
+/// Deadlines are explicit user intent rather than a learned signal, so this
+/// weight sits outside [`CompositeWeights`] and is not tuned by feedback.
+pub const DEADLINE_WEIGHT: f64 = 0.30;
 
 /// Raw metric values used to compute a composite priority score.
 ///
@@ -14,6 +21,9 @@ pub struct MetricInputs {
     pub betweenness: f64,
     pub urgency: Urgency,
     pub decay_days: f64,
+    /// Days until the item's due date (negative when overdue), or `None`
+    /// when the item has no deadline.
+    pub due_in_days: Option<f64>,
 }
 
 /// Configurable weights for the composite formula:
@@ -42,6 +52,10 @@ impl Default for CompositeWeights<f64> {
 
 /// Compute composite priority score from normalized inputs.
 ///
+/// The weighted sum is extended with a deadline term:
+/// `P(v) + DEADLINE_WEIGHT*T`, where `T` ramps from 0 to 1 over the
+/// [`DEADLINE_WINDOW_DAYS`] before the due date (see [`deadline_component`]).
+///
 /// - Returns `f64::INFINITY` when urgency is `Urgent`.
 /// - Returns `f64::NEG_INFINITY` when urgency is `Punt`.
 #[must_use]
@@ -57,18 +71,36 @@ pub fn composite_score(inputs: &MetricInputs, weights: &CompositeWeights) -> f64
     let bc = normalize_unit(inputs.betweenness);
     let u = urgency_component(inputs.urgency);
     let d = decay_component(inputs.decay_days);
-
-    weights.epsilon.mul_add(
-        d,
-        weights.delta.mul_add(
-            u,
-            weights
-                .gamma
-                .mul_add(bc, weights.alpha.mul_add(cp, weights.beta * pr)),
+    let t = deadline_component(inputs.due_in_days);
+
+    DEADLINE_WEIGHT.mul_add(
+        t,
+        weights.epsilon.mul_add(
+            d,
+            weights.delta.mul_add(
+                u,
+                weights
+                    .gamma
+                    .mul_add(bc, weights.alpha.mul_add(cp, weights.beta * pr)),
+            ),
         ),
     )
 }
 
+/// Deadline pressure in `[0, 1]` for an item due in `due_in_days`.
+///
+/// Items without a deadline, or due more than [`DEADLINE_WINDOW_DAYS`] out,
+/// contribute `0.0`. Pressure rises linearly as the deadline approaches and
+/// saturates at `1.0` once the item is due or overdue.
+#[must_use]
+pub fn deadline_component(due_in_days: Option<f64>) -> f64 {
+    match due_in_days {
+        Some(days) if days.is_finite() => normalize_unit(1.0 - days / DEADLINE_WINDOW_DAYS),
+        Some(days) if days.is_infinite() && days.is_sign_negative() => 1.0,
+        _ => 0.0,
+    }
+}
+
 /// Min-max normalization that maps raw metric values to `[0, 1]`.
 ///
 /// If all values are equal (including a single-element slice), all outputs are
@@ -160,6 +192,7 @@ mod tests {
                 betweenness: 0.3,
                 urgency: Urgency::Urgent,
                 decay_days: 9.0,
+                due_in_days: None,
             },
             &CompositeWeights::default(),
         );
@@ -176,6 +209,7 @@ mod tests {
                 betweenness: 0.7,
                 urgency: Urgency::Punt,
                 decay_days: 40.0,
+                due_in_days: None,
             },
             &CompositeWeights::default(),
         );
@@ -192,6 +226,7 @@ mod tests {
                 betweenness: 0.6,
                 urgency: Urgency::Default,
                 decay_days: 7.0,
+                due_in_days: None,
             },
             &CompositeWeights::default(),
         );
@@ -209,6 +244,7 @@ mod tests {
                 betweenness: f64::NAN,
                 urgency: Urgency::Default,
                 decay_days: 45.0,
+                due_in_days: None,
             },
             &CompositeWeights::default(),
         );
@@ -226,6 +262,7 @@ mod tests {
                 betweenness: 0.3,
                 urgency: Urgency::Default,
                 decay_days: 0.0,
+                due_in_days: None,
             },
             &CompositeWeights::default(),
         );
@@ -237,10 +274,61 @@ mod tests {
                 betweenness: 0.3,
                 urgency: Urgency::Default,
                 decay_days: 14.0,
+                due_in_days: None,
             },
             &CompositeWeights::default(),
         );
 
         assert!(boosted > baseline);
     }
+
+    #[test]
+    fn deadline_component_ramps_inside_window() {
+        assert_approx_eq(deadline_component(None), 0.0);
+        assert_approx_eq(deadline_component(Some(30.0)), 0.0);
+        assert_approx_eq(deadline_component(Some(7.0)), 0.5);
+        assert_approx_eq(deadline_component(Some(0.0)), 1.0);
+        assert_approx_eq(deadline_component(Some(-3.0)), 1.0);
+        assert_approx_eq(deadline_component(Some(f64::NAN)), 0.0);
+    }
+
+    #[test]
+    fn composite_score_boosts_items_near_deadline() {
+        let inputs = MetricInputs {
+            critical_path: 0.3,
+            pagerank: 0.3,
+            betweenness: 0.3,
+            urgency: Urgency::Default,
+            decay_days: 0.0,
+            due_in_days: None,
+        };
+        let weights = CompositeWeights::default();
+
+        let undated = composite_score(&inputs, &weights);
+        let distant = composite_score(
+            &MetricInputs {
+                due_in_days: Some(60.0),
+                ..inputs
+            },
+            &weights,
+        );
+        let imminent = composite_score(
+            &MetricInputs {
+                due_in_days: Some(1.0),
+                ..inputs
+            },
+            &weights,
+        );
+        let overdue = composite_score(
+            &MetricInputs {
+                due_in_days: Some(-2.0),
+                ..inputs
+            },
+            &weights,
+        );
+
+        assert_approx_eq(distant, undated);
+        assert!(imminent > distant);
+        assert_approx_eq(overdue, undated + DEADLINE_WEIGHT);
+    }
 }
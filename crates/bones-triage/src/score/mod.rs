@@ -1,3 +1,6 @@
 pub mod composite;
 
-pub use composite::{CompositeWeights, MetricInputs, composite_score, normalize_metric};
+pub use composite::{
+    CompositeWeights, DEADLINE_WEIGHT, MetricInputs, composite_score, deadline_component,
+    normalize_metric,
+};
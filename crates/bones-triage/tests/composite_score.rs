@@ -46,6 +46,7 @@ fn urgent_item_always_scores_highest() {
         betweenness: 0.0,
         urgency: Urgency::Urgent,
         decay_days: 0.0,
+        due_in_days: None,
     };
 
     // Item B: urgent=false, hub node (highest PageRank, highest betweenness)
@@ -55,6 +56,7 @@ fn urgent_item_always_scores_highest() {
         betweenness: 1.0,
         urgency: Urgency::Default,
         decay_days: 14.0,
+        due_in_days: None,
     };
 
     let weights = CompositeWeights::default();
@@ -81,6 +83,7 @@ fn urgent_item_beats_hub_across_all_weight_configs() {
         betweenness: 0.0,
         urgency: Urgency::Urgent,
         decay_days: 0.0,
+        due_in_days: None,
     };
 
     let default_hub = MetricInputs {
@@ -89,6 +92,7 @@ fn urgent_item_beats_hub_across_all_weight_configs() {
         betweenness: 1.0,
         urgency: Urgency::Default,
         decay_days: 14.0,
+        due_in_days: None,
     };
 
     // Three different weight configurations including extreme cases.
@@ -136,6 +140,7 @@ fn high_urgency_no_deps_beats_many_deps_default_urgency() {
         betweenness: 0.0,
         urgency: Urgency::Urgent,
         decay_days: 0.0,
+        due_in_days: None,
     };
 
     let b_hub_default = MetricInputs {
@@ -144,6 +149,7 @@ fn high_urgency_no_deps_beats_many_deps_default_urgency() {
         betweenness: 1.0,
         urgency: Urgency::Default,
         decay_days: 14.0,
+        due_in_days: None,
     };
 
     let weights = CompositeWeights::default();
@@ -170,6 +176,7 @@ fn punted_item_returns_negative_infinity() {
         betweenness: 0.7,
         urgency: Urgency::Punt,
         decay_days: 0.0,
+        due_in_days: None,
     };
 
     let score = composite_score(&punt, &CompositeWeights::default());
@@ -195,6 +202,7 @@ fn punted_item_excluded_from_results() {
                 betweenness: 0.5,
                 urgency: Urgency::Default,
                 decay_days: 0.0,
+                due_in_days: None,
             },
         ),
         (
@@ -206,6 +214,7 @@ fn punted_item_excluded_from_results() {
                 betweenness: 0.9,
                 urgency: Urgency::Punt,
                 decay_days: 0.0,
+                due_in_days: None,
             },
         ),
         (
@@ -216,6 +225,7 @@ fn punted_item_excluded_from_results() {
                 betweenness: 0.3,
                 urgency: Urgency::Default,
                 decay_days: 0.0,
+                due_in_days: None,
             },
         ),
     ];
@@ -376,6 +386,7 @@ fn unchanged_graph_produces_stable_scores() {
             betweenness: 0.6,
             urgency: Urgency::Default,
             decay_days: 5.0,
+            due_in_days: None,
         },
         MetricInputs {
             critical_path: 0.2,
@@ -383,6 +394,7 @@ fn unchanged_graph_produces_stable_scores() {
             betweenness: 0.1,
             urgency: Urgency::Default,
             decay_days: 0.0,
+            due_in_days: None,
         },
         MetricInputs {
             critical_path: 0.0,
@@ -390,6 +402,7 @@ fn unchanged_graph_produces_stable_scores() {
             betweenness: 0.0,
             urgency: Urgency::Urgent,
             decay_days: 0.0,
+            due_in_days: None,
         },
         MetricInputs {
             critical_path: 0.8,
@@ -397,6 +410,7 @@ fn unchanged_graph_produces_stable_scores() {
             betweenness: 0.8,
             urgency: Urgency::Punt,
             decay_days: 7.0,
+            due_in_days: None,
         },
     ];
 